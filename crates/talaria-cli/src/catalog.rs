@@ -0,0 +1,189 @@
+//! `catalog export`: the local workspace as one normalized document per
+//! product, for loading into inventory systems that know nothing about the
+//! manifest layout.
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::workspace::{self, LocalProduct};
+
+#[derive(Debug, Serialize)]
+pub struct CatalogProduct {
+    pub product_id: String,
+    pub sku: String,
+    pub name: Option<String>,
+    /// `published`, `listed`, `enriched`, `needs_enrich` or `new`.
+    pub status: &'static str,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub brand: Option<String>,
+    pub mpn: Option<String>,
+    pub gtin: Option<String>,
+    pub description: Option<String>,
+    pub images: Vec<CatalogImage>,
+    pub listings: Vec<CatalogListing>,
+    /// The structured product as generated by enrichment.
+    pub product: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CatalogImage {
+    pub path: String,
+    pub url: Option<String>,
+    pub media_id: Option<String>,
+    pub hero: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CatalogListing {
+    pub marketplace: String,
+    pub status: Option<String>,
+    pub listing_id: Option<String>,
+    pub title: Option<String>,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub quantity: Option<i64>,
+    pub quantity_sold: Option<i64>,
+    pub category_id: Option<String>,
+    pub category_label: Option<String>,
+    pub condition: Option<String>,
+    pub condition_id: Option<i64>,
+    pub images: Vec<String>,
+    pub package: Option<Value>,
+}
+
+/// Accepts RFC 3339 or a plain `YYYY-MM-DD` (local midnight).
+pub fn parse_since(text: &str) -> Result<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(text.trim()) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+        .map(|at| at.with_timezone(&Utc))
+        .ok_or_else(|| anyhow!("--since '{text}': expected YYYY-MM-DD or an RFC 3339 timestamp"))
+}
+
+/// Every local product, oldest update first; with `since`, only those
+/// updated at or after it. A missing workspace is an empty catalog.
+pub fn export(base: &Path, since: Option<DateTime<Utc>>) -> Result<Vec<CatalogProduct>> {
+    if !base.join("products").exists() {
+        return Ok(Vec::new());
+    }
+    let mut products: Vec<_> = workspace::load_products(base)?
+        .iter()
+        .map(|product| (updated_at(product), product))
+        .filter(|(updated, _)| match (since, updated) {
+            (Some(since), Some(updated)) => *updated >= since,
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .map(|(updated, product)| (updated, normalize(product)))
+        .collect();
+    products.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.product_id.cmp(&b.1.product_id))
+    });
+    Ok(products.into_iter().map(|(_, product)| product).collect())
+}
+
+fn updated_at(product: &LocalProduct) -> Option<DateTime<Utc>> {
+    product.manifest["updated_at"]
+        .as_str()
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&Utc))
+}
+
+fn text(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+fn normalize(product: &LocalProduct) -> CatalogProduct {
+    let manifest = &product.manifest;
+    let structure = &manifest["structure_json"];
+    let dir = product.path.parent().unwrap_or(Path::new("."));
+    let hero = manifest["hero_rel_path"].as_str();
+
+    let images = manifest["images"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|image| {
+            let rel = image["rel_path"].as_str()?;
+            Some(CatalogImage {
+                path: dir.join(rel).display().to_string(),
+                url: text(&image["uploaded_url"]),
+                media_id: text(&image["uploaded_media_id"]),
+                hero: hero == Some(rel),
+            })
+        })
+        .collect();
+
+    let mut listings: Vec<_> = manifest["listings"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(marketplace, listing)| CatalogListing {
+            marketplace: marketplace.clone(),
+            status: text(&listing["status"]),
+            listing_id: text(&listing["listing_id"]),
+            title: text(&listing["title"]),
+            price: listing["price"].as_f64(),
+            currency: text(&listing["currency"]),
+            quantity: listing["quantity"].as_i64(),
+            quantity_sold: listing["quantity_sold"].as_i64(),
+            category_id: text(&listing["category_id"]),
+            category_label: text(&listing["category_label"]),
+            condition: text(&listing["condition"]),
+            condition_id: listing["condition_id"].as_i64(),
+            images: listing["images"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(text)
+                .collect(),
+            package: Some(listing["package"].clone()).filter(|p| p.is_object()),
+        })
+        .collect();
+    listings.sort_by(|a, b| a.marketplace.cmp(&b.marketplace));
+
+    let has_structure = structure.is_object();
+    let status = if listings
+        .iter()
+        .any(|l| l.status.as_deref() == Some("published"))
+    {
+        "published"
+    } else if !listings.is_empty() {
+        "listed"
+    } else if has_structure {
+        "enriched"
+    } else if !manifest["images"].as_array().is_none_or(Vec::is_empty) {
+        "needs_enrich"
+    } else {
+        "new"
+    };
+
+    CatalogProduct {
+        product_id: product.product_id.clone(),
+        sku: product.sku_alias.clone(),
+        name: text(&manifest["display_name"]).or_else(|| text(&structure["name"])),
+        status,
+        created_at: text(&manifest["created_at"]),
+        updated_at: text(&manifest["updated_at"]),
+        brand: text(&structure["brand"]["name"]).or_else(|| text(&structure["brand"])),
+        mpn: text(&structure["mpn"]),
+        gtin: text(&structure["gtin"]),
+        description: text(&structure["description"]),
+        images,
+        listings,
+        product: has_structure.then(|| structure.clone()),
+    }
+}
@@ -0,0 +1,95 @@
+//! The exit-code contract scripts can rely on. Codes come from the core
+//! [`ErrorKind`] of the first `talaria_core::Error` in an error's chain, or
+//! from a [`Failure`] the CLI raised itself:
+//!
+//! | code | meaning |
+//! |------|---------|
+//! | 0 | success |
+//! | 1 | any other failure (network, Hermes server error, I/O, ...) |
+//! | 2 | auth: missing, rejected or unauthorized credentials, or a command a capture-only station may not run |
+//! | 3 | validation: bad flags, input or config |
+//! | 4 | rate limited by Hermes |
+//! | 5 | partial batch failure: some items failed, the rest were done |
+//! | 6 | a listing stage reported warnings `[warning_policy]` fails on, or any under `--fail-on-warning` |
+
+use std::fmt;
+use talaria_core::ErrorKind;
+
+pub const FAILURE: u8 = 1;
+pub const AUTH: u8 = 2;
+pub const VALIDATION: u8 = 3;
+pub const RATE_LIMITED: u8 = 4;
+pub const PARTIAL: u8 = 5;
+pub const WARNINGS: u8 = 6;
+
+/// Failures the CLI detects itself rather than getting from talaria-core.
+#[derive(Debug)]
+pub enum Failure {
+    /// Bad flag or input values.
+    Invalid(String),
+    /// Sign-in did not complete.
+    Auth(String),
+    /// `failed` of `total` items failed; the others were done and reported.
+    Partial {
+        what: &'static str,
+        failed: usize,
+        total: usize,
+    },
+    /// Listing stage warnings the warning policy or `--fail-on-warning`
+    /// fails on.
+    Warnings(Vec<String>),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Invalid(message) | Failure::Auth(message) => f.write_str(message),
+            Failure::Partial {
+                what,
+                failed,
+                total,
+            } => write!(f, "{failed} of {total} {what} failed"),
+            Failure::Warnings(warnings) => {
+                write!(f, "failing stage warnings: {}", warnings.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// An `anyhow::Error` carrying [`Failure::Invalid`], from a format string.
+macro_rules! invalid {
+    ($($arg:tt)*) => {
+        anyhow::Error::new($crate::exit::Failure::Invalid(format!($($arg)*)))
+    };
+}
+pub(crate) use invalid;
+
+pub fn code(err: &anyhow::Error) -> u8 {
+    for cause in err.chain() {
+        if let Some(failure) = cause.downcast_ref::<Failure>() {
+            return match failure {
+                Failure::Invalid(_) => VALIDATION,
+                Failure::Auth(_) => AUTH,
+                Failure::Partial { .. } => PARTIAL,
+                Failure::Warnings(_) => WARNINGS,
+            };
+        }
+        if let Some(err) = cause.downcast_ref::<talaria_core::Error>() {
+            return of_kind(err.kind());
+        }
+    }
+    FAILURE
+}
+
+/// The code a failure of `kind` exits with; also reported per item by batch
+/// commands that carry on past failures.
+pub fn of_kind(kind: ErrorKind) -> u8 {
+    match kind {
+        ErrorKind::Auth => AUTH,
+        ErrorKind::Validation => VALIDATION,
+        ErrorKind::RateLimited => RATE_LIMITED,
+        ErrorKind::Unavailable | ErrorKind::Other => FAILURE,
+    }
+}
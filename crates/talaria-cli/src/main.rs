@@ -1,21 +1,89 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use chrono::SecondsFormat;
 use clap::{Parser, Subcommand, ValueEnum};
+use futures::StreamExt;
 use prettytable::{Table, row};
 use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::ExitCode;
 use std::time::{Duration, Instant};
-use talaria_core::HermesClient;
-use talaria_core::config::Config;
+use talaria_core::artifacts::ArtifactDir;
+use talaria_core::browser;
+use talaria_core::config::{ApiKeyInfo, Config, captures_dir};
+use talaria_core::enrich::{EnrichTotals, enrich_many};
+use talaria_core::i18n;
+use talaria_core::idempotency::IdempotencyStore;
+use talaria_core::image_store::ImageStore;
 use talaria_core::images;
+use talaria_core::integrity::{self, BrokenImageUrl};
+use talaria_core::ledger::{JobLedger, JobLedgerEntry, TrackedState};
+use talaria_core::logging;
+use talaria_core::metrics;
 use talaria_core::models::*;
-use talaria_core::supabase::SupabaseClient;
+use talaria_core::product_diff::{self, FieldDiff, ProductFields};
+use talaria_core::quality::QualityRules;
+use talaria_core::report::{self, ReportFormat};
+use talaria_core::requests::ListingPolicies;
+use talaria_core::spec::{ApiSurface, Endpoint};
+use talaria_core::stats::{self, DayRange, Stats};
+use talaria_core::supabase::SupabaseObject;
+use talaria_core::templates::{RequestTemplate, TemplateStore};
+use talaria_core::upload_cache::UploadCache;
+use talaria_core::upload_journal::{self, UploadJournal};
+use talaria_core::usage_history::{self, UsageDay, UsageHistory};
+use talaria_core::vcr::Vcr;
+use talaria_core::warnings::{self, WarningAction, WarningPolicy};
+use talaria_core::watch::GroupBy;
+use talaria_core::{HermesClient, t};
+
+mod catalog;
+mod exit;
+mod output;
+mod watch;
+mod wizard;
+mod workspace;
+
+use exit::{Failure, invalid};
+use output::{Mode, OutputFormat, note};
 
 #[derive(Parser)]
 #[command(name = "talaria", version)]
 #[command(about = "CLI for the Hermes API (spec-driven)")]
 struct Cli {
+    /// Save every Hermes request/response (secrets redacted) to a cassette file.
+    #[arg(
+        long,
+        global = true,
+        value_name = "CASSETTE",
+        conflicts_with = "replay"
+    )]
+    record: Option<PathBuf>,
+    /// Answer Hermes requests from a recorded cassette instead of the network.
+    #[arg(long, global = true, value_name = "CASSETTE")]
+    replay: Option<PathBuf>,
+    /// Archive every Hermes response, with its request and request_id, as a
+    /// timestamped JSON file in this directory (secrets redacted).
+    #[arg(long, global = true, value_name = "DIR")]
+    save_dir: Option<PathBuf>,
+    /// Fake image uploads and camera captures (also TALARIA_SIMULATE=1);
+    /// Hermes enrich/listing calls still run.
+    #[arg(long, global = true)]
+    simulate: bool,
+    /// Act for this organization instead of HERMES_ORG_ID / `org_id` in config.
+    #[arg(long, global = true, value_name = "ORG_ID")]
+    org_id: Option<String>,
+    /// Print only the final JSON result; no progress or notes.
+    #[arg(long, global = true, conflicts_with = "porcelain")]
+    quiet: bool,
+    /// Print only the final result as stable `key<TAB>value` lines.
+    #[arg(long, global = true)]
+    porcelain: bool,
+    /// Exit with code 6 when a listing stage reports warnings the
+    /// `[warning_policy]` does not ignore (the result is still printed).
+    #[arg(long, global = true)]
+    fail_on_warning: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -34,8 +102,8 @@ enum Commands {
     },
     /// Health check
     Health {
-        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
-        format: OutputFormat,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
     },
     /// HSUF enrich from images
     HsufEnrich(HsufArgs),
@@ -59,51 +127,317 @@ enum Commands {
         #[command(subcommand)]
         cmd: UsageCommands,
     },
+    /// Organizations this key can act for
+    Orgs {
+        #[command(subcommand)]
+        cmd: OrgsCommands,
+    },
     /// Credits balance
     Credits {
-        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
-        format: OutputFormat,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
     },
     /// Image capture/upload helpers
     Images {
         #[command(subcommand)]
         cmd: ImagesCommands,
     },
+    /// Hermes media upload sessions
+    Media {
+        #[command(subcommand)]
+        cmd: MediaCommands,
+    },
+    /// Local/remote product helpers
+    Products {
+        #[command(subcommand)]
+        cmd: ProductsCommands,
+    },
+    /// Headless automation of the TUI flows
+    Run {
+        #[command(subcommand)]
+        cmd: RunCommands,
+    },
+    /// Export the local product catalog
+    Catalog {
+        #[command(subcommand)]
+        cmd: CatalogCommands,
+    },
+    /// Request, retry, upload and job metrics collected on this machine
+    Metrics {
+        #[command(subcommand)]
+        cmd: MetricsCommands,
+    },
+    /// Items captured and listed per day, cycle times and failure rates by
+    /// stage, from the local workspace and job ledger
+    Stats {
+        /// First day (YYYY-MM-DD); defaults to 13 days before --to
+        #[arg(long)]
+        from: Option<String>,
+        /// Last day (YYYY-MM-DD); defaults to today
+        #[arg(long)]
+        to: Option<String>,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Upload (and optionally enrich) images dropped into a folder, one
+    /// product per filename prefix or subfolder, then archive them
+    Watch(WatchArgs),
+    /// Named request templates: policy ids, marketplace, LLM options, prompt rules
+    Templates {
+        #[command(subcommand)]
+        cmd: TemplatesCommands,
+    },
+    /// Open a live listing, an uploaded image or the Hermes dashboard in the
+    /// default browser
+    Open {
+        #[command(subcommand)]
+        cmd: OpenCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum OpenCommands {
+    /// The marketplace item page of a published listing
+    Listing {
+        /// Local product id or sku alias whose recorded listing to open
+        #[arg(long, required_unless_present = "id", conflicts_with = "id")]
+        sku: Option<String>,
+        /// eBay listing id, when there is no local product for it
+        #[arg(long)]
+        id: Option<String>,
+        #[arg(long)]
+        marketplace: Option<MarketplaceOpt>,
+        /// Print the URL instead of opening it
+        #[arg(long)]
+        print: bool,
+    },
+    /// A local product's uploaded image (its Supabase or S3 object URL)
+    Image {
+        /// Local product id or sku alias
+        #[arg(long)]
+        sku: String,
+        /// Which image, 1-based, in manifest order
+        #[arg(long, default_value_t = 1)]
+        index: usize,
+        /// Print the URL instead of opening it
+        #[arg(long)]
+        print: bool,
+    },
+    /// The Hermes web dashboard (`dashboard_url` in config)
+    Dashboard {
+        /// Print the URL instead of opening it
+        #[arg(long)]
+        print: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum MediaCommands {
+    /// Abort the upload sessions interrupted uploads left open, once they
+    /// expire
+    AbortStale {
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplatesCommands {
+    /// Save the given fields as a template, replacing one of the same name
+    Save(SaveTemplateArgs),
+    /// List saved templates
+    List {
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Make a template's fields the config defaults
+    Apply {
+        /// Template name
+        name: String,
+    },
+}
+
+#[derive(Parser)]
+struct SaveTemplateArgs {
+    /// Letters, digits, `-` and `_`; used as `--template <name>`
+    name: String,
+    #[arg(long)]
+    marketplace: Option<MarketplaceOpt>,
+    #[arg(long)]
+    merchant_location_key: Option<String>,
+    #[arg(long)]
+    fulfillment_policy_id: Option<String>,
+    #[arg(long)]
+    payment_policy_id: Option<String>,
+    #[arg(long)]
+    return_policy_id: Option<String>,
+    #[arg(long, value_enum)]
+    llm_ingest_model: Option<LlmModelOpt>,
+    #[arg(long, requires = "llm_ingest_model")]
+    llm_ingest_reasoning: bool,
+    #[arg(long, requires = "llm_ingest_model")]
+    llm_ingest_web_search: bool,
+    #[arg(long, value_enum)]
+    llm_aspects_model: Option<LlmModelOpt>,
+    #[arg(long, requires = "llm_aspects_model")]
+    llm_aspects_reasoning: bool,
+    #[arg(long, requires = "llm_aspects_model")]
+    llm_aspects_web_search: bool,
+    /// Extra instructions for enrichment (`prompt_rules`)
+    #[arg(long)]
+    prompt_rules: Option<String>,
+}
+
+#[derive(clap::Args)]
+struct WatchArgs {
+    /// Folder the photo or tethering app writes into
+    #[arg(long)]
+    dir: PathBuf,
+    /// How files are grouped into skus
+    #[arg(long, value_enum, default_value_t = SkuFromOpt::Filename)]
+    sku_from: SkuFromOpt,
+    /// Where processed files are moved (default: <dir>/.talaria-processed)
+    #[arg(long)]
+    archive: Option<PathBuf>,
+    /// Run HSUF enrichment on each product after upload
+    #[arg(long)]
+    enrich: bool,
+    /// How often the folder is scanned
+    #[arg(long, default_value = "1s")]
+    interval: humantime::Duration,
+    /// How long a product's files must stay unchanged before it is processed
+    #[arg(long, default_value = "3s")]
+    settle: humantime::Duration,
+    /// Process the files already in the folder, then exit
+    #[arg(long)]
+    once: bool,
+    /// Print products most expensive first (credits, then tokens) once all are done
+    #[arg(long, requires_all = ["once", "enrich"])]
+    sort_by_cost: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SkuFromOpt {
+    /// `sku-123_01.jpg` and `sku-123_02.jpg` are one product, `sku-123`
+    Filename,
+    /// `sku-123/front.jpg` and `sku-123/back.jpg` are one product, `sku-123`
+    Subfolder,
+}
+
+/// File selection for `--images-from-dir`.
+#[derive(clap::Args, Clone)]
+struct DirFilterArgs {
+    /// Glob on file name or relative path, repeatable (default: jpg/jpeg/png/webp)
+    #[arg(long, requires = "images_from_dir")]
+    include: Vec<String>,
+    /// Glob of files to leave out, repeatable
+    #[arg(long, requires = "images_from_dir")]
+    exclude: Vec<String>,
+    /// Descend into sub-directories
+    #[arg(long, requires = "images_from_dir")]
+    recursive: bool,
+    /// Skip files larger than this many MiB (0 = no limit)
+    #[arg(long, default_value_t = images::DEFAULT_MAX_UPLOAD_BYTES / (1024 * 1024))]
+    max_file_mb: u64,
+}
+
+impl DirFilterArgs {
+    fn to_filter(&self) -> images::DirFilter {
+        images::DirFilter {
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+            recursive: self.recursive,
+            max_bytes: (self.max_file_mb > 0).then_some(self.max_file_mb * 1024 * 1024),
+        }
+    }
+}
+
+/// `--timeout` for commands that wait on long LLM runs.
+#[derive(clap::Args, Clone)]
+struct TimeoutArgs {
+    /// How long each Hermes call may take, e.g. `15m`; overrides `[timeouts]`
+    #[arg(long, value_name = "DURATION")]
+    timeout: Option<humantime::Duration>,
+}
+
+impl TimeoutArgs {
+    fn apply(&self, client: HermesClient) -> HermesClient {
+        match self.timeout {
+            Some(timeout) => client.with_timeout(timeout.into()),
+            None => client,
+        }
+    }
 }
 
 #[derive(Parser)]
 #[command(group(
     clap::ArgGroup::new("hsuf_images_source")
         .required(true)
-        .args(&["images", "images_from_dir", "capture"])
+        .args(&["images", "images_from_dir", "capture", "manifest"])
 ))]
 struct HsufArgs {
+    /// Image URLs; `-` reads newline-separated URLs from stdin
     #[arg(long, value_delimiter = ' ', conflicts_with_all = ["images_from_dir", "capture"])]
     images: Vec<String>,
     #[arg(long, conflicts_with_all = ["images", "capture"])]
     images_from_dir: Option<PathBuf>,
+    #[command(flatten)]
+    dir_filter: DirFilterArgs,
     #[arg(long, conflicts_with_all = ["images", "images_from_dir"])]
     capture: Option<usize>,
     #[arg(long, requires = "capture")]
     device: Option<u32>,
+    /// JSON array of enrich requests (`images`, optional `sku` and
+    /// `context_text`), enriched in parallel; results are printed as NDJSON
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["images", "images_from_dir", "capture", "sku", "context_text", "context_file", "save_to_product"]
+    )]
+    manifest: Option<PathBuf>,
+    /// Enrich calls in flight at once with --manifest
+    #[arg(long, default_value_t = 4, requires = "manifest", value_parser = clap::value_parser!(u16).range(1..=32))]
+    concurrency: u16,
     #[arg(long)]
     sku: Option<String>,
+    /// Free-text context for the model (brand, defects, measurements, ...)
+    #[arg(long, conflicts_with = "context_file")]
+    context_text: Option<String>,
+    /// Read the context text from a file
+    #[arg(long, value_name = "PATH")]
+    context_file: Option<PathBuf>,
     #[arg(long)]
     include_usage: bool,
+    /// Named template for `llm_ingest` and prompt rules; flags win over it
+    #[arg(long)]
+    template: Option<String>,
     #[arg(long, value_enum)]
     llm_ingest_model: Option<LlmModelOpt>,
     #[arg(long)]
     llm_ingest_reasoning: bool,
     #[arg(long)]
     llm_ingest_web_search: bool,
-    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
-    format: OutputFormat,
+    /// Write the result into the local product manifest (product id or sku alias)
+    #[arg(long, value_name = "PRODUCT_ID|SKU")]
+    save_to_product: Option<String>,
+    /// Also push the structure to Hermes via update_product (needs --save-to-product)
+    #[arg(long, requires = "save_to_product")]
+    push: bool,
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+    #[command(flatten)]
+    timeout: TimeoutArgs,
 }
 
 #[derive(Subcommand)]
 enum ConfigCommands {
     /// Show effective config
     Doctor,
+    /// Compare Hermes' OpenAPI document with the API this build knows
+    CheckApi {
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -114,6 +448,65 @@ enum AuthCommands {
         #[arg(long)]
         no_browser: bool,
     },
+    /// Replace the stored API key with a freshly created one
+    RotateKey {
+        /// Revoke the previous key once the new one is saved and works
+        #[arg(long)]
+        revoke_old: bool,
+        #[command(flatten)]
+        token: AccessTokenArgs,
+    },
+    /// Show which API key is configured and whether Hermes still accepts it
+    Status {
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// List or revoke the API keys on your account
+    Keys {
+        #[command(subcommand)]
+        cmd: AuthKeysCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthKeysCommands {
+    /// List API keys (the one this machine uses is marked)
+    List {
+        #[command(flatten)]
+        token: AccessTokenArgs,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Revoke an API key, e.g. one held by a lost device
+    Revoke {
+        /// Key id (from `auth keys list`)
+        id: String,
+        /// Also allow revoking the key this machine uses
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        token: AccessTokenArgs,
+    },
+}
+
+/// Key management needs a user access token rather than an API key.
+#[derive(clap::Args, Clone)]
+struct AccessTokenArgs {
+    /// Device-auth access token (skips the device login flow)
+    #[arg(long)]
+    access_token: Option<String>,
+    /// Do not attempt to open a browser
+    #[arg(long)]
+    no_browser: bool,
+}
+
+impl AccessTokenArgs {
+    async fn resolve(self, client: &HermesClient) -> Result<String> {
+        match self.access_token {
+            Some(token) => Ok(token),
+            None => device_access_token(client, self.no_browser).await,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -122,6 +515,62 @@ enum ListingsCommands {
     Create(CreateListingArgs),
     /// Continue a listing with overrides
     Continue(ContinueListingArgs),
+    /// Send a local product's listing as a draft (or publish it)
+    Draft(DraftListingArgs),
+    /// Record units of a published listing as sold (status becomes sold at 0 left)
+    MarkSold {
+        /// Product id or sku alias
+        #[arg(long)]
+        sku: String,
+        #[arg(long)]
+        marketplace: Option<MarketplaceOpt>,
+        /// Units sold
+        #[arg(long, default_value_t = 1)]
+        quantity: u32,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Show the allowed conditions recorded for a local product's listing
+    Conditions {
+        /// Local product id or sku alias
+        #[arg(long)]
+        product: String,
+        #[arg(long)]
+        marketplace: Option<MarketplaceOpt>,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Search categories used by local listings and build a category override
+    Categories {
+        /// Words to match against category labels and ids
+        #[arg(long)]
+        query: Option<String>,
+        #[arg(long)]
+        marketplace: Option<MarketplaceOpt>,
+        /// Print the CategorySelectionInput JSON for the Nth match (1-based)
+        #[arg(long)]
+        pick: Option<usize>,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Render a listing response (or a --save-dir artifact of one) as a
+    /// shareable Markdown or HTML report
+    Report {
+        /// Listing response JSON; `-` reads stdin
+        #[arg(long, value_name = "PATH")]
+        input: PathBuf,
+        #[arg(long, value_enum, default_value_t = ReportFormatOpt::Md)]
+        format: ReportFormatOpt,
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormatOpt {
+    Md,
+    Html,
 }
 
 #[derive(Parser)]
@@ -135,28 +584,50 @@ struct CreateListingArgs {
     images: Vec<String>,
     #[arg(long, conflicts_with_all = ["images", "capture"])]
     images_from_dir: Option<PathBuf>,
+    #[command(flatten)]
+    dir_filter: DirFilterArgs,
     #[arg(long, conflicts_with_all = ["images", "images_from_dir"])]
     capture: Option<usize>,
     #[arg(long, requires = "capture")]
     device: Option<u32>,
-    #[arg(long, required = true)]
-    merchant_location_key: String,
-    #[arg(long, required = true)]
-    fulfillment_policy_id: String,
-    #[arg(long, required = true)]
-    payment_policy_id: String,
-    #[arg(long, required = true)]
-    return_policy_id: String,
+    /// Named template (see `templates`) filling the fields not given as flags
+    #[arg(long)]
+    template: Option<String>,
+    /// Policy ids and marketplace default to --template, then the ebay_* config keys
+    #[arg(long)]
+    merchant_location_key: Option<String>,
+    #[arg(long)]
+    fulfillment_policy_id: Option<String>,
+    #[arg(long)]
+    payment_policy_id: Option<String>,
+    #[arg(long)]
+    return_policy_id: Option<String>,
     #[arg(long)]
     marketplace: Option<MarketplaceOpt>,
     #[arg(long)]
     publish: bool,
     #[arg(long)]
     dry_run: bool,
+    /// Ask for signed image URLs (always on when `supabase_use_signed_urls` is configured)
     #[arg(long)]
     use_signed_urls: bool,
     #[arg(long)]
     sku: Option<String>,
+    /// Explicit Idempotency-Key; by default one is derived per request and reused on retries
+    #[arg(long)]
+    idempotency_key: Option<String>,
+    /// Queue the listing as a background job and print the job id
+    #[arg(long)]
+    enqueue: bool,
+    /// Refuse local photos that fail the `[quality]` checks instead of warning
+    #[arg(long)]
+    strict_quality: bool,
+    /// Submit without first checking that every image URL is reachable
+    #[arg(long)]
+    skip_verify: bool,
+    /// Open the published listing in the default browser
+    #[arg(long, conflicts_with = "enqueue")]
+    open: bool,
     #[arg(long, value_enum)]
     llm_ingest_model: Option<LlmModelOpt>,
     #[arg(long)]
@@ -169,8 +640,10 @@ struct CreateListingArgs {
     llm_aspects_reasoning: bool,
     #[arg(long)]
     llm_aspects_web_search: bool,
-    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
-    format: OutputFormat,
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+    #[command(flatten)]
+    timeout: TimeoutArgs,
 }
 
 #[derive(Parser)]
@@ -189,8 +662,37 @@ struct ContinueListingArgs {
     marketplace: Option<MarketplaceOpt>,
     #[arg(long, help = "JSON for CategorySelectionInput")]
     override_category: Option<String>,
+    /// Category id override (label via --category-label); see `listings categories`
+    #[arg(long, conflicts_with = "override_category")]
+    category_id: Option<String>,
+    #[arg(long, requires = "category_id")]
+    category_label: Option<String>,
+    /// Condition override, validated against the allowed list of the local listing
+    #[arg(long)]
+    condition: Option<String>,
+    #[arg(long)]
+    condition_id: Option<i32>,
     #[arg(long, num_args = 1.., value_delimiter = ' ')]
     override_resolved_images: Vec<String>,
+    /// Show what the prior run detected and prompt for each override
+    /// (from --from-job, --response, or the local manifest of --sku)
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "override_category",
+            "category_id",
+            "condition",
+            "condition_id",
+            "override_resolved_images",
+        ]
+    )]
+    interactive: bool,
+    /// Listing job whose result the wizard starts from
+    #[arg(long, requires = "interactive")]
+    from_job: Option<String>,
+    /// Saved listing response the wizard starts from (`listings create` output or a --save-dir artifact)
+    #[arg(long, requires = "interactive", conflicts_with = "from_job")]
+    response: Option<PathBuf>,
     #[arg(long, num_args = 0..)]
     images: Vec<String>,
     #[arg(long, value_enum)]
@@ -205,8 +707,43 @@ struct ContinueListingArgs {
     llm_aspects_reasoning: bool,
     #[arg(long)]
     llm_aspects_web_search: bool,
-    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
-    format: OutputFormat,
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+    #[command(flatten)]
+    timeout: TimeoutArgs,
+}
+
+#[derive(Parser)]
+struct DraftListingArgs {
+    /// Local product id or sku alias
+    #[arg(long)]
+    product: String,
+    #[arg(long)]
+    marketplace: Option<MarketplaceOpt>,
+    /// Named preset from `[[package_templates]]` in the config file
+    #[arg(long)]
+    package_template: Option<String>,
+    /// Package weight with unit, e.g. 12oz, 1.5lb, 500g (overrides the template)
+    #[arg(long)]
+    weight: Option<String>,
+    /// Package length x width x height with unit, e.g. 10x8x4in (overrides the template)
+    #[arg(long)]
+    dims: Option<String>,
+    /// Defaults to the listing's value, then the ebay_* keys from config
+    #[arg(long)]
+    merchant_location_key: Option<String>,
+    #[arg(long)]
+    fulfillment_policy_id: Option<String>,
+    #[arg(long)]
+    payment_policy_id: Option<String>,
+    #[arg(long)]
+    return_policy_id: Option<String>,
+    #[arg(long)]
+    publish: bool,
+    #[arg(long)]
+    dry_run: bool,
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
 }
 
 #[derive(Subcommand)]
@@ -215,8 +752,48 @@ enum JobsCommands {
     Get {
         #[arg(long)]
         id: String,
-        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
-        format: OutputFormat,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// List recently enqueued jobs from the local ledger with their current state
+    List {
+        /// Only show jobs in this state
+        #[arg(long, value_enum)]
+        state: Option<JobStateOpt>,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Poll a job until it completes or fails, refreshing a status table
+    #[command(alias = "wait")]
+    Watch {
+        #[arg(long)]
+        id: String,
+        /// Poll interval (e.g. 2s, 500ms)
+        #[arg(long, default_value = "2s")]
+        interval: humantime::Duration,
+        /// Give up after this long (e.g. 10m)
+        #[arg(long)]
+        timeout: Option<humantime::Duration>,
+        /// POST the final job summary here (defaults to job_notify_url from config)
+        #[arg(long)]
+        notify_url: Option<String>,
+        /// Send a Slack-compatible payload instead of the raw summary
+        #[arg(long)]
+        notify_slack: bool,
+    },
+    /// Enqueue a finished job's request again, optionally changed with --set
+    Retry {
+        #[arg(long)]
+        id: String,
+        /// Change a request field before enqueuing, repeatable: a dotted path
+        /// and a JSON value or plain text, e.g. `llm_aspects.model=gpt-5.2`,
+        /// `publish=true`, `images_source=["https://.../a.jpg"]`, `overrides=null`
+        #[arg(long = "set", value_name = "PATH=VALUE")]
+        sets: Vec<String>,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
     },
 }
 
@@ -231,16 +808,55 @@ enum UsageCommands {
     /// List usage
     List {
         #[arg(long)]
-        org_id: Option<String>,
+        from: Option<String>,
+        #[arg(long)]
+        to: Option<String>,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Credits, jobs and listings consumed per day, from locally kept usage
+    /// snapshots
+    History {
+        /// First day (YYYY-MM-DD); defaults to 30 days before --to
         #[arg(long)]
         from: Option<String>,
+        /// Last day (YYYY-MM-DD); defaults to today
         #[arg(long)]
         to: Option<String>,
-        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
-        format: OutputFormat,
+        /// Only this organization
+        #[arg(long = "org")]
+        org: Option<String>,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+enum OrgsCommands {
+    /// List the organizations visible to the API key (* = current)
+    List {
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MetricsCommands {
+    /// Print the running totals from every talaria run so far
+    Dump {
+        #[arg(long, value_enum, default_value_t = MetricsFormat::Table)]
+        format: MetricsFormat,
     },
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MetricsFormat {
+    Json,
+    Table,
+    /// Prometheus text exposition format
+    Prometheus,
+}
+
 #[derive(Subcommand)]
 enum ImagesCommands {
     /// Capture images from a webcam
@@ -253,22 +869,167 @@ enum ImagesCommands {
         out_dir: Option<PathBuf>,
         #[arg(long)]
         upload: bool,
-        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
-        format: OutputFormat,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
     },
     /// Upload local image files to Supabase
     Upload {
         #[arg(long, num_args = 1.., value_delimiter = ' ', required = true)]
         paths: Vec<PathBuf>,
-        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
-        format: OutputFormat,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Delete uploads that no local product or Hermes media record references
+    Prune {
+        /// Only remove objects older than this (e.g. 30d, 12h)
+        #[arg(long, default_value = "30d")]
+        older_than: humantime::Duration,
+        /// Bucket folder to scan; defaults to the configured upload prefix
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Report what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProductsCommands {
+    /// Show or edit a product's notes (context text sent with enrich requests)
+    Context {
+        #[command(subcommand)]
+        cmd: ProductContextCommands,
+    },
+    /// Compare a local product manifest with its Hermes record, field by field
+    Diff {
+        /// Product id or sku alias
+        #[arg(long)]
+        id: String,
+        /// `table` (the default) prints a colored diff
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProductContextCommands {
+    /// Print a product's context text (local manifest first, then Hermes)
+    Show {
+        /// Product id or sku alias
+        #[arg(long)]
+        id: String,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Replace the context text on Hermes and in the local manifest
+    #[command(group(
+        clap::ArgGroup::new("context_source")
+            .required(true)
+            .args(&["text", "from_file"])
+    ))]
+    Set {
+        /// Product id or sku alias
+        #[arg(long)]
+        id: String,
+        /// New text; an empty string clears it
+        #[arg(long)]
+        text: Option<String>,
+        /// Read the text from a file (`-` for stdin)
+        #[arg(long)]
+        from_file: Option<PathBuf>,
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CatalogCommands {
+    /// Print every local product (images, listings, statuses) as normalized JSON
+    Export {
+        #[arg(long, value_enum, default_value_t = CatalogFormat::Ndjson)]
+        format: CatalogFormat,
+        /// Only products updated at or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
     },
 }
 
 #[derive(Clone, Copy, ValueEnum)]
-enum OutputFormat {
+enum CatalogFormat {
+    /// One product per line
+    Ndjson,
+    /// A single document with export metadata
     Json,
-    Table,
+}
+
+#[derive(Subcommand)]
+enum RunCommands {
+    /// Upload, enrich and list a batch of images, printing one JSON progress line per step
+    Pipeline(PipelineArgs),
+}
+
+#[derive(Parser)]
+#[command(group(
+    clap::ArgGroup::new("pipeline_images_source")
+        .required(true)
+        .args(&["images_from_dir", "capture"])
+))]
+struct PipelineArgs {
+    /// Directory of images to process (taken in file name order)
+    #[arg(long, conflicts_with = "capture")]
+    images_from_dir: Option<PathBuf>,
+    #[command(flatten)]
+    dir_filter: DirFilterArgs,
+    /// Capture this many frames from the webcam instead of reading a directory
+    #[arg(long, conflicts_with = "images_from_dir")]
+    capture: Option<usize>,
+    #[arg(long, requires = "capture")]
+    device: Option<u32>,
+    #[arg(long)]
+    sku: Option<String>,
+    #[arg(long)]
+    marketplace: Option<MarketplaceOpt>,
+    /// Defaults to ebay_merchant_location_key from config
+    #[arg(long)]
+    merchant_location_key: Option<String>,
+    /// Defaults to ebay_fulfillment_policy_id from config
+    #[arg(long)]
+    fulfillment_policy_id: Option<String>,
+    /// Defaults to ebay_payment_policy_id from config
+    #[arg(long)]
+    payment_policy_id: Option<String>,
+    /// Defaults to ebay_return_policy_id from config
+    #[arg(long)]
+    return_policy_id: Option<String>,
+    /// Stop after enrichment without creating a listing
+    #[arg(long, conflicts_with_all = ["publish", "dry_run"])]
+    skip_listing: bool,
+    #[arg(long)]
+    publish: bool,
+    #[arg(long)]
+    dry_run: bool,
+    /// Write the enriched structure into the local product manifest (product id or sku alias)
+    #[arg(long, value_name = "PRODUCT_ID|SKU")]
+    save_to_product: Option<String>,
+    /// Enrich and list without first checking that the uploaded URLs are reachable
+    #[arg(long)]
+    skip_verify: bool,
+    #[command(flatten)]
+    timeout: TimeoutArgs,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum JobStateOpt {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Unknown,
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -299,6 +1060,28 @@ enum MarketplaceOpt {
     Uk,
     #[value(name = "EBAY_DE")]
     De,
+    #[value(name = "EBAY_AU")]
+    Au,
+    #[value(name = "EBAY_CA")]
+    Ca,
+    #[value(name = "EBAY_FR")]
+    Fr,
+    #[value(name = "EBAY_IT")]
+    It,
+    #[value(name = "EBAY_ES")]
+    Es,
+    #[value(name = "EBAY_AT")]
+    At,
+    #[value(name = "EBAY_BE")]
+    Be,
+    #[value(name = "EBAY_CH")]
+    Ch,
+    #[value(name = "EBAY_IE")]
+    Ie,
+    #[value(name = "EBAY_NL")]
+    Nl,
+    #[value(name = "EBAY_PL")]
+    Pl,
 }
 
 impl MarketplaceOpt {
@@ -307,35 +1090,192 @@ impl MarketplaceOpt {
             MarketplaceOpt::Us => MarketplaceId::EbayUs,
             MarketplaceOpt::Uk => MarketplaceId::EbayUk,
             MarketplaceOpt::De => MarketplaceId::EbayDe,
+            MarketplaceOpt::Au => MarketplaceId::EbayAu,
+            MarketplaceOpt::Ca => MarketplaceId::EbayCa,
+            MarketplaceOpt::Fr => MarketplaceId::EbayFr,
+            MarketplaceOpt::It => MarketplaceId::EbayIt,
+            MarketplaceOpt::Es => MarketplaceId::EbayEs,
+            MarketplaceOpt::At => MarketplaceId::EbayAt,
+            MarketplaceOpt::Be => MarketplaceId::EbayBe,
+            MarketplaceOpt::Ch => MarketplaceId::EbayCh,
+            MarketplaceOpt::Ie => MarketplaceId::EbayIe,
+            MarketplaceOpt::Nl => MarketplaceId::EbayNl,
+            MarketplaceOpt::Pl => MarketplaceId::EbayPl,
         }
     }
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let mut config = Config::load()?;
-    let client = HermesClient::new(config.clone())?;
-    let supabase = images::supabase_from_config(&config)?;
-
-    match cli.command {
+async fn main() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        // clap would exit 2 on usage errors, which means auth here.
+        Err(err) => {
+            let _ = err.print();
+            return if err.use_stderr() {
+                ExitCode::from(exit::VALIDATION)
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    };
+    let result = run(cli).await;
+    // Failed commands count too.
+    if let Some(path) = metrics::default_totals_path() {
+        let _ = metrics::flush_totals(metrics::global(), &path);
+    }
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let code = exit::code(&err);
+            tracing::error!(error = %format!("{err:#}"), code, "command failed");
+            eprintln!("{}", t!("cli.error", error = format!("{err:?}")));
+            ExitCode::from(code)
+        }
+    }
+}
+
+/// What `command` does that a capture-only station may not: publishing,
+/// deleting or spending credits.
+fn restricted_action(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::HsufEnrich(_) => Some("HSUF enrichment"),
+        Commands::Listings { cmd } => match cmd {
+            ListingsCommands::Create(_) => Some("creating listings"),
+            ListingsCommands::Continue(_) => Some("continuing listings"),
+            ListingsCommands::Draft(args) if args.publish => Some("publishing listings"),
+            _ => None,
+        },
+        Commands::Jobs {
+            cmd: JobsCommands::Retry { .. },
+        } => Some("retrying listing jobs"),
+        Commands::Run {
+            cmd: RunCommands::Pipeline(_),
+        } => Some("the listing pipeline"),
+        Commands::Images {
+            cmd: ImagesCommands::Prune { dry_run: false, .. },
+        } => Some("deleting uploads"),
+        Commands::Watch(args) if args.enrich => Some("enriching watched products"),
+        _ => None,
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let mut config = Config::load()?;
+    config.simulate |= cli.simulate;
+    i18n::init(config.locale.as_deref());
+    output::init(
+        if cli.porcelain {
+            Mode::Porcelain
+        } else if cli.quiet {
+            Mode::Quiet
+        } else {
+            Mode::Human
+        },
+        config.default_output_format,
+    );
+    // Support logs only; never worth failing the command over.
+    if let Err(err) = logging::init(
+        "talaria-cli",
+        &logging::logs_dir(&config.captures_dir()),
+        config.log_filter.as_deref(),
+    ) {
+        eprintln!("warning: file logging disabled: {err}");
+    }
+    if let Some(action) = restricted_action(&cli.command) {
+        config.mode.permit(action)?;
+    }
+    let fail_on_warning = cli.fail_on_warning;
+    let mut client = HermesClient::new(config.clone())?;
+    if let Some(path) = &cli.record {
+        client = client.with_vcr(Vcr::record(path));
+    } else if let Some(path) = &cli.replay {
+        client = client.with_vcr(Vcr::replay(path)?);
+    }
+    if let Some(dir) = &cli.save_dir {
+        client = client.with_artifacts(
+            ArtifactDir::create(dir).with_context(|| format!("--save-dir {}", dir.display()))?,
+        );
+    }
+    if let Some(org_id) = cli.org_id {
+        client = client.with_org(Some(org_id));
+    }
+    let store = ImageStore::from_config(&config)?;
+
+    match cli.command {
         Commands::Auth { cmd } => match cmd {
             AuthCommands::Login { no_browser } => {
                 auth_login(&client, &mut config, no_browser).await?;
             }
+            AuthCommands::RotateKey { revoke_old, token } => {
+                rotate_api_key(&client, &mut config, token, revoke_old).await?;
+            }
+            AuthCommands::Keys { cmd } => match cmd {
+                AuthKeysCommands::List { token, format } => {
+                    let access_token = token.resolve(&client).await?;
+                    let current = config.api_key_info.as_ref().map(|info| info.id.as_str());
+                    let keys: Vec<_> = client
+                        .list_user_api_keys(&access_token)
+                        .await?
+                        .into_iter()
+                        .map(|key| ApiKeyRow {
+                            current: current == Some(key.id.as_str()),
+                            key,
+                        })
+                        .collect();
+                    emit_json_or_table(
+                        output::format(format, OutputFormat::Table),
+                        &keys,
+                        |keys| api_keys_table(keys),
+                    );
+                }
+                AuthKeysCommands::Revoke { id, force, token } => {
+                    let current = config.api_key_info.as_ref();
+                    if !force && current.is_some_and(|info| info.id == id) {
+                        return Err(invalid!(
+                            "Key {id} is the one this machine uses; revoking it signs this \
+                             machine out. Use `auth rotate-key --revoke-old`, or pass --force"
+                        ));
+                    }
+                    let access_token = token.resolve(&client).await?;
+                    client.delete_user_api_key(&access_token, &id).await?;
+                    output::done(
+                        &t!("cli.key_revoked", id = id),
+                        &serde_json::json!({ "revoked": id }),
+                    );
+                }
+            },
+            AuthCommands::Status { format } => {
+                let status = auth_status(&client, &config).await;
+                emit_json_or_table(output::format(format, OutputFormat::Table), &status, |s| {
+                    let mut table = Table::new();
+                    table.add_row(row!["source", s.source]);
+                    table.add_row(row!["key", s.key.clone().unwrap_or_default()]);
+                    table.add_row(row!["prefix", s.prefix.clone().unwrap_or_default()]);
+                    table.add_row(row!["name", s.name.clone().unwrap_or_default()]);
+                    table.add_row(row!["id", s.id.clone().unwrap_or_default()]);
+                    table.add_row(row![
+                        "authenticates",
+                        s.authenticates.map(|ok| ok.to_string()).unwrap_or_default()
+                    ]);
+                    if let Some(error) = &s.error {
+                        table.add_row(row!["error", error]);
+                    }
+                    table
+                });
+            }
         },
         Commands::Config { cmd } => match cmd {
             ConfigCommands::Doctor => {
-                let report = config.doctor();
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&report).expect("serializable doctor report")
-                );
+                output::emit_json(&config.doctor());
+            }
+            ConfigCommands::CheckApi { format } => {
+                check_api(&client, output::format(format, OutputFormat::Json)).await?;
             }
         },
         Commands::Health { format } => {
             let resp = client.health().await?;
-            emit_json_or_table(format, &resp, |_| {
+            emit_json_or_table(output::format(format, OutputFormat::Json), &resp, |_| {
                 let mut table = Table::new();
                 table.add_row(row!["service", resp.service]);
                 table.add_row(row!["status", resp.status]);
@@ -349,79 +1289,341 @@ async fn main() -> Result<()> {
             });
         }
         Commands::HsufEnrich(args) => {
-            let images = resolve_images_hsuf(&args, supabase.as_ref()).await?;
+            let client = args.timeout.apply(client);
+            let template = load_template(args.template.as_deref())?;
             let llm_ingest = merge_llm_stage_options(
                 "llm-ingest",
                 args.llm_ingest_model,
                 args.llm_ingest_reasoning,
                 args.llm_ingest_web_search,
-                config.llm_ingest.clone(),
+                template.llm_ingest.or(config.llm_ingest.clone()),
             )?;
+            let prompt_rules = template.prompt_rules.or(config.prompt_rules.clone());
+            if let Some(manifest) = &args.manifest {
+                return enrich_manifest(
+                    &client,
+                    manifest,
+                    args.concurrency.into(),
+                    llm_ingest,
+                    prompt_rules,
+                    config.operator_name(),
+                )
+                .await;
+            }
+            let images = resolve_images_hsuf(&args, store.as_ref()).await?;
+            let context_text = match &args.context_file {
+                Some(path) => Some(
+                    std::fs::read_to_string(path)
+                        .map_err(|err| invalid!("--context-file {}: {err}", path.display()))?,
+                ),
+                None => args.context_text.clone(),
+            }
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
             let body = HsufEnrichRequest {
                 images,
                 sku: args.sku,
-                context_text: None,
-                prompt_rules: config.prompt_rules.clone(),
+                context_text,
+                prompt_rules,
                 llm_ingest,
             };
             let resp = client.hsuf_enrich(&body, args.include_usage).await?;
-            emit_json_or_table(args.format, &resp, |r| {
-                let mut table = Table::new();
-                table.add_row(row!["name", r.product.name]);
-                if let Some(color) = &r.product.color {
-                    table.add_row(row!["color", color]);
-                }
-                if let Some(sku) = &r.product.sku {
-                    table.add_row(row!["sku", sku]);
-                }
-                if let Some(usage) = &r.usage {
-                    table.add_row(row![
-                        "usage",
-                        format!(
-                            "input_tokens={} output_tokens={}",
-                            usage.input_tokens.unwrap_or_default(),
-                            usage.output_tokens.unwrap_or_default()
-                        )
-                    ]);
-                }
-                table
-            });
+            if let Some(target) = &args.save_to_product {
+                save_enrich_to_product(&client, target, &resp.product, args.push).await?;
+            }
+            emit_json_or_table(
+                output::format(args.format, OutputFormat::Json),
+                &resp,
+                |r| {
+                    let mut table = Table::new();
+                    table.add_row(row!["name", r.product.name]);
+                    if let Some(color) = &r.product.color {
+                        table.add_row(row!["color", color]);
+                    }
+                    if let Some(sku) = &r.product.sku {
+                        table.add_row(row!["sku", sku]);
+                    }
+                    if let Some(usage) = &r.usage {
+                        table.add_row(row![
+                            "usage",
+                            format!(
+                                "input_tokens={} output_tokens={}",
+                                usage.input_tokens.unwrap_or_default(),
+                                usage.output_tokens.unwrap_or_default()
+                            )
+                        ]);
+                    }
+                    table
+                },
+            );
         }
         Commands::Listings { cmd } => match cmd {
             ListingsCommands::Create(args) => {
-                let resolved_images = resolve_images_listing(&args, supabase.as_ref()).await?;
+                let client = args.timeout.apply(client);
+                let resolved_images =
+                    resolve_images_listing(&args, &client, store.as_ref(), &config.quality).await?;
                 let req = build_public_listing(&args, resolved_images, &config)?;
-                let resp = client.create_listing(&req).await?;
-                emit_listing(args.format, &resp);
+                if args.enqueue {
+                    let job =
+                        enqueue_listing_idempotent(&client, &req, args.idempotency_key.as_deref())
+                            .await?;
+                    emit_json_or_table(
+                        output::format(args.format, OutputFormat::Json),
+                        &job,
+                        |j| {
+                            let mut table = Table::new();
+                            table.add_row(row!["job_id", j.job_id]);
+                            table
+                        },
+                    );
+                } else {
+                    let resp =
+                        create_listing_idempotent(&client, &req, args.idempotency_key.as_deref())
+                            .await?;
+                    emit_listing(output::format(args.format, OutputFormat::Json), &resp);
+                    if args.open {
+                        let marketplace = req.marketplace.clone().unwrap_or_else(|| {
+                            MarketplaceId::from(marketplace_key_or_default(None, &config).as_str())
+                        });
+                        match browser::listing_url(&marketplace, &resp.listing_id) {
+                            Some(url) => try_open_browser(&url),
+                            None => eprintln!(
+                                "warning: no item page known for listing {} on {marketplace}",
+                                resp.listing_id
+                            ),
+                        }
+                    }
+                    check_stage_warnings(fail_on_warning, &config.warning_policy, &resp)?;
+                }
             }
             ListingsCommands::Continue(args) => {
-                let req = build_continue_request(&args, &config)?;
+                let client = args.timeout.apply(client);
+                let overrides = if args.interactive {
+                    let detected = prior_listing_run(&client, &args, &config).await?;
+                    let marketplace = args
+                        .marketplace
+                        .map_or(MarketplaceId::EbayUs, |m| m.into_model());
+                    match wizard::run(
+                        &detected,
+                        &marketplace,
+                        std::io::stdin().lock(),
+                        std::io::stderr(),
+                    )? {
+                        wizard::Outcome::Submit(overrides) => overrides,
+                        wizard::Outcome::Declined => {
+                            eprintln!("not submitted");
+                            return Ok(());
+                        }
+                    }
+                } else {
+                    flag_overrides(&args, &config)?
+                };
+                let req = build_continue_request(&args, &config, overrides)?;
                 let resp = client.continue_listing(&req).await?;
-                emit_listing(args.format, &resp);
+                emit_listing(output::format(args.format, OutputFormat::Json), &resp);
+                check_stage_warnings(fail_on_warning, &config.warning_policy, &resp)?;
+            }
+            ListingsCommands::Draft(args) => {
+                let req = build_draft_request(&args, &config)?;
+                let resp = client.publish_listing_draft(&req).await?;
+                emit_listing(output::format(args.format, OutputFormat::Json), &resp);
+                check_stage_warnings(fail_on_warning, &config.warning_policy, &resp)?;
+            }
+            ListingsCommands::MarkSold {
+                sku,
+                marketplace,
+                quantity,
+                format,
+            } => {
+                let key = marketplace_key_or_default(marketplace, &config);
+                let sold = mark_sold(&client, &sku, &key, quantity.into()).await?;
+                emit_json_or_table(output::format(format, OutputFormat::Json), &sold, |s| {
+                    let mut table = Table::new();
+                    table.add_row(row!["sku", s.sku_alias]);
+                    table.add_row(row!["marketplace", s.marketplace]);
+                    table.add_row(row!["status", s.status]);
+                    table.add_row(row!["quantity", s.quantity]);
+                    table.add_row(row!["quantity_sold", s.quantity_sold]);
+                    table.add_row(row!["sources", s.sources.join(", ")]);
+                    table
+                });
+            }
+            ListingsCommands::Conditions {
+                product,
+                marketplace,
+                format,
+            } => {
+                let key = marketplace_key_or_default(marketplace, &config);
+                let local = workspace::find_product(&captures_dir(), &product)?;
+                let conditions = local.conditions(&key);
+                if conditions.allowed.is_empty() {
+                    return Err(anyhow!(
+                        "no allowed conditions recorded for {} on {key}; run a draft listing first",
+                        local.sku_alias
+                    ));
+                }
+                let rows = conditions
+                    .allowed
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, label)| {
+                        serde_json::json!({
+                            "condition": label,
+                            "condition_id": conditions.allowed_ids.get(idx),
+                            "selected": conditions.current.as_deref() == Some(label.as_str())
+                                || (conditions.current_id.is_some()
+                                    && conditions.current_id
+                                        == conditions.allowed_ids.get(idx).copied()),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                emit_json_or_table(output::format(format, OutputFormat::Table), &rows, |rows| {
+                    let mut table = Table::new();
+                    table.add_row(row!["", "condition", "condition_id"]);
+                    for row in rows {
+                        let marker = if row["selected"].as_bool() == Some(true) {
+                            "*"
+                        } else {
+                            ""
+                        };
+                        table.add_row(row![
+                            marker,
+                            row["condition"].as_str().unwrap_or_default(),
+                            row["condition_id"]
+                                .as_i64()
+                                .map(|id| id.to_string())
+                                .unwrap_or_default()
+                        ]);
+                    }
+                    table
+                });
+            }
+            ListingsCommands::Categories {
+                query,
+                marketplace,
+                pick,
+                format,
+            } => {
+                pick_category(
+                    query.as_deref(),
+                    marketplace,
+                    pick,
+                    output::format(format, OutputFormat::Table),
+                )?;
+            }
+            ListingsCommands::Report {
+                input,
+                format,
+                output,
+            } => {
+                let resp = read_listing_response(&input)?;
+                let format = match format {
+                    ReportFormatOpt::Md => ReportFormat::Markdown,
+                    ReportFormatOpt::Html => ReportFormat::Html,
+                };
+                let text = report::render(&resp, format);
+                match output {
+                    Some(path) => std::fs::write(&path, text)
+                        .map_err(|err| anyhow!("write {}: {err}", path.display()))?,
+                    None => std::io::stdout().write_all(text.as_bytes())?,
+                }
             }
         },
         Commands::Jobs { cmd } => match cmd {
             JobsCommands::Get { id, format } => {
                 let resp = client.get_job_status(&id).await?;
-                emit_json_or_table(format, &resp, job_table);
+                emit_json_or_table(output::format(format, OutputFormat::Json), &resp, job_table);
+            }
+            JobsCommands::List {
+                state,
+                limit,
+                format,
+            } => {
+                let ledger = JobLedger::open_default()?;
+                let mut rows = Vec::new();
+                // Walk back until `limit` rows match, so older jobs in the
+                // wanted state are not cut off by newer ones.
+                for entry in ledger.recent(usize::MAX)? {
+                    if rows.len() >= limit {
+                        break;
+                    }
+                    // A settled job's state no longer changes; skip the
+                    // status call when it cannot match.
+                    if matches!(
+                        (state, settled_job_state(entry.state)),
+                        (Some(wanted), Some(settled)) if settled != wanted
+                    ) {
+                        continue;
+                    }
+                    let status = client.get_job_status(&entry.job_id).await;
+                    let pending = entry.state == TrackedState::Pending;
+                    let row = JobListRow::new(entry, status);
+                    // Outcomes seen here feed the TUI's failed-jobs queue.
+                    let outcome = match row.state {
+                        JobStateOpt::Completed => Some(TrackedState::Completed),
+                        JobStateOpt::Failed => Some(TrackedState::Failed),
+                        _ => None,
+                    };
+                    if let (true, Some(outcome)) = (pending, outcome) {
+                        ledger.set_state(&row.job_id, outcome, row.detail.as_deref())?;
+                    }
+                    if state.is_none_or(|wanted| row.state == wanted) {
+                        rows.push(row);
+                    }
+                }
+                emit_json_or_table(output::format(format, OutputFormat::Table), &rows, |rows| {
+                    job_list_table(rows)
+                });
+            }
+            JobsCommands::Watch {
+                id,
+                interval,
+                timeout,
+                notify_url,
+                notify_slack,
+            } => {
+                let notify = notify_url
+                    .or_else(|| config.job_notify_url.clone())
+                    .map(|url| (url, notify_slack || config.job_notify_slack));
+                watch_job(
+                    &client,
+                    &id,
+                    interval.into(),
+                    timeout.map(Into::into),
+                    notify,
+                )
+                .await?;
+            }
+            JobsCommands::Retry { id, sets, format } => {
+                let job = retry_job(&client, &id, &sets).await?;
+                emit_json_or_table(output::format(format, OutputFormat::Json), &job, |j| {
+                    let mut table = Table::new();
+                    table.add_row(row!["job_id", j.job_id]);
+                    table
+                });
             }
         },
         Commands::Pricing { cmd } => match cmd {
             PricingCommands::Quote(args) => {
-                let resolved_images = resolve_images_listing(&args, supabase.as_ref()).await?;
+                let resolved_images =
+                    resolve_images_listing(&args, &client, store.as_ref(), &config.quality).await?;
                 let req = build_public_listing(&args, resolved_images, &config)?;
                 let resp = client.pricing_quote(&req).await?;
-                emit_json_or_table(args.format, &resp, |quote| {
-                    let mut table = Table::new();
-                    table.add_row(row!["credits_estimated", quote.credits_estimated]);
-                    if let Some(credits) = quote.credits_applied_cents {
-                        table.add_row(row!["credits_applied_cents", credits]);
-                    }
-                    if let Some(net) = quote.net_due_cents {
-                        table.add_row(row!["net_due_cents", net]);
-                    }
-                    table
-                });
+                emit_json_or_table(
+                    output::format(args.format, OutputFormat::Json),
+                    &resp,
+                    |quote| {
+                        let mut table = Table::new();
+                        table.add_row(row!["credits_estimated", quote.credits_estimated]);
+                        if let Some(credits) = quote.credits_applied_cents {
+                            table.add_row(row!["credits_applied_cents", credits]);
+                        }
+                        if let Some(net) = quote.net_due_cents {
+                            table.add_row(row!["net_due_cents", net]);
+                        }
+                        table
+                    },
+                );
             }
         },
         Commands::Images { cmd } => match cmd {
@@ -434,52 +1636,315 @@ async fn main() -> Result<()> {
             } => {
                 let dir = out_dir.unwrap_or(std::env::temp_dir().join("talaria-captures"));
                 let captured = if upload {
-                    let supa = supabase
+                    let store = store
                         .as_ref()
-                        .ok_or_else(|| anyhow!("Supabase config required for --upload"))?;
-                    images::capture_and_upload(count, device, &dir, supa).await?
+                        .ok_or_else(|| anyhow!("Supabase or S3 config required for --upload"))?;
+                    images::capture_and_upload(count, device, &dir, store).await?
                 } else {
-                    talaria_core::camera::capture_many(count, device, &dir)?
+                    capture_frames(&config, count, device, &dir)?
                         .into_iter()
                         .map(|p| p.to_string_lossy().to_string())
                         .collect()
                 };
-                emit_json_or_table(format, &captured, |items| {
-                    let mut table = Table::new();
-                    for item in items {
-                        table.add_row(row!["image", item]);
-                    }
-                    table
-                });
+                emit_json_or_table(
+                    output::format(format, OutputFormat::Json),
+                    &captured,
+                    |items| {
+                        let mut table = Table::new();
+                        for item in items {
+                            table.add_row(row!["image", item]);
+                        }
+                        table
+                    },
+                );
             }
             ImagesCommands::Upload { paths, format } => {
-                let supa = supabase
+                let store = store
                     .as_ref()
-                    .ok_or_else(|| anyhow!("Supabase config required for uploads"))?;
-                let urls = images::upload_paths(&paths, supa).await?;
-                emit_json_or_table(format, &urls, |items| {
-                    let mut table = Table::new();
-                    for item in items {
-                        table.add_row(row!["url", item]);
+                    .ok_or_else(|| anyhow!("Supabase or S3 config required for uploads"))?;
+                let uploaded = images::upload_files(&paths, store).await?;
+                emit_json_or_table(
+                    output::format(format, OutputFormat::Json),
+                    &uploaded,
+                    |items| {
+                        let mut table = Table::new();
+                        table.add_row(row!["file", "url", "sha256", "reused"]);
+                        for item in items {
+                            table.add_row(row![
+                                item.path.display(),
+                                item.url,
+                                item.sha256,
+                                item.reused
+                            ]);
+                        }
+                        table
+                    },
+                );
+            }
+            ImagesCommands::Prune {
+                older_than,
+                prefix,
+                dry_run,
+                format,
+            } => {
+                let store = store
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("Supabase or S3 config required for prune"))?;
+                let (pruned, failed) =
+                    prune_images(&client, store, *older_than, prefix, dry_run).await?;
+                emit_json_or_table(
+                    output::format(format, OutputFormat::Json),
+                    &pruned,
+                    |items| {
+                        let mut table = Table::new();
+                        table.add_row(row!["path", "size", "created_at"]);
+                        for item in items {
+                            table.add_row(row![
+                                item.path,
+                                item.size.map(|s| s.to_string()).unwrap_or_default(),
+                                item.created_at
+                                    .map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+                                    .unwrap_or_default()
+                            ]);
+                        }
+                        table
+                    },
+                );
+                if dry_run {
+                    note!("dry run: {} object(s) would be deleted", pruned.len());
+                } else {
+                    note!("deleted {} object(s)", pruned.len());
+                }
+                if failed > 0 {
+                    return Err(Failure::Partial {
+                        what: "deletions",
+                        failed,
+                        total: failed + pruned.len(),
                     }
-                    table
-                });
+                    .into());
+                }
             }
         },
         Commands::Usage { cmd } => match cmd {
-            UsageCommands::List {
-                org_id,
+            UsageCommands::List { from, to, format } => {
+                let current = from.is_none() && to.is_none();
+                let resp = client.usage(None, from, to).await?;
+                if current {
+                    record_usage(&resp);
+                }
+                emit_json_or_table(output::format(format, OutputFormat::Json), &resp, |items| {
+                    usage_table(items)
+                });
+            }
+            UsageCommands::History {
                 from,
                 to,
+                org,
                 format,
             } => {
-                let resp = client.usage(org_id, from, to).await?;
-                emit_json_or_table(format, &resp, |items| usage_table(items));
+                let range = DayRange::parse_or_last(
+                    from.as_deref(),
+                    to.as_deref(),
+                    usage_history::BURN_DOWN_DAYS,
+                )?;
+                let days = UsageHistory::open_default()?.days(org.as_deref(), range)?;
+                emit_json_or_table(output::format(format, OutputFormat::Table), &days, |days| {
+                    usage_history_table(days)
+                });
+            }
+        },
+        Commands::Orgs { cmd } => match cmd {
+            OrgsCommands::List { format } => {
+                let orgs = list_orgs(&client).await?;
+                emit_json_or_table(output::format(format, OutputFormat::Table), &orgs, |rows| {
+                    orgs_table(rows)
+                });
             }
         },
         Commands::Credits { format } => {
             let resp = client.usage(None, None, None).await?;
-            emit_json_or_table(format, &resp, |items| credits_table(items));
+            record_usage(&resp);
+            emit_json_or_table(
+                output::format(format, OutputFormat::Table),
+                &resp,
+                |items| credits_table(items),
+            );
+        }
+        Commands::Media { cmd } => match cmd {
+            MediaCommands::AbortStale { format } => {
+                let journal = UploadJournal::open_default()?;
+                let results =
+                    upload_journal::abort_stale(&client, &journal, chrono::Utc::now()).await?;
+                emit_json_or_table(
+                    output::format(format, OutputFormat::Table),
+                    &results,
+                    |rows| stale_uploads_table(rows),
+                );
+                let failed = results.iter().filter(|r| r.error.is_some()).count();
+                note!(
+                    "aborted {} expired upload session(s)",
+                    results.len() - failed
+                );
+                if failed > 0 {
+                    return Err(Failure::Partial {
+                        what: "aborts",
+                        failed,
+                        total: results.len(),
+                    }
+                    .into());
+                }
+            }
+        },
+        Commands::Products { cmd } => match cmd {
+            ProductsCommands::Context { cmd } => match cmd {
+                ProductContextCommands::Show { id, format } => {
+                    let context = show_product_context(&client, &id).await?;
+                    emit_product_context(output::format(format, OutputFormat::Json), &context);
+                }
+                ProductContextCommands::Set {
+                    id,
+                    text,
+                    from_file,
+                    format,
+                } => {
+                    let text = match (text, from_file) {
+                        (Some(text), _) => text,
+                        (None, Some(path)) if path.as_os_str() == "-" => {
+                            let mut text = String::new();
+                            std::io::Read::read_to_string(&mut std::io::stdin(), &mut text)?;
+                            text
+                        }
+                        (None, Some(path)) => std::fs::read_to_string(&path)
+                            .map_err(|err| anyhow!("failed to read {}: {err}", path.display()))?,
+                        (None, None) => unreachable!("clap requires --text or --from-file"),
+                    };
+                    let context = set_product_context(&client, &id, text.trim_end()).await?;
+                    emit_product_context(output::format(format, OutputFormat::Json), &context);
+                }
+            },
+            ProductsCommands::Diff { id, format } => {
+                let diff = diff_product(&client, &id).await?;
+                emit_product_diff(output::format(format, OutputFormat::Table), &diff);
+            }
+        },
+        Commands::Run { cmd } => match cmd {
+            RunCommands::Pipeline(args) => {
+                let client = args.timeout.apply(client);
+                run_pipeline(&args, &client, store.as_ref(), &config, fail_on_warning).await?;
+            }
+        },
+        Commands::Catalog { cmd } => match cmd {
+            CatalogCommands::Export {
+                format,
+                since,
+                output,
+            } => {
+                export_catalog(format, since.as_deref(), output.as_deref())?;
+            }
+        },
+        Commands::Watch(args) => {
+            let store = require_store(store.as_ref())?;
+            let opts = watch::Options {
+                dir: &args.dir,
+                group_by: match args.sku_from {
+                    SkuFromOpt::Filename => GroupBy::Filename,
+                    SkuFromOpt::Subfolder => GroupBy::Subfolder,
+                },
+                archive: args.archive.as_deref(),
+                enrich: args.enrich,
+                interval: args.interval.into(),
+                settle: args.settle.into(),
+                once: args.once,
+                sort_by_cost: args.sort_by_cost,
+            };
+            watch::run(&opts, &client, store, &config).await?;
+        }
+        Commands::Templates { cmd } => match cmd {
+            TemplatesCommands::Save(args) => {
+                let name = args.name.clone();
+                let template = template_from_args(args)?;
+                let path = TemplateStore::open_default()?.save(&name, &template)?;
+                output::done(
+                    &t!("cli.template_saved", name = name, path = path.display()),
+                    &serde_json::json!({ "saved": name, "path": path }),
+                );
+            }
+            TemplatesCommands::List { format } => {
+                let rows: Vec<_> = TemplateStore::open_default()?
+                    .list()?
+                    .into_iter()
+                    .map(|(name, template)| TemplateRow { name, template })
+                    .collect();
+                emit_json_or_table(output::format(format, OutputFormat::Table), &rows, |rows| {
+                    templates_table(rows)
+                });
+            }
+            TemplatesCommands::Apply { name } => {
+                let template = TemplateStore::open_default()?.load(&name)?;
+                template.apply_to(&mut config);
+                config.save()?;
+                output::done(
+                    &t!("cli.template_applied", name = name),
+                    &serde_json::json!({ "applied": name }),
+                );
+            }
+        },
+        Commands::Metrics { cmd } => match cmd {
+            MetricsCommands::Dump { format } => {
+                let path = metrics::default_totals_path()
+                    .ok_or_else(|| anyhow!("unable to determine local data directory"))?;
+                let totals = metrics::flush_totals(metrics::global(), &path)?;
+                match format {
+                    MetricsFormat::Json => println!("{}", serde_json::to_string_pretty(&totals)?),
+                    MetricsFormat::Table => metrics_table(&totals).printstd(),
+                    MetricsFormat::Prometheus => print!("{}", totals.to_prometheus()),
+                }
+            }
+        },
+        Commands::Open { cmd } => match cmd {
+            OpenCommands::Listing {
+                sku,
+                id,
+                marketplace,
+                print,
+            } => {
+                let key = marketplace_key_or_default(marketplace, &config);
+                let listing_id = match (sku, id) {
+                    (_, Some(id)) => id,
+                    (Some(sku), None) => recorded_listing_id(&sku, &key)?,
+                    (None, None) => return Err(invalid!("pass --sku or --id")),
+                };
+                let url = browser::listing_url(&MarketplaceId::from(key.as_str()), &listing_id)
+                    .ok_or_else(|| {
+                        invalid!("no item page known for listing {listing_id} on {key}")
+                    })?;
+                open_url(&url, print)?;
+            }
+            OpenCommands::Image { sku, index, print } => {
+                let local = workspace::find_product(&captures_dir(), &sku)
+                    .map_err(|err| invalid!("{err}"))?;
+                let image = index
+                    .checked_sub(1)
+                    .and_then(|idx| local.manifest["images"].get(idx))
+                    .ok_or_else(|| invalid!("{} has no image {index}", local.sku_alias))?;
+                let url = image["uploaded_url"]
+                    .as_str()
+                    .filter(|url| !url.is_empty())
+                    .ok_or_else(|| {
+                        invalid!("image {index} of {} is not uploaded yet", local.sku_alias)
+                    })?;
+                open_url(url, print)?;
+            }
+            OpenCommands::Dashboard { print } => open_url(config.dashboard_url(), print)?,
+        },
+        Commands::Stats { from, to, format } => {
+            let range = DayRange::parse(from.as_deref(), to.as_deref())?;
+            let stats = local_stats(range)?;
+            emit_json_or_table(
+                output::format(format, OutputFormat::Table),
+                &stats,
+                stats_table,
+            );
         }
     }
 
@@ -506,7 +1971,7 @@ fn merge_llm_stage_options(
     } else if let Some(fallback) = fallback {
         fallback
     } else {
-        return Err(anyhow!(
+        return Err(invalid!(
             "{label} flags require --{label}-model or a config default"
         ));
     };
@@ -519,70 +1984,241 @@ fn merge_llm_stage_options(
     Ok(Some(options))
 }
 
+/// The `--template` named by a command, or an empty one.
+fn load_template(name: Option<&str>) -> Result<RequestTemplate> {
+    match name {
+        Some(name) => Ok(TemplateStore::open_default()?.load(name)?),
+        None => Ok(RequestTemplate::default()),
+    }
+}
+
 fn build_public_listing(
     args: &CreateListingArgs,
     images: Vec<String>,
     config: &Config,
 ) -> Result<PublicListingRequest> {
-    let marketplace = args.marketplace.map(|m| m.into_model());
-    let overrides = None;
+    let template = load_template(args.template.as_deref())?;
     let llm_ingest = merge_llm_stage_options(
         "llm-ingest",
         args.llm_ingest_model,
         args.llm_ingest_reasoning,
         args.llm_ingest_web_search,
-        config.llm_ingest.clone(),
+        template.llm_ingest.clone().or(config.llm_ingest.clone()),
     )?;
     let llm_aspects = merge_llm_stage_options(
         "llm-aspects",
         args.llm_aspects_model,
         args.llm_aspects_reasoning,
         args.llm_aspects_web_search,
-        config.llm_aspects.clone(),
+        template.llm_aspects.clone().or(config.llm_aspects.clone()),
     )?;
-    Ok(PublicListingRequest {
+    let setting = |flag: &Option<String>,
+                   from_template: &Option<String>,
+                   from_config: &Option<String>,
+                   name: &str| {
+        flag.clone()
+            .or_else(|| from_template.clone())
+            .or_else(|| from_config.clone())
+            .ok_or_else(|| {
+                invalid!(
+                    "missing --{name}; pass it, or set it in a --template or the ebay_* config keys"
+                )
+            })
+    };
+    let ebay = &config.ebay;
+    let policies = ListingPolicies::new(
+        setting(
+            &args.merchant_location_key,
+            &template.merchant_location_key,
+            &ebay.merchant_location_key,
+            "merchant-location-key",
+        )?,
+        setting(
+            &args.fulfillment_policy_id,
+            &template.fulfillment_policy_id,
+            &ebay.fulfillment_policy_id,
+            "fulfillment-policy-id",
+        )?,
+        setting(
+            &args.payment_policy_id,
+            &template.payment_policy_id,
+            &ebay.payment_policy_id,
+            "payment-policy-id",
+        )?,
+        setting(
+            &args.return_policy_id,
+            &template.return_policy_id,
+            &ebay.return_policy_id,
+            "return-policy-id",
+        )?,
+    );
+    let marketplace = args
+        .marketplace
+        .map(|m| m.into_model())
+        .or(template.marketplace)
+        .or_else(|| ebay.marketplace.as_deref().map(MarketplaceId::from));
+    Ok(
+        PublicListingRequest::builder(policies, ImagesSource::Multiple(images))
+            .sku(args.sku.clone())
+            .marketplace(marketplace)
+            .dry_run(args.dry_run)
+            .publish(args.publish)
+            .use_signed_urls(args.use_signed_urls || config.use_signed_urls())
+            .llm_ingest(llm_ingest)
+            .llm_aspects(llm_aspects)
+            .build()?,
+    )
+}
+
+/// Builds a draft from the local manifest listing for `--marketplace`; the
+/// package comes from `--package-template`/`--weight`/`--dims` when given,
+/// otherwise from the listing.
+fn build_draft_request(args: &DraftListingArgs, config: &Config) -> Result<ListingDraftRequest> {
+    let key = marketplace_key_or_default(args.marketplace, config);
+    let package = talaria_core::package::resolve_package(
+        &config.package_templates,
+        args.package_template.as_deref(),
+        args.weight.as_deref(),
+        args.dims.as_deref(),
+    )?;
+    let local = workspace::find_product(&captures_dir(), &args.product)?;
+    let listing = &local.manifest["listings"][key.as_str()];
+    if !listing.is_object() {
+        return Err(anyhow!(
+            "no {key} listing recorded for {}; generate one first",
+            local.sku_alias
+        ));
+    }
+    let text = |field: &str| {
+        listing[field]
+            .as_str()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let required = |field: &str| text(field).ok_or_else(|| invalid!("{field} is required"));
+    let setting = |arg: &Option<String>, field: &str, fallback: &Option<String>| {
+        arg.clone()
+            .or_else(|| text(field))
+            .or_else(|| fallback.clone())
+            .ok_or_else(|| invalid!("missing --{}", field.replace('_', "-")))
+    };
+
+    let price = listing["price"]
+        .as_f64()
+        .filter(|price| *price > 0.0)
+        .ok_or_else(|| invalid!("price must be positive"))?;
+    let condition_id = listing["condition_id"]
+        .as_i64()
+        .and_then(|id| i32::try_from(id).ok())
+        .filter(|id| *id > 0)
+        .ok_or_else(|| invalid!("condition_id is required"))?;
+    let images: Vec<String> = serde_json::from_value(listing["images"].clone()).unwrap_or_default();
+    if images.is_empty() {
+        return Err(invalid!("images are required"));
+    }
+    let package = match package {
+        Some(package) => Some(package),
+        None => serde_json::from_value::<Option<ListingPackageInput>>(listing["package"].clone())
+            .ok()
+            .flatten()
+            .filter(|p| p.weight.is_some() || p.dimensions.is_some()),
+    };
+
+    Ok(ListingDraftRequest {
+        sku: local.sku_alias.clone(),
+        merchant_location_key: setting(
+            &args.merchant_location_key,
+            "merchant_location_key",
+            &config.ebay.merchant_location_key,
+        )?,
+        fulfillment_policy_id: setting(
+            &args.fulfillment_policy_id,
+            "fulfillment_policy_id",
+            &config.ebay.fulfillment_policy_id,
+        )?,
+        payment_policy_id: setting(
+            &args.payment_policy_id,
+            "payment_policy_id",
+            &config.ebay.payment_policy_id,
+        )?,
+        return_policy_id: setting(
+            &args.return_policy_id,
+            "return_policy_id",
+            &config.ebay.return_policy_id,
+        )?,
+        marketplace: Some(MarketplaceId::from(key.as_str())),
+        listing: ListingDraftInput {
+            title: required("title")?,
+            description: required("description")?,
+            price,
+            currency: required("currency")?,
+            images,
+            category_id: required("category_id")?,
+            category_label: text("category_label"),
+            condition: text("condition").unwrap_or_default(),
+            condition_id,
+            aspects: serde_json::from_value(listing["aspects"].clone()).unwrap_or_default(),
+            package,
+            quantity: listing["quantity"]
+                .as_i64()
+                .and_then(|q| i32::try_from(q).ok()),
+        },
         dry_run: Some(args.dry_run),
-        fulfillment_policy_id: args.fulfillment_policy_id.clone(),
-        images_source: ImagesSource::Multiple(images),
-        llm_aspects,
-        llm_ingest,
-        marketplace,
-        merchant_location_key: args.merchant_location_key.clone(),
-        overrides,
-        payment_policy_id: args.payment_policy_id.clone(),
         publish: Some(args.publish),
-        return_policy_id: args.return_policy_id.clone(),
-        sku: args.sku.clone(),
-        use_signed_urls: Some(args.use_signed_urls),
     })
 }
 
-fn build_continue_request(args: &ContinueListingArgs, config: &Config) -> Result<ContinueRequest> {
+/// Overrides given as `listings continue` flags.
+fn flag_overrides(
+    args: &ContinueListingArgs,
+    config: &Config,
+) -> Result<Option<PublicPipelineOverrides>> {
     let marketplace = args.marketplace.map(|m| m.into_model());
-    let overrides = if args.override_category.is_some() || !args.override_resolved_images.is_empty()
-    {
-        let category = match &args.override_category {
-            Some(raw) => Some(
-                serde_json::from_str::<CategorySelectionInput>(raw)
-                    .map_err(|err| anyhow!("override_category must be valid JSON: {err}"))?,
-            ),
-            None => None,
-        };
-        let resolved_images = if args.override_resolved_images.is_empty() {
-            None
+    let (condition, condition_id) = resolve_condition_override(args, config)?;
+    Ok(
+        if args.override_category.is_some()
+            || args.category_id.is_some()
+            || condition.is_some()
+            || condition_id.is_some()
+            || !args.override_resolved_images.is_empty()
+        {
+            let category = match (&args.override_category, &args.category_id) {
+                (Some(raw), _) => Some(
+                    serde_json::from_str::<CategorySelectionInput>(raw)
+                        .map_err(|err| invalid!("override_category must be valid JSON: {err}"))?,
+                ),
+                (None, Some(id)) => Some(category_override(
+                    id,
+                    args.category_label.as_deref().unwrap_or(id),
+                    marketplace.as_ref().unwrap_or(&MarketplaceId::EbayUs),
+                )?),
+                (None, None) => None,
+            };
+            let resolved_images = if args.override_resolved_images.is_empty() {
+                None
+            } else {
+                Some(args.override_resolved_images.clone())
+            };
+            Some(PublicPipelineOverrides {
+                category,
+                condition,
+                condition_id,
+                product: None,
+                resolved_images,
+            })
         } else {
-            Some(args.override_resolved_images.clone())
-        };
-        Some(PublicPipelineOverrides {
-            category,
-            condition: None,
-            condition_id: None,
-            product: None,
-            resolved_images,
-        })
-    } else {
-        None
-    };
+            None
+        },
+    )
+}
+
+fn build_continue_request(
+    args: &ContinueListingArgs,
+    config: &Config,
+    overrides: Option<PublicPipelineOverrides>,
+) -> Result<ContinueRequest> {
+    let marketplace = args.marketplace.map(|m| m.into_model());
     let llm_ingest = merge_llm_stage_options(
         "llm-ingest",
         args.llm_ingest_model,
@@ -598,78 +2234,1385 @@ fn build_continue_request(args: &ContinueListingArgs, config: &Config) -> Result
         config.llm_aspects.clone(),
     )?;
 
-    Ok(ContinueRequest {
-        fulfillment_policy_id: args.fulfillment_policy_id.clone(),
-        images_source: if args.images.is_empty() {
-            None
+    let policies = ListingPolicies::new(
+        &args.merchant_location_key,
+        &args.fulfillment_policy_id,
+        &args.payment_policy_id,
+        &args.return_policy_id,
+    );
+    let images = (!args.images.is_empty()).then(|| ImagesSource::Multiple(args.images.clone()));
+    Ok(ContinueRequest::builder(&args.sku, policies)
+        .images_source(images)
+        .marketplace(marketplace)
+        .llm_ingest(llm_ingest)
+        .llm_aspects(llm_aspects)
+        .overrides(overrides)
+        .build()?)
+}
+
+/// What the run being continued detected, for `listings continue
+/// --interactive`: the result of `--from-job`, the `--response` file, or
+/// else the listing kept in the local manifest of `--sku`.
+async fn prior_listing_run(
+    client: &HermesClient,
+    args: &ContinueListingArgs,
+    config: &Config,
+) -> Result<wizard::Detected> {
+    if let Some(id) = &args.from_job {
+        return match client.get_job_status(id).await?.state {
+            JobState::Completed { result } => Ok(wizard::Detected::from_response(&result)),
+            JobState::Failed { error, .. } => Err(invalid!("job {id} failed: {error}")),
+            _ => Err(invalid!("job {id} has not completed yet")),
+        };
+    }
+    if let Some(path) = &args.response {
+        if path.as_os_str() == "-" {
+            return Err(invalid!(
+                "--response - would read the wizard's answers; pass a file"
+            ));
+        }
+        return Ok(wizard::Detected::from_response(&read_listing_response(
+            path,
+        )?));
+    }
+    let key = marketplace_key_or_default(args.marketplace, config);
+    let local = workspace::find_product(&captures_dir(), &args.sku).map_err(|_| {
+        invalid!(
+            "no local product for sku {}; pass --from-job or --response",
+            args.sku
+        )
+    })?;
+    let listing = &local.manifest["listings"][&key];
+    if !listing.is_object() {
+        return Err(invalid!(
+            "sku {} has no {key} listing yet; pass --from-job or --response",
+            args.sku
+        ));
+    }
+    Ok(wizard::Detected::from_manifest(
+        listing,
+        local.conditions(&key),
+    ))
+}
+
+fn marketplace_key_or_default(marketplace: Option<MarketplaceOpt>, config: &Config) -> String {
+    marketplace
+        .map(|m| m.into_model().to_string())
+        .or_else(|| config.ebay.marketplace.clone())
+        .unwrap_or_else(|| "EBAY_US".to_string())
+}
+
+/// Validates `--condition`/`--condition-id` against the allowed list stored in
+/// the local manifest for `--sku`, when one exists.
+fn resolve_condition_override(
+    args: &ContinueListingArgs,
+    config: &Config,
+) -> Result<(Option<String>, Option<i32>)> {
+    if args.condition.is_none() && args.condition_id.is_none() {
+        return Ok((None, None));
+    }
+    let key = marketplace_key_or_default(args.marketplace, config);
+    match workspace::find_product(&captures_dir(), &args.sku) {
+        Ok(local) => local
+            .conditions(&key)
+            .resolve(args.condition.as_deref(), args.condition_id),
+        Err(_) => {
+            eprintln!(
+                "warning: no local product for sku {}; condition not validated",
+                args.sku
+            );
+            Ok((args.condition.clone(), args.condition_id))
+        }
+    }
+}
+
+/// eBay default category tree ids per marketplace.
+fn category_tree_id(marketplace: &MarketplaceId) -> Option<&'static str> {
+    let id = match marketplace {
+        MarketplaceId::EbayUs => "0",
+        MarketplaceId::EbayUk => "3",
+        MarketplaceId::EbayDe => "77",
+        MarketplaceId::EbayAu => "15",
+        MarketplaceId::EbayCa => "2",
+        MarketplaceId::EbayFr => "71",
+        MarketplaceId::EbayIt => "101",
+        MarketplaceId::EbayEs => "186",
+        MarketplaceId::EbayAt => "16",
+        MarketplaceId::EbayBe => "23",
+        MarketplaceId::EbayCh => "193",
+        MarketplaceId::EbayIe => "205",
+        MarketplaceId::EbayNl => "146",
+        MarketplaceId::EbayPl => "212",
+        MarketplaceId::Unknown(_) => return None,
+    };
+    Some(id)
+}
+
+fn category_override(
+    id: &str,
+    label: &str,
+    marketplace: &MarketplaceId,
+) -> Result<CategorySelectionInput> {
+    let tree_id = category_tree_id(marketplace)
+        .ok_or_else(|| anyhow!("no known category tree for marketplace {marketplace}"))?;
+    Ok(CategorySelectionInput {
+        confidence: 1.0,
+        id: id.to_string(),
+        label: label.to_string(),
+        rationale: "User override".to_string(),
+        tree_id: tree_id.to_string(),
+    })
+}
+
+fn category_table(items: &[talaria_core::workspace::KnownCategory]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row!["#", "marketplace", "id", "label", "uses"]);
+    for (idx, c) in items.iter().enumerate() {
+        table.add_row(row![idx + 1, c.marketplace, c.id, c.label, c.uses]);
+    }
+    table
+}
+
+fn pick_category(
+    query: Option<&str>,
+    marketplace: Option<MarketplaceOpt>,
+    pick: Option<usize>,
+    format: OutputFormat,
+) -> Result<()> {
+    let marketplace = marketplace.map(|m| m.into_model());
+    let marketplace_key = marketplace.map(|m| m.to_string());
+    let words = query
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    let matches = talaria_core::workspace::known_categories(&captures_dir())?
+        .into_iter()
+        .filter(|c| {
+            marketplace_key
+                .as_ref()
+                .is_none_or(|key| &c.marketplace == key)
+        })
+        .filter(|c| {
+            let haystack = format!("{} {}", c.label, c.id).to_lowercase();
+            words.iter().all(|word| haystack.contains(word))
+        })
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        return Err(anyhow!(
+            "no local listings use a matching category; run a draft listing first"
+        ));
+    }
+
+    let pick = match pick {
+        Some(n) => Some(n),
+        None if matches!(format, OutputFormat::Table) && std::io::stdin().is_terminal() => {
+            category_table(&matches).printstd();
+            eprint!("Pick a category [1-{}] (Enter to skip): ", matches.len());
+            std::io::stderr().flush()?;
+            let mut line = String::new();
+            std::io::stdin().read_line(&mut line)?;
+            let line = line.trim();
+            if line.is_empty() {
+                return Ok(());
+            }
+            Some(
+                line.parse::<usize>()
+                    .map_err(|_| invalid!("expected a number, got '{line}'"))?,
+            )
+        }
+        None => None,
+    };
+
+    let Some(n) = pick else {
+        emit_json_or_table(format, &matches, |items| category_table(items));
+        return Ok(());
+    };
+    let chosen = n
+        .checked_sub(1)
+        .and_then(|idx| matches.get(idx))
+        .ok_or_else(|| invalid!("pick must be between 1 and {}", matches.len()))?;
+    let chosen_marketplace = MarketplaceId::from(chosen.marketplace.as_str());
+    let input = category_override(&chosen.id, &chosen.label, &chosen_marketplace)?;
+    output::emit_line(&input);
+    Ok(())
+}
+
+/// `hsuf-enrich --manifest`: enriches every request in the file with at most
+/// `concurrency` calls in flight, printing one NDJSON line per item as it
+/// completes and a `summary` line with the totals and the `operator` who ran
+/// the batch.
+async fn enrich_manifest(
+    client: &HermesClient,
+    manifest: &std::path::Path,
+    concurrency: usize,
+    llm_ingest: Option<LlmStageOptions>,
+    prompt_rules: Option<String>,
+    operator: Option<String>,
+) -> Result<()> {
+    let text = std::fs::read_to_string(manifest)
+        .map_err(|err| invalid!("--manifest {}: {err}", manifest.display()))?;
+    let mut requests: Vec<HsufEnrichRequest> = serde_json::from_str(&text)
+        .map_err(|err| invalid!("--manifest {}: {err}", manifest.display()))?;
+    for (index, request) in requests.iter_mut().enumerate() {
+        if request.images.is_empty() {
+            return Err(invalid!("--manifest item {index} has no images"));
+        }
+        if request.llm_ingest.is_none() {
+            request.llm_ingest = llm_ingest.clone();
+        }
+        if request.prompt_rules.is_none() {
+            request.prompt_rules = prompt_rules.clone();
+        }
+    }
+    let total = requests.len();
+
+    let mut totals = EnrichTotals::default();
+    let mut outcomes = std::pin::pin!(enrich_many(client, requests, concurrency));
+    while let Some(outcome) = outcomes.next().await {
+        totals.add(&outcome);
+        let mut line = serde_json::json!({
+            "index": outcome.index,
+            "sku": outcome.sku,
+            "attempts": outcome.attempts,
+        });
+        match &outcome.result {
+            Ok(response) => {
+                line["product"] = serde_json::to_value(&response.product)?;
+                line["usage"] = serde_json::to_value(&response.usage)?;
+            }
+            Err(err) => {
+                line["error"] = err.to_string().into();
+                line["exit_code"] = exit::of_kind(err.kind()).into();
+            }
+        }
+        println!("{line}");
+    }
+    println!(
+        "{}",
+        serde_json::json!({ "summary": totals, "operator": operator })
+    );
+    note!(
+        "enriched {} of {total}; {} input / {} output tokens",
+        totals.succeeded,
+        totals.input_tokens,
+        totals.output_tokens
+    );
+    if totals.failed > 0 {
+        return Err(Failure::Partial {
+            what: "enrich requests",
+            failed: totals.failed,
+            total,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+async fn resolve_images_hsuf(args: &HsufArgs, store: Option<&ImageStore>) -> Result<Vec<String>> {
+    if args.images.iter().any(|image| image == "-") {
+        if args.images.len() > 1 {
+            return Err(invalid!("--images - cannot be combined with other URLs"));
+        }
+        return stdin_image_urls();
+    }
+    if !args.images.is_empty() {
+        return Ok(args.images.clone());
+    }
+    if let Some(dir) = &args.images_from_dir {
+        let store = require_store(store)?;
+        return upload_images_dir(dir, &args.dir_filter, store).await;
+    }
+    if let Some(count) = args.capture {
+        if count == 0 {
+            return Err(invalid!("capture count must be > 0"));
+        }
+        let store = require_store(store)?;
+        let dir = std::env::temp_dir().join("talaria-captures");
+        return images::capture_and_upload(count, args.device, &dir, store)
+            .await
+            .map_err(anyhow::Error::from);
+    }
+    Err(invalid!("no images provided"))
+}
+
+/// Newline-separated image URLs for `--images -`; blank lines are skipped.
+fn stdin_image_urls() -> Result<Vec<String>> {
+    let mut urls = Vec::new();
+    for line in std::io::stdin().lines() {
+        let line = line.context("read image URLs from stdin")?;
+        let url = line.trim();
+        if !url.is_empty() {
+            urls.push(url.to_string());
+        }
+    }
+    if urls.is_empty() {
+        return Err(invalid!("no image URLs on stdin"));
+    }
+    Ok(urls)
+}
+
+async fn upload_images_dir(
+    dir: &std::path::Path,
+    filter: &DirFilterArgs,
+    store: &ImageStore,
+) -> Result<Vec<String>> {
+    let result = images::upload_dir_filtered(dir, &filter.to_filter(), store).await?;
+    report_skipped(&result.skipped);
+    Ok(result.uploaded.into_iter().map(|u| u.url).collect())
+}
+
+fn report_skipped(skipped: &[images::SkippedFile]) {
+    for file in skipped {
+        note!("skipped {}: {}", file.path.display(), file.reason);
+    }
+}
+
+/// The listing's image URLs, each checked to be reachable unless
+/// `--skip-verify` is given.
+async fn resolve_images_listing(
+    args: &CreateListingArgs,
+    client: &HermesClient,
+    store: Option<&ImageStore>,
+    quality: &QualityRules,
+) -> Result<Vec<String>> {
+    let urls = collect_images_listing(args, store, quality).await?;
+    if !args.skip_verify {
+        let broken = broken_image_urls(client, &urls).await?;
+        if !broken.is_empty() {
+            return Err(broken_images_error(&broken, urls.len()));
+        }
+    }
+    Ok(urls)
+}
+
+/// Image URLs Hermes would fail to fetch, found before any credits are
+/// spent. Simulated runs have nothing to fetch.
+async fn broken_image_urls(client: &HermesClient, urls: &[String]) -> Result<Vec<BrokenImageUrl>> {
+    if client.is_simulated() {
+        return Ok(Vec::new());
+    }
+    let http = client
+        .network()
+        .client_builder()?
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    Ok(integrity::verify_image_urls(&http, urls, images::DEFAULT_MAX_UPLOAD_BYTES).await)
+}
+
+fn broken_images_error(broken: &[BrokenImageUrl], total: usize) -> anyhow::Error {
+    invalid!(
+        "{} of {total} image URLs failed verification (--skip-verify to submit anyway): {}",
+        broken.len(),
+        broken
+            .iter()
+            .map(|url| format!("{}: {}", url.url, url.reason))
+            .collect::<Vec<_>>()
+            .join("; ")
+    )
+}
+
+/// Local photos are checked against `quality` before they are uploaded;
+/// URLs are taken as they are.
+async fn collect_images_listing(
+    args: &CreateListingArgs,
+    store: Option<&ImageStore>,
+    quality: &QualityRules,
+) -> Result<Vec<String>> {
+    let strict = args.strict_quality || quality.strict;
+    if !args.images.is_empty() {
+        return Ok(args.images.clone());
+    }
+    if let Some(dir) = &args.images_from_dir {
+        let store = require_store(store)?;
+        let listing = images::collect_dir(dir, &args.dir_filter.to_filter())?;
+        check_image_quality(&listing.files, quality, strict)?;
+        return upload_images_dir(dir, &args.dir_filter, store).await;
+    }
+    if let Some(count) = args.capture {
+        if count == 0 {
+            return Err(invalid!("capture count must be > 0"));
+        }
+        let store = require_store(store)?;
+        let dir = std::env::temp_dir().join("talaria-captures");
+        let captures = images::capture(count, args.device, &dir, store.is_simulated())?;
+        check_image_quality(&captures, quality, strict)?;
+        return images::upload_paths(&captures, store)
+            .await
+            .map_err(anyhow::Error::from);
+    }
+    Err(invalid!("no images provided"))
+}
+
+/// Warns about photos that fail the quality checks; under `strict` they stop
+/// the command before anything is uploaded or billed.
+fn check_image_quality(paths: &[PathBuf], rules: &QualityRules, strict: bool) -> Result<()> {
+    let report = images::quality_report(paths, rules)?;
+    if !strict {
+        for finding in &report.findings {
+            note!("warning: {}: {}", finding.path.display(), finding.message);
+        }
+    }
+    for path in &report.unchecked {
+        note!(
+            "quality not checked for {} (converted on upload)",
+            path.display()
+        );
+    }
+    if strict && !report.passed() {
+        let failed: HashSet<_> = report
+            .findings
+            .iter()
+            .map(|finding| &finding.path)
+            .collect();
+        let reasons: Vec<String> = report
+            .findings
+            .iter()
+            .map(|finding| format!("{}: {}", finding.path.display(), finding.message))
+            .collect();
+        return Err(invalid!(
+            "{} of {} photos failed the quality checks: {}",
+            failed.len(),
+            paths.len(),
+            reasons.join("; ")
+        ));
+    }
+    Ok(())
+}
+
+async fn save_enrich_to_product(
+    client: &HermesClient,
+    id_or_sku: &str,
+    product: &Product,
+    push: bool,
+) -> Result<()> {
+    let structure_json = serde_json::to_value(product)?;
+    let mut local = workspace::find_product(&captures_dir(), id_or_sku)?;
+    local.set_field("structure_json", structure_json.clone())?;
+    local.save()?;
+    note!(
+        "saved structure to {} ({})",
+        local.sku_alias,
+        local.path.display()
+    );
+    if push {
+        let update = ProductUpdateRequest {
+            structure_json: Some(structure_json),
+            ..Default::default()
+        };
+        client.update_product(&local.product_id, &update).await?;
+        note!("pushed structure to Hermes product {}", local.product_id);
+    }
+    Ok(())
+}
+
+/// A product's context text and where it was read from or written to
+/// (`local` manifest and/or `hermes`).
+#[derive(Debug, Serialize)]
+struct ProductContext {
+    product_id: String,
+    sku_alias: String,
+    context_text: Option<String>,
+    sources: Vec<&'static str>,
+}
+
+async fn show_product_context(client: &HermesClient, id_or_sku: &str) -> Result<ProductContext> {
+    match workspace::find_product(&captures_dir(), id_or_sku) {
+        Ok(local) => Ok(ProductContext {
+            context_text: local.manifest["context_text"].as_str().map(str::to_string),
+            product_id: local.product_id,
+            sku_alias: local.sku_alias,
+            sources: vec!["local"],
+        }),
+        Err(_) if client.has_api_key() => {
+            let row = client.get_product(id_or_sku).await?;
+            Ok(ProductContext {
+                product_id: row.id,
+                sku_alias: row.sku_alias,
+                context_text: row.context_text,
+                sources: vec!["hermes"],
+            })
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Updates Hermes first (when an API key is configured) so a failed request
+/// leaves the local manifest untouched, then the local manifest if the
+/// product exists locally.
+async fn set_product_context(
+    client: &HermesClient,
+    id_or_sku: &str,
+    text: &str,
+) -> Result<ProductContext> {
+    let context_text = (!text.trim().is_empty()).then(|| text.to_string());
+    let local = match workspace::find_product(&captures_dir(), id_or_sku) {
+        Ok(local) => Some(local),
+        Err(err) if !client.has_api_key() => {
+            return Err(err.context("HERMES_API_KEY missing; only local products can be updated"));
+        }
+        Err(_) => None,
+    };
+    let mut context = ProductContext {
+        product_id: id_or_sku.to_string(),
+        sku_alias: String::new(),
+        context_text: context_text.clone(),
+        sources: Vec::new(),
+    };
+    if let Some(local) = &local {
+        context.product_id = local.product_id.clone();
+        context.sku_alias = local.sku_alias.clone();
+    }
+
+    if client.has_api_key() {
+        let update = ProductUpdateRequest {
+            // An empty string clears the remote text; `None` would leave it as is.
+            context_text: Some(context_text.clone().unwrap_or_default()),
+            ..Default::default()
+        };
+        let row = client.update_product(&context.product_id, &update).await?;
+        context.sku_alias = row.sku_alias;
+        context.sources.push("hermes");
+    } else {
+        note!("{}", t!("cli.local_only"));
+    }
+
+    if let Some(mut local) = local {
+        local.set_field("context_text", serde_json::json!(context_text))?;
+        local.save()?;
+        context.sources.push("local");
+    }
+    Ok(context)
+}
+
+#[derive(Serialize)]
+struct SoldListing {
+    product_id: String,
+    sku_alias: String,
+    marketplace: String,
+    status: String,
+    quantity: i64,
+    quantity_sold: i64,
+    sources: Vec<&'static str>,
+}
+
+/// Marks units sold in the local manifest and on Hermes (listings_json), the
+/// same two places `products context set` writes to. Listings come from the
+/// local manifest when there is one, otherwise from Hermes.
+async fn mark_sold(
+    client: &HermesClient,
+    id_or_sku: &str,
+    marketplace: &str,
+    count: i64,
+) -> Result<SoldListing> {
+    let local = match workspace::find_product(&captures_dir(), id_or_sku) {
+        Ok(local) => Some(local),
+        Err(err) if !client.has_api_key() => {
+            return Err(err.context("HERMES_API_KEY missing; only local products can be updated"));
+        }
+        Err(_) => None,
+    };
+    let (product_id, sku_alias, mut listings) = match &local {
+        Some(local) => (
+            local.product_id.clone(),
+            local.sku_alias.clone(),
+            local.manifest["listings"].clone(),
+        ),
+        None => {
+            let row = client.get_product(id_or_sku).await?;
+            (row.id, row.sku_alias, row.listings_json)
+        }
+    };
+    let listing = listings
+        .get_mut(marketplace)
+        .filter(|listing| listing.is_object())
+        .ok_or_else(|| anyhow!("no {marketplace} listing for {id_or_sku}"))?;
+    workspace::mark_listing_sold(listing, count)?;
+    let mut sold = SoldListing {
+        product_id: product_id.clone(),
+        sku_alias,
+        marketplace: marketplace.to_string(),
+        status: listing["status"].as_str().unwrap_or_default().to_string(),
+        quantity: listing["quantity"].as_i64().unwrap_or_default(),
+        quantity_sold: listing["quantity_sold"].as_i64().unwrap_or_default(),
+        sources: Vec::new(),
+    };
+
+    if client.has_api_key() {
+        let update = ProductUpdateRequest {
+            listings_json: Some(listings.clone()),
+            ..Default::default()
+        };
+        let row = client.update_product(&product_id, &update).await?;
+        sold.sku_alias = row.sku_alias;
+        sold.sources.push("hermes");
+    } else {
+        note!("{}", t!("cli.local_only"));
+    }
+    if let Some(mut local) = local {
+        local.set_field("listings", listings)?;
+        local.save()?;
+        sold.sources.push("local");
+    }
+    Ok(sold)
+}
+
+/// How a local product manifest differs from its Hermes record.
+#[derive(Debug, Serialize)]
+struct ProductDiff {
+    product_id: String,
+    sku_alias: String,
+    in_sync: bool,
+    differences: Vec<FieldDiff>,
+}
+
+async fn diff_product(client: &HermesClient, id_or_sku: &str) -> Result<ProductDiff> {
+    let local = workspace::find_product(&captures_dir(), id_or_sku)?;
+    if !client.has_api_key() {
+        return Err(anyhow!(
+            "HERMES_API_KEY missing; products diff compares with the Hermes record"
+        ));
+    }
+    let remote = client.get_product(&local.product_id).await?;
+    let differences = product_diff::diff(
+        &ProductFields::from_manifest(&local.manifest),
+        &ProductFields::from(&remote),
+    );
+    Ok(ProductDiff {
+        product_id: local.product_id,
+        sku_alias: local.sku_alias,
+        in_sync: differences.is_empty(),
+        differences,
+    })
+}
+
+fn emit_product_diff(format: OutputFormat, diff: &ProductDiff) {
+    match format {
+        OutputFormat::Json => output::print_json(diff),
+        OutputFormat::Table => print_product_diff(diff),
+        OutputFormat::Porcelain => output::print_porcelain("", diff),
+    }
+}
+
+/// Each differing path with the local value as a `-` line and the Hermes
+/// value as a `+` line, colored on a terminal unless `NO_COLOR` is set.
+fn print_product_diff(diff: &ProductDiff) {
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let paint = |code: &str, text: String| {
+        if color {
+            format!("\x1b[{code}m{text}\x1b[0m")
         } else {
-            Some(ImagesSource::Multiple(args.images.clone()))
-        },
-        llm_aspects,
-        llm_ingest,
-        marketplace,
-        merchant_location_key: args.merchant_location_key.clone(),
-        overrides,
-        payment_policy_id: args.payment_policy_id.clone(),
-        return_policy_id: args.return_policy_id.clone(),
-        sku: args.sku.clone(),
+            text
+        }
+    };
+    let shown = |value: &Option<serde_json::Value>| {
+        value
+            .as_ref()
+            .map_or_else(|| "(unset)".to_string(), |value| value.to_string())
+    };
+    let product = format!("{} ({})", diff.product_id, diff.sku_alias);
+    if diff.in_sync {
+        println!("{product}: local manifest and Hermes agree");
+        return;
+    }
+    println!(
+        "{product}: {} difference(s); {} local, {} Hermes",
+        diff.differences.len(),
+        paint("31", "-".to_string()),
+        paint("32", "+".to_string())
+    );
+    for change in &diff.differences {
+        println!("{}", paint("1", change.path.clone()));
+        println!("  {}", paint("31", format!("- {}", shown(&change.local))));
+        println!("  {}", paint("32", format!("+ {}", shown(&change.remote))));
+    }
+}
+
+fn emit_product_context(format: OutputFormat, context: &ProductContext) {
+    emit_json_or_table(format, context, |c| {
+        let mut table = Table::new();
+        table.add_row(row!["product_id", c.product_id]);
+        table.add_row(row!["sku_alias", c.sku_alias]);
+        table.add_row(row![
+            "context_text",
+            c.context_text.as_deref().unwrap_or("-")
+        ]);
+        table.add_row(row!["sources", c.sources.join(", ")]);
+        table
+    });
+}
+
+async fn create_listing_idempotent(
+    client: &HermesClient,
+    req: &PublicListingRequest,
+    explicit_key: Option<&str>,
+) -> Result<ListingResponse> {
+    if let Some(key) = explicit_key {
+        return Ok(client.create_listing_idempotent(req, key).await?);
+    }
+    // The key survives a failed run so re-running the same command reuses it.
+    let mut store = IdempotencyStore::open_default()?;
+    let key = store.key_for("listings", req)?;
+    let resp = client.create_listing_idempotent(req, &key).await?;
+    store.release("listings", req)?;
+    Ok(resp)
+}
+
+async fn enqueue_listing_idempotent(
+    client: &HermesClient,
+    req: &PublicListingRequest,
+    explicit_key: Option<&str>,
+) -> Result<EnqueueResponse> {
+    let job = if let Some(key) = explicit_key {
+        client.enqueue_listing_idempotent(req, key).await?
+    } else {
+        let mut store = IdempotencyStore::open_default()?;
+        let key = store.key_for("jobs/listings", req)?;
+        let job = client.enqueue_listing_idempotent(req, &key).await?;
+        store.release("jobs/listings", req)?;
+        job
+    };
+    JobLedger::open_default()?.record(&job.job_id, req)?;
+    Ok(job)
+}
+
+/// Re-enqueues the request of job `id` with `sets` applied, recording the
+/// new job in the ledger as a retry of the old one.
+async fn retry_job(client: &HermesClient, id: &str, sets: &[String]) -> Result<EnqueueResponse> {
+    let job = client.get_job_status(id).await?;
+    let failed = match job.state {
+        JobState::Queued {} | JobState::Running {} => {
+            return Err(invalid!("job {id} has not finished; retry it once it has"));
+        }
+        JobState::Failed { .. } => true,
+        JobState::Completed { .. } | JobState::Unknown { .. } => false,
+    };
+    let req = set_request_fields(&job.request, sets)?;
+
+    let mut keys = IdempotencyStore::open_default()?;
+    let key = keys.key_for("jobs/listings", &req)?;
+    let retry = client.enqueue_listing_idempotent(&req, &key).await?;
+    keys.release("jobs/listings", &req)?;
+    let ledger = JobLedger::open_default()?;
+    let previous = ledger.get(id)?;
+    ledger.append(JobLedgerEntry {
+        product_id: previous.as_ref().and_then(|entry| entry.product_id.clone()),
+        retry_of: Some(id.to_string()),
+        ..JobLedgerEntry::new(&retry.job_id, &req)
+    })?;
+    if failed && previous.is_some() {
+        ledger.set_state(id, TrackedState::Retried, None)?;
+    }
+    note!("job {id} retried as {}", retry.job_id);
+    Ok(retry)
+}
+
+/// `req` with each `PATH=VALUE` of `sets` applied to its JSON form. Values
+/// are JSON when they parse as such and text otherwise, though numbers and
+/// booleans stay text in fields that hold text (`sku=123`); `null` clears an
+/// optional field.
+fn set_request_fields(req: &PublicListingRequest, sets: &[String]) -> Result<PublicListingRequest> {
+    let mut value = serde_json::to_value(req)?;
+    let mut assigned = Vec::new();
+    for set in sets {
+        let (path, raw) = set
+            .split_once('=')
+            .ok_or_else(|| invalid!("--set {set}: expected PATH=VALUE"))?;
+        let keys: Vec<&str> = path.trim().split('.').collect();
+        if keys.iter().any(|key| key.is_empty()) {
+            return Err(invalid!("--set {set}: empty field name in '{path}'"));
+        }
+        let mut target = &mut value;
+        for key in &keys {
+            if !target.is_object() {
+                *target = serde_json::json!({});
+            }
+            target = target
+                .as_object_mut()
+                .expect("just made an object")
+                .entry(key.to_string())
+                .or_insert(serde_json::Value::Null);
+        }
+        let text = serde_json::Value::String(raw.to_string());
+        let new = match (&*target, serde_json::from_str(raw)) {
+            (
+                serde_json::Value::String(_),
+                Ok(serde_json::Value::Number(_) | serde_json::Value::Bool(_)),
+            )
+            | (_, Err(_)) => text,
+            (_, Ok(parsed)) => parsed,
+        };
+        *target = new.clone();
+        assigned.push((set, format!("/{}", keys.join("/")), new));
+    }
+
+    let req: PublicListingRequest = serde_json::from_value(value)
+        .map_err(|err| invalid!("--set leaves an invalid request: {err}"))?;
+    // Fields the request does not have are dropped by the round trip.
+    let check = serde_json::to_value(&req)?;
+    for (set, pointer, new) in assigned {
+        if !new.is_null() && check.pointer(&pointer).is_none() {
+            return Err(invalid!("--set {set}: the request has no such field"));
+        }
+    }
+    Ok(req)
+}
+
+fn template_from_args(args: SaveTemplateArgs) -> Result<RequestTemplate> {
+    let llm = |model: Option<LlmModelOpt>, reasoning: bool, web_search: bool| {
+        model.map(|model| LlmStageOptions {
+            model: model.into_model(),
+            reasoning: reasoning.then_some(true),
+            web_search: web_search.then_some(true),
+        })
+    };
+    let template = RequestTemplate {
+        marketplace: args.marketplace.map(|m| m.into_model()),
+        merchant_location_key: args.merchant_location_key,
+        fulfillment_policy_id: args.fulfillment_policy_id,
+        payment_policy_id: args.payment_policy_id,
+        return_policy_id: args.return_policy_id,
+        llm_ingest: llm(
+            args.llm_ingest_model,
+            args.llm_ingest_reasoning,
+            args.llm_ingest_web_search,
+        ),
+        llm_aspects: llm(
+            args.llm_aspects_model,
+            args.llm_aspects_reasoning,
+            args.llm_aspects_web_search,
+        ),
+        prompt_rules: args.prompt_rules,
+    };
+    if serde_json::to_value(&template)?
+        .as_object()
+        .is_some_and(|fields| fields.is_empty())
+    {
+        return Err(invalid!("nothing to save; pass at least one field"));
+    }
+    Ok(template)
+}
+
+#[derive(Serialize)]
+struct TemplateRow {
+    name: String,
+    #[serde(flatten)]
+    template: RequestTemplate,
+}
+
+fn stale_uploads_table(rows: &[upload_journal::StaleAbort]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row![
+        "upload_id",
+        "filename",
+        "product_id",
+        "expired_at",
+        "result"
+    ]);
+    for row in rows {
+        let result = match (&row.error, row.aborted) {
+            (Some(err), _) => format!("failed: {err}"),
+            (None, true) => "aborted".to_string(),
+            (None, false) => "already gone".to_string(),
+        };
+        table.add_row(row![
+            row.upload.upload_id,
+            row.upload.filename,
+            row.upload.product_id.clone().unwrap_or_default(),
+            row.upload
+                .expires_at
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            result
+        ]);
+    }
+    table
+}
+
+fn templates_table(rows: &[TemplateRow]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row![
+        "name",
+        "marketplace",
+        "policies",
+        "llm_ingest",
+        "llm_aspects",
+        "prompt_rules"
+    ]);
+    let model = |options: &Option<LlmStageOptions>| {
+        options
+            .as_ref()
+            .map(|options| options.model.to_string())
+            .unwrap_or_default()
+    };
+    for row in rows {
+        let t = &row.template;
+        let policies = [
+            &t.merchant_location_key,
+            &t.fulfillment_policy_id,
+            &t.payment_policy_id,
+            &t.return_policy_id,
+        ]
+        .iter()
+        .filter(|policy| policy.is_some())
+        .count();
+        table.add_row(row![
+            row.name,
+            t.marketplace
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            format!("{policies}/4"),
+            model(&t.llm_ingest),
+            model(&t.llm_aspects),
+            if t.prompt_rules.is_some() { "yes" } else { "" },
+        ]);
+    }
+    table
+}
+
+/// Prints one machine-readable progress line (`{"stage": .., "event": .., ..}`).
+/// Under `--quiet` only the final `pipeline` line is printed; under
+/// `--porcelain` each event's fields become `<stage>.<event>.<field>` lines.
+fn emit_progress(stage: &str, event: &str, fields: serde_json::Value) {
+    match output::mode() {
+        Mode::Quiet if stage != "pipeline" => return,
+        Mode::Porcelain => {
+            output::print_porcelain(&format!("{stage}.{event}"), &fields);
+            return;
+        }
+        _ => {}
+    }
+    let mut line = serde_json::json!({ "stage": stage, "event": event });
+    if let (Some(line), serde_json::Value::Object(fields)) = (line.as_object_mut(), fields) {
+        line.extend(fields);
+    }
+    println!("{line}");
+    let _ = std::io::stdout().flush();
+}
+
+/// Runs `result`, reporting a `failed` progress line for `stage` on error.
+fn stage_result<T>(stage: &str, result: Result<T>) -> Result<T> {
+    if let Err(err) = &result {
+        emit_progress(
+            stage,
+            "failed",
+            serde_json::json!({ "error": format!("{err:#}") }),
+        );
+    }
+    result
+}
+
+/// Files in `dir` picked by the `--include`/`--recursive` filters, in
+/// relative path order. Skipped files are reported as progress events.
+fn pipeline_images_in_dir(dir: &std::path::Path, filter: &DirFilterArgs) -> Result<Vec<PathBuf>> {
+    let listing = images::collect_dir(dir, &filter.to_filter())?;
+    for file in &listing.skipped {
+        emit_progress(
+            "upload",
+            "skipped",
+            serde_json::json!({ "file": file.path, "reason": file.reason }),
+        );
+    }
+    if listing.files.is_empty() {
+        return Err(anyhow!("no images found in {}", dir.display()));
+    }
+    Ok(listing.files)
+}
+
+fn pipeline_setting(
+    flag: Option<&String>,
+    fallback: Option<&String>,
+    name: &str,
+) -> Result<String> {
+    flag.or(fallback).cloned().ok_or_else(|| {
+        invalid!(
+            "--{name} is required (or set ebay_{} in config)",
+            name.replace('-', "_")
+        )
     })
 }
 
-async fn resolve_images_hsuf(
-    args: &HsufArgs,
-    supabase: Option<&SupabaseClient>,
-) -> Result<Vec<String>> {
-    if !args.images.is_empty() {
-        return Ok(args.images.clone());
+/// capture → upload → enrich → listing, the same sequence the TUI drives,
+/// reported as JSON lines on stdout.
+async fn run_pipeline(
+    args: &PipelineArgs,
+    client: &HermesClient,
+    store: Option<&ImageStore>,
+    config: &Config,
+    fail_on_warning: bool,
+) -> Result<()> {
+    let started = Instant::now();
+    // Resolve listing settings up front so a misconfigured run fails before uploading.
+    let listing_settings = if args.skip_listing {
+        None
+    } else {
+        Some((
+            pipeline_setting(
+                args.merchant_location_key.as_ref(),
+                config.ebay.merchant_location_key.as_ref(),
+                "merchant-location-key",
+            )?,
+            pipeline_setting(
+                args.fulfillment_policy_id.as_ref(),
+                config.ebay.fulfillment_policy_id.as_ref(),
+                "fulfillment-policy-id",
+            )?,
+            pipeline_setting(
+                args.payment_policy_id.as_ref(),
+                config.ebay.payment_policy_id.as_ref(),
+                "payment-policy-id",
+            )?,
+            pipeline_setting(
+                args.return_policy_id.as_ref(),
+                config.ebay.return_policy_id.as_ref(),
+                "return-policy-id",
+            )?,
+        ))
+    };
+    let store = require_store(store)?;
+
+    let paths = if let Some(count) = args.capture {
+        if count == 0 {
+            return Err(invalid!("capture count must be > 0"));
+        }
+        emit_progress("capture", "started", serde_json::json!({ "total": count }));
+        let dir = std::env::temp_dir().join("talaria-captures");
+        let paths = stage_result(
+            "capture",
+            capture_frames(config, count, args.device, &dir).map_err(Into::into),
+        )?;
+        emit_progress(
+            "capture",
+            "completed",
+            serde_json::json!({ "files": paths }),
+        );
+        paths
+    } else {
+        let dir = args
+            .images_from_dir
+            .as_ref()
+            .ok_or_else(|| invalid!("--images-from-dir or --capture is required"))?;
+        pipeline_images_in_dir(dir, &args.dir_filter)?
+    };
+
+    let total = paths.len();
+    emit_progress("upload", "started", serde_json::json!({ "total": total }));
+    let mut urls = Vec::with_capacity(total);
+    let mut cache = stage_result("upload", UploadCache::open_default().map_err(Into::into))?;
+    for (idx, path) in paths.iter().enumerate() {
+        let uploaded = stage_result(
+            "upload",
+            images::upload_file_cached(path, store, &mut cache)
+                .await
+                .map_err(|err| anyhow!("{}: {err}", path.display())),
+        )?;
+        emit_progress(
+            "upload",
+            "progress",
+            serde_json::json!({
+                "done": idx + 1,
+                "total": total,
+                "file": path,
+                "url": uploaded.url,
+                "sha256": uploaded.sha256,
+                "reused": uploaded.reused,
+            }),
+        );
+        urls.push(uploaded.url);
+    }
+    emit_progress("upload", "completed", serde_json::json!({ "urls": urls }));
+
+    if !args.skip_verify {
+        emit_progress(
+            "verify",
+            "started",
+            serde_json::json!({ "total": urls.len() }),
+        );
+        let broken = stage_result("verify", broken_image_urls(client, &urls).await)?;
+        if !broken.is_empty() {
+            emit_progress("verify", "failed", serde_json::json!({ "broken": broken }));
+            return Err(broken_images_error(&broken, urls.len()));
+        }
+        emit_progress("verify", "completed", serde_json::json!({}));
+    }
+
+    emit_progress(
+        "enrich",
+        "started",
+        serde_json::json!({ "images": urls.len() }),
+    );
+    let body = HsufEnrichRequest {
+        images: urls.clone(),
+        sku: args.sku.clone(),
+        context_text: None,
+        prompt_rules: config.prompt_rules.clone(),
+        llm_ingest: config.llm_ingest.clone(),
+    };
+    let enriched = stage_result(
+        "enrich",
+        client.hsuf_enrich(&body, false).await.map_err(Into::into),
+    )?;
+    if let Some(target) = &args.save_to_product {
+        stage_result(
+            "enrich",
+            save_enrich_to_product(client, target, &enriched.product, false).await,
+        )?;
+    }
+    emit_progress(
+        "enrich",
+        "completed",
+        serde_json::json!({ "product": enriched.product }),
+    );
+
+    let mut listing = None;
+    if let Some((
+        merchant_location_key,
+        fulfillment_policy_id,
+        payment_policy_id,
+        return_policy_id,
+    )) = listing_settings
+    {
+        emit_progress(
+            "listing",
+            "started",
+            serde_json::json!({ "publish": args.publish, "dry_run": args.dry_run }),
+        );
+        let policies = ListingPolicies::new(
+            merchant_location_key,
+            fulfillment_policy_id,
+            payment_policy_id,
+            return_policy_id,
+        );
+        let req = PublicListingRequest::builder(policies, ImagesSource::Multiple(urls.clone()))
+            .sku(args.sku.clone())
+            .marketplace(args.marketplace.map(|m| m.into_model()))
+            .dry_run(args.dry_run)
+            .publish(args.publish)
+            .use_signed_urls(config.use_signed_urls())
+            .llm_ingest(config.llm_ingest.clone())
+            .llm_aspects(config.llm_aspects.clone())
+            .overrides(PublicPipelineOverrides {
+                category: None,
+                condition: None,
+                condition_id: None,
+                resolved_images: None,
+                product: Some(serde_json::to_value(&enriched.product)?),
+            })
+            .build()?;
+        let resp = stage_result(
+            "listing",
+            create_listing_idempotent(client, &req, None).await,
+        )?;
+        emit_progress(
+            "listing",
+            "completed",
+            serde_json::json!({ "listing_id": resp.listing_id, "stages": resp.stages }),
+        );
+        listing = Some(resp);
     }
-    if let Some(dir) = &args.images_from_dir {
-        let supa = require_supabase(supabase)?;
-        return images::upload_dir(dir, supa)
-            .await
-            .map_err(anyhow::Error::from);
+
+    emit_progress(
+        "pipeline",
+        "completed",
+        serde_json::json!({
+            "images": urls.len(),
+            "listing_id": listing.as_ref().map(|resp| &resp.listing_id),
+            "elapsed_ms": started.elapsed().as_millis(),
+            "operator": config.operator_name(),
+        }),
+    );
+    match &listing {
+        Some(resp) => check_stage_warnings(fail_on_warning, &config.warning_policy, resp),
+        None => Ok(()),
     }
-    if let Some(count) = args.capture {
-        if count == 0 {
-            return Err(anyhow!("capture count must be > 0"));
+}
+
+fn export_catalog(
+    format: CatalogFormat,
+    since: Option<&str>,
+    output: Option<&std::path::Path>,
+) -> Result<()> {
+    let since = since.map(catalog::parse_since).transpose()?;
+    let products = catalog::export(&captures_dir(), since)?;
+    let mut text = match format {
+        CatalogFormat::Ndjson => products
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<serde_json::Result<Vec<_>>>()?
+            .join("\n"),
+        CatalogFormat::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "exported_at": chrono::Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "since": since.map(|at| at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            "count": products.len(),
+            "products": products,
+        }))?,
+    };
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    match output {
+        Some(path) => {
+            std::fs::write(path, text).map_err(|err| anyhow!("write {}: {err}", path.display()))?
         }
-        let supa = require_supabase(supabase)?;
-        let dir = std::env::temp_dir().join("talaria-captures");
-        return images::capture_and_upload(count, args.device, &dir, supa)
-            .await
-            .map_err(anyhow::Error::from);
+        None => std::io::stdout().write_all(text.as_bytes())?,
     }
-    Err(anyhow!("no images provided"))
+    Ok(())
 }
 
-async fn resolve_images_listing(
-    args: &CreateListingArgs,
-    supabase: Option<&SupabaseClient>,
-) -> Result<Vec<String>> {
-    if !args.images.is_empty() {
-        return Ok(args.images.clone());
+/// A `ListingResponse` from `path` (`-` for stdin): either the response
+/// itself, as printed by `listings create --format json`, or a `--save-dir`
+/// artifact wrapping one.
+fn read_listing_response(path: &std::path::Path) -> Result<ListingResponse> {
+    let text = if path.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin()).context("read listing response from stdin")?
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?
+    };
+    let mut value: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|err| invalid!("{} is not JSON: {err}", path.display()))?;
+    if value["response"]["body"].is_object() {
+        value = value["response"]["body"].take();
     }
-    if let Some(dir) = &args.images_from_dir {
-        let supa = require_supabase(supabase)?;
-        return images::upload_dir(dir, supa)
-            .await
-            .map_err(anyhow::Error::from);
+    serde_json::from_value(value)
+        .map_err(|err| invalid!("{} is not a listing response: {err}", path.display()))
+}
+
+fn require_store(store: Option<&ImageStore>) -> Result<&ImageStore> {
+    store.ok_or_else(|| anyhow!("Supabase or S3 config required for upload/capture workflows"))
+}
+
+/// Camera capture, or synthetic frames in simulation mode.
+fn capture_frames(
+    config: &Config,
+    count: usize,
+    device: Option<u32>,
+    dir: &std::path::Path,
+) -> talaria_core::Result<Vec<PathBuf>> {
+    if config.simulate {
+        talaria_core::simulate::capture_frames(count, dir)
+    } else {
+        talaria_core::camera::capture_many(count, device, dir)
     }
-    if let Some(count) = args.capture {
-        if count == 0 {
-            return Err(anyhow!("capture count must be > 0"));
+}
+
+/// Deletes bucket objects under `prefix` older than `older_than` that neither
+/// a local product manifest nor a Hermes media record points at. Returns the
+/// objects deleted (or, with `dry_run`, the ones that would be) and how many
+/// deletions failed; a failed deletion is reported and the rest carry on.
+async fn prune_images(
+    client: &HermesClient,
+    store: &ImageStore,
+    older_than: Duration,
+    prefix: Option<String>,
+    dry_run: bool,
+) -> Result<(Vec<SupabaseObject>, usize)> {
+    let mut referenced = HashSet::new();
+    for value in workspace::manifest_strings(&captures_dir())? {
+        if let Some(path) = store.object_path_from_url(&value) {
+            referenced.insert(path);
         }
-        let supa = require_supabase(supabase)?;
-        let dir = std::env::temp_dir().join("talaria-captures");
-        return images::capture_and_upload(count, args.device, &dir, supa)
+    }
+    // Without the Hermes side we cannot prove an object is orphaned, so bail.
+    let products = client
+        .list_products()
+        .await
+        .map_err(|err| anyhow!("cannot load Hermes products; refusing to prune: {err}"))?;
+    for product in &products {
+        let media = client
+            .list_product_media(&product.id)
             .await
-            .map_err(anyhow::Error::from);
+            .map_err(|err| anyhow!("cannot load Hermes media; refusing to prune: {err}"))?;
+        for item in media.items {
+            if let Some(path) = store.object_path_from_url(&item.url) {
+                referenced.insert(path);
+            }
+            referenced.insert(item.object_key);
+        }
     }
-    Err(anyhow!("no images provided"))
+
+    let cutoff = chrono::Utc::now()
+        - chrono::Duration::from_std(older_than).map_err(|err| invalid!("--older-than: {err}"))?;
+    let prefix = prefix.unwrap_or_else(|| store.upload_prefix().to_string());
+    let mut cache = UploadCache::open_default()?;
+    let scope = store.cache_scope();
+    let mut pruned = Vec::new();
+    let mut failed = 0;
+    for object in store.list_objects(&prefix).await? {
+        let Some(stamp) = object.created_at.or(object.updated_at) else {
+            continue;
+        };
+        if stamp >= cutoff || referenced.contains(&object.path) {
+            continue;
+        }
+        if !dry_run {
+            if let Err(err) = store.delete_object(&object.path).await {
+                eprintln!("warning: delete {} failed: {err}", object.path);
+                failed += 1;
+                continue;
+            }
+            cache.forget_object(&scope, &object.path)?;
+        }
+        pruned.push(object);
+    }
+    Ok((pruned, failed))
 }
 
-fn require_supabase<'a>(supa: Option<&'a SupabaseClient>) -> Result<&'a SupabaseClient> {
-    supa.ok_or_else(|| anyhow!("Supabase config required for upload/capture workflows"))
+async fn check_api(client: &HermesClient, format: OutputFormat) -> Result<()> {
+    let doc = client.openapi().await?;
+    let server = ApiSurface::from_openapi(&doc)?;
+    let drift = ApiSurface::known().drift(&server);
+    emit_json_or_table(format, &drift, |drift| {
+        let mut table = Table::new();
+        table.add_row(row![
+            "server_version",
+            drift.server_version.as_deref().unwrap_or("-")
+        ]);
+        table.add_row(row!["client_fingerprint", drift.client_fingerprint]);
+        table.add_row(row!["server_fingerprint", drift.server_fingerprint]);
+        let endpoints = |list: &[Endpoint]| {
+            list.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let fields = |map: &BTreeMap<String, Vec<String>>| {
+            map.iter()
+                .map(|(schema, fields)| format!("{schema}: {}", fields.join(", ")))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        table.add_row(row![
+            "unknown_endpoints",
+            endpoints(&drift.unknown_endpoints)
+        ]);
+        table.add_row(row![
+            "missing_endpoints",
+            endpoints(&drift.missing_endpoints)
+        ]);
+        table.add_row(row!["unknown_schemas", drift.unknown_schemas.join("\n")]);
+        table.add_row(row!["unknown_fields", fields(&drift.unknown_fields)]);
+        table.add_row(row!["missing_fields", fields(&drift.missing_fields)]);
+        table
+    });
+    if drift.breaking() {
+        eprintln!(
+            "warning: Hermes no longer serves {} endpoint(s) and {} schema(s) this talaria uses; upgrade talaria",
+            drift.missing_endpoints.len(),
+            drift.missing_fields.len()
+        );
+    } else if drift.upgrade_available() {
+        eprintln!(
+            "warning: Hermes has {} endpoint(s) and {} schema(s) with fields this talaria does not know; a newer talaria can use them",
+            drift.unknown_endpoints.len(),
+            drift.unknown_fields.len()
+        );
+    } else {
+        note!(
+            "talaria is up to date with Hermes {}",
+            drift.server_version.as_deref().unwrap_or("(unversioned)")
+        );
+    }
+    Ok(())
 }
 
 fn emit_json_or_table<T: Serialize>(
@@ -678,14 +3621,12 @@ fn emit_json_or_table<T: Serialize>(
     table_builder: impl FnOnce(&T) -> Table,
 ) {
     match format {
-        OutputFormat::Json => {
-            let json = serde_json::to_string_pretty(value).expect("serializable output");
-            println!("{json}");
-        }
+        OutputFormat::Json => output::print_json(value),
         OutputFormat::Table => {
             let table = table_builder(value);
             table.printstd();
         }
+        OutputFormat::Porcelain => output::print_porcelain("", value),
     }
 }
 
@@ -706,9 +3647,8 @@ fn emit_listing(format: OutputFormat, resp: &ListingResponse) {
             if let Some(summary) = stage_output_summary(&stage.output) {
                 table.add_row(row!["    summary", summary]);
             }
-            if let Some(warnings) = stage_output_warnings(&stage.output)
-                && !warnings.is_empty()
-            {
+            let warnings = warnings::stage_warnings(stage);
+            if !warnings.is_empty() {
                 table.add_row(row!["    warnings", warnings.join("; ")]);
             }
         }
@@ -723,16 +3663,28 @@ fn stage_output_summary(output: &serde_json::Value) -> Option<String> {
         .map(str::to_string)
 }
 
-fn stage_output_warnings(output: &serde_json::Value) -> Option<Vec<String>> {
-    let warnings = output.get("warnings")?;
-    if let Some(values) = warnings.as_array() {
-        let items = values
-            .iter()
-            .filter_map(|value| value.as_str().map(str::to_string))
-            .collect::<Vec<_>>();
-        return Some(items);
+/// Applies `[warning_policy]` to the stage warnings of `resp`: notes the
+/// ones to warn about and fails with those to fail on, each prefixed with
+/// the stage name. `--fail-on-warning` fails on every warning the policy
+/// does not ignore.
+fn check_stage_warnings(
+    fail_on_warning: bool,
+    policy: &WarningPolicy,
+    resp: &ListingResponse,
+) -> Result<()> {
+    let mut failed = Vec::new();
+    for warning in policy.evaluate(resp) {
+        match warning.action {
+            WarningAction::Ignore => {}
+            WarningAction::Warn if !fail_on_warning => note!("warning: {warning}"),
+            WarningAction::Warn | WarningAction::Fail => failed.push(warning.to_string()),
+        }
+    }
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(Failure::Warnings(failed).into())
     }
-    warnings.as_str().map(|value| vec![value.to_string()])
 }
 
 fn job_table(info: &JobInfo) -> Table {
@@ -763,10 +3715,192 @@ fn job_table(info: &JobInfo) -> Table {
                 table.add_row(row!["stage", stage]);
             }
         }
+        JobState::Unknown { state } => {
+            table.add_row(row!["state", state]);
+        }
+    }
+    table
+}
+
+#[derive(Serialize)]
+struct JobListRow {
+    job_id: String,
+    sku: Option<String>,
+    enqueued_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip)]
+    state: JobStateOpt,
+    state_label: String,
+    detail: Option<String>,
+    listing_id: Option<String>,
+}
+
+impl JobListRow {
+    fn new(entry: JobLedgerEntry, status: talaria_core::Result<JobInfo>) -> Self {
+        let (state, state_label, detail, listing_id) = match status {
+            Ok(info) => {
+                let label = job_state_label(&info.state).to_string();
+                match info.state {
+                    JobState::Queued {} => (JobStateOpt::Queued, label, None, None),
+                    JobState::Running {} => (JobStateOpt::Running, label, None, None),
+                    JobState::Completed { result } => {
+                        (JobStateOpt::Completed, label, None, Some(result.listing_id))
+                    }
+                    JobState::Failed { error, stage } => {
+                        let detail = match stage {
+                            Some(stage) => format!("{stage}: {error}"),
+                            None => error,
+                        };
+                        (JobStateOpt::Failed, label, Some(detail), None)
+                    }
+                    JobState::Unknown { .. } => (JobStateOpt::Unknown, label, None, None),
+                }
+            }
+            Err(err) => (
+                JobStateOpt::Unknown,
+                "unknown".to_string(),
+                Some(err.to_string()),
+                None,
+            ),
+        };
+        Self {
+            job_id: entry.job_id,
+            sku: entry.sku,
+            enqueued_at: entry.enqueued_at,
+            state,
+            state_label,
+            detail,
+            listing_id,
+        }
+    }
+}
+
+/// The Hermes state of a job whose outcome the ledger already recorded.
+fn settled_job_state(state: TrackedState) -> Option<JobStateOpt> {
+    match state {
+        TrackedState::Pending => None,
+        TrackedState::Completed => Some(JobStateOpt::Completed),
+        TrackedState::Failed | TrackedState::Retried | TrackedState::Dismissed => {
+            Some(JobStateOpt::Failed)
+        }
+    }
+}
+
+fn job_list_table(rows: &[JobListRow]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row![
+        "job_id",
+        "sku",
+        "enqueued_at",
+        "state",
+        "listing_id",
+        "detail"
+    ]);
+    for row in rows {
+        table.add_row(row![
+            row.job_id,
+            row.sku.clone().unwrap_or_default(),
+            row.enqueued_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            row.state_label,
+            row.listing_id.clone().unwrap_or_default(),
+            row.detail.clone().unwrap_or_default()
+        ]);
+    }
+    table
+}
+
+fn job_state_label(state: &JobState) -> &str {
+    match state {
+        JobState::Queued {} => "queued",
+        JobState::Running {} => "running",
+        JobState::Completed { .. } => "completed",
+        JobState::Failed { .. } => "failed",
+        JobState::Unknown { state } => state,
     }
+}
+
+fn job_watch_table(info: &JobInfo, elapsed: Duration) -> Table {
+    let mut table = Table::new();
+    table.add_row(row!["id", "state", "stage", "retries", "elapsed"]);
+    let stage = match &info.state {
+        JobState::Failed {
+            stage: Some(stage), ..
+        } => stage.clone(),
+        _ => "-".to_string(),
+    };
+    let retries = match (info.retry, info.max_retries) {
+        (Some(retry), Some(max)) => format!("{retry}/{max}"),
+        (Some(retry), None) => retry.to_string(),
+        _ => "0".to_string(),
+    };
+    table.add_row(row![
+        info.id.clone(),
+        job_state_label(&info.state),
+        stage,
+        retries,
+        humantime::format_duration(Duration::from_secs(elapsed.as_secs())).to_string()
+    ]);
     table
 }
 
+async fn watch_job(
+    client: &HermesClient,
+    id: &str,
+    interval: Duration,
+    timeout: Option<Duration>,
+    notify: Option<(String, bool)>,
+) -> Result<()> {
+    let interactive = std::io::stdout().is_terminal();
+    // The live table is for people; scripts only get the summary.
+    let show_table = output::mode() == Mode::Human;
+    let started = Instant::now();
+    let mut drawn_lines = 0usize;
+    loop {
+        let info = client.get_job_status(id).await?;
+        let elapsed = started.elapsed();
+        if show_table {
+            let rendered = job_watch_table(&info, elapsed).to_string();
+            if interactive && drawn_lines > 0 {
+                // Move back over the previous table and clear it before redrawing.
+                print!("\x1b[{drawn_lines}A\x1b[J");
+            }
+            print!("{rendered}");
+            std::io::stdout().flush()?;
+            drawn_lines = rendered.lines().count();
+        }
+
+        let terminal = matches!(
+            info.state,
+            JobState::Completed { .. } | JobState::Failed { .. }
+        );
+        let timed_out = timeout.is_some_and(|limit| elapsed >= limit);
+        if terminal || timed_out {
+            let summary = serde_json::json!({
+                "id": info.id,
+                "state": job_state_label(&info.state),
+                "terminal": terminal,
+                "elapsed_secs": elapsed.as_secs(),
+                "retry": info.retry,
+                "job": info,
+            });
+            output::emit_line(&summary);
+            if let Some((url, slack)) = &notify {
+                let payload = talaria_core::notify::job_payload(&summary, *slack);
+                if let Err(err) = talaria_core::notify::post_webhook(url, &payload).await {
+                    eprintln!("warning: job notification to {url} failed: {err}");
+                }
+            }
+            if !terminal {
+                return Err(anyhow!("timed out waiting for job {id}"));
+            }
+            if matches!(info.state, JobState::Failed { .. }) {
+                return Err(anyhow!("job {id} failed"));
+            }
+            return Ok(());
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
 fn usage_table(items: &[UsageSummary]) -> Table {
     let mut table = Table::new();
     table.add_row(row![
@@ -794,6 +3928,174 @@ fn usage_table(items: &[UsageSummary]) -> Table {
     table
 }
 
+/// Keeps the current window's usage in the local history; a failure only
+/// warns.
+fn record_usage(usage: &[UsageSummary]) {
+    let recorded =
+        UsageHistory::open_default().and_then(|history| history.record(usage, chrono::Utc::now()));
+    if let Err(err) = recorded {
+        eprintln!("warning: usage history not updated: {err}");
+    }
+}
+
+/// A row per organization and day, with a bar of the credits consumed.
+fn usage_history_table(days: &[UsageDay]) -> Table {
+    const BAR_WIDTH: i64 = 30;
+    let most = days
+        .iter()
+        .map(|day| day.credits_consumed)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let mut table = Table::new();
+    table.add_row(row![
+        "date",
+        "org_id",
+        "credits",
+        "listings_run",
+        "jobs_enqueued",
+        "balance",
+        ""
+    ]);
+    for day in days {
+        let bar = (day.credits_consumed.max(0) * BAR_WIDTH + most - 1) / most;
+        table.add_row(row![
+            day.date,
+            &day.org_id,
+            day.credits_consumed,
+            day.listings_run,
+            day.jobs_enqueued,
+            day.credit_balance_cents
+                .map(|cents| cents.to_string())
+                .unwrap_or_else(|| "-".into()),
+            "█".repeat(bar as usize)
+        ]);
+    }
+    table
+}
+
+#[derive(Serialize)]
+struct OrgRow {
+    org_id: String,
+    /// The organization requests are scoped to.
+    current: bool,
+}
+
+/// Hermes has no organization directory, so the list is the distinct orgs in
+/// an unscoped usage report, plus the configured one if it has no usage yet.
+async fn list_orgs(client: &HermesClient) -> Result<Vec<OrgRow>> {
+    let usage = client
+        .clone()
+        .with_org(None)
+        .usage(None, None, None)
+        .await?;
+    let mut ids: Vec<String> = usage.into_iter().map(|item| item.org_id).collect();
+    ids.extend(client.org_id().map(str::to_string));
+    ids.sort();
+    ids.dedup();
+    let current = client.org_id();
+    Ok(ids
+        .into_iter()
+        .map(|org_id| OrgRow {
+            current: current == Some(org_id.as_str()),
+            org_id,
+        })
+        .collect())
+}
+
+fn orgs_table(rows: &[OrgRow]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row!["", "org_id"]);
+    for row in rows {
+        table.add_row(row![if row.current { "*" } else { "" }, row.org_id]);
+    }
+    table
+}
+
+fn metrics_table(totals: &metrics::Snapshot) -> Table {
+    let mut table = Table::new();
+    table.add_row(row!["series", "value"]);
+    for (series, value) in &totals.counters {
+        table.add_row(row![series, value]);
+    }
+    for (series, value) in &totals.gauges {
+        table.add_row(row![series, format!("{value:.1}")]);
+    }
+    for (series, timing) in &totals.timings {
+        table.add_row(row![
+            series,
+            format!(
+                "n={} mean={:.3}s max={:.3}s",
+                timing.count,
+                timing.mean_secs(),
+                timing.max_secs
+            )
+        ]);
+    }
+    table
+}
+
+/// [`stats::compute`] over the local workspace and job ledger.
+fn local_stats(range: DayRange) -> Result<Stats> {
+    let base = captures_dir();
+    let sessions = workspace::load_sessions(&base)?;
+    let sku_products = if base.join("products").exists() {
+        workspace::load_products(&base)?
+            .into_iter()
+            .map(|product| (product.sku_alias, product.product_id))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let jobs = JobLedger::open_default()?.recent(usize::MAX)?;
+    Ok(stats::compute(range, &sessions, &jobs, &sku_products))
+}
+
+fn stats_table(stats: &Stats) -> Table {
+    let cycle = |secs: Option<u64>| {
+        secs.map(|secs| humantime::format_duration(Duration::from_secs(secs)).to_string())
+            .unwrap_or_else(|| "-".into())
+    };
+    let mut table = Table::new();
+    table.add_row(row![
+        "day",
+        "captured",
+        "listed",
+        "published",
+        "failed",
+        "avg_cycle"
+    ]);
+    for day in &stats.days {
+        table.add_row(row![
+            day.date,
+            day.captured,
+            day.listed,
+            day.published,
+            day.failed,
+            cycle(day.avg_cycle_secs)
+        ]);
+    }
+    table.add_row(row![
+        "total",
+        stats.captured,
+        stats.listed,
+        stats.published,
+        stats.failed,
+        cycle(stats.avg_cycle_secs)
+    ]);
+    for stage in &stats.failures_by_stage {
+        table.add_row(row![
+            format!("failed at {}", stage.stage),
+            "",
+            "",
+            "",
+            stage.failed,
+            format!("{:.0}%", stage.rate * 100.0)
+        ]);
+    }
+    table
+}
+
 fn credits_table(items: &[UsageSummary]) -> Table {
     let mut table = Table::new();
     table.add_row(row![
@@ -827,12 +4129,183 @@ fn credits_table(items: &[UsageSummary]) -> Table {
 }
 
 async fn auth_login(client: &HermesClient, config: &mut Config, no_browser: bool) -> Result<()> {
-    let start = client.device_auth_start().await?;
-    println!(
-        "Open {} and enter code: {}",
-        start.verification_uri, start.user_code
+    let access_token = device_access_token(client, no_browser).await?;
+    let key = create_key(client, &access_token).await?;
+    *config = with_key(config, &key);
+    config.save()?;
+    output::done(
+        &t!("cli.key_saved", prefix = key.prefix),
+        &serde_json::json!({ "id": key.id, "prefix": key.prefix }),
+    );
+    Ok(())
+}
+
+/// Creates a key, checks that Hermes accepts it, saves it and only then (with
+/// `revoke_old`) revokes the key it replaced.
+async fn rotate_api_key(
+    client: &HermesClient,
+    config: &mut Config,
+    token: AccessTokenArgs,
+    revoke_old: bool,
+) -> Result<()> {
+    if client.is_simulated() {
+        return Err(anyhow!("auth rotate-key is unavailable with --simulate"));
+    }
+    let old = config.api_key_info.clone();
+    if revoke_old && old.is_none() {
+        return Err(anyhow!(
+            "The current key's id is unknown (it was not created by `talaria auth login`), \
+             so it cannot be revoked; rerun without --revoke-old"
+        ));
+    }
+    let access_token = token.resolve(client).await?;
+    let key = create_key(client, &access_token).await?;
+    let updated = with_key(config, &key);
+    HermesClient::new(updated.clone())?
+        .usage(None, None, None)
+        .await
+        .context("Hermes rejected the newly created key; the config was left unchanged")?;
+    updated.save()?;
+    *config = updated;
+    let mut message = format!("New Hermes API key saved. Prefix: {}", key.prefix);
+    if std::env::var(talaria_core::config::ENV_API_KEY).is_ok() {
+        eprintln!(
+            "warning: {} is set and still overrides the saved key",
+            talaria_core::config::ENV_API_KEY
+        );
+    }
+    let mut revoked = None;
+    if let (true, Some(old)) = (revoke_old, old) {
+        client
+            .delete_user_api_key(&access_token, &old.id)
+            .await
+            .with_context(|| {
+                format!(
+                    "New key {} was saved, but revoking the previous key failed",
+                    key.prefix
+                )
+            })?;
+        message.push_str(&format!(
+            "\nRevoked previous key {} ({})",
+            old.prefix, old.name
+        ));
+        revoked = Some(old.id);
+    }
+    output::done(
+        &message,
+        &serde_json::json!({ "id": key.id, "prefix": key.prefix, "revoked": revoked }),
+    );
+    Ok(())
+}
+
+async fn create_key(client: &HermesClient, access_token: &str) -> Result<UserApiKeyCreateResponse> {
+    let name = format!(
+        "Talaria {} {}",
+        hostname_label(),
+        chrono::Local::now().format("%Y%m%d-%H%M")
     );
-    println!("Waiting for authorization...");
+    Ok(client.create_user_api_key(access_token, &name).await?)
+}
+
+fn with_key(config: &Config, key: &UserApiKeyCreateResponse) -> Config {
+    Config {
+        api_key: Some(key.secret.clone()),
+        api_key_info: Some(ApiKeyInfo {
+            id: key.id.clone(),
+            name: key.name.clone(),
+            prefix: key.prefix.clone(),
+        }),
+        ..config.clone()
+    }
+}
+
+#[derive(Serialize)]
+struct ApiKeyRow {
+    #[serde(flatten)]
+    key: UserApiKey,
+    /// The key in this machine's config.
+    current: bool,
+}
+
+fn api_keys_table(rows: &[ApiKeyRow]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row![
+        "",
+        "id",
+        "name",
+        "prefix",
+        "created_at",
+        "last_used_at"
+    ]);
+    let at = |at: Option<chrono::DateTime<chrono::Utc>>| {
+        at.map(|at| at.to_rfc3339_opts(SecondsFormat::Secs, true))
+            .unwrap_or_default()
+    };
+    for row in rows {
+        table.add_row(row![
+            if row.current { "*" } else { "" },
+            row.key.id,
+            row.key.name,
+            row.key.prefix,
+            at(row.key.created_at),
+            at(row.key.last_used_at)
+        ]);
+    }
+    table
+}
+
+#[derive(Serialize)]
+struct AuthStatus {
+    /// `environment`, `config` or `none`.
+    source: &'static str,
+    key: Option<String>,
+    prefix: Option<String>,
+    name: Option<String>,
+    id: Option<String>,
+    /// `None` when there is no key or Hermes could not be reached.
+    authenticates: Option<bool>,
+    error: Option<String>,
+}
+
+async fn auth_status(client: &HermesClient, config: &Config) -> AuthStatus {
+    let source = if config.api_key.is_none() {
+        "none"
+    } else if std::env::var(talaria_core::config::ENV_API_KEY).is_ok() {
+        "environment"
+    } else {
+        "config"
+    };
+    let info = config.api_key_info.as_ref();
+    let (authenticates, error) = if config.api_key.is_none() {
+        (None, None)
+    } else {
+        match client.usage(None, None, None).await {
+            Ok(_) => (Some(true), None),
+            Err(talaria_core::Error::Api {
+                status, message, ..
+            }) if matches!(status.as_u16(), 401 | 403) => (Some(false), Some(message)),
+            Err(err) => (None, Some(err.to_string())),
+        }
+    };
+    AuthStatus {
+        source,
+        key: config.redacted_api_key(),
+        prefix: info.map(|i| i.prefix.clone()),
+        name: info.map(|i| i.name.clone()),
+        id: info.map(|i| i.id.clone()),
+        authenticates,
+        error,
+    }
+}
+
+async fn device_access_token(client: &HermesClient, no_browser: bool) -> Result<String> {
+    let start = client.device_auth_start().await?;
+    output::prompt(&t!(
+        "cli.device_open",
+        url = start.verification_uri,
+        code = start.user_code
+    ));
+    output::prompt(&t!("cli.device_waiting"));
 
     if !no_browser {
         try_open_browser(&start.verification_uri_complete);
@@ -843,9 +4316,7 @@ async fn auth_login(client: &HermesClient, config: &mut Config, no_browser: bool
     let interval = Duration::from_secs(start.interval.max(1));
     let access_token = loop {
         if Instant::now() >= deadline {
-            return Err(anyhow!(
-                "Device code expired. Run `talaria auth login` again."
-            ));
+            return Err(Failure::Auth(t!("cli.device_expired")).into());
         }
         tokio::time::sleep(interval).await;
         let poll = client.device_auth_poll(&start.device_code).await?;
@@ -854,46 +4325,63 @@ async fn auth_login(client: &HermesClient, config: &mut Config, no_browser: bool
             DeviceAuthStatus::Authorized => {
                 let token = poll
                     .access_token
-                    .ok_or_else(|| anyhow!("Missing access token from device auth"))?;
+                    .ok_or_else(|| Failure::Auth("Missing access token from device auth".into()))?;
                 break token;
             }
             DeviceAuthStatus::Expired => {
-                return Err(anyhow!(
-                    "Device code expired. Run `talaria auth login` again."
-                ));
+                return Err(Failure::Auth(t!("cli.device_expired")).into());
             }
             DeviceAuthStatus::Consumed => {
-                return Err(anyhow!(
-                    "Device code already used. Run `talaria auth login` again."
-                ));
+                return Err(Failure::Auth(t!("cli.device_used")).into());
+            }
+            DeviceAuthStatus::Unknown(status) => {
+                return Err(Failure::Auth(format!(
+                    "Unexpected device auth status '{status}'. Run `talaria auth login` again."
+                ))
+                .into());
             }
         }
     };
-
-    let name = format!(
-        "Talaria {} {}",
-        hostname_label(),
-        chrono::Local::now().format("%Y%m%d-%H%M")
-    );
-    let key = client.create_user_api_key(&access_token, &name).await?;
-    config.api_key = Some(key.secret.clone());
-    config.save()?;
-    println!("Hermes API key saved. Prefix: {}", key.prefix);
-    Ok(())
+    Ok(access_token)
 }
 
 fn try_open_browser(url: &str) {
-    let result = if cfg!(target_os = "windows") {
-        Command::new("cmd").args(["/C", "start", "", url]).status()
-    } else if cfg!(target_os = "macos") {
-        Command::new("open").arg(url).status()
-    } else {
-        Command::new("xdg-open").arg(url).status()
-    };
+    if let Err(err) = browser::open(url) {
+        eprintln!("{}", t!("cli.browser_failed", error = err, url = url));
+    }
+}
 
-    if let Err(err) = result {
-        eprintln!("Failed to open browser: {err}. Visit {url} manually.");
+/// Opens `url` for `talaria open`, or with `print` only shows it.
+fn open_url(url: &str, print: bool) -> Result<()> {
+    if !print {
+        browser::open(url)
+            .map_err(|err| anyhow!("failed to open browser: {err}; visit {url} manually"))?;
     }
+    output::done(
+        &if print {
+            url.to_string()
+        } else {
+            t!("cli.opened", url = url)
+        },
+        &serde_json::json!({ "url": url, "opened": !print }),
+    );
+    Ok(())
+}
+
+/// The listing id recorded in the local manifest of `sku` for `marketplace`.
+fn recorded_listing_id(sku: &str, marketplace: &str) -> Result<String> {
+    let local = workspace::find_product(&captures_dir(), sku)
+        .map_err(|_| invalid!("no local product for sku {sku}; pass --id"))?;
+    local.manifest["listings"][marketplace]["listing_id"]
+        .as_str()
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            invalid!(
+                "sku {} has no published {marketplace} listing; pass --id",
+                local.sku_alias
+            )
+        })
 }
 
 fn hostname_label() -> String {
@@ -0,0 +1,188 @@
+//! Where command output goes and in what shape. Results go to stdout as
+//! JSON, a table or porcelain lines; human-oriented notes go to stderr and
+//! are dropped under `--quiet` and `--porcelain`, so scripts only ever see
+//! the result.
+//!
+//! Porcelain is one `key<TAB>value` line per scalar, keys being the dotted
+//! JSON path (`stages.0.name`). It follows the JSON field names, not the
+//! wording of tables or messages, so it stays stable when those change.
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+use std::sync::OnceLock;
+use talaria_core::config;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Table,
+    Porcelain,
+}
+
+impl From<config::OutputFormat> for OutputFormat {
+    fn from(format: config::OutputFormat) -> Self {
+        match format {
+            config::OutputFormat::Json => OutputFormat::Json,
+            config::OutputFormat::Table => OutputFormat::Table,
+            config::OutputFormat::Porcelain => OutputFormat::Porcelain,
+        }
+    }
+}
+
+/// Set by the global `--quiet` / `--porcelain` flags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Human,
+    /// Only the final JSON result.
+    Quiet,
+    /// Only the final result, as porcelain lines.
+    Porcelain,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Settings {
+    mode: Mode,
+    /// `default_output_format` from the config.
+    default_format: Option<OutputFormat>,
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Applies the global flags and config for the rest of the run.
+pub fn init(mode: Mode, default_format: Option<config::OutputFormat>) {
+    let _ = SETTINGS.set(Settings {
+        mode,
+        default_format: default_format.map(OutputFormat::from),
+    });
+}
+
+fn settings() -> Settings {
+    SETTINGS.get().copied().unwrap_or_default()
+}
+
+pub fn mode() -> Mode {
+    settings().mode
+}
+
+/// The format for a command: `--format` if given, then the mode (`--porcelain`
+/// or `--quiet`), then `default_output_format`, then the command's own default.
+pub fn format(explicit: Option<OutputFormat>, fallback: OutputFormat) -> OutputFormat {
+    let settings = settings();
+    explicit
+        .or(match settings.mode {
+            Mode::Human => None,
+            Mode::Quiet => Some(OutputFormat::Json),
+            Mode::Porcelain => Some(OutputFormat::Porcelain),
+        })
+        .or(settings.default_format)
+        .unwrap_or(fallback)
+}
+
+/// A human-oriented note on stderr; silent under `--quiet` and `--porcelain`.
+macro_rules! note {
+    ($($arg:tt)*) => {
+        if $crate::output::mode() == $crate::output::Mode::Human {
+            eprintln!($($arg)*);
+        }
+    };
+}
+pub(crate) use note;
+
+/// Text the user has to act on (device codes, prompts). It goes to stdout
+/// normally and to stderr when stdout is reserved for the result.
+pub fn prompt(text: &str) {
+    if mode() == Mode::Human {
+        println!("{text}");
+    } else {
+        eprintln!("{text}");
+    }
+}
+
+/// Reports a command that has no other output: `human` as is, or `value`
+/// when the caller asked for machine output.
+pub fn done<T: Serialize>(human: &str, value: &T) {
+    match mode() {
+        Mode::Human => println!("{human}"),
+        Mode::Quiet => print_json(value),
+        Mode::Porcelain => print_porcelain("", value),
+    }
+}
+
+/// JSON, or porcelain lines under `--porcelain`, for results that have no
+/// table form.
+pub fn emit_json<T: Serialize>(value: &T) {
+    match format(None, OutputFormat::Json) {
+        OutputFormat::Porcelain => print_porcelain("", value),
+        _ => print_json(value),
+    }
+}
+
+/// One compact JSON line, or porcelain lines under `--porcelain`.
+pub fn emit_line<T: Serialize>(value: &T) {
+    match format(None, OutputFormat::Json) {
+        OutputFormat::Porcelain => print_porcelain("", value),
+        _ => println!(
+            "{}",
+            serde_json::to_string(value).expect("serializable output")
+        ),
+    }
+}
+
+pub fn print_json<T: Serialize>(value: &T) {
+    let json = serde_json::to_string_pretty(value).expect("serializable output");
+    println!("{json}");
+}
+
+/// Prints `value` as porcelain lines, each key prefixed with `prefix.`
+/// unless `prefix` is empty.
+pub fn print_porcelain<T: Serialize>(prefix: &str, value: &T) {
+    let value = serde_json::to_value(value).expect("serializable output");
+    let mut out = std::io::stdout().lock();
+    for line in porcelain_lines(prefix, &value) {
+        let _ = writeln!(out, "{line}");
+    }
+    let _ = out.flush();
+}
+
+/// `key<TAB>value` lines for every scalar in `value`, object keys sorted.
+/// Strings are printed raw with `\`, tab and newline escaped; `null` is an
+/// empty value.
+fn porcelain_lines(prefix: &str, value: &Value) -> Vec<String> {
+    let mut lines = Vec::new();
+    flatten(prefix.to_string(), value, &mut lines);
+    lines
+}
+
+fn flatten(key: String, value: &Value, lines: &mut Vec<String>) {
+    let child = |name: &str| {
+        if key.is_empty() {
+            name.to_string()
+        } else {
+            format!("{key}.{name}")
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (name, value) in map {
+                flatten(child(name), value, lines);
+            }
+        }
+        Value::Array(items) => {
+            for (idx, value) in items.iter().enumerate() {
+                flatten(child(&idx.to_string()), value, lines);
+            }
+        }
+        Value::Null => lines.push(format!("{key}\t")),
+        Value::String(text) => lines.push(format!(
+            "{key}\t{}",
+            text.replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        )),
+        Value::Bool(_) | Value::Number(_) => lines.push(format!("{key}\t{value}")),
+    }
+}
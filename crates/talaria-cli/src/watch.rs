@@ -0,0 +1,234 @@
+//! `watch`: uploads (and optionally enriches) the images a tethering or
+//! photo app drops into a folder, one product per filename prefix or
+//! subfolder, then moves them to the archive folder.
+//!
+//! Enriched products report the tokens and credits they used; credits are
+//! the change in the org's usage counter around each product, so other
+//! work on the org at the same time is counted too.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use talaria_core::HermesClient;
+use talaria_core::config::Config;
+use talaria_core::cost::{self, ItemCost, STAGE_INGEST, StageCost};
+use talaria_core::image_store::ImageStore;
+use talaria_core::images;
+use talaria_core::models::{HsufEnrichRequest, Product};
+use talaria_core::watch::{Batch, FolderWatch, GroupBy};
+
+use crate::emit_progress;
+use crate::exit::Failure;
+use crate::output::{self, note};
+
+pub struct Options<'a> {
+    pub dir: &'a Path,
+    pub group_by: GroupBy,
+    pub archive: Option<&'a Path>,
+    pub enrich: bool,
+    pub interval: Duration,
+    pub settle: Duration,
+    /// Process what has settled, then exit instead of watching.
+    pub once: bool,
+    /// With `once`: print the products most expensive first, after all
+    /// are done.
+    pub sort_by_cost: bool,
+}
+
+/// One processed product, printed as a JSON line.
+#[derive(Debug, Serialize)]
+struct Processed {
+    sku: String,
+    images: Vec<String>,
+    archived: Vec<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product: Option<Product>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost: Option<ItemCost>,
+    /// Who ran the station, for per-person throughput.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    operator: Option<String>,
+}
+
+/// The org's credit counter, read around each product.
+struct CreditMeter {
+    last: Option<i64>,
+}
+
+impl CreditMeter {
+    async fn start(client: &HermesClient) -> Self {
+        Self {
+            last: Self::read(client).await,
+        }
+    }
+
+    async fn read(client: &HermesClient) -> Option<i64> {
+        match client.usage(None, None, None).await {
+            Ok(summaries) => Some(cost::credits_consumed(&summaries)),
+            Err(err) => {
+                tracing::debug!(error = %err, "usage unavailable; credits not reported");
+                None
+            }
+        }
+    }
+
+    /// Credits used since the previous reading.
+    async fn used(&mut self, client: &HermesClient) -> Option<i64> {
+        let now = Self::read(client).await;
+        // The counter restarts with each billing window.
+        let used = self.last.zip(now).map(|(last, now)| (now - last).max(0));
+        self.last = now.or(self.last);
+        used
+    }
+}
+
+pub async fn run(
+    opts: &Options<'_>,
+    client: &HermesClient,
+    store: &ImageStore,
+    config: &Config,
+) -> Result<()> {
+    // With --once, files already sitting in the folder count as settled.
+    let settle = if opts.once {
+        Duration::ZERO
+    } else {
+        opts.settle
+    };
+    let mut watch = FolderWatch::new(opts.dir, opts.archive, opts.group_by, settle);
+    note!(
+        "watching {} (archive: {})",
+        opts.dir.display(),
+        watch.archive_dir().display()
+    );
+    let (mut done, mut failed) = (0, 0);
+    let mut meter = if opts.enrich {
+        Some(CreditMeter::start(client).await)
+    } else {
+        None
+    };
+    let mut totals = ItemCost::default();
+    let mut report = Vec::new();
+    loop {
+        let (batches, skipped) = watch
+            .poll(Instant::now())
+            .with_context(|| format!("scan {}", opts.dir.display()))?;
+        for file in skipped {
+            emit_progress(
+                "watch",
+                "skipped",
+                json!({ "file": file.path, "reason": file.reason }),
+            );
+        }
+        for batch in batches {
+            emit_progress(
+                "watch",
+                "started",
+                json!({ "sku": batch.key, "files": batch.files }),
+            );
+            match process(&mut watch, &batch, meter.as_mut(), client, store, config).await {
+                Ok(processed) => {
+                    done += 1;
+                    if let Some(cost) = &processed.cost {
+                        totals.add(cost);
+                    }
+                    if opts.sort_by_cost {
+                        report.push(processed);
+                    } else {
+                        output::emit_line(&processed);
+                    }
+                }
+                Err(err) => {
+                    failed += 1;
+                    emit_progress(
+                        "watch",
+                        "failed",
+                        json!({ "sku": batch.key, "error": format!("{err:#}") }),
+                    );
+                    watch.hold(&batch);
+                }
+            }
+        }
+        if opts.once {
+            break;
+        }
+        tokio::time::sleep(opts.interval).await;
+    }
+    cost::sort_by_cost(&mut report, |processed| processed.cost.as_ref());
+    for processed in &report {
+        output::emit_line(processed);
+    }
+    note!("{done} product(s) processed, {failed} failed");
+    if opts.enrich {
+        emit_progress(
+            "watch",
+            "totals",
+            json!({ "products": done, "cost": totals, "operator": config.operator_name() }),
+        );
+        let credits = totals
+            .credits()
+            .map_or_else(|| "unknown".to_string(), |c| c.to_string());
+        note!("{} token(s), {credits} credit(s)", totals.tokens());
+    }
+    if failed > 0 {
+        return Err(Failure::Partial {
+            what: "products",
+            failed,
+            total: done + failed,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Uploads and archives one product; with a `meter`, also enriches it.
+async fn process(
+    watch: &mut FolderWatch,
+    batch: &Batch,
+    meter: Option<&mut CreditMeter>,
+    client: &HermesClient,
+    store: &ImageStore,
+    config: &Config,
+) -> Result<Processed> {
+    let images: Vec<String> = images::upload_files(&batch.files, store)
+        .await?
+        .into_iter()
+        .map(|uploaded| uploaded.url)
+        .collect();
+    let (product, cost) = match meter {
+        Some(meter) => {
+            let req = HsufEnrichRequest {
+                images: images.clone(),
+                sku: Some(batch.key.clone()),
+                context_text: None,
+                prompt_rules: config.prompt_rules.clone(),
+                llm_ingest: config.llm_ingest.clone(),
+            };
+            let resp = client.hsuf_enrich(&req, true).await?;
+            let mut cost = ItemCost::default();
+            cost.record(
+                STAGE_INGEST,
+                StageCost {
+                    credits: meter.used(client).await,
+                    ..resp
+                        .usage
+                        .as_ref()
+                        .map(StageCost::from_usage)
+                        .unwrap_or_default()
+                },
+            );
+            (Some(resp.product), Some(cost))
+        }
+        None => (None, None),
+    };
+    let archived = watch.archive(batch)?;
+    Ok(Processed {
+        sku: batch.key.clone(),
+        images,
+        archived,
+        product,
+        cost,
+        operator: config.operator_name(),
+    })
+}
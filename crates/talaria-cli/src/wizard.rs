@@ -0,0 +1,283 @@
+//! `listings continue --interactive`: shows what a prior listing run detected
+//! (category, condition, images) and asks for each override in turn, so
+//! nobody has to hand-write `CategorySelectionInput` JSON or copy image URLs.
+//!
+//! Answers are read line by line, so the wizard can also be scripted by
+//! piping them in.
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+use talaria_core::models::{ListingResponse, MarketplaceId, PublicPipelineOverrides};
+
+use crate::exit::invalid;
+use crate::workspace::ListingConditions;
+
+/// What a prior run settled on.
+#[derive(Debug, Default)]
+pub struct Detected {
+    /// Category id and label.
+    pub category: Option<(String, String)>,
+    pub conditions: ListingConditions,
+    pub images: Vec<String>,
+}
+
+impl Detected {
+    /// From the stage outputs of a listing response, looked up the way the
+    /// TUI does: the `category` and `prepare_conditions` stages, then the
+    /// `listing` / `build_listing` stage, then the `push_inventory` request.
+    pub fn from_response(resp: &ListingResponse) -> Self {
+        let stage = |name: &str| {
+            resp.stages
+                .iter()
+                .find(|stage| stage.name == name)
+                .map(|stage| &stage.output)
+        };
+        let build = stage("listing").or_else(|| stage("build_listing"));
+        let category = stage("category")
+            .and_then(|output| output.get("selected"))
+            .and_then(|selected| text(&selected["id"]).map(|id| (id, text(&selected["label"]))))
+            .or_else(|| {
+                let build = build?;
+                text(&build["category_id"]).map(|id| (id, text(&build["category_label"])))
+            })
+            .map(|(id, label)| {
+                let label = label.unwrap_or_else(|| id.clone());
+                (id, label)
+            });
+        let conditions = stage("prepare_conditions");
+        let field = |name: &str| conditions.map_or(&Value::Null, |output| &output[name]);
+        let images = build
+            .and_then(|build| build.get("images").or_else(|| build.get("media")))
+            .or_else(|| {
+                stage("push_inventory")
+                    .and_then(|output| output.pointer("/inventory_request/product/image_urls"))
+            })
+            .map(urls)
+            .unwrap_or_default();
+
+        Self {
+            category,
+            conditions: ListingConditions {
+                allowed: urls(field("allowed")),
+                allowed_ids: ids(field("allowed_condition_ids")),
+                current: build
+                    .and_then(|build| text(&build["condition"]))
+                    .or_else(|| text(field("default"))),
+                current_id: field("default_condition_id")
+                    .as_i64()
+                    .and_then(|id| i32::try_from(id).ok()),
+            },
+            images,
+        }
+    }
+
+    /// From a listing kept in a local manifest (`listings.<marketplace>`),
+    /// as recorded by the TUI after a run.
+    pub fn from_manifest(listing: &Value, conditions: ListingConditions) -> Self {
+        Self {
+            category: text(&listing["category_id"]).map(|id| {
+                let label = text(&listing["category_label"]).unwrap_or_else(|| id.clone());
+                (id, label)
+            }),
+            conditions,
+            images: urls(&listing["images"]),
+        }
+    }
+}
+
+/// How the wizard ended.
+#[derive(Debug)]
+pub enum Outcome {
+    /// Submit with these overrides; `None` keeps everything the run detected.
+    Submit(Option<PublicPipelineOverrides>),
+    Declined,
+}
+
+/// Prompts on `output` and reads answers from `input`. A blank answer keeps
+/// the detected value; an answer that does not parse is asked again.
+pub fn run(
+    detected: &Detected,
+    marketplace: &MarketplaceId,
+    input: impl BufRead,
+    output: impl Write,
+) -> Result<Outcome> {
+    let mut term = Prompter { input, output };
+    term.say("Prior run detected:")?;
+    term.say(format!(
+        "  category   {}",
+        detected
+            .category
+            .as_ref()
+            .map_or("-".into(), |(id, label)| format!("{id} {label}"))
+    ))?;
+    let conditions = &detected.conditions;
+    term.say(format!(
+        "  condition  {}",
+        condition_text(conditions.current.as_deref(), conditions.current_id)
+    ))?;
+    term.say(format!("  images     {}", detected.images.len()))?;
+    for (idx, url) in detected.images.iter().enumerate() {
+        term.say(format!("    {}. {url}", idx + 1))?;
+    }
+
+    let kept_category = detected
+        .category
+        .as_ref()
+        .map_or("Enter for none".into(), |(id, _)| {
+            format!("Enter keeps {id}")
+        });
+    let category_id = term.ask(&format!("Category id ({kept_category})"))?;
+    let category = if category_id.is_empty() {
+        None
+    } else {
+        let label = term.ask(&format!("Category label (Enter uses {category_id})"))?;
+        let label = if label.is_empty() {
+            &category_id
+        } else {
+            &label
+        };
+        Some(crate::category_override(&category_id, label, marketplace)?)
+    };
+
+    for (idx, label) in conditions.allowed.iter().enumerate() {
+        term.say(format!("    {}. {label}", idx + 1))?;
+    }
+    let (condition, condition_id) = loop {
+        let answer = term.ask(&format!(
+            "Condition, number or label (Enter keeps {})",
+            condition_text(conditions.current.as_deref(), conditions.current_id)
+        ))?;
+        if answer.is_empty() {
+            break (None, None);
+        }
+        let label = match answer.parse::<usize>() {
+            Ok(n) if (1..=conditions.allowed.len()).contains(&n) => &conditions.allowed[n - 1],
+            _ => &answer,
+        };
+        match conditions.resolve(Some(label), None) {
+            Ok(resolved) => break resolved,
+            Err(err) => term.say(format!("  {err}"))?,
+        }
+    };
+
+    let mut images = if detected.images.is_empty() {
+        Vec::new()
+    } else {
+        loop {
+            let answer = term.ask("Images to keep, in order, e.g. 2,1,3 (Enter keeps all)")?;
+            match pick_images(&detected.images, &answer) {
+                Ok(images) => break images,
+                Err(err) => term.say(format!("  {err}"))?,
+            }
+        }
+    };
+    let extra = term.ask("More image URLs, space separated (Enter for none)")?;
+    images.extend(extra.split_whitespace().map(str::to_string));
+    let resolved_images = (images != detected.images).then_some(images);
+
+    let overrides = (category.is_some()
+        || condition.is_some()
+        || condition_id.is_some()
+        || resolved_images.is_some())
+    .then_some(PublicPipelineOverrides {
+        category,
+        condition,
+        condition_id,
+        product: None,
+        resolved_images,
+    });
+    match &overrides {
+        Some(overrides) => {
+            term.say("Overrides:")?;
+            term.say(serde_json::to_string_pretty(overrides)?)?;
+        }
+        None => term.say("No overrides; the run continues as detected.")?,
+    }
+    let answer = term.ask("Submit? [Y/n]")?;
+    Ok(
+        if matches!(answer.to_ascii_lowercase().as_str(), "" | "y" | "yes") {
+            Outcome::Submit(overrides)
+        } else {
+            Outcome::Declined
+        },
+    )
+}
+
+/// Detected images picked by 1-based position; blank keeps them all.
+fn pick_images(images: &[String], answer: &str) -> Result<Vec<String>> {
+    if answer.is_empty() {
+        return Ok(images.to_vec());
+    }
+    answer
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|idx| images.get(idx))
+                .cloned()
+                .ok_or_else(|| anyhow!("'{part}' is not an image number 1-{}", images.len()))
+        })
+        .collect()
+}
+
+fn condition_text(label: Option<&str>, id: Option<i32>) -> String {
+    match (label, id) {
+        (Some(label), Some(id)) => format!("{label} ({id})"),
+        (Some(label), None) => label.to_string(),
+        (None, Some(id)) => id.to_string(),
+        (None, None) => "none".to_string(),
+    }
+}
+
+struct Prompter<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    fn say(&mut self, text: impl AsRef<str>) -> Result<()> {
+        writeln!(self.output, "{}", text.as_ref())?;
+        Ok(())
+    }
+
+    fn ask(&mut self, prompt: &str) -> Result<String> {
+        write!(self.output, "{prompt}: ")?;
+        self.output.flush()?;
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(invalid!("input ended before the wizard finished"));
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+fn text(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// Strings from a list of strings or of `{ "url": ... }` objects.
+fn urls(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.as_str().or_else(|| item["url"].as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+fn ids(value: &Value) -> Vec<i32> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|id| id.as_i64().and_then(|id| i32::try_from(id).ok()))
+        .collect()
+}
@@ -0,0 +1,265 @@
+//! Read/write access to the local product workspace shared with the TUI
+//! (`TALARIA_CAPTURES_DIR`, same layout as the TUI storage module).
+//!
+//! Manifests are handled as raw JSON so fields owned by the TUI round-trip
+//! untouched when the CLI only updates a single key.
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use talaria_core::stats::SessionSample;
+
+pub struct LocalProduct {
+    pub product_id: String,
+    pub sku_alias: String,
+    pub path: PathBuf,
+    pub manifest: Value,
+}
+
+/// Loads every readable product manifest under `base/products`.
+pub fn load_products(base: &Path) -> Result<Vec<LocalProduct>> {
+    let products = base.join("products");
+    read_products(&products).with_context(|| format!("read products dir {}", products.display()))
+}
+
+/// Reads `<dir>/*/product.json`, skipping entries without a readable manifest.
+fn read_products(dir: &Path) -> std::io::Result<Vec<LocalProduct>> {
+    let entries = fs::read_dir(dir)?;
+    let mut out = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path().join("product.json");
+        let Ok(bytes) = fs::read(&path) else {
+            continue;
+        };
+        let Ok(manifest) = serde_json::from_slice::<Value>(&bytes) else {
+            continue;
+        };
+        out.push(LocalProduct {
+            product_id: manifest["product_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            sku_alias: manifest["sku_alias"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            path,
+            manifest,
+        });
+    }
+    Ok(out)
+}
+
+/// Reads every capture session under `base/sessions`; a workspace without
+/// sessions has none.
+pub fn load_sessions(base: &Path) -> Result<Vec<SessionSample>> {
+    let sessions = base.join("sessions");
+    let entries = match fs::read_dir(&sessions) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("read sessions dir {}", sessions.display()));
+        }
+    };
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| fs::read(entry.path().join("session.json")).ok())
+        .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+        .collect())
+}
+
+/// Finds a local product by its id or sku alias.
+pub fn find_product(base: &Path, id_or_sku: &str) -> Result<LocalProduct> {
+    load_products(base)?
+        .into_iter()
+        .find(|p| p.product_id == id_or_sku || p.sku_alias == id_or_sku)
+        .ok_or_else(|| {
+            anyhow!(
+                "no local product matches '{id_or_sku}' in {}",
+                base.join("products").display()
+            )
+        })
+}
+
+/// Every string value found in the local product manifests (URLs, paths,
+/// ids), trashed products included; used to tell whether an uploaded object
+/// is still referenced. A missing workspace yields an empty list.
+pub fn manifest_strings(base: &Path) -> Result<Vec<String>> {
+    if !base.join("products").exists() {
+        return Ok(Vec::new());
+    }
+    fn collect(value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::String(s) => out.push(s.clone()),
+            Value::Array(items) => items.iter().for_each(|v| collect(v, out)),
+            Value::Object(map) => map.values().for_each(|v| collect(v, out)),
+            _ => {}
+        }
+    }
+    let mut products = load_products(base)?;
+    // The TUI trash (`products/_trash/<entry>/`) can still be restored, so
+    // its uploads stay referenced until the trash is purged.
+    if let Ok(trashed) = read_products(&base.join("products").join("_trash")) {
+        products.extend(trashed);
+    }
+    let mut out = Vec::new();
+    for product in products {
+        collect(&product.manifest, &mut out);
+    }
+    Ok(out)
+}
+
+impl LocalProduct {
+    pub fn set_field(&mut self, key: &str, value: Value) -> Result<()> {
+        let obj = self
+            .manifest
+            .as_object_mut()
+            .context("product manifest is not a JSON object")?;
+        obj.insert(key.to_string(), value);
+        obj.insert(
+            "updated_at".to_string(),
+            Value::String(chrono::Local::now().to_rfc3339()),
+        );
+        Ok(())
+    }
+
+    /// Allowed condition labels/ids recorded from the last listing run on
+    /// `marketplace`, paired by position, plus the currently chosen condition.
+    pub fn conditions(&self, marketplace: &str) -> ListingConditions {
+        let listing = &self.manifest["listings"][marketplace];
+        let labels = listing["allowed_conditions"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let ids = listing["allowed_condition_ids"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_i64().and_then(|id| i32::try_from(id).ok()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        ListingConditions {
+            allowed: labels,
+            allowed_ids: ids,
+            current: listing["condition"].as_str().map(str::to_string),
+            current_id: listing["condition_id"]
+                .as_i64()
+                .and_then(|id| i32::try_from(id).ok()),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let tmp = self.path.with_extension("json.tmp");
+        let bytes = serde_json::to_vec_pretty(&self.manifest).context("serialize manifest")?;
+        fs::write(&tmp, bytes).context("write temp manifest")?;
+        fs::rename(&tmp, &self.path).context("rename temp manifest")?;
+        Ok(())
+    }
+}
+
+/// Records `count` units of a live listing as sold: `quantity` (default 1)
+/// goes down, `quantity_sold` up, and the status becomes `sold` once none
+/// are left.
+pub fn mark_listing_sold(listing: &mut Value, count: i64) -> Result<()> {
+    let obj = listing
+        .as_object_mut()
+        .context("listing is not a JSON object")?;
+    let status = obj
+        .get("status")
+        .and_then(Value::as_str)
+        .unwrap_or("draft")
+        .to_ascii_lowercase();
+    if !matches!(status.as_str(), "published" | "active") {
+        return Err(anyhow!(
+            "listing is {status}; only published listings can be marked sold"
+        ));
+    }
+    let left = obj.get("quantity").and_then(Value::as_i64).unwrap_or(1);
+    if count < 1 || count > left {
+        return Err(anyhow!("cannot mark {count} sold; {left} left"));
+    }
+    let sold = obj
+        .get("quantity_sold")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+    obj.insert("quantity".into(), (left - count).into());
+    obj.insert("quantity_sold".into(), (sold + count).into());
+    if left == count {
+        obj.insert("status".into(), "sold".into());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ListingConditions {
+    pub allowed: Vec<String>,
+    pub allowed_ids: Vec<i32>,
+    pub current: Option<String>,
+    pub current_id: Option<i32>,
+}
+
+impl ListingConditions {
+    /// Validates a requested condition against the allowed list, filling in
+    /// the missing half of the label/id pair when it can be inferred.
+    pub fn resolve(
+        &self,
+        label: Option<&str>,
+        id: Option<i32>,
+    ) -> Result<(Option<String>, Option<i32>)> {
+        if self.allowed.is_empty() && self.allowed_ids.is_empty() {
+            return Ok((label.map(str::to_string), id));
+        }
+        let by_label = label.map(|wanted| {
+            self.allowed
+                .iter()
+                .position(|allowed| allowed.eq_ignore_ascii_case(wanted))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "condition '{wanted}' is not allowed; choose one of: {}",
+                        self.allowed.join(", ")
+                    )
+                })
+        });
+        let by_id = id.map(|wanted| {
+            self.allowed_ids
+                .iter()
+                .position(|allowed| *allowed == wanted)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "condition id {wanted} is not allowed; choose one of: {}",
+                        self.allowed_ids
+                            .iter()
+                            .map(i32::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })
+        });
+        let by_label = by_label.transpose()?;
+        let by_id = by_id.transpose()?;
+        if let (Some(a), Some(b)) = (by_label, by_id)
+            && a != b
+            && self.allowed.len() == self.allowed_ids.len()
+        {
+            return Err(anyhow!(
+                "condition '{}' does not match condition id {}",
+                self.allowed[a],
+                self.allowed_ids[b]
+            ));
+        }
+        let paired = self.allowed.len() == self.allowed_ids.len();
+        let label = by_label
+            .or(by_id.filter(|_| paired))
+            .map(|idx| self.allowed[idx].clone());
+        let id = by_id
+            .or(by_label.filter(|_| paired))
+            .map(|idx| self.allowed_ids[idx]);
+        Ok((label, id))
+    }
+}
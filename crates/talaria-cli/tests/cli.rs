@@ -0,0 +1,2297 @@
+//! Runs the `talaria-cli` binary against a mock Hermes server with an isolated
+//! config/data directory.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn temp_home(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "talaria-cli-test-{name}-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    std::fs::create_dir_all(&dir).expect("temp home");
+    dir
+}
+
+fn talaria(server: &MockServer, home: &Path, api_key: Option<&str>, args: &[&str]) -> Output {
+    talaria_command(server, home, api_key, args)
+        .output()
+        .expect("run talaria-cli")
+}
+
+/// `talaria` with `stdin` piped in.
+fn talaria_stdin(
+    server: &MockServer,
+    home: &Path,
+    api_key: Option<&str>,
+    args: &[&str],
+    stdin: &str,
+) -> Output {
+    let mut child = talaria_command(server, home, api_key, args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run talaria-cli");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(stdin.as_bytes())
+        .expect("write stdin");
+    child.wait_with_output().expect("run talaria-cli")
+}
+
+fn talaria_command(
+    server: &MockServer,
+    home: &Path,
+    api_key: Option<&str>,
+    args: &[&str],
+) -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_talaria-cli"));
+    cmd.args(args)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env("HERMES_BASE_URL", server.uri())
+        .env("TALARIA_LOCALE", "en");
+    for var in [
+        "HERMES_API_KEY",
+        "HERMES_ORG_ID",
+        "SUPABASE_URL",
+        "SUPABASE_SERVICE_ROLE_KEY",
+        "TALARIA_CAPTURES_DIR",
+    ] {
+        cmd.env_remove(var);
+    }
+    if let Some(key) = api_key {
+        cmd.env("HERMES_API_KEY", key);
+    }
+    cmd
+}
+
+fn stdout_json(output: &Output) -> Value {
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("json stdout")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn health_and_job_status_print_json() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"status": "ok", "service": "hermes", "version": "1.2.3"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_1"))
+        .and(header("x-hermes-key", "sk_test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "job_1",
+            "state": "failed",
+            "error": "no images",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:01:00Z",
+            "request": {
+                "fulfillment_policy_id": "1",
+                "payment_policy_id": "2",
+                "return_policy_id": "3",
+                "merchant_location_key": "home",
+                "images_source": "https://img.example/a.jpg",
+            },
+        })))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("health");
+    let health = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["health", "--format", "json"],
+    ));
+    assert_eq!(health["version"], "1.2.3");
+
+    let job = stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["jobs", "get", "--id", "job_1"],
+    ));
+    assert_eq!(job["state"], "failed");
+    assert_eq!(job["error"], "no images");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn config_check_api_reports_what_the_client_does_not_know() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/openapi.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "info": {"version": "3.0.0"},
+            "paths": {
+                "/health": {"get": {}},
+                "/v1/products/{id}": {"get": {}, "patch": {}, "delete": {}},
+                "/v1/bundles": {"post": {}},
+            },
+            "components": {"schemas": {
+                "HealthResponse": {"properties": {
+                    "status": {}, "service": {}, "git_sha": {}, "version": {}, "region": {}
+                }},
+            }},
+        })))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("check-api");
+    let output = talaria(&server, &home, None, &["config", "check-api"]);
+    let drift = stdout_json(&output);
+    assert_eq!(drift["server_version"], "3.0.0");
+    assert_eq!(drift["unknown_endpoints"][0]["path"], "v1/bundles");
+    assert_eq!(drift["unknown_fields"]["HealthResponse"], json!(["region"]));
+    assert!(
+        drift["missing_endpoints"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|endpoint| endpoint["path"] == "listings")
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("upgrade talaria"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn api_errors_exit_nonzero_with_message() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/nope"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({"error": "job not found"})))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("errors");
+    let output = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["jobs", "get", "--id", "nope"],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("job not found"), "stderr: {stderr}");
+
+    let output = talaria(&server, &home, None, &["jobs", "get", "--id", "nope"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing API key"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn exit_codes_distinguish_auth_validation_and_rate_limits() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/nope"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({"error": "job not found"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/denied"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({"error": "bad key"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/busy"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "0")
+                .set_body_json(json!({"error": "slow down"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"status": "ok", "service": "hermes"})),
+        )
+        .mount(&server)
+        .await;
+
+    let home = temp_home("exit-codes");
+    let code =
+        |api_key: Option<&str>, args: &[&str]| talaria(&server, &home, api_key, args).status.code();
+    assert_eq!(code(Some("sk_test"), &["health"]), Some(0));
+    assert_eq!(code(None, &["jobs", "get", "--id", "nope"]), Some(2));
+    assert_eq!(
+        code(Some("sk_test"), &["jobs", "get", "--id", "denied"]),
+        Some(2)
+    );
+    assert_eq!(
+        code(Some("sk_test"), &["jobs", "get", "--id", "nope"]),
+        Some(3)
+    );
+    assert_eq!(code(Some("sk_test"), &["jobs", "get", "--bogus"]), Some(3));
+    assert_eq!(code(Some("sk_test"), &["--help"]), Some(0));
+    assert_eq!(
+        code(Some("sk_test"), &["jobs", "get", "--id", "busy"]),
+        Some(4)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn capture_only_mode_refuses_publish_and_credit_commands() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("capture-only");
+    let config_dir = home.join("config/talaria");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.toml"), "mode = \"capture-only\"\n").unwrap();
+
+    for args in [
+        &["hsuf-enrich", "--images", "https://example.com/a.jpg"][..],
+        &["listings", "draft", "--product", "SKU-1", "--publish"],
+        &["jobs", "retry", "--id", "job_1"],
+    ] {
+        let output = talaria(&server, &home, Some("sk_test"), args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("not allowed on a capture-only station"),
+            "{stderr}"
+        );
+    }
+    // Reading and local curation still work.
+    let products = talaria(&server, &home, None, &["catalog", "export"]);
+    assert!(products.status.success(), "{products:?}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn auth_login_polls_and_saves_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/auth/device/start"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "device_code": "dev_1",
+            "user_code": "ABCD-EFGH",
+            "verification_uri": "https://hermes.example/device",
+            "verification_uri_complete": "https://hermes.example/device?code=ABCD-EFGH",
+            "expires_in": 60,
+            "interval": 1,
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/auth/device/poll"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"status": "authorized", "access_token": "at_1"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/user/api-keys"))
+        .and(header("authorization", "Bearer at_1"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": "key_1",
+            "name": "Talaria test",
+            "prefix": "sk_live_ab",
+            "secret": "sk_live_abcdef",
+        })))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("login");
+    let output = talaria(&server, &home, None, &["auth", "login", "--no-browser"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("ABCD-EFGH"));
+    let saved = std::fs::read_to_string(home.join("config/talaria/config.toml")).unwrap();
+    assert!(saved.contains("sk_live_abcdef"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn record_then_replay_health() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"status": "ok", "service": "hermes"})),
+        )
+        .mount(&server)
+        .await;
+
+    let home = temp_home("vcr");
+    let cassette = home.join("health.json");
+    let cassette_arg = cassette.to_str().unwrap();
+    let recorded = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["health", "--record", cassette_arg],
+    ));
+    let replayed = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["--replay", cassette_arg, "health"],
+    ));
+    assert_eq!(recorded, replayed);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn simulate_capture_and_upload_without_camera_or_supabase() {
+    let server = MockServer::start().await;
+    let home = temp_home("simulate");
+    let out_dir = home.join("captures");
+    let args = [
+        "--simulate",
+        "images",
+        "capture",
+        "--count",
+        "2",
+        "--upload",
+        "--out-dir",
+        out_dir.to_str().unwrap(),
+    ];
+    let first = stdout_json(&talaria(&server, &home, None, &args));
+    let second = stdout_json(&talaria(&server, &home, None, &args));
+    assert_eq!(first, second);
+    let urls = first.as_array().unwrap();
+    assert_eq!(urls.len(), 2);
+    assert!(
+        urls[0]
+            .as_str()
+            .unwrap()
+            .starts_with("https://media.simulated.talaria.invalid/")
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn product_context_set_updates_hermes_and_manifest() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/products/prod_1"))
+        .and(body_partial_json(json!({"context_text": "Scratch on lid"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "prod_1",
+            "sku_alias": "SKU-1",
+            "context_text": "Scratch on lid",
+            "listings_json": {},
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-02T00:00:00Z",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("context");
+    let product_dir = home.join("data/talaria/captures/products/prod_1");
+    std::fs::create_dir_all(&product_dir).unwrap();
+    let manifest = product_dir.join("product.json");
+    std::fs::write(
+        &manifest,
+        json!({"product_id": "prod_1", "sku_alias": "SKU-1", "images": []}).to_string(),
+    )
+    .unwrap();
+    let notes = home.join("notes.md");
+    std::fs::write(&notes, "Scratch on lid\n").unwrap();
+
+    let set = stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[
+            "products",
+            "context",
+            "set",
+            "--id",
+            "SKU-1",
+            "--from-file",
+            notes.to_str().unwrap(),
+        ],
+    ));
+    assert_eq!(set["sources"], json!(["hermes", "local"]));
+    let saved: Value = serde_json::from_slice(&std::fs::read(&manifest).unwrap()).unwrap();
+    assert_eq!(saved["context_text"], "Scratch on lid");
+    assert_eq!(saved["images"], json!([]));
+
+    let shown = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["products", "context", "show", "--id", "prod_1"],
+    ));
+    assert_eq!(shown["context_text"], "Scratch on lid");
+    assert_eq!(shown["sources"], json!(["local"]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn products_diff_reports_fields_that_differ_from_hermes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/products/prod_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "prod_1",
+            "sku_alias": "SKU-1",
+            "display_name": "Brass lamp (remote)",
+            "context_text": "",
+            "listings_json": {"EBAY_US": {"title": "Brass lamp", "price": 30}},
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-02T00:00:00Z",
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("diff");
+    let product_dir = home.join("data/talaria/captures/products/prod_1");
+    std::fs::create_dir_all(&product_dir).unwrap();
+    std::fs::write(
+        product_dir.join("product.json"),
+        json!({
+            "product_id": "prod_1",
+            "sku_alias": "SKU-1",
+            "display_name": "Brass lamp",
+            "images": [],
+            "listings": {"EBAY_US": {"title": "Brass lamp", "price": 25.0}},
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let diff = stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["products", "diff", "--id", "SKU-1", "--format", "json"],
+    ));
+    assert_eq!(diff["in_sync"], false);
+    assert_eq!(
+        diff["differences"],
+        json!([
+            {"path": "display_name", "local": "Brass lamp", "remote": "Brass lamp (remote)"},
+            {"path": "listings_json.EBAY_US.price", "local": 25.0, "remote": 30},
+        ])
+    );
+
+    let table = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["products", "diff", "--id", "prod_1"],
+    );
+    let text = String::from_utf8_lossy(&table.stdout);
+    assert!(text.contains("2 difference(s)"), "{text}");
+    assert!(text.contains("  - 25.0\n  + 30\n"), "{text}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn listings_draft_fills_package_from_template_and_flags() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/listings/publish-draft"))
+        .and(body_partial_json(json!({
+            "sku": "SKU-1",
+            "merchant_location_key": "loc-1",
+            "dry_run": true,
+            "listing": {
+                "title": "Brass lamp",
+                "package": {
+                    "weight": {"value": 24, "unit": "OUNCE"},
+                    "dimensions": {"length": 10.0, "width": 8.0, "height": 4.0, "unit": "INCH"},
+                },
+            },
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"listing_id": "lst_1", "stages": []})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("draft");
+    let config_dir = home.join("config/talaria");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "ebay_merchant_location_key = \"loc-1\"\nebay_fulfillment_policy_id = \"f\"\n\
+         ebay_payment_policy_id = \"p\"\nebay_return_policy_id = \"r\"\n\n\
+         [[package_templates]]\nname = \"small-box\"\nweight = \"12oz\"\ndims = \"10x8x4in\"\n",
+    )
+    .unwrap();
+    let product_dir = home.join("data/talaria/captures/products/prod_1");
+    std::fs::create_dir_all(&product_dir).unwrap();
+    std::fs::write(
+        product_dir.join("product.json"),
+        json!({
+            "product_id": "prod_1",
+            "sku_alias": "SKU-1",
+            "listings": {"EBAY_US": {
+                "title": "Brass lamp",
+                "description": "A lamp.",
+                "price": 25.0,
+                "currency": "USD",
+                "category_id": "112581",
+                "condition": "USED_GOOD",
+                "condition_id": 3000,
+                "images": ["https://cdn.example/a.jpg"],
+            }},
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let draft = |extra: &[&str]| {
+        let mut args = vec!["listings", "draft", "--product", "SKU-1", "--dry-run"];
+        args.extend_from_slice(extra);
+        talaria(&server, &home, Some("sk_test"), &args)
+    };
+    let out = stdout_json(&draft(&[
+        "--package-template",
+        "small-box",
+        "--weight",
+        "1.5lb",
+    ]));
+    assert_eq!(out["listing_id"], "lst_1");
+
+    let bad = draft(&["--weight", "12 stone"]);
+    assert!(!bad.status.success());
+    assert!(String::from_utf8_lossy(&bad.stderr).contains("unknown weight unit 'stone'"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn catalog_export_normalizes_products_and_filters_by_since() {
+    let server = MockServer::start().await;
+    let home = temp_home("catalog");
+    let products = home.join("data/talaria/captures/products");
+    for (id, updated, listings) in [
+        ("prod_old", "2026-01-01T10:00:00Z", json!({})),
+        (
+            "prod_new",
+            "2026-03-05T10:00:00Z",
+            json!({"EBAY_US": {
+                "title": "Brass lamp",
+                "price": 25.0,
+                "currency": "USD",
+                "status": "published",
+                "listing_id": "lst_9",
+                "images": ["https://cdn.example/a.jpg"],
+            }}),
+        ),
+    ] {
+        std::fs::create_dir_all(products.join(id)).unwrap();
+        std::fs::write(
+            products.join(id).join("product.json"),
+            json!({
+                "product_id": id,
+                "sku_alias": id.to_uppercase(),
+                "structure_json": {"name": "Lamp", "brand": {"name": "Acme"}},
+                "listings": listings,
+                "images": [{"rel_path": "images/a.jpg", "uploaded_url": "https://cdn.example/a.jpg"}],
+                "hero_rel_path": "images/a.jpg",
+                "created_at": "2026-01-01T09:00:00Z",
+                "updated_at": updated,
+            })
+            .to_string(),
+        )
+        .unwrap();
+    }
+
+    let out = talaria(&server, &home, None, &["catalog", "export"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let lines: Vec<Value> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["product_id"], "prod_old");
+    assert_eq!(lines[0]["status"], "enriched");
+    assert_eq!(lines[0]["brand"], "Acme");
+    assert_eq!(lines[0]["images"][0]["hero"], true);
+
+    let doc = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &[
+            "catalog",
+            "export",
+            "--format",
+            "json",
+            "--since",
+            "2026-02-01",
+        ],
+    ));
+    assert_eq!(doc["count"], 1);
+    let product = &doc["products"][0];
+    assert_eq!(product["sku"], "PROD_NEW");
+    assert_eq!(product["status"], "published");
+    assert_eq!(product["listings"][0]["marketplace"], "EBAY_US");
+    assert_eq!(product["listings"][0]["listing_id"], "lst_9");
+
+    let bad = talaria(
+        &server,
+        &home,
+        None,
+        &["catalog", "export", "--since", "last week"],
+    );
+    assert!(!bad.status.success());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stats_counts_captures_listings_and_failures_per_day() {
+    let server = MockServer::start().await;
+    let home = temp_home("stats");
+    let data = home.join("data/talaria");
+    let session = data.join("captures/sessions/sess_1");
+    std::fs::create_dir_all(&session).unwrap();
+    std::fs::write(
+        session.join("session.json"),
+        json!({
+            "session_id": "sess_1",
+            "product_id": "prod_1",
+            "created_at": "2026-03-01T09:00:00Z",
+            "committed_at": "2026-03-01T10:00:00Z",
+            "frames": [],
+            "picks": {},
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let product = data.join("captures/products/prod_1");
+    std::fs::create_dir_all(&product).unwrap();
+    std::fs::write(
+        product.join("product.json"),
+        json!({"product_id": "prod_1", "sku_alias": "SKU-1"}).to_string(),
+    )
+    .unwrap();
+    let request = |sku: &str| {
+        json!({
+            "sku": sku,
+            "images_source": ["https://x/a.jpg"],
+            "merchant_location_key": "loc-1",
+            "fulfillment_policy_id": "pol-f",
+            "payment_policy_id": "pol-p",
+            "return_policy_id": "pol-r",
+            "publish": true,
+        })
+    };
+    // The CLI records jobs by SKU only; stats find the product through it.
+    let ledger = [
+        json!({"job_id": "job_1", "sku": "SKU-1", "enqueued_at": "2026-03-02T09:00:00Z", "request": request("SKU-1")}),
+        json!({"job_id": "job_2", "sku": "SKU-2", "enqueued_at": "2026-03-02T09:00:00Z", "request": request("SKU-2")}),
+        json!({"job_id": "job_1", "state": "completed", "at": "2026-03-02T12:00:00Z"}),
+        json!({"job_id": "job_2", "state": "failed", "error": "category: no match", "at": "2026-03-02T12:00:00Z"}),
+    ]
+    .map(|line| line.to_string())
+    .join("\n");
+    std::fs::write(data.join("jobs.jsonl"), ledger + "\n").unwrap();
+
+    let stats = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &[
+            "stats",
+            "--from",
+            "2026-03-01",
+            "--to",
+            "2026-03-02",
+            "--format",
+            "json",
+        ],
+    ));
+    assert_eq!(stats["days"].as_array().map(Vec::len), Some(2));
+    assert_eq!(stats["days"][0]["captured"], 1);
+    assert_eq!(stats["days"][1]["published"], 1);
+    assert_eq!(stats["days"][1]["failed"], 1);
+    assert_eq!(stats["avg_cycle_secs"], 27 * 3600);
+    assert_eq!(stats["failures_by_stage"][0]["stage"], "category");
+    assert_eq!(stats["failures_by_stage"][0]["rate"], 0.5);
+
+    let reversed = talaria(
+        &server,
+        &home,
+        None,
+        &["stats", "--from", "2026-03-02", "--to", "2026-03-01"],
+    );
+    assert_eq!(reversed.status.code(), Some(3));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn usage_history_records_a_snapshot_per_day_and_reports_growth() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "org_id": "org_a",
+            "counters": {"credits_consumed": 50, "jobs_enqueued": 9, "listings_run": 7},
+            "window_from": "2026-03-01T00:00:00Z",
+        }])))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("usage-history");
+    let data = home.join("data/talaria");
+    std::fs::create_dir_all(&data).unwrap();
+    let snapshot = |credits: i64, window_from: &str| {
+        json!({
+            "taken_at": "2026-03-01T18:00:00Z",
+            "credits_consumed": credits,
+            "jobs_enqueued": credits / 10,
+            "listings_run": credits / 10,
+            "window_from": window_from,
+        })
+    };
+    std::fs::write(
+        data.join("usage-history.json"),
+        json!({"org_a": {
+            "2026-02-27": snapshot(40, "2026-02-01T00:00:00Z"),
+            // A new billing window starts the counters again.
+            "2026-03-01": snapshot(10, "2026-03-01T00:00:00Z"),
+            "2026-03-02": snapshot(30, "2026-03-01T00:00:00Z"),
+        }})
+        .to_string(),
+    )
+    .unwrap();
+
+    let history = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &[
+            "usage",
+            "history",
+            "--from",
+            "2026-03-01",
+            "--to",
+            "2026-03-02",
+            "--format",
+            "json",
+        ],
+    ));
+    let credits: Vec<_> = history
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|day| (day["date"].clone(), day["credits_consumed"].clone()))
+        .collect();
+    assert_eq!(
+        credits,
+        [
+            (json!("2026-03-01"), json!(10)),
+            (json!("2026-03-02"), json!(20))
+        ]
+    );
+
+    // Fetching the current window records today's snapshot.
+    stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["credits", "--format", "json"],
+    ));
+    let history = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["usage", "history", "--org", "org_a", "--format", "json"],
+    ));
+    let today = history.as_array().unwrap().last().unwrap();
+    assert_eq!(today["credits_consumed"], 20);
+    assert_eq!(today["listings_run"], 4);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn listings_mark_sold_counts_down_and_syncs_hermes() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/products/prod_1"))
+        .and(body_partial_json(json!({"listings_json": {"EBAY_US": {
+            "quantity": 0,
+            "quantity_sold": 3,
+        }}})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "prod_1",
+            "sku_alias": "SKU-1",
+            "listings_json": {},
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-02T00:00:00Z",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("sold");
+    let product_dir = home.join("data/talaria/captures/products/prod_1");
+    std::fs::create_dir_all(&product_dir).unwrap();
+    let manifest = product_dir.join("product.json");
+    std::fs::write(
+        &manifest,
+        json!({
+            "product_id": "prod_1",
+            "sku_alias": "SKU-1",
+            "listings": {"EBAY_US": {"title": "Lamp", "status": "published", "quantity": 3}},
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let local = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["listings", "mark-sold", "--sku", "SKU-1"],
+    ));
+    assert_eq!(
+        (&local["status"], &local["quantity"], &local["sources"]),
+        (&json!("published"), &json!(2), &json!(["local"]))
+    );
+
+    let synced = stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[
+            "listings",
+            "mark-sold",
+            "--sku",
+            "prod_1",
+            "--quantity",
+            "2",
+        ],
+    ));
+    assert_eq!(synced["status"], "sold");
+    let saved: Value = serde_json::from_slice(&std::fs::read(&manifest).unwrap()).unwrap();
+    assert_eq!(saved["listings"]["EBAY_US"]["quantity_sold"], 3);
+    assert_eq!(saved["listings"]["EBAY_US"]["title"], "Lamp");
+
+    let again = talaria(
+        &server,
+        &home,
+        None,
+        &["listings", "mark-sold", "--sku", "SKU-1"],
+    );
+    assert!(String::from_utf8_lossy(&again.stderr).contains("listing is sold"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn open_print_builds_listing_image_and_dashboard_urls() {
+    let server = MockServer::start().await;
+    let home = temp_home("open");
+    let product_dir = home.join("data/talaria/captures/products/prod_1");
+    std::fs::create_dir_all(&product_dir).unwrap();
+    std::fs::write(
+        product_dir.join("product.json"),
+        json!({
+            "product_id": "prod_1",
+            "sku_alias": "SKU-1",
+            "images": [
+                {"rel_path": "images/1.jpg", "uploaded_url": "https://cdn.test/1.jpg"},
+                {"rel_path": "images/2.jpg"},
+            ],
+            "listings": {"EBAY_DE": {"listing_id": "1234567890", "status": "published"}},
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let open = |args: &[&str]| {
+        let mut all = vec!["--quiet", "open"];
+        all.extend_from_slice(args);
+        all.push("--print");
+        talaria(&server, &home, None, &all)
+    };
+
+    let listing = stdout_json(&open(&[
+        "listing",
+        "--sku",
+        "SKU-1",
+        "--marketplace",
+        "EBAY_DE",
+    ]));
+    assert_eq!(listing["url"], "https://www.ebay.de/itm/1234567890");
+    assert_eq!(listing["opened"], false);
+    let by_id = stdout_json(&open(&["listing", "--id", "42"]));
+    assert_eq!(by_id["url"], "https://www.ebay.com/itm/42");
+    let unlisted = open(&["listing", "--sku", "SKU-1"]);
+    assert_eq!(unlisted.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&unlisted.stderr).contains("no published EBAY_US listing"));
+
+    let image = stdout_json(&open(&["image", "--sku", "prod_1"]));
+    assert_eq!(image["url"], "https://cdn.test/1.jpg");
+    let pending = open(&["image", "--sku", "prod_1", "--index", "2"]);
+    assert!(String::from_utf8_lossy(&pending.stderr).contains("not uploaded yet"));
+
+    let dashboard = stdout_json(&open(&["dashboard"]));
+    assert_eq!(dashboard["url"], "https://app.hermes-api.dev");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn locale_translates_cli_messages() {
+    let server = MockServer::start().await;
+    let home = temp_home("locale");
+
+    let failed = talaria_command(&server, &home, None, &["open", "listing", "--sku", "nope"])
+        .env("TALARIA_LOCALE", "de_DE.UTF-8")
+        .output()
+        .expect("run talaria-cli");
+    assert!(String::from_utf8_lossy(&failed.stderr).starts_with("Fehler: "));
+
+    let english = talaria(&server, &home, None, &["open", "listing", "--sku", "nope"]);
+    assert!(String::from_utf8_lossy(&english.stderr).starts_with("Error: "));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn auth_rotate_key_replaces_and_revokes_old_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/user/api-keys"))
+        .and(header("authorization", "Bearer at_1"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": "key_2",
+            "name": "Talaria laptop",
+            "prefix": "sk_live_new",
+            "secret": "sk_live_newsecret",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .and(header("x-hermes-key", "sk_live_newsecret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .and(header("x-hermes-key", "sk_live_oldsecret"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({"error": "invalid api key"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/user/api-keys/key_1"))
+        .and(header("authorization", "Bearer at_1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("rotate");
+    let config_path = home.join("config/talaria/config.toml");
+    std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &config_path,
+        "api_key = \"sk_live_oldsecret\"\n\
+         ebay_merchant_location_key = \"loc-1\"\n\
+         api_key_info = { id = \"key_1\", name = \"Talaria old\", prefix = \"sk_live_old\" }\n",
+    )
+    .unwrap();
+
+    let status = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["auth", "status", "--format", "json"],
+    ));
+    assert_eq!(status["source"], "config");
+    assert_eq!(status["id"], "key_1");
+    assert_eq!(status["authenticates"], false);
+
+    let output = talaria(
+        &server,
+        &home,
+        None,
+        &[
+            "auth",
+            "rotate-key",
+            "--access-token",
+            "at_1",
+            "--revoke-old",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("sk_live_new") && stdout.contains("Revoked previous key"));
+
+    let saved = std::fs::read_to_string(&config_path).unwrap();
+    assert!(saved.contains("sk_live_newsecret") && !saved.contains("sk_live_oldsecret"));
+    assert!(saved.contains("loc-1"), "other settings survive: {saved}");
+    assert!(!config_path.with_extension("toml.tmp").exists());
+
+    let status = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["auth", "status", "--format", "json"],
+    ));
+    assert_eq!(status["prefix"], "sk_live_new");
+    assert_eq!(status["name"], "Talaria laptop");
+    assert_eq!(status["authenticates"], true);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn auth_keys_list_marks_current_key_and_revoke_spares_it() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/api-keys"))
+        .and(header("authorization", "Bearer at_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": "key_1", "name": "Talaria laptop", "prefix": "sk_live_ab"},
+            {"id": "key_2", "name": "Talaria lost phone", "prefix": "sk_live_cd"},
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/user/api-keys/key_2"))
+        .and(header("authorization", "Bearer at_1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("keys");
+    let config_path = home.join("config/talaria/config.toml");
+    std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &config_path,
+        "api_key = \"sk_live_abcdef\"\n\
+         api_key_info = { id = \"key_1\", name = \"Talaria laptop\", prefix = \"sk_live_ab\" }\n",
+    )
+    .unwrap();
+
+    let keys = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &[
+            "auth",
+            "keys",
+            "list",
+            "--access-token",
+            "at_1",
+            "--format",
+            "json",
+        ],
+    ));
+    assert_eq!(keys[0]["current"], true);
+    assert_eq!(keys[1]["current"], false);
+    assert_eq!(keys[1]["name"], "Talaria lost phone");
+
+    let refused = talaria(
+        &server,
+        &home,
+        None,
+        &["auth", "keys", "revoke", "key_1", "--access-token", "at_1"],
+    );
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("--force"));
+
+    let output = talaria(
+        &server,
+        &home,
+        None,
+        &["auth", "keys", "revoke", "key_2", "--access-token", "at_1"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn org_id_flag_overrides_config_and_orgs_list_marks_current() {
+    let usage = |org: &str| {
+        json!({
+            "org_id": org,
+            "counters": {"credits_consumed": 1, "jobs_enqueued": 2, "listings_run": 3},
+        })
+    };
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .and(query_param("org_id", "org_b"))
+        .and(header("x-hermes-org", "org_b"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([usage("org_b")])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .and(|req: &wiremock::Request| {
+            req.url.query().is_none() && !req.headers.contains_key("x-hermes-org")
+        })
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([usage("org_b"), usage("org_c")])),
+        )
+        .mount(&server)
+        .await;
+
+    let home = temp_home("orgs");
+    let config_path = home.join("config/talaria/config.toml");
+    std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    std::fs::write(&config_path, "org_id = \"org_a\"\n").unwrap();
+
+    let scoped = stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["usage", "list", "--org-id", "org_b"],
+    ));
+    assert_eq!(scoped[0]["org_id"], "org_b");
+
+    let orgs = stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["orgs", "list", "--format", "json"],
+    ));
+    assert_eq!(
+        orgs,
+        json!([
+            {"org_id": "org_a", "current": true},
+            {"org_id": "org_b", "current": false},
+            {"org_id": "org_c", "current": false},
+        ])
+    );
+}
+
+#[tokio::test]
+async fn metrics_dump_accumulates_requests_across_runs() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"status": "ok", "service": "hermes"})),
+        )
+        .mount(&server)
+        .await;
+
+    let home = temp_home("metrics");
+    for _ in 0..2 {
+        stdout_json(&talaria(
+            &server,
+            &home,
+            None,
+            &["health", "--format", "json"],
+        ));
+    }
+
+    let totals = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["metrics", "dump", "--format", "json"],
+    ));
+    assert_eq!(
+        totals["counters"][r#"talaria_hermes_requests_total{endpoint="health",status="200"}"#],
+        2
+    );
+
+    let output = talaria(
+        &server,
+        &home,
+        None,
+        &["metrics", "dump", "--format", "prometheus"],
+    );
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("# TYPE talaria_hermes_requests_total counter"));
+    assert!(text.contains(r#"talaria_hermes_request_duration_seconds_count{endpoint="health"} 2"#));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn default_output_format_and_quiet_porcelain_flags() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"status": "ok", "service": "hermes", "version": "1.2.3"})),
+        )
+        .mount(&server)
+        .await;
+
+    let home = temp_home("output-format");
+    let config_path = home.join("config/talaria/config.toml");
+    std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    std::fs::write(&config_path, "default_output_format = \"table\"\n").unwrap();
+
+    let table = talaria(&server, &home, None, &["health"]);
+    assert!(table.status.success());
+    assert!(serde_json::from_slice::<Value>(&table.stdout).is_err());
+    assert!(String::from_utf8_lossy(&table.stdout).contains("hermes"));
+
+    let explicit = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["health", "--format", "json"],
+    ));
+    assert_eq!(explicit["version"], "1.2.3");
+
+    let quiet = stdout_json(&talaria(&server, &home, None, &["--quiet", "health"]));
+    assert_eq!(quiet["service"], "hermes");
+
+    let porcelain = talaria(&server, &home, None, &["health", "--porcelain"]);
+    assert!(porcelain.status.success());
+    let lines: Vec<_> = String::from_utf8_lossy(&porcelain.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert!(lines.contains(&"service\thermes".to_string()));
+    assert!(lines.contains(&"version\t1.2.3".to_string()));
+    assert!(porcelain.stderr.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn hsuf_enrich_reads_image_urls_from_stdin_and_context_from_a_file() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hsuf/enrich"))
+        .and(body_partial_json(json!({
+            "images": ["https://img.example/a.jpg", "https://img.example/b.jpg"],
+            "context_text": "Blue ceramic mug, small chip on the rim",
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "product": {"name": "Blue mug", "image": "https://img.example/a.jpg", "offers": {}},
+        })))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("hsuf-stdin");
+    let context = home.join("context.txt");
+    std::fs::write(&context, "Blue ceramic mug, small chip on the rim\n").unwrap();
+    let output = talaria_stdin(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[
+            "hsuf-enrich",
+            "--images",
+            "-",
+            "--context-file",
+            context.to_str().unwrap(),
+        ],
+        "https://img.example/a.jpg\n\n  https://img.example/b.jpg\n",
+    );
+    assert_eq!(stdout_json(&output)["product"]["name"], "Blue mug");
+
+    let output = talaria_stdin(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["hsuf-enrich", "--images", "-"],
+        "\n",
+    );
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no image URLs on stdin"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn timeout_flag_overrides_the_configured_enrich_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hsuf/enrich"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "product": {"name": "Blue mug", "image": "https://img.example/a.jpg", "offers": {}},
+                }))
+                .set_delay(std::time::Duration::from_millis(1500)),
+        )
+        .mount(&server)
+        .await;
+
+    let home = temp_home("timeout-flag");
+    let config_dir = home.join("config").join("talaria");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.toml"), "[timeouts]\nenrich = 1\n").unwrap();
+    let args = ["hsuf-enrich", "--images", "https://img.example/a.jpg"];
+
+    let output = talaria(&server, &home, Some("sk_test"), &args);
+    assert_eq!(output.status.code(), Some(1));
+    let output = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[&args[..], &["--timeout", "10s"]].concat(),
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(stdout_json(&output)["product"]["name"], "Blue mug");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn hsuf_enrich_manifest_prints_ndjson_per_item_and_a_summary() {
+    let server = MockServer::start().await;
+    for sku in ["mug-1", "mug-2"] {
+        Mock::given(method("POST"))
+            .and(path("/hsuf/enrich"))
+            .and(query_param("include_usage", "true"))
+            .and(body_partial_json(
+                json!({"sku": sku, "prompt_rules": "No emoji."}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "product": {"name": sku, "image": "https://img.example/a.jpg", "offers": {}},
+                "usage": {"input_tokens": 300, "output_tokens": 40},
+            })))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/hsuf/enrich"))
+        .and(body_partial_json(json!({"sku": "mug-3"})))
+        .respond_with(
+            ResponseTemplate::new(422).set_body_json(json!({"error": "images unreadable"})),
+        )
+        .mount(&server)
+        .await;
+
+    let home = temp_home("hsuf-manifest");
+    let config_dir = home.join("config/talaria");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "prompt_rules = \"No emoji.\"\noperator = \"dana\"\n",
+    )
+    .unwrap();
+    let manifest = home.join("items.json");
+    let items: Vec<Value> = (1..=3)
+        .map(|n| json!({"sku": format!("mug-{n}"), "images": [format!("https://img.example/{n}.jpg")]}))
+        .collect();
+    std::fs::write(&manifest, serde_json::to_string(&items).unwrap()).unwrap();
+
+    let output = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[
+            "hsuf-enrich",
+            "--manifest",
+            manifest.to_str().unwrap(),
+            "--concurrency",
+            "2",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(5));
+    let lines: Vec<Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 4);
+    let item = |sku: &str| lines.iter().find(|line| line["sku"] == sku).unwrap();
+    assert_eq!(item("mug-2")["product"]["name"], "mug-2");
+    assert_eq!(item("mug-3")["exit_code"], 3);
+    assert!(
+        item("mug-3")["error"]
+            .as_str()
+            .unwrap()
+            .contains("images unreadable")
+    );
+    assert_eq!(
+        lines[3]["summary"],
+        json!({
+            "succeeded": 2,
+            "failed": 1,
+            "rate_limited": 0,
+            "input_tokens": 600,
+            "output_tokens": 80,
+        })
+    );
+    assert_eq!(lines[3]["operator"], "dana");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn watch_once_uploads_enriches_and_archives_each_product() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hsuf/enrich"))
+        .and(body_partial_json(json!({"sku": "mug-7"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "product": {"name": "Mug", "image": "https://img.example/a.jpg", "offers": {}},
+        })))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("watch");
+    let incoming = home.join("incoming");
+    std::fs::create_dir_all(&incoming).unwrap();
+    std::fs::write(incoming.join("mug-7_01.jpg"), b"front").unwrap();
+    std::fs::write(incoming.join("mug-7_02.jpg"), b"back").unwrap();
+    let output = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[
+            "--simulate",
+            "--quiet",
+            "watch",
+            "--dir",
+            incoming.to_str().unwrap(),
+            "--enrich",
+            "--once",
+        ],
+    );
+    let line = stdout_json(&output);
+    assert_eq!(line["sku"], "mug-7");
+    assert_eq!(line["images"].as_array().unwrap().len(), 2);
+    assert_eq!(line["product"]["name"], "Mug");
+    let archived = incoming.join(".talaria-processed/mug-7");
+    assert!(archived.join("mug-7_01.jpg").exists());
+    assert!(archived.join("mug-7_02.jpg").exists());
+    assert!(!incoming.join("mug-7_01.jpg").exists());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn watch_sort_by_cost_reports_tokens_credits_and_totals() {
+    let server = MockServer::start().await;
+    for (sku, input_tokens) in [("coat-1", 9000), ("mug-7", 500)] {
+        Mock::given(method("POST"))
+            .and(path("/hsuf/enrich"))
+            .and(query_param("include_usage", "true"))
+            .and(body_partial_json(json!({"sku": sku})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "product": {"name": sku, "image": "https://img.example/a.jpg", "offers": {}},
+                "usage": {"input_tokens": input_tokens, "output_tokens": 100},
+            })))
+            .mount(&server)
+            .await;
+    }
+    // Read before the batch and after each product.
+    for credits in [100, 104, 110] {
+        Mock::given(method("GET"))
+            .and(path("/v1/usage"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "org_id": "org_1",
+                "counters": {"credits_consumed": credits, "jobs_enqueued": 0, "listings_run": 0},
+            }])))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+    }
+
+    let home = temp_home("watch-cost");
+    let incoming = home.join("incoming");
+    std::fs::create_dir_all(&incoming).unwrap();
+    std::fs::write(incoming.join("mug-7_01.jpg"), b"mug").unwrap();
+    std::fs::write(incoming.join("coat-1_01.jpg"), b"coat").unwrap();
+    let output = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[
+            "--simulate",
+            "watch",
+            "--dir",
+            incoming.to_str().unwrap(),
+            "--enrich",
+            "--once",
+            "--sort-by-cost",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let lines: Vec<Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let products: Vec<&Value> = lines.iter().filter(|l| l.get("stage").is_none()).collect();
+    assert_eq!(products.len(), 2);
+    let credits = |line: &Value| {
+        line["cost"]["stages"]["ingest"]["credits"]
+            .as_i64()
+            .unwrap()
+    };
+    assert!(credits(products[0]) >= credits(products[1]), "{products:?}");
+    assert_eq!(credits(products[0]) + credits(products[1]), 10);
+    for product in &products {
+        let tokens = if product["sku"] == "coat-1" {
+            9000
+        } else {
+            500
+        };
+        assert_eq!(product["cost"]["stages"]["ingest"]["input_tokens"], tokens);
+    }
+    let totals = lines.iter().find(|l| l["event"] == "totals").unwrap();
+    assert_eq!(totals["products"], 2);
+    assert_eq!(totals["cost"]["stages"]["ingest"]["input_tokens"], 9500);
+    assert_eq!(totals["cost"]["stages"]["ingest"]["credits"], 10);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn listings_create_checks_photo_quality_before_upload() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/jobs/listings"))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({"job_id": "job_q"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("quality");
+    let photos = home.join("photos");
+    std::fs::create_dir_all(&photos).unwrap();
+    let pixels: Vec<u8> = (0..120 * 90)
+        .flat_map(|i| {
+            let value = if (i % 120 / 6 + i / 120 / 6) % 2 == 0 {
+                40
+            } else {
+                200
+            };
+            [value; 3]
+        })
+        .collect();
+    std::fs::write(
+        photos.join("small.png"),
+        talaria_core::simulate::encode_png(120, 90, &pixels),
+    )
+    .unwrap();
+    let create = |extra: &[&str]| {
+        let mut args = vec![
+            "--simulate",
+            "listings",
+            "create",
+            "--images-from-dir",
+            photos.to_str().unwrap(),
+            "--merchant-location-key",
+            "home",
+            "--fulfillment-policy-id",
+            "1",
+            "--payment-policy-id",
+            "2",
+            "--return-policy-id",
+            "3",
+            "--idempotency-key",
+            "key-q",
+        ];
+        args.extend_from_slice(extra);
+        talaria(&server, &home, Some("sk_test"), &args)
+    };
+
+    let refused = create(&["--strict-quality"]);
+    assert_eq!(refused.status.code(), Some(3), "{refused:?}");
+    let stderr = String::from_utf8_lossy(&refused.stderr);
+    assert!(
+        stderr.contains("small.png") && stderr.contains("500 px"),
+        "{stderr}"
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+
+    let warned = create(&["--enqueue"]);
+    assert!(warned.status.success(), "{warned:?}");
+    assert!(String::from_utf8_lossy(&warned.stderr).contains("warning: "));
+    assert_eq!(stdout_json(&warned)["job_id"], "job_q");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn listings_continue_interactive_prompts_from_the_job_result() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_w"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "job_w",
+            "state": "completed",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:01:00Z",
+            "request": {
+                "fulfillment_policy_id": "1",
+                "payment_policy_id": "2",
+                "return_policy_id": "3",
+                "merchant_location_key": "home",
+                "images_source": "https://img.example/a.jpg",
+            },
+            "result": {
+                "listing_id": "lst_w",
+                "stages": [
+                    {
+                        "name": "category",
+                        "elapsed_ms": 5,
+                        "timestamp": "2026-01-01T00:00:10Z",
+                        "output": {
+                            "selected": {"id": "31388", "label": "Digital Cameras"},
+                        },
+                    },
+                    {
+                        "name": "prepare_conditions",
+                        "elapsed_ms": 3,
+                        "timestamp": "2026-01-01T00:00:20Z",
+                        "output": {
+                            "default": "New",
+                            "default_condition_id": 1000,
+                            "allowed": ["New", "Used"],
+                            "allowed_condition_ids": [1000, 3000],
+                        },
+                    },
+                    {
+                        "name": "listing",
+                        "elapsed_ms": 9,
+                        "timestamp": "2026-01-01T00:00:30Z",
+                        "output": {
+                            "images": ["https://img.example/a.jpg", "https://img.example/b.jpg"],
+                        },
+                    },
+                ],
+            },
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/listings/continue"))
+        .and(body_partial_json(json!({
+            "sku": "cam-1",
+            "overrides": {
+                "condition": "Used",
+                "condition_id": 3000,
+                "resolved_images": [
+                    "https://img.example/b.jpg",
+                    "https://img.example/a.jpg",
+                    "https://img.example/c.jpg",
+                ],
+            },
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"listing_id": "lst_w2", "stages": []})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("continue-wizard");
+    let args = [
+        "listings",
+        "continue",
+        "--sku",
+        "cam-1",
+        "--merchant-location-key",
+        "home",
+        "--fulfillment-policy-id",
+        "1",
+        "--payment-policy-id",
+        "2",
+        "--return-policy-id",
+        "3",
+        "--interactive",
+        "--from-job",
+        "job_w",
+    ];
+    // Keep the category, answer "Mint" (not allowed) then "2", reorder the
+    // images, add one, and submit.
+    let answers = "\nMint\n2\n2,1\nhttps://img.example/c.jpg\n\n";
+    let out = talaria_stdin(&server, &home, Some("sk_test"), &args, answers);
+    assert!(out.status.success(), "{out:?}");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("31388 Digital Cameras"), "{stderr}");
+    assert!(
+        stderr.contains("condition 'Mint' is not allowed"),
+        "{stderr}"
+    );
+    assert_eq!(stdout_json(&out)["listing_id"], "lst_w2");
+
+    let declined = talaria_stdin(&server, &home, Some("sk_test"), &args, "\n\n\n\nn\n");
+    assert!(declined.status.success(), "{declined:?}");
+    assert!(String::from_utf8_lossy(&declined.stderr).contains("not submitted"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn warning_policy_fails_on_matching_stage_warnings() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/listings/continue"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "listing_id": "lst_p",
+            "stages": [{
+                "name": "aspects",
+                "elapsed_ms": 7,
+                "timestamp": "2026-01-01T00:00:00Z",
+                "output": {"warnings": ["Missing required aspect: Brand", "Color guessed"]},
+            }],
+        })))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("warning-policy");
+    let config_dir = home.join("config/talaria");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    let write_policy = |action: &str| {
+        std::fs::write(
+            config_dir.join("config.toml"),
+            format!(
+                "[warning_policy]\n\n[[warning_policy.rules]]\n\
+                 pattern = \"*missing required aspect*\"\naction = \"{action}\"\n"
+            ),
+        )
+        .unwrap()
+    };
+    let args = [
+        "listings",
+        "continue",
+        "--sku",
+        "sku-1",
+        "--merchant-location-key",
+        "home",
+        "--fulfillment-policy-id",
+        "1",
+        "--payment-policy-id",
+        "2",
+        "--return-policy-id",
+        "3",
+    ];
+
+    write_policy("fail");
+    let failed = talaria(&server, &home, Some("sk_test"), &args);
+    assert_eq!(failed.status.code(), Some(6), "{failed:?}");
+    let printed: Value = serde_json::from_slice(&failed.stdout).expect("stdout json");
+    assert_eq!(printed["listing_id"], "lst_p");
+    let stderr = String::from_utf8_lossy(&failed.stderr);
+    assert!(
+        stderr.contains("aspects: Missing required aspect: Brand"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("warning: aspects: Color guessed"),
+        "{stderr}"
+    );
+
+    write_policy("ignore");
+    let ignored = talaria(&server, &home, Some("sk_test"), &args);
+    assert!(ignored.status.success(), "{ignored:?}");
+    let strict = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[&["--fail-on-warning"], &args[..]].concat(),
+    );
+    assert_eq!(strict.status.code(), Some(6), "{strict:?}");
+    let stderr = String::from_utf8_lossy(&strict.stderr);
+    assert!(
+        stderr.contains("Color guessed") && !stderr.contains("Brand"),
+        "{stderr}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn jobs_retry_reenqueues_the_request_with_set_fields() {
+    let server = MockServer::start().await;
+    let job = |id: &str, state: &str| {
+        json!({
+            "id": id,
+            "state": state,
+            "error": "aspects stage failed",
+            "stage": "aspects",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:01:00Z",
+            "request": {
+                "fulfillment_policy_id": "1",
+                "payment_policy_id": "2",
+                "return_policy_id": "3",
+                "merchant_location_key": "home",
+                "images_source": "https://img.example/a.jpg",
+                "sku": "sku-9",
+                "llm_aspects": {"model": "gpt-5-mini", "reasoning": true},
+            },
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_f"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(job("job_f", "failed")))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_r"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(job("job_r", "running")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/jobs/listings"))
+        .and(body_partial_json(json!({
+            "sku": "123",
+            "publish": true,
+            "llm_aspects": {"model": "gpt-5.2", "reasoning": true},
+            "images_source": ["https://img.example/a.jpg", "https://img.example/b.jpg"],
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"job_id": "job_f2"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("jobs-retry");
+    let retried = stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[
+            "jobs",
+            "retry",
+            "--id",
+            "job_f",
+            "--set",
+            "llm_aspects.model=gpt-5.2",
+            "--set",
+            "publish=true",
+            "--set",
+            "sku=123",
+            "--set",
+            r#"images_source=["https://img.example/a.jpg","https://img.example/b.jpg"]"#,
+        ],
+    ));
+    assert_eq!(retried["job_id"], "job_f2");
+    let ledger = std::fs::read_to_string(home.join("data/talaria/jobs.jsonl")).unwrap();
+    assert!(ledger.contains(r#""retry_of":"job_f""#), "{ledger}");
+
+    let unknown = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["jobs", "retry", "--id", "job_f", "--set", "colour=red"],
+    );
+    assert_eq!(unknown.status.code(), Some(3), "{unknown:?}");
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("no such field"));
+    let running = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["jobs", "retry", "--id", "job_r"],
+    );
+    assert_eq!(running.status.code(), Some(3), "{running:?}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn jobs_list_filters_by_state_before_the_limit() {
+    let server = MockServer::start().await;
+    let job = |id: &str, state: &str| {
+        json!({
+            "id": id,
+            "state": state,
+            "error": "aspects stage failed",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:01:00Z",
+            "request": {
+                "fulfillment_policy_id": "1",
+                "payment_policy_id": "2",
+                "return_policy_id": "3",
+                "merchant_location_key": "home",
+                "images_source": "https://img.example/a.jpg",
+            },
+            "result": {"listing_id": "lst_1", "stages": []},
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_old"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(job("job_old", "failed")))
+        .mount(&server)
+        .await;
+    // Already completed in the ledger: only the unfiltered listing asks.
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_done"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(job("job_done", "completed")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_new"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(job("job_new", "running")))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("jobs-list");
+    let data = home.join("data/talaria");
+    std::fs::create_dir_all(&data).unwrap();
+    let ledger = [
+        json!({"job_id": "job_old", "sku": "SKU-1", "enqueued_at": "2026-03-01T09:00:00Z"}),
+        json!({"job_id": "job_done", "sku": "SKU-2", "enqueued_at": "2026-03-02T09:00:00Z"}),
+        json!({"job_id": "job_new", "sku": "SKU-3", "enqueued_at": "2026-03-03T09:00:00Z"}),
+        json!({"job_id": "job_done", "state": "completed", "at": "2026-03-02T12:00:00Z"}),
+    ]
+    .map(|line| line.to_string())
+    .join("\n");
+    std::fs::write(data.join("jobs.jsonl"), ledger + "\n").unwrap();
+    let list = |args: &[&str]| {
+        let mut all = vec!["jobs", "list", "--format", "json"];
+        all.extend_from_slice(args);
+        stdout_json(&talaria(&server, &home, Some("sk_test"), &all))
+    };
+
+    let failed = list(&["--state", "failed", "--limit", "1"]);
+    assert_eq!(failed.as_array().map(Vec::len), Some(1), "{failed}");
+    assert_eq!(failed[0]["job_id"], "job_old");
+    assert_eq!(failed[0]["detail"], "aspects stage failed");
+    let saved = std::fs::read_to_string(data.join("jobs.jsonl")).unwrap();
+    assert!(
+        saved.contains(r#"{"job_id":"job_old","state":"failed""#),
+        "{saved}"
+    );
+
+    let recent = list(&["--limit", "2"]);
+    let ids: Vec<_> = recent
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["job_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["job_new", "job_done"]);
+    assert!(list(&["--state", "queued"]).as_array().unwrap().is_empty());
+}
+
+fn watched_job(state: &str) -> Value {
+    json!({
+        "id": "job_w",
+        "state": state,
+        "created_at": "2026-01-01T00:00:00Z",
+        "updated_at": "2026-01-01T00:01:00Z",
+        "request": {
+            "fulfillment_policy_id": "1",
+            "payment_policy_id": "2",
+            "return_policy_id": "3",
+            "merchant_location_key": "home",
+            "images_source": "https://img.example/a.jpg",
+        },
+        "result": {"listing_id": "lst_w", "stages": []},
+    })
+}
+
+/// The summary `jobs watch` prints last.
+fn last_json_line(output: &Output) -> Value {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().last().expect("summary line");
+    serde_json::from_str(line).expect("json summary")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn jobs_watch_polls_until_the_job_completes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_w"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(watched_job("running")))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_w"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(watched_job("completed")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("jobs-watch");
+    let output = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["jobs", "watch", "--id", "job_w", "--interval", "10ms"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let summary = last_json_line(&output);
+    assert_eq!(summary["id"], "job_w");
+    assert_eq!(summary["state"], "completed");
+    assert_eq!(summary["terminal"], true);
+    assert_eq!(summary["job"]["result"]["listing_id"], "lst_w");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn jobs_watch_gives_up_after_the_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_w"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(watched_job("running")))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("jobs-watch-timeout");
+    let output = talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &[
+            "--quiet",
+            "jobs",
+            "watch",
+            "--id",
+            "job_w",
+            "--interval",
+            "20ms",
+            "--timeout",
+            "100ms",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("timed out waiting for job job_w"));
+    let summary = last_json_line(&output);
+    assert_eq!(summary["state"], "running");
+    assert_eq!(summary["terminal"], false);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn templates_fill_listing_fields_under_explicit_flags() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/listings"))
+        .and(body_partial_json(json!({
+            "marketplace": "EBAY_UK",
+            "merchant_location_key": "garage",
+            "fulfillment_policy_id": "f-1",
+            "payment_policy_id": "p-cli",
+            "return_policy_id": "r-1",
+            "llm_aspects": {"model": "gpt-5.2", "web_search": true},
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"listing_id": "lst_t", "stages": []})),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("templates");
+    let saved = talaria(
+        &server,
+        &home,
+        None,
+        &[
+            "templates",
+            "save",
+            "garage-sale",
+            "--marketplace",
+            "EBAY_UK",
+            "--merchant-location-key",
+            "garage",
+            "--fulfillment-policy-id",
+            "f-1",
+            "--payment-policy-id",
+            "p-1",
+            "--return-policy-id",
+            "r-1",
+            "--llm-aspects-model",
+            "gpt-5.2",
+            "--llm-aspects-web-search",
+        ],
+    );
+    assert!(saved.status.success(), "{saved:?}");
+    assert!(
+        home.join("config/talaria/templates/garage-sale.toml")
+            .exists()
+    );
+    let listed = stdout_json(&talaria(
+        &server,
+        &home,
+        None,
+        &["templates", "list", "--format", "json"],
+    ));
+    assert_eq!(listed[0]["name"], "garage-sale");
+    assert_eq!(listed[0]["merchant_location_key"], "garage");
+
+    let create = |extra: &[&str]| {
+        let mut args = vec![
+            "listings",
+            "create",
+            "--images",
+            "https://img.example/a.jpg",
+            "--payment-policy-id",
+            "p-cli",
+            "--skip-verify",
+        ];
+        args.extend_from_slice(extra);
+        talaria(&server, &home, Some("sk_test"), &args)
+    };
+    let missing = create(&[]);
+    assert_eq!(missing.status.code(), Some(3), "{missing:?}");
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("--merchant-location-key"));
+    assert_eq!(
+        stdout_json(&create(&["--template", "garage-sale"]))["listing_id"],
+        "lst_t"
+    );
+
+    let applied = talaria(&server, &home, None, &["templates", "apply", "garage-sale"]);
+    assert!(applied.status.success(), "{applied:?}");
+    assert_eq!(stdout_json(&create(&[]))["listing_id"], "lst_t");
+    let unknown = talaria(&server, &home, None, &["templates", "apply", "nope"]);
+    assert_eq!(unknown.status.code(), Some(3), "{unknown:?}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn listings_create_refuses_unreachable_image_urls_before_submitting() {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/img/ok.jpg"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-type", "image/jpeg"))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/img/private.jpg"))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/listings"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"listing_id": "lst_v", "stages": []})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("verify-images");
+    let ok = format!("{}/img/ok.jpg", server.uri());
+    let private = format!("{}/img/private.jpg", server.uri());
+    let images = format!("{ok} {private}");
+    let create = |extra: &[&str]| {
+        let mut args = vec![
+            "listings",
+            "create",
+            "--images",
+            &images,
+            "--merchant-location-key",
+            "home",
+            "--fulfillment-policy-id",
+            "1",
+            "--payment-policy-id",
+            "2",
+            "--return-policy-id",
+            "3",
+        ];
+        args.extend_from_slice(extra);
+        talaria(&server, &home, Some("sk_test"), &args)
+    };
+
+    let refused = create(&[]);
+    assert_eq!(refused.status.code(), Some(3), "{refused:?}");
+    let stderr = String::from_utf8_lossy(&refused.stderr);
+    assert!(
+        stderr.contains("1 of 2 image URLs") && stderr.contains("private.jpg: HTTP 403"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("ok.jpg:"), "{stderr}");
+
+    let listed = create(&["--skip-verify"]);
+    assert_eq!(stdout_json(&listed)["listing_id"], "lst_v");
+}
+
+#[tokio::test]
+async fn media_abort_stale_aborts_expired_journaled_uploads() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/media/uploads/up_old/abort"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let home = temp_home("media-abort-stale");
+    let data = home.join("data/talaria");
+    std::fs::create_dir_all(&data).unwrap();
+    let entry = |id: &str, expires_at: &str| {
+        json!({
+            "upload_id": id,
+            "filename": format!("{id}.jpg"),
+            "product_id": "p1",
+            "started_at": "2026-01-01T00:00:00Z",
+            "expires_at": expires_at,
+        })
+    };
+    std::fs::write(
+        data.join("pending_uploads.json"),
+        serde_json::to_vec(&json!({
+            "up_old": entry("up_old", "2026-01-01T00:10:00Z"),
+            "up_live": entry("up_live", "2999-01-01T00:00:00Z"),
+        }))
+        .unwrap(),
+    )
+    .unwrap();
+
+    let aborted = stdout_json(&talaria(
+        &server,
+        &home,
+        Some("sk_test"),
+        &["media", "abort-stale", "--format", "json"],
+    ));
+    assert_eq!(aborted.as_array().map(Vec::len), Some(1));
+    assert_eq!(aborted[0]["upload_id"], "up_old");
+    assert_eq!(aborted[0]["aborted"], true);
+    let left: Value =
+        serde_json::from_slice(&std::fs::read(data.join("pending_uploads.json")).unwrap()).unwrap();
+    assert!(left.get("up_old").is_none());
+    assert!(left.get("up_live").is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn images_prune_keeps_uploads_of_trashed_products() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/products"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/storage/v1/object/list/images-bucket"))
+        .and(body_partial_json(json!({"prefix": "talaria"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"name": "kept.jpg", "id": "obj_1", "created_at": "2020-01-01T00:00:00Z"},
+            {"name": "orphan.jpg", "id": "obj_2", "created_at": "2020-01-01T00:00:00Z"},
+        ])))
+        .mount(&server)
+        .await;
+
+    let home = temp_home("images-prune-trash");
+    // Layout of the TUI trash: `products/_trash/<entry>/product.json`.
+    let trashed = home.join("data/talaria/captures/products/_trash/prod_1_20260101_000000000");
+    std::fs::create_dir_all(&trashed).unwrap();
+    let kept_url = format!(
+        "{}/storage/v1/object/public/images-bucket/talaria/kept.jpg",
+        server.uri()
+    );
+    std::fs::write(
+        trashed.join("product.json"),
+        json!({
+            "product_id": "prod_1",
+            "sku_alias": "PROD_1",
+            "images": [{"rel_path": "images/a.jpg", "uploaded_url": kept_url}],
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let pruned = stdout_json(
+        &talaria_command(
+            &server,
+            &home,
+            Some("sk_test"),
+            &["images", "prune", "--dry-run", "--format", "json"],
+        )
+        .env("SUPABASE_URL", server.uri())
+        .env("SUPABASE_SERVICE_ROLE_KEY", "service_key")
+        .output()
+        .expect("run talaria-cli"),
+    );
+    let paths: Vec<&str> = pruned
+        .as_array()
+        .expect("pruned list")
+        .iter()
+        .filter_map(|item| item["path"].as_str())
+        .collect();
+    assert_eq!(paths, ["talaria/orphan.jpg"]);
+}
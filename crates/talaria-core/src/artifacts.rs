@@ -0,0 +1,116 @@
+//! Response artifacts: with an [`ArtifactDir`] attached to the client, the
+//! final response of every Hermes call is written to its own timestamped
+//! JSON file, together with the request that produced it, its `request_id`
+//! and how long it took. Long listing runs and quotes are archived as they
+//! happen, for later comparison or to attach to a support ticket.
+//!
+//! Secrets are scrubbed the same way as in [`crate::vcr`] cassettes.
+
+use crate::error::Result;
+use crate::metrics;
+use crate::vcr::{RawResponse, RecordedRequest, RecordedResponse, scrub_json, scrub_path};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// One saved call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Artifact {
+    pub saved_at: String,
+    /// The `x-request-id` header, or the `request_id` of an error body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub attempts: usize,
+    pub elapsed_ms: u64,
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// Where artifacts go; clones of the client share one sequence, so files
+/// sort in the order the calls finished.
+#[derive(Debug, Clone)]
+pub struct ArtifactDir {
+    dir: PathBuf,
+    seq: Arc<AtomicU64>,
+}
+
+impl ArtifactDir {
+    /// Creates `dir` up front, so a bad path fails before any call is made.
+    pub fn create(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            seq: Arc::new(AtomicU64::new(1)),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes `<UTC time>-<seq>-<method>-<endpoint>.json` and returns its path.
+    pub(crate) fn save(
+        &self,
+        method: &Method,
+        path: &str,
+        body: Option<&[u8]>,
+        response: &RawResponse,
+        attempts: usize,
+        elapsed: Duration,
+    ) -> Result<PathBuf> {
+        let now = chrono::Utc::now();
+        let response = RecordedResponse::from_raw(response);
+        let request_id = response.headers.get("x-request-id").cloned().or_else(|| {
+            response
+                .body
+                .as_ref()
+                .and_then(|body| body["request_id"].as_str())
+                .map(str::to_string)
+        });
+        let artifact = Artifact {
+            saved_at: now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            request_id,
+            attempts,
+            elapsed_ms: elapsed.as_millis() as u64,
+            request: RecordedRequest {
+                method: method.to_string(),
+                path: scrub_path(path),
+                body: body
+                    .and_then(|bytes| serde_json::from_slice(bytes).ok())
+                    .map(scrub_json),
+            },
+            response,
+        };
+        let seq = self.seq.fetch_add(1, Ordering::Relaxed);
+        let name = format!(
+            "{}-{seq:04}-{}-{}.json",
+            now.format("%Y%m%dT%H%M%S%.3fZ"),
+            method.as_str().to_ascii_lowercase(),
+            file_slug(path)
+        );
+        let file = self.dir.join(name);
+        fs::write(&file, serde_json::to_vec_pretty(&artifact)?)?;
+        Ok(file)
+    }
+}
+
+/// The endpoint (ids folded, query dropped) as a file name fragment:
+/// `v1/products/<uuid>/media` becomes `v1-products-id-media`.
+fn file_slug(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or_default();
+    let slug = metrics::endpoint_label(path)
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "root".to_string()
+    } else {
+        slug
+    }
+}
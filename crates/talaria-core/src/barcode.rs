@@ -0,0 +1,289 @@
+//! Barcode detection on captured frames, so an item's UPC/EAN can become its
+//! SKU and be handed to enrichment.
+//!
+//! The decoder reads EAN-13, UPC-A and EAN-8 along horizontal scanlines, in
+//! either direction, from an 8-bit grayscale buffer. Reading image files needs
+//! the `barcode` feature (it pulls in the `image` decoder).
+
+use crate::error::{Error, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Whether [`scan_file`] can decode images in this build.
+pub const SCAN_FILES_ENABLED: bool = cfg!(feature = "barcode");
+
+/// Scanlines sampled across the frame height.
+const SCANLINES: usize = 32;
+/// Minimum spread between darkest and lightest pixel for a row to be read.
+const MIN_CONTRAST: u8 = 48;
+/// Quiet zone required before and after a symbol, in modules.
+const QUIET_MODULES: f32 = 3.0;
+/// Largest summed width error (in modules) accepted for one digit.
+const MAX_DIGIT_ERROR: f32 = 1.6;
+
+/// Element widths of the left-hand odd-parity ("L") digit codes, space first.
+/// Even-parity ("G") codes are these reversed; right-hand codes are the same
+/// widths starting with a bar.
+const L_CODES: [[u8; 4]; 10] = [
+    [3, 2, 1, 1],
+    [2, 2, 2, 1],
+    [2, 1, 2, 2],
+    [1, 4, 1, 1],
+    [1, 1, 3, 2],
+    [1, 2, 3, 1],
+    [1, 1, 1, 4],
+    [1, 3, 1, 2],
+    [1, 2, 1, 3],
+    [3, 1, 1, 2],
+];
+
+/// EAN-13 first digit, encoded by which of the six left digits use G codes
+/// (bit 5 = first left digit).
+const FIRST_DIGIT_PARITY: [u8; 10] = [
+    0b000000, 0b001011, 0b001101, 0b001110, 0b010011, 0b011001, 0b011100, 0b010101, 0b010110,
+    0b011010,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Symbology {
+    Ean13,
+    UpcA,
+    Ean8,
+}
+
+impl Symbology {
+    pub fn label(self) -> &'static str {
+        match self {
+            Symbology::Ean13 => "EAN-13",
+            Symbology::UpcA => "UPC-A",
+            Symbology::Ean8 => "EAN-8",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Barcode {
+    pub symbology: Symbology,
+    /// Digits including the check digit (12 for UPC-A).
+    pub value: String,
+}
+
+impl Barcode {
+    /// Line added to a product's context text, e.g. `UPC-A: 036000291452`.
+    pub fn context_line(&self) -> String {
+        format!("{}: {}", self.symbology.label(), self.value)
+    }
+
+    /// `context` with [`Self::context_line`] appended, unless the code is
+    /// already mentioned.
+    pub fn add_to_context(&self, context: &str) -> String {
+        let context = context.trim_end();
+        if context.contains(&self.value) {
+            context.to_string()
+        } else if context.is_empty() {
+            self.context_line()
+        } else {
+            format!("{context}\n{}", self.context_line())
+        }
+    }
+}
+
+/// Decodes the most frequently read barcode in a grayscale frame.
+pub fn scan_luma(width: usize, height: usize, luma: &[u8]) -> Option<Barcode> {
+    if width == 0 || height == 0 || luma.len() < width * height {
+        return None;
+    }
+    let step = (height / SCANLINES).max(1);
+    let mut hits: HashMap<Barcode, usize> = HashMap::new();
+    for y in (step / 2..height).step_by(step) {
+        let row = &luma[y * width..(y + 1) * width];
+        let reversed: Vec<u8> = row.iter().rev().copied().collect();
+        for line in [row, reversed.as_slice()] {
+            if let Some(code) = decode_row(line) {
+                *hits.entry(code).or_default() += 1;
+            }
+        }
+    }
+    hits.into_iter()
+        .max_by(|(a, n), (b, m)| n.cmp(m).then_with(|| b.value.cmp(&a.value)))
+        .map(|(code, _)| code)
+}
+
+/// Decodes a barcode from an image file (JPEG/PNG).
+#[cfg(feature = "barcode")]
+pub fn scan_file(path: &Path) -> Result<Option<Barcode>> {
+    let image = image::open(path)
+        .map_err(|err| Error::UnsupportedImage(format!("{}: {err}", path.display())))?
+        .to_luma8();
+    Ok(scan_luma(
+        image.width() as usize,
+        image.height() as usize,
+        image.as_raw(),
+    ))
+}
+
+#[cfg(not(feature = "barcode"))]
+pub fn scan_file(path: &Path) -> Result<Option<Barcode>> {
+    Err(Error::UnsupportedImage(format!(
+        "{} (barcode scanning needs the `barcode` feature)",
+        path.display()
+    )))
+}
+
+/// Run-length encodes one row against a midpoint threshold. Even indices are
+/// spaces and odd indices bars; the first space may be empty.
+fn runs(row: &[u8]) -> Option<Vec<u32>> {
+    let (min, max) = row
+        .iter()
+        .fold((u8::MAX, u8::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    if max.saturating_sub(min) < MIN_CONTRAST {
+        return None;
+    }
+    let threshold = (u16::from(min) + u16::from(max)) / 2;
+    let mut runs = Vec::new();
+    let (mut bar, mut len) = (false, 0u32);
+    for &value in row {
+        let is_bar = u16::from(value) < threshold;
+        if is_bar == bar {
+            len += 1;
+        } else {
+            runs.push(len);
+            bar = is_bar;
+            len = 1;
+        }
+    }
+    runs.push(len);
+    Some(runs)
+}
+
+fn decode_row(row: &[u8]) -> Option<Barcode> {
+    let runs = runs(row)?;
+    (1..runs.len())
+        .step_by(2)
+        .find_map(|start| decode_ean13(&runs, start).or_else(|| decode_ean8(&runs, start)))
+}
+
+/// Guard bars are all one module wide.
+fn is_guard(runs: &[u32], module: f32) -> bool {
+    runs.iter()
+        .all(|&r| (r as f32 - module).abs() <= module * 0.5 + 0.5)
+}
+
+fn has_quiet_zones(runs: &[u32], start: usize, len: usize, module: f32) -> bool {
+    let quiet = module * QUIET_MODULES;
+    runs[start - 1] as f32 >= quiet && runs.get(start + len).is_none_or(|&r| r as f32 >= quiet)
+}
+
+/// Matches four element widths against the digit codes. Returns the digit and
+/// whether it used an even-parity (G) code.
+fn decode_digit(runs: &[u32], module: f32) -> Option<(u8, bool)> {
+    let total: u32 = runs.iter().sum();
+    if (total as f32 - 7.0 * module).abs() > 7.0 * module * 0.3 {
+        return None;
+    }
+    let scale = 7.0 / total as f32;
+    let mut best = None;
+    let mut best_error = MAX_DIGIT_ERROR;
+    for (digit, widths) in L_CODES.iter().enumerate() {
+        let mut reversed = *widths;
+        reversed.reverse();
+        for (even, widths) in [(false, widths), (true, &reversed)] {
+            let error: f32 = runs
+                .iter()
+                .zip(widths)
+                .map(|(&r, &w)| (r as f32 * scale - f32::from(w)).abs())
+                .sum();
+            if error < best_error {
+                best_error = error;
+                best = Some((digit as u8, even));
+            }
+        }
+    }
+    best
+}
+
+fn decode_ean13(runs: &[u32], start: usize) -> Option<Barcode> {
+    const LEN: usize = 59;
+    let symbol = runs.get(start..start + LEN)?;
+    let module = symbol.iter().sum::<u32>() as f32 / 95.0;
+    if !has_quiet_zones(runs, start, LEN, module)
+        || !is_guard(&symbol[..3], module)
+        || !is_guard(&symbol[27..32], module)
+        || !is_guard(&symbol[56..], module)
+    {
+        return None;
+    }
+    let mut digits = vec![0u8];
+    let mut parity = 0u8;
+    for chunk in symbol[3..27].chunks(4) {
+        let (digit, even) = decode_digit(chunk, module)?;
+        digits.push(digit);
+        parity = (parity << 1) | u8::from(even);
+    }
+    for chunk in symbol[32..56].chunks(4) {
+        let (digit, even) = decode_digit(chunk, module)?;
+        if even {
+            return None;
+        }
+        digits.push(digit);
+    }
+    digits[0] = FIRST_DIGIT_PARITY.iter().position(|&p| p == parity)? as u8;
+    if !check_digit_ok(&digits) {
+        return None;
+    }
+    let value: String = digits.iter().map(|d| char::from(b'0' + d)).collect();
+    Some(match value.strip_prefix('0') {
+        Some(upc) => Barcode {
+            symbology: Symbology::UpcA,
+            value: upc.to_string(),
+        },
+        None => Barcode {
+            symbology: Symbology::Ean13,
+            value,
+        },
+    })
+}
+
+fn decode_ean8(runs: &[u32], start: usize) -> Option<Barcode> {
+    const LEN: usize = 43;
+    let symbol = runs.get(start..start + LEN)?;
+    let module = symbol.iter().sum::<u32>() as f32 / 67.0;
+    if !has_quiet_zones(runs, start, LEN, module)
+        || !is_guard(&symbol[..3], module)
+        || !is_guard(&symbol[19..24], module)
+        || !is_guard(&symbol[40..], module)
+    {
+        return None;
+    }
+    let mut digits = Vec::with_capacity(8);
+    for chunk in symbol[3..19].chunks(4).chain(symbol[24..40].chunks(4)) {
+        let (digit, even) = decode_digit(chunk, module)?;
+        if even {
+            return None;
+        }
+        digits.push(digit);
+    }
+    if !check_digit_ok(&digits) {
+        return None;
+    }
+    Some(Barcode {
+        symbology: Symbology::Ean8,
+        value: digits.iter().map(|d| char::from(b'0' + d)).collect(),
+    })
+}
+
+/// GS1 mod-10 check: weights alternate 3 and 1 from the right, check digit
+/// excluded.
+fn check_digit_ok(digits: &[u8]) -> bool {
+    let Some((&check, body)) = digits.split_last() else {
+        return false;
+    };
+    let sum: u32 = body
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| u32::from(d) * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    (10 - sum % 10) % 10 == u32::from(check)
+}
@@ -0,0 +1,112 @@
+//! A synchronous facade over [`HermesClient`] for tools that do not run an
+//! async runtime (build scripts, sync GUIs). Each client owns a small tokio
+//! runtime and blocks the calling thread on it, like `reqwest::blocking`.
+//!
+//! Do not call it from inside an async runtime: blocking there panics. Use
+//! [`HermesClient`] directly instead.
+//!
+//! ```no_run
+//! use talaria_core::blocking::HermesClientBlocking;
+//! use talaria_core::config::Config;
+//!
+//! let client = HermesClientBlocking::new(Config::load()?)?;
+//! println!("{}", client.health()?.status);
+//! # Ok::<(), talaria_core::Error>(())
+//! ```
+
+use crate::client::HermesClient;
+use crate::config::Config;
+use crate::error::Result;
+use crate::models::*;
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+
+/// Declares blocking wrappers that forward to the async method of the same
+/// name.
+macro_rules! blocking {
+    ($(
+        $(#[$meta:meta])*
+        fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;
+    )*) => {
+        $(
+            $(#[$meta])*
+            pub fn $name(&self $(, $arg: $ty)*) -> Result<$ret> {
+                self.block_on(self.inner.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+#[derive(Debug)]
+pub struct HermesClientBlocking {
+    inner: HermesClient,
+    runtime: Runtime,
+}
+
+impl HermesClientBlocking {
+    pub fn new(config: Config) -> Result<Self> {
+        Self::from_async(HermesClient::new(config)?)
+    }
+
+    /// Wraps an already configured client (VCR, artifacts, org).
+    pub fn from_async(inner: HermesClient) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// The async client, for settings and endpoints not wrapped here.
+    pub fn inner(&self) -> &HermesClient {
+        &self.inner
+    }
+
+    /// Runs any future to completion on this client's runtime, e.g. an
+    /// endpoint of [`inner`](Self::inner) without a blocking wrapper.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    blocking! {
+        fn health(&self) -> HealthResponse;
+        fn hsuf_enrich(&self, body: &HsufEnrichRequest, include_usage: bool) -> HsufEnrichResponse;
+        fn create_listing(&self, body: &PublicListingRequest) -> ListingResponse;
+        fn enqueue_listing(&self, body: &PublicListingRequest) -> EnqueueResponse;
+        /// See [`HermesClient::create_listing_idempotent`].
+        fn create_listing_idempotent(
+            &self,
+            body: &PublicListingRequest,
+            idempotency_key: &str
+        ) -> ListingResponse;
+        /// See [`HermesClient::enqueue_listing_idempotent`].
+        fn enqueue_listing_idempotent(
+            &self,
+            body: &PublicListingRequest,
+            idempotency_key: &str
+        ) -> EnqueueResponse;
+        fn continue_listing(&self, body: &ContinueRequest) -> ListingResponse;
+        fn publish_listing_draft(&self, body: &ListingDraftRequest) -> ListingResponse;
+        fn get_job_status(&self, id: &str) -> JobInfo;
+        fn pricing_quote(&self, body: &PublicListingRequest) -> PricingQuote;
+        fn comparable_prices(&self, body: &ComparablePricesRequest) -> ComparablePricesResponse;
+        fn usage(
+            &self,
+            org_id: Option<String>,
+            from: Option<String>,
+            to: Option<String>
+        ) -> Vec<UsageSummary>;
+        fn create_media_upload(&self, body: &CreateUploadRequest) -> UploadSession;
+        fn complete_media_upload(
+            &self,
+            upload_id: &str,
+            body: Option<&CompleteUploadRequest>
+        ) -> CompleteUploadResponse;
+        fn abort_media_upload(&self, upload_id: &str) -> ();
+        fn update_media(&self, media_id: &str, body: &UpdateMediaRequest) -> Media;
+        fn delete_media(&self, media_id: &str) -> ();
+        fn list_product_media(&self, product_id: &str) -> ListMediaResponse;
+        fn list_products(&self) -> Vec<ProductRecord>;
+        fn create_product(&self, body: &ProductCreateRequest) -> ProductRecord;
+        fn get_product(&self, product_id: &str) -> ProductRecord;
+        fn update_product(&self, product_id: &str, body: &ProductUpdateRequest) -> ProductRecord;
+        fn delete_product(&self, product_id: &str) -> ();
+    }
+}
@@ -0,0 +1,69 @@
+//! Opening URLs in the default browser, and the marketplace URLs worth
+//! opening: the live listing for a listing id.
+
+use crate::models::MarketplaceId;
+use std::io;
+use std::process::{Command, Stdio};
+
+/// Opens `url` with the platform handler (`start`, `open` or `xdg-open`).
+/// Returns once the handler exits, which for most desktops is right after it
+/// hands the URL to the browser.
+pub fn open(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", "", url]);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    };
+    // The handler's own chatter would land on top of the TUI.
+    let status = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "browser handler exited with {status}"
+        )))
+    }
+}
+
+/// eBay site serving `marketplace`, or `None` for marketplaces this build
+/// doesn't know.
+pub fn marketplace_host(marketplace: &MarketplaceId) -> Option<&'static str> {
+    Some(match marketplace {
+        MarketplaceId::EbayUs => "www.ebay.com",
+        MarketplaceId::EbayUk => "www.ebay.co.uk",
+        MarketplaceId::EbayDe => "www.ebay.de",
+        MarketplaceId::EbayAu => "www.ebay.com.au",
+        MarketplaceId::EbayCa => "www.ebay.ca",
+        MarketplaceId::EbayFr => "www.ebay.fr",
+        MarketplaceId::EbayIt => "www.ebay.it",
+        MarketplaceId::EbayEs => "www.ebay.es",
+        MarketplaceId::EbayAt => "www.ebay.at",
+        MarketplaceId::EbayBe => "www.benl.ebay.be",
+        MarketplaceId::EbayCh => "www.ebay.ch",
+        MarketplaceId::EbayIe => "www.ebay.ie",
+        MarketplaceId::EbayNl => "www.ebay.nl",
+        MarketplaceId::EbayPl => "www.ebay.pl",
+        MarketplaceId::Unknown(_) => return None,
+    })
+}
+
+/// The live item page for an eBay `listing_id` on `marketplace`.
+pub fn listing_url(marketplace: &MarketplaceId, listing_id: &str) -> Option<String> {
+    let listing_id = listing_id.trim();
+    if listing_id.is_empty() {
+        return None;
+    }
+    marketplace_host(marketplace).map(|host| format!("https://{host}/itm/{listing_id}"))
+}
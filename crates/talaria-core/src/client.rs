@@ -1,20 +1,56 @@
-use crate::config::Config;
+use crate::artifacts::ArtifactDir;
+use crate::config::{Config, ProductBackend};
 use crate::error::{Error, Result};
+use crate::idempotency::IDEMPOTENCY_HEADER;
+use crate::metrics;
 use crate::models::*;
-use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderMap, HeaderValue, RETRY_AFTER};
+use crate::net::{self, NetworkConfig, TimeoutKind, Timeouts};
+use crate::simulate::MediaSimulator;
+#[cfg(feature = "native")]
+use crate::supabase::SupabaseProducts;
+use crate::vcr::{RawResponse, Vcr, VcrMode, relative_path};
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue, RETRY_AFTER};
 use reqwest::{Client, Method, StatusCode, Url};
-use serde::Serialize;
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::sleep;
 
 const USER_AGENT: &str = "talaria/0.1";
+/// Scopes API-key requests to one organization.
+pub const ORG_HEADER: &str = "X-Hermes-Org";
 
 #[derive(Clone)]
 pub struct HermesClient {
     http: Client,
     base_url: Url,
     api_key: Option<String>,
+    org_id: Option<String>,
+    network: Arc<NetworkConfig>,
+    timeouts: Timeouts,
+    /// Replaces every entry of `timeouts`; see [`HermesClient::with_timeout`].
+    timeout_override: Option<Duration>,
+    vcr: Option<Vcr>,
+    artifacts: Option<ArtifactDir>,
+    media_sim: Option<MediaSimulator>,
+    /// Takes the product calls when `product_backend = "supabase"`.
+    #[cfg(feature = "native")]
+    products_db: Option<SupabaseProducts>,
+}
+
+impl std::fmt::Debug for HermesClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HermesClient")
+            .field("base_url", &self.base_url.as_str())
+            .field("has_api_key", &self.api_key.is_some())
+            .field("org_id", &self.org_id)
+            .field("vcr", &self.vcr.as_ref().map(Vcr::mode))
+            .field("artifacts", &self.artifacts.as_ref().map(ArtifactDir::dir))
+            .field("simulated", &self.media_sim.is_some())
+            .field("product_backend", &self.product_backend())
+            .finish()
+    }
 }
 
 impl HermesClient {
@@ -29,19 +65,68 @@ impl HermesClient {
                 .map_err(|err| Error::InvalidConfig(format!("invalid base url: {err}")))?;
         }
 
-        let http = Client::builder()
-            .timeout(Duration::from_secs(180))
-            .user_agent(USER_AGENT)
-            .build()
-            .map_err(|err| Error::InvalidConfig(format!("failed to build client: {err}")))?;
+        let http = net::with_timeout(
+            config.network.client_builder()?,
+            config.timeouts.get(TimeoutKind::Default),
+        )
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|err| Error::InvalidConfig(format!("failed to build client: {err}")))?;
+        #[cfg(feature = "native")]
+        let products_db = match config.product_backend {
+            ProductBackend::Hermes => None,
+            ProductBackend::Supabase => Some(SupabaseProducts::from_config(&config)?),
+        };
 
         Ok(Self {
             http,
             base_url: base,
             api_key: config.api_key,
+            org_id: config.org_id,
+            network: Arc::new(config.network),
+            timeouts: config.timeouts,
+            timeout_override: None,
+            vcr: None,
+            artifacts: None,
+            media_sim: config.simulate.then(MediaSimulator::default),
+            #[cfg(feature = "native")]
+            products_db,
         })
     }
 
+    /// Where product calls go; see [`ProductBackend`].
+    pub fn product_backend(&self) -> ProductBackend {
+        #[cfg(feature = "native")]
+        if self.products_db.is_some() {
+            return ProductBackend::Supabase;
+        }
+        ProductBackend::Hermes
+    }
+
+    /// Media uploads are answered locally (see [`crate::simulate`]); callers
+    /// should skip the storage PUT to `upload_url`.
+    pub fn is_simulated(&self) -> bool {
+        self.media_sim.is_some()
+    }
+
+    /// Records every call to, or replays every call from, a cassette.
+    pub fn with_vcr(mut self, vcr: Vcr) -> Self {
+        self.vcr = Some(vcr);
+        self
+    }
+
+    /// Saves the final response of every call into `artifacts`.
+    pub fn with_artifacts(mut self, artifacts: ArtifactDir) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    fn is_replaying(&self) -> bool {
+        self.vcr
+            .as_ref()
+            .is_some_and(|vcr| vcr.mode() == VcrMode::Replay)
+    }
+
     pub fn base_url(&self) -> &Url {
         &self.base_url
     }
@@ -50,34 +135,69 @@ impl HermesClient {
         self.api_key.is_some()
     }
 
+    /// Proxy and TLS settings, for other clients that talk to the same
+    /// hosts (e.g. PUTs to upload URLs).
+    pub fn network(&self) -> &NetworkConfig {
+        &self.network
+    }
+
+    pub fn org_id(&self) -> Option<&str> {
+        self.org_id.as_deref()
+    }
+
+    /// Gives every call `timeout`, whatever its kind; for commands told how
+    /// long to wait (`--timeout`).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_override = Some(timeout);
+        self
+    }
+
+    /// How long a call of `kind` may take.
+    pub fn timeout(&self, kind: TimeoutKind) -> Duration {
+        self.timeout_override
+            .unwrap_or_else(|| self.timeouts.get(kind))
+    }
+
+    /// Acts for `org_id` instead of the configured organization (`None`: the
+    /// key's default).
+    pub fn with_org(mut self, org_id: Option<String>) -> Self {
+        #[cfg(feature = "native")]
+        {
+            self.products_db = self.products_db.map(|db| db.with_org(org_id.clone()));
+        }
+        self.org_id = org_id;
+        self
+    }
+
     pub async fn health(&self) -> Result<HealthResponse> {
-        self.request::<(), _>(Method::GET, "health", None, None, false, true)
+        self.send(
+            RequestSpec::get("health")
+                .public()
+                .timeout(TimeoutKind::Health),
+        )
+        .await
+    }
+
+    /// The OpenAPI document Hermes publishes, for comparing against
+    /// [`ApiSurface::known`](crate::spec::ApiSurface::known).
+    pub async fn openapi(&self) -> Result<serde_json::Value> {
+        self.send(RequestSpec::get(crate::spec::OPENAPI_PATH).public())
             .await
     }
 
     pub async fn device_auth_start(&self) -> Result<DeviceAuthStartResponse> {
-        self.request::<(), _>(
-            Method::POST,
-            "v1/auth/device/start",
-            None,
-            None,
-            false,
-            false,
-        )
-        .await
+        self.send(RequestSpec::post("v1/auth/device/start").public())
+            .await
     }
 
     pub async fn device_auth_poll(&self, device_code: &str) -> Result<DeviceAuthPollResponse> {
         let body = DeviceAuthPollRequest {
             device_code: device_code.to_string(),
         };
-        self.request(
-            Method::POST,
-            "v1/auth/device/poll",
-            None,
-            Some(&body),
-            false,
-            false,
+        self.send(
+            RequestSpec::post("v1/auth/device/poll")
+                .public()
+                .json(&body),
         )
         .await
     }
@@ -90,48 +210,88 @@ impl HermesClient {
         let body = UserApiKeyCreateRequest {
             name: name.to_string(),
         };
-        self.request_user_auth(Method::POST, "user/api-keys", Some(&body), access_token)
+        self.send(
+            RequestSpec::post("user/api-keys")
+                .bearer(access_token)
+                .json(&body),
+        )
+        .await
+    }
+
+    pub async fn list_user_api_keys(&self, access_token: &str) -> Result<Vec<UserApiKey>> {
+        self.send(RequestSpec::get("user/api-keys").bearer(access_token))
             .await
     }
 
+    pub async fn delete_user_api_key(&self, access_token: &str, key_id: &str) -> Result<()> {
+        let spec = RequestSpec::delete(format!("user/api-keys/{key_id}")).bearer(access_token);
+        self.send::<_, EmptyBody>(spec).await.map(drop)
+    }
+
     pub async fn hsuf_enrich(
         &self,
         body: &HsufEnrichRequest,
         include_usage: bool,
     ) -> Result<HsufEnrichResponse> {
-        let mut query = Vec::new();
+        let mut spec = RequestSpec::post("hsuf/enrich")
+            .timeout(TimeoutKind::Enrich)
+            .json(body);
         if include_usage {
-            query.push(("include_usage".to_string(), "true".to_string()));
+            spec = spec.query("include_usage", "true");
         }
-        self.request(
-            Method::POST,
-            "hsuf/enrich",
-            Some(query),
-            Some(body),
-            true,
-            false,
-        )
-        .await
+        self.send(spec).await
     }
 
     pub async fn create_listing(&self, body: &PublicListingRequest) -> Result<ListingResponse> {
-        self.request(Method::POST, "listings", None, Some(body), true, false)
-            .await
+        self.send(
+            RequestSpec::post("listings")
+                .timeout(TimeoutKind::Listings)
+                .json(body),
+        )
+        .await
     }
 
     pub async fn enqueue_listing(&self, body: &PublicListingRequest) -> Result<EnqueueResponse> {
-        self.request(Method::POST, "jobs/listings", None, Some(body), true, false)
+        self.send(RequestSpec::post("jobs/listings").json(body))
             .await
     }
 
+    /// Like `create_listing`, but sends an `Idempotency-Key` so the request can
+    /// be retried safely after a timeout without creating a second listing.
+    pub async fn create_listing_idempotent(
+        &self,
+        body: &PublicListingRequest,
+        idempotency_key: &str,
+    ) -> Result<ListingResponse> {
+        self.send(
+            RequestSpec::post("listings")
+                .timeout(TimeoutKind::Listings)
+                .json(body)
+                .idempotency_key(idempotency_key),
+        )
+        .await
+    }
+
+    /// Like `enqueue_listing`, but sends an `Idempotency-Key` so the request can
+    /// be retried safely after a timeout without enqueueing a duplicate job.
+    pub async fn enqueue_listing_idempotent(
+        &self,
+        body: &PublicListingRequest,
+        idempotency_key: &str,
+    ) -> Result<EnqueueResponse> {
+        self.send(
+            RequestSpec::post("jobs/listings")
+                .json(body)
+                .idempotency_key(idempotency_key),
+        )
+        .await
+    }
+
     pub async fn continue_listing(&self, body: &ContinueRequest) -> Result<ListingResponse> {
-        self.request(
-            Method::POST,
-            "listings/continue",
-            None,
-            Some(body),
-            true,
-            false,
+        self.send(
+            RequestSpec::post("listings/continue")
+                .timeout(TimeoutKind::Listings)
+                .json(body),
         )
         .await
     }
@@ -140,33 +300,26 @@ impl HermesClient {
         &self,
         body: &ListingDraftRequest,
     ) -> Result<ListingResponse> {
-        self.request(
-            Method::POST,
-            "listings/publish-draft",
-            None,
-            Some(body),
-            true,
-            false,
-        )
-        .await
+        self.send(RequestSpec::post("listings/publish-draft").json(body))
+            .await
     }
 
     pub async fn get_job_status(&self, id: &str) -> Result<JobInfo> {
-        let path = format!("jobs/{id}");
-        self.request(Method::GET, &path, None, Option::<&()>::None, true, true)
-            .await
+        self.send(RequestSpec::get(format!("jobs/{id}"))).await
     }
 
     pub async fn pricing_quote(&self, body: &PublicListingRequest) -> Result<PricingQuote> {
-        self.request(
-            Method::POST,
-            "v1/pricing/quote",
-            None,
-            Some(body),
-            true,
-            false,
-        )
-        .await
+        self.send(RequestSpec::post("v1/pricing/quote").json(body))
+            .await
+    }
+
+    /// Recent marketplace prices for items like the one described.
+    pub async fn comparable_prices(
+        &self,
+        body: &ComparablePricesRequest,
+    ) -> Result<ComparablePricesResponse> {
+        self.send(RequestSpec::post("v1/pricing/comparables").json(body))
+            .await
     }
 
     pub async fn usage(
@@ -175,37 +328,19 @@ impl HermesClient {
         from: Option<String>,
         to: Option<String>,
     ) -> Result<Vec<UsageSummary>> {
-        let mut query = Vec::new();
-        if let Some(org) = org_id {
-            query.push(("org_id".to_string(), org));
-        }
-        if let Some(f) = from {
-            query.push(("from".to_string(), f));
-        }
-        if let Some(t) = to {
-            query.push(("to".to_string(), t));
-        }
-        self.request(
-            Method::GET,
-            "v1/usage",
-            Some(query),
-            Option::<&()>::None,
-            true,
-            true,
-        )
-        .await
+        let spec = RequestSpec::get("v1/usage")
+            .query_opt("org_id", org_id.or_else(|| self.org_id.clone()))
+            .query_opt("from", from)
+            .query_opt("to", to);
+        self.send(spec).await
     }
 
     pub async fn create_media_upload(&self, body: &CreateUploadRequest) -> Result<UploadSession> {
-        self.request(
-            Method::POST,
-            "v1/media/uploads",
-            None,
-            Some(body),
-            true,
-            false,
-        )
-        .await
+        if let Some(sim) = &self.media_sim {
+            return Ok(sim.create_upload(body));
+        }
+        self.send(RequestSpec::post("v1/media/uploads").json(body))
+            .await
     }
 
     pub async fn complete_media_upload(
@@ -213,67 +348,71 @@ impl HermesClient {
         upload_id: &str,
         body: Option<&CompleteUploadRequest>,
     ) -> Result<CompleteUploadResponse> {
-        let path = format!("v1/media/uploads/{upload_id}/complete");
-        if let Some(b) = body {
-            self.request(Method::POST, &path, None, Some(b), true, false)
-                .await
-        } else {
-            self.request(Method::POST, &path, None, Option::<&()>::None, true, false)
-                .await
+        if let Some(sim) = &self.media_sim {
+            return sim.complete_upload(upload_id, body);
+        }
+        let spec = RequestSpec::post(format!("v1/media/uploads/{upload_id}/complete"));
+        match body {
+            Some(body) => self.send(spec.json(body)).await,
+            None => self.send(spec).await,
         }
     }
 
     pub async fn abort_media_upload(&self, upload_id: &str) -> Result<()> {
-        let path = format!("v1/media/uploads/{upload_id}/abort");
-        self.request_no_content(Method::POST, &path, None, Option::<&()>::None, true, false)
-            .await
+        if let Some(sim) = &self.media_sim {
+            sim.abort_upload(upload_id);
+            return Ok(());
+        }
+        let spec = RequestSpec::post(format!("v1/media/uploads/{upload_id}/abort"));
+        self.send::<_, EmptyBody>(spec).await.map(drop)
     }
 
     pub async fn delete_media(&self, media_id: &str) -> Result<()> {
-        let path = format!("v1/media/{media_id}");
-        self.request_no_content(
-            Method::DELETE,
-            &path,
-            None,
-            Option::<&()>::None,
-            true,
-            false,
-        )
-        .await
+        if let Some(sim) = &self.media_sim {
+            return sim.delete_media(media_id);
+        }
+        let spec = RequestSpec::delete(format!("v1/media/{media_id}"));
+        self.send::<_, EmptyBody>(spec).await.map(drop)
     }
 
     pub async fn update_media(&self, media_id: &str, body: &UpdateMediaRequest) -> Result<Media> {
-        let path = format!("v1/media/{media_id}");
-        self.request(Method::PATCH, &path, None, Some(body), true, false)
+        if let Some(sim) = &self.media_sim {
+            return sim.update_media(media_id, body);
+        }
+        self.send(RequestSpec::patch(format!("v1/media/{media_id}")).json(body))
             .await
     }
 
     pub async fn list_product_media(&self, product_id: &str) -> Result<ListMediaResponse> {
-        let path = format!("v1/products/{product_id}/media");
-        self.request(Method::GET, &path, None, Option::<&()>::None, true, true)
+        if let Some(sim) = &self.media_sim {
+            return Ok(sim.list_product_media(product_id));
+        }
+        self.send(RequestSpec::get(format!("v1/products/{product_id}/media")))
             .await
     }
 
     pub async fn list_products(&self) -> Result<Vec<ProductRecord>> {
-        self.request(
-            Method::GET,
-            "v1/products",
-            None,
-            Option::<&()>::None,
-            true,
-            true,
-        )
-        .await
+        #[cfg(feature = "native")]
+        if let Some(db) = &self.products_db {
+            return db.list_products().await;
+        }
+        self.send(RequestSpec::get("v1/products")).await
     }
 
     pub async fn create_product(&self, body: &ProductCreateRequest) -> Result<ProductRecord> {
-        self.request(Method::POST, "v1/products", None, Some(body), true, false)
-            .await
+        #[cfg(feature = "native")]
+        if let Some(db) = &self.products_db {
+            return db.create_product(body).await;
+        }
+        self.send(RequestSpec::post("v1/products").json(body)).await
     }
 
     pub async fn get_product(&self, product_id: &str) -> Result<ProductRecord> {
-        let path = format!("v1/products/{product_id}");
-        self.request(Method::GET, &path, None, Option::<&()>::None, true, true)
+        #[cfg(feature = "native")]
+        if let Some(db) = &self.products_db {
+            return db.get_product(product_id).await;
+        }
+        self.send(RequestSpec::get(format!("v1/products/{product_id}")))
             .await
     }
 
@@ -282,231 +421,446 @@ impl HermesClient {
         product_id: &str,
         body: &ProductUpdateRequest,
     ) -> Result<ProductRecord> {
-        let path = format!("v1/products/{product_id}");
-        self.request(Method::PATCH, &path, None, Some(body), true, false)
+        #[cfg(feature = "native")]
+        if let Some(db) = &self.products_db {
+            return db.update_product(product_id, body).await;
+        }
+        self.send(RequestSpec::patch(format!("v1/products/{product_id}")).json(body))
             .await
     }
 
     pub async fn delete_product(&self, product_id: &str) -> Result<()> {
-        let path = format!("v1/products/{product_id}");
-        self.request_no_content(
-            Method::DELETE,
-            &path,
-            None,
-            Option::<&()>::None,
-            true,
-            false,
-        )
-        .await
+        #[cfg(feature = "native")]
+        if let Some(db) = &self.products_db {
+            return db.delete_product(product_id).await;
+        }
+        let spec = RequestSpec::delete(format!("v1/products/{product_id}"));
+        self.send::<_, EmptyBody>(spec).await.map(drop)
     }
 
-    async fn request<B, T>(
-        &self,
-        method: Method,
-        path: &str,
-        query: Option<Vec<(String, String)>>,
-        body: Option<&B>,
-        auth: bool,
-        retry: bool,
-    ) -> Result<T>
+    /// Runs a request described by `spec` and decodes the response body as `T`
+    /// (use [`EmptyBody`] for endpoints that return nothing). Retries follow
+    /// the spec's [`RetryPolicy`]; errors carry the API error body and
+    /// `x-request-id` when Hermes sends them.
+    pub async fn send<B, T>(&self, spec: RequestSpec<'_, B>) -> Result<T>
     where
         B: Serialize + ?Sized,
         T: DeserializeOwned,
     {
         let mut url = self
             .base_url
-            .join(path)
+            .join(&spec.path)
             .map_err(|err| Error::InvalidConfig(format!("invalid url: {err}")))?;
-        if let Some(q) = &query {
+        if !spec.query.is_empty() {
             let mut pairs = url.query_pairs_mut();
-            for (key, value) in q {
+            for (key, value) in &spec.query {
                 pairs.append_pair(key, value);
             }
         }
 
-        if auth && self.api_key.is_none() {
-            return Err(Error::MissingApiKey {
-                endpoint: path.to_string(),
-            });
-        }
-
-        let mut attempts = 0usize;
-        let max_attempts = if retry { 3 } else { 1 };
-        loop {
-            attempts += 1;
-            let mut headers = HeaderMap::new();
-            headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-            if auth && let Some(key) = &self.api_key {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        match spec.auth {
+            Auth::None => {}
+            Auth::ApiKey => match &self.api_key {
                 // Keep the value out of logs.
+                Some(key) => {
+                    headers.insert(
+                        "X-Hermes-Key",
+                        HeaderValue::from_str(key).map_err(|_| {
+                            Error::InvalidConfig("invalid characters in api key".into())
+                        })?,
+                    );
+                    if let Some(org_id) = &self.org_id {
+                        headers.insert(
+                            ORG_HEADER,
+                            HeaderValue::from_str(org_id).map_err(|_| {
+                                Error::InvalidConfig("invalid characters in org id".into())
+                            })?,
+                        );
+                    }
+                }
+                // Replays never reach Hermes, so offline demos need no key.
+                None if self.is_replaying() => {}
+                None => {
+                    return Err(Error::MissingApiKey {
+                        endpoint: spec.path.into_owned(),
+                    });
+                }
+            },
+            Auth::Bearer(token) => {
                 headers.insert(
-                    "X-Hermes-Key",
-                    HeaderValue::from_str(key).map_err(|_| {
-                        Error::InvalidConfig("invalid characters in api key".into())
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| {
+                        Error::InvalidConfig("invalid characters in access token".into())
                     })?,
                 );
             }
+        }
+        if let Some(key) = spec.idempotency_key {
+            headers.insert(
+                IDEMPOTENCY_HEADER,
+                HeaderValue::from_str(key).map_err(|_| {
+                    Error::InvalidConfig("invalid characters in idempotency key".into())
+                })?,
+            );
+        }
 
-            let mut req = self
-                .http
-                .request(method.clone(), url.clone())
-                .headers(headers);
-            if let Some(b) = body {
-                req = req.json(b);
-            }
+        let body = spec.body.map(serde_json::to_vec).transpose()?;
 
-            let response = req.send().await?;
-            let status = response.status();
-            let headers = response.headers().clone();
-            if status.is_success() {
-                let parsed = response.json::<T>().await?;
-                return Ok(parsed);
-            }
-
-            let request_id = headers
+        let endpoint = metrics::endpoint_label(&spec.path);
+        let timeout = self.timeout(spec.timeout);
+        let max_attempts = spec.retry.max_attempts.max(1);
+        let mut attempts = 0usize;
+        let first_started = clock::Instant::now();
+        loop {
+            attempts += 1;
+            let started = clock::Instant::now();
+            let result = self
+                .execute(&spec.method, &url, &headers, body.as_deref(), timeout)
+                .await;
+            record_attempt(&endpoint, result.as_ref().ok(), started.elapsed());
+            let response = match result {
+                Ok(response) => response,
+                // With an idempotency key the server dedupes, so a timeout or
+                // dropped connection is safe to retry.
+                Err(Error::Http(err)) if spec.retry.transport_errors && attempts < max_attempts => {
+                    tracing::info!(
+                        method = %spec.method,
+                        endpoint = %endpoint,
+                        attempt = attempts,
+                        error = %err,
+                        "retrying hermes request after transport error"
+                    );
+                    metrics::global().incr(
+                        metrics::HERMES_RETRIES,
+                        &[("endpoint", endpoint.as_str())],
+                        1,
+                    );
+                    clock::sleep(compute_backoff(attempts, None)).await;
+                    continue;
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        method = %spec.method,
+                        endpoint = %endpoint,
+                        attempts,
+                        error = %err,
+                        "hermes request failed"
+                    );
+                    return Err(err);
+                }
+            };
+            let status = response.status;
+            let request_id = response
+                .headers
                 .get("x-request-id")
                 .and_then(|v| v.to_str().ok())
                 .map(|s| s.to_string());
+            let accepted = match spec.expect_status {
+                Some(expected) => status == expected,
+                None => status.is_success(),
+            };
+            let retrying = !accepted && is_retryable(status) && attempts < max_attempts;
+            if let Some(artifacts) = self.artifacts.as_ref().filter(|_| !retrying) {
+                let path = relative_path(&self.base_url, &url);
+                let elapsed = first_started.elapsed();
+                // Archiving is best effort; the call itself already happened.
+                if let Err(err) = artifacts.save(
+                    &spec.method,
+                    &path,
+                    body.as_deref(),
+                    &response,
+                    attempts,
+                    elapsed,
+                ) {
+                    tracing::warn!(endpoint = %endpoint, error = %err, "saving response artifact failed");
+                }
+            }
+            if accepted {
+                tracing::debug!(
+                    method = %spec.method,
+                    endpoint = %endpoint,
+                    status = status.as_u16(),
+                    request_id = request_id.as_deref().unwrap_or_default(),
+                    attempts,
+                    "hermes request"
+                );
+                return decode_body(&response.body);
+            }
 
-            let text = response.text().await.unwrap_or_default();
+            let text = String::from_utf8_lossy(&response.body).into_owned();
             let api_error = serde_json::from_str::<ApiError>(&text).ok();
-            let should_retry = retry && is_retryable(status);
-
-            if should_retry && attempts < max_attempts {
-                let delay = compute_backoff(attempts, headers.get(RETRY_AFTER));
-                sleep(delay).await;
+            if retrying {
+                tracing::info!(
+                    method = %spec.method,
+                    endpoint = %endpoint,
+                    status = status.as_u16(),
+                    request_id = request_id.as_deref().unwrap_or_default(),
+                    attempt = attempts,
+                    "retrying hermes request"
+                );
+                metrics::global().incr(
+                    metrics::HERMES_RETRIES,
+                    &[("endpoint", endpoint.as_str())],
+                    1,
+                );
+                let delay = compute_backoff(attempts, response.headers.get(RETRY_AFTER));
+                clock::sleep(delay).await;
                 continue;
             }
 
+            tracing::warn!(
+                method = %spec.method,
+                endpoint = %endpoint,
+                status = status.as_u16(),
+                request_id = request_id
+                    .as_deref()
+                    .or(api_error.as_ref().and_then(|e| e.request_id.as_deref()))
+                    .unwrap_or_default(),
+                attempts,
+                error = api_error.as_ref().map(|e| e.error.as_str()).unwrap_or_default(),
+                "hermes request failed"
+            );
             return Err(Error::from_api(status, api_error, Some(text), request_id));
         }
     }
 
-    async fn request_no_content<B>(
+    /// One round trip: from the cassette when replaying, otherwise over HTTP
+    /// (and into the cassette when recording).
+    async fn execute(
         &self,
-        method: Method,
-        path: &str,
-        query: Option<Vec<(String, String)>>,
-        body: Option<&B>,
-        auth: bool,
-        retry: bool,
-    ) -> Result<()>
-    where
-        B: Serialize + ?Sized,
-    {
-        let mut url = self
-            .base_url
-            .join(path)
-            .map_err(|err| Error::InvalidConfig(format!("invalid url: {err}")))?;
-        if let Some(q) = &query {
-            let mut pairs = url.query_pairs_mut();
-            for (key, value) in q {
-                pairs.append_pair(key, value);
-            }
+        method: &Method,
+        url: &Url,
+        headers: &HeaderMap,
+        body: Option<&[u8]>,
+        timeout: Duration,
+    ) -> Result<RawResponse> {
+        let path = relative_path(&self.base_url, url);
+        if let Some(vcr) = self
+            .vcr
+            .as_ref()
+            .filter(|vcr| vcr.mode() == VcrMode::Replay)
+        {
+            return vcr.replay_response(method, &path);
         }
 
-        if auth && self.api_key.is_none() {
-            return Err(Error::MissingApiKey {
-                endpoint: path.to_string(),
-            });
+        let mut req = self
+            .http
+            .request(method.clone(), url.clone())
+            .headers(headers.clone())
+            .timeout(timeout);
+        if let Some(body) = body {
+            req = req
+                .header(CONTENT_TYPE, "application/json")
+                .body(body.to_vec());
+        }
+        let response = req.send().await?;
+        let raw = RawResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?.to_vec(),
+        };
+        if let Some(vcr) = &self.vcr {
+            vcr.record_interaction(method, &path, body, &raw)?;
         }
+        Ok(raw)
+    }
+}
 
-        let mut attempts = 0usize;
-        let max_attempts = if retry { 3 } else { 1 };
-        loop {
-            attempts += 1;
-            let mut headers = HeaderMap::new();
-            headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-            if auth && let Some(key) = &self.api_key {
-                headers.insert(
-                    "X-Hermes-Key",
-                    HeaderValue::from_str(key).map_err(|_| {
-                        Error::InvalidConfig("invalid characters in api key".into())
-                    })?,
-                );
-            }
+/// How a request authenticates against Hermes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Auth<'a> {
+    /// Public endpoint; no credentials are sent.
+    None,
+    /// `X-Hermes-Key` from the config; fails early with `MissingApiKey`.
+    ApiKey,
+    /// `Authorization: Bearer` with a user access token (device login).
+    Bearer(&'a str),
+}
 
-            let mut req = self
-                .http
-                .request(method.clone(), url.clone())
-                .headers(headers);
-            if let Some(b) = body {
-                req = req.json(b);
-            }
+/// When a failed request is tried again. 429 and 5xx responses are retried
+/// up to `max_attempts`; transport errors only when `transport_errors` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: usize,
+    pub transport_errors: bool,
+}
 
-            let response = req.send().await?;
-            let status = response.status();
-            let headers = response.headers().clone();
-            if status.is_success() {
-                return Ok(());
-            }
+impl RetryPolicy {
+    pub const NEVER: RetryPolicy = RetryPolicy {
+        max_attempts: 1,
+        transport_errors: false,
+    };
+    pub const DEFAULT: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        transport_errors: false,
+    };
+    /// For requests the server dedupes (idempotency key): timeouts are safe too.
+    pub const IDEMPOTENT: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        transport_errors: true,
+    };
+}
 
-            let request_id = headers
-                .get("x-request-id")
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.to_string());
+/// Describes one Hermes call for [`HermesClient::send`].
+///
+/// Defaults: API-key auth, any 2xx accepted, and retries only for `GET`
+/// (mutations are not repeated unless an idempotency key is attached).
+#[derive(Debug, Clone)]
+pub struct RequestSpec<'a, B: ?Sized = ()> {
+    method: Method,
+    path: Cow<'a, str>,
+    query: Vec<(String, String)>,
+    body: Option<&'a B>,
+    auth: Auth<'a>,
+    retry: RetryPolicy,
+    idempotency_key: Option<&'a str>,
+    expect_status: Option<StatusCode>,
+    timeout: TimeoutKind,
+}
 
-            let text = response.text().await.unwrap_or_default();
-            let api_error = serde_json::from_str::<ApiError>(&text).ok();
-            let should_retry = retry && is_retryable(status);
+impl<'a> RequestSpec<'a> {
+    pub fn new(method: Method, path: impl Into<Cow<'a, str>>) -> Self {
+        let retry = if method == Method::GET {
+            RetryPolicy::DEFAULT
+        } else {
+            RetryPolicy::NEVER
+        };
+        Self {
+            method,
+            path: path.into(),
+            query: Vec::new(),
+            body: None,
+            auth: Auth::ApiKey,
+            retry,
+            idempotency_key: None,
+            expect_status: None,
+            timeout: TimeoutKind::Default,
+        }
+    }
 
-            if should_retry && attempts < max_attempts {
-                let delay = compute_backoff(attempts, headers.get(RETRY_AFTER));
-                sleep(delay).await;
-                continue;
-            }
+    pub fn get(path: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(Method::GET, path)
+    }
 
-            return Err(Error::from_api(status, api_error, Some(text), request_id));
-        }
+    pub fn post(path: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(Method::POST, path)
     }
 
-    async fn request_user_auth<B, T>(
-        &self,
-        method: Method,
-        path: &str,
-        body: Option<&B>,
-        access_token: &str,
-    ) -> Result<T>
-    where
-        B: Serialize + ?Sized,
-        T: DeserializeOwned,
-    {
-        let url = self
-            .base_url
-            .join(path)
-            .map_err(|err| Error::InvalidConfig(format!("invalid url: {err}")))?;
+    pub fn patch(path: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(Method::PATCH, path)
+    }
 
-        let mut headers = HeaderMap::new();
-        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", access_token))
-                .map_err(|_| Error::InvalidConfig("invalid characters in access token".into()))?,
-        );
-
-        let mut req = self.http.request(method, url).headers(headers);
-        if let Some(b) = body {
-            req = req.json(b);
+    pub fn delete(path: impl Into<Cow<'a, str>>) -> Self {
+        Self::new(Method::DELETE, path)
+    }
+}
+
+impl<'a, B: ?Sized> RequestSpec<'a, B> {
+    /// Sends `body` as JSON.
+    pub fn json<C: Serialize + ?Sized>(self, body: &'a C) -> RequestSpec<'a, C> {
+        RequestSpec {
+            method: self.method,
+            path: self.path,
+            query: self.query,
+            body: Some(body),
+            auth: self.auth,
+            retry: self.retry,
+            idempotency_key: self.idempotency_key,
+            expect_status: self.expect_status,
+            timeout: self.timeout,
         }
+    }
 
-        let response = req.send().await?;
-        let status = response.status();
-        let headers = response.headers().clone();
-        if status.is_success() {
-            let parsed = response.json::<T>().await?;
-            return Ok(parsed);
+    pub fn query(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.query.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Adds the pair only when `value` is set.
+    pub fn query_opt(self, key: &str, value: Option<impl Into<String>>) -> Self {
+        match value {
+            Some(value) => self.query(key, value),
+            None => self,
         }
+    }
+
+    pub fn auth(mut self, auth: Auth<'a>) -> Self {
+        self.auth = auth;
+        self
+    }
 
-        let request_id = headers
-            .get("x-request-id")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
+    pub fn public(self) -> Self {
+        self.auth(Auth::None)
+    }
 
-        let text = response.text().await.unwrap_or_default();
-        let api_error = serde_json::from_str::<ApiError>(&text).ok();
-        Err(Error::from_api(status, api_error, Some(text), request_id))
+    pub fn bearer(self, token: &'a str) -> Self {
+        self.auth(Auth::Bearer(token))
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Attaches an `Idempotency-Key` and switches to [`RetryPolicy::IDEMPOTENT`].
+    pub fn idempotency_key(mut self, key: &'a str) -> Self {
+        self.idempotency_key = Some(key);
+        self.retry = RetryPolicy::IDEMPOTENT;
+        self
+    }
+
+    /// Only `status` counts as success; anything else becomes an API error.
+    pub fn expect_status(mut self, status: StatusCode) -> Self {
+        self.expect_status = Some(status);
+        self
+    }
+
+    /// Runs under the client's timeout for `kind` instead of the default one.
+    pub fn timeout(mut self, kind: TimeoutKind) -> Self {
+        self.timeout = kind;
+        self
     }
 }
 
+/// Response type for endpoints that reply with no body (or one we ignore).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmptyBody;
+
+impl<'de> Deserialize<'de> for EmptyBody {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        IgnoredAny::deserialize(deserializer)?;
+        Ok(EmptyBody)
+    }
+}
+
+/// An empty body decodes as JSON `null`, so `EmptyBody` and `Option<T>` accept it.
+fn decode_body<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(serde_json::from_slice(b"null")?);
+    }
+    Ok(serde_json::from_slice(bytes)?)
+}
+
+/// Counts one round trip; `response` is `None` for transport errors.
+fn record_attempt(endpoint: &str, response: Option<&RawResponse>, elapsed: Duration) {
+    let status = response.map_or_else(|| "error".to_string(), |r| r.status.as_u16().to_string());
+    let registry = metrics::global();
+    registry.incr(
+        metrics::HERMES_REQUESTS,
+        &[("endpoint", endpoint), ("status", &status)],
+        1,
+    );
+    registry.observe(
+        metrics::HERMES_REQUEST_DURATION,
+        &[("endpoint", endpoint)],
+        elapsed,
+    );
+}
+
 fn is_retryable(status: StatusCode) -> bool {
     status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
 }
@@ -521,3 +875,53 @@ fn compute_backoff(attempt: usize, retry_after: Option<&HeaderValue>) -> Duratio
     let base = 500u64 * (1 << (attempt.saturating_sub(1)).min(4));
     Duration::from_millis(base)
 }
+
+/// Timers for the retry loop. `std::time::Instant` and tokio's timer panic in
+/// the browser, where the JS host's clock and `setTimeout` stand in.
+mod clock {
+    #[cfg(target_arch = "wasm32")]
+    use std::time::Duration;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen::JsCast;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use std::time::Instant;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use tokio::time::sleep;
+
+    #[cfg(target_arch = "wasm32")]
+    #[derive(Debug, Clone, Copy)]
+    pub struct Instant(f64);
+
+    #[cfg(target_arch = "wasm32")]
+    impl Instant {
+        pub fn now() -> Self {
+            Self(js_sys::Date::now())
+        }
+
+        pub fn elapsed(&self) -> Duration {
+            Duration::from_secs_f64((js_sys::Date::now() - self.0).max(0.0) / 1000.0)
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub async fn sleep(duration: Duration) {
+        let millis = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+        let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+            let global = js_sys::global();
+            let set_timeout = js_sys::Reflect::get(&global, &"setTimeout".into())
+                .ok()
+                .and_then(|f| f.dyn_into::<js_sys::Function>().ok());
+            match set_timeout {
+                Some(set_timeout) => {
+                    let _ = set_timeout.call2(&global, &resolve, &millis.into());
+                }
+                // No timer in this host: retry straight away.
+                None => {
+                    let _ = resolve.call0(&global);
+                }
+            }
+        });
+        let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+    }
+}
@@ -0,0 +1,140 @@
+//! Gray-card colour correction: red, green and blue gains read off a neutral
+//! gray card filling the centre of a frame, and applied to later captures so
+//! colours hold when the lighting changes.
+//!
+//! The gains live in the `[white_balance]` config table. The TUI corrects
+//! the frames it saves from the live stream and records the gains on the
+//! session, so a batch step can correct tethered JPEGs the same way.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// sRGB value of 18% gray, what a levelled card reads as.
+const LEVEL_TARGET: f64 = 118.0;
+/// Below this a channel is too dark to measure; above it, clipped.
+const MIN_CHANNEL: f64 = 20.0;
+const MAX_CHANNEL: f64 = 245.0;
+/// Largest channel mean over the smallest one that still passes for gray.
+const MAX_CAST: f64 = 2.5;
+/// Standard deviation of the patch's luma beyond which it is not one card.
+const MAX_PATCH_DEVIATION: f64 = 24.0;
+/// Gains outside this range mean the calibration frame was not a gray card.
+const GAIN_RANGE: std::ops::RangeInclusive<f64> = 0.25..=4.0;
+
+/// The `[white_balance]` config table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct WhiteBalance {
+    /// Red, green and blue multipliers; set by calibrating from a gray card.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gains: Option<[f64; 3]>,
+    /// Also correct exposure, so the card reads as 18% gray rather than only
+    /// neutral.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub level: bool,
+}
+
+impl WhiteBalance {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(gains) = self.gains {
+            validate_gains(gains)?;
+        }
+        Ok(())
+    }
+
+    /// Gains that make the gray card in the centre of `rgb` (packed RGB,
+    /// `width` × `height`) neutral, and mid-gray when `level` is set. The
+    /// middle fifth of the frame is taken as the card; a patch that is
+    /// clipped, too dark, strongly tinted or not uniform is refused.
+    pub fn calibrate(&self, width: usize, height: usize, rgb: &[u8]) -> Result<[f64; 3]> {
+        if width == 0 || height == 0 || rgb.len() < width * height * 3 {
+            return Err(Error::UnsupportedImage("empty gray card frame".into()));
+        }
+        let (patch_w, patch_h) = ((width / 5).max(1), (height / 5).max(1));
+        let (left, top) = ((width - patch_w) / 2, (height - patch_h) / 2);
+        let mut sums = [0.0; 3];
+        let mut luma_sq = 0.0;
+        let mut luma_sum = 0.0;
+        for y in top..top + patch_h {
+            let row = &rgb[(y * width + left) * 3..(y * width + left + patch_w) * 3];
+            for px in row.chunks_exact(3) {
+                for (sum, &value) in sums.iter_mut().zip(px) {
+                    *sum += f64::from(value);
+                }
+                let luma = luma(px);
+                luma_sum += luma;
+                luma_sq += luma * luma;
+            }
+        }
+        let count = (patch_w * patch_h) as f64;
+        let means = sums.map(|sum| sum / count);
+        let luma_mean = luma_sum / count;
+        let deviation = (luma_sq / count - luma_mean * luma_mean).max(0.0).sqrt();
+
+        let (low, high) = means
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), &m| (lo.min(m), hi.max(m)));
+        let refuse = |reason: &str| {
+            Err(Error::UnsupportedImage(format!(
+                "{reason}; fill the centre of the frame with the gray card"
+            )))
+        };
+        if low < MIN_CHANNEL {
+            return refuse("gray card too dark");
+        }
+        if high > MAX_CHANNEL {
+            return refuse("gray card overexposed");
+        }
+        if high / low > MAX_CAST {
+            return refuse("centre is not neutral gray");
+        }
+        if deviation > MAX_PATCH_DEVIATION {
+            return refuse("centre is not a plain card");
+        }
+
+        let target = if self.level {
+            LEVEL_TARGET
+        } else {
+            means.iter().sum::<f64>() / 3.0
+        };
+        let gains = means.map(|mean| target / mean);
+        if validate_gains(gains).is_err() {
+            return refuse("gray card too far off to correct");
+        }
+        Ok(gains)
+    }
+}
+
+/// Multiplies packed RGB pixels by `gains`, clamping at white.
+pub fn apply_gains(rgb: &mut [u8], gains: [f64; 3]) {
+    let lut = gains.map(|gain| {
+        let mut table = [0u8; 256];
+        for (value, out) in table.iter_mut().enumerate() {
+            *out = (value as f64 * gain).round().clamp(0.0, 255.0) as u8;
+        }
+        table
+    });
+    for px in rgb.chunks_exact_mut(3) {
+        for (value, table) in px.iter_mut().zip(&lut) {
+            *value = table[usize::from(*value)];
+        }
+    }
+}
+
+fn validate_gains(gains: [f64; 3]) -> Result<()> {
+    if gains
+        .iter()
+        .all(|gain| gain.is_finite() && GAIN_RANGE.contains(gain))
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidConfig(format!(
+            "white_balance.gains must each be between {} and {}",
+            GAIN_RANGE.start(),
+            GAIN_RANGE.end()
+        )))
+    }
+}
+
+fn luma(px: &[u8]) -> f64 {
+    0.299 * f64::from(px[0]) + 0.587 * f64::from(px[1]) + 0.114 * f64::from(px[2])
+}
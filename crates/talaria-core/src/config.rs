@@ -1,51 +1,181 @@
+use crate::color::WhiteBalance;
 use crate::error::{Error, Result};
+use crate::grading::{self, ConditionGrade};
+use crate::measure::MeasurementConfig;
 use crate::models::LlmStageOptions;
-use dirs::config_dir;
+use crate::net::{NetworkConfig, NetworkDoctor, Timeouts};
+use crate::package::{self, PackageTemplate};
+use crate::pricing::PricingRules;
+use crate::quality::QualityRules;
+use crate::shots::{self, SessionTemplate};
+use crate::warnings::WarningPolicy;
+use dirs::{config_dir, data_local_dir};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 pub const DEFAULT_BASE_URL: &str = "https://api.hermes-api.dev";
+pub const DEFAULT_DASHBOARD_URL: &str = "https://app.hermes-api.dev";
 pub const ENV_BASE_URL: &str = "HERMES_BASE_URL";
 pub const ENV_API_KEY: &str = "HERMES_API_KEY";
+pub const ENV_ORG_ID: &str = "HERMES_ORG_ID";
 pub const ENV_SUPABASE_URL: &str = "SUPABASE_URL";
 pub const ENV_SUPABASE_SERVICE_ROLE_KEY: &str = "SUPABASE_SERVICE_ROLE_KEY";
 pub const ENV_SUPABASE_BUCKET: &str = "SUPABASE_BUCKET";
 pub const ENV_SUPABASE_PUBLIC_BASE: &str = "SUPABASE_PUBLIC_BASE";
 pub const ENV_SUPABASE_UPLOAD_PREFIX: &str = "SUPABASE_UPLOAD_PREFIX";
+pub const ENV_SUPABASE_USE_SIGNED_URLS: &str = "SUPABASE_USE_SIGNED_URLS";
+pub const ENV_S3_ENDPOINT: &str = "S3_ENDPOINT";
+pub const ENV_S3_BUCKET: &str = "S3_BUCKET";
+pub const ENV_S3_REGION: &str = "S3_REGION";
+pub const ENV_S3_ACCESS_KEY_ID: &str = "S3_ACCESS_KEY_ID";
+pub const ENV_S3_SECRET_ACCESS_KEY: &str = "S3_SECRET_ACCESS_KEY";
+pub const ENV_S3_PUBLIC_BASE: &str = "S3_PUBLIC_BASE";
+pub const ENV_S3_UPLOAD_PREFIX: &str = "S3_UPLOAD_PREFIX";
+pub const ENV_S3_USE_PRESIGNED_URLS: &str = "S3_USE_PRESIGNED_URLS";
 pub const ENV_EBAY_MARKETPLACE: &str = "EBAY_MARKETPLACE";
 pub const ENV_EBAY_MERCHANT_LOCATION_KEY: &str = "EBAY_MERCHANT_LOCATION_KEY";
 pub const ENV_EBAY_FULFILLMENT_POLICY_ID: &str = "EBAY_FULFILLMENT_POLICY_ID";
 pub const ENV_EBAY_PAYMENT_POLICY_ID: &str = "EBAY_PAYMENT_POLICY_ID";
 pub const ENV_EBAY_RETURN_POLICY_ID: &str = "EBAY_RETURN_POLICY_ID";
 pub const ENV_TUI_PREVIEW_HEIGHT_PCT: &str = "TALARIA_TUI_PREVIEW_HEIGHT_PCT";
+pub const ENV_CAPTURES_DIR: &str = "TALARIA_CAPTURES_DIR";
+pub const ENV_JOB_NOTIFY_URL: &str = "TALARIA_JOB_NOTIFY_URL";
+pub const ENV_SIMULATE: &str = "TALARIA_SIMULATE";
+pub const ENV_PROXY: &str = "TALARIA_PROXY";
+pub const ENV_CA_BUNDLE: &str = "TALARIA_CA_BUNDLE";
+pub const ENV_METRICS_LISTEN: &str = "TALARIA_METRICS_LISTEN";
+pub const ENV_LOG: &str = "TALARIA_LOG";
 pub const DEFAULT_SUPABASE_BUCKET: &str = "images-bucket";
 pub const DEFAULT_SUPABASE_UPLOAD_PREFIX: &str = "talaria";
+/// Lifetime of signed image URLs; long enough for queued listing jobs to fetch them.
+pub const DEFAULT_SUPABASE_SIGNED_URL_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+/// Region S3 requests are signed for when `s3_region` is unset; MinIO and R2
+/// accept it.
+pub const DEFAULT_S3_REGION: &str = "us-east-1";
 pub const DEFAULT_EBAY_MARKETPLACE: &str = "EBAY_US";
 pub const DEFAULT_TUI_PREVIEW_HEIGHT_PCT: u8 = 40;
+pub const DEFAULT_CAPTURE_COUNTDOWN_SECS: u32 = 3;
+pub const DEFAULT_TURNTABLE_EVERY_MS: u64 = 1500;
+pub const DEFAULT_TURNTABLE_FRAMES: u32 = 24;
+pub const DEFAULT_CAPTURE_FILE_TEMPLATE: &str = "frame_{ts}.{ext}";
 
 /// Runtime configuration resolved from environment and optional config file.
 #[derive(Debug, Clone)]
 pub struct Config {
     pub base_url: String,
     pub api_key: Option<String>,
+    /// What Hermes told us about `api_key` when it was created; unknown for
+    /// keys pasted in by hand or taken from `HERMES_API_KEY`.
+    pub api_key_info: Option<ApiKeyInfo>,
+    /// Organization that API-key calls act for (`X-Hermes-Org`); `None`
+    /// leaves it to the key's default.
+    pub org_id: Option<String>,
     pub supabase: Option<SupabaseConfig>,
+    /// An S3-compatible bucket (MinIO, R2, B2) that image uploads go to
+    /// instead of Supabase Storage; set by `s3_endpoint`.
+    pub s3: Option<S3Config>,
     pub ebay: EbaySettings,
     pub llm_ingest: Option<LlmStageOptions>,
     pub llm_aspects: Option<LlmStageOptions>,
     pub prompt_rules: Option<String>,
     pub tui_preview_height_pct: Option<u8>,
+    /// Where the TUI keeps its product workspace; `TALARIA_CAPTURES_DIR` wins.
+    pub tui_captures_dir: Option<PathBuf>,
+    pub tui_preview_backend: PreviewBackend,
+    /// Network cameras (HTTP MJPEG or RTSP URLs) offered in the TUI's
+    /// device picker.
+    pub tui_camera_urls: Vec<String>,
+    /// TUI key overrides from the `[keys]` section (action name -> key spec).
+    pub tui_keys: BTreeMap<String, String>,
+    /// Countdown and foot-pedal capture from `[capture_triggers]`.
+    pub tui_capture_triggers: CaptureTriggers,
+    /// Names and format of captured frames from `[capture_files]`.
+    pub tui_capture_files: CaptureFiles,
+    /// Camera captured together with the picked one, from `[second_camera]`.
+    pub tui_second_camera: Option<SecondCamera>,
+    /// Webhook POSTed when a watched job finishes.
+    pub job_notify_url: Option<String>,
+    /// Send Slack-compatible payloads to `job_notify_url`.
+    pub job_notify_slack: bool,
+    /// Fake media uploads and camera frames (`TALARIA_SIMULATE` or `--simulate`);
+    /// see [`crate::simulate`].
+    pub simulate: bool,
+    /// Condition grading rubric from `[[condition_grades]]`, or the built-in
+    /// one; see [`crate::grading`].
+    pub condition_grades: Vec<ConditionGrade>,
+    /// Photo measurement calibration from `[measurement]`; see
+    /// [`crate::measure`].
+    pub measurement: MeasurementConfig,
+    /// Gray card colour correction from `[white_balance]`; see
+    /// [`crate::color`].
+    pub white_balance: WhiteBalance,
+    /// Named package presets from `[[package_templates]]`; see
+    /// [`crate::package`].
+    pub package_templates: Vec<PackageTemplate>,
+    /// Shot lists for capture sessions from `[[session_templates]]`; see
+    /// [`crate::shots`].
+    pub session_templates: Vec<SessionTemplate>,
+    /// Margin rules for price suggestions from `[pricing]`; see
+    /// [`crate::pricing`].
+    pub pricing: PricingRules,
+    /// Photo checks run before enrich and listing from `[quality]`; see
+    /// [`crate::quality`].
+    pub quality: QualityRules,
+    /// Proxy and TLS settings from `[network]` (`TALARIA_PROXY` and
+    /// `TALARIA_CA_BUNDLE` win); see [`crate::net`].
+    pub network: NetworkConfig,
+    /// Per-kind Hermes request timeouts from `[timeouts]`; see
+    /// [`crate::net::Timeouts`].
+    pub timeouts: Timeouts,
+    /// What listing stage warnings do, from `[warning_policy]`; see
+    /// [`crate::warnings`].
+    pub warning_policy: WarningPolicy,
+    /// Address the TUI serves Prometheus metrics on, e.g. `127.0.0.1:9464`;
+    /// needs the `prometheus` feature. See [`crate::metrics`].
+    pub metrics_listen: Option<String>,
+    /// `RUST_LOG`-style directives for the JSON logs, e.g.
+    /// `info,talaria_core::client=debug` (`TALARIA_LOG` wins); see
+    /// [`crate::logging`].
+    pub log_filter: Option<String>,
+    /// Format CLI commands use when `--format` is not given; each command
+    /// keeps its own default when unset.
+    pub default_output_format: Option<OutputFormat>,
+    /// What this station may do, from `mode`.
+    pub mode: AccessMode,
+    /// Who works this station, from `operator`; see [`Config::operator_name`].
+    pub operator: Option<String>,
+    /// Where products are read and written, from `product_backend`.
+    pub product_backend: ProductBackend,
+    /// Hermes web dashboard, from `dashboard_url`; see
+    /// [`Config::dashboard_url`].
+    pub dashboard_url: Option<String>,
+    /// Language for CLI and TUI messages, e.g. `de`, from `locale`; unset
+    /// follows the system. See [`crate::i18n`].
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
 struct ConfigFile {
     base_url: Option<String>,
     api_key: Option<String>,
+    api_key_info: Option<ApiKeyInfo>,
+    org_id: Option<String>,
     supabase_url: Option<String>,
     supabase_service_role_key: Option<String>,
     supabase_bucket: Option<String>,
     supabase_public_base: Option<String>,
     supabase_upload_prefix: Option<String>,
+    supabase_use_signed_urls: Option<bool>,
+    s3_endpoint: Option<String>,
+    s3_bucket: Option<String>,
+    s3_region: Option<String>,
+    s3_access_key_id: Option<String>,
+    s3_secret_access_key: Option<String>,
+    s3_public_base: Option<String>,
+    s3_upload_prefix: Option<String>,
+    s3_use_presigned_urls: Option<bool>,
     ebay_marketplace: Option<String>,
     ebay_merchant_location_key: Option<String>,
     ebay_fulfillment_policy_id: Option<String>,
@@ -55,18 +185,64 @@ struct ConfigFile {
     llm_aspects: Option<LlmStageOptions>,
     prompt_rules: Option<String>,
     tui_preview_height_pct: Option<u8>,
+    tui_captures_dir: Option<PathBuf>,
+    tui_preview_backend: Option<PreviewBackend>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tui_camera_urls: Vec<String>,
+    job_notify_url: Option<String>,
+    job_notify_slack: Option<bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    keys: BTreeMap<String, String>,
+    capture_triggers: Option<CaptureTriggers>,
+    capture_files: Option<CaptureFiles>,
+    second_camera: Option<SecondCamera>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    condition_grades: Vec<ConditionGrade>,
+    measurement: Option<MeasurementConfig>,
+    white_balance: Option<WhiteBalance>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    package_templates: Vec<PackageTemplate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    session_templates: Vec<SessionTemplate>,
+    pricing: Option<PricingRules>,
+    quality: Option<QualityRules>,
+    network: Option<NetworkConfig>,
+    timeouts: Option<Timeouts>,
+    warning_policy: Option<WarningPolicy>,
+    metrics_listen: Option<String>,
+    log_filter: Option<String>,
+    default_output_format: Option<OutputFormat>,
+    mode: Option<AccessMode>,
+    operator: Option<String>,
+    product_backend: Option<ProductBackend>,
+    dashboard_url: Option<String>,
+    locale: Option<String>,
+}
+
+/// Identifies a user API key created by `auth login` or `auth rotate-key`,
+/// so it can be shown and revoked later; the secret stays in `api_key`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyInfo {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ConfigDoctor {
     pub base_url: String,
     pub api_key_redacted: Option<String>,
+    pub org_id: Option<String>,
     pub source: String,
+    pub product_backend: ProductBackend,
     pub supabase: Option<SupabaseDoctor>,
+    pub s3: Option<S3Doctor>,
     pub ebay: EbaySettings,
     pub llm_ingest: Option<LlmStageOptions>,
     pub llm_aspects: Option<LlmStageOptions>,
     pub prompt_rules: Option<String>,
+    pub network: NetworkDoctor,
+    pub timeouts: Timeouts,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -76,6 +252,7 @@ pub struct SupabaseDoctor {
     pub upload_prefix: String,
     pub service_role_key_redacted: Option<String>,
     pub public_base: Option<String>,
+    pub use_signed_urls: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -85,6 +262,338 @@ pub struct SupabaseConfig {
     pub bucket: String,
     pub public_base: Option<String>,
     pub upload_prefix: String,
+    /// Hand out signed object URLs instead of public ones (private buckets).
+    pub use_signed_urls: bool,
+    /// Copied from [`Config::network`] so Supabase clients use the same
+    /// proxy and certificates.
+    pub network: NetworkConfig,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct S3Doctor {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub upload_prefix: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key_redacted: Option<String>,
+    pub public_base: Option<String>,
+    pub use_presigned_urls: bool,
+}
+
+/// An S3-compatible image bucket. Objects are addressed path-style
+/// (`<endpoint>/<bucket>/<key>`), which MinIO needs and R2 and B2 accept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    /// Base of public object URLs, e.g. an R2 custom domain; defaults to
+    /// `<endpoint>/<bucket>`.
+    pub public_base: Option<String>,
+    pub upload_prefix: String,
+    /// Hand out presigned GET URLs instead of public ones (private buckets).
+    pub use_presigned_urls: bool,
+}
+
+/// What CLI commands print when `--format` is not given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Json,
+    Table,
+    /// Stable `key<TAB>value` lines for scripts.
+    Porcelain,
+}
+
+/// What a station may do. Warehouse stations run `capture-only`: they
+/// capture and curate, but cannot publish, delete or spend credits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccessMode {
+    #[default]
+    Full,
+    CaptureOnly,
+}
+
+impl AccessMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AccessMode::Full => "full",
+            AccessMode::CaptureOnly => "capture-only",
+        }
+    }
+
+    /// `Ok` in full mode; in capture-only mode, the error to report for
+    /// `action`, e.g. `"publishing listings"`.
+    pub fn permit(self, action: &str) -> Result<()> {
+        match self {
+            AccessMode::Full => Ok(()),
+            AccessMode::CaptureOnly => Err(Error::Restricted(action.to_string())),
+        }
+    }
+}
+
+/// Where products are kept. Self-hosted deployments without the Hermes
+/// products API point `supabase` at the `products` table of their Supabase
+/// project (`SUPABASE_URL` and `SUPABASE_SERVICE_ROLE_KEY`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProductBackend {
+    /// The Hermes `v1/products` API.
+    #[default]
+    Hermes,
+    /// The Supabase `products` table, through PostgREST.
+    Supabase,
+}
+
+/// How the TUI shows the camera/image preview.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewBackend {
+    /// Inline terminal graphics when the terminal supports them, else a window.
+    #[default]
+    Auto,
+    Terminal,
+    Window,
+}
+
+impl PreviewBackend {
+    pub const ALL: [PreviewBackend; 3] = [
+        PreviewBackend::Auto,
+        PreviewBackend::Terminal,
+        PreviewBackend::Window,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PreviewBackend::Auto => "auto",
+            PreviewBackend::Terminal => "terminal",
+            PreviewBackend::Window => "window",
+        }
+    }
+}
+
+impl std::str::FromStr for PreviewBackend {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        PreviewBackend::ALL
+            .into_iter()
+            .find(|b| b.as_str().eq_ignore_ascii_case(value.trim()))
+            .ok_or_else(|| {
+                Error::InvalidConfig(format!(
+                    "unknown preview backend '{value}' (auto, terminal, window)"
+                ))
+            })
+    }
+}
+
+/// The `[capture_triggers]` table: hands-free ways to fire the TUI camera.
+/// The capture hotkey itself is `capture_hotkey` in `[keys]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureTriggers {
+    /// Delay of a countdown capture, shown over the preview.
+    pub countdown_secs: u32,
+    /// Input device of a USB foot pedal, e.g.
+    /// `/dev/input/by-id/usb-PCsensor_FootSwitch-event-kbd` (Linux only). It
+    /// is grabbed, so its key presses no longer reach other programs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pedal_device: Option<PathBuf>,
+    /// Key codes (as `evtest` prints them) that fire a capture; any key when
+    /// empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pedal_keys: Vec<u16>,
+    /// Start a countdown from the pedal instead of capturing at once.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub pedal_countdown: bool,
+    /// Interval between frames of a turntable run.
+    pub turntable_every_ms: u64,
+    /// Frames a turntable run takes before it stops.
+    pub turntable_frames: u32,
+}
+
+impl Default for CaptureTriggers {
+    fn default() -> Self {
+        Self {
+            countdown_secs: DEFAULT_CAPTURE_COUNTDOWN_SECS,
+            pedal_device: None,
+            pedal_keys: Vec::new(),
+            pedal_countdown: false,
+            turntable_every_ms: DEFAULT_TURNTABLE_EVERY_MS,
+            turntable_frames: DEFAULT_TURNTABLE_FRAMES,
+        }
+    }
+}
+
+impl CaptureTriggers {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=60).contains(&self.countdown_secs) {
+            return Err(Error::InvalidConfig(
+                "capture_triggers.countdown_secs must be between 1 and 60".into(),
+            ));
+        }
+        if !(100..=600_000).contains(&self.turntable_every_ms) {
+            return Err(Error::InvalidConfig(
+                "capture_triggers.turntable_every_ms must be between 100 and 600000".into(),
+            ));
+        }
+        if !(1..=720).contains(&self.turntable_frames) {
+            return Err(Error::InvalidConfig(
+                "capture_triggers.turntable_frames must be between 1 and 720".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The `[capture_files]` table: how the TUI names and encodes the frames it
+/// saves into a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureFiles {
+    /// File name with `{sku}`, `{session}`, `{seq}` (the frame's number in
+    /// the session), `{ts}` and `{ext}` placeholders, e.g.
+    /// `{sku}_{session}_{seq}_{ts}.{ext}`. `.{ext}` is added when left out.
+    pub template: String,
+    /// Encoding of frames saved from the stream; tethered cameras keep the
+    /// JPEG they send.
+    pub format: CaptureFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureFormat {
+    #[default]
+    Jpg,
+    Png,
+    /// Lossless WebP.
+    Webp,
+}
+
+impl CaptureFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            CaptureFormat::Jpg => "jpg",
+            CaptureFormat::Png => "png",
+            CaptureFormat::Webp => "webp",
+        }
+    }
+}
+
+impl Default for CaptureFiles {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_CAPTURE_FILE_TEMPLATE.to_string(),
+            format: CaptureFormat::default(),
+        }
+    }
+}
+
+impl CaptureFiles {
+    const FIELDS: [&str; 5] = ["sku", "session", "seq", "ts", "ext"];
+
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |why: &str| {
+            Error::InvalidConfig(format!("capture_files.template '{}' {why}", self.template))
+        };
+        if self.template.contains(['/', '\\']) {
+            return Err(invalid("must be a file name, not a path"));
+        }
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| invalid("has an unclosed '{'"))?;
+            let field = &rest[start + 1..start + end];
+            if !Self::FIELDS.contains(&field) {
+                return Err(invalid(&format!(
+                    "has unknown field {{{field}}} (sku, session, seq, ts, ext)"
+                )));
+            }
+            rest = &rest[start + end + 1..];
+        }
+        if !self.template.contains("{seq}") && !self.template.contains("{ts}") {
+            return Err(invalid("needs {seq} or {ts} to keep names apart"));
+        }
+        Ok(())
+    }
+
+    /// The file name for frame `seq` of `session`, with `ts` as the
+    /// timestamp. Characters unsafe in file names are replaced in `sku`.
+    pub fn file_name(&self, sku: &str, session: &str, seq: u32, ts: &str) -> String {
+        let sku: String = sku
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let ext = self.format.extension();
+        let mut name = self
+            .template
+            .replace("{sku}", &sku)
+            .replace("{session}", session)
+            .replace("{seq}", &format!("{seq:03}"))
+            .replace("{ts}", ts)
+            .replace("{ext}", ext);
+        if !self.template.contains("{ext}") {
+            name = format!("{name}.{ext}");
+        }
+        name
+    }
+}
+
+/// The `[second_camera]` table: a camera the TUI streams next to the one
+/// picked in the device picker, so one keypress captures both, e.g. a side
+/// view beside an overhead rig.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecondCamera {
+    /// Webcam index, as numbered in the device picker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+    /// HTTP MJPEG or RTSP stream, instead of a webcam.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Label of this camera's frames in the session.
+    #[serde(default = "default_second_camera_label")]
+    pub label: String,
+    /// Label of the picked camera's frame in a paired capture.
+    #[serde(default = "default_primary_camera_label")]
+    pub primary_label: String,
+}
+
+fn default_second_camera_label() -> String {
+    "side".to_string()
+}
+
+fn default_primary_camera_label() -> String {
+    "top".to_string()
+}
+
+impl SecondCamera {
+    pub fn validate(&self) -> Result<()> {
+        if self.index.is_some() == self.url.is_some() {
+            return Err(Error::InvalidConfig(
+                "second_camera needs either index or url".into(),
+            ));
+        }
+        if self.label.trim().is_empty()
+            || self.primary_label.trim().is_empty()
+            || self.label == self.primary_label
+        {
+            return Err(Error::InvalidConfig(
+                "second_camera label and primary_label must be set and differ".into(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -96,6 +605,53 @@ pub struct EbaySettings {
     pub return_policy_id: Option<String>,
 }
 
+/// Built-in defaults with no config file or environment applied.
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: None,
+            api_key_info: None,
+            org_id: None,
+            supabase: None,
+            s3: None,
+            ebay: EbaySettings::default(),
+            llm_ingest: None,
+            llm_aspects: None,
+            prompt_rules: None,
+            tui_preview_height_pct: None,
+            tui_captures_dir: None,
+            tui_preview_backend: PreviewBackend::default(),
+            tui_camera_urls: Vec::new(),
+            tui_keys: BTreeMap::new(),
+            tui_capture_triggers: CaptureTriggers::default(),
+            tui_capture_files: CaptureFiles::default(),
+            tui_second_camera: None,
+            job_notify_url: None,
+            job_notify_slack: false,
+            simulate: false,
+            condition_grades: grading::default_rubric(),
+            measurement: MeasurementConfig::default(),
+            white_balance: WhiteBalance::default(),
+            package_templates: Vec::new(),
+            session_templates: Vec::new(),
+            pricing: PricingRules::default(),
+            quality: QualityRules::default(),
+            network: NetworkConfig::default(),
+            timeouts: Timeouts::default(),
+            warning_policy: WarningPolicy::default(),
+            metrics_listen: None,
+            log_filter: None,
+            default_output_format: None,
+            mode: AccessMode::default(),
+            operator: None,
+            product_backend: ProductBackend::default(),
+            dashboard_url: None,
+            locale: None,
+        }
+    }
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let file_path = config_path();
@@ -111,24 +667,182 @@ impl Config {
             .or_else(|| file_config.as_ref().and_then(|c| c.base_url.clone()))
             .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
 
-        let api_key = std::env::var(ENV_API_KEY)
-            .ok()
+        let env_api_key = std::env::var(ENV_API_KEY).ok();
+        let api_key_info = file_config
+            .as_ref()
+            .and_then(|c| c.api_key_info.clone())
+            .filter(|_| env_api_key.is_none());
+        let api_key = env_api_key
             .or_else(|| file_config.as_ref().and_then(|c| c.api_key.clone()))
             .filter(|v| !v.trim().is_empty());
 
-        let supabase = resolve_supabase(file_config.as_ref());
+        let org_id = std::env::var(ENV_ORG_ID)
+            .ok()
+            .or_else(|| file_config.as_ref().and_then(|c| c.org_id.clone()))
+            .filter(|v| !v.trim().is_empty());
+
+        let mut network = file_config
+            .as_ref()
+            .and_then(|c| c.network.clone())
+            .unwrap_or_default();
+        if let Some(proxy) = std::env::var(ENV_PROXY)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+        {
+            network.proxy = Some(proxy);
+        }
+        if let Some(path) = std::env::var_os(ENV_CA_BUNDLE).filter(|v| !v.is_empty()) {
+            network.ca_bundle = Some(PathBuf::from(path));
+        }
+        network.validate()?;
+
+        let supabase = resolve_supabase(file_config.as_ref(), &network);
+        let s3 = resolve_s3(file_config.as_ref());
         let ebay = resolve_ebay(file_config.as_ref());
         let tui_preview_height_pct = resolve_tui_preview_height(file_config.as_ref());
+        let condition_grades = match file_config.as_ref().map(|c| &c.condition_grades) {
+            Some(grades) if !grades.is_empty() => {
+                grading::validate_rubric(grades)?;
+                grades.clone()
+            }
+            _ => grading::default_rubric(),
+        };
+        let measurement = file_config
+            .as_ref()
+            .and_then(|c| c.measurement.clone())
+            .unwrap_or_default();
+        measurement.validate()?;
+        let white_balance = file_config
+            .as_ref()
+            .and_then(|c| c.white_balance)
+            .unwrap_or_default();
+        white_balance.validate()?;
+        let tui_capture_triggers = file_config
+            .as_ref()
+            .and_then(|c| c.capture_triggers.clone())
+            .unwrap_or_default();
+        tui_capture_triggers.validate()?;
+        let tui_capture_files = file_config
+            .as_ref()
+            .and_then(|c| c.capture_files.clone())
+            .unwrap_or_default();
+        tui_capture_files.validate()?;
+        let tui_second_camera = file_config.as_ref().and_then(|c| c.second_camera.clone());
+        if let Some(camera) = &tui_second_camera {
+            camera.validate()?;
+        }
+        let package_templates = file_config
+            .as_ref()
+            .map(|c| c.package_templates.clone())
+            .unwrap_or_default();
+        package::validate_templates(&package_templates)?;
+        let session_templates = file_config
+            .as_ref()
+            .map(|c| c.session_templates.clone())
+            .unwrap_or_default();
+        shots::validate_templates(&session_templates)?;
+        let pricing = file_config
+            .as_ref()
+            .and_then(|c| c.pricing.clone())
+            .unwrap_or_default();
+        pricing.validate()?;
+        let quality = file_config
+            .as_ref()
+            .and_then(|c| c.quality.clone())
+            .unwrap_or_default();
+        quality.validate()?;
+        let timeouts = file_config
+            .as_ref()
+            .and_then(|c| c.timeouts)
+            .unwrap_or_default();
+        timeouts.validate()?;
+        let warning_policy = file_config
+            .as_ref()
+            .and_then(|c| c.warning_policy.clone())
+            .unwrap_or_default();
+        warning_policy.validate()?;
 
         Ok(Self {
             base_url,
             api_key,
+            api_key_info,
+            org_id,
             supabase,
+            s3,
             ebay,
             llm_ingest: file_config.as_ref().and_then(|c| c.llm_ingest.clone()),
             llm_aspects: file_config.as_ref().and_then(|c| c.llm_aspects.clone()),
             prompt_rules: file_config.as_ref().and_then(|c| c.prompt_rules.clone()),
             tui_preview_height_pct,
+            tui_captures_dir: file_config
+                .as_ref()
+                .and_then(|c| c.tui_captures_dir.clone()),
+            tui_preview_backend: file_config
+                .as_ref()
+                .and_then(|c| c.tui_preview_backend)
+                .unwrap_or_default(),
+            tui_camera_urls: file_config
+                .as_ref()
+                .map(|c| c.tui_camera_urls.clone())
+                .unwrap_or_default(),
+            tui_keys: file_config
+                .as_ref()
+                .map(|c| c.keys.clone())
+                .unwrap_or_default(),
+            tui_capture_triggers,
+            tui_capture_files,
+            tui_second_camera,
+            job_notify_url: std::env::var(ENV_JOB_NOTIFY_URL)
+                .ok()
+                .or_else(|| file_config.as_ref().and_then(|c| c.job_notify_url.clone()))
+                .filter(|v| !v.trim().is_empty()),
+            job_notify_slack: file_config
+                .as_ref()
+                .and_then(|c| c.job_notify_slack)
+                .unwrap_or(false),
+            simulate: std::env::var(ENV_SIMULATE)
+                .ok()
+                .and_then(|value| parse_bool(&value))
+                .unwrap_or(false),
+            condition_grades,
+            measurement,
+            white_balance,
+            package_templates,
+            session_templates,
+            pricing,
+            quality,
+            network,
+            timeouts,
+            warning_policy,
+            metrics_listen: std::env::var(ENV_METRICS_LISTEN)
+                .ok()
+                .or_else(|| file_config.as_ref().and_then(|c| c.metrics_listen.clone()))
+                .filter(|v| !v.trim().is_empty()),
+            log_filter: std::env::var(ENV_LOG)
+                .ok()
+                .or_else(|| file_config.as_ref().and_then(|c| c.log_filter.clone()))
+                .filter(|v| !v.trim().is_empty()),
+            default_output_format: file_config.as_ref().and_then(|c| c.default_output_format),
+            mode: file_config
+                .as_ref()
+                .and_then(|c| c.mode)
+                .unwrap_or_default(),
+            operator: file_config
+                .as_ref()
+                .and_then(|c| c.operator.clone())
+                .filter(|v| !v.trim().is_empty()),
+            product_backend: file_config
+                .as_ref()
+                .and_then(|c| c.product_backend)
+                .unwrap_or_default(),
+            dashboard_url: file_config
+                .as_ref()
+                .and_then(|c| c.dashboard_url.clone())
+                .filter(|v| !v.trim().is_empty()),
+            locale: file_config
+                .as_ref()
+                .and_then(|c| c.locale.clone())
+                .filter(|v| !v.trim().is_empty()),
         })
     }
 
@@ -146,6 +860,8 @@ impl Config {
         let file_config = ConfigFile {
             base_url: Some(self.base_url.clone()),
             api_key: self.api_key.clone(),
+            api_key_info: self.api_key_info.clone(),
+            org_id: self.org_id.clone(),
             supabase_url: self.supabase.as_ref().map(|s| s.url.clone()),
             supabase_service_role_key: self
                 .supabase
@@ -154,6 +870,25 @@ impl Config {
             supabase_bucket: self.supabase.as_ref().map(|s| s.bucket.clone()),
             supabase_public_base: self.supabase.as_ref().and_then(|s| s.public_base.clone()),
             supabase_upload_prefix: self.supabase.as_ref().map(|s| s.upload_prefix.clone()),
+            supabase_use_signed_urls: self
+                .supabase
+                .as_ref()
+                .and_then(|s| s.use_signed_urls.then_some(true)),
+            s3_endpoint: self.s3.as_ref().map(|s| s.endpoint.clone()),
+            s3_bucket: self.s3.as_ref().map(|s| s.bucket.clone()),
+            s3_region: self
+                .s3
+                .as_ref()
+                .map(|s| s.region.clone())
+                .filter(|region| region != DEFAULT_S3_REGION),
+            s3_access_key_id: self.s3.as_ref().and_then(|s| s.access_key_id.clone()),
+            s3_secret_access_key: self.s3.as_ref().and_then(|s| s.secret_access_key.clone()),
+            s3_public_base: self.s3.as_ref().and_then(|s| s.public_base.clone()),
+            s3_upload_prefix: self.s3.as_ref().map(|s| s.upload_prefix.clone()),
+            s3_use_presigned_urls: self
+                .s3
+                .as_ref()
+                .and_then(|s| s.use_presigned_urls.then_some(true)),
             ebay_marketplace: self.ebay.marketplace.clone(),
             ebay_merchant_location_key: self.ebay.merchant_location_key.clone(),
             ebay_fulfillment_policy_id: self.ebay.fulfillment_policy_id.clone(),
@@ -163,10 +898,52 @@ impl Config {
             llm_aspects: self.llm_aspects.clone(),
             prompt_rules: self.prompt_rules.clone(),
             tui_preview_height_pct: self.tui_preview_height_pct,
+            tui_captures_dir: self.tui_captures_dir.clone(),
+            tui_preview_backend: (self.tui_preview_backend != PreviewBackend::Auto)
+                .then_some(self.tui_preview_backend),
+            tui_camera_urls: self.tui_camera_urls.clone(),
+            job_notify_url: self.job_notify_url.clone(),
+            job_notify_slack: self.job_notify_slack.then_some(true),
+            keys: self.tui_keys.clone(),
+            capture_triggers: (self.tui_capture_triggers != CaptureTriggers::default())
+                .then(|| self.tui_capture_triggers.clone()),
+            capture_files: (self.tui_capture_files != CaptureFiles::default())
+                .then(|| self.tui_capture_files.clone()),
+            second_camera: self.tui_second_camera.clone(),
+            condition_grades: if self.condition_grades == grading::default_rubric() {
+                Vec::new()
+            } else {
+                self.condition_grades.clone()
+            },
+            measurement: (self.measurement != MeasurementConfig::default())
+                .then(|| self.measurement.clone()),
+            white_balance: (self.white_balance != WhiteBalance::default())
+                .then_some(self.white_balance),
+            package_templates: self.package_templates.clone(),
+            session_templates: self.session_templates.clone(),
+            pricing: (self.pricing != PricingRules::default()).then(|| self.pricing.clone()),
+            quality: (self.quality != QualityRules::default()).then(|| self.quality.clone()),
+            network: (self.network != NetworkConfig::default()).then(|| self.network.clone()),
+            timeouts: (self.timeouts != Timeouts::default()).then_some(self.timeouts),
+            warning_policy: (self.warning_policy != WarningPolicy::default())
+                .then(|| self.warning_policy.clone()),
+            metrics_listen: self.metrics_listen.clone(),
+            log_filter: self.log_filter.clone(),
+            default_output_format: self.default_output_format,
+            mode: (self.mode != AccessMode::Full).then_some(self.mode),
+            operator: self.operator.clone(),
+            product_backend: (self.product_backend != ProductBackend::Hermes)
+                .then_some(self.product_backend),
+            dashboard_url: self.dashboard_url.clone(),
+            locale: self.locale.clone(),
         };
         let serialized = toml::to_string_pretty(&file_config)
             .map_err(|err| Error::InvalidConfig(format!("failed to serialize config: {err}")))?;
-        fs::write(&path, serialized)
+        // Write then rename, so a crash never leaves a half-written file (and
+        // with it, a lost API key).
+        let tmp = path.with_extension("toml.tmp");
+        fs::write(&tmp, serialized)
+            .and_then(|()| fs::rename(&tmp, &path))
             .map_err(|err| Error::InvalidConfig(format!("failed to write config: {err}")))?;
         Ok(())
     }
@@ -180,27 +957,83 @@ impl Config {
         ConfigDoctor {
             base_url: self.base_url.clone(),
             api_key_redacted: self.redacted_api_key(),
+            org_id: self.org_id.clone(),
             source,
+            product_backend: self.product_backend,
             supabase: self.supabase.as_ref().map(|s| SupabaseDoctor {
                 supabase_url: s.url.clone(),
                 bucket: s.bucket.clone(),
                 upload_prefix: s.upload_prefix.clone(),
                 service_role_key_redacted: s.service_role_key.as_ref().map(|v| redact(v)),
                 public_base: s.public_base.clone(),
+                use_signed_urls: s.use_signed_urls,
+            }),
+            s3: self.s3.as_ref().map(|s| S3Doctor {
+                endpoint: s.endpoint.clone(),
+                bucket: s.bucket.clone(),
+                region: s.region.clone(),
+                upload_prefix: s.upload_prefix.clone(),
+                access_key_id: s.access_key_id.clone(),
+                secret_access_key_redacted: s.secret_access_key.as_ref().map(|v| redact(v)),
+                public_base: s.public_base.clone(),
+                use_presigned_urls: s.use_presigned_urls,
             }),
             ebay: self.ebay.clone(),
             llm_ingest: self.llm_ingest.clone(),
             llm_aspects: self.llm_aspects.clone(),
             prompt_rules: self.prompt_rules.clone(),
+            network: self.network.doctor(),
+            timeouts: self.timeouts,
         }
     }
 
+    /// Whether listing requests should ask Hermes for signed image URLs.
+    pub fn use_signed_urls(&self) -> bool {
+        self.supabase.as_ref().is_some_and(|s| s.use_signed_urls)
+    }
+
+    /// The TUI workspace directory: `TALARIA_CAPTURES_DIR`, then the config
+    /// file, then `<data_local_dir>/talaria/captures`.
+    pub fn captures_dir(&self) -> PathBuf {
+        if let Some(dir) = std::env::var_os(ENV_CAPTURES_DIR) {
+            return PathBuf::from(dir);
+        }
+        self.tui_captures_dir.clone().unwrap_or_else(|| {
+            data_local_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("talaria")
+                .join("captures")
+        })
+    }
+
+    /// Who local artifacts and batch reports are attributed to: `operator`
+    /// from config, else the login name (`USER`, or `USERNAME` on Windows).
+    pub fn operator_name(&self) -> Option<String> {
+        self.operator
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// The Hermes web dashboard: `dashboard_url` from config, else
+    /// [`DEFAULT_DASHBOARD_URL`].
+    pub fn dashboard_url(&self) -> &str {
+        self.dashboard_url
+            .as_deref()
+            .unwrap_or(DEFAULT_DASHBOARD_URL)
+    }
+
     pub fn redacted_api_key(&self) -> Option<String> {
         self.api_key.as_ref().map(|v| redact(v))
     }
 }
 
-fn resolve_supabase(file_config: Option<&ConfigFile>) -> Option<SupabaseConfig> {
+fn resolve_supabase(
+    file_config: Option<&ConfigFile>,
+    network: &NetworkConfig,
+) -> Option<SupabaseConfig> {
     let supabase_url = std::env::var(ENV_SUPABASE_URL)
         .ok()
         .or_else(|| file_config.and_then(|c| c.supabase_url.clone()));
@@ -225,12 +1058,49 @@ fn resolve_supabase(file_config: Option<&ConfigFile>) -> Option<SupabaseConfig>
         .or_else(|| file_config.and_then(|c| c.supabase_upload_prefix.clone()))
         .unwrap_or_else(|| DEFAULT_SUPABASE_UPLOAD_PREFIX.to_string());
 
+    let use_signed_urls = std::env::var(ENV_SUPABASE_USE_SIGNED_URLS)
+        .ok()
+        .and_then(|value| parse_bool(&value))
+        .or_else(|| file_config.and_then(|c| c.supabase_use_signed_urls))
+        .unwrap_or(false);
+
     supabase_url.map(|url| SupabaseConfig {
         url,
         service_role_key,
         bucket,
         public_base,
         upload_prefix,
+        use_signed_urls,
+        network: network.clone(),
+    })
+}
+
+/// Present once an endpoint is set. Uploads default to the same prefix as
+/// Supabase's.
+fn resolve_s3(file_config: Option<&ConfigFile>) -> Option<S3Config> {
+    let setting = |env: &str, file: fn(&ConfigFile) -> Option<String>| {
+        std::env::var(env)
+            .ok()
+            .or_else(|| file_config.and_then(file))
+            .filter(|s| !s.trim().is_empty())
+    };
+    let endpoint = setting(ENV_S3_ENDPOINT, |c| c.s3_endpoint.clone())?;
+    let use_presigned_urls = std::env::var(ENV_S3_USE_PRESIGNED_URLS)
+        .ok()
+        .and_then(|value| parse_bool(&value))
+        .or_else(|| file_config.and_then(|c| c.s3_use_presigned_urls))
+        .unwrap_or(false);
+    Some(S3Config {
+        endpoint,
+        bucket: setting(ENV_S3_BUCKET, |c| c.s3_bucket.clone()).unwrap_or_default(),
+        region: setting(ENV_S3_REGION, |c| c.s3_region.clone())
+            .unwrap_or_else(|| DEFAULT_S3_REGION.to_string()),
+        access_key_id: setting(ENV_S3_ACCESS_KEY_ID, |c| c.s3_access_key_id.clone()),
+        secret_access_key: setting(ENV_S3_SECRET_ACCESS_KEY, |c| c.s3_secret_access_key.clone()),
+        public_base: setting(ENV_S3_PUBLIC_BASE, |c| c.s3_public_base.clone()),
+        upload_prefix: setting(ENV_S3_UPLOAD_PREFIX, |c| c.s3_upload_prefix.clone())
+            .unwrap_or_else(|| DEFAULT_SUPABASE_UPLOAD_PREFIX.to_string()),
+        use_presigned_urls,
     })
 }
 
@@ -273,10 +1143,31 @@ fn resolve_tui_preview_height(file_config: Option<&ConfigFile>) -> Option<u8> {
         .or_else(|| file_config.and_then(|c| c.tui_preview_height_pct))
 }
 
-fn config_path() -> Option<PathBuf> {
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Location of the config file (`~/.config/talaria/config.toml`).
+pub fn config_path() -> Option<PathBuf> {
     config_dir().map(|dir| dir.join("talaria").join("config.toml"))
 }
 
+/// Directory for local state that is not configuration (ledgers, key stores).
+pub fn data_dir() -> Option<PathBuf> {
+    data_local_dir().map(|dir| dir.join("talaria"))
+}
+
+/// The TUI workspace directory as [`Config::captures_dir`] resolves it, for
+/// callers without a loaded config; a config file that fails to load is
+/// treated as absent.
+pub fn captures_dir() -> PathBuf {
+    Config::load().unwrap_or_default().captures_dir()
+}
+
 fn redact(key: &str) -> String {
     if key.len() <= 4 {
         return "****".to_string();
@@ -0,0 +1,243 @@
+//! Conversion of phone/camera formats (HEIC, RAW) that marketplaces and the
+//! enrich pipeline can't consume into JPEG before upload.
+//!
+//! Both converters are optional: `heic` decodes through libheif, `raw`
+//! extracts the full-size JPEG preview cameras embed in CR2/NEF/ARW/DNG files.
+//! Without the matching feature those inputs are rejected up front.
+
+use crate::error::{Error, Result};
+use crate::upload_cache::sha256_hex;
+use std::path::{Path, PathBuf};
+
+pub const HEIC_EXTENSIONS: &[&str] = &["heic", "heif"];
+pub const RAW_EXTENSIONS: &[&str] = &["cr2", "cr3", "nef", "arw", "dng", "raf", "orf", "rw2"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// Uploaded as-is.
+    Native,
+    Heic,
+    Raw,
+}
+
+impl InputKind {
+    pub fn of(path: &Path) -> Self {
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            return InputKind::Native;
+        };
+        let ext = ext.to_ascii_lowercase();
+        if HEIC_EXTENSIONS.contains(&ext.as_str()) {
+            InputKind::Heic
+        } else if RAW_EXTENSIONS.contains(&ext.as_str()) {
+            InputKind::Raw
+        } else {
+            InputKind::Native
+        }
+    }
+
+    /// Whether this build can convert the input; `Native` always is.
+    pub fn supported(self) -> bool {
+        match self {
+            InputKind::Native => true,
+            InputKind::Heic => cfg!(feature = "heic"),
+            InputKind::Raw => cfg!(feature = "raw"),
+        }
+    }
+
+    /// Cargo feature needed to convert this input.
+    pub fn feature(self) -> Option<&'static str> {
+        match self {
+            InputKind::Native => None,
+            InputKind::Heic => Some("heic"),
+            InputKind::Raw => Some("raw"),
+        }
+    }
+}
+
+/// Extensions this build converts, for directory scans.
+pub fn convertible_extensions() -> Vec<&'static str> {
+    let mut out = Vec::new();
+    if InputKind::Heic.supported() {
+        out.extend_from_slice(HEIC_EXTENSIONS);
+    }
+    if InputKind::Raw.supported() {
+        out.extend_from_slice(RAW_EXTENSIONS);
+    }
+    out
+}
+
+/// Returns a JPEG-compatible path for every input, converting HEIC/RAW files
+/// into `out_dir`. Fails before converting anything if some inputs cannot be
+/// handled by this build, naming all of them.
+pub fn prepare_for_upload(paths: &[PathBuf], out_dir: &Path) -> Result<Vec<PathBuf>> {
+    let unsupported: Vec<String> = paths
+        .iter()
+        .filter_map(|path| {
+            let kind = InputKind::of(path);
+            (!kind.supported()).then(|| unsupported(path, kind).to_string())
+        })
+        .collect();
+    if !unsupported.is_empty() {
+        return Err(Error::UnsupportedImage(unsupported.join(", ")));
+    }
+    paths
+        .iter()
+        .map(|path| match InputKind::of(path) {
+            InputKind::Native => Ok(path.clone()),
+            kind => convert_to_jpeg(path, kind, out_dir),
+        })
+        .collect()
+}
+
+fn convert_to_jpeg(path: &Path, kind: InputKind, out_dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(out_dir)?;
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("image");
+    // Same-named files from different folders must not overwrite each other.
+    let tag = &sha256_hex(path.to_string_lossy().as_bytes())[..8];
+    let out = out_dir.join(format!("{stem}-{tag}.jpg"));
+    match kind {
+        InputKind::Native => return Ok(path.to_path_buf()),
+        InputKind::Heic => heic_to_jpeg(path, &out)?,
+        InputKind::Raw => raw_to_jpeg(path, &out)?,
+    }
+    Ok(out)
+}
+
+fn unsupported(path: &Path, kind: InputKind) -> Error {
+    Error::UnsupportedImage(format!(
+        "{} (needs the `{}` feature)",
+        path.display(),
+        kind.feature().unwrap_or_default()
+    ))
+}
+
+#[cfg(feature = "heic")]
+fn heic_to_jpeg(path: &Path, out: &Path) -> Result<()> {
+    heic::to_jpeg(path, out)
+}
+
+#[cfg(not(feature = "heic"))]
+fn heic_to_jpeg(path: &Path, _out: &Path) -> Result<()> {
+    Err(unsupported(path, InputKind::Heic))
+}
+
+#[cfg(feature = "raw")]
+fn raw_to_jpeg(path: &Path, out: &Path) -> Result<()> {
+    let bytes = std::fs::read(path)?;
+    let preview = embedded_jpeg(&bytes).ok_or_else(|| {
+        Error::UnsupportedImage(format!("{}: no embedded JPEG preview", path.display()))
+    })?;
+    std::fs::write(out, preview)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "raw"))]
+fn raw_to_jpeg(path: &Path, _out: &Path) -> Result<()> {
+    Err(unsupported(path, InputKind::Raw))
+}
+
+/// Largest well-formed JPEG stream inside `bytes` (RAW containers carry a
+/// small thumbnail and a full-size preview; the preview wins).
+pub fn embedded_jpeg(bytes: &[u8]) -> Option<&[u8]> {
+    let mut best: Option<&[u8]> = None;
+    let mut idx = 0;
+    while idx + 3 < bytes.len() {
+        if bytes[idx..].starts_with(&[0xFF, 0xD8, 0xFF])
+            && let Some(len) = jpeg_len(&bytes[idx..])
+        {
+            let candidate = &bytes[idx..idx + len];
+            if best.is_none_or(|b| candidate.len() > b.len()) {
+                best = Some(candidate);
+            }
+            idx += len;
+            continue;
+        }
+        idx += 1;
+    }
+    best
+}
+
+/// Length of the JPEG stream starting at `data[0]` (SOI) through EOI, walking
+/// marker segments so thumbnails nested in APP segments are skipped over.
+fn jpeg_len(data: &[u8]) -> Option<usize> {
+    let mut pos = 2;
+    loop {
+        while data.get(pos) == Some(&0xFF) && data.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        if data.get(pos) != Some(&0xFF) {
+            return None;
+        }
+        let marker = *data.get(pos + 1)?;
+        match marker {
+            0xD9 => return Some(pos + 2),
+            0xD0..=0xD7 | 0x01 => pos += 2,
+            0xDA => {
+                let seg = u16::from_be_bytes([*data.get(pos + 2)?, *data.get(pos + 3)?]) as usize;
+                pos += 2 + seg;
+                // Entropy-coded data: stop at the first marker that isn't a
+                // stuffed byte or a restart marker.
+                loop {
+                    let b = *data.get(pos)?;
+                    if b == 0xFF {
+                        let next = *data.get(pos + 1)?;
+                        if next == 0x00 || (0xD0..=0xD7).contains(&next) {
+                            pos += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    pos += 1;
+                }
+            }
+            _ => {
+                let seg = u16::from_be_bytes([*data.get(pos + 2)?, *data.get(pos + 3)?]) as usize;
+                if seg < 2 {
+                    return None;
+                }
+                pos += 2 + seg;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "heic")]
+mod heic {
+    use crate::error::{Error, Result};
+    use image::codecs::jpeg::JpegEncoder;
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+    use std::path::Path;
+
+    const JPEG_QUALITY: u8 = 90;
+
+    pub fn to_jpeg(path: &Path, out: &Path) -> Result<()> {
+        let fail = |err: &dyn std::fmt::Display| {
+            Error::UnsupportedImage(format!("{}: {err}", path.display()))
+        };
+        let name = path.to_str().ok_or_else(|| fail(&"non UTF-8 path"))?;
+        let ctx = HeifContext::read_from_file(name).map_err(|err| fail(&err))?;
+        let handle = ctx.primary_image_handle().map_err(|err| fail(&err))?;
+        let decoded = LibHeif::new()
+            .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)
+            .map_err(|err| fail(&err))?;
+        let plane = decoded
+            .planes()
+            .interleaved
+            .ok_or_else(|| fail(&"missing interleaved RGB plane"))?;
+        let row = plane.width as usize * 3;
+        let mut rgb = Vec::with_capacity(row * plane.height as usize);
+        for y in 0..plane.height as usize {
+            rgb.extend_from_slice(&plane.data[y * plane.stride..y * plane.stride + row]);
+        }
+        let file = std::fs::File::create(out)?;
+        JpegEncoder::new_with_quality(std::io::BufWriter::new(file), JPEG_QUALITY)
+            .encode(
+                &rgb,
+                plane.width,
+                plane.height,
+                image::ExtendedColorType::Rgb8,
+            )
+            .map_err(|err| fail(&err))?;
+        Ok(())
+    }
+}
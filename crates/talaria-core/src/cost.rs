@@ -0,0 +1,90 @@
+//! Token and credit accounting for batch runs: what each item's LLM stages
+//! used, totals across the batch, and ordering items by cost so expensive
+//! categories stand out.
+
+use crate::models::{IngestUsage, UsageSummary};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// Stage name for HSUF enrichment.
+pub const STAGE_INGEST: &str = "ingest";
+
+/// What one LLM stage used.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageCost {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    /// Credits charged for the stage; `None` when they could not be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<i64>,
+}
+
+impl StageCost {
+    pub fn from_usage(usage: &IngestUsage) -> Self {
+        Self {
+            input_tokens: usage.input_tokens.unwrap_or_default().into(),
+            output_tokens: usage.output_tokens.unwrap_or_default().into(),
+            credits: None,
+        }
+    }
+
+    fn add(&mut self, other: &StageCost) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.credits = match (self.credits, other.credits) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or_default() + b.unwrap_or_default()),
+        };
+    }
+}
+
+/// Per-stage use of one batch item, or of a whole batch once added up.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemCost {
+    pub stages: BTreeMap<String, StageCost>,
+}
+
+impl ItemCost {
+    pub fn record(&mut self, stage: &str, cost: StageCost) {
+        self.stages.entry(stage.to_string()).or_default().add(&cost);
+    }
+
+    pub fn add(&mut self, other: &ItemCost) {
+        for (stage, cost) in &other.stages {
+            self.record(stage, cost.clone());
+        }
+    }
+
+    pub fn tokens(&self) -> i64 {
+        self.stages
+            .values()
+            .map(|s| s.input_tokens + s.output_tokens)
+            .sum()
+    }
+
+    /// Credits over all stages; `None` when no stage knows its credits.
+    pub fn credits(&self) -> Option<i64> {
+        self.stages
+            .values()
+            .filter_map(|s| s.credits)
+            .reduce(|a, b| a + b)
+    }
+}
+
+/// Credits the org has consumed, summed over the `v1/usage` summaries.
+pub fn credits_consumed(summaries: &[UsageSummary]) -> i64 {
+    summaries.iter().map(|s| s.counters.credits_consumed).sum()
+}
+
+/// Most expensive first: by credits, then by tokens. Items without a cost
+/// go last, in their original order.
+pub fn sort_by_cost<T>(items: &mut [T], cost: impl Fn(&T) -> Option<&ItemCost>) {
+    items.sort_by_key(|item| {
+        Reverse(
+            cost(item)
+                .map(|cost| (cost.credits().unwrap_or_default(), cost.tokens()))
+                .unwrap_or((-1, -1)),
+        )
+    });
+}
@@ -0,0 +1,110 @@
+//! Enriching many products at once. [`enrich_many`] fans HSUF enrich calls
+//! out with bounded concurrency; a 429 pauses every call in flight, then the
+//! rate-limited request is sent again.
+
+use crate::client::HermesClient;
+use crate::error::{ErrorKind, Result};
+use crate::models::{HsufEnrichRequest, HsufEnrichResponse};
+use futures::{Stream, StreamExt, stream};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Sends per item, including the first, before a 429 is given up on.
+pub const RATE_LIMIT_ATTEMPTS: usize = 5;
+/// Pause after the first 429 of an item; doubled per further 429.
+const RATE_LIMIT_PAUSE: Duration = Duration::from_secs(1);
+const MAX_RATE_LIMIT_PAUSE: Duration = Duration::from_secs(30);
+
+/// The result of one request passed to [`enrich_many`].
+#[derive(Debug)]
+pub struct EnrichOutcome {
+    /// Position of the request in the input.
+    pub index: usize,
+    pub sku: Option<String>,
+    /// Sends it took, including those answered with 429.
+    pub attempts: usize,
+    pub result: Result<HsufEnrichResponse>,
+}
+
+/// Counts and token usage over the outcomes of a batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct EnrichTotals {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Sends answered with 429 and retried.
+    pub rate_limited: usize,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+}
+
+impl EnrichTotals {
+    pub fn add(&mut self, outcome: &EnrichOutcome) {
+        let limited = match &outcome.result {
+            Ok(response) => {
+                self.succeeded += 1;
+                if let Some(usage) = &response.usage {
+                    self.input_tokens += i64::from(usage.input_tokens.unwrap_or_default());
+                    self.output_tokens += i64::from(usage.output_tokens.unwrap_or_default());
+                }
+                outcome.attempts - 1
+            }
+            Err(err) => {
+                self.failed += 1;
+                // The last send's 429 was not retried.
+                outcome.attempts - usize::from(err.kind() != ErrorKind::RateLimited)
+            }
+        };
+        self.rate_limited += limited;
+    }
+}
+
+/// Enriches every request with at most `max_concurrent` calls in flight,
+/// yielding outcomes as they complete (not in input order). Usage is always
+/// requested so [`EnrichTotals`] can sum it.
+pub fn enrich_many(
+    client: &HermesClient,
+    requests: Vec<HsufEnrichRequest>,
+    max_concurrent: usize,
+) -> impl Stream<Item = EnrichOutcome> + '_ {
+    // A 429 on any call holds back every send until this instant.
+    let resume_at: Arc<Mutex<Option<Instant>>> = Arc::default();
+    stream::iter(requests.into_iter().enumerate())
+        .map(move |(index, request)| {
+            let resume_at = Arc::clone(&resume_at);
+            async move {
+                let mut attempts = 0;
+                loop {
+                    let wait = resume_at
+                        .lock()
+                        .map(|at| at.saturating_duration_since(Instant::now()));
+                    if let Some(wait) = wait.filter(|wait| !wait.is_zero()) {
+                        tokio::time::sleep(wait).await;
+                        continue;
+                    }
+                    attempts += 1;
+                    let result = client.hsuf_enrich(&request, true).await;
+                    let rate_limited = result
+                        .as_ref()
+                        .is_err_and(|err| err.kind() == ErrorKind::RateLimited);
+                    if rate_limited && attempts < RATE_LIMIT_ATTEMPTS {
+                        let pause =
+                            (RATE_LIMIT_PAUSE * (1 << (attempts - 1))).min(MAX_RATE_LIMIT_PAUSE);
+                        tracing::info!(index, attempts, ?pause, "enrich rate limited; pausing");
+                        let until = Instant::now() + pause;
+                        let mut resume_at = resume_at.lock();
+                        *resume_at = Some(resume_at.map_or(until, |at| at.max(until)));
+                        continue;
+                    }
+                    return EnrichOutcome {
+                        index,
+                        sku: request.sku,
+                        attempts,
+                        result,
+                    };
+                }
+            }
+        })
+        .buffer_unordered(max_concurrent.max(1))
+}
@@ -18,6 +18,24 @@ pub enum Error {
     SupabaseUpload { status: StatusCode, message: String },
     #[error("supabase db request failed: {status} {message}")]
     SupabaseDb { status: StatusCode, message: String },
+    /// From an S3-compatible bucket; `status` is unknown when the request
+    /// never got a response.
+    #[error("s3 request failed: {message}")]
+    S3 {
+        status: Option<StatusCode>,
+        message: String,
+    },
+    #[error("webhook request failed: {status} {message}")]
+    Webhook { status: StatusCode, message: String },
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("invalid package: {0}")]
+    InvalidPackage(String),
+    #[error("unsupported image input: {0}")]
+    UnsupportedImage(String),
+    /// Withheld by `mode = "capture-only"`; see [`crate::config::AccessMode`].
+    #[error("{0} is not allowed on a capture-only station (config `mode`)")]
+    Restricted(String),
     #[error("camera unavailable: {0}")]
     CameraUnavailable(String),
     #[error("request failed: {0}")]
@@ -31,9 +49,74 @@ pub enum Error {
     },
     #[error("serialization error: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error("cassette error: {0}")]
+    Cassette(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// What kind of failure an [`Error`] is, for callers that react to classes of
+/// failure (CLI exit codes, retries) rather than to single variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Missing, rejected or unauthorized credentials.
+    Auth,
+    /// Bad input or configuration; retrying the same call will not help.
+    Validation,
+    /// The server asked us to slow down (429).
+    RateLimited,
+    /// The service could not be reached or failed on its side.
+    Unavailable,
+    Other,
+}
+
+impl ErrorKind {
+    fn of_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorKind::Auth,
+            StatusCode::TOO_MANY_REQUESTS => ErrorKind::RateLimited,
+            StatusCode::BAD_REQUEST
+            | StatusCode::NOT_FOUND
+            | StatusCode::CONFLICT
+            | StatusCode::PAYLOAD_TOO_LARGE
+            | StatusCode::UNPROCESSABLE_ENTITY => ErrorKind::Validation,
+            status if status.is_server_error() => ErrorKind::Unavailable,
+            _ => ErrorKind::Other,
+        }
+    }
 }
 
 impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::MissingApiKey { .. } | Error::Restricted(_) => ErrorKind::Auth,
+            Error::InvalidConfig(_)
+            | Error::InvalidRequest(_)
+            | Error::MissingSupabaseConfig(_)
+            | Error::InvalidPackage(_)
+            | Error::UnsupportedImage(_) => ErrorKind::Validation,
+            Error::Api { status, .. }
+            | Error::SupabaseUpload { status, .. }
+            | Error::SupabaseDb { status, .. }
+            | Error::Webhook { status, .. } => ErrorKind::of_status(*status),
+            Error::S3 { status, .. } => status.map_or(ErrorKind::Unavailable, ErrorKind::of_status),
+            Error::Http(err) => err
+                .status()
+                .map_or(ErrorKind::Unavailable, ErrorKind::of_status),
+            Error::CameraUnavailable(_) | Error::Serde(_) | Error::Cassette(_) | Error::Io(_) => {
+                ErrorKind::Other
+            }
+        }
+    }
+
+    /// The Hermes `request_id` of a failed API call, to quote to support.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Error::Api { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
     pub fn from_api(
         status: StatusCode,
         api_error: Option<ApiError>,
@@ -0,0 +1,253 @@
+//! Condition grading: a rubric of grades (New, Like New, ...) that map to
+//! marketplace condition values, and the grading recorded for a product.
+//!
+//! The rubric comes from `[[condition_grades]]` tables in the config file and
+//! falls back to [`default_rubric`]. A product's [`ProductGrading`] lives in its
+//! local manifest; applying it to a listing sets `condition`/`condition_id` and
+//! appends a condition report (notes and defects) to the description.
+
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// First line of the report appended to listing descriptions; everything from
+/// here on is replaced when the grading changes.
+pub const CONDITION_REPORT_HEADING: &str = "Condition report:";
+
+/// One grade of a rubric.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionGrade {
+    pub id: String,
+    pub label: String,
+    /// What qualifies an item for this grade; shown while grading.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guidance: Option<String>,
+    /// Marketplace condition enum, e.g. `USED_GOOD`.
+    pub condition: String,
+    /// Marketplace condition id, e.g. `5000`. Higher ids are worse condition.
+    pub condition_id: i32,
+    /// Number of defect photo slots offered for this grade.
+    #[serde(default)]
+    pub defect_slots: usize,
+}
+
+/// Built-in rubric used when the config file defines none.
+pub fn default_rubric() -> Vec<ConditionGrade> {
+    let grade = |id: &str, label: &str, guidance: &str, condition: &str, condition_id, slots| {
+        ConditionGrade {
+            id: id.to_string(),
+            label: label.to_string(),
+            guidance: Some(guidance.to_string()),
+            condition: condition.to_string(),
+            condition_id,
+            defect_slots: slots,
+        }
+    };
+    vec![
+        grade(
+            "new",
+            "New",
+            "Unused and unopened, in original packaging.",
+            "NEW",
+            1000,
+            0,
+        ),
+        grade(
+            "like_new",
+            "Like New",
+            "Used briefly; no visible wear.",
+            "USED_EXCELLENT",
+            3000,
+            1,
+        ),
+        grade(
+            "good",
+            "Good",
+            "Light wear from normal use; fully functional.",
+            "USED_GOOD",
+            5000,
+            2,
+        ),
+        grade(
+            "acceptable",
+            "Acceptable",
+            "Heavy wear or cosmetic damage; still works.",
+            "USED_ACCEPTABLE",
+            6000,
+            3,
+        ),
+    ]
+}
+
+/// Rejects rubrics a grading screen could not use: no grades, blank ids or
+/// labels, or duplicate ids.
+pub fn validate_rubric(grades: &[ConditionGrade]) -> Result<()> {
+    if grades.is_empty() {
+        return Err(Error::InvalidConfig(
+            "condition_grades must list at least one grade".into(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    for grade in grades {
+        if grade.id.trim().is_empty() || grade.label.trim().is_empty() {
+            return Err(Error::InvalidConfig(
+                "condition_grades entries need an id and a label".into(),
+            ));
+        }
+        if grade.condition.trim().is_empty() {
+            return Err(Error::InvalidConfig(format!(
+                "condition grade '{}' has no condition",
+                grade.id
+            )));
+        }
+        if !seen.insert(grade.id.as_str()) {
+            return Err(Error::InvalidConfig(format!(
+                "duplicate condition grade '{}'",
+                grade.id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// A defect noted while grading, optionally pointing at the product image
+/// that shows it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DefectNote {
+    pub note: String,
+    /// Product image (`rel_path` in the manifest) showing the defect.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub photo: Option<String>,
+}
+
+/// The grade chosen for a product. The marketplace mapping is copied from the
+/// rubric so the grading still applies if the rubric changes later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductGrading {
+    pub grade: String,
+    pub label: String,
+    pub condition: String,
+    pub condition_id: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defects: Vec<DefectNote>,
+    pub graded_at: DateTime<Utc>,
+}
+
+impl ProductGrading {
+    /// Grading for `grade`; blank notes and defects are dropped.
+    pub fn new(grade: &ConditionGrade, notes: Option<String>, defects: Vec<DefectNote>) -> Self {
+        Self {
+            grade: grade.id.clone(),
+            label: grade.label.clone(),
+            condition: grade.condition.clone(),
+            condition_id: grade.condition_id,
+            notes: notes
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty()),
+            defects: defects
+                .into_iter()
+                .map(|d| DefectNote {
+                    note: d.note.trim().to_string(),
+                    photo: d.photo,
+                })
+                .filter(|d| !d.note.is_empty())
+                .collect(),
+            graded_at: Utc::now(),
+        }
+    }
+
+    /// Condition to put on a listing whose category accepts only the given
+    /// values (empty lists mean anything goes).
+    ///
+    /// If the graded id is not allowed, the nearest allowed id that is no
+    /// better than it is used, so an item is never listed above its grade;
+    /// failing that, the worst allowed id. The condition string comes from the
+    /// same position in `allowed_conditions` when the two lists line up.
+    /// `None` means the listing's current value should be kept.
+    pub fn listing_condition(
+        &self,
+        allowed_conditions: &[String],
+        allowed_ids: &[i32],
+    ) -> (Option<String>, Option<i32>) {
+        let condition_allowed = |condition: &str| {
+            allowed_conditions.is_empty()
+                || allowed_conditions
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(condition))
+        };
+        if allowed_ids.is_empty() {
+            let condition = condition_allowed(&self.condition).then(|| self.condition.clone());
+            return (condition, Some(self.condition_id));
+        }
+
+        let id = if allowed_ids.contains(&self.condition_id) {
+            self.condition_id
+        } else {
+            allowed_ids
+                .iter()
+                .copied()
+                .filter(|id| *id > self.condition_id)
+                .min()
+                .or_else(|| allowed_ids.iter().copied().max())
+                .unwrap_or(self.condition_id)
+        };
+        let condition = if allowed_conditions.len() == allowed_ids.len() {
+            allowed_ids
+                .iter()
+                .position(|allowed| *allowed == id)
+                .map(|idx| allowed_conditions[idx].clone())
+        } else if id == self.condition_id && condition_allowed(&self.condition) {
+            Some(self.condition.clone())
+        } else {
+            None
+        };
+        (condition, Some(id))
+    }
+
+    /// The report appended to descriptions. `photo_number` maps a defect's
+    /// image to its 1-based position in the listing, if it is part of it.
+    pub fn condition_report(&self, photo_number: impl Fn(&str) -> Option<usize>) -> String {
+        let mut lines = vec![format!("{CONDITION_REPORT_HEADING} {}", self.label)];
+        if let Some(notes) = &self.notes {
+            lines.push(notes.clone());
+        }
+        for defect in &self.defects {
+            match defect.photo.as_deref().and_then(&photo_number) {
+                Some(n) => lines.push(format!("- {} (see photo {n})", defect.note)),
+                None => lines.push(format!("- {}", defect.note)),
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// `description` with any previous condition report replaced by this one.
+    pub fn describe(
+        &self,
+        description: Option<&str>,
+        photo_number: impl Fn(&str) -> Option<usize>,
+    ) -> String {
+        let base = description.map(strip_condition_report).unwrap_or_default();
+        let report = self.condition_report(photo_number);
+        if base.is_empty() {
+            report
+        } else {
+            format!("{base}\n\n{report}")
+        }
+    }
+}
+
+/// `description` without a trailing condition report.
+pub fn strip_condition_report(description: &str) -> &str {
+    let cut = if description.starts_with(CONDITION_REPORT_HEADING) {
+        Some(0)
+    } else {
+        description.rfind(&format!("\n{CONDITION_REPORT_HEADING}"))
+    };
+    match cut {
+        Some(idx) => description[..idx].trim_end(),
+        None => description,
+    }
+}
@@ -0,0 +1,165 @@
+//! Translated user-facing strings for the CLI and TUI.
+//!
+//! Each locale is a TOML file under `crates/talaria-core/locales/`, compiled
+//! in. Tables nest message ids (`[tui.tabs] home = "Home"` is
+//! `tui.tabs.home`) and values may hold `{name}` placeholders, filled by
+//! [`t!`](crate::t). A message a locale lacks falls back to English; an
+//! unknown id shows as the id itself.
+//!
+//! The locale comes from `TALARIA_LOCALE`, then `locale` in config, then the
+//! system's `LC_ALL`, `LC_MESSAGES` or `LANG`; see [`detect`].
+//!
+//! Adding a locale: copy `locales/en.toml` to `locales/<code>.toml`,
+//! translate the values (placeholders stay as they are) and add the file to
+//! [`LOCALES`]. `tests/i18n.rs` checks that every locale has the same
+//! messages and placeholders as English.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub const ENV_LOCALE: &str = "TALARIA_LOCALE";
+
+/// Bundled locales as (code, TOML source); English first, as the fallback.
+pub const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.toml")),
+    ("de", include_str!("../locales/de.toml")),
+];
+
+/// Index into [`LOCALES`] of the active locale.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+fn catalogs() -> &'static [HashMap<String, String>] {
+    static CATALOGS: OnceLock<Vec<HashMap<String, String>>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        LOCALES
+            .iter()
+            .map(|(_, source)| parse_catalog(source).unwrap_or_default())
+            .collect()
+    })
+}
+
+/// Flattens a locale file into message id → text.
+pub fn parse_catalog(source: &str) -> Result<HashMap<String, String>, toml::de::Error> {
+    fn flatten(prefix: &str, table: &toml::Table, out: &mut HashMap<String, String>) {
+        for (key, value) in table {
+            let id = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match value {
+                toml::Value::String(text) => {
+                    out.insert(id, text.clone());
+                }
+                toml::Value::Table(table) => flatten(&id, table, out),
+                _ => {}
+            }
+        }
+    }
+    let table = toml::from_str::<toml::Table>(source)?;
+    let mut out = HashMap::new();
+    flatten("", &table, &mut out);
+    Ok(out)
+}
+
+/// Names of the `{placeholders}` in a message, in order of appearance.
+pub fn placeholders(text: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start + 1..].find('}') else {
+            break;
+        };
+        out.push(&rest[start + 1..start + 1 + len]);
+        rest = &rest[start + 1 + len + 1..];
+    }
+    out
+}
+
+/// The bundled locale a `de_DE.UTF-8`/`de-AT`/`de` style tag asks for.
+pub fn supported(tag: &str) -> Option<&'static str> {
+    let language = tag
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    LOCALES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| *code == language)
+}
+
+/// Picks the locale: `TALARIA_LOCALE`, then `configured`, then `LC_ALL`,
+/// `LC_MESSAGES` and `LANG`. The first one set decides; a locale that is
+/// not bundled (or `C`/`POSIX`) means English.
+pub fn detect(configured: Option<&str>) -> &'static str {
+    let env = |name: &str| std::env::var(name).ok();
+    [
+        env(ENV_LOCALE),
+        configured.map(str::to_string),
+        env("LC_ALL"),
+        env("LC_MESSAGES"),
+        env("LANG"),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|tag| !tag.trim().is_empty())
+    .and_then(|tag| supported(tag.trim()))
+    .unwrap_or("en")
+}
+
+/// Switches to `code`; unknown codes switch to English. Returns the locale
+/// now in use.
+pub fn set_locale(code: &str) -> &'static str {
+    let index = supported(code)
+        .and_then(|code| LOCALES.iter().position(|(c, _)| *c == code))
+        .unwrap_or(0);
+    CURRENT.store(index, Ordering::Relaxed);
+    LOCALES[index].0
+}
+
+/// [`detect`]s the locale and switches to it; frontends call this once the
+/// config is loaded, and again when it is reloaded.
+pub fn init(configured: Option<&str>) -> &'static str {
+    set_locale(detect(configured))
+}
+
+/// Code of the locale in use, e.g. `de`.
+pub fn locale() -> &'static str {
+    LOCALES[CURRENT.load(Ordering::Relaxed)].0
+}
+
+/// The message `id` in the active locale, else English, else `id` itself.
+pub fn text(id: &str) -> &str {
+    let catalogs = catalogs();
+    catalogs[CURRENT.load(Ordering::Relaxed)]
+        .get(id)
+        .or_else(|| catalogs[0].get(id))
+        .map_or(id, String::as_str)
+}
+
+/// [`text`] with each `{name}` replaced by its value from `args`.
+pub fn format(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = text(id).to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    out
+}
+
+/// A translated message as a `String`: `t!("tui.tabs.home")`, or with
+/// placeholders `t!("cli.opened", url = url)`.
+#[macro_export]
+macro_rules! t {
+    ($id:expr) => {
+        $crate::i18n::text($id).to_string()
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format(
+            $id,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
@@ -0,0 +1,108 @@
+//! Local idempotency keys for listing submissions.
+//!
+//! A key is derived per logical request (endpoint + request body) and persisted
+//! so that a retry after a timeout, or a re-run of the same command, reuses the
+//! key the server may already have seen instead of charging credits twice.
+
+use crate::config::data_dir;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+pub const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+const STORE_FILE: &str = "idempotency.json";
+const KEY_TTL_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyEntry {
+    pub key: String,
+    pub scope: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// File-backed map of request fingerprints to idempotency keys.
+#[derive(Debug)]
+pub struct IdempotencyStore {
+    path: PathBuf,
+    entries: BTreeMap<String, IdempotencyEntry>,
+}
+
+impl IdempotencyStore {
+    /// Opens the store under the local data dir (`~/.local/share/talaria`).
+    pub fn open_default() -> Result<Self> {
+        let dir = data_dir().ok_or_else(|| {
+            Error::InvalidConfig("unable to determine local data directory".into())
+        })?;
+        Self::open(dir.join(STORE_FILE))
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut entries: BTreeMap<String, IdempotencyEntry> = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        let cutoff = Utc::now() - Duration::hours(KEY_TTL_HOURS);
+        entries.retain(|_, entry| entry.created_at > cutoff);
+        Ok(Self { path, entries })
+    }
+
+    /// Returns the key for this request, creating and persisting one if needed.
+    pub fn key_for<B: Serialize + ?Sized>(&mut self, scope: &str, body: &B) -> Result<String> {
+        let fingerprint = fingerprint(scope, body)?;
+        if let Some(entry) = self.entries.get(&fingerprint) {
+            return Ok(entry.key.clone());
+        }
+        let entry = IdempotencyEntry {
+            key: Uuid::new_v4().to_string(),
+            scope: scope.to_string(),
+            created_at: Utc::now(),
+        };
+        let key = entry.key.clone();
+        self.entries.insert(fingerprint, entry);
+        self.save()?;
+        Ok(key)
+    }
+
+    /// Forgets the key once the server has acknowledged the request, so the
+    /// next identical submission is treated as a new logical request.
+    pub fn release<B: Serialize + ?Sized>(&mut self, scope: &str, body: &B) -> Result<()> {
+        let fingerprint = fingerprint(scope, body)?;
+        if self.entries.remove(&fingerprint).is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&self.entries)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+fn fingerprint<B: Serialize + ?Sized>(scope: &str, body: &B) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(scope.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(serde_json::to_vec(body)?);
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -0,0 +1,131 @@
+//! Where uploaded images are kept: Supabase Storage, or with the `s3`
+//! feature any S3-compatible bucket. [`crate::images`] uploads through
+//! either.
+
+use crate::config::Config;
+use crate::error::Result;
+#[cfg(feature = "s3")]
+use crate::s3::S3Store;
+use crate::supabase::{SupabaseClient, SupabaseObject};
+
+#[derive(Clone)]
+pub enum ImageStore {
+    Supabase(SupabaseClient),
+    #[cfg(feature = "s3")]
+    S3(S3Store),
+}
+
+impl From<SupabaseClient> for ImageStore {
+    fn from(client: SupabaseClient) -> Self {
+        ImageStore::Supabase(client)
+    }
+}
+
+#[cfg(feature = "s3")]
+impl From<S3Store> for ImageStore {
+    fn from(store: S3Store) -> Self {
+        ImageStore::S3(store)
+    }
+}
+
+impl ImageStore {
+    /// The configured store: the S3 bucket when `s3_endpoint` is set, else
+    /// Supabase (always, in simulation mode).
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        if config.simulate {
+            return Ok(Some(
+                SupabaseClient::simulated(config.supabase.as_ref()).into(),
+            ));
+        }
+        if let Some(s3) = &config.s3 {
+            return s3_store(s3).map(Some);
+        }
+        match &config.supabase {
+            Some(cfg) => SupabaseClient::from_config(cfg).map(|client| Some(client.into())),
+            None => Ok(None),
+        }
+    }
+
+    pub fn is_simulated(&self) -> bool {
+        match self {
+            ImageStore::Supabase(client) => client.is_simulated(),
+            #[cfg(feature = "s3")]
+            ImageStore::S3(_) => false,
+        }
+    }
+
+    pub fn upload_prefix(&self) -> &str {
+        match self {
+            ImageStore::Supabase(client) => client.upload_prefix(),
+            #[cfg(feature = "s3")]
+            ImageStore::S3(store) => store.upload_prefix(),
+        }
+    }
+
+    /// Stores `bytes` under the upload prefix and returns the new object path.
+    pub async fn upload_object(&self, filename_hint: &str, bytes: Vec<u8>) -> Result<String> {
+        match self {
+            ImageStore::Supabase(client) => client.upload_object(filename_hint, bytes).await,
+            #[cfg(feature = "s3")]
+            ImageStore::S3(store) => store.upload_object(filename_hint, bytes).await,
+        }
+    }
+
+    /// URL handed to listings for an existing object: signed or presigned
+    /// when the store is configured for it, public otherwise.
+    pub async fn object_url(&self, object_path: &str) -> Result<String> {
+        match self {
+            ImageStore::Supabase(client) => client.object_url(object_path).await,
+            #[cfg(feature = "s3")]
+            ImageStore::S3(store) => store.object_url(object_path).await,
+        }
+    }
+
+    /// Identifies the storage target for the upload cache.
+    pub fn cache_scope(&self) -> String {
+        match self {
+            ImageStore::Supabase(client) => client.cache_scope(),
+            #[cfg(feature = "s3")]
+            ImageStore::S3(store) => store.cache_scope(),
+        }
+    }
+
+    /// Lists every object under `prefix`, a folder path relative to the
+    /// bucket.
+    pub async fn list_objects(&self, prefix: &str) -> Result<Vec<SupabaseObject>> {
+        match self {
+            ImageStore::Supabase(client) => client.list_objects(prefix).await,
+            #[cfg(feature = "s3")]
+            ImageStore::S3(store) => store.list_objects(prefix).await,
+        }
+    }
+
+    pub async fn delete_object(&self, object_path: &str) -> Result<()> {
+        match self {
+            ImageStore::Supabase(client) => client.delete_object(object_path).await,
+            #[cfg(feature = "s3")]
+            ImageStore::S3(store) => store.delete_object(object_path).await,
+        }
+    }
+
+    /// Maps a URL this store handed out back to its object path.
+    pub fn object_path_from_url(&self, url: &str) -> Option<String> {
+        match self {
+            ImageStore::Supabase(client) => client.object_path_from_url(url),
+            #[cfg(feature = "s3")]
+            ImageStore::S3(store) => store.object_path_from_url(url),
+        }
+    }
+}
+
+#[cfg(feature = "s3")]
+fn s3_store(config: &crate::config::S3Config) -> Result<ImageStore> {
+    S3Store::from_config(config).map(ImageStore::S3)
+}
+
+#[cfg(not(feature = "s3"))]
+fn s3_store(_config: &crate::config::S3Config) -> Result<ImageStore> {
+    Err(crate::error::Error::InvalidConfig(
+        "s3_endpoint is set, but this build lacks the `s3` feature".into(),
+    ))
+}
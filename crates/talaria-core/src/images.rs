@@ -1,56 +1,373 @@
 use crate::camera;
+use crate::convert::{self, InputKind};
 use crate::error::{Error, Result};
-use crate::supabase::SupabaseClient;
+use crate::image_store::ImageStore;
+use crate::quality::{ImageMetrics, QualityReport, QualityRules};
+use crate::simulate;
+use crate::upload_cache::{UploadCache, sha256_hex};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-/// Build a Supabase client if configuration is present.
-pub fn supabase_from_config(config: &crate::config::Config) -> Result<Option<SupabaseClient>> {
-    match &config.supabase {
-        Some(cfg) => SupabaseClient::from_config(cfg).map(Some),
-        None => Ok(None),
+/// Result of uploading one local file.
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadedImage {
+    pub path: PathBuf,
+    pub url: String,
+    pub sha256: String,
+    /// True when identical bytes were already in the bucket and no upload happened.
+    pub reused: bool,
+}
+
+pub async fn upload_paths(paths: &[PathBuf], client: &ImageStore) -> Result<Vec<String>> {
+    Ok(upload_files(paths, client)
+        .await?
+        .into_iter()
+        .map(|uploaded| uploaded.url)
+        .collect())
+}
+
+/// Uploads each file, skipping bytes already uploaded to the same bucket
+/// (tracked by sha256 in the local [`UploadCache`]). Up to
+/// [`UPLOAD_CONCURRENCY`] uploads run at once; results follow `paths` order.
+pub async fn upload_files(paths: &[PathBuf], client: &ImageStore) -> Result<Vec<UploadedImage>> {
+    let sources = convert::prepare_for_upload(paths, &converted_dir())?;
+    let mut cache = UploadCache::open_default()?;
+    let scope = client.cache_scope();
+    let mut hashes = Vec::with_capacity(paths.len());
+    let mut pending = Vec::new();
+    let mut known: HashMap<String, String> = HashMap::new();
+    for path in &sources {
+        let bytes = read_upload(path)?;
+        let sha256 = sha256_hex(&bytes);
+        if let Some(entry) = cache.get(&scope, &sha256) {
+            known.insert(sha256.clone(), entry.object_path.clone());
+        } else if !pending.iter().any(|(sha, _, _)| *sha == sha256) {
+            pending.push((sha256.clone(), upload_name(path), bytes));
+        }
+        hashes.push(sha256);
+    }
+
+    let fresh: Vec<(String, String)> =
+        stream::iter(pending.into_iter().map(|(sha256, name, bytes)| async move {
+            client
+                .upload_object(&name, bytes)
+                .await
+                .map(|object_path| (sha256, object_path))
+        }))
+        .buffered(UPLOAD_CONCURRENCY)
+        .try_collect()
+        .await?;
+    let mut unclaimed = HashSet::new();
+    for (sha256, object_path) in fresh {
+        cache.insert(&scope, &sha256, &object_path)?;
+        unclaimed.insert(sha256.clone());
+        known.insert(sha256, object_path);
     }
+
+    let mut out = Vec::with_capacity(paths.len());
+    for (path, sha256) in paths.iter().zip(hashes) {
+        let object_path = &known[&sha256];
+        // Only the first file with freshly uploaded bytes counts as an upload.
+        let reused = !unclaimed.remove(&sha256);
+        out.push(UploadedImage {
+            path: path.clone(),
+            url: client.object_url(object_path).await?,
+            sha256,
+            reused,
+        });
+    }
+    Ok(out)
 }
 
-pub async fn upload_paths(paths: &[PathBuf], client: &SupabaseClient) -> Result<Vec<String>> {
-    let mut urls = Vec::new();
-    for path in paths {
-        let url = client.upload_image_file(path).await?;
-        urls.push(url);
+pub async fn upload_file_cached(
+    path: &Path,
+    client: &ImageStore,
+    cache: &mut UploadCache,
+) -> Result<UploadedImage> {
+    let source = convert::prepare_for_upload(&[path.to_path_buf()], &converted_dir())?;
+    let source = source.first().map(PathBuf::as_path).unwrap_or(path);
+    let bytes = read_upload(source)?;
+    let sha256 = sha256_hex(&bytes);
+    let scope = client.cache_scope();
+    if let Some(entry) = cache.get(&scope, &sha256) {
+        return Ok(UploadedImage {
+            path: path.to_path_buf(),
+            url: client.object_url(&entry.object_path).await?,
+            sha256,
+            reused: true,
+        });
+    }
+    let object_path = client.upload_object(&upload_name(source), bytes).await?;
+    cache.insert(&scope, &sha256, &object_path)?;
+    Ok(UploadedImage {
+        path: path.to_path_buf(),
+        url: client.object_url(&object_path).await?,
+        sha256,
+        reused: false,
+    })
+}
+
+fn read_upload(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|err| {
+        Error::MissingSupabaseConfig(format!("read error {}: {err}", path.display()))
+    })
+}
+
+fn upload_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("image.jpg")
+        .to_string()
+}
+
+/// Image extensions collected from a directory when no `--include` glob is given.
+pub const DEFAULT_IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp"];
+/// Files above this size are skipped unless the filter says otherwise.
+pub const DEFAULT_MAX_UPLOAD_BYTES: u64 = 25 * 1024 * 1024;
+/// Uploads in flight at once; results keep the input order.
+pub const UPLOAD_CONCURRENCY: usize = 4;
+
+/// Where converted HEIC/RAW inputs are written before upload.
+pub fn converted_dir() -> PathBuf {
+    std::env::temp_dir().join("talaria-converted")
+}
+
+/// Which files [`collect_dir`] picks up.
+#[derive(Debug, Clone)]
+pub struct DirFilter {
+    /// Globs matched against the file name or the path relative to the root
+    /// (e.g. `*.jpg`, `angles/**`). Empty means [`DEFAULT_IMAGE_EXTENSIONS`]
+    /// plus HEIC/RAW inputs (reported as skipped when this build can't convert them).
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub recursive: bool,
+    pub max_bytes: Option<u64>,
+}
+
+impl Default for DirFilter {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            recursive: false,
+            max_bytes: Some(DEFAULT_MAX_UPLOAD_BYTES),
+        }
     }
-    Ok(urls)
 }
 
-pub async fn upload_dir(dir: &Path, client: &SupabaseClient) -> Result<Vec<String>> {
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// Files selected from a directory, ordered by their path relative to the
+/// root (byte-wise, so `a/1.jpg` sorts before `b.jpg`), plus everything that
+/// was left out and why.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DirListing {
+    pub files: Vec<PathBuf>,
+    pub skipped: Vec<SkippedFile>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DirUpload {
+    pub uploaded: Vec<UploadedImage>,
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Walks `dir` according to `filter`. Hidden entries (`.DS_Store`, `.git`)
+/// are always skipped; sidecars and other non-matching files are reported in
+/// [`DirListing::skipped`].
+pub fn collect_dir(dir: &Path, filter: &DirFilter) -> Result<DirListing> {
     if !dir.is_dir() {
         return Err(Error::MissingSupabaseConfig(format!(
             "not a directory: {}",
             dir.display()
         )));
     }
-    let mut paths = Vec::new();
-    for entry in fs::read_dir(dir).map_err(|err| Error::MissingSupabaseConfig(err.to_string()))? {
-        let entry = entry.map_err(|err| Error::MissingSupabaseConfig(err.to_string()))?;
-        let path = entry.path();
-        if path.is_file() {
-            paths.push(path);
+    let include = build_globs(&filter.include)?;
+    let exclude = build_globs(&filter.exclude)?;
+    let mut listing = DirListing::default();
+    let mut found: Vec<(String, PathBuf)> = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current).map_err(|err| {
+            Error::MissingSupabaseConfig(format!("read {}: {err}", current.display()))
+        })? {
+            let entry = entry.map_err(|err| Error::MissingSupabaseConfig(err.to_string()))?;
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let rel = path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if name.starts_with('.') {
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                listing.skipped.push(SkippedFile {
+                    path,
+                    reason: "unreadable".into(),
+                });
+                continue;
+            };
+            if meta.is_dir() {
+                if filter.recursive {
+                    pending.push(path);
+                }
+                continue;
+            }
+            let matches = |set: &GlobSet| set.is_match(&name) || set.is_match(&rel);
+            let kind = InputKind::of(&path);
+            let included = if filter.include.is_empty() {
+                has_image_extension(&path) || kind != InputKind::Native
+            } else {
+                matches(&include)
+            };
+            let reason = if !included {
+                Some("not matched by include filter".to_string())
+            } else if !kind.supported() {
+                Some(format!(
+                    "needs the `{}` feature to convert",
+                    kind.feature().unwrap_or_default()
+                ))
+            } else if !filter.exclude.is_empty() && matches(&exclude) {
+                Some("matched exclude filter".to_string())
+            } else {
+                filter
+                    .max_bytes
+                    .filter(|max| meta.len() > *max)
+                    .map(|max| format!("{} bytes exceeds limit of {max}", meta.len()))
+            };
+            match reason {
+                Some(reason) => listing.skipped.push(SkippedFile { path, reason }),
+                None => found.push((rel, path)),
+            }
         }
     }
-    if paths.is_empty() {
+    found.sort_by(|a, b| a.0.cmp(&b.0));
+    listing.files = found.into_iter().map(|(_, path)| path).collect();
+    listing.skipped.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(listing)
+}
+
+pub async fn upload_dir(dir: &Path, client: &ImageStore) -> Result<Vec<String>> {
+    Ok(upload_dir_filtered(dir, &DirFilter::default(), client)
+        .await?
+        .uploaded
+        .into_iter()
+        .map(|uploaded| uploaded.url)
+        .collect())
+}
+
+pub async fn upload_dir_filtered(
+    dir: &Path,
+    filter: &DirFilter,
+    client: &ImageStore,
+) -> Result<DirUpload> {
+    let listing = collect_dir(dir, filter)?;
+    if listing.files.is_empty() {
         return Err(Error::MissingSupabaseConfig(format!(
-            "no files found in {}",
-            dir.display()
+            "no matching files found in {} ({} skipped)",
+            dir.display(),
+            listing.skipped.len()
         )));
     }
-    upload_paths(&paths, client).await
+    Ok(DirUpload {
+        uploaded: upload_files(&listing.files, client).await?,
+        skipped: listing.skipped,
+    })
+}
+
+fn has_image_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            DEFAULT_IMAGE_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+fn build_globs(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .case_insensitive(true)
+            .literal_separator(false)
+            .build()
+            .map_err(|err| Error::InvalidConfig(format!("invalid glob '{pattern}': {err}")))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|err| Error::InvalidConfig(format!("invalid glob set: {err}")))
+}
+
+/// Checks local photos against `rules` before they are uploaded. Photos in
+/// formats that are converted first (HEIC, RAW) are listed as unchecked.
+pub fn quality_report(paths: &[PathBuf], rules: &QualityRules) -> Result<QualityReport> {
+    let mut report = QualityReport::default();
+    for path in paths {
+        if InputKind::of(path) != InputKind::Native {
+            report.unchecked.push(path.clone());
+            continue;
+        }
+        match decode_luma(path) {
+            Ok(metrics) => report.add(path.clone(), metrics, rules),
+            Err(Error::UnsupportedImage(reason)) => report.add_unreadable(path.clone(), reason),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(feature = "quality")]
+fn decode_luma(path: &Path) -> Result<ImageMetrics> {
+    let image = image::open(path)
+        .map_err(|err| Error::UnsupportedImage(err.to_string()))?
+        .to_luma8();
+    Ok(ImageMetrics::from_luma(
+        image.width() as usize,
+        image.height() as usize,
+        image.as_raw(),
+    ))
+}
+
+#[cfg(not(feature = "quality"))]
+fn decode_luma(path: &Path) -> Result<ImageMetrics> {
+    Err(Error::InvalidConfig(format!(
+        "cannot check {}: image quality checks need the `quality` feature",
+        path.display()
+    )))
+}
+
+/// Takes `count` photos (fake frames when simulating) into `out_dir`.
+pub fn capture(
+    count: usize,
+    device_idx: Option<u32>,
+    out_dir: &Path,
+    simulated: bool,
+) -> Result<Vec<PathBuf>> {
+    if simulated {
+        simulate::capture_frames(count, out_dir)
+    } else {
+        camera::capture_many(count, device_idx, out_dir)
+    }
 }
 
 pub async fn capture_and_upload(
     count: usize,
     device_idx: Option<u32>,
     out_dir: &Path,
-    client: &SupabaseClient,
+    client: &ImageStore,
 ) -> Result<Vec<String>> {
-    let captures = camera::capture_many(count, device_idx, out_dir)?;
+    let captures = capture(count, device_idx, out_dir, client.is_simulated())?;
     upload_paths(&captures, client).await
 }
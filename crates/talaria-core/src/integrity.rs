@@ -0,0 +1,157 @@
+//! Checks that the URL storage serves for an upload carries the bytes that
+//! were uploaded, by the sha256 recorded when the image was committed.
+//!
+//! A `HEAD` compares the size, and the ETag when the store uses the sha256
+//! as one; a spot-check downloads the object and hashes it again.
+//!
+//! [`verify_image_urls`] is the lighter pre-flight run before a listing is
+//! submitted: Hermes fetches the images server-side, and a URL it cannot
+//! read fails the listing after credits are spent.
+
+use crate::error::Result;
+use crate::upload_cache::sha256_hex;
+use futures::stream::{self, StreamExt};
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG};
+use serde::Serialize;
+
+/// URLs checked at once by [`verify_image_urls`].
+pub const VERIFY_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServedCheck {
+    /// The downloaded bytes hash to the expected sha256.
+    Rehashed,
+    /// Size (and ETag, when it is a sha256) match; the content was not read.
+    Headers,
+    /// What is served differs from what was uploaded.
+    Mismatch(String),
+}
+
+/// Checks `url` against the uploaded `sha256` and `len`, downloading the
+/// object to hash it when `rehash` is set. Network failures are errors, not
+/// mismatches.
+pub async fn verify_served(
+    http: &reqwest::Client,
+    url: &str,
+    sha256: &str,
+    len: u64,
+    rehash: bool,
+) -> Result<ServedCheck> {
+    let head = http.head(url).send().await?.error_for_status()?;
+    let served_len = head
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if let Some(served_len) = served_len.filter(|served| *served != len) {
+        return Ok(ServedCheck::Mismatch(format!(
+            "served {served_len} bytes, uploaded {len}"
+        )));
+    }
+    let etag = head
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim_start_matches("W/").trim_matches('"'));
+    if let Some(etag) = etag.filter(|etag| is_sha256(etag) && !etag.eq_ignore_ascii_case(sha256)) {
+        return Ok(ServedCheck::Mismatch(format!(
+            "served ETag {etag}, uploaded sha256 {sha256}"
+        )));
+    }
+    if !rehash {
+        return Ok(ServedCheck::Headers);
+    }
+    let bytes = http
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let served = sha256_hex(&bytes);
+    Ok(if served.eq_ignore_ascii_case(sha256) {
+        ServedCheck::Rehashed
+    } else {
+        ServedCheck::Mismatch(format!("served sha256 {served}, uploaded {sha256}"))
+    })
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// An image URL that will not work in a listing, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BrokenImageUrl {
+    pub url: String,
+    pub reason: String,
+}
+
+/// `HEAD`s each URL and reports those that do not answer 2xx, are served as
+/// something other than an image, or are larger than `max_bytes`. Servers
+/// that refuse `HEAD` are asked with a `GET` whose body is not read. Missing
+/// headers pass; results follow `urls` order.
+pub async fn verify_image_urls(
+    http: &reqwest::Client,
+    urls: &[String],
+    max_bytes: u64,
+) -> Vec<BrokenImageUrl> {
+    stream::iter(urls.iter().map(|url| async move {
+        check_image_url(http, url, max_bytes)
+            .await
+            .map(|reason| BrokenImageUrl {
+                url: url.clone(),
+                reason,
+            })
+    }))
+    .buffered(VERIFY_CONCURRENCY)
+    .filter_map(|broken| async move { broken })
+    .collect()
+    .await
+}
+
+async fn check_image_url(http: &reqwest::Client, url: &str, max_bytes: u64) -> Option<String> {
+    let mut resp = match http.head(url).send().await {
+        Ok(resp) => resp,
+        Err(err) => return Some(format!("unreachable: {err}")),
+    };
+    if matches!(
+        resp.status(),
+        StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+    ) {
+        resp = match http.get(url).send().await {
+            Ok(resp) => resp,
+            Err(err) => return Some(format!("unreachable: {err}")),
+        };
+    }
+    let status = resp.status();
+    if !status.is_success() {
+        return Some(format!("HTTP {status}"));
+    }
+    let header = |name| {
+        resp.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+    if let Some(mime) = header(CONTENT_TYPE).filter(|mime| !is_image_type(mime)) {
+        return Some(format!("served as {mime}, not an image"));
+    }
+    header(CONTENT_LENGTH)
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|len| *len > max_bytes)
+        .map(|len| format!("{len} bytes exceeds limit of {max_bytes}"))
+}
+
+/// Image types, plus the generic binary types object stores serve for
+/// uploads stored without one.
+fn is_image_type(mime: &str) -> bool {
+    let essence = mime.split(';').next().unwrap_or(mime).trim();
+    essence
+        .get(..6)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("image/"))
+        || ["application/octet-stream", "binary/octet-stream"]
+            .iter()
+            .any(|generic| essence.eq_ignore_ascii_case(generic))
+}
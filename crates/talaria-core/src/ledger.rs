@@ -0,0 +1,244 @@
+//! Append-only local ledger of enqueued listing jobs.
+//!
+//! Hermes has no "list my jobs" endpoint, so every successful `enqueue_listing`
+//! is recorded here (one JSON object per line) for later lookups. Each entry
+//! keeps the request it was enqueued with, so a failed job can be retried;
+//! state changes are appended as separate lines and folded in on read.
+
+use crate::config::data_dir;
+use crate::error::{Error, Result};
+use crate::metrics;
+use crate::models::PublicListingRequest;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const LEDGER_FILE: &str = "jobs.jsonl";
+
+/// What the ledger last heard about a job.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackedState {
+    /// Enqueued; no outcome recorded yet.
+    #[default]
+    Pending,
+    Completed,
+    Failed,
+    /// Failed, then re-enqueued as another job.
+    Retried,
+    /// Failed and set aside without a retry.
+    Dismissed,
+}
+
+impl TrackedState {
+    fn is_pending(&self) -> bool {
+        *self == TrackedState::Pending
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobLedgerEntry {
+    pub job_id: String,
+    pub sku: Option<String>,
+    pub enqueued_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    /// The request as enqueued. Entries written before requests were kept
+    /// have none and cannot be retried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<PublicListingRequest>,
+    /// The failed job this one re-enqueued.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_of: Option<String>,
+    #[serde(default, skip_serializing_if = "TrackedState::is_pending")]
+    pub state: TrackedState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When the job's outcome was first recorded; filled in on read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+impl JobLedgerEntry {
+    pub fn new(job_id: &str, request: &PublicListingRequest) -> Self {
+        Self {
+            job_id: job_id.to_string(),
+            sku: request.sku.clone(),
+            enqueued_at: Utc::now(),
+            product_id: None,
+            request: Some(request.clone()),
+            retry_of: None,
+            state: TrackedState::Pending,
+            error: None,
+            settled_at: None,
+        }
+    }
+
+    /// Failed with a stored request to send again.
+    pub fn retryable(&self) -> bool {
+        self.state == TrackedState::Failed && self.request.is_some()
+    }
+}
+
+/// A state change for an earlier entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StateUpdate {
+    job_id: String,
+    state: TrackedState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Line {
+    Entry(Box<JobLedgerEntry>),
+    Update(StateUpdate),
+}
+
+#[derive(Debug, Clone)]
+pub struct JobLedger {
+    path: PathBuf,
+}
+
+impl JobLedger {
+    /// Opens the ledger under the local data dir (`~/.local/share/talaria`).
+    pub fn open_default() -> Result<Self> {
+        let dir = data_dir().ok_or_else(|| {
+            Error::InvalidConfig("unable to determine local data directory".into())
+        })?;
+        Ok(Self::open(dir.join(LEDGER_FILE)))
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, job_id: &str, request: &PublicListingRequest) -> Result<JobLedgerEntry> {
+        self.append(JobLedgerEntry::new(job_id, request))
+    }
+
+    /// Records `entry` as is, for callers that also know the product or the
+    /// job being retried.
+    pub fn append(&self, entry: JobLedgerEntry) -> Result<JobLedgerEntry> {
+        tracing::info!(
+            job_id = %entry.job_id,
+            product_id = entry.product_id.as_deref().unwrap_or_default(),
+            sku = entry.sku.as_deref().unwrap_or_default(),
+            retry_of = entry.retry_of.as_deref().unwrap_or_default(),
+            "listing job enqueued"
+        );
+        self.write_line(&entry)?;
+        Ok(entry)
+    }
+
+    pub fn set_state(&self, job_id: &str, state: TrackedState, error: Option<&str>) -> Result<()> {
+        let outcome = match state {
+            TrackedState::Completed => Some("completed"),
+            TrackedState::Failed => Some("failed"),
+            _ => None,
+        };
+        if let Some(outcome) = outcome {
+            // Only the first outcome counts; later updates are bookkeeping.
+            if let Ok(Some(entry)) = self.get(job_id).map(|e| e.filter(|e| e.state.is_pending())) {
+                let elapsed = (Utc::now() - entry.enqueued_at)
+                    .to_std()
+                    .unwrap_or_default();
+                metrics::global().observe(metrics::JOB_DURATION, &[("outcome", outcome)], elapsed);
+            }
+        }
+        match state {
+            TrackedState::Failed => tracing::warn!(
+                job_id,
+                error = error.unwrap_or_default(),
+                "listing job failed"
+            ),
+            _ => tracing::info!(job_id, state = ?state, "listing job state"),
+        }
+        self.write_line(&StateUpdate {
+            job_id: job_id.to_string(),
+            state,
+            error: error.map(str::to_string),
+            at: Utc::now(),
+        })
+    }
+
+    /// Returns up to `limit` entries with their latest state, newest first.
+    /// Malformed lines are skipped.
+    pub fn recent(&self, limit: usize) -> Result<Vec<JobLedgerEntry>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut entries: Vec<JobLedgerEntry> = Vec::new();
+        let mut index = HashMap::new();
+        for line in contents.lines() {
+            match serde_json::from_str::<Line>(line) {
+                Ok(Line::Entry(entry)) => {
+                    index.insert(entry.job_id.clone(), entries.len());
+                    entries.push(*entry);
+                }
+                Ok(Line::Update(update)) => {
+                    if let Some(entry) = index.get(&update.job_id).map(|&i| &mut entries[i]) {
+                        if entry.settled_at.is_none()
+                            && matches!(
+                                update.state,
+                                TrackedState::Completed | TrackedState::Failed
+                            )
+                        {
+                            entry.settled_at = Some(update.at);
+                        }
+                        entry.state = update.state;
+                        if update.error.is_some() {
+                            entry.error = update.error;
+                        }
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+        Ok(entries.into_iter().rev().take(limit).collect())
+    }
+
+    /// Failed jobs that can be retried, newest first.
+    pub fn failed(&self) -> Result<Vec<JobLedgerEntry>> {
+        Ok(self
+            .recent(usize::MAX)?
+            .into_iter()
+            .filter(JobLedgerEntry::retryable)
+            .collect())
+    }
+
+    /// The latest state of `job_id`, if it was recorded.
+    pub fn get(&self, job_id: &str) -> Result<Option<JobLedgerEntry>> {
+        Ok(self
+            .recent(usize::MAX)?
+            .into_iter()
+            .find(|entry| entry.job_id == job_id))
+    }
+
+    fn write_line(&self, value: &impl Serialize) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut line = serde_json::to_vec(value)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        Ok(())
+    }
+}
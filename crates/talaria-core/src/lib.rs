@@ -1,15 +1,70 @@
 //! Core Hermes API client and typed models derived from the OpenAPI spec.
 //! This crate is consumed by both the CLI and TUI frontends.
+//!
+//! With default features off it builds for `wasm32-unknown-unknown`, keeping
+//! the models, config types and [`HermesClient`] for browser dashboards.
 
+pub mod artifacts;
+pub mod barcode;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "native")]
+pub mod browser;
 pub mod camera;
 pub mod client;
+pub mod color;
 pub mod config;
+#[cfg(feature = "native")]
+pub mod convert;
+pub mod cost;
+#[cfg(feature = "native")]
+pub mod enrich;
 pub mod error;
+pub mod grading;
+pub mod i18n;
+pub mod idempotency;
+#[cfg(feature = "native")]
+pub mod image_store;
+#[cfg(feature = "native")]
 pub mod images;
+#[cfg(feature = "native")]
+pub mod integrity;
+#[cfg(feature = "native")]
+pub mod ledger;
+pub mod logging;
+pub mod measure;
+pub mod metrics;
 pub mod models;
+pub mod net;
+pub mod notify;
+pub mod package;
+pub mod pricing;
+pub mod product_diff;
+pub mod quality;
+pub mod report;
+pub mod requests;
+#[cfg(feature = "s3")]
+pub mod s3;
+pub mod shots;
+pub mod simulate;
+pub mod spec;
+#[cfg(feature = "native")]
+pub mod stats;
+#[cfg(feature = "native")]
 pub mod supabase;
+pub mod templates;
+pub mod upload_cache;
+pub mod upload_journal;
+#[cfg(feature = "native")]
+pub mod usage_history;
+pub mod vcr;
+pub mod warnings;
+#[cfg(feature = "native")]
+pub mod watch;
+#[cfg(feature = "native")]
+pub mod workspace;
 
 pub use crate::client::HermesClient;
 pub use crate::config::Config;
-pub use crate::error::{Error, Result};
+pub use crate::error::{Error, ErrorKind, Result};
 pub use crate::models::*;
@@ -0,0 +1,383 @@
+//! Structured logs: `tracing` events from the core client and the TUI
+//! workers, written as one JSON object per line to a size-rotated file in
+//! the logs dir (`<captures>/logs`), so a failed listing's `request_id` can
+//! be lined up with the capture and upload events around it.
+//!
+//! Targets are module paths (`talaria_core::client`,
+//! `talaria_tui::workers::upload`, ...) and are filtered with `RUST_LOG`-style
+//! directives from `log_filter` / `TALARIA_LOG`.
+
+use crate::error::{Error, Result};
+use parking_lot::Mutex;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Directives used when neither `log_filter` nor `TALARIA_LOG` is set.
+pub const DEFAULT_FILTER: &str = "info";
+/// Size at which the current log file is rotated.
+pub const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+/// Rotated files kept next to the current one (`<app>.log.1` is the newest).
+pub const KEEP_FILES: usize = 5;
+
+/// Parsed `RUST_LOG`-style directives: a bare level sets the default, and
+/// `target=level` applies to that module path and everything under it. The
+/// longest matching target wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    default: LevelFilter,
+    /// Sorted longest target first.
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    pub fn enabled(&self, target: &str, level: &Level) -> bool {
+        level <= &self.level_for(target)
+    }
+
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .find(|(prefix, _)| {
+                target == prefix
+                    || target
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, LevelFilter::max)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        DEFAULT_FILTER.parse().expect("default filter parses")
+    }
+}
+
+impl FromStr for Filter {
+    type Err = Error;
+
+    fn from_str(directives: &str) -> Result<Self> {
+        let parse_level = |value: &str| {
+            value.trim().parse::<LevelFilter>().map_err(|_| {
+                Error::InvalidConfig(format!("log_filter: unknown level `{}`", value.trim()))
+            })
+        };
+        let mut filter = Filter {
+            default: LevelFilter::ERROR,
+            targets: Vec::new(),
+        };
+        for directive in directives.split(',').map(str::trim) {
+            if directive.is_empty() {
+                continue;
+            }
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    let target = target.trim();
+                    if target.is_empty() {
+                        return Err(Error::InvalidConfig(format!(
+                            "log_filter: missing target in `{directive}`"
+                        )));
+                    }
+                    filter
+                        .targets
+                        .push((target.to_string(), parse_level(level)?));
+                }
+                None => filter.default = parse_level(directive)?,
+            }
+        }
+        filter
+            .targets
+            .sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        Ok(filter)
+    }
+}
+
+/// An append-only log file that moves itself aside once it reaches
+/// `max_bytes`, keeping the `keep` most recent rotations.
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    file: File,
+    len: u64,
+}
+
+impl RotatingFile {
+    pub fn open(path: &Path, max_bytes: u64, keep: usize) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            max_bytes,
+            keep,
+            file,
+            len,
+        })
+    }
+
+    /// Writes one whole line, rotating first if it would overflow the file.
+    pub fn write_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        if self.len > 0 && self.len + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(line)?;
+        self.len += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if self.keep == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.rotated(self.keep));
+            for n in (1..self.keep).rev() {
+                let _ = fs::rename(self.rotated(n), self.rotated(n + 1));
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+            self.file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+        }
+        self.len = 0;
+        Ok(())
+    }
+}
+
+struct SpanData {
+    name: &'static str,
+    fields: Map<String, Value>,
+    refs: usize,
+}
+
+thread_local! {
+    static ENTERED: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A `tracing` subscriber writing JSON lines: `ts`, `level`, `target`,
+/// `app`, `pid`, `message`, the event's `fields`, and the `spans` it ran
+/// inside (outermost first) with their fields.
+pub struct JsonLogger {
+    app: String,
+    filter: Filter,
+    out: Mutex<RotatingFile>,
+    spans: Mutex<HashMap<u64, SpanData>>,
+    next_id: AtomicU64,
+}
+
+impl JsonLogger {
+    pub fn new(app: &str, filter: Filter, out: RotatingFile) -> Self {
+        Self {
+            app: app.to_string(),
+            filter,
+            out: Mutex::new(out),
+            spans: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+impl Subscriber for JsonLogger {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if self.enabled(metadata) {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(self.filter.max_level())
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.filter.enabled(metadata.target(), metadata.level())
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut fields = JsonFields::default();
+        span.record(&mut fields);
+        self.spans.lock().insert(
+            id,
+            SpanData {
+                name: span.metadata().name(),
+                fields: fields.0,
+                refs: 1,
+            },
+        );
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if let Some(data) = self.spans.lock().get_mut(&span.into_u64()) {
+            let mut fields = JsonFields(std::mem::take(&mut data.fields));
+            values.record(&mut fields);
+            data.fields = fields.0;
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let mut fields = JsonFields::default();
+        event.record(&mut fields);
+        let mut fields = fields.0;
+        let message = fields.remove("message").unwrap_or(Value::Null);
+
+        let spans: Vec<Value> = ENTERED.with(|entered| {
+            let spans = self.spans.lock();
+            entered
+                .borrow()
+                .iter()
+                .filter_map(|id| spans.get(id))
+                .map(|span| {
+                    let mut value = span.fields.clone();
+                    value.insert("name".into(), span.name.into());
+                    Value::Object(value)
+                })
+                .collect()
+        });
+
+        let mut line = Map::new();
+        line.insert(
+            "ts".into(),
+            chrono::Utc::now()
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+                .into(),
+        );
+        line.insert("level".into(), metadata.level().as_str().into());
+        line.insert("target".into(), metadata.target().into());
+        line.insert("app".into(), self.app.clone().into());
+        line.insert("pid".into(), std::process::id().into());
+        line.insert("message".into(), message);
+        if !fields.is_empty() {
+            line.insert("fields".into(), Value::Object(fields));
+        }
+        if !spans.is_empty() {
+            line.insert("spans".into(), Value::Array(spans));
+        }
+        let Ok(mut bytes) = serde_json::to_vec(&Value::Object(line)) else {
+            return;
+        };
+        bytes.push(b'\n');
+        // Logging must never take the app down; a full disk just loses lines.
+        let _ = self.out.lock().write_line(&bytes);
+    }
+
+    fn enter(&self, span: &Id) {
+        ENTERED.with(|entered| entered.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        ENTERED.with(|entered| {
+            let mut entered = entered.borrow_mut();
+            if let Some(pos) = entered.iter().rposition(|id| *id == span.into_u64()) {
+                entered.remove(pos);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(data) = self.spans.lock().get_mut(&span.into_u64()) {
+            data.refs += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let mut spans = self.spans.lock();
+        let id = span.into_u64();
+        match spans.get_mut(&id) {
+            Some(data) if data.refs > 1 => {
+                data.refs -= 1;
+                false
+            }
+            Some(_) => {
+                spans.remove(&id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Default)]
+struct JsonFields(Map<String, Value>);
+
+impl Visit for JsonFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0.insert(field.name().into(), value.to_string().into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().into(), format!("{value:?}").into());
+    }
+}
+
+/// The logs dir frontends share, next to the captures they describe.
+pub fn logs_dir(captures_dir: &Path) -> PathBuf {
+    captures_dir.join("logs")
+}
+
+/// Installs a [`JsonLogger`] writing to `<dir>/<app>.log` as the process's
+/// global subscriber and returns the log path. `filter` is `log_filter`
+/// from the config; `None` means [`DEFAULT_FILTER`].
+pub fn init(app: &str, dir: &Path, filter: Option<&str>) -> Result<PathBuf> {
+    let filter = filter.unwrap_or(DEFAULT_FILTER).parse::<Filter>()?;
+    let path = dir.join(format!("{app}.log"));
+    let out = RotatingFile::open(&path, MAX_FILE_BYTES, KEEP_FILES)?;
+    tracing::subscriber::set_global_default(JsonLogger::new(app, filter, out))
+        .map_err(|err| Error::InvalidConfig(format!("logging already initialised: {err}")))?;
+    Ok(path)
+}
@@ -0,0 +1,345 @@
+//! Measuring items from photos: a pixels-per-unit calibration taken from a
+//! reference grid or a square marker in the frame, and the dimensions
+//! recorded for a product.
+//!
+//! The calibration lives in the `[measurement]` config table. Measured
+//! dimensions are kept in the product's local manifest and override the
+//! `width`/`height`/`depth` of the structured product, and the package size of
+//! its listings once all three are known.
+
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Smallest grid period or marker side, in pixels, worth trusting.
+const MIN_FEATURE_PX: usize = 6;
+/// Autocorrelation a grid period must reach to count as periodic.
+const MIN_GRID_CORRELATION: f64 = 0.3;
+/// Share of a marker's outline that must be dark.
+const MIN_MARKER_BORDER: f64 = 0.85;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LengthUnit {
+    #[default]
+    #[serde(rename = "in")]
+    Inch,
+    #[serde(rename = "cm")]
+    Centimeter,
+}
+
+impl LengthUnit {
+    pub fn label(self) -> &'static str {
+        match self {
+            LengthUnit::Inch => "in",
+            LengthUnit::Centimeter => "cm",
+        }
+    }
+
+    /// Unit code used for product and package dimensions.
+    pub fn unit_code(self) -> &'static str {
+        match self {
+            LengthUnit::Inch => "INCH",
+            LengthUnit::Centimeter => "CENTIMETER",
+        }
+    }
+}
+
+/// The `[measurement]` config table.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MeasurementConfig {
+    #[serde(default)]
+    pub unit: LengthUnit,
+    /// Calibration at the capture resolution; set by calibrating from a frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pixels_per_unit: Option<f64>,
+    /// Side of one square of the reference grid, in `unit`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid_square: Option<f64>,
+    /// Outer side of the square reference marker (e.g. ArUco), in `unit`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker_size: Option<f64>,
+}
+
+impl MeasurementConfig {
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in [
+            ("pixels_per_unit", self.pixels_per_unit),
+            ("grid_square", self.grid_square),
+            ("marker_size", self.marker_size),
+        ] {
+            if let Some(value) = value
+                && !(value.is_finite() && value > 0.0)
+            {
+                return Err(Error::InvalidConfig(format!(
+                    "measurement.{name} must be greater than 0"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Pixels per unit read from a grayscale frame showing the reference
+    /// marker (preferred) or grid. `Ok(None)` means the reference was not
+    /// found; an error means neither reference size is configured.
+    pub fn calibrate(&self, width: usize, height: usize, luma: &[u8]) -> Result<Option<f64>> {
+        if let Some(size) = self.marker_size {
+            return Ok(marker_side(width, height, luma).map(|side| side / size));
+        }
+        if let Some(size) = self.grid_square {
+            return Ok(grid_period(width, height, luma).map(|period| period / size));
+        }
+        Err(Error::InvalidConfig(
+            "set measurement.grid_square or measurement.marker_size to calibrate".into(),
+        ))
+    }
+}
+
+/// Spacing, in pixels, of the lines of a regular grid in a grayscale frame.
+/// Columns and rows are checked separately; both are averaged when found.
+pub fn grid_period(width: usize, height: usize, luma: &[u8]) -> Option<f64> {
+    if width == 0 || height == 0 || luma.len() < width * height {
+        return None;
+    }
+    let mut columns = vec![0.0; width];
+    let mut rows = vec![0.0; height];
+    for (y, row) in luma.chunks_exact(width).take(height).enumerate() {
+        for (x, &value) in row.iter().enumerate() {
+            columns[x] += f64::from(value);
+            rows[y] += f64::from(value);
+        }
+    }
+    let periods: Vec<f64> = [columns, rows]
+        .iter()
+        .filter_map(|profile| profile_period(profile))
+        .collect();
+    if periods.is_empty() {
+        None
+    } else {
+        Some(periods.iter().sum::<f64>() / periods.len() as f64)
+    }
+}
+
+/// Period of a 1-D intensity profile from its autocorrelation. The first peak
+/// gives the period roughly; each later multiple of it refines the estimate.
+fn profile_period(profile: &[f64]) -> Option<f64> {
+    let n = profile.len();
+    let mean = profile.iter().sum::<f64>() / n as f64;
+    let centered: Vec<f64> = profile.iter().map(|v| v - mean).collect();
+    let energy = centered.iter().map(|v| v * v).sum::<f64>() / n as f64;
+    if energy <= f64::EPSILON {
+        return None;
+    }
+    let max_lag = n / 2;
+    let correlation: Vec<f64> = (0..=max_lag)
+        .map(|lag| {
+            let sum: f64 = centered
+                .iter()
+                .zip(&centered[lag..])
+                .map(|(a, b)| a * b)
+                .sum();
+            sum / (n - lag) as f64 / energy
+        })
+        .collect();
+    let is_peak = |lag: usize| {
+        lag < max_lag
+            && correlation[lag] >= correlation[lag - 1]
+            && correlation[lag] > correlation[lag + 1]
+    };
+    let first = (MIN_FEATURE_PX..max_lag)
+        .find(|&lag| is_peak(lag) && correlation[lag] >= MIN_GRID_CORRELATION)?;
+
+    let mut period = refine_peak(&correlation, first);
+    for multiple in 2.. {
+        let slack = (period / 4.0).max(1.0);
+        let (low, high) = (
+            (multiple as f64 * period - slack).round() as usize,
+            (multiple as f64 * period + slack).round() as usize,
+        );
+        if high >= max_lag {
+            break;
+        }
+        let peak = (low..=high).max_by(|&a, &b| correlation[a].total_cmp(&correlation[b]))?;
+        period = refine_peak(&correlation, peak) / multiple as f64;
+    }
+    Some(period)
+}
+
+/// Sub-sample peak position from a parabola through the neighbours.
+fn refine_peak(values: &[f64], idx: usize) -> f64 {
+    if idx == 0 || idx + 1 >= values.len() {
+        return idx as f64;
+    }
+    let (a, b, c) = (values[idx - 1], values[idx], values[idx + 1]);
+    let denom = a - 2.0 * b + c;
+    if denom.abs() <= f64::EPSILON {
+        idx as f64
+    } else {
+        idx as f64 + 0.5 * (a - c) / denom
+    }
+}
+
+/// Side, in pixels, of the largest dark square with a solid outline, such as
+/// the border of an ArUco marker. Thin dark lines (a grid under the marker)
+/// are eroded away first so they do not join up with it.
+pub fn marker_side(width: usize, height: usize, luma: &[u8]) -> Option<f64> {
+    if width == 0 || height == 0 || luma.len() < width * height {
+        return None;
+    }
+    let luma = &luma[..width * height];
+    let threshold = dark_threshold(luma)?;
+    let radius = (width.min(height) / 300).max(2);
+    let dark = erode(
+        &luma.iter().map(|&v| v < threshold).collect::<Vec<_>>(),
+        width,
+        radius,
+    );
+    let mut seen = vec![false; dark.len()];
+    let mut best: Option<(usize, f64)> = None;
+    let mut stack = Vec::new();
+    for start in 0..dark.len() {
+        if !dark[start] || seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        stack.push(start);
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (width, 0, height, 0);
+        let mut count = 0usize;
+        while let Some(idx) = stack.pop() {
+            let (x, y) = (idx % width, idx / width);
+            count += 1;
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+            let neighbours = [
+                (x > 0).then(|| idx - 1),
+                (x + 1 < width).then_some(idx + 1),
+                (y > 0).then(|| idx - width),
+                (y + 1 < height).then_some(idx + width),
+            ];
+            for next in neighbours.into_iter().flatten() {
+                if dark[next] && !seen[next] {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        let (w, h) = (max_x - min_x + 1, max_y - min_y + 1);
+        let aspect = w as f64 / h as f64;
+        if w.min(h) < MIN_FEATURE_PX
+            || !(0.85..=1.18).contains(&aspect)
+            || (count as f64) < 0.4 * (w * h) as f64
+            || best.is_some_and(|(area, _)| area >= w * h)
+        {
+            continue;
+        }
+        let outline: Vec<usize> = (min_x..=max_x)
+            .flat_map(|x| [min_y * width + x, max_y * width + x])
+            .chain((min_y..=max_y).flat_map(|y| [y * width + min_x, y * width + max_x]))
+            .collect();
+        let solid = outline.iter().filter(|&&idx| dark[idx]).count();
+        if solid as f64 >= MIN_MARKER_BORDER * outline.len() as f64 {
+            best = Some((w * h, (w + h) as f64 / 2.0));
+        }
+    }
+    best.map(|(_, side)| side + 2.0 * radius as f64)
+}
+
+/// Shrinks the set pixels of a `width`-wide mask by `radius` on every side.
+fn erode(mask: &[bool], width: usize, radius: usize) -> Vec<bool> {
+    let height = mask.len() / width;
+    let keep = |len: usize, pos: usize, set: &dyn Fn(usize) -> bool| {
+        pos >= radius && pos + radius < len && (pos - radius..=pos + radius).all(set)
+    };
+    let across: Vec<bool> = (0..mask.len())
+        .map(|idx| {
+            let (x, row) = (idx % width, idx - idx % width);
+            keep(width, x, &|x| mask[row + x])
+        })
+        .collect();
+    (0..mask.len())
+        .map(|idx| {
+            let (x, y) = (idx % width, idx / width);
+            keep(height, y, &|y| across[y * width + x])
+        })
+        .collect()
+}
+
+/// Midpoint between the 5th and 95th luma percentiles, or `None` for a flat
+/// frame.
+fn dark_threshold(luma: &[u8]) -> Option<u8> {
+    let mut histogram = [0usize; 256];
+    for &value in luma {
+        histogram[value as usize] += 1;
+    }
+    let percentile = |share: f64| {
+        let target = (luma.len() as f64 * share) as usize;
+        let mut seen = 0;
+        histogram
+            .iter()
+            .position(|&n| {
+                seen += n;
+                seen > target
+            })
+            .unwrap_or(255)
+    };
+    let (low, high) = (percentile(0.05), percentile(0.95));
+    (high - low >= 32).then(|| ((low + high) / 2) as u8)
+}
+
+/// Dimensions measured for a product. Width and height come from a front
+/// shot, depth from a side shot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeasuredDimensions {
+    pub unit: LengthUnit,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<f64>,
+    pub measured_at: DateTime<Utc>,
+}
+
+impl MeasuredDimensions {
+    /// e.g. `W 12.5 × H 8 × D 3.25 in`; unmeasured sides are skipped.
+    pub fn summary(&self) -> String {
+        let sides: Vec<String> = [("W", self.width), ("H", self.height), ("D", self.depth)]
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|v| format!("{name} {}", round2(v))))
+            .collect();
+        format!("{} {}", sides.join(" × "), self.unit.label())
+    }
+
+    /// Sets `width`/`height`/`depth` on a structured product object.
+    pub fn apply_to_structure(&self, structure: &mut Value) {
+        let Some(object) = structure.as_object_mut() else {
+            return;
+        };
+        for (field, value) in [
+            ("width", self.width),
+            ("height", self.height),
+            ("depth", self.depth),
+        ] {
+            if let Some(value) = value {
+                object.insert(
+                    field.to_string(),
+                    json!({ "value": round2(value), "unit_code": self.unit.unit_code() }),
+                );
+            }
+        }
+    }
+
+    /// Package length, width and height (longest side first), once all three
+    /// sides are measured.
+    pub fn package_sides(&self) -> Option<[f64; 3]> {
+        let mut sides = [self.width?, self.height?, self.depth?];
+        sides.sort_by(|a, b| b.total_cmp(a));
+        Some(sides.map(round2))
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -0,0 +1,283 @@
+//! Process-wide counters, gauges and timings: Hermes requests by endpoint and
+//! status, retries, upload bytes, capture FPS and job durations.
+//!
+//! Everything records into [`global()`]. Frontends fold it into the totals
+//! file under the data dir (read back by `talaria metrics dump`), show a
+//! [`Snapshot`] directly, or render it as Prometheus text.
+
+use crate::config::data_dir;
+#[cfg(feature = "prometheus")]
+use crate::error::Error;
+use crate::error::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const TOTALS_FILE: &str = "metrics.json";
+
+pub const HERMES_REQUESTS: &str = "talaria_hermes_requests_total";
+pub const HERMES_RETRIES: &str = "talaria_hermes_retries_total";
+pub const HERMES_REQUEST_DURATION: &str = "talaria_hermes_request_duration_seconds";
+pub const UPLOAD_BYTES: &str = "talaria_upload_bytes_total";
+pub const CAPTURE_FPS: &str = "talaria_capture_fps";
+pub const JOB_DURATION: &str = "talaria_job_duration_seconds";
+
+/// Count, sum and max of observed durations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub count: u64,
+    pub sum_secs: f64,
+    pub max_secs: f64,
+}
+
+impl Timing {
+    pub fn mean_secs(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum_secs / self.count as f64
+        }
+    }
+
+    fn merge(&mut self, other: &Timing) {
+        self.count += other.count;
+        self.sum_secs += other.sum_secs;
+        self.max_secs = self.max_secs.max(other.max_secs);
+    }
+}
+
+/// Recorded values keyed by series, `name{label="value",...}`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    #[serde(default)]
+    pub counters: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub gauges: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub timings: BTreeMap<String, Timing>,
+}
+
+impl Snapshot {
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty() && self.gauges.is_empty() && self.timings.is_empty()
+    }
+
+    /// Adds `other` on top: counters and timings accumulate, gauges take the
+    /// newer value.
+    pub fn merge(&mut self, other: &Snapshot) {
+        for (series, value) in &other.counters {
+            *self.counters.entry(series.clone()).or_default() += value;
+        }
+        for (series, value) in &other.gauges {
+            self.gauges.insert(series.clone(), *value);
+        }
+        for (series, timing) in &other.timings {
+            self.timings
+                .entry(series.clone())
+                .or_default()
+                .merge(timing);
+        }
+    }
+
+    /// Prometheus text exposition format; timings become summaries without
+    /// quantiles plus a `_max` gauge.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        write_family(&mut out, &self.counters, "counter", |out, series, value| {
+            let _ = writeln!(out, "{series} {value}");
+        });
+        write_family(&mut out, &self.gauges, "gauge", |out, series, value| {
+            let _ = writeln!(out, "{series} {value}");
+        });
+        write_family(&mut out, &self.timings, "summary", |out, series, t| {
+            let (name, labels) = split_series(series);
+            let _ = writeln!(out, "{name}_count{labels} {}", t.count);
+            let _ = writeln!(out, "{name}_sum{labels} {}", t.sum_secs);
+            let _ = writeln!(out, "{name}_max{labels} {}", t.max_secs);
+        });
+        out
+    }
+}
+
+fn write_family<T>(
+    out: &mut String,
+    values: &BTreeMap<String, T>,
+    kind: &str,
+    mut line: impl FnMut(&mut String, &str, &T),
+) {
+    let mut current = "";
+    for (series, value) in values {
+        let (name, _) = split_series(series);
+        if name != current {
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            current = name;
+        }
+        line(out, series, value);
+    }
+}
+
+fn split_series(series: &str) -> (&str, &str) {
+    match series.find('{') {
+        Some(idx) => series.split_at(idx),
+        None => (series, ""),
+    }
+}
+
+/// `name{a="1",b="2"}`, or just `name` without labels.
+pub fn series(name: &str, labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let labels = labels
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{key}=\"{}\"",
+                value.replace('\\', "\\\\").replace('"', "\\\"")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{name}{{{labels}}}")
+}
+
+/// A request path with its ids folded to `:id`, so `v1/products/<uuid>`
+/// stays one series. Segments with a digit count as ids, except `v1`-style
+/// version prefixes.
+pub fn endpoint_label(path: &str) -> String {
+    path.trim_matches('/')
+        .split('/')
+        .map(|segment| {
+            let is_version = segment.len() > 1
+                && segment.starts_with('v')
+                && segment[1..].chars().all(|c| c.is_ascii_digit());
+            if !is_version && segment.chars().any(|c| c.is_ascii_digit()) {
+                ":id"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[derive(Debug, Default)]
+pub struct Registry {
+    inner: Mutex<Snapshot>,
+}
+
+impl Registry {
+    pub fn incr(&self, name: &str, labels: &[(&str, &str)], by: u64) {
+        *self
+            .inner
+            .lock()
+            .counters
+            .entry(series(name, labels))
+            .or_default() += by;
+    }
+
+    pub fn set_gauge(&self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.inner.lock().gauges.insert(series(name, labels), value);
+    }
+
+    pub fn observe(&self, name: &str, labels: &[(&str, &str)], elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        self.inner
+            .lock()
+            .timings
+            .entry(series(name, labels))
+            .or_default()
+            .merge(&Timing {
+                count: 1,
+                sum_secs: secs,
+                max_secs: secs,
+            });
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        self.inner.lock().clone()
+    }
+
+    /// Hands over everything recorded so far and starts again from zero.
+    pub fn take(&self) -> Snapshot {
+        std::mem::take(&mut *self.inner.lock())
+    }
+}
+
+/// The registry every talaria component records into.
+pub fn global() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Registry::default)
+}
+
+/// Where [`flush_totals`] keeps the running totals (`~/.local/share/talaria`).
+pub fn default_totals_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(TOTALS_FILE))
+}
+
+pub fn load_totals(path: &Path) -> Result<Snapshot> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Snapshot::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Folds what `registry` recorded since the last flush into the totals at
+/// `path` and returns the new totals.
+pub fn flush_totals(registry: &Registry, path: &Path) -> Result<Snapshot> {
+    let recorded = registry.take();
+    let mut totals = load_totals(path)?;
+    if recorded.is_empty() {
+        return Ok(totals);
+    }
+    totals.merge(&recorded);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(&totals)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(totals)
+}
+
+/// Serves `GET /metrics` (Prometheus text) for the process's [`global()`]
+/// registry from a background thread.
+#[cfg(feature = "prometheus")]
+pub fn serve_prometheus(addr: &str) -> Result<std::thread::JoinHandle<()>> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind(addr)
+        .map_err(|err| Error::InvalidConfig(format!("metrics_listen {addr}: {err}")))?;
+    Ok(std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let mut request_line = String::new();
+            if BufReader::new(&stream)
+                .read_line(&mut request_line)
+                .is_err()
+            {
+                continue;
+            }
+            let response = if request_line.starts_with("GET /metrics ") {
+                let body = global().snapshot().to_prometheus();
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: text/plain; version=0.0.4\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                    .to_string()
+            };
+            let _ = stream.write_all(response.as_bytes());
+        }
+    }))
+}
@@ -3,15 +3,78 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::skip_serializing_none;
 use std::collections::{BTreeMap, HashMap};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum LlmModel {
-    #[serde(rename = "gpt-5.2")]
-    Gpt5_2,
-    #[serde(rename = "gpt-5-mini")]
-    Gpt5Mini,
-    #[serde(rename = "gpt-5-nano")]
-    Gpt5Nano,
+use std::fmt;
+
+/// Declares a string-valued enum that tolerates values added on the server:
+/// anything unrecognised deserializes to `Unknown(String)` and serializes back
+/// unchanged, so older clients keep working against newer payloads.
+macro_rules! open_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $( $(#[$vmeta:meta])* $variant:ident => $value:literal, )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $( $(#[$vmeta])* $variant, )*
+            /// A value not known to this client version.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// All values known to this client version.
+            pub const KNOWN: &'static [$name] = &[$($name::$variant),*];
+
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $( $name::$variant => $value, )*
+                    $name::Unknown(value) => value,
+                }
+            }
+
+            pub fn is_known(&self) -> bool {
+                !matches!(self, $name::Unknown(_))
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $( $value => $name::$variant, )*
+                    other => $name::Unknown(other.to_string()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
+
+open_enum! {
+    pub enum LlmModel {
+        Gpt5_2 => "gpt-5.2",
+        Gpt5Mini => "gpt-5-mini",
+        Gpt5Nano => "gpt-5-nano",
+    }
 }
 
 #[skip_serializing_none]
@@ -220,6 +283,10 @@ pub enum JobState {
         error: String,
         stage: Option<String>,
     },
+    /// A state added on the server after this client was built; `state`
+    /// keeps the server's value so it serializes back unchanged.
+    #[serde(untagged)]
+    Unknown { state: String },
 }
 
 /// components.schemas.JobInfo
@@ -256,6 +323,40 @@ pub struct PricingQuote {
     pub unit_rate_cents: Option<i64>,
 }
 
+/// components.schemas.ComparablePricesRequest
+#[skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComparablePricesRequest {
+    pub marketplace: Option<MarketplaceId>,
+    pub query: Option<String>,
+    pub brand: Option<String>,
+    pub mpn: Option<String>,
+    pub condition: Option<String>,
+    pub category_id: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// components.schemas.ComparablePricesResponse
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparablePricesResponse {
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub comparables: Vec<ComparablePrice>,
+}
+
+/// components.schemas.ComparablePrice
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparablePrice {
+    pub title: Option<String>,
+    pub price: f64,
+    pub currency: Option<String>,
+    pub condition: Option<String>,
+    pub sold: Option<bool>,
+    pub url: Option<String>,
+}
+
 /// components.schemas.UsageSummary
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -313,14 +414,14 @@ pub struct DeviceAuthStartResponse {
     pub interval: u64,
 }
 
-/// components.schemas.DeviceAuthStatus
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum DeviceAuthStatus {
-    Pending,
-    Authorized,
-    Consumed,
-    Expired,
+open_enum! {
+    /// components.schemas.DeviceAuthStatus
+    pub enum DeviceAuthStatus {
+        Pending => "pending",
+        Authorized => "authorized",
+        Consumed => "consumed",
+        Expired => "expired",
+    }
 }
 
 /// components.schemas.DeviceAuthPollResponse
@@ -355,6 +456,17 @@ pub struct UserApiKeyCreateResponse {
     pub secret: String,
 }
 
+/// components.schemas.UserApiKey
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserApiKey {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
 /// components.schemas.HealthResponse
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -379,11 +491,11 @@ pub struct CreateUploadRequest {
     pub sha256: Option<String>,
 }
 
-/// components.schemas.UploadMethod
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum UploadMethod {
-    #[serde(rename = "PUT")]
-    Put,
+open_enum! {
+    /// components.schemas.UploadMethod
+    pub enum UploadMethod {
+        Put => "PUT",
+    }
 }
 
 /// components.schemas.UploadSession
@@ -413,15 +525,13 @@ pub struct CompleteUploadResponse {
     pub media: Media,
 }
 
-/// components.schemas.MediaPurpose
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub enum MediaPurpose {
-    #[serde(rename = "product_image")]
-    ProductImage,
-    #[serde(rename = "hero")]
-    Hero,
-    #[serde(rename = "session_frame")]
-    SessionFrame,
+open_enum! {
+    /// components.schemas.MediaPurpose
+    pub enum MediaPurpose {
+        ProductImage => "product_image",
+        Hero => "hero",
+        SessionFrame => "session_frame",
+    }
 }
 
 /// components.schemas.Media
@@ -474,15 +584,24 @@ pub enum ImageField {
     Multiple(Vec<String>),
 }
 
-/// components.schemas.MarketplaceId
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub enum MarketplaceId {
-    #[serde(rename = "EBAY_US")]
-    EbayUs,
-    #[serde(rename = "EBAY_UK")]
-    EbayUk,
-    #[serde(rename = "EBAY_DE")]
-    EbayDe,
+open_enum! {
+    /// components.schemas.MarketplaceId
+    pub enum MarketplaceId {
+        EbayUs => "EBAY_US",
+        EbayUk => "EBAY_UK",
+        EbayDe => "EBAY_DE",
+        EbayAu => "EBAY_AU",
+        EbayCa => "EBAY_CA",
+        EbayFr => "EBAY_FR",
+        EbayIt => "EBAY_IT",
+        EbayEs => "EBAY_ES",
+        EbayAt => "EBAY_AT",
+        EbayBe => "EBAY_BE",
+        EbayCh => "EBAY_CH",
+        EbayIe => "EBAY_IE",
+        EbayNl => "EBAY_NL",
+        EbayPl => "EBAY_PL",
+    }
 }
 
 /// components.schemas.PublicPipelineOverrides
@@ -559,36 +678,35 @@ pub struct Offer {
     pub quantity: Option<u32>,
 }
 
-/// components.schemas.OfferAvailability
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum OfferAvailability {
-    InStock,
-    OutOfStock,
-    PreOrder,
-    Backorder,
-    Unknown,
-}
-
-/// components.schemas.ProductCondition
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum ProductCondition {
-    New,
-    Used,
-    Refurbished,
-    Damaged,
-}
-
-/// components.schemas.AgeGroup
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum AgeGroup {
-    Newborn,
-    Infant,
-    Toddler,
-    Kids,
-    Adults,
+open_enum! {
+    /// components.schemas.OfferAvailability
+    pub enum OfferAvailability {
+        InStock => "in_stock",
+        OutOfStock => "out_of_stock",
+        PreOrder => "pre_order",
+        Backorder => "backorder",
+    }
+}
+
+open_enum! {
+    /// components.schemas.ProductCondition
+    pub enum ProductCondition {
+        New => "new",
+        Used => "used",
+        Refurbished => "refurbished",
+        Damaged => "damaged",
+    }
+}
+
+open_enum! {
+    /// components.schemas.AgeGroup
+    pub enum AgeGroup {
+        Newborn => "newborn",
+        Infant => "infant",
+        Toddler => "toddler",
+        Kids => "kids",
+        Adults => "adults",
+    }
 }
 
 /// components.schemas.SizeField
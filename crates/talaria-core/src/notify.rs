@@ -0,0 +1,50 @@
+//! Outgoing webhook notifications for finished jobs.
+//!
+//! The default payload is the job summary JSON as-is; with `slack = true` it is
+//! wrapped as `{"text": ..., "attachments": [...]}` so it can be posted to a
+//! Slack (or Slack-compatible) incoming webhook directly.
+
+use crate::error::{Error, Result};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+/// Builds the webhook body for a job summary as produced by `jobs watch`.
+pub fn job_payload(summary: &Value, slack: bool) -> Value {
+    if !slack {
+        return summary.clone();
+    }
+    let id = summary["id"].as_str().unwrap_or("?");
+    let state = summary["state"].as_str().unwrap_or("unknown");
+    let elapsed = summary["elapsed_secs"].as_u64().unwrap_or_default();
+    let (icon, color) = match state {
+        "completed" => (":white_check_mark:", "good"),
+        "failed" => (":x:", "danger"),
+        _ => (":hourglass:", "warning"),
+    };
+    let mut fields = vec![
+        json!({ "title": "State", "value": state, "short": true }),
+        json!({ "title": "Elapsed", "value": format!("{elapsed}s"), "short": true }),
+    ];
+    if let Some(error) = summary["job"]["error"].as_str() {
+        fields.push(json!({ "title": "Error", "value": error, "short": false }));
+    }
+    json!({
+        "text": format!("{icon} Talaria job `{id}` {state}"),
+        "attachments": [{ "color": color, "fields": fields }],
+    })
+}
+
+/// POSTs `payload` as JSON to `url`. Non-2xx responses are errors.
+pub async fn post_webhook(url: &str, payload: &Value) -> Result<()> {
+    let http = crate::net::with_timeout(Client::builder(), Duration::from_secs(15))
+        .build()
+        .map_err(|err| Error::InvalidConfig(format!("failed to build client: {err}")))?;
+    let resp = http.post(url).json(payload).send().await?;
+    let status = resp.status();
+    if !status.is_success() {
+        let message = resp.text().await.unwrap_or_default();
+        return Err(Error::Webhook { status, message });
+    }
+    Ok(())
+}
@@ -0,0 +1,186 @@
+//! Package weight and dimensions for listing drafts: parsing values such as
+//! `12oz` or `10x8x4in`, and named presets from `[[package_templates]]` in the
+//! config file.
+
+use crate::error::{Error, Result};
+use crate::models::{ListingDimensionsInput, ListingPackageInput, ListingWeightInput};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const WEIGHT_UNITS: &str = "oz, lb, g, kg";
+const DIMENSION_UNITS: &str = "in, cm";
+
+/// A named weight/dimension preset, e.g. `small-box`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageTemplate {
+    pub name: String,
+    /// Weight with unit, e.g. `12oz`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<String>,
+    /// Length x width x height with unit, e.g. `10x8x4in`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dims: Option<String>,
+}
+
+impl PackageTemplate {
+    pub fn package(&self) -> Result<ListingPackageInput> {
+        Ok(ListingPackageInput {
+            weight: self.weight.as_deref().map(parse_weight).transpose()?,
+            dimensions: self.dims.as_deref().map(parse_dimensions).transpose()?,
+        })
+    }
+}
+
+/// Rejects templates without a name, with duplicate names, with nothing set,
+/// or with values that do not parse.
+pub fn validate_templates(templates: &[PackageTemplate]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for template in templates {
+        let name = template.name.trim();
+        if name.is_empty() {
+            return Err(Error::InvalidConfig(
+                "package_templates entries need a name".into(),
+            ));
+        }
+        if !seen.insert(name) {
+            return Err(Error::InvalidConfig(format!(
+                "duplicate package template '{name}'"
+            )));
+        }
+        if template.weight.is_none() && template.dims.is_none() {
+            return Err(Error::InvalidConfig(format!(
+                "package template '{name}' sets neither weight nor dims"
+            )));
+        }
+        template
+            .package()
+            .map_err(|err| Error::InvalidConfig(format!("package template '{name}': {err}")))?;
+    }
+    Ok(())
+}
+
+pub fn find_template<'a>(
+    templates: &'a [PackageTemplate],
+    name: &str,
+) -> Result<&'a PackageTemplate> {
+    templates
+        .iter()
+        .find(|t| t.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| {
+            let known: Vec<_> = templates.iter().map(|t| t.name.as_str()).collect();
+            Error::InvalidPackage(if known.is_empty() {
+                format!("unknown package template '{name}' (none configured)")
+            } else {
+                format!(
+                    "unknown package template '{name}' (known: {})",
+                    known.join(", ")
+                )
+            })
+        })
+}
+
+/// Package from an optional template plus explicit weight/dims, which win
+/// over the template's. `None` when nothing was given.
+pub fn resolve_package(
+    templates: &[PackageTemplate],
+    template: Option<&str>,
+    weight: Option<&str>,
+    dims: Option<&str>,
+) -> Result<Option<ListingPackageInput>> {
+    let mut package = match template {
+        Some(name) => find_template(templates, name)?.package()?,
+        None => ListingPackageInput {
+            weight: None,
+            dimensions: None,
+        },
+    };
+    if let Some(weight) = weight {
+        package.weight = Some(parse_weight(weight)?);
+    }
+    if let Some(dims) = dims {
+        package.dimensions = Some(parse_dimensions(dims)?);
+    }
+    Ok((package.weight.is_some() || package.dimensions.is_some()).then_some(package))
+}
+
+/// Parses `12oz`, `1.5 lb`, `500g` or `12 OUNCE`. Fractions are rounded up;
+/// fractional pounds and kilograms become ounces and grams, since package
+/// weights are whole numbers.
+pub fn parse_weight(text: &str) -> Result<ListingWeightInput> {
+    let (value, unit) = split_unit(text, "weight", WEIGHT_UNITS)?;
+    let value = single_number(value, text, "weight")?;
+    let (value, unit) = match unit.as_str() {
+        "oz" | "ounce" | "ounces" => (value, "OUNCE"),
+        "lb" | "lbs" | "pound" | "pounds" if value.fract() == 0.0 => (value, "POUND"),
+        "lb" | "lbs" | "pound" | "pounds" => (value * 16.0, "OUNCE"),
+        "g" | "gram" | "grams" => (value, "GRAM"),
+        "kg" | "kilogram" | "kilograms" if value.fract() == 0.0 => (value, "KILOGRAM"),
+        "kg" | "kilogram" | "kilograms" => (value * 1000.0, "GRAM"),
+        other => {
+            return Err(Error::InvalidPackage(format!(
+                "unknown weight unit '{other}' in '{text}' ({WEIGHT_UNITS})"
+            )));
+        }
+    };
+    Ok(ListingWeightInput {
+        value: value.ceil() as u32,
+        unit: unit.to_string(),
+    })
+}
+
+/// Parses `10x8x4in`, `25 x 20 x 10 cm` or `6.0 x 4.0 x 2.0 INCH` as length x
+/// width x height. Values are rounded up to one decimal.
+pub fn parse_dimensions(text: &str) -> Result<ListingDimensionsInput> {
+    let (values, unit) = split_unit(text, "dimensions", DIMENSION_UNITS)?;
+    let unit = match unit.as_str() {
+        "in" | "inch" | "inches" => "INCH",
+        "cm" | "centimeter" | "centimeters" => "CENTIMETER",
+        other => {
+            return Err(Error::InvalidPackage(format!(
+                "unknown dimension unit '{other}' in '{text}' ({DIMENSION_UNITS})"
+            )));
+        }
+    };
+    let sides = values
+        .split(['x', 'X', '×', '*'])
+        .map(|side| single_number(side, text, "dimensions"))
+        .collect::<Result<Vec<_>>>()?;
+    let [length, width, height] = sides[..] else {
+        return Err(Error::InvalidPackage(format!(
+            "dimensions '{text}' need three values, length x width x height"
+        )));
+    };
+    let ceil = |value: f64| (value * 10.0).ceil() / 10.0;
+    Ok(ListingDimensionsInput {
+        length: ceil(length),
+        width: ceil(width),
+        height: ceil(height),
+        unit: unit.to_string(),
+    })
+}
+
+/// Splits a trailing alphabetic unit off `text`, lowercased.
+fn split_unit<'a>(text: &'a str, what: &str, units: &str) -> Result<(&'a str, String)> {
+    let trimmed = text.trim();
+    let start = trimmed
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_ascii_alphabetic())
+        .last()
+        .map(|(idx, _)| idx);
+    match start {
+        Some(idx) if idx > 0 => Ok((&trimmed[..idx], trimmed[idx..].to_ascii_lowercase())),
+        _ => Err(Error::InvalidPackage(format!(
+            "{what} '{text}' needs a number and a unit ({units})"
+        ))),
+    }
+}
+
+fn single_number(value: &str, text: &str, what: &str) -> Result<f64> {
+    match value.trim().parse::<f64>() {
+        Ok(number) if number.is_finite() && number > 0.0 => Ok(number),
+        _ => Err(Error::InvalidPackage(format!(
+            "{what} '{text}' must be positive numbers"
+        ))),
+    }
+}
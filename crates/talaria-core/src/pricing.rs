@@ -0,0 +1,168 @@
+//! Price suggestions for listings: a range taken from comparable marketplace
+//! prices, adjusted by the margin rules in the `[pricing]` config table.
+
+use crate::error::{Error, Result};
+use crate::models::ComparablePrice;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The `[pricing]` config table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PricingRules {
+    /// Percent added to (or, when negative, taken off) the comparable prices.
+    #[serde(default)]
+    pub margin_pct: f64,
+    /// Percentiles of the comparable prices used for the ends of the range.
+    #[serde(default = "default_low_percentile")]
+    pub low_percentile: f64,
+    #[serde(default = "default_high_percentile")]
+    pub high_percentile: f64,
+    /// Multiplier per condition label (e.g. `USED_GOOD = 0.8`), for when the
+    /// comparables are in better shape than the item.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub condition_factors: BTreeMap<String, f64>,
+    /// Never suggest less than this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_price: Option<f64>,
+    /// End suggested prices in .99.
+    #[serde(default)]
+    pub charm: bool,
+}
+
+fn default_low_percentile() -> f64 {
+    25.0
+}
+
+fn default_high_percentile() -> f64 {
+    75.0
+}
+
+impl Default for PricingRules {
+    fn default() -> Self {
+        Self {
+            margin_pct: 0.0,
+            low_percentile: default_low_percentile(),
+            high_percentile: default_high_percentile(),
+            condition_factors: BTreeMap::new(),
+            min_price: None,
+            charm: false,
+        }
+    }
+}
+
+impl PricingRules {
+    pub fn validate(&self) -> Result<()> {
+        if !self.margin_pct.is_finite() || self.margin_pct <= -100.0 {
+            return Err(Error::InvalidConfig(
+                "pricing.margin_pct must be greater than -100".into(),
+            ));
+        }
+        let percentile = |value: f64| (0.0..=100.0).contains(&value);
+        if !percentile(self.low_percentile)
+            || !percentile(self.high_percentile)
+            || self.low_percentile > self.high_percentile
+        {
+            return Err(Error::InvalidConfig(
+                "pricing.low_percentile and high_percentile must be 0-100, low <= high".into(),
+            ));
+        }
+        if let Some((name, _)) = self
+            .condition_factors
+            .iter()
+            .find(|(_, factor)| !(factor.is_finite() && **factor > 0.0))
+        {
+            return Err(Error::InvalidConfig(format!(
+                "pricing.condition_factors.{name} must be greater than 0"
+            )));
+        }
+        if let Some(min) = self.min_price
+            && !(min.is_finite() && min >= 0.0)
+        {
+            return Err(Error::InvalidConfig(
+                "pricing.min_price must not be negative".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Factor for `condition`, matched case-insensitively; 1 when unlisted.
+    pub fn condition_factor(&self, condition: Option<&str>) -> f64 {
+        condition
+            .and_then(|condition| {
+                self.condition_factors
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(condition.trim()))
+            })
+            .map(|(_, factor)| *factor)
+            .unwrap_or(1.0)
+    }
+
+    fn finish(&self, price: f64) -> f64 {
+        let price = price.max(self.min_price.unwrap_or(0.0));
+        if self.charm && price >= 1.0 {
+            price.round() - 0.01
+        } else {
+            (price * 100.0).round() / 100.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceSuggestion {
+    pub currency: Option<String>,
+    pub low: f64,
+    pub suggested: f64,
+    pub high: f64,
+    /// How many comparable prices the range is based on.
+    pub comparables: usize,
+    /// Combined condition factor and margin applied to the comparables.
+    pub adjustment: f64,
+}
+
+/// Suggests a low/median/high range from `comparables`, keeping only those
+/// in `currency` when it is known and only sold items when there are any.
+/// `None` when nothing usable is left.
+pub fn suggest_price(
+    comparables: &[ComparablePrice],
+    currency: Option<&str>,
+    condition: Option<&str>,
+    rules: &PricingRules,
+) -> Option<PriceSuggestion> {
+    let usable: Vec<_> = comparables
+        .iter()
+        .filter(|comp| comp.price.is_finite() && comp.price > 0.0)
+        .filter(|comp| match (currency, comp.currency.as_deref()) {
+            (Some(wanted), Some(theirs)) => wanted.eq_ignore_ascii_case(theirs),
+            _ => true,
+        })
+        .collect();
+    let sold = usable.iter().any(|comp| comp.sold == Some(true));
+    let mut prices: Vec<f64> = usable
+        .iter()
+        .filter(|comp| !sold || comp.sold == Some(true))
+        .map(|comp| comp.price)
+        .collect();
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort_by(f64::total_cmp);
+
+    let adjustment = rules.condition_factor(condition) * (1.0 + rules.margin_pct / 100.0);
+    let at = |pct: f64| rules.finish(percentile(&prices, pct) * adjustment);
+    Some(PriceSuggestion {
+        currency: currency.map(str::to_string),
+        low: at(rules.low_percentile),
+        suggested: at(50.0),
+        high: at(rules.high_percentile),
+        comparables: prices.len(),
+        adjustment,
+    })
+}
+
+/// Linear interpolation between the closest ranks of sorted `values`.
+fn percentile(values: &[f64], pct: f64) -> f64 {
+    let rank = (pct / 100.0) * (values.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    values[lower] + (values[upper] - values[lower]) * (rank - lower as f64)
+}
@@ -0,0 +1,268 @@
+//! Field-by-field comparison of a product's local manifest with its Hermes
+//! [`ProductRecord`], the groundwork for two-way sync. Only the fields both
+//! sides carry are compared: `sku_alias`, `display_name`, `context_text`,
+//! `structure_json` and the listings (`listings` locally, `listings_json` on
+//! Hermes). Inside the JSON fields each differing leaf is reported under its
+//! path, e.g. `listings_json.EBAY_US.price`.
+//!
+//! Given the fields as they were at the last sync, [`conflicts`] finds what
+//! both sides changed and [`merge`] combines the two sides' changes.
+
+use crate::models::ProductRecord;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The synced fields of one side of a product.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductFields {
+    pub sku_alias: String,
+    pub display_name: Option<String>,
+    pub context_text: Option<String>,
+    pub structure_json: Option<Value>,
+    /// Listings by marketplace.
+    pub listings_json: Value,
+}
+
+impl ProductFields {
+    /// Reads the local manifest layout shared by the TUI and CLI, where the
+    /// listings live under `listings`.
+    pub fn from_manifest(manifest: &Value) -> Self {
+        let text = |key: &str| manifest[key].as_str().map(str::to_string);
+        Self {
+            sku_alias: text("sku_alias").unwrap_or_default(),
+            display_name: text("display_name"),
+            context_text: text("context_text"),
+            structure_json: Some(manifest["structure_json"].clone()).filter(|v| !v.is_null()),
+            listings_json: manifest["listings"].clone(),
+        }
+    }
+}
+
+impl From<&ProductRecord> for ProductFields {
+    fn from(record: &ProductRecord) -> Self {
+        Self {
+            sku_alias: record.sku_alias.clone(),
+            display_name: record.display_name.clone(),
+            context_text: record.context_text.clone(),
+            structure_json: record.structure_json.clone(),
+            listings_json: record.listings_json.clone(),
+        }
+    }
+}
+
+/// One value that differs. A side is `None` where the field or path is
+/// missing (or `null`) there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDiff {
+    /// Field name, followed by the path inside JSON fields (`.key`, `[idx]`).
+    pub path: String,
+    pub local: Option<Value>,
+    pub remote: Option<Value>,
+}
+
+impl FieldDiff {
+    /// The top-level field the difference is in.
+    pub fn field(&self) -> &str {
+        self.path
+            .split(['.', '['])
+            .next()
+            .unwrap_or(self.path.as_str())
+    }
+}
+
+/// Every difference between `local` and `remote`, in field order. Blank text
+/// counts as unset, and no listings as an empty map. Arrays of the same
+/// length are compared item by item; otherwise the whole array differs.
+/// Empty means the two sides are in sync.
+pub fn diff(local: &ProductFields, remote: &ProductFields) -> Vec<FieldDiff> {
+    let mut out = Vec::new();
+    for ((field, local), (_, remote)) in values(local).into_iter().zip(values(remote)) {
+        diff_values(field.to_string(), &local, &remote, &mut out);
+    }
+    out
+}
+
+/// The differences between `local` and `remote` at paths both changed
+/// since `base`, the fields as of the last sync. A path only one side
+/// changed is not a conflict; [`merge`] takes that side's value.
+pub fn conflicts(
+    base: &ProductFields,
+    local: &ProductFields,
+    remote: &ProductFields,
+) -> Vec<FieldDiff> {
+    let mut out = Vec::new();
+    for (((field, base), (_, local)), (_, remote)) in values(base)
+        .into_iter()
+        .zip(values(local))
+        .zip(values(remote))
+    {
+        conflict_values(field.to_string(), &base, &local, &remote, &mut out);
+    }
+    out
+}
+
+/// Three-way merge against `base`: each path takes the side that changed
+/// it, and local wins where both did (see [`conflicts`]).
+pub fn merge(base: &ProductFields, local: &ProductFields, remote: &ProductFields) -> ProductFields {
+    let merged: Vec<Value> = values(base)
+        .into_iter()
+        .zip(values(local))
+        .zip(values(remote))
+        .map(|(((_, base), (_, local)), (_, remote))| merge_values(&base, &local, &remote))
+        .collect();
+    let text = |value: &Value| value.as_str().map(str::to_string);
+    ProductFields {
+        sku_alias: text(&merged[0]).unwrap_or_default(),
+        display_name: text(&merged[1]),
+        context_text: text(&merged[2]),
+        structure_json: Some(merged[3].clone()).filter(|v| !v.is_null()),
+        listings_json: merged[4].clone(),
+    }
+}
+
+/// The fields as comparable values, in field order.
+fn values(fields: &ProductFields) -> [(&'static str, Value); 5] {
+    let text = |value: &Option<String>| {
+        value
+            .as_deref()
+            .filter(|text| !text.trim().is_empty())
+            .map(|text| Value::String(text.to_string()))
+            .unwrap_or(Value::Null)
+    };
+    let listings = match &fields.listings_json {
+        Value::Null => Value::Object(Map::new()),
+        other => other.clone(),
+    };
+    [
+        ("sku_alias", Value::String(fields.sku_alias.clone())),
+        ("display_name", text(&fields.display_name)),
+        ("context_text", text(&fields.context_text)),
+        (
+            "structure_json",
+            fields.structure_json.clone().unwrap_or(Value::Null),
+        ),
+        ("listings_json", listings),
+    ]
+}
+
+fn same(a: &Value, b: &Value) -> bool {
+    let mut out = Vec::new();
+    diff_values(String::new(), a, b, &mut out);
+    out.is_empty()
+}
+
+/// Keys of any of `objects`, sorted.
+fn keys<'a>(objects: &[&'a Map<String, Value>]) -> Vec<&'a String> {
+    let mut keys: Vec<&String> = objects.iter().flat_map(|object| object.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+fn conflict_values(
+    path: String,
+    base: &Value,
+    local: &Value,
+    remote: &Value,
+    out: &mut Vec<FieldDiff>,
+) {
+    if same(local, remote) || same(base, local) || same(base, remote) {
+        return;
+    }
+    match (base, local, remote) {
+        (Value::Object(base_map), Value::Object(local_map), Value::Object(remote_map)) => {
+            for key in keys(&[base_map, local_map, remote_map]) {
+                conflict_values(
+                    format!("{path}.{key}"),
+                    base_map.get(key).unwrap_or(&Value::Null),
+                    local_map.get(key).unwrap_or(&Value::Null),
+                    remote_map.get(key).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        (Value::Array(base_items), Value::Array(local_items), Value::Array(remote_items))
+            if base_items.len() == local_items.len() && local_items.len() == remote_items.len() =>
+        {
+            for (idx, ((base, local), remote)) in base_items
+                .iter()
+                .zip(local_items)
+                .zip(remote_items)
+                .enumerate()
+            {
+                conflict_values(format!("{path}[{idx}]"), base, local, remote, out);
+            }
+        }
+        _ => out.push(FieldDiff {
+            path,
+            local: Some(local.clone()).filter(|v| !v.is_null()),
+            remote: Some(remote.clone()).filter(|v| !v.is_null()),
+        }),
+    }
+}
+
+fn merge_values(base: &Value, local: &Value, remote: &Value) -> Value {
+    if same(base, remote) || same(local, remote) {
+        return local.clone();
+    }
+    if same(base, local) {
+        return remote.clone();
+    }
+    match (base, local, remote) {
+        (Value::Object(base_map), Value::Object(local_map), Value::Object(remote_map)) => {
+            let mut merged = Map::new();
+            for key in keys(&[base_map, local_map, remote_map]) {
+                let value = merge_values(
+                    base_map.get(key).unwrap_or(&Value::Null),
+                    local_map.get(key).unwrap_or(&Value::Null),
+                    remote_map.get(key).unwrap_or(&Value::Null),
+                );
+                if !value.is_null() {
+                    merged.insert(key.clone(), value);
+                }
+            }
+            Value::Object(merged)
+        }
+        (Value::Array(base_items), Value::Array(local_items), Value::Array(remote_items))
+            if base_items.len() == local_items.len() && local_items.len() == remote_items.len() =>
+        {
+            Value::Array(
+                base_items
+                    .iter()
+                    .zip(local_items)
+                    .zip(remote_items)
+                    .map(|((base, local), remote)| merge_values(base, local, remote))
+                    .collect(),
+            )
+        }
+        _ => local.clone(),
+    }
+}
+
+fn diff_values(path: String, local: &Value, remote: &Value, out: &mut Vec<FieldDiff>) {
+    match (local, remote) {
+        (Value::Object(local), Value::Object(remote)) => {
+            for key in keys(&[local, remote]) {
+                diff_values(
+                    format!("{path}.{key}"),
+                    local.get(key).unwrap_or(&Value::Null),
+                    remote.get(key).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        (Value::Array(local), Value::Array(remote)) if local.len() == remote.len() => {
+            for (idx, (local, remote)) in local.iter().zip(remote).enumerate() {
+                diff_values(format!("{path}[{idx}]"), local, remote, out);
+            }
+        }
+        // `10` and `10.0` are the same price.
+        (Value::Number(local), Value::Number(remote)) if local.as_f64() == remote.as_f64() => {}
+        _ if local == remote => {}
+        _ => out.push(FieldDiff {
+            path,
+            local: Some(local.clone()).filter(|v| !v.is_null()),
+            remote: Some(remote.clone()).filter(|v| !v.is_null()),
+        }),
+    }
+}
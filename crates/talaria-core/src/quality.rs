@@ -0,0 +1,296 @@
+//! Quality checks for photos before they are sent to Hermes: resolution,
+//! sharpness, exposure and near-duplicates, against the limits in the
+//! `[quality]` config table. Catching a blurry or undersized photo here is
+//! cheaper than paying for an enrich or listing run that eBay then rejects.
+//!
+//! The measures work on grayscale buffers; decoding files is left to
+//! [`crate::images::quality_report`].
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Longest side, in pixels, sharpness is measured at. Fixing the scale keeps
+/// scores comparable between a phone photo and a DSLR frame.
+const SHARPNESS_SIDE: usize = 512;
+/// Luma at or below (above) which a pixel counts as clipped black (white).
+const DARK_CLIP: u8 = 5;
+const BRIGHT_CLIP: u8 = 250;
+
+/// The `[quality]` config table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityRules {
+    /// Shortest allowed longest side in pixels; eBay rejects photos under 500.
+    #[serde(default = "default_min_long_side")]
+    pub min_long_side: u32,
+    /// Lowest Laplacian variance (at 512 px) a photo may score.
+    #[serde(default = "default_min_sharpness")]
+    pub min_sharpness: f64,
+    /// Largest share of pixels allowed to be clipped black.
+    #[serde(default = "default_max_dark_clipped")]
+    pub max_dark_clipped: f64,
+    /// Largest share of pixels allowed to be clipped white. Higher than the
+    /// dark limit because white backdrops clip on purpose.
+    #[serde(default = "default_max_bright_clipped")]
+    pub max_bright_clipped: f64,
+    /// Photos whose difference hashes are at most this many bits apart are
+    /// reported as duplicates.
+    #[serde(default = "default_duplicate_distance")]
+    pub duplicate_distance: u32,
+    /// Refuse to submit photos with findings instead of warning about them.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+fn default_min_long_side() -> u32 {
+    500
+}
+
+fn default_min_sharpness() -> f64 {
+    25.0
+}
+
+fn default_max_dark_clipped() -> f64 {
+    0.25
+}
+
+fn default_max_bright_clipped() -> f64 {
+    0.6
+}
+
+fn default_duplicate_distance() -> u32 {
+    4
+}
+
+impl Default for QualityRules {
+    fn default() -> Self {
+        Self {
+            min_long_side: default_min_long_side(),
+            min_sharpness: default_min_sharpness(),
+            max_dark_clipped: default_max_dark_clipped(),
+            max_bright_clipped: default_max_bright_clipped(),
+            duplicate_distance: default_duplicate_distance(),
+            strict: false,
+        }
+    }
+}
+
+impl QualityRules {
+    pub fn validate(&self) -> Result<()> {
+        if !(self.min_sharpness.is_finite() && self.min_sharpness >= 0.0) {
+            return Err(Error::InvalidConfig(
+                "quality.min_sharpness must be 0 or greater".into(),
+            ));
+        }
+        for (name, value) in [
+            ("max_dark_clipped", self.max_dark_clipped),
+            ("max_bright_clipped", self.max_bright_clipped),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(Error::InvalidConfig(format!(
+                    "quality.{name} must be between 0 and 1"
+                )));
+            }
+        }
+        if self.duplicate_distance > 64 {
+            return Err(Error::InvalidConfig(
+                "quality.duplicate_distance must be at most 64".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Measures of one grayscale image.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ImageMetrics {
+    pub width: u32,
+    pub height: u32,
+    /// Variance of the Laplacian at [`SHARPNESS_SIDE`]; higher is sharper.
+    pub sharpness: f64,
+    /// Shares of pixels clipped black and white.
+    pub dark_clipped: f64,
+    pub bright_clipped: f64,
+    /// 64-bit difference hash, for spotting near-duplicates.
+    #[serde(skip)]
+    pub dhash: u64,
+}
+
+impl ImageMetrics {
+    pub fn from_luma(width: usize, height: usize, luma: &[u8]) -> Self {
+        let pixels = &luma[..(width * height).min(luma.len())];
+        let share = |count: usize| count as f64 / pixels.len().max(1) as f64;
+        let scale = (SHARPNESS_SIDE as f64 / width.max(height).max(1) as f64).min(1.0);
+        let (small_w, small_h) = (
+            ((width as f64 * scale) as usize).max(1),
+            ((height as f64 * scale) as usize).max(1),
+        );
+        Self {
+            width: width as u32,
+            height: height as u32,
+            sharpness: laplacian_variance(
+                small_w,
+                small_h,
+                &downsample(width, height, pixels, small_w, small_h),
+            ),
+            dark_clipped: share(pixels.iter().filter(|&&v| v <= DARK_CLIP).count()),
+            bright_clipped: share(pixels.iter().filter(|&&v| v >= BRIGHT_CLIP).count()),
+            dhash: dhash(width, height, pixels),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    LowResolution,
+    Blurry,
+    Underexposed,
+    Overexposed,
+    Duplicate,
+    /// The file could not be decoded.
+    Unreadable,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QualityFinding {
+    pub path: PathBuf,
+    pub kind: FindingKind,
+    pub message: String,
+}
+
+/// One checked photo.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImageQuality {
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub metrics: ImageMetrics,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct QualityReport {
+    pub images: Vec<ImageQuality>,
+    pub findings: Vec<QualityFinding>,
+    /// Files in formats converted before upload (HEIC, RAW), which are not
+    /// checked.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unchecked: Vec<PathBuf>,
+}
+
+impl QualityReport {
+    pub fn passed(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Adds a measured photo and its findings under `rules`, including
+    /// duplicates of photos added before it.
+    pub fn add(&mut self, path: PathBuf, metrics: ImageMetrics, rules: &QualityRules) {
+        let mut finding = |kind, message: String| {
+            self.findings.push(QualityFinding {
+                path: path.clone(),
+                kind,
+                message,
+            });
+        };
+        let long_side = metrics.width.max(metrics.height);
+        if long_side < rules.min_long_side {
+            finding(
+                FindingKind::LowResolution,
+                format!(
+                    "{}x{} px; the longest side must be at least {} px",
+                    metrics.width, metrics.height, rules.min_long_side
+                ),
+            );
+        }
+        if metrics.sharpness < rules.min_sharpness {
+            finding(
+                FindingKind::Blurry,
+                format!(
+                    "sharpness {:.1} is below {:.1}",
+                    metrics.sharpness, rules.min_sharpness
+                ),
+            );
+        }
+        if metrics.dark_clipped > rules.max_dark_clipped {
+            finding(
+                FindingKind::Underexposed,
+                format!("{:.0}% of pixels are black", metrics.dark_clipped * 100.0),
+            );
+        }
+        if metrics.bright_clipped > rules.max_bright_clipped {
+            finding(
+                FindingKind::Overexposed,
+                format!("{:.0}% of pixels are white", metrics.bright_clipped * 100.0),
+            );
+        }
+        if let Some(original) = self.images.iter().find(|image| {
+            (image.metrics.dhash ^ metrics.dhash).count_ones() <= rules.duplicate_distance
+        }) {
+            finding(
+                FindingKind::Duplicate,
+                format!("looks the same as {}", original.path.display()),
+            );
+        }
+        self.images.push(ImageQuality { path, metrics });
+    }
+
+    pub fn add_unreadable(&mut self, path: PathBuf, reason: String) {
+        self.findings.push(QualityFinding {
+            path,
+            kind: FindingKind::Unreadable,
+            message: reason,
+        });
+    }
+}
+
+/// Box-filters a grayscale buffer down to `out_w` x `out_h`.
+fn downsample(width: usize, height: usize, luma: &[u8], out_w: usize, out_h: usize) -> Vec<f64> {
+    if width == 0 || height == 0 || luma.len() < width * height {
+        return vec![0.0; out_w * out_h];
+    }
+    let mut sums = vec![0.0; out_w * out_h];
+    let mut counts = vec![0u32; out_w * out_h];
+    for (y, row) in luma.chunks_exact(width).take(height).enumerate() {
+        let out_row = (y * out_h / height) * out_w;
+        for (x, &value) in row.iter().enumerate() {
+            let idx = out_row + x * out_w / width;
+            sums[idx] += value as f64;
+            counts[idx] += 1;
+        }
+    }
+    sums.iter()
+        .zip(counts)
+        .map(|(sum, count)| sum / count.max(1) as f64)
+        .collect()
+}
+
+/// Variance of the 4-neighbour Laplacian.
+fn laplacian_variance(width: usize, height: usize, luma: &[f64]) -> f64 {
+    if width < 3 || height < 3 {
+        return 0.0;
+    }
+    let at = |x: usize, y: usize| luma[y * width + x];
+    let (mut sum, mut sum_sq) = (0.0, 0.0);
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let lap = -4.0 * at(x, y) + at(x - 1, y) + at(x + 1, y) + at(x, y - 1) + at(x, y + 1);
+            sum += lap;
+            sum_sq += lap * lap;
+        }
+    }
+    let n = ((width - 2) * (height - 2)) as f64;
+    let mean = sum / n;
+    (sum_sq / n - mean * mean).max(0.0)
+}
+
+/// Difference hash: a 9x8 thumbnail, one bit per horizontal neighbour pair.
+fn dhash(width: usize, height: usize, luma: &[u8]) -> u64 {
+    let small = downsample(width, height, luma, 9, 8);
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash = (hash << 1) | u64::from(small[y * 9 + x] < small[y * 9 + x + 1]);
+        }
+    }
+    hash
+}
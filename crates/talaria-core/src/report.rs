@@ -0,0 +1,332 @@
+//! Human-readable reports of a [`ListingResponse`]: per-stage timings and
+//! summaries, warnings, the final title, price, category, condition and
+//! aspects, and image thumbnails, as Markdown or a standalone HTML page for
+//! teammates who don't read JSON.
+//!
+//! Stage outputs are free-form, so the listing fields are looked up the same
+//! way the TUI does: the `listing` / `build_listing` stage first, then the
+//! product of the `push_inventory` request.
+
+use crate::models::{ListingResponse, StageReport};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Thumbnail width in the rendered report, in CSS pixels.
+const THUMBNAIL_PX: u32 = 160;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+/// The final listing as far as the stage outputs tell.
+#[derive(Debug, Default, PartialEq)]
+struct ListingFields {
+    title: Option<String>,
+    price: Option<f64>,
+    currency: Option<String>,
+    category: Option<String>,
+    condition: Option<String>,
+    aspects: BTreeMap<String, Vec<String>>,
+    images: Vec<String>,
+}
+
+impl ListingFields {
+    fn from_response(resp: &ListingResponse) -> Self {
+        let build = stage_output(resp, "listing").or_else(|| stage_output(resp, "build_listing"));
+        let product = stage_output(resp, "push_inventory")
+            .and_then(|output| output.get("inventory_request"))
+            .and_then(|request| request.get("product"));
+        let field = |name: &str| {
+            build
+                .and_then(|build| build.get(name))
+                .or_else(|| product.and_then(|product| product.get(name)))
+        };
+        let text = |value: Option<&Value>| {
+            value
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+        };
+
+        Self {
+            title: text(field("title")),
+            price: build.and_then(|build| build["price"].as_f64()),
+            currency: text(build.and_then(|build| build.get("currency"))),
+            category: text(
+                stage_output(resp, "category")
+                    .and_then(|output| output.get("selected"))
+                    .and_then(|selected| selected.get("label")),
+            )
+            .or_else(|| text(build.and_then(|build| build.get("category_label")))),
+            condition: text(build.and_then(|build| build.get("condition"))).or_else(|| {
+                text(stage_output(resp, "prepare_conditions").and_then(|o| o.get("default")))
+            }),
+            aspects: field("aspects")
+                .and_then(|value| serde_json::from_value(value.clone()).ok())
+                .unwrap_or_default(),
+            images: build
+                .and_then(|build| build.get("images").or_else(|| build.get("media")))
+                .or_else(|| product.and_then(|product| product.get("image_urls")))
+                .map(urls)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn price(&self) -> Option<String> {
+        self.price.map(|price| match &self.currency {
+            Some(currency) => format!("{price:.2} {currency}"),
+            None => format!("{price:.2}"),
+        })
+    }
+
+    /// Label/value rows for the fields that are present.
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        [
+            ("Title", self.title.clone()),
+            ("Price", self.price()),
+            ("Category", self.category.clone()),
+            ("Condition", self.condition.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.map(|value| (label, value)))
+        .collect()
+    }
+}
+
+fn stage_output<'a>(resp: &'a ListingResponse, name: &str) -> Option<&'a Value> {
+    resp.stages
+        .iter()
+        .find(|stage| stage.name == name)
+        .map(|stage| &stage.output)
+}
+
+/// Image URLs from a list of strings or of `{ "url": ... }` objects.
+fn urls(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.as_str().or_else(|| item["url"].as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+fn summary(stage: &StageReport) -> Option<&str> {
+    stage.output.get("summary").and_then(Value::as_str)
+}
+
+fn warnings(stage: &StageReport) -> Vec<String> {
+    stage
+        .output
+        .get("warnings")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn render(resp: &ListingResponse, format: ReportFormat) -> String {
+    let fields = ListingFields::from_response(resp);
+    match format {
+        ReportFormat::Markdown => markdown(resp, &fields),
+        ReportFormat::Html => html(resp, &fields),
+    }
+}
+
+fn total_ms(resp: &ListingResponse) -> i64 {
+    resp.stages.iter().map(|stage| stage.elapsed_ms).sum()
+}
+
+fn stage_warnings(resp: &ListingResponse) -> Vec<(&str, String)> {
+    resp.stages
+        .iter()
+        .flat_map(|stage| {
+            warnings(stage)
+                .into_iter()
+                .map(move |warning| (stage.name.as_str(), warning))
+        })
+        .collect()
+}
+
+/// Text for a Markdown table cell: on one line, with pipes escaped and `<`
+/// kept from being read as an HTML tag.
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace('<', "&lt;")
+        .replace(['\r', '\n'], " ")
+}
+
+fn markdown(resp: &ListingResponse, fields: &ListingFields) -> String {
+    let mut out = String::new();
+    let heading = fields.title.as_deref().unwrap_or("Listing report");
+    let _ = writeln!(out, "# {}\n", md_cell(heading));
+    let _ = writeln!(out, "Listing `{}`\n", resp.listing_id);
+
+    let rows = fields.rows();
+    if !rows.is_empty() {
+        out.push_str("| Field | Value |\n|---|---|\n");
+        for (label, value) in rows {
+            let _ = writeln!(out, "| {label} | {} |", md_cell(&value));
+        }
+        out.push('\n');
+    }
+
+    if !fields.aspects.is_empty() {
+        out.push_str("## Aspects\n\n| Aspect | Values |\n|---|---|\n");
+        for (name, values) in &fields.aspects {
+            let _ = writeln!(
+                out,
+                "| {} | {} |",
+                md_cell(name),
+                md_cell(&values.join(", "))
+            );
+        }
+        out.push('\n');
+    }
+
+    if !fields.images.is_empty() {
+        out.push_str("## Images\n\n");
+        for (idx, url) in fields.images.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "<a href=\"{url}\"><img src=\"{url}\" alt=\"image {}\" width=\"{THUMBNAIL_PX}\"></a>",
+                idx + 1,
+                url = html_escape(url)
+            );
+        }
+        out.push('\n');
+    }
+
+    let warnings = stage_warnings(resp);
+    if !warnings.is_empty() {
+        out.push_str("## Warnings\n\n");
+        for (stage, warning) in warnings {
+            let _ = writeln!(out, "- **{stage}**: {}", md_cell(&warning));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## Stages\n\n| Stage | Time (ms) | Summary |\n|---|---:|---|\n");
+    for stage in &resp.stages {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} |",
+            md_cell(&stage.name),
+            stage.elapsed_ms,
+            md_cell(summary(stage).unwrap_or(""))
+        );
+    }
+    let _ = writeln!(out, "| **Total** | **{}** | |", total_ms(resp));
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn html(resp: &ListingResponse, fields: &ListingFields) -> String {
+    let heading = html_escape(fields.title.as_deref().unwrap_or("Listing report"));
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{heading}</title>\n<style>\n\
+         body {{ font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; }}\n\
+         table {{ border-collapse: collapse; margin-bottom: 1.5rem; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 0.3rem 0.6rem; text-align: left; vertical-align: top; }}\n\
+         td.num {{ text-align: right; }}\n\
+         .thumbs img {{ width: {THUMBNAIL_PX}px; margin: 0 0.5rem 0.5rem 0; border: 1px solid #ccc; }}\n\
+         .warnings {{ color: #8a5300; }}\n\
+         </style>\n</head>\n<body>\n<h1>{heading}</h1>\n<p>Listing <code>{}</code></p>\n",
+        html_escape(&resp.listing_id)
+    );
+
+    let rows = fields.rows();
+    if !rows.is_empty() {
+        out.push_str("<table>\n");
+        for (label, value) in rows {
+            let _ = writeln!(
+                out,
+                "<tr><th>{label}</th><td>{}</td></tr>",
+                html_escape(&value)
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    if !fields.aspects.is_empty() {
+        out.push_str("<h2>Aspects</h2>\n<table>\n");
+        for (name, values) in &fields.aspects {
+            let _ = writeln!(
+                out,
+                "<tr><th>{}</th><td>{}</td></tr>",
+                html_escape(name),
+                html_escape(&values.join(", "))
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    if !fields.images.is_empty() {
+        out.push_str("<h2>Images</h2>\n<div class=\"thumbs\">\n");
+        for (idx, url) in fields.images.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "<a href=\"{url}\"><img src=\"{url}\" alt=\"image {}\" loading=\"lazy\"></a>",
+                idx + 1,
+                url = html_escape(url)
+            );
+        }
+        out.push_str("</div>\n");
+    }
+
+    let warnings = stage_warnings(resp);
+    if !warnings.is_empty() {
+        out.push_str("<h2>Warnings</h2>\n<ul class=\"warnings\">\n");
+        for (stage, warning) in warnings {
+            let _ = writeln!(
+                out,
+                "<li><strong>{}</strong>: {}</li>",
+                html_escape(stage),
+                html_escape(&warning)
+            );
+        }
+        out.push_str("</ul>\n");
+    }
+
+    out.push_str(
+        "<h2>Stages</h2>\n<table>\n<tr><th>Stage</th><th>Time (ms)</th><th>Summary</th></tr>\n",
+    );
+    for stage in &resp.stages {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+            html_escape(&stage.name),
+            stage.elapsed_ms,
+            html_escape(summary(stage).unwrap_or(""))
+        );
+    }
+    let _ = writeln!(
+        out,
+        "<tr><th>Total</th><th class=\"num\">{}</th><td></td></tr>\n</table>\n</body>\n</html>",
+        total_ms(resp)
+    );
+    out
+}
@@ -0,0 +1,341 @@
+//! Builders for the listing request models. The models mirror the OpenAPI
+//! schemas field for field, so most of their fields are `Option`s; the
+//! builders take the fields Hermes requires up front, leave the rest unset
+//! (Hermes' defaults) until a setter is called, and check the result in
+//! `build()` before anything is sent.
+//!
+//! ```
+//! use talaria_core::models::{ImagesSource, MarketplaceId, PublicListingRequest};
+//! use talaria_core::requests::ListingPolicies;
+//!
+//! let policies = ListingPolicies::new("warehouse", "ship-1", "pay-1", "ret-1");
+//! let request = PublicListingRequest::builder(
+//!     policies,
+//!     ImagesSource::Single("https://img.example/a.jpg".into()),
+//! )
+//! .sku("sku-123".to_string())
+//! .marketplace(MarketplaceId::EbayUk)
+//! .dry_run(true)
+//! .build()
+//! .unwrap();
+//! assert_eq!(request.sku.as_deref(), Some("sku-123"));
+//! ```
+
+use crate::error::{Error, Result};
+use crate::models::{
+    ContinueRequest, ImagesSource, ListingDraftInput, ListingDraftRequest, LlmStageOptions,
+    MarketplaceId, PublicListingRequest, PublicPipelineOverrides,
+};
+
+/// The inventory location and eBay business policies every listing request
+/// names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingPolicies {
+    pub merchant_location_key: String,
+    pub fulfillment_policy_id: String,
+    pub payment_policy_id: String,
+    pub return_policy_id: String,
+}
+
+impl ListingPolicies {
+    pub fn new(
+        merchant_location_key: impl Into<String>,
+        fulfillment_policy_id: impl Into<String>,
+        payment_policy_id: impl Into<String>,
+        return_policy_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            merchant_location_key: merchant_location_key.into(),
+            fulfillment_policy_id: fulfillment_policy_id.into(),
+            payment_policy_id: payment_policy_id.into(),
+            return_policy_id: return_policy_id.into(),
+        }
+    }
+}
+
+/// The request's copies of the [`ListingPolicies`] fields, in that order.
+fn validate_policies(policies: [&str; 4]) -> Result<()> {
+    let fields = [
+        "merchant_location_key",
+        "fulfillment_policy_id",
+        "payment_policy_id",
+        "return_policy_id",
+    ];
+    fields
+        .into_iter()
+        .zip(policies)
+        .try_for_each(|(field, value)| not_blank(field, value))
+}
+
+fn not_blank(field: &str, value: &str) -> Result<()> {
+    if value.trim().is_empty() {
+        return Err(Error::InvalidRequest(format!("{field} must not be blank")));
+    }
+    Ok(())
+}
+
+fn validate_images(images: &ImagesSource) -> Result<()> {
+    let urls = match images {
+        ImagesSource::Single(url) => std::slice::from_ref(url),
+        ImagesSource::Multiple(urls) => urls.as_slice(),
+    };
+    if urls.is_empty() {
+        return Err(Error::InvalidRequest(
+            "at least one image is required".into(),
+        ));
+    }
+    urls.iter().try_for_each(|url| not_blank("image url", url))
+}
+
+fn validate_overrides(overrides: Option<&PublicPipelineOverrides>) -> Result<()> {
+    match overrides.and_then(|overrides| overrides.condition_id) {
+        Some(id) if id <= 0 => Err(Error::InvalidRequest(format!(
+            "condition_id must be positive, got {id}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+impl PublicListingRequest {
+    pub fn builder(
+        policies: ListingPolicies,
+        images_source: ImagesSource,
+    ) -> PublicListingRequestBuilder {
+        PublicListingRequestBuilder {
+            request: PublicListingRequest {
+                dry_run: None,
+                fulfillment_policy_id: policies.fulfillment_policy_id,
+                images_source,
+                llm_aspects: None,
+                llm_ingest: None,
+                marketplace: None,
+                merchant_location_key: policies.merchant_location_key,
+                overrides: None,
+                payment_policy_id: policies.payment_policy_id,
+                publish: None,
+                return_policy_id: policies.return_policy_id,
+                sku: None,
+                use_signed_urls: None,
+            },
+        }
+    }
+}
+
+/// Builds a [`PublicListingRequest`]; see [`PublicListingRequest::builder`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct PublicListingRequestBuilder {
+    request: PublicListingRequest,
+}
+
+impl PublicListingRequestBuilder {
+    pub fn sku(mut self, sku: impl Into<Option<String>>) -> Self {
+        self.request.sku = sku.into();
+        self
+    }
+
+    pub fn marketplace(mut self, marketplace: impl Into<Option<MarketplaceId>>) -> Self {
+        self.request.marketplace = marketplace.into();
+        self
+    }
+
+    /// Run every stage but create nothing on eBay.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.request.dry_run = Some(dry_run);
+        self
+    }
+
+    /// Publish the offer instead of leaving it as an unpublished draft.
+    pub fn publish(mut self, publish: bool) -> Self {
+        self.request.publish = Some(publish);
+        self
+    }
+
+    /// Let Hermes sign private storage URLs before fetching them. Unset
+    /// unless `true`, as Hermes' default is off.
+    pub fn use_signed_urls(mut self, use_signed_urls: bool) -> Self {
+        self.request.use_signed_urls = use_signed_urls.then_some(true);
+        self
+    }
+
+    pub fn llm_ingest(mut self, options: impl Into<Option<LlmStageOptions>>) -> Self {
+        self.request.llm_ingest = options.into();
+        self
+    }
+
+    pub fn llm_aspects(mut self, options: impl Into<Option<LlmStageOptions>>) -> Self {
+        self.request.llm_aspects = options.into();
+        self
+    }
+
+    pub fn overrides(mut self, overrides: impl Into<Option<PublicPipelineOverrides>>) -> Self {
+        self.request.overrides = overrides.into();
+        self
+    }
+
+    pub fn build(self) -> Result<PublicListingRequest> {
+        let request = self.request;
+        validate_policies([
+            &request.merchant_location_key,
+            &request.fulfillment_policy_id,
+            &request.payment_policy_id,
+            &request.return_policy_id,
+        ])?;
+        validate_images(&request.images_source)?;
+        if let Some(sku) = &request.sku {
+            not_blank("sku", sku)?;
+        }
+        validate_overrides(request.overrides.as_ref())?;
+        Ok(request)
+    }
+}
+
+impl ListingDraftRequest {
+    pub fn builder(
+        sku: impl Into<String>,
+        policies: ListingPolicies,
+        listing: ListingDraftInput,
+    ) -> ListingDraftRequestBuilder {
+        ListingDraftRequestBuilder {
+            request: ListingDraftRequest {
+                sku: sku.into(),
+                merchant_location_key: policies.merchant_location_key,
+                fulfillment_policy_id: policies.fulfillment_policy_id,
+                payment_policy_id: policies.payment_policy_id,
+                return_policy_id: policies.return_policy_id,
+                marketplace: None,
+                listing,
+                dry_run: None,
+                publish: None,
+            },
+        }
+    }
+}
+
+/// Builds a [`ListingDraftRequest`]; see [`ListingDraftRequest::builder`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ListingDraftRequestBuilder {
+    request: ListingDraftRequest,
+}
+
+impl ListingDraftRequestBuilder {
+    pub fn marketplace(mut self, marketplace: impl Into<Option<MarketplaceId>>) -> Self {
+        self.request.marketplace = marketplace.into();
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.request.dry_run = Some(dry_run);
+        self
+    }
+
+    pub fn publish(mut self, publish: bool) -> Self {
+        self.request.publish = Some(publish);
+        self
+    }
+
+    pub fn build(self) -> Result<ListingDraftRequest> {
+        let request = self.request;
+        not_blank("sku", &request.sku)?;
+        validate_policies([
+            &request.merchant_location_key,
+            &request.fulfillment_policy_id,
+            &request.payment_policy_id,
+            &request.return_policy_id,
+        ])?;
+        let listing = &request.listing;
+        for (field, value) in [
+            ("title", &listing.title),
+            ("description", &listing.description),
+            ("currency", &listing.currency),
+            ("category_id", &listing.category_id),
+        ] {
+            not_blank(field, value)?;
+        }
+        if !(listing.price.is_finite() && listing.price > 0.0) {
+            return Err(Error::InvalidRequest("price must be positive".into()));
+        }
+        if listing.condition_id <= 0 {
+            return Err(Error::InvalidRequest(
+                "condition_id must be positive".into(),
+            ));
+        }
+        if listing.quantity.is_some_and(|quantity| quantity <= 0) {
+            return Err(Error::InvalidRequest("quantity must be positive".into()));
+        }
+        validate_images(&ImagesSource::Multiple(listing.images.clone()))?;
+        Ok(request)
+    }
+}
+
+impl ContinueRequest {
+    pub fn builder(sku: impl Into<String>, policies: ListingPolicies) -> ContinueRequestBuilder {
+        ContinueRequestBuilder {
+            request: ContinueRequest {
+                fulfillment_policy_id: policies.fulfillment_policy_id,
+                images_source: None,
+                llm_aspects: None,
+                llm_ingest: None,
+                marketplace: None,
+                merchant_location_key: policies.merchant_location_key,
+                overrides: None,
+                payment_policy_id: policies.payment_policy_id,
+                return_policy_id: policies.return_policy_id,
+                sku: sku.into(),
+            },
+        }
+    }
+}
+
+/// Builds a [`ContinueRequest`]; see [`ContinueRequest::builder`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ContinueRequestBuilder {
+    request: ContinueRequest,
+}
+
+impl ContinueRequestBuilder {
+    /// Replaces the images of the listing being continued; without it Hermes
+    /// reuses the ones it already has.
+    pub fn images_source(mut self, images: impl Into<Option<ImagesSource>>) -> Self {
+        self.request.images_source = images.into();
+        self
+    }
+
+    pub fn marketplace(mut self, marketplace: impl Into<Option<MarketplaceId>>) -> Self {
+        self.request.marketplace = marketplace.into();
+        self
+    }
+
+    pub fn llm_ingest(mut self, options: impl Into<Option<LlmStageOptions>>) -> Self {
+        self.request.llm_ingest = options.into();
+        self
+    }
+
+    pub fn llm_aspects(mut self, options: impl Into<Option<LlmStageOptions>>) -> Self {
+        self.request.llm_aspects = options.into();
+        self
+    }
+
+    pub fn overrides(mut self, overrides: impl Into<Option<PublicPipelineOverrides>>) -> Self {
+        self.request.overrides = overrides.into();
+        self
+    }
+
+    pub fn build(self) -> Result<ContinueRequest> {
+        let request = self.request;
+        not_blank("sku", &request.sku)?;
+        validate_policies([
+            &request.merchant_location_key,
+            &request.fulfillment_policy_id,
+            &request.payment_policy_id,
+            &request.return_policy_id,
+        ])?;
+        if let Some(images) = &request.images_source {
+            validate_images(images)?;
+        }
+        validate_overrides(request.overrides.as_ref())?;
+        Ok(request)
+    }
+}
@@ -0,0 +1,250 @@
+//! Image uploads to an S3-compatible bucket (MinIO, Cloudflare R2,
+//! Backblaze B2), for deployments without Supabase Storage.
+//!
+//! Requests go through `aws-sdk-s3`'s own HTTP client, so the `[network]`
+//! proxy and CA bundle do not apply to them.
+
+use crate::config::{DEFAULT_SUPABASE_SIGNED_URL_TTL_SECS, S3Config};
+use crate::error::{Error, Result};
+use crate::metrics;
+use crate::supabase::{SupabaseObject, new_object_path};
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::config::{
+    BehaviorVersion, Credentials, Region, RequestChecksumCalculation, ResponseChecksumValidation,
+};
+use aws_sdk_s3::error::{DisplayErrorContext, ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use chrono::{DateTime, Utc};
+use mime_guess::MimeGuess;
+use reqwest::{StatusCode, Url};
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct S3Store {
+    client: aws_sdk_s3::Client,
+    endpoint: Url,
+    public_base: Url,
+    bucket: String,
+    upload_prefix: String,
+    use_presigned_urls: bool,
+}
+
+impl S3Store {
+    pub fn from_config(config: &S3Config) -> Result<Self> {
+        let endpoint = config
+            .endpoint
+            .parse::<Url>()
+            .map_err(|err| Error::InvalidConfig(format!("invalid S3_ENDPOINT: {err}")))?;
+        if config.bucket.trim().is_empty() {
+            return Err(Error::InvalidConfig(
+                "S3_BUCKET required with S3_ENDPOINT".into(),
+            ));
+        }
+        let (Some(key_id), Some(secret)) = (&config.access_key_id, &config.secret_access_key)
+        else {
+            return Err(Error::InvalidConfig(
+                "S3_ACCESS_KEY_ID and S3_SECRET_ACCESS_KEY required for uploads".into(),
+            ));
+        };
+        let public_base = match &config.public_base {
+            Some(base) => base
+                .parse::<Url>()
+                .map_err(|err| Error::InvalidConfig(format!("invalid S3_PUBLIC_BASE: {err}")))?,
+            None => endpoint
+                .join(&format!("{}/", config.bucket))
+                .map_err(|err| Error::InvalidConfig(format!("invalid S3_BUCKET: {err}")))?,
+        };
+        let sdk_config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(endpoint.as_str().trim_end_matches('/'))
+            .region(Region::new(config.region.clone()))
+            .credentials_provider(Credentials::new(
+                key_id.clone(),
+                secret.clone(),
+                None,
+                None,
+                "talaria-config",
+            ))
+            .force_path_style(true)
+            // Checksum trailers on every upload are newer than most
+            // S3-compatible servers; send them only where S3 requires one.
+            .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+            .response_checksum_validation(ResponseChecksumValidation::WhenRequired)
+            .build();
+        Ok(Self {
+            client: aws_sdk_s3::Client::from_conf(sdk_config),
+            endpoint,
+            public_base,
+            bucket: config.bucket.clone(),
+            upload_prefix: config.upload_prefix.clone(),
+            use_presigned_urls: config.use_presigned_urls,
+        })
+    }
+
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    pub fn upload_prefix(&self) -> &str {
+        &self.upload_prefix
+    }
+
+    pub fn use_presigned_urls(&self) -> bool {
+        self.use_presigned_urls
+    }
+
+    /// Stores `bytes` under the upload prefix and returns the new object key.
+    pub async fn upload_object(&self, filename_hint: &str, bytes: Vec<u8>) -> Result<String> {
+        let key = new_object_path(&self.upload_prefix, filename_hint);
+        let mime = MimeGuess::from_path(filename_hint)
+            .first_raw()
+            .unwrap_or("application/octet-stream");
+        let len = bytes.len() as u64;
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .content_type(mime)
+            .body(ByteStream::from(bytes))
+            .send()
+            .await
+            .map_err(s3_error)?;
+        metrics::global().incr(metrics::UPLOAD_BYTES, &[("target", "s3")], len);
+        Ok(key)
+    }
+
+    /// URL handed to listings for an existing object: presigned when
+    /// `use_presigned_urls` is set, public otherwise.
+    pub async fn object_url(&self, key: &str) -> Result<String> {
+        if self.use_presigned_urls {
+            return self
+                .presigned_url(
+                    key,
+                    Duration::from_secs(DEFAULT_SUPABASE_SIGNED_URL_TTL_SECS),
+                )
+                .await;
+        }
+        Ok(self.public_url(key))
+    }
+
+    /// A GET URL for `key` that works for `ttl` without credentials, even on
+    /// a private bucket. S3 caps `ttl` at seven days.
+    pub async fn presigned_url(&self, key: &str, ttl: Duration) -> Result<String> {
+        let presigning = PresigningConfig::expires_in(ttl.max(Duration::from_secs(1)))
+            .map_err(|err| Error::InvalidConfig(format!("presigned URL lifetime: {err}")))?;
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key.trim_start_matches('/'))
+            .presigned(presigning)
+            .await
+            .map_err(s3_error)?;
+        Ok(request.uri().to_string())
+    }
+
+    pub fn public_url(&self, key: &str) -> String {
+        format!(
+            "{}/{}",
+            self.public_base.as_str().trim_end_matches('/'),
+            key.trim_start_matches('/')
+        )
+    }
+
+    /// Identifies the storage target (endpoint + bucket) for the upload cache.
+    pub fn cache_scope(&self) -> String {
+        format!(
+            "{}/{}",
+            self.endpoint.as_str().trim_end_matches('/'),
+            self.bucket
+        )
+    }
+
+    /// Lists every object under `prefix` (a folder path relative to the
+    /// bucket). S3 keeps no creation time, so objects carry only
+    /// `updated_at`.
+    pub async fn list_objects(&self, prefix: &str) -> Result<Vec<SupabaseObject>> {
+        let prefix = prefix.trim_matches('/');
+        let prefix = if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{prefix}/")
+        };
+        let mut out = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let page = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(&prefix)
+                .set_continuation_token(token.take())
+                .send()
+                .await
+                .map_err(s3_error)?;
+            for object in page.contents() {
+                let Some(key) = object.key() else {
+                    continue;
+                };
+                out.push(SupabaseObject {
+                    path: key.to_string(),
+                    size: object.size().and_then(|size| u64::try_from(size).ok()),
+                    created_at: None,
+                    updated_at: object.last_modified().and_then(|at| {
+                        DateTime::<Utc>::from_timestamp(at.secs(), at.subsec_nanos())
+                    }),
+                });
+            }
+            token = page.next_continuation_token().map(str::to_string);
+            if !page.is_truncated().unwrap_or(false) || token.is_none() {
+                break;
+            }
+        }
+        out.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(out)
+    }
+
+    /// Deletes a single object (key relative to the bucket).
+    pub async fn delete_object(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key.trim_start_matches('/'))
+            .send()
+            .await
+            .map_err(s3_error)?;
+        Ok(())
+    }
+
+    /// Maps a public or presigned URL for this bucket back to its key.
+    pub fn object_path_from_url(&self, url: &str) -> Option<String> {
+        let url = url.split(['?', '#']).next().unwrap_or(url);
+        let bucket_base = format!(
+            "{}/{}/",
+            self.endpoint.as_str().trim_end_matches('/'),
+            self.bucket
+        );
+        let public_base = format!("{}/", self.public_base.as_str().trim_end_matches('/'));
+        [public_base, bucket_base]
+            .iter()
+            .find_map(|base| url.strip_prefix(base.as_str()))
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+    }
+}
+
+fn s3_error<E>(err: SdkError<E, HttpResponse>) -> Error
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+{
+    let status = err
+        .raw_response()
+        .and_then(|resp| StatusCode::from_u16(resp.status().as_u16()).ok());
+    let message = match (err.code(), err.message()) {
+        (Some(code), Some(message)) => format!("{code}: {message}"),
+        (Some(code), None) => code.to_string(),
+        _ => DisplayErrorContext(&err).to_string(),
+    };
+    Error::S3 { status, message }
+}
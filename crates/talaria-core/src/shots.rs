@@ -0,0 +1,85 @@
+//! Session templates: named shot lists (e.g. apparel: front, back, tag,
+//! defect close-up) that a capture session is checked against before its
+//! frames are committed to the product.
+//!
+//! Templates come from `[[session_templates]]` tables in the config file.
+//! Frames picked during a session are labelled with the shot they cover, and
+//! the labels stay on the product images after the commit.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionTemplate {
+    pub name: String,
+    /// Shots a session needs before it is committed, in shooting order.
+    #[serde(default)]
+    pub shots: Vec<String>,
+    /// Shots offered in the checklist but not required.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub optional: Vec<String>,
+}
+
+impl SessionTemplate {
+    /// Every shot label, required ones first.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.shots.iter().chain(&self.optional).map(String::as_str)
+    }
+
+    pub fn is_required(&self, label: &str) -> bool {
+        self.shots.iter().any(|shot| shot == label)
+    }
+
+    /// Required shots not among `assigned`.
+    pub fn missing<'a>(&'a self, assigned: &[&str]) -> Vec<&'a str> {
+        self.shots
+            .iter()
+            .map(String::as_str)
+            .filter(|shot| !assigned.contains(shot))
+            .collect()
+    }
+
+    /// The first shot, required before optional, not among `assigned`.
+    pub fn next_unfilled(&self, assigned: &[&str]) -> Option<&str> {
+        self.labels().find(|shot| !assigned.contains(shot))
+    }
+}
+
+/// Rejects templates without a name, with duplicate names, without shots,
+/// or with blank or repeated shot labels.
+pub fn validate_templates(templates: &[SessionTemplate]) -> Result<()> {
+    let mut names = HashSet::new();
+    for template in templates {
+        let name = template.name.trim();
+        if name.is_empty() {
+            return Err(Error::InvalidConfig(
+                "session_templates entries need a name".into(),
+            ));
+        }
+        if !names.insert(name.to_ascii_lowercase()) {
+            return Err(Error::InvalidConfig(format!(
+                "duplicate session template '{name}'"
+            )));
+        }
+        let mut labels = HashSet::new();
+        for label in template.labels() {
+            if label.trim().is_empty() {
+                return Err(Error::InvalidConfig(format!(
+                    "session template '{name}' has a blank shot"
+                )));
+            }
+            if !labels.insert(label) {
+                return Err(Error::InvalidConfig(format!(
+                    "session template '{name}' lists shot '{label}' twice"
+                )));
+            }
+        }
+        if labels.is_empty() {
+            return Err(Error::InvalidConfig(format!(
+                "session template '{name}' has no shots"
+            )));
+        }
+    }
+    Ok(())
+}
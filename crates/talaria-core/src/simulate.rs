@@ -0,0 +1,279 @@
+//! Simulation mode: stand-ins for the parts of a workflow that need
+//! credentials or hardware, so the CLI/TUI flow can be demoed and tested
+//! without them.
+//!
+//! Enabled with `--simulate` or `TALARIA_SIMULATE=1`. Supabase uploads and
+//! Hermes media uploads return fake URLs derived from the file contents (the
+//! same bytes always get the same URL), and the camera produces synthetic
+//! test-pattern frames. Enrichment and listing calls are not simulated.
+
+use crate::error::{Error, Result};
+use crate::models::{
+    CompleteUploadRequest, CompleteUploadResponse, CreateUploadRequest, ListMediaResponse, Media,
+    UpdateMediaRequest, UploadMethod, UploadSession,
+};
+use crate::upload_cache::sha256_hex;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Host used for every simulated URL; `.invalid` never resolves.
+pub const SIMULATED_MEDIA_BASE: &str = "https://media.simulated.talaria.invalid";
+pub const SIMULATED_CAMERA_NAME: &str = "Synthetic camera (simulation)";
+pub const SYNTHETIC_FRAME_WIDTH: u32 = 640;
+pub const SYNTHETIC_FRAME_HEIGHT: u32 = 480;
+
+/// Object key for simulated uploads: stable for the same name and bytes.
+pub fn object_key(prefix: &str, filename: &str, bytes: &[u8]) -> String {
+    format!(
+        "{}/{}-{}",
+        prefix.trim_end_matches('/'),
+        &sha256_hex(bytes)[..16],
+        filename.rsplit(['/', '\\']).next().unwrap_or(filename)
+    )
+}
+
+/// Fixed timestamp for simulated records so output is reproducible.
+fn epoch() -> DateTime<Utc> {
+    DateTime::<Utc>::UNIX_EPOCH
+}
+
+/// In-memory stand-in for the Hermes media endpoints.
+#[derive(Debug, Clone, Default)]
+pub struct MediaSimulator {
+    state: Arc<Mutex<MediaState>>,
+}
+
+#[derive(Debug, Default)]
+struct MediaState {
+    pending: BTreeMap<String, Media>,
+    media: BTreeMap<String, Media>,
+}
+
+impl MediaSimulator {
+    pub fn create_upload(&self, body: &CreateUploadRequest) -> UploadSession {
+        let fingerprint = sha256_hex(
+            format!(
+                "{}|{}|{}",
+                body.filename,
+                body.sha256.as_deref().unwrap_or_default(),
+                body.product_id.as_deref().unwrap_or_default()
+            )
+            .as_bytes(),
+        );
+        let id = &fingerprint[..16];
+        let object_key = format!("media/{id}/{}", body.filename);
+        let url = format!("{SIMULATED_MEDIA_BASE}/{object_key}");
+        let media = Media {
+            content_length: body.content_length,
+            content_type: body.content_type.clone(),
+            created_at: epoch(),
+            filename: Some(body.filename.clone()),
+            media_id: format!("sim_media_{id}"),
+            object_key: object_key.clone(),
+            product_id: body.product_id.clone(),
+            purpose: body.purpose.clone(),
+            rank: None,
+            session_id: body.session_id.clone(),
+            sha256: body.sha256.clone(),
+            updated_at: epoch(),
+            url: url.clone(),
+        };
+        let upload_id = format!("sim_up_{id}");
+        self.state.lock().pending.insert(upload_id.clone(), media);
+        UploadSession {
+            expires_at: epoch(),
+            headers: None,
+            method: UploadMethod::Put,
+            object_key,
+            upload_id: upload_id.clone(),
+            upload_url: format!("{SIMULATED_MEDIA_BASE}/uploads/{upload_id}"),
+            url: Some(url),
+        }
+    }
+
+    pub fn complete_upload(
+        &self,
+        upload_id: &str,
+        body: Option<&CompleteUploadRequest>,
+    ) -> Result<CompleteUploadResponse> {
+        let mut state = self.state.lock();
+        let mut media = state
+            .pending
+            .remove(upload_id)
+            .ok_or_else(|| not_found(format!("upload {upload_id}")))?;
+        if let Some(sha256) = body.and_then(|b| b.sha256.clone()) {
+            media.sha256 = Some(sha256);
+        }
+        state.media.insert(media.media_id.clone(), media.clone());
+        Ok(CompleteUploadResponse { media })
+    }
+
+    pub fn abort_upload(&self, upload_id: &str) {
+        self.state.lock().pending.remove(upload_id);
+    }
+
+    pub fn delete_media(&self, media_id: &str) -> Result<()> {
+        self.state
+            .lock()
+            .media
+            .remove(media_id)
+            .map(drop)
+            .ok_or_else(|| not_found(format!("media {media_id}")))
+    }
+
+    pub fn update_media(&self, media_id: &str, body: &UpdateMediaRequest) -> Result<Media> {
+        let mut state = self.state.lock();
+        let media = state
+            .media
+            .get_mut(media_id)
+            .ok_or_else(|| not_found(format!("media {media_id}")))?;
+        if let Some(purpose) = &body.purpose {
+            media.purpose = Some(purpose.clone());
+        }
+        if let Some(rank) = body.rank {
+            media.rank = Some(rank);
+        }
+        Ok(media.clone())
+    }
+
+    pub fn list_product_media(&self, product_id: &str) -> ListMediaResponse {
+        let mut media: Vec<Media> = self
+            .state
+            .lock()
+            .media
+            .values()
+            .filter(|m| m.product_id.as_deref() == Some(product_id))
+            .cloned()
+            .collect();
+        media.sort_by_key(|m| m.rank.unwrap_or(i32::MAX));
+        ListMediaResponse { items: media }
+    }
+}
+
+fn not_found(what: String) -> Error {
+    Error::from_api(
+        StatusCode::NOT_FOUND,
+        None,
+        Some(format!("{what} not found (simulation)")),
+        None,
+    )
+}
+
+/// RGB8 test pattern: colour bars with a bar that moves with `seq`, so a
+/// live preview visibly updates. Deterministic for a given `seq`.
+pub fn synthetic_frame(width: u32, height: u32, seq: u64) -> Vec<u8> {
+    const BARS: [[u8; 3]; 7] = [
+        [235, 235, 235],
+        [235, 235, 16],
+        [16, 235, 235],
+        [16, 235, 16],
+        [235, 16, 235],
+        [235, 16, 16],
+        [16, 16, 235],
+    ];
+    let (w, h) = (width.max(1) as u64, height.max(1) as u64);
+    let marker = (seq * 8) % w;
+    let mut pixels = Vec::with_capacity((w * h * 3) as usize);
+    for y in 0..h {
+        for x in 0..w {
+            let pixel = if x.abs_diff(marker) < 6 {
+                [16, 16, 16]
+            } else if y > h * 3 / 4 {
+                // Grey ramp along the bottom.
+                let v = (x * 255 / w) as u8;
+                [v, v, v]
+            } else {
+                BARS[(x * BARS.len() as u64 / w) as usize]
+            };
+            pixels.extend_from_slice(&pixel);
+        }
+    }
+    pixels
+}
+
+/// Writes `count` synthetic frames as PNGs into `out_dir`. Names and
+/// contents depend only on the index, so reruns produce identical files.
+pub fn capture_frames(count: usize, out_dir: &Path) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(out_dir)?;
+    (0..count)
+        .map(|i| {
+            let pixels = synthetic_frame(SYNTHETIC_FRAME_WIDTH, SYNTHETIC_FRAME_HEIGHT, i as u64);
+            let path = out_dir.join(format!("capture-sim-{i}.png"));
+            fs::write(
+                &path,
+                encode_png(SYNTHETIC_FRAME_WIDTH, SYNTHETIC_FRAME_HEIGHT, &pixels),
+            )?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Minimal RGB8 PNG encoder (uncompressed deflate blocks) so simulated
+/// captures work in builds without the `camera` feature.
+pub fn encode_png(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+    let row_len = width as usize * 3;
+    let mut raw = Vec::with_capacity((row_len + 1) * height as usize);
+    for row in rgb.chunks(row_len).take(height as usize) {
+        raw.push(0); // filter: none
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        zlib.push(u8::from(blocks.peek().is_none()));
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]); // 8-bit RGB, no interlace
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    for (kind, data) in [(b"IHDR", &ihdr), (b"IDAT", &zlib), (b"IEND", &Vec::new())] {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend_from_slice(kind);
+        png.extend_from_slice(data);
+        let crc = crc32(&png[start..]);
+        png.extend_from_slice(&crc.to_be_bytes());
+    }
+    png
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
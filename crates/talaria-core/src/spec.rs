@@ -0,0 +1,324 @@
+//! The slice of the Hermes OpenAPI contract this build knows about, and how it
+//! compares with the document a server publishes. `talaria config check-api`
+//! uses it to tell users when Hermes has moved ahead of their client.
+//!
+//! The endpoints are the ones [`HermesClient`](crate::HermesClient) calls; the
+//! schema fields are read from the `components.schemas.*` structs in
+//! `models.rs`, so the surface cannot drift from the models themselves.
+
+use crate::error::{Error, Result};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Where Hermes serves its OpenAPI document, relative to the base URL.
+pub const OPENAPI_PATH: &str = "openapi.json";
+
+/// Method and path template of every endpoint the client calls.
+const ENDPOINTS: &[(&str, &str)] = &[
+    ("get", "health"),
+    ("post", "v1/auth/device/start"),
+    ("post", "v1/auth/device/poll"),
+    ("get", "user/api-keys"),
+    ("post", "user/api-keys"),
+    ("delete", "user/api-keys/{key_id}"),
+    ("post", "hsuf/enrich"),
+    ("post", "listings"),
+    ("post", "listings/continue"),
+    ("post", "listings/publish-draft"),
+    ("post", "jobs/listings"),
+    ("get", "jobs/{id}"),
+    ("post", "v1/pricing/quote"),
+    ("post", "v1/pricing/comparables"),
+    ("get", "v1/usage"),
+    ("post", "v1/media/uploads"),
+    ("post", "v1/media/uploads/{upload_id}/complete"),
+    ("post", "v1/media/uploads/{upload_id}/abort"),
+    ("patch", "v1/media/{media_id}"),
+    ("delete", "v1/media/{media_id}"),
+    ("get", "v1/products"),
+    ("post", "v1/products"),
+    ("get", "v1/products/{product_id}"),
+    ("patch", "v1/products/{product_id}"),
+    ("delete", "v1/products/{product_id}"),
+    ("get", "v1/products/{product_id}/media"),
+];
+
+const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Endpoint {
+    pub method: String,
+    /// Path template without the leading slash, as written in the spec.
+    pub path: String,
+}
+
+impl Endpoint {
+    fn new(method: &str, path: &str) -> Self {
+        Self {
+            method: method.to_ascii_lowercase(),
+            path: path.trim_start_matches('/').to_string(),
+        }
+    }
+
+    /// Path parameters are compared by position, not name.
+    fn key(&self) -> (String, String) {
+        let mut path = String::with_capacity(self.path.len());
+        let mut in_param = false;
+        for ch in self.path.chars() {
+            match ch {
+                '{' => {
+                    in_param = true;
+                    path.push_str("{}");
+                }
+                '}' => in_param = false,
+                _ if !in_param => path.push(ch),
+                _ => {}
+            }
+        }
+        (self.method.clone(), path)
+    }
+}
+
+impl std::fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} /{}", self.method.to_ascii_uppercase(), self.path)
+    }
+}
+
+/// Fields of one schema. `open` schemas flatten another type into
+/// themselves, so fields the server adds to them cannot be told apart from
+/// ones the client reads through the flattened type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaFields {
+    pub fields: BTreeSet<String>,
+    pub open: bool,
+}
+
+/// Endpoints and object schemas of an API description.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiSurface {
+    pub version: Option<String>,
+    pub endpoints: BTreeSet<Endpoint>,
+    pub schemas: BTreeMap<String, SchemaFields>,
+}
+
+impl ApiSurface {
+    /// What this build of the client was written against.
+    pub fn known() -> Self {
+        Self {
+            version: None,
+            endpoints: ENDPOINTS
+                .iter()
+                .map(|(method, path)| Endpoint::new(method, path))
+                .collect(),
+            schemas: model_schemas(include_str!("models.rs")),
+        }
+    }
+
+    /// Reads `paths` and `components.schemas` of an OpenAPI 3 document.
+    pub fn from_openapi(doc: &Value) -> Result<Self> {
+        let paths = doc
+            .get("paths")
+            .and_then(Value::as_object)
+            .ok_or_else(|| Error::InvalidConfig("OpenAPI document has no paths".into()))?;
+        let endpoints = paths
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .into_iter()
+                    .flat_map(|ops| ops.keys())
+                    .filter(|method| HTTP_METHODS.contains(&method.to_ascii_lowercase().as_str()))
+                    .map(move |method| Endpoint::new(method, path))
+            })
+            .collect();
+        let schemas = doc
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, schema)| {
+                let properties = schema.get("properties")?.as_object()?;
+                Some((
+                    name.clone(),
+                    SchemaFields {
+                        fields: properties.keys().cloned().collect(),
+                        open: false,
+                    },
+                ))
+            })
+            .collect();
+        Ok(Self {
+            version: doc
+                .pointer("/info/version")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            endpoints,
+            schemas,
+        })
+    }
+
+    /// Short SHA-256 over the endpoints and schema fields; equal surfaces
+    /// have equal fingerprints.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        for endpoint in &self.endpoints {
+            let (method, path) = endpoint.key();
+            hasher.update(format!("{method} {path}\n"));
+        }
+        for (name, schema) in &self.schemas {
+            hasher.update(format!("{name}:"));
+            for field in &schema.fields {
+                hasher.update(format!(" {field}"));
+            }
+            hasher.update("\n");
+        }
+        hasher
+            .finalize()
+            .iter()
+            .take(6)
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// How `server` differs from `self`, the client's surface. Only the
+    /// server's copies of schemas the client models are compared field by
+    /// field; the server's other schemas are listed by name.
+    pub fn drift(&self, server: &ApiSurface) -> SpecDrift {
+        let client_keys: BTreeSet<_> = self.endpoints.iter().map(Endpoint::key).collect();
+        let server_keys: BTreeSet<_> = server.endpoints.iter().map(Endpoint::key).collect();
+        let mut drift = SpecDrift {
+            server_version: server.version.clone(),
+            client_fingerprint: self.fingerprint(),
+            server_fingerprint: server.fingerprint(),
+            unknown_endpoints: server
+                .endpoints
+                .iter()
+                .filter(|endpoint| !client_keys.contains(&endpoint.key()))
+                .cloned()
+                .collect(),
+            missing_endpoints: self
+                .endpoints
+                .iter()
+                .filter(|endpoint| !server_keys.contains(&endpoint.key()))
+                .cloned()
+                .collect(),
+            ..SpecDrift::default()
+        };
+        for (name, server_schema) in &server.schemas {
+            let Some(client_schema) = self.schemas.get(name) else {
+                drift.unknown_schemas.push(name.clone());
+                continue;
+            };
+            let unknown: Vec<String> = server_schema
+                .fields
+                .difference(&client_schema.fields)
+                .cloned()
+                .collect();
+            if !unknown.is_empty() && !client_schema.open {
+                drift.unknown_fields.insert(name.clone(), unknown);
+            }
+            let missing: Vec<String> = client_schema
+                .fields
+                .difference(&server_schema.fields)
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                drift.missing_fields.insert(name.clone(), missing);
+            }
+        }
+        drift
+    }
+}
+
+/// Differences between the client's API surface and a server's.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SpecDrift {
+    pub server_version: Option<String>,
+    pub client_fingerprint: String,
+    pub server_fingerprint: String,
+    /// Served by Hermes but never called by this client.
+    pub unknown_endpoints: Vec<Endpoint>,
+    /// Called by this client but no longer served.
+    pub missing_endpoints: Vec<Endpoint>,
+    /// Server schemas with no model in this client.
+    pub unknown_schemas: Vec<String>,
+    /// Fields the server sends or accepts that the client's models drop.
+    pub unknown_fields: BTreeMap<String, Vec<String>>,
+    /// Fields the client's models have that the server no longer lists.
+    pub missing_fields: BTreeMap<String, Vec<String>>,
+}
+
+impl SpecDrift {
+    /// The server offers endpoints or fields this client cannot use.
+    pub fn upgrade_available(&self) -> bool {
+        !self.unknown_endpoints.is_empty() || !self.unknown_fields.is_empty()
+    }
+
+    /// The client relies on endpoints or fields the server dropped.
+    pub fn breaking(&self) -> bool {
+        !self.missing_endpoints.is_empty() || !self.missing_fields.is_empty()
+    }
+
+    pub fn in_sync(&self) -> bool {
+        !self.upgrade_available() && !self.breaking() && self.unknown_schemas.is_empty()
+    }
+}
+
+/// Field names of the `/// components.schemas.X` structs in `source`.
+fn model_schemas(source: &str) -> BTreeMap<String, SchemaFields> {
+    let mut schemas = BTreeMap::new();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        let Some(name) = line
+            .strip_prefix("/// components.schemas.")
+            .and_then(|rest| rest.split_whitespace().next())
+        else {
+            continue;
+        };
+        let header = format!("pub struct {name} {{");
+        // Skip attributes; enums and aliases have no field list.
+        if lines
+            .by_ref()
+            .find(|line| !line.starts_with("#[") && !line.starts_with("///"))
+            .is_none_or(|line| line.trim() != header)
+        {
+            continue;
+        }
+        let mut schema = SchemaFields::default();
+        let mut rename = None;
+        let mut flatten = false;
+        for line in lines.by_ref() {
+            let line = line.trim();
+            if line == "}" {
+                break;
+            }
+            if let Some(attr) = line.strip_prefix("#[serde(") {
+                flatten |= attr.contains("flatten");
+                rename = attr
+                    .split_once("rename = \"")
+                    .and_then(|(_, rest)| rest.split_once('"'))
+                    .map(|(name, _)| name.to_string());
+                continue;
+            }
+            let Some((field, _)) = line
+                .strip_prefix("pub ")
+                .and_then(|rest| rest.split_once(':'))
+            else {
+                continue;
+            };
+            if std::mem::take(&mut flatten) {
+                schema.open = true;
+            } else {
+                let field = field.trim().trim_start_matches("r#");
+                schema
+                    .fields
+                    .insert(rename.take().unwrap_or_else(|| field.to_string()));
+            }
+            rename = None;
+        }
+        schemas.insert(name.to_string(), schema);
+    }
+    schemas
+}
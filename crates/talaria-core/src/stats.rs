@@ -0,0 +1,250 @@
+//! Throughput and cycle times from local records: capture sessions in the
+//! workspace and listing jobs in the [`JobLedger`](crate::ledger::JobLedger).
+//!
+//! Days are local calendar days. A product counts as captured on the day its
+//! session was committed, and a listing job on the day its outcome was
+//! recorded (jobs whose outcome is unknown are left out). The cycle time of
+//! a published listing runs from the start of the product's first session to
+//! the job's completion. A failed job's stage is the prefix of its error,
+//! e.g. `aspects` in `aspects: timeout`.
+
+use crate::error::{Error, Result};
+use crate::ledger::{JobLedgerEntry, TrackedState};
+use chrono::{DateTime, Days, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Days covered when no range is given, today included.
+pub const DEFAULT_DAYS: u64 = 14;
+
+/// Stage of failed jobs whose error names none.
+const UNKNOWN_STAGE: &str = "unknown";
+
+/// A capture session, as read from the workspace's `session.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionSample {
+    pub product_id: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub committed_at: Option<DateTime<Utc>>,
+}
+
+/// An inclusive range of local days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DayRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl DayRange {
+    /// `from` and `to` as `YYYY-MM-DD`; `to` defaults to today and `from`
+    /// to [`DEFAULT_DAYS`] days before `to`.
+    pub fn parse(from: Option<&str>, to: Option<&str>) -> Result<Self> {
+        Self::parse_or_last(from, to, DEFAULT_DAYS)
+    }
+
+    /// [`parse`](Self::parse), covering `days` days when `from` is not given.
+    pub fn parse_or_last(from: Option<&str>, to: Option<&str>, days: u64) -> Result<Self> {
+        let date = |flag: &str, text: &str| {
+            NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").map_err(|_| {
+                Error::InvalidConfig(format!("--{flag} '{text}': expected YYYY-MM-DD"))
+            })
+        };
+        let to = match to {
+            Some(text) => date("to", text)?,
+            None => Local::now().date_naive(),
+        };
+        let from = match from {
+            Some(text) => date("from", text)?,
+            None => to - Days::new(days.max(1) - 1),
+        };
+        if from > to {
+            return Err(Error::InvalidConfig(format!(
+                "--from {from} is after --to {to}"
+            )));
+        }
+        Ok(Self { from, to })
+    }
+
+    /// The [`DEFAULT_DAYS`] days up to today.
+    pub fn recent() -> Self {
+        Self::last(DEFAULT_DAYS)
+    }
+
+    /// The `days` days up to today.
+    pub fn last(days: u64) -> Self {
+        let to = Local::now().date_naive();
+        Self {
+            from: to - Days::new(days.max(1) - 1),
+            to,
+        }
+    }
+
+    pub fn contains(self, date: NaiveDate) -> bool {
+        (self.from..=self.to).contains(&date)
+    }
+
+    pub fn days(self) -> impl Iterator<Item = NaiveDate> {
+        self.from.iter_days().take_while(move |day| *day <= self.to)
+    }
+}
+
+/// One day's numbers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DayStats {
+    pub date: NaiveDate,
+    /// Sessions committed.
+    pub captured: usize,
+    /// Listing jobs completed, drafts included.
+    pub listed: usize,
+    /// Completed listing jobs that published.
+    pub published: usize,
+    /// Listing jobs that failed, retried or not.
+    pub failed: usize,
+    /// Mean cycle time of the listings published that day.
+    pub avg_cycle_secs: Option<u64>,
+}
+
+/// Failed listing jobs of one stage, out of every job with an outcome.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StageFailures {
+    pub stage: String,
+    pub failed: usize,
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub range: DayRange,
+    pub days: Vec<DayStats>,
+    pub captured: usize,
+    pub listed: usize,
+    pub published: usize,
+    pub failed: usize,
+    pub avg_cycle_secs: Option<u64>,
+    /// Most failures first.
+    pub failures_by_stage: Vec<StageFailures>,
+}
+
+/// Stats for `range`. `sku_products` maps SKUs to product ids, for ledger
+/// entries recorded without a product (the CLI's).
+pub fn compute(
+    range: DayRange,
+    sessions: &[SessionSample],
+    jobs: &[JobLedgerEntry],
+    sku_products: &HashMap<String, String>,
+) -> Stats {
+    let mut days: BTreeMap<NaiveDate, DayStats> = range
+        .days()
+        .map(|date| {
+            (
+                date,
+                DayStats {
+                    date,
+                    ..DayStats::default()
+                },
+            )
+        })
+        .collect();
+
+    let mut first_session: HashMap<&str, DateTime<Utc>> = HashMap::new();
+    for session in sessions {
+        first_session
+            .entry(session.product_id.as_str())
+            .and_modify(|at| *at = (*at).min(session.created_at))
+            .or_insert(session.created_at);
+        if let Some(day) = session
+            .committed_at
+            .and_then(|at| days.get_mut(&local_day(at)))
+        {
+            day.captured += 1;
+        }
+    }
+
+    let mut cycles: BTreeMap<NaiveDate, Vec<u64>> = BTreeMap::new();
+    let mut stages: BTreeMap<String, usize> = BTreeMap::new();
+    let mut settled = 0;
+    for job in jobs {
+        let Some(at) = job.settled_at else {
+            continue;
+        };
+        let Some(day) = days.get_mut(&local_day(at)) else {
+            continue;
+        };
+        settled += 1;
+        match job.state {
+            TrackedState::Completed => {
+                day.listed += 1;
+                let published = job
+                    .request
+                    .as_ref()
+                    .is_some_and(|req| req.publish == Some(true));
+                if !published {
+                    continue;
+                }
+                day.published += 1;
+                let product = job.product_id.as_deref().or_else(|| {
+                    job.sku
+                        .as_ref()
+                        .and_then(|sku| sku_products.get(sku))
+                        .map(String::as_str)
+                });
+                if let Some(started) = product.and_then(|product| first_session.get(product))
+                    && let Ok(cycle) = (at - *started).to_std()
+                {
+                    cycles.entry(day.date).or_default().push(cycle.as_secs());
+                }
+            }
+            TrackedState::Failed | TrackedState::Retried | TrackedState::Dismissed => {
+                day.failed += 1;
+                *stages.entry(failed_stage(job)).or_default() += 1;
+            }
+            TrackedState::Pending => {}
+        }
+    }
+
+    for (date, secs) in &cycles {
+        if let Some(day) = days.get_mut(date) {
+            day.avg_cycle_secs = mean(secs);
+        }
+    }
+    let days: Vec<DayStats> = days.into_values().collect();
+    let mut failures_by_stage: Vec<StageFailures> = stages
+        .into_iter()
+        .map(|(stage, failed)| StageFailures {
+            stage,
+            failed,
+            rate: failed as f64 / settled as f64,
+        })
+        .collect();
+    failures_by_stage.sort_by_key(|stage| std::cmp::Reverse(stage.failed));
+    Stats {
+        range,
+        captured: days.iter().map(|day| day.captured).sum(),
+        listed: days.iter().map(|day| day.listed).sum(),
+        published: days.iter().map(|day| day.published).sum(),
+        failed: days.iter().map(|day| day.failed).sum(),
+        avg_cycle_secs: mean(&cycles.into_values().flatten().collect::<Vec<_>>()),
+        days,
+        failures_by_stage,
+    }
+}
+
+/// The stage named before the first `:` of the job's error.
+fn failed_stage(job: &JobLedgerEntry) -> String {
+    job.error
+        .as_deref()
+        .and_then(|error| error.split_once(':'))
+        .map(|(stage, _)| stage.trim())
+        .filter(|stage| !stage.is_empty() && !stage.contains(' '))
+        .unwrap_or(UNKNOWN_STAGE)
+        .to_string()
+}
+
+fn local_day(at: DateTime<Utc>) -> NaiveDate {
+    at.with_timezone(&Local).date_naive()
+}
+
+fn mean(values: &[u64]) -> Option<u64> {
+    (!values.is_empty()).then(|| values.iter().sum::<u64>() / values.len() as u64)
+}
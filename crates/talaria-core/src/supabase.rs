@@ -1,5 +1,11 @@
-use crate::config::SupabaseConfig;
+use crate::config::{
+    Config, DEFAULT_SUPABASE_BUCKET, DEFAULT_SUPABASE_SIGNED_URL_TTL_SECS,
+    DEFAULT_SUPABASE_UPLOAD_PREFIX, SupabaseConfig,
+};
 use crate::error::{Error, Result};
+use crate::metrics;
+use crate::models::{ProductCreateRequest, ProductRecord, ProductUpdateRequest};
+use crate::simulate::{self, SIMULATED_MEDIA_BASE};
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
 use chrono::{DateTime, Utc};
 use mime_guess::MimeGuess;
@@ -8,7 +14,8 @@ use reqwest::{Client, Url};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Clone)]
 pub struct SupabaseClient {
@@ -18,6 +25,36 @@ pub struct SupabaseClient {
     bucket: String,
     service_role_key: String,
     upload_prefix: String,
+    use_signed_urls: bool,
+    simulated: bool,
+}
+
+/// An object stored in the configured bucket, as returned by [`SupabaseClient::list_objects`].
+#[derive(Debug, Clone, Serialize)]
+pub struct SupabaseObject {
+    /// Path relative to the bucket root.
+    pub path: String,
+    pub size: Option<u64>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StorageListEntry {
+    name: String,
+    /// `null` for folder placeholders.
+    id: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    metadata: Option<serde_json::Value>,
+}
+
+const LIST_PAGE_SIZE: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+struct SignedUrlResponse {
+    #[serde(rename = "signedURL", alias = "signedUrl")]
+    signed_url: String,
 }
 
 impl SupabaseClient {
@@ -43,7 +80,9 @@ impl SupabaseClient {
             ));
         };
 
-        let http = Client::builder()
+        let http = config
+            .network
+            .client_builder()?
             .user_agent("talaria-supabase/0.1")
             .build()
             .map_err(|err| {
@@ -57,9 +96,38 @@ impl SupabaseClient {
             bucket: config.bucket.clone(),
             service_role_key: key,
             upload_prefix: config.upload_prefix.clone(),
+            use_signed_urls: config.use_signed_urls,
+            simulated: false,
         })
     }
 
+    /// Client for simulation mode: nothing is sent, uploads return
+    /// deterministic public URLs on [`SIMULATED_MEDIA_BASE`]. Bucket and
+    /// prefix come from `config` when present.
+    pub fn simulated(config: Option<&SupabaseConfig>) -> Self {
+        let base_url: Url = SIMULATED_MEDIA_BASE
+            .parse()
+            .expect("valid simulated base url");
+        Self {
+            http: Client::new(),
+            public_base: base_url.clone(),
+            base_url,
+            bucket: config
+                .map(|c| c.bucket.clone())
+                .unwrap_or_else(|| DEFAULT_SUPABASE_BUCKET.to_string()),
+            service_role_key: String::new(),
+            upload_prefix: config
+                .map(|c| c.upload_prefix.clone())
+                .unwrap_or_else(|| DEFAULT_SUPABASE_UPLOAD_PREFIX.to_string()),
+            use_signed_urls: false,
+            simulated: true,
+        }
+    }
+
+    pub fn is_simulated(&self) -> bool {
+        self.simulated
+    }
+
     pub fn bucket(&self) -> &str {
         &self.bucket
     }
@@ -76,6 +144,10 @@ impl SupabaseClient {
         clone
     }
 
+    pub fn use_signed_urls(&self) -> bool {
+        self.use_signed_urls
+    }
+
     pub async fn upload_image_file(&self, path: &Path) -> Result<String> {
         let data = fs::read(path).map_err(|err| {
             Error::MissingSupabaseConfig(format!("read error {}: {err}", path.display()))
@@ -89,12 +161,20 @@ impl SupabaseClient {
     }
 
     pub async fn upload_image_bytes(&self, filename_hint: &str, bytes: Vec<u8>) -> Result<String> {
-        let object_path = format!(
-            "{}/{}-{}",
-            self.upload_prefix.trim_end_matches('/'),
-            timestamp_ms(),
-            sanitize_filename(filename_hint)
-        );
+        let object_path = self.upload_object(filename_hint, bytes).await?;
+        self.object_url(&object_path).await
+    }
+
+    /// Stores `bytes` under the upload prefix and returns the new object path.
+    pub async fn upload_object(&self, filename_hint: &str, bytes: Vec<u8>) -> Result<String> {
+        if self.simulated {
+            return Ok(simulate::object_key(
+                &self.upload_prefix,
+                &sanitize_filename(filename_hint),
+                &bytes,
+            ));
+        }
+        let object_path = new_object_path(&self.upload_prefix, filename_hint);
         let url = self
             .base_url
             .join(&format!(
@@ -106,18 +186,9 @@ impl SupabaseClient {
         let mime = MimeGuess::from_path(filename_hint)
             .first_raw()
             .unwrap_or("application/octet-stream");
+        let len = bytes.len() as u64;
 
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", self.service_role_key))
-                .map_err(|_| Error::InvalidConfig("invalid supabase key".into()))?,
-        );
-        headers.insert(
-            "apikey",
-            HeaderValue::from_str(&self.service_role_key)
-                .map_err(|_| Error::InvalidConfig("invalid supabase key".into()))?,
-        );
+        let mut headers = self.auth_headers()?;
         headers.insert(
             CONTENT_TYPE,
             HeaderValue::from_str(mime)
@@ -142,8 +213,205 @@ impl SupabaseClient {
                 message: snippet,
             });
         }
+        metrics::global().incr(metrics::UPLOAD_BYTES, &[("target", "supabase")], len);
+        Ok(object_path)
+    }
+
+    /// URL handed to listings for an existing object: signed when
+    /// `use_signed_urls` is set, public otherwise.
+    pub async fn object_url(&self, object_path: &str) -> Result<String> {
+        if self.use_signed_urls {
+            return self
+                .create_signed_url(
+                    object_path,
+                    Duration::from_secs(DEFAULT_SUPABASE_SIGNED_URL_TTL_SECS),
+                )
+                .await;
+        }
+        Ok(self.public_url(object_path))
+    }
+
+    /// Identifies the storage target (project + bucket) for the upload cache.
+    pub fn cache_scope(&self) -> String {
+        format!(
+            "{}/{}",
+            self.base_url.as_str().trim_end_matches('/'),
+            self.bucket
+        )
+    }
+
+    /// Signs `object_path` (relative to the bucket) for `ttl` via the storage
+    /// sign endpoint. Works for private buckets, unlike [`Self::public_url`].
+    pub async fn create_signed_url(&self, object_path: &str, ttl: Duration) -> Result<String> {
+        if self.simulated {
+            return Ok(format!("{}?token=simulated", self.public_url(object_path)));
+        }
+        let url = self
+            .base_url
+            .join(&format!(
+                "storage/v1/object/sign/{}/{}",
+                self.bucket,
+                object_path.trim_start_matches('/')
+            ))
+            .map_err(|err| Error::InvalidConfig(format!("invalid supabase sign url: {err}")))?;
+        let resp = self
+            .http
+            .post(url)
+            .headers(self.auth_headers()?)
+            .json(&serde_json::json!({ "expiresIn": ttl.as_secs().max(1) }))
+            .send()
+            .await
+            .map_err(Error::Http)?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            let snippet = body.chars().take(200).collect::<String>();
+            return Err(Error::SupabaseUpload {
+                status,
+                message: snippet,
+            });
+        }
+        let signed: SignedUrlResponse = resp.json().await.map_err(Error::Http)?;
+        Ok(self.signed_url(&signed.signed_url))
+    }
+
+    /// Absolute URL for the `signedURL` path returned by the sign endpoint
+    /// (e.g. `/object/sign/<bucket>/<path>?token=...`).
+    pub fn signed_url(&self, signed_path: &str) -> String {
+        format!(
+            "{}/storage/v1/{}",
+            self.base_url.as_str().trim_end_matches('/'),
+            signed_path.trim_start_matches('/')
+        )
+    }
+
+    /// Lists every object under `prefix` (a folder path relative to the
+    /// bucket), descending into sub-folders.
+    pub async fn list_objects(&self, prefix: &str) -> Result<Vec<SupabaseObject>> {
+        if self.simulated {
+            return Ok(Vec::new());
+        }
+        let url = self
+            .base_url
+            .join(&format!("storage/v1/object/list/{}", self.bucket))
+            .map_err(|err| Error::InvalidConfig(format!("invalid supabase list url: {err}")))?;
+        let mut out = Vec::new();
+        let mut folders = vec![prefix.trim_matches('/').to_string()];
+        while let Some(folder) = folders.pop() {
+            let mut offset = 0usize;
+            loop {
+                let resp = self
+                    .http
+                    .post(url.clone())
+                    .headers(self.auth_headers()?)
+                    .json(&serde_json::json!({
+                        "prefix": folder,
+                        "limit": LIST_PAGE_SIZE,
+                        "offset": offset,
+                        "sortBy": { "column": "name", "order": "asc" },
+                    }))
+                    .send()
+                    .await
+                    .map_err(Error::Http)?;
+                let entries: Vec<StorageListEntry> = self.parse_storage_json(resp).await?;
+                let page_len = entries.len();
+                for entry in entries {
+                    let path = if folder.is_empty() {
+                        entry.name.clone()
+                    } else {
+                        format!("{folder}/{}", entry.name)
+                    };
+                    if entry.id.is_none() {
+                        folders.push(path);
+                        continue;
+                    }
+                    out.push(SupabaseObject {
+                        path,
+                        size: entry
+                            .metadata
+                            .as_ref()
+                            .and_then(|m| m.get("size"))
+                            .and_then(|v| v.as_u64()),
+                        created_at: entry.created_at,
+                        updated_at: entry.updated_at,
+                    });
+                }
+                if page_len < LIST_PAGE_SIZE {
+                    break;
+                }
+                offset += page_len;
+            }
+        }
+        out.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(out)
+    }
+
+    /// Deletes a single object (path relative to the bucket).
+    pub async fn delete_object(&self, object_path: &str) -> Result<()> {
+        if self.simulated {
+            return Ok(());
+        }
+        let url = self
+            .base_url
+            .join(&format!(
+                "storage/v1/object/{}/{}",
+                self.bucket,
+                object_path.trim_start_matches('/')
+            ))
+            .map_err(|err| Error::InvalidConfig(format!("invalid supabase object url: {err}")))?;
+        let resp = self
+            .http
+            .delete(url)
+            .headers(self.auth_headers()?)
+            .send()
+            .await
+            .map_err(Error::Http)?;
+        let _: serde_json::Value = self.parse_storage_json(resp).await?;
+        Ok(())
+    }
+
+    /// Maps a public or signed URL for this bucket back to its object path.
+    pub fn object_path_from_url(&self, url: &str) -> Option<String> {
+        let url = url.split(['?', '#']).next().unwrap_or(url);
+        ["public", "sign", "authenticated"]
+            .iter()
+            .find_map(|kind| {
+                let marker = format!("/storage/v1/object/{kind}/{}/", self.bucket);
+                url.find(&marker).map(|idx| &url[idx + marker.len()..])
+            })
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+    }
+
+    async fn parse_storage_json<T: for<'de> Deserialize<'de>>(
+        &self,
+        resp: reqwest::Response,
+    ) -> Result<T> {
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            let snippet = body.chars().take(200).collect::<String>();
+            return Err(Error::SupabaseUpload {
+                status,
+                message: snippet,
+            });
+        }
+        resp.json().await.map_err(Error::Http)
+    }
 
-        Ok(self.public_url(&object_path))
+    fn auth_headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", self.service_role_key))
+                .map_err(|_| Error::InvalidConfig("invalid supabase key".into()))?,
+        );
+        headers.insert(
+            "apikey",
+            HeaderValue::from_str(&self.service_role_key)
+                .map_err(|_| Error::InvalidConfig("invalid supabase key".into()))?,
+        );
+        Ok(headers)
     }
 
     pub fn public_url(&self, object_path: &str) -> String {
@@ -194,6 +462,7 @@ pub struct SupabaseProductInsert {
 #[serde_with::skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct SupabaseProductUpdate {
+    pub sku_alias: Option<String>,
     pub display_name: Option<String>,
     pub context_text: Option<String>,
     pub structure_json: Option<serde_json::Value>,
@@ -229,7 +498,9 @@ impl SupabaseDbClient {
             ));
         };
 
-        let http = Client::builder()
+        let http = config
+            .network
+            .client_builder()?
             .user_agent("talaria-supabase-db/0.1")
             .build()
             .map_err(|err| {
@@ -297,6 +568,24 @@ impl SupabaseDbClient {
         &self,
         org_id: &str,
         product_id: &str,
+    ) -> Result<Option<SupabaseProductRow>> {
+        self.fetch_product_where(org_id, "id", product_id).await
+    }
+
+    pub async fn fetch_product_by_sku(
+        &self,
+        org_id: &str,
+        sku_alias: &str,
+    ) -> Result<Option<SupabaseProductRow>> {
+        self.fetch_product_where(org_id, "sku_alias", sku_alias)
+            .await
+    }
+
+    async fn fetch_product_where(
+        &self,
+        org_id: &str,
+        column: &str,
+        value: &str,
     ) -> Result<Option<SupabaseProductRow>> {
         let mut url = self.rest_url("products")?;
         {
@@ -306,7 +595,7 @@ impl SupabaseDbClient {
                 "id,org_id,sku_alias,display_name,context_text,structure_json,listings_json,created_at,updated_at",
             );
             q.append_pair("org_id", &format!("eq.{org_id}"));
-            q.append_pair("id", &format!("eq.{product_id}"));
+            q.append_pair(column, &format!("eq.{value}"));
             q.append_pair("limit", "1");
         }
         let mut rows: Vec<SupabaseProductRow> = self.get_json(url).await?;
@@ -330,6 +619,25 @@ impl SupabaseDbClient {
         self.parse_single_row(resp).await
     }
 
+    /// Inserts `row`, or overwrites the row with its id.
+    pub async fn upsert_product(&self, row: &SupabaseProductInsert) -> Result<SupabaseProductRow> {
+        let mut url = self.rest_url("products")?;
+        url.query_pairs_mut().append_pair("on_conflict", "id");
+        let resp = self
+            .http
+            .post(url)
+            .headers(self.auth_headers())
+            .header(
+                "Prefer",
+                "resolution=merge-duplicates,return=representation",
+            )
+            .json(row)
+            .send()
+            .await
+            .map_err(Error::Http)?;
+        self.parse_single_row(resp).await
+    }
+
     pub async fn update_product(
         &self,
         org_id: &str,
@@ -450,6 +758,151 @@ impl SupabaseDbClient {
     }
 }
 
+impl From<SupabaseProductRow> for ProductRecord {
+    fn from(row: SupabaseProductRow) -> Self {
+        Self {
+            id: row.id,
+            sku_alias: row.sku_alias,
+            display_name: row.display_name,
+            context_text: row.context_text,
+            structure_json: row.structure_json,
+            listings_json: row
+                .listings_json
+                .unwrap_or_else(|| serde_json::Value::Object(Default::default())),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// The `products` table standing in for the Hermes products API, for
+/// [`ProductBackend::Supabase`](crate::config::ProductBackend::Supabase).
+/// Rows belong to the configured organization, or else to the one the
+/// Hermes API key was issued for, looked up once in `api_keys`.
+#[derive(Clone)]
+pub struct SupabaseProducts {
+    db: SupabaseDbClient,
+    org_id: Option<String>,
+    api_key: Option<String>,
+    key_org: Arc<OnceLock<String>>,
+}
+
+impl SupabaseProducts {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let supabase = config.supabase.as_ref().ok_or_else(|| {
+            Error::MissingSupabaseConfig("product_backend = \"supabase\" needs SUPABASE_URL".into())
+        })?;
+        Ok(Self {
+            db: SupabaseDbClient::from_config(supabase)?,
+            org_id: config.org_id.clone(),
+            api_key: config.api_key.clone(),
+            key_org: Arc::default(),
+        })
+    }
+
+    /// Acts for `org_id` instead of the configured organization (`None`:
+    /// the API key's).
+    pub fn with_org(mut self, org_id: Option<String>) -> Self {
+        self.org_id = org_id;
+        self
+    }
+
+    async fn org_id(&self) -> Result<String> {
+        if let Some(org_id) = &self.org_id {
+            return Ok(org_id.clone());
+        }
+        if let Some(org_id) = self.key_org.get() {
+            return Ok(org_id.clone());
+        }
+        let Some(api_key) = &self.api_key else {
+            return Err(Error::InvalidConfig(
+                "product_backend = \"supabase\" needs org_id or a Hermes API key".into(),
+            ));
+        };
+        let context = self
+            .db
+            .resolve_api_key_context(api_key)
+            .await?
+            .ok_or_else(|| {
+                Error::InvalidConfig("the Hermes API key is not in Supabase api_keys".into())
+            })?;
+        Ok(self.key_org.get_or_init(|| context.org_id).clone())
+    }
+
+    pub async fn list_products(&self) -> Result<Vec<ProductRecord>> {
+        let rows = self.db.list_products(&self.org_id().await?).await?;
+        Ok(rows.into_iter().map(ProductRecord::from).collect())
+    }
+
+    /// By id, or by SKU when no product has that id.
+    pub async fn get_product(&self, id_or_sku: &str) -> Result<ProductRecord> {
+        let org_id = self.org_id().await?;
+        let row = match self.db.fetch_product(&org_id, id_or_sku).await? {
+            Some(row) => Some(row),
+            None => self.db.fetch_product_by_sku(&org_id, id_or_sku).await?,
+        };
+        row.map(ProductRecord::from)
+            .ok_or_else(|| Error::SupabaseDb {
+                status: reqwest::StatusCode::NOT_FOUND,
+                message: format!("product {id_or_sku} not found"),
+            })
+    }
+
+    /// Creating a product that already exists returns it unchanged, so a
+    /// product can be pushed again without losing its listings.
+    pub async fn create_product(&self, body: &ProductCreateRequest) -> Result<ProductRecord> {
+        let org_id = self.org_id().await?;
+        if let Some(id) = &body.id
+            && let Some(row) = self.db.fetch_product(&org_id, id).await?
+        {
+            return Ok(row.into());
+        }
+        let id = body
+            .id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let now = Utc::now();
+        let row = SupabaseProductInsert {
+            sku_alias: body.sku_alias.clone().unwrap_or_else(|| id.clone()),
+            id,
+            org_id,
+            display_name: body.display_name.clone(),
+            context_text: None,
+            structure_json: None,
+            listings_json: serde_json::Value::Object(Default::default()),
+            created_at: now,
+            updated_at: now,
+        };
+        Ok(self.db.upsert_product(&row).await?.into())
+    }
+
+    pub async fn update_product(
+        &self,
+        product_id: &str,
+        body: &ProductUpdateRequest,
+    ) -> Result<ProductRecord> {
+        let update = SupabaseProductUpdate {
+            sku_alias: body.sku_alias.clone(),
+            display_name: body.display_name.clone(),
+            context_text: body.context_text.clone(),
+            structure_json: body.structure_json.clone(),
+            listings_json: body.listings_json.clone(),
+            updated_at: Some(Utc::now()),
+        };
+        let row = self
+            .db
+            .update_product(&self.org_id().await?, product_id, &update)
+            .await?;
+        Ok(row.into())
+    }
+
+    pub async fn delete_product(&self, product_id: &str) -> Result<()> {
+        self.db
+            .delete_product(&self.org_id().await?, product_id)
+            .await
+    }
+}
+
 fn derive_prefix(presented: &str) -> Option<String> {
     if let Some(idx) = presented.find('_') {
         let parts: Vec<&str> = presented.split('_').collect();
@@ -461,6 +914,17 @@ fn derive_prefix(presented: &str) -> Option<String> {
     Some(presented.chars().take(8).collect::<String>())
 }
 
+/// Where a fresh upload of `filename_hint` goes: under `prefix`, named
+/// apart by the upload time.
+pub(crate) fn new_object_path(prefix: &str, filename_hint: &str) -> String {
+    format!(
+        "{}/{}-{}",
+        prefix.trim_end_matches('/'),
+        timestamp_ms(),
+        sanitize_filename(filename_hint)
+    )
+}
+
 fn sanitize_filename(name: &str) -> String {
     let clean = name
         .chars()
@@ -0,0 +1,152 @@
+//! Named, partial listing requests kept as TOML files under
+//! `~/.config/talaria/templates/<name>.toml`: policy ids, marketplace, LLM
+//! options and prompt rules a team would otherwise repeat as flags on every
+//! command. Copying a file shares the template.
+
+use crate::config::{Config, config_path};
+use crate::error::{Error, Result};
+use crate::models::{LlmStageOptions, MarketplaceId};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fields a template may set. Flags given with a template win over it, and
+/// fields it leaves unset come from the config as usual.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestTemplate {
+    pub marketplace: Option<MarketplaceId>,
+    pub merchant_location_key: Option<String>,
+    pub fulfillment_policy_id: Option<String>,
+    pub payment_policy_id: Option<String>,
+    pub return_policy_id: Option<String>,
+    pub llm_ingest: Option<LlmStageOptions>,
+    pub llm_aspects: Option<LlmStageOptions>,
+    /// Extra instructions for enrichment, as `prompt_rules` in the config.
+    pub prompt_rules: Option<String>,
+}
+
+impl RequestTemplate {
+    /// Makes the template's fields the config defaults, leaving the rest of
+    /// `config` as it was.
+    pub fn apply_to(&self, config: &mut Config) {
+        let ebay = &mut config.ebay;
+        if let Some(marketplace) = &self.marketplace {
+            ebay.marketplace = Some(marketplace.to_string());
+        }
+        for (value, setting) in [
+            (&self.merchant_location_key, &mut ebay.merchant_location_key),
+            (&self.fulfillment_policy_id, &mut ebay.fulfillment_policy_id),
+            (&self.payment_policy_id, &mut ebay.payment_policy_id),
+            (&self.return_policy_id, &mut ebay.return_policy_id),
+        ] {
+            if value.is_some() {
+                setting.clone_from(value);
+            }
+        }
+        if self.llm_ingest.is_some() {
+            config.llm_ingest.clone_from(&self.llm_ingest);
+        }
+        if self.llm_aspects.is_some() {
+            config.llm_aspects.clone_from(&self.llm_aspects);
+        }
+        if self.prompt_rules.is_some() {
+            config.prompt_rules.clone_from(&self.prompt_rules);
+        }
+    }
+}
+
+/// The templates directory.
+#[derive(Debug, Clone)]
+pub struct TemplateStore {
+    dir: PathBuf,
+}
+
+impl TemplateStore {
+    /// Opens `templates/` next to the config file.
+    pub fn open_default() -> Result<Self> {
+        let dir = config_path()
+            .and_then(|path| path.parent().map(|dir| dir.join("templates")))
+            .ok_or_else(|| Error::InvalidConfig("unable to determine config directory".into()))?;
+        Ok(Self::open(dir))
+    }
+
+    pub fn open(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.toml"))
+    }
+
+    /// Writes `template` under `name`, replacing any template of that name.
+    pub fn save(&self, name: &str, template: &RequestTemplate) -> Result<PathBuf> {
+        validate_name(name)?;
+        fs::create_dir_all(&self.dir)?;
+        let text = toml::to_string_pretty(template)
+            .map_err(|err| Error::InvalidConfig(format!("template {name}: {err}")))?;
+        let path = self.path(name);
+        let tmp = path.with_extension("toml.tmp");
+        fs::write(&tmp, text)?;
+        fs::rename(&tmp, &path)?;
+        Ok(path)
+    }
+
+    pub fn load(&self, name: &str) -> Result<RequestTemplate> {
+        validate_name(name)?;
+        let text = match fs::read_to_string(self.path(name)) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::InvalidConfig(format!(
+                    "no template named '{name}'; see `talaria templates list`"
+                )));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        toml::from_str(&text).map_err(|err| Error::InvalidConfig(format!("template {name}: {err}")))
+    }
+
+    /// Every template by name, sorted. Files that do not parse are skipped
+    /// with a warning.
+    pub fn list(&self) -> Result<Vec<(String, RequestTemplate)>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut out = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "toml") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            match self.load(name) {
+                Ok(template) => out.push((name.to_string(), template)),
+                Err(err) => tracing::warn!(template = name, error = %err, "template skipped"),
+            }
+        }
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(out)
+    }
+}
+
+/// Names are file stems: letters, digits, `-` and `_`.
+pub fn validate_name(name: &str) -> Result<()> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidConfig(format!(
+            "template name '{name}' may only use letters, digits, '-' and '_'"
+        )))
+    }
+}
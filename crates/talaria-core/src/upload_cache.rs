@@ -0,0 +1,104 @@
+//! Local content-hash map of uploaded images.
+//!
+//! Each upload is keyed by the sha256 of its bytes within a storage scope
+//! (Supabase project + bucket), so re-uploading the same file reuses the
+//! object that is already in the bucket instead of storing a second copy.
+
+use crate::config::data_dir;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CACHE_FILE: &str = "uploads.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadCacheEntry {
+    pub object_path: String,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// File-backed map of `(scope, sha256)` to the uploaded object path.
+#[derive(Debug)]
+pub struct UploadCache {
+    path: PathBuf,
+    entries: BTreeMap<String, UploadCacheEntry>,
+}
+
+impl UploadCache {
+    /// Opens the cache under the local data dir (`~/.local/share/talaria`).
+    pub fn open_default() -> Result<Self> {
+        let dir = data_dir().ok_or_else(|| {
+            Error::InvalidConfig("unable to determine local data directory".into())
+        })?;
+        Self::open(dir.join(CACHE_FILE))
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn get(&self, scope: &str, sha256: &str) -> Option<&UploadCacheEntry> {
+        self.entries.get(&cache_key(scope, sha256))
+    }
+
+    pub fn insert(&mut self, scope: &str, sha256: &str, object_path: &str) -> Result<()> {
+        self.entries.insert(
+            cache_key(scope, sha256),
+            UploadCacheEntry {
+                object_path: object_path.to_string(),
+                uploaded_at: Utc::now(),
+            },
+        );
+        self.save()
+    }
+
+    /// Drops every entry pointing at `object_path`, e.g. after the object was deleted.
+    pub fn forget_object(&mut self, scope: &str, object_path: &str) -> Result<bool> {
+        let prefix = format!("{scope}#");
+        let before = self.entries.len();
+        self.entries
+            .retain(|key, entry| !(key.starts_with(&prefix) && entry.object_path == object_path));
+        let removed = self.entries.len() != before;
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&self.entries)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Lowercase hex sha256 of `bytes`, as sent in `CreateUploadRequest.sha256`.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn cache_key(scope: &str, sha256: &str) -> String {
+    format!("{scope}#{sha256}")
+}
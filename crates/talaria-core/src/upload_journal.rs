@@ -0,0 +1,151 @@
+//! Local journal of Hermes media upload sessions between
+//! `create_media_upload` and `complete_media_upload` / `abort_media_upload`.
+//!
+//! A session whose process died in between stays open on Hermes. Entries
+//! are written before the bytes go out and dropped once the session is
+//! finished, so what is left is what leaked; [`abort_stale`] aborts those
+//! past their expiry (on TUI startup and from `talaria media abort-stale`).
+
+use crate::client::HermesClient;
+use crate::config::data_dir;
+use crate::error::{Error, Result};
+use crate::models::{CreateUploadRequest, UploadSession};
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const JOURNAL_FILE: &str = "pending_uploads.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingUpload {
+    pub upload_id: String,
+    pub filename: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// File-backed map of upload id to the session still open for it.
+///
+/// Every change re-reads the file first, so the TUI's upload worker and a
+/// cleanup run elsewhere do not drop each other's entries.
+#[derive(Debug, Clone)]
+pub struct UploadJournal {
+    path: PathBuf,
+}
+
+impl UploadJournal {
+    /// Opens the journal under the local data dir (`~/.local/share/talaria`).
+    pub fn open_default() -> Result<Self> {
+        let dir = data_dir().ok_or_else(|| {
+            Error::InvalidConfig("unable to determine local data directory".into())
+        })?;
+        Ok(Self::open(dir.join(JOURNAL_FILE)))
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Notes a session `create_media_upload` just returned.
+    pub fn record(&self, session: &UploadSession, request: &CreateUploadRequest) -> Result<()> {
+        let entry = PendingUpload {
+            upload_id: session.upload_id.clone(),
+            filename: request.filename.clone(),
+            product_id: request.product_id.clone(),
+            started_at: Utc::now(),
+            expires_at: session.expires_at,
+        };
+        self.update(|entries| {
+            entries.insert(entry.upload_id.clone(), entry);
+        })
+    }
+
+    /// Drops a session once it was completed or aborted.
+    pub fn finish(&self, upload_id: &str) -> Result<()> {
+        self.update(|entries| {
+            entries.remove(upload_id);
+        })
+    }
+
+    /// Every session still open, oldest first.
+    pub fn pending(&self) -> Result<Vec<PendingUpload>> {
+        let mut entries: Vec<_> = self.load()?.into_values().collect();
+        entries.sort_by_key(|entry| entry.started_at);
+        Ok(entries)
+    }
+
+    /// Sessions that expired by `now`.
+    pub fn stale(&self, now: DateTime<Utc>) -> Result<Vec<PendingUpload>> {
+        Ok(self
+            .pending()?
+            .into_iter()
+            .filter(|entry| entry.expires_at <= now)
+            .collect())
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, PendingUpload>> {
+        match fs::read(&self.path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut BTreeMap<String, PendingUpload>)) -> Result<()> {
+        let mut entries = self.load()?;
+        change(&mut entries);
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&entries)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// What [`abort_stale`] did with one expired session.
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleAbort {
+    #[serde(flatten)]
+    pub upload: PendingUpload,
+    /// `false` when Hermes no longer knew the session.
+    pub aborted: bool,
+    /// Why the abort failed; the session stays in the journal for next time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aborts every journaled session that expired by `now` and drops it from the
+/// journal. A session Hermes answers 404 for is gone already and is dropped
+/// too; other failures keep it for the next run.
+pub async fn abort_stale(
+    client: &HermesClient,
+    journal: &UploadJournal,
+    now: DateTime<Utc>,
+) -> Result<Vec<StaleAbort>> {
+    let mut out = Vec::new();
+    for upload in journal.stale(now)? {
+        let (aborted, error) = match client.abort_media_upload(&upload.upload_id).await {
+            Ok(()) => (true, None),
+            Err(Error::Api { status, .. }) if status == StatusCode::NOT_FOUND => (false, None),
+            Err(err) => (false, Some(err.to_string())),
+        };
+        if error.is_none() {
+            journal.finish(&upload.upload_id)?;
+        }
+        out.push(StaleAbort {
+            upload,
+            aborted,
+            error,
+        });
+    }
+    Ok(out)
+}
@@ -0,0 +1,168 @@
+//! Local history of `v1/usage` snapshots, one per organization and day.
+//!
+//! Hermes reports usage for its current billing window only, so each fetch
+//! keeps the day's latest snapshot in `~/.local/share/talaria`: the TUI polls
+//! once a minute, and `talaria usage list` and `talaria credits` record what
+//! they fetch. A day's consumption is how far the window's counters grew
+//! since the previous snapshot.
+
+use crate::config::data_dir;
+use crate::error::{Error, Result};
+use crate::models::UsageSummary;
+use crate::stats::DayRange;
+use chrono::{DateTime, Days, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HISTORY_FILE: &str = "usage-history.json";
+
+/// Snapshots older than this many days are dropped on the next record.
+pub const KEEP_DAYS: u64 = 400;
+
+/// Days the TUI's credits burn-down covers.
+pub const BURN_DOWN_DAYS: u64 = 30;
+
+/// The last usage report of one organization on one day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub credits_consumed: i64,
+    pub jobs_enqueued: i64,
+    pub listings_run: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credit_balance_cents: Option<i64>,
+    /// Start of the billing window the counters cover; a new one resets them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_from: Option<DateTime<Utc>>,
+}
+
+impl UsageSnapshot {
+    pub fn new(summary: &UsageSummary, taken_at: DateTime<Utc>) -> Self {
+        Self {
+            taken_at,
+            credits_consumed: summary.counters.credits_consumed,
+            jobs_enqueued: summary.counters.jobs_enqueued,
+            listings_run: summary.counters.listings_run,
+            credit_balance_cents: summary.tiered.as_ref().map(|t| t.credit_balance_cents),
+            window_from: summary.window_from,
+        }
+    }
+}
+
+/// Snapshots by organization, then local day.
+pub type Snapshots = BTreeMap<String, BTreeMap<NaiveDate, UsageSnapshot>>;
+
+/// One organization's consumption on one day.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageDay {
+    pub org_id: String,
+    pub date: NaiveDate,
+    pub credits_consumed: i64,
+    pub jobs_enqueued: i64,
+    pub listings_run: i64,
+    /// Balance at the day's last snapshot.
+    pub credit_balance_cents: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UsageHistory {
+    path: PathBuf,
+}
+
+impl UsageHistory {
+    /// Opens the history under the local data dir (`~/.local/share/talaria`).
+    pub fn open_default() -> Result<Self> {
+        let dir = data_dir().ok_or_else(|| {
+            Error::InvalidConfig("unable to determine local data directory".into())
+        })?;
+        Ok(Self::open(dir.join(HISTORY_FILE)))
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> Result<Snapshots> {
+        match fs::read(&self.path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Snapshots::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Keeps `usage` as its organizations' snapshot for the local day of
+    /// `taken_at`, replacing an earlier one from that day.
+    pub fn record(&self, usage: &[UsageSummary], taken_at: DateTime<Utc>) -> Result<()> {
+        if usage.is_empty() {
+            return Ok(());
+        }
+        let mut snapshots = self.load()?;
+        let day = taken_at.with_timezone(&Local).date_naive();
+        for summary in usage {
+            snapshots
+                .entry(summary.org_id.clone())
+                .or_default()
+                .insert(day, UsageSnapshot::new(summary, taken_at));
+        }
+        let oldest = day - Days::new(KEEP_DAYS);
+        for days in snapshots.values_mut() {
+            days.retain(|date, _| *date >= oldest);
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&snapshots)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// Consumption per recorded day in `range`, of `org_id` or of every
+    /// organization, oldest first.
+    pub fn days(&self, org_id: Option<&str>, range: DayRange) -> Result<Vec<UsageDay>> {
+        Ok(usage_days(&self.load()?, org_id, range))
+    }
+}
+
+/// What each snapshot adds to the one before it. The first snapshot and
+/// the first of a new billing window count in full; days without a
+/// snapshot are left out, their usage landing on the next recorded day.
+pub fn usage_days(snapshots: &Snapshots, org_id: Option<&str>, range: DayRange) -> Vec<UsageDay> {
+    let mut out = Vec::new();
+    for (org, days) in snapshots {
+        if org_id.is_some_and(|id| id != org) {
+            continue;
+        }
+        let mut previous: Option<&UsageSnapshot> = None;
+        for (date, snapshot) in days {
+            let base = previous.filter(|prev| {
+                prev.window_from == snapshot.window_from
+                    && prev.credits_consumed <= snapshot.credits_consumed
+            });
+            let grown = |field: fn(&UsageSnapshot) -> i64| {
+                field(snapshot) - base.map(field).unwrap_or_default()
+            };
+            if range.contains(*date) {
+                out.push(UsageDay {
+                    org_id: org.clone(),
+                    date: *date,
+                    credits_consumed: grown(|s| s.credits_consumed),
+                    jobs_enqueued: grown(|s| s.jobs_enqueued).max(0),
+                    listings_run: grown(|s| s.listings_run).max(0),
+                    credit_balance_cents: snapshot.credit_balance_cents,
+                });
+            }
+            previous = Some(snapshot);
+        }
+    }
+    out.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.org_id.cmp(&b.org_id)));
+    out
+}
@@ -0,0 +1,303 @@
+//! Record/replay ("VCR") transport for Hermes calls.
+//!
+//! In record mode every request the client sends, and the response it gets
+//! back, is appended to a JSON cassette. In replay mode responses are served
+//! from the cassette and nothing touches the network. Credentials never reach
+//! the file: request headers are not stored, only a few response headers are
+//! kept, and secret-looking JSON fields and query params are redacted.
+
+use crate::error::{Error, Result};
+use parking_lot::Mutex;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub const REDACTED: &str = "[REDACTED]";
+const CASSETTE_VERSION: u32 = 1;
+const KEPT_RESPONSE_HEADERS: &[&str] = &["content-type", "retry-after", "x-request-id"];
+const SECRET_FIELDS: &[&str] = &[
+    "api_key",
+    "access_token",
+    "refresh_token",
+    "device_code",
+    "password",
+    "secret",
+    "service_role_key",
+    "token",
+];
+
+/// On-disk cassette: interactions in the order they happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cassette {
+    pub version: u32,
+    pub interactions: Vec<Interaction>,
+}
+
+impl Default for Cassette {
+    fn default() -> Self {
+        Self {
+            version: CASSETTE_VERSION,
+            interactions: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path and query relative to the base URL, so a cassette replays against
+    /// any Hermes host.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// JSON bodies are stored as JSON (and scrubbed); anything else as text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// A response as the send pipeline sees it, whether it came off the wire or
+/// out of a cassette.
+pub(crate) struct RawResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcrMode {
+    Record,
+    Replay,
+}
+
+/// Shared handle attached to a [`HermesClient`](crate::HermesClient) with
+/// `with_vcr`; clones of the client share one cassette.
+#[derive(Debug, Clone)]
+pub struct Vcr {
+    inner: Arc<Mutex<VcrState>>,
+}
+
+#[derive(Debug)]
+struct VcrState {
+    mode: VcrMode,
+    path: PathBuf,
+    cassette: Cassette,
+    used: Vec<bool>,
+}
+
+impl Vcr {
+    /// Starts a fresh cassette at `path`; it is rewritten after every call so
+    /// a failing command still leaves a usable file behind.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::with_state(VcrMode::Record, path.into(), Cassette::default())
+    }
+
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let bytes = fs::read(&path)
+            .map_err(|err| Error::Cassette(format!("failed to read {}: {err}", path.display())))?;
+        let cassette: Cassette = serde_json::from_slice(&bytes).map_err(|err| {
+            Error::Cassette(format!("{} is not a cassette: {err}", path.display()))
+        })?;
+        if cassette.version != CASSETTE_VERSION {
+            return Err(Error::Cassette(format!(
+                "{} has unsupported version {}",
+                path.display(),
+                cassette.version
+            )));
+        }
+        Ok(Self::with_state(VcrMode::Replay, path, cassette))
+    }
+
+    fn with_state(mode: VcrMode, path: PathBuf, cassette: Cassette) -> Self {
+        let used = vec![false; cassette.interactions.len()];
+        Self {
+            inner: Arc::new(Mutex::new(VcrState {
+                mode,
+                path,
+                cassette,
+                used,
+            })),
+        }
+    }
+
+    pub fn mode(&self) -> VcrMode {
+        self.inner.lock().mode
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.inner.lock().path.clone()
+    }
+
+    /// Serves the next unused interaction recorded for this method and path.
+    /// Once those run out the last one is repeated, so extra polls of a job
+    /// keep seeing its final state.
+    pub(crate) fn replay_response(&self, method: &Method, path: &str) -> Result<RawResponse> {
+        let path = scrub_path(path);
+        let mut state = self.inner.lock();
+        let matches: Vec<usize> = state
+            .cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.request.method == method.as_str() && i.request.path == path)
+            .map(|(idx, _)| idx)
+            .collect();
+        let Some(&last) = matches.last() else {
+            return Err(Error::Cassette(format!(
+                "no recorded response for {method} {path} in {}",
+                state.path.display()
+            )));
+        };
+        let idx = matches
+            .into_iter()
+            .find(|&idx| !state.used[idx])
+            .unwrap_or(last);
+        state.used[idx] = true;
+        Ok(state.cassette.interactions[idx].response.to_raw())
+    }
+
+    pub(crate) fn record_interaction(
+        &self,
+        method: &Method,
+        path: &str,
+        body: Option<&[u8]>,
+        response: &RawResponse,
+    ) -> Result<()> {
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method: method.to_string(),
+                path: scrub_path(path),
+                body: body
+                    .and_then(|bytes| serde_json::from_slice(bytes).ok())
+                    .map(scrub_json),
+            },
+            response: RecordedResponse::from_raw(response),
+        };
+        let mut state = self.inner.lock();
+        state.cassette.interactions.push(interaction);
+        state.used.push(true);
+        write_cassette(&state.path, &state.cassette)
+    }
+}
+
+impl RecordedResponse {
+    pub(crate) fn from_raw(raw: &RawResponse) -> Self {
+        let headers = KEPT_RESPONSE_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = raw.headers.get(*name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        let (body, text) = if raw.body.iter().all(u8::is_ascii_whitespace) {
+            (None, None)
+        } else {
+            match serde_json::from_slice::<Value>(&raw.body) {
+                Ok(json) => (Some(scrub_json(json)), None),
+                Err(_) => (None, Some(String::from_utf8_lossy(&raw.body).into_owned())),
+            }
+        };
+        Self {
+            status: raw.status.as_u16(),
+            headers,
+            body,
+            text,
+        }
+    }
+
+    fn to_raw(&self) -> RawResponse {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+        let body = match (&self.body, &self.text) {
+            (Some(json), _) => serde_json::to_vec(json).unwrap_or_default(),
+            (None, Some(text)) => text.clone().into_bytes(),
+            (None, None) => Vec::new(),
+        };
+        RawResponse {
+            status: StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK),
+            headers,
+            body,
+        }
+    }
+}
+
+/// `url` relative to `base`, which is how requests are keyed in a cassette.
+pub(crate) fn relative_path(base: &Url, url: &Url) -> String {
+    url.as_str()
+        .strip_prefix(base.as_str())
+        .unwrap_or(url.as_str())
+        .to_string()
+}
+
+fn is_secret_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRET_FIELDS.contains(&name.as_str()) || name.ends_with("_token") || name.ends_with("_secret")
+}
+
+/// Replaces the values of secret-looking fields, at any depth, with [`REDACTED`].
+pub fn scrub_json(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    if is_secret_field(&key) && !value.is_null() {
+                        (key, Value::String(REDACTED.to_string()))
+                    } else {
+                        (key, scrub_json(value))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(scrub_json).collect()),
+        other => other,
+    }
+}
+
+pub(crate) fn scrub_path(path: &str) -> String {
+    let Some((path, query)) = path.split_once('?') else {
+        return path.to_string();
+    };
+    let query: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if is_secret_field(key) => format!("{key}={REDACTED}"),
+            _ => pair.to_string(),
+        })
+        .collect();
+    format!("{path}?{}", query.join("&"))
+}
+
+fn write_cassette(path: &Path, cassette: &Cassette) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(cassette)?)?;
+    Ok(())
+}
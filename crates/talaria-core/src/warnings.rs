@@ -0,0 +1,139 @@
+//! What to do about the warnings listing stages report in their output, from
+//! the `[warning_policy]` config table. Rules match warning text with
+//! case-insensitive globs, optionally per stage; the first matching rule
+//! decides, and `default` covers the rest:
+//!
+//! ```toml
+//! [warning_policy]
+//! default = "warn"
+//!
+//! [[warning_policy.rules]]
+//! pattern = "*missing required aspect*"
+//! action = "fail"
+//!
+//! [[warning_policy.rules]]
+//! stage = "pricing"
+//! pattern = "*no comparables*"
+//! action = "ignore"
+//! ```
+
+use crate::error::{Error, Result};
+use crate::models::{ListingResponse, StageReport};
+use globset::{GlobBuilder, GlobMatcher};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningAction {
+    Ignore,
+    /// Report the warning and carry on.
+    #[default]
+    Warn,
+    /// Fail the command (exit code 6 in the CLI) once the result is out.
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarningRule {
+    /// Glob over the whole warning text, e.g. `*missing required aspect*`.
+    pub pattern: String,
+    /// Only warnings from this stage; any stage when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+    pub action: WarningAction,
+}
+
+/// The `[warning_policy]` config table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarningPolicy {
+    /// Action for warnings no rule matches.
+    #[serde(default)]
+    pub default: WarningAction,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<WarningRule>,
+}
+
+/// A stage warning and what the policy makes of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StageWarning {
+    pub stage: String,
+    pub message: String,
+    pub action: WarningAction,
+}
+
+impl std::fmt::Display for StageWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.stage, self.message)
+    }
+}
+
+impl WarningPolicy {
+    pub fn validate(&self) -> Result<()> {
+        self.matchers().map(|_| ())
+    }
+
+    /// Every warning in the stages of `resp`, in stage order, with the
+    /// action of the first rule that matches it.
+    pub fn evaluate(&self, resp: &ListingResponse) -> Vec<StageWarning> {
+        // Patterns were checked when the config was loaded; a policy built
+        // by hand with a bad one just has that rule never match.
+        let matchers = self.matchers().unwrap_or_default();
+        resp.stages
+            .iter()
+            .flat_map(|stage| {
+                stage_warnings(stage)
+                    .into_iter()
+                    .map(move |message| (stage, message))
+            })
+            .map(|(stage, message)| {
+                let action = self
+                    .rules
+                    .iter()
+                    .zip(&matchers)
+                    .find(|(rule, matcher)| {
+                        rule.stage.as_ref().is_none_or(|name| *name == stage.name)
+                            && matcher.is_match(&message)
+                    })
+                    .map_or(self.default, |(rule, _)| rule.action);
+                StageWarning {
+                    stage: stage.name.clone(),
+                    message,
+                    action,
+                }
+            })
+            .collect()
+    }
+
+    fn matchers(&self) -> Result<Vec<GlobMatcher>> {
+        self.rules
+            .iter()
+            .map(|rule| {
+                GlobBuilder::new(&rule.pattern)
+                    .case_insensitive(true)
+                    .literal_separator(false)
+                    .build()
+                    .map(|glob| glob.compile_matcher())
+                    .map_err(|err| {
+                        Error::InvalidConfig(format!(
+                            "warning_policy pattern '{}': {err}",
+                            rule.pattern
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+/// The `warnings` of a stage output: a list of strings or a single one.
+pub fn stage_warnings(stage: &StageReport) -> Vec<String> {
+    match stage.output.get("warnings") {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Some(Value::String(warning)) => vec![warning.clone()],
+        _ => Vec::new(),
+    }
+}
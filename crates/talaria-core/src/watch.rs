@@ -0,0 +1,245 @@
+//! Watch-folder ingestion: picks up images a tethering or photo app drops
+//! into a directory, groups them into products and hands each group over
+//! once it has stopped changing.
+//!
+//! The folder is polled rather than subscribed to: a photo app writes a
+//! JPEG over several calls, and a group is only complete once the app moves
+//! on to the next product, so the caller needs a quiet period either way.
+//! A group is ready when none of its files have appeared or changed for the
+//! settle time. Processed files are moved to an archive folder; a group that
+//! failed is held back until one of its files changes or a new one arrives.
+
+use crate::error::{Error, Result};
+use crate::images::{self, DirFilter, SkippedFile};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Archive folder name used when none is given; hidden, so scans skip it.
+pub const DEFAULT_ARCHIVE_DIR: &str = ".talaria-processed";
+/// How long a group must stay unchanged before it is processed.
+pub const DEFAULT_SETTLE: Duration = Duration::from_secs(3);
+
+/// How files are grouped into products.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Files directly in the folder, by file name with any trailing frame
+    /// number dropped: `sku-123_01.jpg`, `sku-123_02.jpg` and `sku-123.jpg`
+    /// are all `sku-123`.
+    Filename,
+    /// One product per subfolder, named after it: `sku-123/front.jpg`.
+    Subfolder,
+}
+
+/// Images that make up one product, in file name order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batch {
+    /// The sku the files were grouped under.
+    pub key: String,
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Seen {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Tracked {
+    seen: Seen,
+    changed_at: Instant,
+}
+
+#[derive(Debug)]
+pub struct FolderWatch {
+    root: PathBuf,
+    archive: PathBuf,
+    group_by: GroupBy,
+    settle: Duration,
+    files: HashMap<PathBuf, Tracked>,
+    /// Groups that failed, with the files as they were then.
+    held: HashMap<String, BTreeMap<PathBuf, Seen>>,
+    reported: HashSet<PathBuf>,
+}
+
+impl FolderWatch {
+    /// `archive` defaults to [`DEFAULT_ARCHIVE_DIR`] inside `root`.
+    pub fn new(root: &Path, archive: Option<&Path>, group_by: GroupBy, settle: Duration) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            archive: archive
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| root.join(DEFAULT_ARCHIVE_DIR)),
+            group_by,
+            settle,
+            files: HashMap::new(),
+            held: HashMap::new(),
+            reported: HashSet::new(),
+        }
+    }
+
+    pub fn archive_dir(&self) -> &Path {
+        &self.archive
+    }
+
+    /// Scans the folder and returns the groups that have settled as of
+    /// `now`, plus files newly found to be unusable (each reported once).
+    pub fn poll(&mut self, now: Instant) -> Result<(Vec<Batch>, Vec<SkippedFile>)> {
+        let filter = DirFilter {
+            recursive: self.group_by == GroupBy::Subfolder,
+            ..DirFilter::default()
+        };
+        let listing = images::collect_dir(&self.root, &filter)?;
+        let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        let mut present = HashSet::new();
+        for path in listing.files {
+            if path.starts_with(&self.archive) {
+                continue;
+            }
+            let Some(key) = self.key_for(&path) else {
+                continue;
+            };
+            let Ok(meta) = fs::metadata(&path) else {
+                continue;
+            };
+            let seen = Seen {
+                len: meta.len(),
+                modified: meta.modified().ok(),
+            };
+            match self.files.get_mut(&path) {
+                Some(tracked) if tracked.seen == seen => {}
+                Some(tracked) => {
+                    *tracked = Tracked {
+                        seen,
+                        changed_at: now,
+                    };
+                }
+                None => {
+                    self.files.insert(
+                        path.clone(),
+                        Tracked {
+                            seen,
+                            changed_at: now,
+                        },
+                    );
+                }
+            }
+            present.insert(path.clone());
+            groups.entry(key).or_default().push(path);
+        }
+        self.files.retain(|path, _| present.contains(path));
+
+        let mut ready = Vec::new();
+        for (key, files) in groups {
+            let settled = files
+                .iter()
+                .all(|path| now.duration_since(self.files[path].changed_at) >= self.settle);
+            if !settled {
+                continue;
+            }
+            let snapshot: BTreeMap<PathBuf, Seen> = files
+                .iter()
+                .map(|path| (path.clone(), self.files[path].seen))
+                .collect();
+            if self.held.get(&key) == Some(&snapshot) {
+                continue;
+            }
+            self.held.remove(&key);
+            ready.push(Batch { key, files });
+        }
+
+        let skipped = listing
+            .skipped
+            .into_iter()
+            .filter(|file| !file.path.starts_with(&self.archive))
+            .filter(|file| self.reported.insert(file.path.clone()))
+            .collect();
+        Ok((ready, skipped))
+    }
+
+    /// The product a file belongs to, or `None` when it sits where the
+    /// grouping doesn't look (e.g. loose files in subfolder mode).
+    pub fn key_for(&self, path: &Path) -> Option<String> {
+        let rel = path.strip_prefix(&self.root).ok()?;
+        match self.group_by {
+            GroupBy::Filename => {
+                if rel.components().count() != 1 {
+                    return None;
+                }
+                filename_key(path)
+            }
+            GroupBy::Subfolder => {
+                let mut components = rel.components();
+                let folder = components.next()?.as_os_str().to_str()?.to_string();
+                components.next().map(|_| folder)
+            }
+        }
+    }
+
+    /// Moves a processed group to `<archive>/<key>/`, renaming on clashes
+    /// with earlier runs. Returns the new paths.
+    pub fn archive(&mut self, batch: &Batch) -> Result<Vec<PathBuf>> {
+        let dir = self.archive.join(&batch.key);
+        fs::create_dir_all(&dir)?;
+        let mut moved = Vec::with_capacity(batch.files.len());
+        for file in &batch.files {
+            let name = file
+                .file_name()
+                .ok_or_else(|| Error::InvalidConfig(format!("not a file: {}", file.display())))?;
+            let target = unique_path(&dir.join(name));
+            fs::rename(file, &target).or_else(|_| {
+                // Archive on another filesystem.
+                fs::copy(file, &target).and_then(|_| fs::remove_file(file))
+            })?;
+            self.files.remove(file);
+            moved.push(target);
+        }
+        Ok(moved)
+    }
+
+    /// Holds a failed group back until one of its files changes or a new
+    /// file joins it.
+    pub fn hold(&mut self, batch: &Batch) {
+        let snapshot = batch
+            .files
+            .iter()
+            .filter_map(|path| Some((path.clone(), self.files.get(path)?.seen)))
+            .collect();
+        self.held.insert(batch.key.clone(), snapshot);
+    }
+}
+
+/// The file stem without a trailing `_<n>` or ` <n>` frame number. Hyphens
+/// are left alone since skus often end in `-<digits>` themselves.
+fn filename_key(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?.trim();
+    let key = stem
+        .rsplit_once(['_', ' '])
+        .filter(|(prefix, frame)| {
+            !prefix.trim().is_empty()
+                && !frame.is_empty()
+                && frame.chars().all(|c| c.is_ascii_digit())
+        })
+        .map_or(stem, |(prefix, _)| prefix.trim());
+    (!key.is_empty()).then(|| key.to_string())
+}
+
+fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{stem}.{n}{ext}")))
+        .find(|candidate| !candidate.exists())
+        .expect("some suffix is free")
+}
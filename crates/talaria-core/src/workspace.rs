@@ -0,0 +1,69 @@
+//! Lookups over the local product workspace (`<captures>/products/<id>/`)
+//! that the TUI captures into and the CLI reads.
+//!
+//! Manifests are read as raw JSON so both frontends agree on them without
+//! sharing the TUI's manifest types.
+
+use crate::error::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// A category previously chosen for one of the local listings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KnownCategory {
+    pub marketplace: String,
+    pub id: String,
+    pub label: String,
+    pub uses: usize,
+}
+
+/// Collects distinct categories from the `listings` of all local products,
+/// one per marketplace and category id, most used first. A category whose
+/// label differs between listings keeps the first non-empty label seen. A
+/// workspace without products has none.
+pub fn known_categories(base: &Path) -> Result<Vec<KnownCategory>> {
+    let entries = match fs::read_dir(base.join("products")) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut out: Vec<KnownCategory> = Vec::new();
+    for entry in entries.flatten() {
+        let Ok(bytes) = fs::read(entry.path().join("product.json")) else {
+            continue;
+        };
+        let Ok(manifest) = serde_json::from_slice::<Value>(&bytes) else {
+            continue;
+        };
+        let Some(listings) = manifest["listings"].as_object() else {
+            continue;
+        };
+        for (marketplace, listing) in listings {
+            let Some(id) = listing["category_id"].as_str().filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            let label = listing["category_label"].as_str().unwrap_or_default();
+            match out
+                .iter_mut()
+                .find(|c| c.marketplace == *marketplace && c.id == id)
+            {
+                Some(existing) => {
+                    existing.uses += 1;
+                    if existing.label.is_empty() {
+                        existing.label = label.to_string();
+                    }
+                }
+                None => out.push(KnownCategory {
+                    marketplace: marketplace.clone(),
+                    id: id.to_string(),
+                    label: label.to_string(),
+                    uses: 1,
+                }),
+            }
+        }
+    }
+    out.sort_by(|a, b| b.uses.cmp(&a.uses).then_with(|| a.label.cmp(&b.label)));
+    Ok(out)
+}
@@ -0,0 +1,93 @@
+use serde_json::{Value, json};
+use talaria_core::artifacts::ArtifactDir;
+use talaria_core::client::HermesClient;
+use talaria_core::config::Config;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn final_responses_are_saved_once_per_call_with_secrets_scrubbed() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503).insert_header("retry-after", "0"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-request-id", "req-health")
+                .set_body_json(json!({"status": "ok", "service": "hermes"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/auth/device/poll"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"status": "authorized", "access_token": "at_secret"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_9"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .set_body_json(json!({"error": "job not found", "request_id": "req-missing"})),
+        )
+        .mount(&server)
+        .await;
+
+    let dir = std::env::temp_dir().join(format!("talaria-artifacts-{}", uuid::Uuid::new_v4()));
+    let hermes = HermesClient::new(Config {
+        base_url: server.uri(),
+        api_key: Some("sk_live_key".into()),
+        ..Config::default()
+    })
+    .expect("client")
+    .with_artifacts(ArtifactDir::create(&dir).expect("artifact dir"));
+    hermes.health().await.expect("health");
+    hermes.device_auth_poll("dev_code_1").await.expect("poll");
+    assert!(hermes.get_job_status("job_9").await.is_err());
+
+    let mut files: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    let names: Vec<String> = files
+        .iter()
+        .map(|file| file.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        names.len(),
+        3,
+        "one file per call, not per attempt: {names:?}"
+    );
+    assert!(names[0].ends_with("-0001-get-health.json"), "{names:?}");
+    assert!(names[1].ends_with("-0002-post-v1-auth-device-poll.json"));
+    assert!(names[2].ends_with("-0003-get-jobs-id.json"));
+
+    let read = |idx: usize| -> Value {
+        serde_json::from_slice(&std::fs::read(&files[idx]).unwrap()).unwrap()
+    };
+    let health = read(0);
+    assert_eq!(health["attempts"], 2);
+    assert_eq!(health["request_id"], "req-health");
+    assert_eq!(health["request"]["method"], "GET");
+    assert_eq!(health["response"]["status"], 200);
+    assert_eq!(health["response"]["body"]["service"], "hermes");
+
+    let poll = std::fs::read_to_string(&files[1]).unwrap();
+    for secret in ["sk_live_key", "at_secret", "dev_code_1"] {
+        assert!(!poll.contains(secret), "{secret} leaked into an artifact");
+    }
+
+    let missing = read(2);
+    assert_eq!(missing["response"]["status"], 404);
+    assert_eq!(missing["request_id"], "req-missing");
+
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,139 @@
+use talaria_core::barcode::{Barcode, Symbology, scan_luma};
+
+const L_CODES: [[usize; 4]; 10] = [
+    [3, 2, 1, 1],
+    [2, 2, 2, 1],
+    [2, 1, 2, 2],
+    [1, 4, 1, 1],
+    [1, 1, 3, 2],
+    [1, 2, 3, 1],
+    [1, 1, 1, 4],
+    [1, 3, 1, 2],
+    [1, 2, 1, 3],
+    [3, 1, 1, 2],
+];
+const PARITY: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL",
+    "LGGLGL",
+];
+
+/// Modules of an EAN-13/EAN-8 symbol with quiet zones (true = bar).
+fn modules(digits: &str) -> Vec<bool> {
+    let digits: Vec<usize> = digits.bytes().map(|b| (b - b'0') as usize).collect();
+    let (parity, left, right) = if digits.len() == 13 {
+        (PARITY[digits[0]], &digits[1..7], &digits[7..])
+    } else {
+        ("LLLL", &digits[..4], &digits[4..])
+    };
+    let mut out = vec![false; 10];
+    let mut push = |widths: &[usize], first_bar: bool| {
+        for (i, &w) in widths.iter().enumerate() {
+            out.extend(std::iter::repeat_n((i % 2 == 0) == first_bar, w));
+        }
+    };
+    push(&[1, 1, 1], true);
+    for (d, p) in left.iter().zip(parity.chars()) {
+        let mut widths = L_CODES[*d];
+        if p == 'G' {
+            widths.reverse();
+        }
+        push(&widths, false);
+    }
+    push(&[1, 1, 1, 1, 1], false);
+    for d in right {
+        push(&L_CODES[*d], true);
+    }
+    push(&[1, 1, 1], true);
+    out.extend([false; 10]);
+    out
+}
+
+/// Grayscale frame with the symbol drawn `px` pixels per module, offset into a
+/// noisy mid-grey background.
+fn frame(digits: &str, px: usize, flip: bool) -> (usize, usize, Vec<u8>) {
+    let mods = modules(digits);
+    let (width, height) = (mods.len() * px + 60, 48);
+    let mut luma = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let noise = ((x * 7 + y * 13) % 11) as u8;
+            let value = match x.checked_sub(30).map(|x| x / px) {
+                _ if !(8..40).contains(&y) => 120 + noise,
+                Some(m) if m < mods.len() => {
+                    if mods[m] {
+                        30 + noise
+                    } else {
+                        220 - noise
+                    }
+                }
+                _ => 220 - noise,
+            };
+            luma.push(value);
+        }
+    }
+    if flip {
+        luma.reverse();
+    }
+    (width, height, luma)
+}
+
+fn scan(digits: &str, px: usize, flip: bool) -> Option<Barcode> {
+    let (w, h, luma) = frame(digits, px, flip);
+    scan_luma(w, h, &luma)
+}
+
+#[test]
+fn reads_upc_a_and_ean_13_at_several_scales() {
+    for px in [2, 3, 5] {
+        assert_eq!(
+            scan("0036000291452", px, false),
+            Some(Barcode {
+                symbology: Symbology::UpcA,
+                value: "036000291452".into(),
+            }),
+            "{px}px modules"
+        );
+    }
+    let ean = scan("4006381333931", 3, false).unwrap();
+    assert_eq!(ean.symbology, Symbology::Ean13);
+    assert_eq!(ean.value, "4006381333931");
+    assert_eq!(ean.context_line(), "EAN-13: 4006381333931");
+    assert_eq!(
+        ean.add_to_context("Hiking boots\n"),
+        "Hiking boots\nEAN-13: 4006381333931"
+    );
+    assert_eq!(ean.add_to_context("EAN 4006381333931"), "EAN 4006381333931");
+}
+
+#[test]
+fn reads_upside_down_and_ean_8() {
+    assert_eq!(
+        scan("5901234123457", 3, true).map(|b| b.value),
+        Some("5901234123457".into())
+    );
+    let short = scan("96385074", 4, false).unwrap();
+    assert_eq!(short.symbology, Symbology::Ean8);
+    assert_eq!(short.value, "96385074");
+}
+
+#[test]
+fn rejects_bad_check_digits_and_blank_frames() {
+    assert_eq!(scan("0036000291453", 3, false), None);
+    assert_eq!(scan_luma(64, 8, &[128; 64 * 8]), None);
+    assert_eq!(scan_luma(64, 8, &[]), None);
+}
+
+#[cfg(feature = "barcode")]
+#[test]
+fn scans_png_files() {
+    let (w, h, luma) = frame("0036000291452", 3, false);
+    let rgb: Vec<u8> = luma.iter().flat_map(|&v| [v, v, v]).collect();
+    let path = std::env::temp_dir().join(format!("talaria-barcode-{}.png", std::process::id()));
+    std::fs::write(
+        &path,
+        talaria_core::simulate::encode_png(w as u32, h as u32, &rgb),
+    )
+    .unwrap();
+    let found = talaria_core::barcode::scan_file(&path).unwrap();
+    assert_eq!(found.map(|b| b.value), Some("036000291452".into()));
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "blocking")]
+
+use serde_json::json;
+use talaria_core::blocking::HermesClientBlocking;
+use talaria_core::config::Config;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[test]
+fn blocking_client_calls_hermes_without_a_caller_runtime() {
+    // The mock server needs a runtime of its own; the client must not.
+    let server_rt = tokio::runtime::Runtime::new().unwrap();
+    let server = server_rt.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"status": "ok", "service": "hermes"})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v1/products/p1"))
+            .and(header("x-hermes-key", "sk_test"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        server
+    });
+
+    let client = HermesClientBlocking::new(Config {
+        base_url: server.uri(),
+        api_key: Some("sk_test".into()),
+        ..Config::default()
+    })
+    .unwrap();
+    assert_eq!(client.health().unwrap().service, "hermes");
+    client.delete_product("p1").unwrap();
+    let err = client.get_job_status("missing").unwrap_err();
+    assert_eq!(err.kind(), talaria_core::ErrorKind::Validation, "{err}");
+
+    server_rt.block_on(server.verify());
+}
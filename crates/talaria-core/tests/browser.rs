@@ -0,0 +1,19 @@
+use talaria_core::MarketplaceId;
+use talaria_core::browser::listing_url;
+
+#[test]
+fn listing_url_uses_the_marketplace_site() {
+    assert_eq!(
+        listing_url(&MarketplaceId::EbayUs, "1234567890").as_deref(),
+        Some("https://www.ebay.com/itm/1234567890")
+    );
+    assert_eq!(
+        listing_url(&MarketplaceId::EbayDe, " 42 ").as_deref(),
+        Some("https://www.ebay.de/itm/42")
+    );
+    assert_eq!(listing_url(&MarketplaceId::EbayUk, ""), None);
+    assert_eq!(
+        listing_url(&MarketplaceId::Unknown("EBAY_MARS".into()), "42"),
+        None
+    );
+}
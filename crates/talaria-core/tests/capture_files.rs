@@ -0,0 +1,73 @@
+use talaria_core::config::{CaptureFiles, CaptureFormat, CaptureTriggers, SecondCamera};
+
+#[test]
+fn capture_file_names_follow_the_template() {
+    let files = CaptureFiles {
+        template: "{sku}_{session}_{seq}_{ts}.{ext}".into(),
+        format: CaptureFormat::Png,
+    };
+    files.validate().unwrap();
+    assert_eq!(
+        files.file_name("SKU 7/a", "s1", 4, "20260101_120000_000"),
+        "SKU_7_a_s1_004_20260101_120000_000.png"
+    );
+
+    let files = CaptureFiles {
+        template: "{sku}-{seq}".into(),
+        format: CaptureFormat::Webp,
+    };
+    assert_eq!(files.file_name("A1", "s1", 12, "ts"), "A1-012.webp");
+    assert_eq!(
+        CaptureFiles::default().file_name("A1", "s1", 1, "ts"),
+        "frame_ts.jpg"
+    );
+}
+
+#[test]
+fn capture_file_templates_are_checked() {
+    for (template, why) in [
+        ("{sku}_{when}.{ext}", "unknown field {when}"),
+        ("{sku}.{ext}", "needs {seq} or {ts}"),
+        ("frames/{seq}.{ext}", "not a path"),
+        ("{seq", "unclosed"),
+    ] {
+        let files = CaptureFiles {
+            template: template.into(),
+            ..CaptureFiles::default()
+        };
+        let err = files.validate().unwrap_err().to_string();
+        assert!(err.contains(why), "{template}: {err}");
+    }
+}
+
+#[test]
+fn second_camera_needs_one_source_and_distinct_labels() {
+    let camera: SecondCamera = toml::from_str("index = 1").unwrap();
+    assert_eq!(
+        (camera.label.as_str(), camera.primary_label.as_str()),
+        ("side", "top")
+    );
+    camera.validate().unwrap();
+
+    for text in [
+        "label = \"side\"",
+        "index = 1\nurl = \"http://cam/video\"",
+        "url = \"http://cam/video\"\nlabel = \"top\"",
+    ] {
+        let camera: SecondCamera = toml::from_str(text).unwrap();
+        assert!(camera.validate().is_err(), "{text}");
+    }
+}
+
+#[test]
+fn turntable_runs_need_a_sane_interval_and_frame_count() {
+    let triggers: CaptureTriggers =
+        toml::from_str("turntable_every_ms = 500\nturntable_frames = 36").unwrap();
+    triggers.validate().unwrap();
+    assert_eq!(triggers.countdown_secs, 3);
+
+    for text in ["turntable_every_ms = 10", "turntable_frames = 0"] {
+        let triggers: CaptureTriggers = toml::from_str(text).unwrap();
+        assert!(triggers.validate().is_err(), "{text}");
+    }
+}
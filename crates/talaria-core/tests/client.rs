@@ -0,0 +1,398 @@
+use serde_json::{Value, json};
+use talaria_core::client::HermesClient;
+use talaria_core::config::Config;
+use talaria_core::error::Error;
+use talaria_core::models::*;
+use wiremock::matchers::{body_partial_json, header, header_exists, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const KEY: &str = "sk_test_123";
+
+fn client(server: &MockServer, api_key: Option<&str>) -> HermesClient {
+    HermesClient::new(Config {
+        base_url: server.uri(),
+        api_key: api_key.map(str::to_string),
+        ..Config::default()
+    })
+    .expect("client")
+}
+
+fn listing_request() -> PublicListingRequest {
+    serde_json::from_value(json!({
+        "fulfillment_policy_id": "1",
+        "payment_policy_id": "2",
+        "return_policy_id": "3",
+        "merchant_location_key": "home",
+        "images_source": ["https://img.example/a.jpg"],
+    }))
+    .expect("listing request")
+}
+
+fn media_json(media_id: &str) -> Value {
+    json!({
+        "media_id": media_id,
+        "object_key": format!("media/{media_id}.jpg"),
+        "url": format!("https://cdn.example/{media_id}.jpg"),
+        "created_at": "2026-01-01T00:00:00Z",
+        "updated_at": "2026-01-01T00:00:00Z",
+    })
+}
+
+fn job_json(id: &str) -> Value {
+    json!({
+        "id": id,
+        "state": "completed",
+        "result": {"listing_id": "L-1", "stages": []},
+        "created_at": "2026-01-01T00:00:00Z",
+        "updated_at": "2026-01-01T00:01:00Z",
+        "request": serde_json::to_value(listing_request()).unwrap(),
+    })
+}
+
+fn usage_json() -> Value {
+    json!([{
+        "org_id": "org_1",
+        "counters": {"credits_consumed": 7, "jobs_enqueued": 2, "listings_run": 3},
+    }])
+}
+
+#[tokio::test]
+async fn health_is_public() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"status": "ok", "service": "hermes"})),
+        )
+        .mount(&server)
+        .await;
+
+    let health = client(&server, Some(KEY)).health().await.expect("health");
+    assert_eq!(health.status, "ok");
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[0].headers.get("x-hermes-key").is_none());
+}
+
+#[tokio::test]
+async fn authenticated_calls_send_key_and_query() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/job_1"))
+        .and(header("x-hermes-key", KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(job_json("job_1")))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .and(query_param("org_id", "org_1"))
+        .and(header("x-hermes-key", KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(usage_json()))
+        .mount(&server)
+        .await;
+
+    let hermes = client(&server, Some(KEY));
+    let job = hermes.get_job_status("job_1").await.expect("job");
+    assert!(matches!(job.state, JobState::Completed { ref result } if result.listing_id == "L-1"));
+    let usage = hermes
+        .usage(Some("org_1".into()), None, None)
+        .await
+        .expect("usage");
+    assert_eq!(usage[0].counters.listings_run, 3);
+}
+
+#[tokio::test]
+async fn org_id_scopes_key_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .and(query_param("org_id", "org_2"))
+        .and(header("x-hermes-org", "org_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(usage_json()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"status": "ok", "service": "hermes"})),
+        )
+        .mount(&server)
+        .await;
+
+    let hermes = HermesClient::new(Config {
+        base_url: server.uri(),
+        api_key: Some(KEY.into()),
+        org_id: Some("org_2".into()),
+        ..Config::default()
+    })
+    .expect("client");
+    assert_eq!(hermes.org_id(), Some("org_2"));
+    hermes.usage(None, None, None).await.expect("usage");
+    hermes.health().await.expect("health");
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[1].headers.get("x-hermes-org").is_none());
+
+    let unscoped = hermes.with_org(None);
+    assert!(unscoped.usage(None, None, None).await.is_err());
+}
+
+#[tokio::test]
+async fn missing_api_key_fails_without_a_request() {
+    let server = MockServer::start().await;
+    let err = client(&server, None)
+        .enqueue_listing(&listing_request())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::MissingApiKey { ref endpoint } if endpoint == "jobs/listings"));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn get_retries_after_429() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(usage_json()))
+        .mount(&server)
+        .await;
+
+    let usage = client(&server, Some(KEY))
+        .usage(None, None, None)
+        .await
+        .expect("usage after retry");
+    assert_eq!(usage.len(), 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn plain_posts_are_not_retried_but_idempotent_ones_are() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/jobs/listings"))
+        .respond_with(ResponseTemplate::new(503).insert_header("retry-after", "0"))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/jobs/listings"))
+        .and(header_exists("idempotency-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"job_id": "job_9"})))
+        .mount(&server)
+        .await;
+
+    let hermes = client(&server, Some(KEY));
+    let err = hermes
+        .enqueue_listing(&listing_request())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Api { status, .. } if status.as_u16() == 503));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    let queued = hermes
+        .enqueue_listing_idempotent(&listing_request(), "idem-1")
+        .await
+        .expect("idempotent enqueue");
+    assert_eq!(queued.job_id, "job_9");
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[2].headers.get("idempotency-key").unwrap(),
+        "idem-1"
+    );
+}
+
+#[tokio::test]
+async fn api_errors_keep_message_and_request_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/listings"))
+        .respond_with(
+            ResponseTemplate::new(422)
+                .insert_header("x-request-id", "req_42")
+                .set_body_json(json!({
+                    "error": "merchant_location_key is unknown",
+                    "code": "validation_error",
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let err = client(&server, Some(KEY))
+        .create_listing(&listing_request())
+        .await
+        .unwrap_err();
+    assert_eq!(err.request_id(), Some("req_42"));
+    match err {
+        Error::Api {
+            status,
+            message,
+            api_error,
+            request_id,
+        } => {
+            assert_eq!(status.as_u16(), 422);
+            assert_eq!(message, "merchant_location_key is unknown");
+            assert_eq!(api_error.unwrap().code.as_deref(), Some("validation_error"));
+            assert_eq!(request_id.as_deref(), Some("req_42"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn media_upload_lifecycle() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/media/uploads"))
+        .and(body_partial_json(json!({"filename": "a.jpg"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "upload_id": "up_1",
+            "upload_url": "https://storage.example/up_1",
+            "object_key": "media/m_1.jpg",
+            "method": "PUT",
+            "expires_at": "2026-01-01T00:10:00Z",
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/media/uploads/up_1/complete"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"media": media_json("m_1")})))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/media/uploads/up_2/abort"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/media/m_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"deleted": true})))
+        .mount(&server)
+        .await;
+
+    let hermes = client(&server, Some(KEY));
+    let session = hermes
+        .create_media_upload(&CreateUploadRequest {
+            content_length: Some(3),
+            content_type: Some("image/jpeg".into()),
+            filename: "a.jpg".into(),
+            metadata: None,
+            product_id: None,
+            purpose: None,
+            session_id: None,
+            sha256: None,
+        })
+        .await
+        .expect("create upload");
+    assert_eq!(session.upload_id, "up_1");
+    let done = hermes
+        .complete_media_upload(&session.upload_id, None)
+        .await
+        .expect("complete");
+    assert_eq!(done.media.media_id, "m_1");
+    hermes
+        .abort_media_upload("up_2")
+        .await
+        .expect("abort (204)");
+    hermes
+        .delete_media("m_1")
+        .await
+        .expect("delete (ignored body)");
+}
+
+#[tokio::test]
+async fn device_auth_flow() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/auth/device/start"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "device_code": "dev_1",
+            "user_code": "ABCD-EFGH",
+            "verification_uri": "https://hermes.example/device",
+            "verification_uri_complete": "https://hermes.example/device?code=ABCD-EFGH",
+            "expires_in": 600,
+            "interval": 1,
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/auth/device/poll"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "pending"})))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/auth/device/poll"))
+        .and(body_partial_json(json!({"device_code": "dev_1"})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"status": "authorized", "access_token": "at_1"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/user/api-keys"))
+        .and(header("authorization", "Bearer at_1"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": "key_1",
+            "name": "test",
+            "prefix": "sk_live_ab",
+            "secret": "sk_live_abcdef",
+        })))
+        .mount(&server)
+        .await;
+
+    let hermes = client(&server, None);
+    let start = hermes.device_auth_start().await.expect("start");
+    let first = hermes.device_auth_poll(&start.device_code).await.unwrap();
+    assert_eq!(first.status, DeviceAuthStatus::Pending);
+    let second = hermes.device_auth_poll(&start.device_code).await.unwrap();
+    assert_eq!(second.status, DeviceAuthStatus::Authorized);
+    let key = hermes
+        .create_user_api_key(second.access_token.as_deref().unwrap(), "test")
+        .await
+        .expect("api key");
+    assert_eq!(key.secret, "sk_live_abcdef");
+}
+
+#[tokio::test]
+async fn user_api_keys_list_and_revoke_with_access_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/api-keys"))
+        .and(header("authorization", "Bearer at_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": "key_1", "name": "Talaria laptop", "prefix": "sk_live_ab",
+             "created_at": "2026-01-01T00:00:00Z", "last_used_at": "2026-02-01T00:00:00Z"},
+            {"id": "key_2", "name": "Talaria studio", "prefix": "sk_live_cd"},
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/user/api-keys/key_2"))
+        .and(header("authorization", "Bearer at_1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let hermes = client(&server, Some(KEY));
+    let keys = hermes.list_user_api_keys("at_1").await.expect("list");
+    assert_eq!(keys.len(), 2);
+    assert!(keys[0].last_used_at.is_some() && keys[1].created_at.is_none());
+    hermes
+        .delete_user_api_key("at_1", "key_2")
+        .await
+        .expect("revoke");
+    let requests = server.received_requests().await.unwrap();
+    assert!(
+        requests
+            .iter()
+            .all(|r| r.headers.get("x-hermes-key").is_none())
+    );
+}
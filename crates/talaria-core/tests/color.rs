@@ -0,0 +1,51 @@
+use talaria_core::color::{WhiteBalance, apply_gains};
+
+const W: usize = 200;
+const H: usize = 150;
+
+/// A frame of one colour, with darker noise around a plain centre.
+fn frame(color: [u8; 3]) -> Vec<u8> {
+    (0..W * H)
+        .flat_map(|idx| {
+            let (x, y) = (idx % W, idx / W);
+            if (60..140).contains(&x) && (45..105).contains(&y) {
+                color
+            } else {
+                [((x * 7 + y * 13) % 200) as u8; 3]
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn gray_card_gains_neutralise_a_cast_and_level_on_request() {
+    // A gray card under warm light.
+    let mut warm = frame([150, 120, 90]);
+    let gains = WhiteBalance::default().calibrate(W, H, &warm).unwrap();
+    apply_gains(&mut warm, gains);
+    let centre = (H / 2 * W + W / 2) * 3;
+    assert_eq!(&warm[centre..centre + 3], &[120, 120, 120]);
+
+    let levelled = WhiteBalance {
+        gains: None,
+        level: true,
+    };
+    let gains = levelled.calibrate(W, H, &frame([60, 60, 60])).unwrap();
+    assert!(gains.iter().all(|gain| (gain - 118.0 / 60.0).abs() < 1e-9));
+}
+
+#[test]
+fn frames_without_a_usable_card_are_refused() {
+    let config = WhiteBalance::default();
+    for (color, reason) in [
+        ([10, 10, 10], "too dark"),
+        ([250, 250, 250], "overexposed"),
+        ([200, 60, 60], "not neutral"),
+    ] {
+        let err = config.calibrate(W, H, &frame(color)).unwrap_err();
+        assert!(err.to_string().contains(reason), "{err}");
+    }
+
+    let bad: WhiteBalance = toml::from_str("gains = [1.0, 9.0, 1.0]").unwrap();
+    assert!(bad.validate().is_err());
+}
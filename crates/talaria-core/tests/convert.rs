@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+use talaria_core::convert::{InputKind, embedded_jpeg, prepare_for_upload};
+
+fn jpeg(scan_len: usize) -> Vec<u8> {
+    let mut out = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00];
+    out.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02]);
+    out.extend(std::iter::repeat_n(0x11, scan_len));
+    out.extend_from_slice(&[0xFF, 0x00, 0x22, 0xFF, 0xD9]);
+    out
+}
+
+#[test]
+fn embedded_jpeg_picks_largest_preview() {
+    let thumb = jpeg(4);
+    let preview = jpeg(64);
+    let mut raw = b"II*\0 tiff header".to_vec();
+    raw.extend_from_slice(&thumb);
+    raw.extend_from_slice(b"padding");
+    raw.extend_from_slice(&preview);
+    raw.extend_from_slice(b"trailer");
+
+    assert_eq!(embedded_jpeg(&raw), Some(preview.as_slice()));
+    assert_eq!(embedded_jpeg(b"no jpeg here"), None);
+}
+
+#[test]
+fn input_kind_and_unsupported_inputs() {
+    assert_eq!(InputKind::of(&PathBuf::from("a.HEIC")), InputKind::Heic);
+    assert_eq!(InputKind::of(&PathBuf::from("a.nef")), InputKind::Raw);
+    assert_eq!(InputKind::of(&PathBuf::from("a.jpg")), InputKind::Native);
+
+    let out = std::env::temp_dir().join("talaria-convert-test");
+    let native = vec![PathBuf::from("a.jpg")];
+    assert_eq!(prepare_for_upload(&native, &out).unwrap(), native);
+
+    if !cfg!(feature = "heic") {
+        let err = prepare_for_upload(
+            &[
+                PathBuf::from("a.jpg"),
+                PathBuf::from("b.heic"),
+                PathBuf::from("c.HEIF"),
+            ],
+            &out,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("b.heic") && err.contains("c.HEIF"), "{err}");
+    }
+}
@@ -0,0 +1,44 @@
+use talaria_core::cost::{ItemCost, STAGE_INGEST, StageCost, sort_by_cost};
+use talaria_core::models::IngestUsage;
+
+fn item(input: i32, output: i32, credits: Option<i64>) -> ItemCost {
+    let mut cost = ItemCost::default();
+    let usage = IngestUsage {
+        input_tokens: Some(input),
+        output_tokens: Some(output),
+    };
+    cost.record(
+        STAGE_INGEST,
+        StageCost {
+            credits,
+            ..StageCost::from_usage(&usage)
+        },
+    );
+    cost
+}
+
+#[test]
+fn totals_add_tokens_and_known_credits_per_stage() {
+    let mut totals = ItemCost::default();
+    totals.add(&item(1200, 300, Some(4)));
+    totals.add(&item(800, 100, None));
+    let ingest = &totals.stages[STAGE_INGEST];
+    assert_eq!((ingest.input_tokens, ingest.output_tokens), (2000, 400));
+    assert_eq!(totals.credits(), Some(4));
+    assert_eq!(totals.tokens(), 2400);
+    assert_eq!(item(1, 1, None).credits(), None);
+}
+
+#[test]
+fn sorting_puts_credits_before_tokens_and_unknown_last() {
+    let mut items = vec![
+        ("lamp", None),
+        ("mug", Some(item(500, 50, Some(2)))),
+        ("coat", Some(item(9000, 900, Some(2)))),
+        ("boots", Some(item(100, 10, Some(6)))),
+        ("tie", Some(item(300, 30, None))),
+    ];
+    sort_by_cost(&mut items, |(_, cost)| cost.as_ref());
+    let order: Vec<_> = items.iter().map(|(name, _)| *name).collect();
+    assert_eq!(order, ["boots", "coat", "mug", "tie", "lamp"]);
+}
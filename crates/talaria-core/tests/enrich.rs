@@ -0,0 +1,89 @@
+use futures::StreamExt;
+use serde_json::json;
+use talaria_core::client::HermesClient;
+use talaria_core::config::Config;
+use talaria_core::enrich::{EnrichTotals, enrich_many};
+use talaria_core::error::ErrorKind;
+use talaria_core::models::HsufEnrichRequest;
+use wiremock::matchers::{body_partial_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(server: &MockServer) -> HermesClient {
+    HermesClient::new(Config {
+        base_url: server.uri(),
+        api_key: Some("sk_test_123".into()),
+        ..Config::default()
+    })
+    .expect("client")
+}
+
+fn request(sku: &str) -> HsufEnrichRequest {
+    serde_json::from_value(json!({
+        "images": [format!("https://img.example/{sku}.jpg")],
+        "sku": sku,
+    }))
+    .expect("enrich request")
+}
+
+fn enriched(sku: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "product": {"name": sku, "image": "https://img.example/a.jpg", "offers": {}},
+        "usage": {"input_tokens": 100, "output_tokens": 10},
+    }))
+}
+
+#[tokio::test]
+async fn enrich_many_retries_rate_limits_and_sums_usage() {
+    let server = MockServer::start().await;
+    // The first call for B is rate limited, the next one goes through.
+    Mock::given(method("POST"))
+        .and(path("/hsuf/enrich"))
+        .and(body_partial_json(json!({"sku": "B"})))
+        .respond_with(ResponseTemplate::new(429).set_body_json(json!({"error": "slow down"})))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    for sku in ["A", "B"] {
+        Mock::given(method("POST"))
+            .and(path("/hsuf/enrich"))
+            .and(query_param("include_usage", "true"))
+            .and(body_partial_json(json!({"sku": sku})))
+            .respond_with(enriched(sku))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/hsuf/enrich"))
+        .and(body_partial_json(json!({"sku": "C"})))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({"error": "no images"})))
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let mut outcomes: Vec<_> =
+        enrich_many(&client, vec![request("A"), request("B"), request("C")], 2)
+            .collect()
+            .await;
+    outcomes.sort_by_key(|outcome| outcome.index);
+
+    let mut totals = EnrichTotals::default();
+    for outcome in &outcomes {
+        totals.add(outcome);
+    }
+    assert_eq!(
+        totals,
+        EnrichTotals {
+            succeeded: 2,
+            failed: 1,
+            rate_limited: 1,
+            input_tokens: 200,
+            output_tokens: 20,
+        }
+    );
+    assert_eq!(outcomes[1].sku.as_deref(), Some("B"));
+    assert_eq!(outcomes[1].attempts, 2);
+    assert_eq!(outcomes[1].result.as_ref().unwrap().product.name, "B");
+    let err = outcomes[2].result.as_ref().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Validation);
+}
@@ -0,0 +1,93 @@
+use talaria_core::grading::{
+    CONDITION_REPORT_HEADING, DefectNote, ProductGrading, default_rubric, strip_condition_report,
+    validate_rubric,
+};
+
+fn graded(id: &str) -> ProductGrading {
+    let rubric = default_rubric();
+    let grade = rubric.iter().find(|g| g.id == id).expect("grade");
+    ProductGrading::new(
+        grade,
+        Some("  Box shows shelf wear.  ".into()),
+        vec![
+            DefectNote {
+                note: "Scuff on left heel".into(),
+                photo: Some("images/curated/02.jpg".into()),
+            },
+            DefectNote {
+                note: "   ".into(),
+                photo: None,
+            },
+            DefectNote {
+                note: "Lace tip frayed".into(),
+                photo: Some("images/curated/09.jpg".into()),
+            },
+        ],
+    )
+}
+
+#[test]
+fn default_rubric_is_valid_and_maps_to_ebay_conditions() {
+    let rubric = default_rubric();
+    validate_rubric(&rubric).unwrap();
+    let labels: Vec<_> = rubric.iter().map(|g| g.label.as_str()).collect();
+    assert_eq!(labels, ["New", "Like New", "Good", "Acceptable"]);
+    assert_eq!(rubric[2].condition, "USED_GOOD");
+    assert_eq!(rubric[2].condition_id, 5000);
+
+    let mut dup = rubric.clone();
+    dup[1].id = "new".into();
+    assert!(validate_rubric(&dup).is_err());
+    assert!(validate_rubric(&[]).is_err());
+}
+
+#[test]
+fn listing_condition_never_lists_above_the_grade() {
+    let good = graded("good");
+    assert_eq!(good.notes.as_deref(), Some("Box shows shelf wear."));
+    assert_eq!(good.defects.len(), 2);
+
+    assert_eq!(
+        good.listing_condition(&[], &[]),
+        (Some("USED_GOOD".into()), Some(5000))
+    );
+    let allowed = ["NEW".to_string(), "USED_EXCELLENT".into(), "USED".into()];
+    // 5000 is not offered: fall to the next worse id, never up to 3000.
+    assert_eq!(
+        good.listing_condition(&allowed, &[1000, 3000, 5500]),
+        (Some("USED".into()), Some(5500))
+    );
+    // Nothing worse is offered: take the worst one there is.
+    assert_eq!(
+        good.listing_condition(&allowed[..2], &[1000, 3000]),
+        (Some("USED_EXCELLENT".into()), Some(3000))
+    );
+    // Lists that do not line up leave an unknown condition string alone.
+    assert_eq!(
+        good.listing_condition(&allowed[..1], &[1000, 6000]),
+        (None, Some(6000))
+    );
+}
+
+#[test]
+fn description_report_is_replaced_not_stacked() {
+    let photo_number = |rel: &str| (rel == "images/curated/02.jpg").then_some(3);
+    let good = graded("good");
+    let once = good.describe(Some("Trail runners, size 10."), photo_number);
+    assert_eq!(
+        once,
+        format!(
+            "Trail runners, size 10.\n\n{CONDITION_REPORT_HEADING} Good\nBox shows shelf wear.\n\
+             - Scuff on left heel (see photo 3)\n- Lace tip frayed"
+        )
+    );
+
+    let regraded = graded("acceptable").describe(Some(&once), photo_number);
+    assert!(regraded.starts_with("Trail runners, size 10.\n\nCondition report: Acceptable\n"));
+    assert_eq!(regraded.matches(CONDITION_REPORT_HEADING).count(), 1);
+    assert_eq!(strip_condition_report(&regraded), "Trail runners, size 10.");
+    assert_eq!(
+        graded("new").describe(None, |_| None),
+        "Condition report: New\nBox shows shelf wear.\n- Scuff on left heel\n- Lace tip frayed"
+    );
+}
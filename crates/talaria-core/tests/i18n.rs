@@ -0,0 +1,39 @@
+use talaria_core::i18n::{self, LOCALES, parse_catalog, placeholders};
+
+#[test]
+fn every_locale_has_the_english_messages_and_placeholders() {
+    let english = parse_catalog(LOCALES[0].1).expect("en.toml parses");
+    for (code, source) in &LOCALES[1..] {
+        let catalog = parse_catalog(source).unwrap_or_else(|err| panic!("{code}.toml: {err}"));
+        for (id, text) in &english {
+            let translated = catalog
+                .get(id)
+                .unwrap_or_else(|| panic!("{code}.toml is missing {id}"));
+            let mut want = placeholders(text);
+            let mut got = placeholders(translated);
+            want.sort_unstable();
+            got.sort_unstable();
+            assert_eq!(got, want, "{code}.toml placeholders of {id}");
+        }
+        for id in catalog.keys() {
+            assert!(english.contains_key(id), "{code}.toml has unknown id {id}");
+        }
+    }
+}
+
+#[test]
+fn locale_tags_select_bundled_locales_and_fall_back_to_english() {
+    assert_eq!(i18n::supported("de_DE.UTF-8"), Some("de"));
+    assert_eq!(i18n::supported("de-AT"), Some("de"));
+    assert_eq!(i18n::supported("C"), None);
+
+    assert_eq!(i18n::set_locale("de"), "de");
+    assert_eq!(
+        talaria_core::t!("tui.toast.copied", what = "Anfrage-ID"),
+        "Anfrage-ID kopiert."
+    );
+    assert_eq!(i18n::text("tui.no.such.message"), "tui.no.such.message");
+
+    assert_eq!(i18n::set_locale("tlh"), "en");
+    assert_eq!(talaria_core::t!("tui.tabs.home"), "Home");
+}
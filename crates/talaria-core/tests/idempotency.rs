@@ -0,0 +1,32 @@
+use serde_json::json;
+use talaria_core::idempotency::IdempotencyStore;
+
+fn temp_store_path() -> std::path::PathBuf {
+    std::env::temp_dir()
+        .join(format!("talaria-idem-{}", uuid::Uuid::new_v4()))
+        .join("idempotency.json")
+}
+
+#[test]
+fn idempotency_key_reused_until_released() {
+    let path = temp_store_path();
+    let body = json!({"sku": "SKU-1", "images_source": ["https://x/a.jpg"]});
+
+    let mut store = IdempotencyStore::open(&path).unwrap();
+    let first = store.key_for("listings", &body).unwrap();
+
+    // A fresh process sees the persisted key.
+    let mut reopened = IdempotencyStore::open(&path).unwrap();
+    assert_eq!(reopened.key_for("listings", &body).unwrap(), first);
+    assert_ne!(
+        reopened.key_for("jobs/listings", &body).unwrap(),
+        first,
+        "scope is part of the fingerprint"
+    );
+
+    reopened.release("listings", &body).unwrap();
+    let mut after = IdempotencyStore::open(&path).unwrap();
+    assert_ne!(after.key_for("listings", &body).unwrap(), first);
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
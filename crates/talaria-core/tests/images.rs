@@ -0,0 +1,66 @@
+use std::fs;
+use talaria_core::images::{DirFilter, collect_dir};
+
+fn temp_dir() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("talaria-walk-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(dir.join("angles")).unwrap();
+    fs::write(dir.join("b.jpg"), b"b").unwrap();
+    fs::write(dir.join("a.JPG"), b"a").unwrap();
+    fs::write(dir.join(".DS_Store"), b"x").unwrap();
+    fs::write(dir.join("a.CR2.xmp"), b"sidecar").unwrap();
+    fs::write(dir.join("big.png"), vec![0u8; 64]).unwrap();
+    fs::write(dir.join("angles/c.jpg"), b"c").unwrap();
+    dir
+}
+
+fn names(dir: &std::path::Path, paths: &[std::path::PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|p| {
+            p.strip_prefix(dir)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect()
+}
+
+#[test]
+fn collect_dir_filters_and_orders() {
+    let dir = temp_dir();
+
+    let flat = collect_dir(
+        &dir,
+        &DirFilter {
+            max_bytes: Some(16),
+            ..DirFilter::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(names(&dir, &flat.files), ["a.JPG", "b.jpg"]);
+    let skipped = names(
+        &dir,
+        &flat
+            .skipped
+            .iter()
+            .map(|s| s.path.clone())
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        skipped,
+        ["a.CR2.xmp", "big.png"],
+        "hidden files are not reported"
+    );
+
+    let recursive = collect_dir(
+        &dir,
+        &DirFilter {
+            include: vec!["*.jpg".into()],
+            exclude: vec!["b.*".into()],
+            recursive: true,
+            max_bytes: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(names(&dir, &recursive.files), ["a.JPG", "angles/c.jpg"]);
+}
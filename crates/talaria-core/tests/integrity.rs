@@ -0,0 +1,121 @@
+use talaria_core::integrity::{ServedCheck, verify_image_urls, verify_served};
+use talaria_core::upload_cache::sha256_hex;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn verify_served_checks_headers_then_rehashes_on_spot_check() {
+    let server = MockServer::start().await;
+    let body = b"jpeg bytes".to_vec();
+    let sha = sha256_hex(&body);
+    for verb in ["HEAD", "GET"] {
+        Mock::given(method(verb))
+            .and(path("/good.jpg"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", format!("\"{sha}\"").as_str())
+                    .set_body_bytes(body.clone()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method(verb))
+            .and(path("/swapped.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"other byte".to_vec()))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("HEAD"))
+        .and(path("/short.jpg"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"jpeg".to_vec()))
+        .mount(&server)
+        .await;
+
+    let http = reqwest::Client::new();
+    let url = |name: &str| format!("{}/{name}", server.uri());
+    let len = body.len() as u64;
+
+    let check = verify_served(&http, &url("good.jpg"), &sha, len, false).await;
+    assert_eq!(check.unwrap(), ServedCheck::Headers);
+    let check = verify_served(&http, &url("good.jpg"), &sha, len, true).await;
+    assert_eq!(check.unwrap(), ServedCheck::Rehashed);
+
+    // Same size and no usable ETag: only the re-hash catches it.
+    let check = verify_served(&http, &url("swapped.jpg"), &sha, len, false).await;
+    assert_eq!(check.unwrap(), ServedCheck::Headers);
+    let check = verify_served(&http, &url("swapped.jpg"), &sha, len, true).await;
+    assert!(matches!(check.unwrap(), ServedCheck::Mismatch(why) if why.contains("served sha256")));
+
+    let check = verify_served(&http, &url("short.jpg"), &sha, len, false).await;
+    assert!(matches!(check.unwrap(), ServedCheck::Mismatch(why) if why.contains("served 4 bytes")));
+
+    let wrong = "0".repeat(64);
+    let check = verify_served(&http, &url("good.jpg"), &wrong, len, false).await;
+    assert!(matches!(check.unwrap(), ServedCheck::Mismatch(why) if why.contains("ETag")));
+
+    assert!(
+        verify_served(&http, &url("missing.jpg"), &sha, len, false)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn verify_image_urls_reports_broken_urls_in_order() {
+    let server = MockServer::start().await;
+    let head = |route: &str, response: ResponseTemplate| {
+        Mock::given(method("HEAD"))
+            .and(path(route.to_string()))
+            .respond_with(response)
+    };
+    head(
+        "/ok.jpg",
+        ResponseTemplate::new(200).insert_header("content-type", "image/jpeg"),
+    )
+    .mount(&server)
+    .await;
+    head("/forbidden.jpg", ResponseTemplate::new(403))
+        .mount(&server)
+        .await;
+    head(
+        "/page.jpg",
+        ResponseTemplate::new(200).insert_header("content-type", "text/html; charset=utf-8"),
+    )
+    .mount(&server)
+    .await;
+    head(
+        "/huge.jpg",
+        ResponseTemplate::new(200).set_body_bytes(vec![0u8; 64]),
+    )
+    .mount(&server)
+    .await;
+    head("/no-head.jpg", ResponseTemplate::new(405))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/no-head.jpg"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-type", "image/png"))
+        .mount(&server)
+        .await;
+
+    let url = |name: &str| format!("{}/{name}", server.uri());
+    let urls: Vec<String> = [
+        "ok.jpg",
+        "forbidden.jpg",
+        "page.jpg",
+        "huge.jpg",
+        "no-head.jpg",
+    ]
+    .iter()
+    .map(|name| url(name))
+    .collect();
+    let broken = verify_image_urls(&reqwest::Client::new(), &urls, 32).await;
+
+    let reported: Vec<_> = broken.iter().map(|b| b.url.as_str()).collect();
+    assert_eq!(
+        reported,
+        [url("forbidden.jpg"), url("page.jpg"), url("huge.jpg")]
+    );
+    assert!(broken[0].reason.contains("403"));
+    assert!(broken[1].reason.contains("text/html"));
+    assert!(broken[2].reason.contains("64 bytes"));
+}
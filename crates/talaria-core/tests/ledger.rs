@@ -0,0 +1,128 @@
+use serde_json::json;
+use talaria_core::ledger::{JobLedger, JobLedgerEntry, TrackedState};
+use talaria_core::models::PublicListingRequest;
+
+fn temp_ledger() -> JobLedger {
+    JobLedger::open(
+        std::env::temp_dir()
+            .join(format!("talaria-ledger-{}", uuid::Uuid::new_v4()))
+            .join("jobs.jsonl"),
+    )
+}
+
+fn request(sku: &str) -> PublicListingRequest {
+    serde_json::from_value(json!({
+        "sku": sku,
+        "images_source": ["https://x/a.jpg"],
+        "merchant_location_key": "loc-1",
+        "fulfillment_policy_id": "pol-f",
+        "payment_policy_id": "pol-p",
+        "return_policy_id": "pol-r",
+    }))
+    .unwrap()
+}
+
+#[test]
+fn state_updates_fold_onto_entries() {
+    let ledger = temp_ledger();
+    ledger.record("job_1", &request("SKU-1")).unwrap();
+    ledger.record("job_2", &request("SKU-2")).unwrap();
+    ledger
+        .set_state("job_1", TrackedState::Failed, Some("aspects: timeout"))
+        .unwrap();
+    ledger
+        .set_state("job_2", TrackedState::Completed, None)
+        .unwrap();
+
+    let recent = ledger.recent(10).unwrap();
+    assert_eq!(
+        recent
+            .iter()
+            .map(|e| (e.job_id.as_str(), e.state))
+            .collect::<Vec<_>>(),
+        [
+            ("job_2", TrackedState::Completed),
+            ("job_1", TrackedState::Failed)
+        ]
+    );
+    let failed = ledger.failed().unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].error.as_deref(), Some("aspects: timeout"));
+    assert_eq!(
+        failed[0].request.as_ref().unwrap().sku.as_deref(),
+        Some("SKU-1")
+    );
+
+    // Retrying links the new job and takes the old one off the queue.
+    let retry = ledger
+        .append(JobLedgerEntry {
+            retry_of: Some("job_1".into()),
+            ..JobLedgerEntry::new("job_3", failed[0].request.as_ref().unwrap())
+        })
+        .unwrap();
+    ledger
+        .set_state("job_1", TrackedState::Retried, None)
+        .unwrap();
+    assert!(ledger.failed().unwrap().is_empty());
+    let job_3 = ledger.get("job_3").unwrap().unwrap();
+    assert_eq!(job_3.retry_of.as_deref(), Some("job_1"));
+    assert_eq!(job_3.state, TrackedState::Pending);
+    assert_eq!(retry.sku.as_deref(), Some("SKU-1"));
+
+    let _ = std::fs::remove_dir_all(ledger.path().parent().unwrap());
+}
+
+#[test]
+fn entries_without_requests_are_not_retryable() {
+    let ledger = temp_ledger();
+    std::fs::create_dir_all(ledger.path().parent().unwrap()).unwrap();
+    std::fs::write(
+        ledger.path(),
+        concat!(
+            r#"{"job_id":"old","sku":null,"enqueued_at":"2026-01-02T03:04:05Z"}"#,
+            "\n",
+            "not json\n",
+            r#"{"job_id":"old","state":"failed","at":"2026-01-02T03:05:00Z"}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+
+    let recent = ledger.recent(10).unwrap();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].state, TrackedState::Failed);
+    assert!(!recent[0].retryable());
+    assert!(ledger.failed().unwrap().is_empty());
+
+    let _ = std::fs::remove_dir_all(ledger.path().parent().unwrap());
+}
+
+#[test]
+fn recent_is_newest_first_up_to_the_limit() {
+    let ledger = temp_ledger();
+    assert!(ledger.recent(5).unwrap().is_empty());
+    for id in ["job_1", "job_2", "job_3"] {
+        ledger.record(id, &request(id)).unwrap();
+    }
+    ledger
+        .set_state("job_1", TrackedState::Completed, None)
+        .unwrap();
+    // Only the first outcome settles a job.
+    ledger
+        .set_state("job_1", TrackedState::Failed, Some("late"))
+        .unwrap();
+
+    let ids =
+        |entries: Vec<JobLedgerEntry>| entries.into_iter().map(|e| e.job_id).collect::<Vec<_>>();
+    assert_eq!(ids(ledger.recent(2).unwrap()), ["job_3", "job_2"]);
+    assert_eq!(ids(ledger.recent(10).unwrap()), ["job_3", "job_2", "job_1"]);
+
+    let job_1 = ledger.get("job_1").unwrap().unwrap();
+    assert_eq!(job_1.state, TrackedState::Failed);
+    assert_eq!(job_1.error.as_deref(), Some("late"));
+    assert!(job_1.settled_at.is_some());
+    assert!(ledger.get("job_2").unwrap().unwrap().settled_at.is_none());
+    assert!(ledger.get("nope").unwrap().is_none());
+
+    let _ = std::fs::remove_dir_all(ledger.path().parent().unwrap());
+}
@@ -0,0 +1,69 @@
+use serde_json::Value;
+use talaria_core::logging::{Filter, JsonLogger, RotatingFile};
+use tracing::Level;
+
+#[test]
+fn filter_directives_pick_the_longest_matching_target() {
+    let filter: Filter = "warn,talaria_core=info,talaria_core::client=debug"
+        .parse()
+        .expect("filter");
+    assert!(filter.enabled("talaria_core::client", &Level::DEBUG));
+    assert!(!filter.enabled("talaria_core::client", &Level::TRACE));
+    assert!(filter.enabled("talaria_core::ledger", &Level::INFO));
+    assert!(!filter.enabled("talaria_core::ledger", &Level::DEBUG));
+    assert!(!filter.enabled("talaria_core_extra", &Level::INFO));
+    assert!(filter.enabled("talaria_tui::workers::upload", &Level::WARN));
+    assert!(!filter.enabled("talaria_tui::workers::upload", &Level::INFO));
+
+    assert!("info,talaria_core=loud".parse::<Filter>().is_err());
+    assert!("=debug".parse::<Filter>().is_err());
+}
+
+#[test]
+fn events_are_json_lines_with_span_fields_and_rotate() {
+    let dir = std::env::temp_dir().join(format!("talaria-logging-{}", uuid::Uuid::new_v4()));
+    let path = dir.join("talaria-test.log");
+    let logger = |filter: &str| {
+        let out = RotatingFile::open(&path, 400, 2).expect("log file");
+        JsonLogger::new("talaria-test", filter.parse().expect("filter"), out)
+    };
+
+    tracing::subscriber::with_default(logger("info,talaria_core::client=debug"), || {
+        let _span = tracing::info_span!("upload", product_id = "prod-1").entered();
+        tracing::warn!(
+            target: "talaria_core::client",
+            request_id = "req-42",
+            status = 502u64,
+            "hermes request failed"
+        );
+        tracing::debug!(target: "talaria_core::ledger", "filtered out");
+    });
+    let text = std::fs::read_to_string(&path).expect("log file");
+    assert_eq!(text.lines().count(), 1);
+    let line: Value = serde_json::from_str(text.trim_end()).expect("json");
+    assert_eq!(line["level"], "WARN");
+    assert_eq!(line["target"], "talaria_core::client");
+    assert_eq!(line["app"], "talaria-test");
+    assert_eq!(line["message"], "hermes request failed");
+    assert_eq!(line["fields"]["request_id"], "req-42");
+    assert_eq!(line["fields"]["status"], 502);
+    assert_eq!(line["spans"][0]["name"], "upload");
+    assert_eq!(line["spans"][0]["product_id"], "prod-1");
+
+    tracing::subscriber::with_default(logger("info"), || {
+        for n in 0..10u64 {
+            tracing::info!(target: "talaria_tui::workers::upload", n, "upload started");
+        }
+    });
+    let rotated = |n: usize| std::path::PathBuf::from(format!("{}.{n}", path.display()));
+    let mut lines = 0;
+    for file in [path.clone(), rotated(1), rotated(2)] {
+        let text = std::fs::read_to_string(&file).expect("log file");
+        assert!(text.len() <= 400);
+        lines += text.lines().count();
+    }
+    assert!(!rotated(3).exists());
+    assert!(lines < 11, "the oldest lines rotate away");
+
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,103 @@
+use chrono::Utc;
+use serde_json::json;
+use talaria_core::measure::{LengthUnit, MeasuredDimensions, MeasurementConfig};
+
+const W: usize = 400;
+const H: usize = 300;
+
+/// Light paper with 2px dark lines every `period` pixels, plus some texture.
+fn grid(period: f64) -> Vec<u8> {
+    let on_line = |pos: usize| {
+        let phase = (pos as f64 + 3.0) % period;
+        phase < 2.0
+    };
+    (0..W * H)
+        .map(|idx| {
+            let (x, y) = (idx % W, idx / W);
+            if on_line(x) || on_line(y) {
+                60
+            } else {
+                220 - ((x * 7 + y * 13) % 11) as u8
+            }
+        })
+        .collect()
+}
+
+/// A dark square of `side` pixels with light "bit" cells inside, on grid paper.
+fn marker(side: usize) -> Vec<u8> {
+    let mut luma = grid(40.0);
+    let (left, top) = (150, 80);
+    let cell = side / 6;
+    for y in top..top + side {
+        for x in left..left + side {
+            let (cx, cy) = ((x - left) / cell, (y - top) / cell);
+            let inner = (1..5).contains(&cx) && (1..5).contains(&cy);
+            luma[y * W + x] = if inner && (cx + cy) % 2 == 0 { 235 } else { 15 };
+        }
+    }
+    luma
+}
+
+#[test]
+fn calibrates_from_a_grid_or_a_marker() {
+    let config = MeasurementConfig {
+        grid_square: Some(0.5),
+        ..Default::default()
+    };
+    let ppu = config.calibrate(W, H, &grid(18.5)).unwrap().unwrap();
+    assert!((ppu - 37.0).abs() < 0.3, "{ppu}");
+
+    let config = MeasurementConfig {
+        unit: LengthUnit::Centimeter,
+        marker_size: Some(3.0),
+        ..config
+    };
+    let ppu = config.calibrate(W, H, &marker(96)).unwrap().unwrap();
+    assert!((ppu - 32.0).abs() < 0.01, "{ppu}");
+
+    // Nothing to find, and nothing to calibrate against.
+    assert_eq!(config.calibrate(W, H, &vec![128; W * H]).unwrap(), None);
+    assert!(
+        MeasurementConfig::default()
+            .calibrate(W, H, &grid(20.0))
+            .is_err()
+    );
+    let bad = MeasurementConfig {
+        pixels_per_unit: Some(0.0),
+        ..Default::default()
+    };
+    assert!(bad.validate().is_err());
+}
+
+#[test]
+fn dimensions_override_structure_and_package() {
+    let partial = MeasuredDimensions {
+        unit: LengthUnit::Inch,
+        width: Some(12.5),
+        height: Some(8.004),
+        depth: None,
+        measured_at: Utc::now(),
+    };
+    assert_eq!(partial.summary(), "W 12.5 × H 8 in");
+    assert_eq!(partial.package_sides(), None);
+
+    let mut structure = json!({
+        "name": "Camera bag",
+        "width": { "value": 10, "unit_code": "CENTIMETER" },
+        "depth": { "value": 4, "unit_code": "CENTIMETER" }
+    });
+    partial.apply_to_structure(&mut structure);
+    assert_eq!(
+        structure["width"],
+        json!({ "value": 12.5, "unit_code": "INCH" })
+    );
+    assert_eq!(structure["height"]["value"], json!(8.0));
+    // Sides that were not measured keep the enriched value.
+    assert_eq!(structure["depth"]["unit_code"], "CENTIMETER");
+
+    let full = MeasuredDimensions {
+        depth: Some(14.0),
+        ..partial
+    };
+    assert_eq!(full.package_sides(), Some([14.0, 12.5, 8.0]));
+}
@@ -0,0 +1,88 @@
+use serde_json::json;
+use std::time::Duration;
+use talaria_core::client::HermesClient;
+use talaria_core::config::Config;
+use talaria_core::metrics::{self, Registry};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn requests_and_retries_are_counted_by_endpoint_and_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503).insert_header("retry-after", "0"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"status": "ok", "service": "hermes"})),
+        )
+        .mount(&server)
+        .await;
+
+    let hermes = HermesClient::new(Config {
+        base_url: server.uri(),
+        ..Config::default()
+    })
+    .expect("client");
+    hermes.health().await.expect("health");
+
+    let snapshot = metrics::global().snapshot();
+    let requests = |status: &str| {
+        snapshot.counters[&metrics::series(
+            metrics::HERMES_REQUESTS,
+            &[("endpoint", "health"), ("status", status)],
+        )]
+    };
+    assert_eq!(requests("503"), 1);
+    assert_eq!(requests("200"), 1);
+    assert_eq!(
+        snapshot.counters[&metrics::series(metrics::HERMES_RETRIES, &[("endpoint", "health")])],
+        1
+    );
+    let timing = snapshot.timings
+        [&metrics::series(metrics::HERMES_REQUEST_DURATION, &[("endpoint", "health")])];
+    assert_eq!(timing.count, 2);
+}
+
+#[test]
+fn totals_accumulate_across_flushes_and_render_as_prometheus() {
+    assert_eq!(
+        metrics::endpoint_label("v1/products/3f1c2a9e-0000-4000-8000-000000000001/media"),
+        "v1/products/:id/media"
+    );
+
+    let dir = std::env::temp_dir().join(format!("talaria-metrics-{}", uuid::Uuid::new_v4()));
+    let totals_path = dir.join("metrics.json");
+    let registry = Registry::default();
+    for _ in 0..2 {
+        registry.incr(metrics::UPLOAD_BYTES, &[("target", "supabase")], 1024);
+        registry.set_gauge(metrics::CAPTURE_FPS, &[], 29.5);
+        registry.observe(
+            metrics::JOB_DURATION,
+            &[("outcome", "completed")],
+            Duration::from_secs(4),
+        );
+        metrics::flush_totals(&registry, &totals_path).expect("flush");
+    }
+    assert!(registry.snapshot().is_empty());
+
+    let totals = metrics::load_totals(&totals_path).expect("load");
+    assert_eq!(
+        totals.counters[r#"talaria_upload_bytes_total{target="supabase"}"#],
+        2048
+    );
+    let job = totals.timings[r#"talaria_job_duration_seconds{outcome="completed"}"#];
+    assert_eq!((job.count, job.mean_secs()), (2, 4.0));
+
+    let text = totals.to_prometheus();
+    assert!(text.contains("# TYPE talaria_upload_bytes_total counter\n"));
+    assert!(text.contains("talaria_capture_fps 29.5\n"));
+    assert!(text.contains(r#"talaria_job_duration_seconds_count{outcome="completed"} 2"#));
+    assert!(text.contains(r#"talaria_job_duration_seconds_sum{outcome="completed"} 8"#));
+
+    let _ = std::fs::remove_dir_all(dir);
+}
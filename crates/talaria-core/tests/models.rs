@@ -48,3 +48,75 @@ fn job_state_completed_roundtrip() {
         _ => panic!("unexpected variant"),
     }
 }
+
+#[test]
+fn marketplace_id_unknown_roundtrip() {
+    let known: MarketplaceId = serde_json::from_str(r#""EBAY_FR""#).unwrap();
+    assert_eq!(known, MarketplaceId::EbayFr);
+
+    let unknown: MarketplaceId = serde_json::from_str(r#""EBAY_JP""#).unwrap();
+    assert_eq!(unknown, MarketplaceId::Unknown("EBAY_JP".into()));
+    assert!(!unknown.is_known());
+    assert_eq!(serde_json::to_string(&unknown).unwrap(), r#""EBAY_JP""#);
+}
+
+#[test]
+fn open_enums_roundtrip_unknown_values() {
+    let model: LlmModel = serde_json::from_str(r#""gpt-6""#).unwrap();
+    assert_eq!(model, LlmModel::Unknown("gpt-6".into()));
+    assert_eq!(serde_json::to_string(&model).unwrap(), r#""gpt-6""#);
+    assert_eq!(LlmModel::from("gpt-5-nano"), LlmModel::Gpt5Nano);
+
+    let purpose: MediaPurpose = serde_json::from_str(r#""thumbnail""#).unwrap();
+    assert_eq!(purpose.as_str(), "thumbnail");
+    assert!(!purpose.is_known());
+
+    let status: DeviceAuthStatus = serde_json::from_str(r#""denied""#).unwrap();
+    assert_eq!(serde_json::to_string(&status).unwrap(), r#""denied""#);
+    let status: DeviceAuthStatus = serde_json::from_str(r#""authorized""#).unwrap();
+    assert_eq!(status, DeviceAuthStatus::Authorized);
+
+    let availability: OfferAvailability = serde_json::from_str(r#""discontinued""#).unwrap();
+    assert_eq!(
+        availability,
+        OfferAvailability::Unknown("discontinued".into())
+    );
+    assert_eq!(
+        serde_json::to_string(&availability).unwrap(),
+        r#""discontinued""#
+    );
+    let availability: OfferAvailability = serde_json::from_str(r#""pre_order""#).unwrap();
+    assert_eq!(availability, OfferAvailability::PreOrder);
+}
+
+#[test]
+fn job_state_unknown_roundtrips() {
+    let state: JobState = serde_json::from_str(r#"{"state":"paused"}"#).unwrap();
+    assert!(matches!(&state, JobState::Unknown { state } if state == "paused"));
+    assert_eq!(
+        serde_json::to_string(&state).unwrap(),
+        r#"{"state":"paused"}"#
+    );
+
+    let state: JobState = serde_json::from_str(r#"{"state":"running"}"#).unwrap();
+    assert!(matches!(state, JobState::Running {}));
+
+    // Flattened into a job, the raw state survives next to the other fields.
+    let job: JobInfo = serde_json::from_value(serde_json::json!({
+        "id": "job_1",
+        "state": "paused",
+        "created_at": "2026-01-01T00:00:00Z",
+        "updated_at": "2026-01-01T00:01:00Z",
+        "request": {
+            "fulfillment_policy_id": "1",
+            "payment_policy_id": "2",
+            "return_policy_id": "3",
+            "merchant_location_key": "home",
+            "images_source": "https://img.example/a.jpg",
+        },
+    }))
+    .unwrap();
+    let json = serde_json::to_value(&job).unwrap();
+    assert_eq!(json["state"], "paused");
+    assert_eq!(json["id"], "job_1");
+}
@@ -0,0 +1,21 @@
+use serde_json::json;
+use talaria_core::notify::job_payload;
+
+#[test]
+fn job_payload_plain_and_slack() {
+    let summary = json!({
+        "id": "job-1",
+        "state": "failed",
+        "terminal": true,
+        "elapsed_secs": 42,
+        "job": { "error": "pipeline crashed" },
+    });
+
+    assert_eq!(job_payload(&summary, false), summary);
+
+    let slack = job_payload(&summary, true);
+    assert!(slack["text"].as_str().unwrap().contains("job-1"));
+    assert_eq!(slack["attachments"][0]["color"], "danger");
+    let fields = slack["attachments"][0]["fields"].as_array().unwrap();
+    assert!(fields.iter().any(|f| f["value"] == "pipeline crashed"));
+}
@@ -0,0 +1,89 @@
+use talaria_core::error::Error;
+use talaria_core::package::{
+    PackageTemplate, parse_dimensions, parse_weight, resolve_package, validate_templates,
+};
+
+fn small_box() -> PackageTemplate {
+    PackageTemplate {
+        name: "small-box".into(),
+        weight: Some("12oz".into()),
+        dims: Some("10x8x4in".into()),
+    }
+}
+
+#[test]
+fn parses_weights_and_dimensions_with_units() {
+    let weight = |text| {
+        let w = parse_weight(text).unwrap();
+        (w.value, w.unit)
+    };
+    assert_eq!(weight("12oz"), (12, "OUNCE".into()));
+    assert_eq!(weight(" 2 LB "), (2, "POUND".into()));
+    assert_eq!(weight("1.5lb"), (24, "OUNCE".into()));
+    assert_eq!(weight("0.25 kg"), (250, "GRAM".into()));
+    assert_eq!(weight("10.2 OUNCE"), (11, "OUNCE".into()));
+
+    let dims = parse_dimensions("10 x 8.25 x 4.01 CM").unwrap();
+    assert_eq!(
+        (dims.length, dims.width, dims.height, dims.unit.as_str()),
+        (10.0, 8.3, 4.1, "CENTIMETER")
+    );
+    assert_eq!(parse_dimensions("6x4x2inches").unwrap().unit, "INCH");
+
+    for bad in ["12", "12 stone", "oz", "-3oz", "0lb"] {
+        assert!(
+            matches!(parse_weight(bad), Err(Error::InvalidPackage(_))),
+            "{bad}"
+        );
+    }
+    for bad in ["10x8x4", "10x8in", "10x8x4x2in", "10x8x4ft", "10xx4in"] {
+        assert!(parse_dimensions(bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn templates_resolve_and_explicit_values_win() {
+    let templates = vec![
+        small_box(),
+        PackageTemplate {
+            name: "envelope".into(),
+            weight: Some("3oz".into()),
+            dims: None,
+        },
+    ];
+    validate_templates(&templates).unwrap();
+
+    let package = resolve_package(&templates, Some("Small-Box"), Some("1lb"), None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(package.weight.unwrap().unit, "POUND");
+    assert_eq!(package.dimensions.unwrap().length, 10.0);
+
+    let package = resolve_package(&templates, None, None, Some("9x6x1in"))
+        .unwrap()
+        .unwrap();
+    assert!(package.weight.is_none());
+    assert!(
+        resolve_package(&templates, None, None, None)
+            .unwrap()
+            .is_none()
+    );
+
+    let err = resolve_package(&templates, Some("crate"), None, None).unwrap_err();
+    assert!(
+        err.to_string().contains("known: small-box, envelope"),
+        "{err}"
+    );
+
+    let mut dup = templates.clone();
+    dup[1].name = "small-box".into();
+    assert!(validate_templates(&dup).is_err());
+    let bad = [PackageTemplate {
+        dims: Some("10x8x4 furlongs".into()),
+        ..small_box()
+    }];
+    assert!(matches!(
+        validate_templates(&bad),
+        Err(Error::InvalidConfig(_))
+    ));
+}
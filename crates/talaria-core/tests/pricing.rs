@@ -0,0 +1,107 @@
+use serde_json::json;
+use talaria_core::client::HermesClient;
+use talaria_core::config::Config;
+use talaria_core::models::{ComparablePrice, ComparablePricesRequest};
+use talaria_core::pricing::{PricingRules, suggest_price};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn comp(price: f64, currency: &str, sold: Option<bool>) -> ComparablePrice {
+    ComparablePrice {
+        title: None,
+        price,
+        currency: Some(currency.into()),
+        condition: None,
+        sold,
+        url: None,
+    }
+}
+
+#[test]
+fn range_uses_percentiles_and_margin_rules() {
+    let comps: Vec<_> = [10.0, 20.0, 30.0, 40.0, 50.0]
+        .into_iter()
+        .map(|price| comp(price, "USD", None))
+        .chain([comp(500.0, "GBP", None), comp(-1.0, "USD", None)])
+        .collect();
+
+    let plain = suggest_price(&comps, Some("USD"), None, &PricingRules::default()).unwrap();
+    assert_eq!(
+        (plain.low, plain.suggested, plain.high, plain.comparables),
+        (20.0, 30.0, 40.0, 5)
+    );
+
+    let rules = PricingRules {
+        margin_pct: 10.0,
+        condition_factors: [("USED_GOOD".to_string(), 0.5)].into(),
+        min_price: Some(12.0),
+        charm: true,
+        ..PricingRules::default()
+    };
+    let used = suggest_price(&comps, Some("USD"), Some("used_good"), &rules).unwrap();
+    // 20/30/40 * 0.55 = 11/16.5/22, floored at 12, ending in .99.
+    assert_eq!((used.low, used.suggested, used.high), (11.99, 16.99, 21.99));
+    assert!((used.adjustment - 0.55).abs() < 1e-9);
+
+    // Sold items win over asking prices when there are any.
+    let mixed = [
+        comp(100.0, "USD", Some(false)),
+        comp(40.0, "USD", Some(true)),
+    ];
+    let sold = suggest_price(&mixed, None, None, &PricingRules::default()).unwrap();
+    assert_eq!((sold.suggested, sold.comparables), (40.0, 1));
+    assert!(suggest_price(&comps[5..6], Some("USD"), None, &PricingRules::default()).is_none());
+}
+
+#[test]
+fn invalid_rules_are_rejected() {
+    PricingRules::default().validate().unwrap();
+    for rules in [
+        PricingRules {
+            low_percentile: 80.0,
+            high_percentile: 20.0,
+            ..PricingRules::default()
+        },
+        PricingRules {
+            margin_pct: -100.0,
+            ..PricingRules::default()
+        },
+        PricingRules {
+            condition_factors: [("NEW".to_string(), 0.0)].into(),
+            ..PricingRules::default()
+        },
+    ] {
+        assert!(rules.validate().is_err(), "{rules:?}");
+    }
+}
+
+#[tokio::test]
+async fn comparable_prices_posts_item_details() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/pricing/comparables"))
+        .and(body_partial_json(json!({"brand": "Nikon", "mpn": "FM2"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "currency": "USD",
+            "comparables": [{"title": "Nikon FM2 body", "price": 310.0, "sold": true}],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let hermes = HermesClient::new(Config {
+        base_url: server.uri(),
+        api_key: Some("sk_test".into()),
+        ..Config::default()
+    })
+    .unwrap();
+    let resp = hermes
+        .comparable_prices(&ComparablePricesRequest {
+            brand: Some("Nikon".into()),
+            mpn: Some("FM2".into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(resp.comparables[0].price, 310.0);
+}
@@ -0,0 +1,58 @@
+use serde_json::json;
+use talaria_core::product_diff::{self, ProductFields};
+
+fn fields(display_name: &str, listings: serde_json::Value) -> ProductFields {
+    ProductFields {
+        sku_alias: "SKU-1".to_string(),
+        display_name: Some(display_name.to_string()),
+        listings_json: listings,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn diff_walks_into_listings_and_ignores_number_form() {
+    let local = ProductFields::from_manifest(&json!({
+        "sku_alias": "SKU-1",
+        "display_name": "Lamp",
+        "context_text": "  ",
+        "listings": {"EBAY_US": {"price": 25.0, "images": ["a", "b"]}},
+    }));
+    let remote = fields(
+        "Brass lamp",
+        json!({"EBAY_US": {"price": 25, "images": ["a", "c"]}}),
+    );
+    let paths: Vec<_> = product_diff::diff(&local, &remote)
+        .into_iter()
+        .map(|diff| diff.path)
+        .collect();
+    assert_eq!(paths, ["display_name", "listings_json.EBAY_US.images[1]"]);
+    assert!(product_diff::diff(&remote, &remote).is_empty());
+}
+
+#[test]
+fn merge_takes_each_sides_changes_and_reports_overlaps() {
+    let base = fields("Lamp", json!({"EBAY_US": {"price": 25, "title": "Lamp"}}));
+    let local = fields(
+        "Brass lamp",
+        json!({"EBAY_US": {"price": 20, "title": "Lamp"}}),
+    );
+    let remote = fields(
+        "Lamp",
+        json!({"EBAY_US": {"price": 30, "title": "Old lamp"}}),
+    );
+
+    let conflicts = product_diff::conflicts(&base, &local, &remote);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].path, "listings_json.EBAY_US.price");
+    assert_eq!(conflicts[0].field(), "listings_json");
+    assert_eq!(conflicts[0].remote, Some(json!(30)));
+
+    let merged = product_diff::merge(&base, &local, &remote);
+    assert_eq!(merged.display_name.as_deref(), Some("Brass lamp"));
+    assert_eq!(
+        merged.listings_json,
+        json!({"EBAY_US": {"price": 20, "title": "Old lamp"}})
+    );
+    assert!(product_diff::conflicts(&base, &local, &merged).is_empty());
+}
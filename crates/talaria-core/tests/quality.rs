@@ -0,0 +1,96 @@
+use talaria_core::quality::{FindingKind, ImageMetrics, QualityReport, QualityRules};
+
+/// A `size` x `size` checkerboard of `cell` px squares between `low` and `high`.
+fn checkerboard(size: usize, cell: usize, low: u8, high: u8) -> Vec<u8> {
+    (0..size * size)
+        .map(|i| {
+            let (x, y) = (i % size, i / size);
+            if (x / cell + y / cell).is_multiple_of(2) {
+                low
+            } else {
+                high
+            }
+        })
+        .collect()
+}
+
+fn kinds(report: &QualityReport, path: &str) -> Vec<FindingKind> {
+    report
+        .findings
+        .iter()
+        .filter(|finding| finding.path == std::path::Path::new(path))
+        .map(|finding| finding.kind)
+        .collect()
+}
+
+#[test]
+fn sharp_well_exposed_photo_passes() {
+    let luma = checkerboard(800, 8, 60, 190);
+    let metrics = ImageMetrics::from_luma(800, 800, &luma);
+    let mut report = QualityReport::default();
+    report.add("a.jpg".into(), metrics, &QualityRules::default());
+    assert!(report.passed(), "{:?}", report.findings);
+}
+
+#[test]
+fn small_blurry_clipped_and_duplicate_photos_are_reported() {
+    let rules = QualityRules::default();
+    let mut report = QualityReport::default();
+
+    let sharp = checkerboard(800, 8, 60, 190);
+    report.add(
+        "front.jpg".into(),
+        ImageMetrics::from_luma(800, 800, &sharp),
+        &rules,
+    );
+    report.add(
+        "front-again.jpg".into(),
+        ImageMetrics::from_luma(800, 800, &sharp),
+        &rules,
+    );
+
+    let small = checkerboard(300, 4, 60, 190);
+    report.add(
+        "thumb.jpg".into(),
+        ImageMetrics::from_luma(300, 300, &small),
+        &rules,
+    );
+
+    // A smooth gradient has no edges to speak of.
+    let soft: Vec<u8> = (0..800 * 800).map(|i| 40 + ((i % 800) / 8) as u8).collect();
+    report.add(
+        "soft.jpg".into(),
+        ImageMetrics::from_luma(800, 800, &soft),
+        &rules,
+    );
+
+    let dark = checkerboard(800, 8, 0, 3);
+    report.add(
+        "dark.jpg".into(),
+        ImageMetrics::from_luma(800, 800, &dark),
+        &rules,
+    );
+
+    assert!(kinds(&report, "front.jpg").is_empty());
+    assert_eq!(kinds(&report, "front-again.jpg"), [FindingKind::Duplicate]);
+    assert_eq!(kinds(&report, "thumb.jpg"), [FindingKind::LowResolution]);
+    assert!(kinds(&report, "soft.jpg").contains(&FindingKind::Blurry));
+    assert!(kinds(&report, "dark.jpg").contains(&FindingKind::Underexposed));
+    assert!(!report.passed());
+}
+
+#[test]
+fn quality_rules_are_validated() {
+    let rules = QualityRules {
+        max_bright_clipped: 1.5,
+        ..QualityRules::default()
+    };
+    assert!(
+        rules
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("max_bright_clipped")
+    );
+    QualityRules::default().validate().unwrap();
+}
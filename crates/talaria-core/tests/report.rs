@@ -0,0 +1,60 @@
+use serde_json::json;
+use talaria_core::models::ListingResponse;
+use talaria_core::report::{self, ReportFormat};
+
+fn response() -> ListingResponse {
+    serde_json::from_value(json!({
+        "listing_id": "lst_1",
+        "stages": [
+            {
+                "name": "category",
+                "elapsed_ms": 120,
+                "timestamp": "2026-01-01T00:00:00Z",
+                "output": {
+                    "summary": "Mugs | Cups",
+                    "selected": {"id": "20625", "label": "Mugs & Cups"},
+                },
+            },
+            {
+                "name": "listing",
+                "elapsed_ms": 880,
+                "timestamp": "2026-01-01T00:00:01Z",
+                "output": {
+                    "title": "Blue <Ceramic> Mug",
+                    "price": 12.5,
+                    "currency": "USD",
+                    "condition": "Used",
+                    "aspects": {"Color": ["Blue"], "Material": ["Ceramic", "Glaze"]},
+                    "images": ["https://img.example/a.jpg", {"url": "https://img.example/b.jpg?x=1&y=2"}],
+                    "warnings": ["brand not detected"],
+                },
+            },
+        ],
+    }))
+    .expect("listing response")
+}
+
+#[test]
+fn markdown_report_has_fields_aspects_thumbnails_warnings_and_timings() {
+    let text = report::render(&response(), ReportFormat::Markdown);
+    assert!(text.starts_with("# Blue &lt;Ceramic> Mug\n"));
+    assert!(text.contains("| Price | 12.50 USD |"));
+    assert!(text.contains("| Category | Mugs & Cups |"));
+    assert!(text.contains("| Material | Ceramic, Glaze |"));
+    assert!(text.contains(r#"<img src="https://img.example/b.jpg?x=1&amp;y=2" alt="image 2""#));
+    assert!(text.contains("- **listing**: brand not detected"));
+    assert!(text.contains("| category | 120 | Mugs \\| Cups |"));
+    assert!(text.contains("| **Total** | **1000** | |"));
+}
+
+#[test]
+fn html_report_is_a_standalone_escaped_page() {
+    let text = report::render(&response(), ReportFormat::Html);
+    assert!(text.starts_with("<!DOCTYPE html>"));
+    assert!(text.contains("<h1>Blue &lt;Ceramic&gt; Mug</h1>"));
+    assert!(text.contains("<tr><th>Condition</th><td>Used</td></tr>"));
+    assert!(text.contains("<li><strong>listing</strong>: brand not detected</li>"));
+    assert!(text.contains(r#"<td class="num">880</td>"#));
+    assert!(!text.contains("<Ceramic>"));
+    assert!(text.trim_end().ends_with("</html>"));
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+use talaria_core::Error;
+use talaria_core::models::{
+    ContinueRequest, ImagesSource, ListingDraftInput, ListingDraftRequest, MarketplaceId,
+    PublicListingRequest, PublicPipelineOverrides,
+};
+use talaria_core::requests::ListingPolicies;
+
+fn policies() -> ListingPolicies {
+    ListingPolicies::new("home", "ship-1", "pay-1", "ret-1")
+}
+
+fn draft_input() -> ListingDraftInput {
+    ListingDraftInput {
+        title: "Blue lamp".into(),
+        description: "A lamp".into(),
+        price: 25.0,
+        currency: "USD".into(),
+        images: vec!["https://img.example/a.jpg".into()],
+        category_id: "112581".into(),
+        category_label: None,
+        condition: "Used".into(),
+        condition_id: 3000,
+        aspects: BTreeMap::new(),
+        package: None,
+        quantity: None,
+    }
+}
+
+#[test]
+fn public_listing_builder_leaves_unset_fields_out_of_the_body() {
+    let request = PublicListingRequest::builder(
+        policies(),
+        ImagesSource::Multiple(vec!["https://img.example/a.jpg".into()]),
+    )
+    .sku("sku-1".to_string())
+    .publish(true)
+    .use_signed_urls(false)
+    .build()
+    .unwrap();
+    let body = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "fulfillment_policy_id": "ship-1",
+            "images_source": ["https://img.example/a.jpg"],
+            "merchant_location_key": "home",
+            "payment_policy_id": "pay-1",
+            "publish": true,
+            "return_policy_id": "ret-1",
+            "sku": "sku-1",
+        })
+    );
+}
+
+#[test]
+fn builders_reject_blank_and_out_of_range_fields() {
+    let blank_policy = ListingPolicies {
+        payment_policy_id: " ".into(),
+        ..policies()
+    };
+    let err = PublicListingRequest::builder(blank_policy, ImagesSource::Single("u".into()))
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidRequest(ref msg) if msg.contains("payment_policy_id")));
+
+    let err = PublicListingRequest::builder(policies(), ImagesSource::Multiple(Vec::new()))
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("image"), "{err}");
+
+    let err = ContinueRequest::builder("sku-1", policies())
+        .overrides(PublicPipelineOverrides {
+            category: None,
+            condition: None,
+            condition_id: Some(0),
+            resolved_images: None,
+            product: None,
+        })
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("condition_id"), "{err}");
+
+    let err = ListingDraftRequest::builder(
+        "sku-1",
+        policies(),
+        ListingDraftInput {
+            price: 0.0,
+            ..draft_input()
+        },
+    )
+    .build()
+    .unwrap_err();
+    assert!(err.to_string().contains("price"), "{err}");
+}
+
+#[test]
+fn continue_and_draft_builders_carry_the_required_fields() {
+    let request = ContinueRequest::builder("sku-1", policies())
+        .marketplace(MarketplaceId::EbayDe)
+        .build()
+        .unwrap();
+    assert_eq!(request.sku, "sku-1");
+    assert_eq!(request.return_policy_id, "ret-1");
+    assert!(request.images_source.is_none());
+
+    let draft = ListingDraftRequest::builder("sku-1", policies(), draft_input())
+        .dry_run(true)
+        .build()
+        .unwrap();
+    assert_eq!(draft.merchant_location_key, "home");
+    assert_eq!(draft.dry_run, Some(true));
+    assert_eq!(draft.publish, None);
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "s3")]
+
+use talaria_core::config::{Config, S3Config};
+use talaria_core::image_store::ImageStore;
+use talaria_core::s3::S3Store;
+use wiremock::matchers::{method, path, path_regex, query_param};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+fn config(endpoint: &str) -> S3Config {
+    S3Config {
+        endpoint: endpoint.to_string(),
+        bucket: "photos".into(),
+        region: "us-east-1".into(),
+        access_key_id: Some("AKIDTEST".into()),
+        secret_access_key: Some("secret".into()),
+        public_base: None,
+        upload_prefix: "talaria".into(),
+        use_presigned_urls: false,
+    }
+}
+
+#[tokio::test]
+async fn s3_store_uploads_lists_and_deletes_path_style() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path_regex(r"^/photos/talaria/\d+-shoe\.jpg$"))
+        .and(|req: &Request| {
+            req.headers
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|auth| auth.starts_with("AWS4-HMAC-SHA256 Credential=AKIDTEST/"))
+                && req.body == b"jpeg bytes"
+        })
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex("^/photos/?$"))
+        .and(query_param("list-type", "2"))
+        .and(query_param("prefix", "talaria/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>photos</Name><Prefix>talaria/</Prefix><KeyCount>2</KeyCount><IsTruncated>false</IsTruncated>
+  <Contents><Key>talaria/2-b.jpg</Key><LastModified>2026-03-02T10:00:00.000Z</LastModified><Size>20</Size></Contents>
+  <Contents><Key>talaria/1-a.jpg</Key><LastModified>2026-03-01T10:00:00.000Z</LastModified><Size>10</Size></Contents>
+</ListBucketResult>"#,
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/photos/talaria/1-a.jpg"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let store: ImageStore = S3Store::from_config(&config(&server.uri())).unwrap().into();
+    let key = store
+        .upload_object("shoe.jpg", b"jpeg bytes".to_vec())
+        .await
+        .unwrap();
+    let url = store.object_url(&key).await.unwrap();
+    assert_eq!(url, format!("{}/photos/{key}", server.uri()));
+    assert_eq!(store.object_path_from_url(&url), Some(key));
+    assert_eq!(store.cache_scope(), format!("{}/photos", server.uri()));
+
+    let objects = store.list_objects("/talaria/").await.unwrap();
+    let listed: Vec<_> = objects
+        .iter()
+        .map(|object| (object.path.as_str(), object.size))
+        .collect();
+    assert_eq!(
+        listed,
+        [("talaria/1-a.jpg", Some(10)), ("talaria/2-b.jpg", Some(20))]
+    );
+    assert_eq!(
+        objects[0].updated_at.map(|at| at.to_rfc3339()).as_deref(),
+        Some("2026-03-01T10:00:00+00:00")
+    );
+    store.delete_object("talaria/1-a.jpg").await.unwrap();
+}
+
+#[tokio::test]
+async fn s3_store_presigns_private_objects_and_maps_public_base_urls() {
+    let mut cfg = config("https://minio.example.com");
+    cfg.use_presigned_urls = true;
+    let store = S3Store::from_config(&cfg).unwrap();
+    let url = store.object_url("talaria/1-a.jpg").await.unwrap();
+    assert!(url.starts_with("https://minio.example.com/photos/talaria/1-a.jpg?"));
+    assert!(url.contains("X-Amz-Credential=AKIDTEST%2F"));
+    assert!(url.contains("X-Amz-Expires=604800"));
+    assert!(url.contains("X-Amz-Signature="));
+    assert_eq!(
+        store.object_path_from_url(&url).as_deref(),
+        Some("talaria/1-a.jpg")
+    );
+
+    let mut cfg = config("https://account.r2.cloudflarestorage.com");
+    cfg.public_base = Some("https://images.example.com".into());
+    let store = S3Store::from_config(&cfg).unwrap();
+    let url = store.object_url("talaria/1-a.jpg").await.unwrap();
+    assert_eq!(url, "https://images.example.com/talaria/1-a.jpg");
+    assert_eq!(
+        store.object_path_from_url(&url).as_deref(),
+        Some("talaria/1-a.jpg")
+    );
+
+    cfg.secret_access_key = None;
+    assert!(S3Store::from_config(&cfg).is_err());
+}
+
+#[test]
+fn configured_s3_endpoint_takes_over_image_uploads() {
+    let config = Config {
+        s3: Some(config("https://s3.us-west-004.backblazeb2.com")),
+        ..Config::default()
+    };
+    let store = ImageStore::from_config(&config).unwrap().unwrap();
+    assert!(matches!(store, ImageStore::S3(_)));
+    assert_eq!(
+        store.cache_scope(),
+        "https://s3.us-west-004.backblazeb2.com/photos"
+    );
+    assert!(
+        ImageStore::from_config(&Config::default())
+            .unwrap()
+            .is_none()
+    );
+}
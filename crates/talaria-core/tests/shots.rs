@@ -0,0 +1,49 @@
+use talaria_core::error::Error;
+use talaria_core::shots::{SessionTemplate, validate_templates};
+
+fn apparel() -> SessionTemplate {
+    SessionTemplate {
+        name: "apparel".into(),
+        shots: vec!["front".into(), "back".into(), "tag".into()],
+        optional: vec!["defect close-up".into()],
+    }
+}
+
+#[test]
+fn missing_and_next_shots_follow_the_template_order() {
+    let template = apparel();
+    assert_eq!(template.missing(&[]), ["front", "back", "tag"]);
+    assert_eq!(
+        template.missing(&["back", "defect close-up"]),
+        ["front", "tag"]
+    );
+    assert_eq!(template.next_unfilled(&["front"]), Some("back"));
+    assert_eq!(
+        template.next_unfilled(&["front", "back", "tag"]),
+        Some("defect close-up")
+    );
+    assert_eq!(
+        template.next_unfilled(&["front", "back", "tag", "defect close-up"]),
+        None
+    );
+    assert!(template.is_required("tag"));
+    assert!(!template.is_required("defect close-up"));
+}
+
+#[test]
+fn invalid_templates_are_rejected() {
+    assert!(validate_templates(&[apparel()]).is_ok());
+
+    let err = |templates: &[SessionTemplate]| match validate_templates(templates) {
+        Err(Error::InvalidConfig(message)) => message,
+        other => panic!("expected InvalidConfig, got {other:?}"),
+    };
+    assert!(err(&[apparel(), apparel()]).contains("duplicate session template 'apparel'"));
+    let mut empty = apparel();
+    empty.shots.clear();
+    empty.optional.clear();
+    assert!(err(&[empty]).contains("has no shots"));
+    let mut repeated = apparel();
+    repeated.optional.push("front".into());
+    assert!(err(&[repeated]).contains("lists shot 'front' twice"));
+}
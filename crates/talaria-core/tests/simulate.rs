@@ -0,0 +1,99 @@
+use talaria_core::client::HermesClient;
+use talaria_core::config::Config;
+use talaria_core::models::{CreateUploadRequest, UpdateMediaRequest};
+use talaria_core::simulate::{self, SIMULATED_MEDIA_BASE};
+use talaria_core::supabase::SupabaseClient;
+
+#[tokio::test]
+async fn simulated_supabase_urls_depend_only_on_content() {
+    let client = SupabaseClient::simulated(None);
+    let first = client
+        .upload_image_bytes("shoe.jpg", b"same bytes".to_vec())
+        .await
+        .unwrap();
+    let again = client
+        .upload_image_bytes("shoe.jpg", b"same bytes".to_vec())
+        .await
+        .unwrap();
+    let other = client
+        .upload_image_bytes("shoe.jpg", b"other bytes".to_vec())
+        .await
+        .unwrap();
+    assert_eq!(first, again);
+    assert_ne!(first, other);
+    assert!(first.starts_with(SIMULATED_MEDIA_BASE));
+    assert!(first.ends_with("-shoe.jpg"));
+    assert_eq!(
+        client.object_path_from_url(&first).as_deref(),
+        first.split("/images-bucket/").nth(1)
+    );
+    assert!(client.list_objects("talaria").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn simulated_hermes_media_needs_no_key_or_server() {
+    let hermes = HermesClient::new(Config {
+        base_url: "http://127.0.0.1:9".into(),
+        simulate: true,
+        ..Config::default()
+    })
+    .unwrap();
+    assert!(hermes.is_simulated());
+
+    let session = hermes
+        .create_media_upload(&CreateUploadRequest {
+            content_length: Some(3),
+            content_type: Some("image/jpeg".into()),
+            filename: "a.jpg".into(),
+            metadata: None,
+            product_id: Some("prod_1".into()),
+            purpose: None,
+            session_id: None,
+            sha256: Some("abc".into()),
+        })
+        .await
+        .unwrap();
+    let done = hermes
+        .complete_media_upload(&session.upload_id, None)
+        .await
+        .unwrap();
+    assert_eq!(Some(done.media.url.as_str()), session.url.as_deref());
+    assert!(done.media.url.starts_with(SIMULATED_MEDIA_BASE));
+
+    let ranked = hermes
+        .update_media(
+            &done.media.media_id,
+            &UpdateMediaRequest {
+                metadata: None,
+                purpose: None,
+                rank: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(ranked.rank, Some(2));
+    let listed = hermes.list_product_media("prod_1").await.unwrap();
+    assert_eq!(listed.items.len(), 1);
+
+    hermes.delete_media(&done.media.media_id).await.unwrap();
+    assert!(hermes.delete_media(&done.media.media_id).await.is_err());
+}
+
+#[test]
+fn synthetic_captures_are_valid_and_repeatable() {
+    let dir = std::env::temp_dir().join(format!("talaria-sim-{}", std::process::id()));
+    let first = simulate::capture_frames(2, &dir).unwrap();
+    let bytes = std::fs::read(&first[1]).unwrap();
+    assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert_eq!(&bytes[12..16], b"IHDR");
+    assert_eq!(
+        &bytes[16..20],
+        &simulate::SYNTHETIC_FRAME_WIDTH.to_be_bytes()
+    );
+    assert!(bytes.ends_with(&[b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+    let second = simulate::capture_frames(2, &dir).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(bytes, std::fs::read(&second[1]).unwrap());
+    assert_ne!(bytes, std::fs::read(&second[0]).unwrap());
+}
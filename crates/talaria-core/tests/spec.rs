@@ -0,0 +1,82 @@
+use serde_json::json;
+use talaria_core::spec::ApiSurface;
+
+#[test]
+fn known_surface_reads_models_and_client_endpoints() {
+    let known = ApiSurface::known();
+    let usage = &known.schemas["IngestUsage"];
+    assert_eq!(
+        usage.fields.iter().collect::<Vec<_>>(),
+        ["input_tokens", "output_tokens"]
+    );
+    assert!(!usage.open);
+    assert!(known.schemas["JobInfo"].open, "state is flattened");
+    assert!(!known.schemas["JobInfo"].fields.contains("state"));
+    assert!(
+        !known.schemas.contains_key("JobState"),
+        "enums have no fields"
+    );
+    assert!(
+        known
+            .endpoints
+            .iter()
+            .any(|endpoint| endpoint.to_string() == "GET /v1/products/{product_id}/media")
+    );
+}
+
+#[test]
+fn drift_lists_new_and_removed_endpoints_and_fields() {
+    let known = ApiSurface::known();
+    let mut paths = serde_json::Map::new();
+    for endpoint in &known.endpoints {
+        // Parameter names differ from the client's but positions match.
+        let path = format!("/{}", endpoint.path.replace("{product_id}", "{id}"));
+        if path == "/v1/usage" {
+            continue;
+        }
+        paths
+            .entry(path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .unwrap()
+            .insert(endpoint.method.clone(), json!({}));
+    }
+    paths.insert("/v1/products/{id}/variants".into(), json!({"get": {}}));
+    let doc = json!({
+        "info": {"version": "2.4.0"},
+        "paths": paths,
+        "components": {"schemas": {
+            "IngestUsage": {"properties": {
+                "input_tokens": {}, "output_tokens": {}, "cached_tokens": {}
+            }},
+            "EnqueueResponse": {"properties": {}},
+            "JobInfo": {"properties": {"state": {}, "id": {}, "created_at": {},
+                "updated_at": {}, "request": {}, "retry": {}, "max_retries": {}}},
+            "Variant": {"properties": {"sku": {}}},
+        }},
+    });
+
+    let server = ApiSurface::from_openapi(&doc).unwrap();
+    let drift = known.drift(&server);
+    assert_eq!(drift.server_version.as_deref(), Some("2.4.0"));
+    assert_eq!(
+        drift
+            .unknown_endpoints
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        ["GET /v1/products/{id}/variants"]
+    );
+    assert_eq!(drift.missing_endpoints.len(), 1);
+    assert_eq!(drift.missing_endpoints[0].path, "v1/usage");
+    assert_eq!(drift.unknown_schemas, ["Variant"]);
+    assert_eq!(drift.unknown_fields.len(), 1);
+    assert_eq!(drift.unknown_fields["IngestUsage"], ["cached_tokens"]);
+    assert_eq!(drift.missing_fields["EnqueueResponse"], ["job_id"]);
+    assert!(drift.upgrade_available() && drift.breaking());
+    assert_ne!(drift.client_fingerprint, drift.server_fingerprint);
+
+    let same = known.drift(&known.clone());
+    assert!(same.in_sync());
+    assert_eq!(same.client_fingerprint, same.server_fingerprint);
+}
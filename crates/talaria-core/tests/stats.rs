@@ -0,0 +1,123 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::json;
+use std::collections::HashMap;
+use talaria_core::ledger::{JobLedger, TrackedState};
+use talaria_core::models::PublicListingRequest;
+use talaria_core::stats::{self, DayRange, SessionSample};
+
+fn at(text: &str) -> DateTime<Utc> {
+    text.parse().unwrap()
+}
+
+fn request(sku: &str, publish: bool) -> PublicListingRequest {
+    serde_json::from_value(json!({
+        "sku": sku,
+        "images_source": ["https://x/a.jpg"],
+        "merchant_location_key": "loc-1",
+        "fulfillment_policy_id": "pol-f",
+        "payment_policy_id": "pol-p",
+        "return_policy_id": "pol-r",
+        "publish": publish,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn days_count_captures_listings_cycle_times_and_stage_failures() {
+    let ledger = JobLedger::open(
+        std::env::temp_dir()
+            .join(format!("talaria-stats-{}", uuid::Uuid::new_v4()))
+            .join("jobs.jsonl"),
+    );
+    for (job, sku, publish) in [
+        ("job_1", "SKU-1", true),
+        ("job_2", "SKU-2", false),
+        ("job_3", "SKU-3", true),
+        ("job_4", "SKU-4", true),
+        ("job_5", "SKU-5", true),
+    ] {
+        ledger.record(job, &request(sku, publish)).unwrap();
+    }
+    ledger
+        .set_state("job_1", TrackedState::Completed, None)
+        .unwrap();
+    ledger
+        .set_state("job_2", TrackedState::Completed, None)
+        .unwrap();
+    ledger
+        .set_state("job_3", TrackedState::Failed, Some("aspects: timeout"))
+        .unwrap();
+    ledger
+        .set_state("job_3", TrackedState::Retried, None)
+        .unwrap();
+    ledger
+        .set_state("job_4", TrackedState::Failed, Some("Hermes unavailable"))
+        .unwrap();
+    let mut jobs = ledger.recent(usize::MAX).unwrap();
+    let _ = std::fs::remove_dir_all(ledger.path().parent().unwrap());
+    // job_5 is still pending.
+    assert_eq!(
+        jobs.iter().filter(|job| job.settled_at.is_some()).count(),
+        4
+    );
+
+    // Outcomes land on two days; the second listing job was a draft.
+    let settled = [
+        ("job_1", "2026-03-02T12:00:00Z"),
+        ("job_2", "2026-03-02T12:00:00Z"),
+        ("job_3", "2026-03-03T12:00:00Z"),
+        ("job_4", "2026-03-03T12:00:00Z"),
+    ];
+    for job in &mut jobs {
+        if let Some((_, when)) = settled.iter().find(|(id, _)| *id == job.job_id) {
+            job.settled_at = Some(at(when));
+        }
+    }
+    let sessions = [
+        SessionSample {
+            product_id: "p1".into(),
+            created_at: at("2026-03-01T10:00:00Z"),
+            committed_at: Some(at("2026-03-01T11:00:00Z")),
+        },
+        // A later session of the same product does not restart the clock.
+        SessionSample {
+            product_id: "p1".into(),
+            created_at: at("2026-03-02T08:00:00Z"),
+            committed_at: Some(at("2026-03-02T09:00:00Z")),
+        },
+        SessionSample {
+            product_id: "p9".into(),
+            created_at: at("2026-02-20T10:00:00Z"),
+            committed_at: None,
+        },
+    ];
+    let skus = HashMap::from([("SKU-1".to_string(), "p1".to_string())]);
+    let range = DayRange::parse(Some("2026-03-01"), Some("2026-03-03")).unwrap();
+    let stats = stats::compute(range, &sessions, &jobs, &skus);
+
+    let day = |date: &str| {
+        let date: NaiveDate = date.parse().unwrap();
+        stats.days.iter().find(|day| day.date == date).unwrap()
+    };
+    assert_eq!(stats.days.len(), 3);
+    assert_eq!(day("2026-03-01").captured, 1);
+    assert_eq!(day("2026-03-02").captured, 1);
+    assert_eq!(day("2026-03-02").listed, 2);
+    assert_eq!(day("2026-03-02").published, 1);
+    assert_eq!(day("2026-03-02").avg_cycle_secs, Some(26 * 3600));
+    assert_eq!(day("2026-03-03").failed, 2);
+    assert_eq!(
+        (stats.captured, stats.listed, stats.published, stats.failed),
+        (2, 2, 1, 2)
+    );
+    assert_eq!(stats.avg_cycle_secs, Some(26 * 3600));
+    let stages: Vec<_> = stats
+        .failures_by_stage
+        .iter()
+        .map(|stage| (stage.stage.as_str(), stage.failed, stage.rate))
+        .collect();
+    assert_eq!(stages, [("aspects", 1, 0.25), ("unknown", 1, 0.25)]);
+
+    assert!(DayRange::parse(Some("2026-03-04"), Some("2026-03-03")).is_err());
+    assert!(DayRange::parse(Some("March"), None).is_err());
+}
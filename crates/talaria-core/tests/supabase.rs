@@ -1,5 +1,10 @@
-use talaria_core::config::SupabaseConfig;
+use serde_json::json;
+use talaria_core::client::HermesClient;
+use talaria_core::config::{Config, ProductBackend, SupabaseConfig};
+use talaria_core::models::{ProductCreateRequest, ProductUpdateRequest};
 use talaria_core::supabase::SupabaseClient;
+use wiremock::matchers::{body_partial_json, header, headers, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[test]
 fn supabase_public_url_builds() {
@@ -9,6 +14,8 @@ fn supabase_public_url_builds() {
         bucket: "bucket".into(),
         public_base: None,
         upload_prefix: "talaria".into(),
+        use_signed_urls: false,
+        network: Default::default(),
     };
     let client = SupabaseClient::from_config(&cfg).unwrap();
     let url = client.public_url("talaria/image.jpg");
@@ -17,3 +24,165 @@ fn supabase_public_url_builds() {
         "https://example.supabase.co/storage/v1/object/public/bucket/talaria/image.jpg"
     );
 }
+
+#[test]
+fn supabase_signed_url_builds() {
+    let cfg = SupabaseConfig {
+        url: "https://example.supabase.co".into(),
+        service_role_key: Some("sk_test".into()),
+        bucket: "private".into(),
+        public_base: None,
+        upload_prefix: "talaria".into(),
+        use_signed_urls: true,
+        network: Default::default(),
+    };
+    let client = SupabaseClient::from_config(&cfg).unwrap();
+    assert!(client.use_signed_urls());
+    let url = client.signed_url("/object/sign/private/talaria/image.jpg?token=abc");
+    assert_eq!(
+        url,
+        "https://example.supabase.co/storage/v1/object/sign/private/talaria/image.jpg?token=abc"
+    );
+}
+
+#[test]
+fn supabase_object_path_from_url() {
+    let cfg = SupabaseConfig {
+        url: "https://example.supabase.co".into(),
+        service_role_key: Some("sk_test".into()),
+        bucket: "bucket".into(),
+        public_base: Some("https://cdn.example.com".into()),
+        upload_prefix: "talaria".into(),
+        use_signed_urls: false,
+        network: Default::default(),
+    };
+    let client = SupabaseClient::from_config(&cfg).unwrap();
+    let public = client.public_url("talaria/1-a.jpg");
+    assert_eq!(
+        client.object_path_from_url(&public).as_deref(),
+        Some("talaria/1-a.jpg")
+    );
+    assert_eq!(
+        client
+            .object_path_from_url(
+                "https://example.supabase.co/storage/v1/object/sign/bucket/talaria/2-b.jpg?token=x"
+            )
+            .as_deref(),
+        Some("talaria/2-b.jpg")
+    );
+    assert_eq!(
+        client.object_path_from_url("https://example.com/storage/v1/object/public/other/x.jpg"),
+        None
+    );
+}
+
+#[tokio::test]
+async fn supabase_product_backend_replaces_the_hermes_products_api() {
+    let server = MockServer::start().await;
+    let row = |display_name: &str| {
+        json!([{
+            "id": "prod_1",
+            "org_id": "org_a",
+            "sku_alias": "SKU-1",
+            "display_name": display_name,
+            "listings_json": null,
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-02T00:00:00Z",
+        }])
+    };
+    Mock::given(method("GET"))
+        .and(path("/rest/v1/products"))
+        .and(query_param("org_id", "eq.org_a"))
+        .and(query_param("id", "eq.SKU-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/v1/products"))
+        .and(query_param("sku_alias", "eq.SKU-1"))
+        .and(header("apikey", "service_key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(row("Lamp")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/v1/products"))
+        .and(query_param("id", "eq.prod_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/v1/products"))
+        .and(query_param("on_conflict", "id"))
+        .and(headers(
+            "prefer",
+            vec!["resolution=merge-duplicates", "return=representation"],
+        ))
+        .and(body_partial_json(json!({
+            "id": "prod_2",
+            "org_id": "org_a",
+            "sku_alias": "SKU-2",
+            "listings_json": {},
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(row("Vase")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/rest/v1/products"))
+        .and(query_param("org_id", "eq.org_a"))
+        .and(query_param("id", "eq.prod_1"))
+        .and(body_partial_json(json!({"display_name": "Brass lamp"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(row("Brass lamp")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = HermesClient::new(Config {
+        // Nothing may reach Hermes.
+        base_url: "http://127.0.0.1:9".into(),
+        org_id: Some("org_a".into()),
+        product_backend: ProductBackend::Supabase,
+        supabase: Some(SupabaseConfig {
+            url: server.uri(),
+            service_role_key: Some("service_key".into()),
+            bucket: "bucket".into(),
+            public_base: None,
+            upload_prefix: "talaria".into(),
+            use_signed_urls: false,
+            network: Default::default(),
+        }),
+        ..Config::default()
+    })
+    .unwrap();
+    assert_eq!(client.product_backend(), ProductBackend::Supabase);
+
+    let product = client.get_product("SKU-1").await.unwrap();
+    assert_eq!(product.id, "prod_1");
+    assert_eq!(product.listings_json, json!({}));
+    client
+        .create_product(&ProductCreateRequest {
+            id: Some("prod_2".into()),
+            sku_alias: Some("SKU-2".into()),
+            display_name: Some("Vase".into()),
+        })
+        .await
+        .unwrap();
+    let updated = client
+        .update_product(
+            "prod_1",
+            &ProductUpdateRequest {
+                display_name: Some("Brass lamp".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.display_name.as_deref(), Some("Brass lamp"));
+
+    let missing = HermesClient::new(Config {
+        product_backend: ProductBackend::Supabase,
+        ..Config::default()
+    });
+    assert!(missing.is_err());
+}
@@ -0,0 +1,42 @@
+use talaria_core::config::Config;
+use talaria_core::models::{LlmModel, LlmStageOptions, MarketplaceId};
+use talaria_core::templates::{RequestTemplate, TemplateStore};
+
+#[test]
+fn templates_round_trip_and_apply_only_their_fields() {
+    let dir = std::env::temp_dir().join(format!("talaria-templates-{}", uuid::Uuid::new_v4()));
+    let store = TemplateStore::open(&dir);
+    assert!(store.list().unwrap().is_empty());
+
+    let template = RequestTemplate {
+        marketplace: Some(MarketplaceId::EbayDe),
+        payment_policy_id: Some("p-1".into()),
+        llm_aspects: Some(LlmStageOptions {
+            model: LlmModel::Gpt5Mini,
+            reasoning: Some(true),
+            web_search: None,
+        }),
+        ..RequestTemplate::default()
+    };
+    store.save("garage-sale", &template).unwrap();
+    std::fs::write(dir.join("broken.toml"), "marketplace = [").unwrap();
+    let listed = store.list().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].0, "garage-sale");
+    assert!(store.save("../escape", &template).is_err());
+    assert!(store.load("missing").is_err());
+
+    let mut config = Config::default();
+    config.ebay.return_policy_id = Some("r-0".into());
+    config.prompt_rules = Some("keep".into());
+    store.load("garage-sale").unwrap().apply_to(&mut config);
+    assert_eq!(config.ebay.marketplace.as_deref(), Some("EBAY_DE"));
+    assert_eq!(config.ebay.payment_policy_id.as_deref(), Some("p-1"));
+    assert_eq!(config.ebay.return_policy_id.as_deref(), Some("r-0"));
+    assert_eq!(config.prompt_rules.as_deref(), Some("keep"));
+    assert_eq!(
+        config.llm_aspects.map(|options| options.reasoning),
+        Some(Some(true))
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
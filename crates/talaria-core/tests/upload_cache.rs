@@ -0,0 +1,40 @@
+use talaria_core::upload_cache::{UploadCache, sha256_hex};
+
+fn temp_cache_path() -> std::path::PathBuf {
+    std::env::temp_dir()
+        .join(format!("talaria-uploads-{}", uuid::Uuid::new_v4()))
+        .join("uploads.json")
+}
+
+#[test]
+fn upload_cache_reuses_by_hash_within_scope() {
+    let path = temp_cache_path();
+    let sha = sha256_hex(b"same bytes");
+    assert_eq!(sha.len(), 64);
+    assert_eq!(sha, sha256_hex(b"same bytes"));
+
+    let mut cache = UploadCache::open(&path).unwrap();
+    cache
+        .insert("https://a.supabase.co/bucket", &sha, "talaria/1-a.jpg")
+        .unwrap();
+
+    let reopened = UploadCache::open(&path).unwrap();
+    assert_eq!(
+        reopened
+            .get("https://a.supabase.co/bucket", &sha)
+            .map(|e| e.object_path.as_str()),
+        Some("talaria/1-a.jpg")
+    );
+    assert!(
+        reopened.get("https://a.supabase.co/other", &sha).is_none(),
+        "bucket is part of the key"
+    );
+
+    let mut reopened = reopened;
+    assert!(
+        reopened
+            .forget_object("https://a.supabase.co/bucket", "talaria/1-a.jpg")
+            .unwrap()
+    );
+    assert!(UploadCache::open(&path).unwrap().is_empty());
+}
@@ -0,0 +1,97 @@
+use chrono::{Duration, Utc};
+use talaria_core::client::HermesClient;
+use talaria_core::config::Config;
+use talaria_core::models::{CreateUploadRequest, UploadMethod, UploadSession};
+use talaria_core::upload_journal::{UploadJournal, abort_stale};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn session(upload_id: &str, expires_in: Duration) -> UploadSession {
+    UploadSession {
+        expires_at: Utc::now() + expires_in,
+        headers: None,
+        method: UploadMethod::Put,
+        object_key: format!("media/{upload_id}.jpg"),
+        upload_id: upload_id.to_string(),
+        upload_url: format!("https://storage.example/{upload_id}"),
+        url: None,
+    }
+}
+
+fn request(filename: &str) -> CreateUploadRequest {
+    CreateUploadRequest {
+        content_length: None,
+        content_type: None,
+        filename: filename.to_string(),
+        metadata: None,
+        product_id: Some("p1".to_string()),
+        purpose: None,
+        session_id: None,
+        sha256: None,
+    }
+}
+
+#[tokio::test]
+async fn abort_stale_aborts_expired_sessions_and_keeps_failures() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/media/uploads/up_old/abort"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/media/uploads/up_gone/abort"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("no such upload"))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/media/uploads/up_down/abort"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+        .mount(&server)
+        .await;
+    let client = HermesClient::new(Config {
+        base_url: server.uri(),
+        api_key: Some("sk_test".to_string()),
+        ..Config::default()
+    })
+    .unwrap();
+
+    let dir = std::env::temp_dir().join(format!("talaria-journal-{}", uuid::Uuid::new_v4()));
+    let journal = UploadJournal::open(dir.join("pending_uploads.json"));
+    for (id, expires_in) in [
+        ("up_old", Duration::minutes(-5)),
+        ("up_gone", Duration::minutes(-1)),
+        ("up_down", Duration::minutes(-1)),
+        ("up_live", Duration::minutes(10)),
+        ("up_done", Duration::minutes(-5)),
+    ] {
+        journal
+            .record(&session(id, expires_in), &request(&format!("{id}.jpg")))
+            .unwrap();
+    }
+    journal.finish("up_done").unwrap();
+
+    let results = abort_stale(&client, &journal, Utc::now()).await.unwrap();
+    let mut outcomes: Vec<_> = results
+        .iter()
+        .map(|r| (r.upload.upload_id.as_str(), r.aborted, r.error.is_some()))
+        .collect();
+    outcomes.sort();
+    assert_eq!(
+        outcomes,
+        [
+            ("up_down", false, true),
+            ("up_gone", false, false),
+            ("up_old", true, false),
+        ]
+    );
+    let left: Vec<_> = journal
+        .pending()
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.upload_id)
+        .collect();
+    assert_eq!(left, ["up_down", "up_live"]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::json;
+use talaria_core::models::UsageSummary;
+use talaria_core::stats::DayRange;
+use talaria_core::usage_history::UsageHistory;
+
+fn at(text: &str) -> DateTime<Utc> {
+    text.parse().unwrap()
+}
+
+fn summary(org: &str, credits: i64, window_from: &str) -> UsageSummary {
+    serde_json::from_value(json!({
+        "org_id": org,
+        "counters": {"credits_consumed": credits, "jobs_enqueued": credits, "listings_run": 1},
+        "tiered": {
+            "cost_cents": 0,
+            "credit_balance_cents": 1000 - credits,
+            "credits_applied_cents": 0,
+            "enterprise": false,
+            "net_due_cents": 0,
+            "tiers": [],
+            "total_events": 0,
+            "total_units": 0,
+        },
+        "window_from": window_from,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn keeps_the_last_snapshot_of_each_day_and_reports_what_it_added() {
+    let history = UsageHistory::open(
+        std::env::temp_dir()
+            .join(format!("talaria-usage-{}", uuid::Uuid::new_v4()))
+            .join("usage-history.json"),
+    );
+    let march = "2026-03-01T00:00:00Z";
+    let april = "2026-04-01T00:00:00Z";
+    // Too old to keep by the time the last snapshot is recorded.
+    history
+        .record(&[summary("org_a", 5, march)], at("2025-01-10T12:00:00Z"))
+        .unwrap();
+    history
+        .record(&[summary("org_a", 10, march)], at("2026-03-30T12:00:00Z"))
+        .unwrap();
+    history
+        .record(
+            &[summary("org_a", 12, march), summary("org_b", 3, march)],
+            at("2026-03-31T09:00:00Z"),
+        )
+        .unwrap();
+    history
+        .record(&[summary("org_a", 25, march)], at("2026-03-31T12:00:00Z"))
+        .unwrap();
+    history
+        .record(&[summary("org_a", 4, april)], at("2026-04-01T12:00:00Z"))
+        .unwrap();
+
+    let snapshots = history.load().unwrap();
+    assert_eq!(snapshots["org_a"].len(), 3);
+    let range = DayRange::parse(Some("2026-03-31"), Some("2026-04-01")).unwrap();
+    let days: Vec<_> = history
+        .days(None, range)
+        .unwrap()
+        .into_iter()
+        .map(|day| {
+            (
+                day.org_id,
+                day.date,
+                day.credits_consumed,
+                day.credit_balance_cents,
+            )
+        })
+        .collect();
+    let date = |text: &str| text.parse::<NaiveDate>().unwrap();
+    assert_eq!(
+        days,
+        [
+            ("org_a".to_string(), date("2026-03-31"), 15, Some(975)),
+            ("org_b".to_string(), date("2026-03-31"), 3, Some(997)),
+            ("org_a".to_string(), date("2026-04-01"), 4, Some(996)),
+        ]
+    );
+    assert_eq!(history.days(Some("org_b"), range).unwrap().len(), 1);
+    let _ = std::fs::remove_dir_all(history.path().parent().unwrap());
+}
@@ -0,0 +1,121 @@
+use serde_json::{Value, json};
+use talaria_core::client::HermesClient;
+use talaria_core::config::Config;
+use talaria_core::error::Error;
+use talaria_core::vcr::{REDACTED, Vcr};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn cassette_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "talaria-vcr-{name}-{}-{}.json",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ))
+}
+
+fn client(base_url: String, api_key: Option<&str>, vcr: Vcr) -> HermesClient {
+    HermesClient::new(Config {
+        base_url,
+        api_key: api_key.map(str::to_string),
+        ..Config::default()
+    })
+    .expect("client")
+    .with_vcr(vcr)
+}
+
+#[tokio::test]
+async fn records_scrubbed_cassette_and_replays_offline() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/auth/device/poll"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"status": "authorized", "access_token": "at_secret"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/usage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "org_id": "org_1",
+            "counters": {"credits_consumed": 7, "jobs_enqueued": 2, "listings_run": 3},
+        }])))
+        .mount(&server)
+        .await;
+
+    let cassette = cassette_path("roundtrip");
+    let recorder = client(server.uri(), Some("sk_live_key"), Vcr::record(&cassette));
+    let poll = recorder.device_auth_poll("dev_code_1").await.unwrap();
+    assert_eq!(poll.access_token.as_deref(), Some("at_secret"));
+    recorder
+        .usage(Some("org_1".into()), None, None)
+        .await
+        .unwrap();
+
+    let text = std::fs::read_to_string(&cassette).unwrap();
+    for secret in ["sk_live_key", "at_secret", "dev_code_1"] {
+        assert!(!text.contains(secret), "{secret} leaked into cassette");
+    }
+    let saved: Value = serde_json::from_str(&text).unwrap();
+    let first = &saved["interactions"][0];
+    assert_eq!(first["request"]["path"], "v1/auth/device/poll");
+    assert_eq!(first["request"]["body"]["device_code"], REDACTED);
+    assert_eq!(
+        saved["interactions"][1]["request"]["path"],
+        "v1/usage?org_id=org_1"
+    );
+
+    // Different host, no key, server gone: everything comes from the cassette.
+    drop(server);
+    let player = client(
+        "http://127.0.0.1:9".into(),
+        None,
+        Vcr::replay(&cassette).unwrap(),
+    );
+    let usage = player
+        .usage(Some("org_1".into()), None, None)
+        .await
+        .unwrap();
+    assert_eq!(usage[0].counters.listings_run, 3);
+    let poll = player.device_auth_poll("anything").await.unwrap();
+    assert_eq!(poll.access_token.as_deref(), Some(REDACTED));
+
+    let err = player.health().await.unwrap_err();
+    assert!(matches!(err, Error::Cassette(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn replay_serves_responses_in_order_then_repeats_the_last() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"status": "starting", "service": "hermes"})),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"status": "ok", "service": "hermes"})),
+        )
+        .mount(&server)
+        .await;
+
+    let cassette = cassette_path("order");
+    let recorder = client(server.uri(), None, Vcr::record(&cassette));
+    recorder.health().await.unwrap();
+    recorder.health().await.unwrap();
+
+    let player = client(server.uri(), None, Vcr::replay(&cassette).unwrap());
+    let statuses = [
+        player.health().await.unwrap().status,
+        player.health().await.unwrap().status,
+        player.health().await.unwrap().status,
+    ];
+    assert_eq!(statuses, ["starting", "ok", "ok"]);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
@@ -0,0 +1,78 @@
+use serde_json::json;
+use talaria_core::models::ListingResponse;
+use talaria_core::warnings::{WarningAction, WarningPolicy};
+
+fn response() -> ListingResponse {
+    serde_json::from_value(json!({
+        "listing_id": "lst_1",
+        "stages": [
+            {
+                "name": "aspects",
+                "elapsed_ms": 10,
+                "timestamp": "2026-01-01T00:00:00Z",
+                "output": {"warnings": ["Missing required aspect: Brand", "Aspect Color guessed"]},
+            },
+            {
+                "name": "pricing",
+                "elapsed_ms": 4,
+                "timestamp": "2026-01-01T00:00:01Z",
+                "output": {"warnings": "no comparables found"},
+            },
+        ],
+    }))
+    .expect("listing response")
+}
+
+#[test]
+fn first_matching_rule_decides_and_default_covers_the_rest() {
+    let policy: WarningPolicy = toml::from_str(
+        r#"
+        default = "ignore"
+
+        [[rules]]
+        pattern = "*missing required aspect*"
+        action = "fail"
+
+        [[rules]]
+        stage = "aspects"
+        pattern = "*"
+        action = "warn"
+
+        [[rules]]
+        stage = "aspects"
+        pattern = "*no comparables*"
+        action = "fail"
+        "#,
+    )
+    .expect("policy");
+    policy.validate().expect("valid policy");
+
+    let actions: Vec<_> = policy
+        .evaluate(&response())
+        .into_iter()
+        .map(|warning| (warning.stage, warning.action))
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            ("aspects".to_string(), WarningAction::Fail),
+            ("aspects".to_string(), WarningAction::Warn),
+            ("pricing".to_string(), WarningAction::Ignore),
+        ]
+    );
+
+    let warnings = WarningPolicy::default().evaluate(&response());
+    assert!(
+        warnings
+            .iter()
+            .all(|warning| warning.action == WarningAction::Warn)
+    );
+    assert_eq!(warnings[2].to_string(), "pricing: no comparables found");
+}
+
+#[test]
+fn bad_patterns_are_rejected() {
+    let policy: WarningPolicy =
+        toml::from_str("[[rules]]\npattern = \"[oops\"\naction = \"fail\"\n").expect("policy");
+    assert!(policy.validate().is_err());
+}
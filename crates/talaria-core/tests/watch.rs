@@ -0,0 +1,88 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+use talaria_core::watch::{DEFAULT_ARCHIVE_DIR, FolderWatch, GroupBy};
+
+fn write(path: &Path, bytes: &[u8]) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, bytes).unwrap();
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("talaria-watch-{name}-{}", uuid::Uuid::new_v4()))
+}
+
+#[test]
+fn filename_groups_settle_archive_and_failed_groups_wait_for_changes() {
+    let dir = temp_dir("filename");
+    write(&dir.join("sku-123_01.jpg"), b"front");
+    write(&dir.join("sku-123_02.jpg"), b"back");
+    write(&dir.join("lamp.jpg"), b"lamp");
+    write(&dir.join("notes.txt"), b"not an image");
+    let settle = Duration::from_secs(3);
+    let mut watch = FolderWatch::new(&dir, None, GroupBy::Filename, settle);
+
+    let start = Instant::now();
+    let (ready, skipped) = watch.poll(start).unwrap();
+    assert!(ready.is_empty(), "nothing has settled yet");
+    assert_eq!(skipped.len(), 1);
+    assert!(
+        watch.poll(start).unwrap().1.is_empty(),
+        "skips are reported once"
+    );
+
+    // A new frame restarts its group's settle time only.
+    write(&dir.join("sku-123_03.jpg"), b"side");
+    let (ready, _) = watch.poll(start + Duration::from_secs(2)).unwrap();
+    assert!(ready.is_empty());
+    let (ready, _) = watch.poll(start + Duration::from_secs(3)).unwrap();
+    assert_eq!(ready.len(), 1);
+    assert_eq!(ready[0].key, "lamp");
+
+    let later = start + Duration::from_secs(5);
+    let (ready, _) = watch.poll(later).unwrap();
+    let batch = ready.iter().find(|batch| batch.key == "sku-123").unwrap();
+    assert_eq!(batch.files.len(), 3);
+    assert!(batch.files[0].ends_with("sku-123_01.jpg"));
+
+    watch.hold(batch);
+    let (ready, _) = watch.poll(later).unwrap();
+    assert!(ready.iter().all(|batch| batch.key != "sku-123"), "held");
+
+    let moved = watch.archive(&ready[0]).unwrap();
+    assert_eq!(
+        moved,
+        vec![dir.join(DEFAULT_ARCHIVE_DIR).join("lamp").join("lamp.jpg")]
+    );
+    assert!(!dir.join("lamp.jpg").exists());
+
+    // A clash with an earlier run is renamed, not overwritten.
+    write(&dir.join("lamp.jpg"), b"lamp again");
+    let (ready, _) = watch.poll(later).unwrap();
+    assert!(ready.is_empty());
+    let (ready, _) = watch.poll(later + settle).unwrap();
+    let moved = watch.archive(&ready[0]).unwrap();
+    assert!(moved[0].ends_with("lamp/lamp.1.jpg"));
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn subfolders_are_products_and_loose_files_are_ignored() {
+    let dir = temp_dir("subfolder");
+    let archive = temp_dir("subfolder-archive");
+    write(&dir.join("sku-9/front.jpg"), b"front");
+    write(&dir.join("sku-9/detail/tag.jpg"), b"tag");
+    write(&dir.join("loose.jpg"), b"loose");
+    let mut watch = FolderWatch::new(&dir, Some(&archive), GroupBy::Subfolder, Duration::ZERO);
+
+    let (ready, _) = watch.poll(Instant::now()).unwrap();
+    assert_eq!(ready.len(), 1);
+    assert_eq!(ready[0].key, "sku-9");
+    assert_eq!(ready[0].files.len(), 2);
+    watch.archive(&ready[0]).unwrap();
+    assert!(archive.join("sku-9/front.jpg").exists());
+    assert!(archive.join("sku-9/tag.jpg").exists());
+
+    let _ = std::fs::remove_dir_all(dir);
+    let _ = std::fs::remove_dir_all(archive);
+}
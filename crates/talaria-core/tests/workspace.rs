@@ -0,0 +1,59 @@
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use talaria_core::workspace::{KnownCategory, known_categories};
+
+fn temp_workspace() -> PathBuf {
+    std::env::temp_dir().join(format!("talaria-workspace-{}", uuid::Uuid::new_v4()))
+}
+
+fn write_product(base: &Path, id: &str, listings: serde_json::Value) {
+    let dir = base.join("products").join(id);
+    std::fs::create_dir_all(&dir).unwrap();
+    let manifest = json!({ "product_id": id, "listings": listings });
+    std::fs::write(dir.join("product.json"), manifest.to_string()).unwrap();
+}
+
+fn category(marketplace: &str, id: &str, label: &str, uses: usize) -> KnownCategory {
+    KnownCategory {
+        marketplace: marketplace.into(),
+        id: id.into(),
+        label: label.into(),
+        uses,
+    }
+}
+
+#[test]
+fn categories_count_once_per_marketplace_and_id() {
+    let base = temp_workspace();
+    write_product(
+        &base,
+        "prod_1",
+        json!({
+            "EBAY_US": { "category_id": "11450", "category_label": "Clothing" },
+            "EBAY_DE": { "category_id": "11450", "category_label": "Kleidung" },
+        }),
+    );
+    write_product(
+        &base,
+        "prod_2",
+        json!({ "EBAY_US": { "category_id": "11450", "category_label": "Clothing & Shoes" } }),
+    );
+    write_product(
+        &base,
+        "prod_3",
+        json!({ "EBAY_US": { "category_id": "", "category_label": "Draft" } }),
+    );
+
+    let categories = known_categories(&base).unwrap();
+    assert_eq!(categories.len(), 2);
+    assert_eq!(categories[0].marketplace, "EBAY_US");
+    assert_eq!(categories[0].id, "11450");
+    assert_eq!(categories[0].uses, 2);
+    assert!(["Clothing", "Clothing & Shoes"].contains(&categories[0].label.as_str()));
+    assert_eq!(categories[1], category("EBAY_DE", "11450", "Kleidung", 1));
+}
+
+#[test]
+fn missing_workspace_has_no_categories() {
+    assert!(known_categories(&temp_workspace()).unwrap().is_empty());
+}
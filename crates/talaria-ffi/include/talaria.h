@@ -0,0 +1,51 @@
+/*
+ * C API of libtalaria (crates/talaria-ffi). Requests and responses are JSON
+ * strings; every function returns TALARIA_OK or a TALARIA_ERR_* code and
+ * stores the response, or {"code", "message", "status", "request_id"} on
+ * failure, in *out_json when out_json is not NULL. Free returned strings
+ * with talaria_string_free and clients with talaria_client_free.
+ *
+ * Calls block; make them from a worker thread. Do not share one client
+ * between threads at the same time.
+ */
+#ifndef TALARIA_H
+#define TALARIA_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define TALARIA_OK 0
+#define TALARIA_ERR_OTHER 1
+#define TALARIA_ERR_AUTH 2
+#define TALARIA_ERR_VALIDATION 3
+#define TALARIA_ERR_RATE_LIMITED 4
+#define TALARIA_ERR_UNAVAILABLE 5
+#define TALARIA_ERR_PANIC 6
+
+typedef struct TalariaClient TalariaClient;
+
+/* config_json: NULL or {"base_url", "api_key", "org_id", "timeouts"}, each
+ * optional and overriding the user's Talaria config; "timeouts" holds seconds
+ * per request kind: {"default", "health", "enrich", "listings"}. out_json is
+ * set only on failure. */
+int talaria_client_new(const char *config_json, TalariaClient **out_client, char **out_json);
+void talaria_client_free(TalariaClient *client);
+void talaria_string_free(char *json);
+
+/* HsufEnrichRequest -> HsufEnrichResponse */
+int talaria_enrich(const TalariaClient *client, const char *request_json, char **out_json);
+/* PublicListingRequest -> ListingResponse. idempotency_key may be NULL. */
+int talaria_create_listing(const TalariaClient *client, const char *request_json,
+                           const char *idempotency_key, char **out_json);
+/* PublicListingRequest -> EnqueueResponse ({"job_id"}). */
+int talaria_enqueue_listing(const TalariaClient *client, const char *request_json,
+                            const char *idempotency_key, char **out_json);
+/* job id -> JobInfo */
+int talaria_poll_job(const TalariaClient *client, const char *job_id, char **out_json);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* TALARIA_H */
@@ -0,0 +1,394 @@
+//! C API over the Hermes client, for desktop shells (Swift, Electron) that
+//! want the CLI's retry, idempotency and error handling without a Rust
+//! toolchain of their own. `include/talaria.h` declares the same functions.
+//!
+//! Every call takes and returns JSON. Requests and responses are the
+//! `talaria_core::models` types as Hermes documents them. Each function
+//! returns a status code (`TALARIA_OK` or one of the `TALARIA_ERR_*` codes)
+//! and, when `out_json` is not NULL, stores a string in it: the response on
+//! success, `{"code", "message", "status", "request_id"}` on failure. Free it
+//! with [`talaria_string_free`].
+//!
+//! A client blocks the calling thread on its own runtime; call it from a
+//! worker thread, not the UI thread. One client may not be used by two
+//! threads at once.
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use talaria_core::blocking::HermesClientBlocking;
+use talaria_core::idempotency::IdempotencyStore;
+use talaria_core::ledger::JobLedger;
+use talaria_core::models::{HsufEnrichRequest, PublicListingRequest};
+use talaria_core::net::Timeouts;
+use talaria_core::{Config, Error, ErrorKind, HermesClient};
+
+pub const TALARIA_OK: c_int = 0;
+pub const TALARIA_ERR_OTHER: c_int = 1;
+pub const TALARIA_ERR_AUTH: c_int = 2;
+/// Bad JSON, a NULL argument or a request Hermes rejected.
+pub const TALARIA_ERR_VALIDATION: c_int = 3;
+pub const TALARIA_ERR_RATE_LIMITED: c_int = 4;
+pub const TALARIA_ERR_UNAVAILABLE: c_int = 5;
+/// The call panicked; the client should be freed and recreated.
+pub const TALARIA_ERR_PANIC: c_int = 6;
+
+/// Opaque client handle returned by [`talaria_client_new`].
+pub struct TalariaClient {
+    inner: HermesClientBlocking,
+}
+
+/// Settings passed to [`talaria_client_new`]; each one overrides what
+/// `Config::load()` found in the environment and config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClientOptions {
+    base_url: Option<String>,
+    api_key: Option<String>,
+    org_id: Option<String>,
+    /// Per-kind request timeouts in seconds, like the `[timeouts]` table.
+    timeouts: Option<Timeouts>,
+}
+
+#[derive(Debug, Serialize)]
+struct FfiError {
+    code: c_int,
+    message: String,
+    /// HTTP status of a failed Hermes call.
+    status: Option<u16>,
+    request_id: Option<String>,
+}
+
+impl FfiError {
+    fn validation(message: impl Into<String>) -> Self {
+        Self {
+            code: TALARIA_ERR_VALIDATION,
+            message: message.into(),
+            status: None,
+            request_id: None,
+        }
+    }
+}
+
+impl From<Error> for FfiError {
+    fn from(err: Error) -> Self {
+        let code = match err.kind() {
+            ErrorKind::Auth => TALARIA_ERR_AUTH,
+            ErrorKind::Validation => TALARIA_ERR_VALIDATION,
+            ErrorKind::RateLimited => TALARIA_ERR_RATE_LIMITED,
+            ErrorKind::Unavailable => TALARIA_ERR_UNAVAILABLE,
+            ErrorKind::Other => TALARIA_ERR_OTHER,
+        };
+        let (status, request_id) = match &err {
+            Error::Api {
+                status, request_id, ..
+            } => (Some(status.as_u16()), request_id.clone()),
+            Error::Http(err) => (err.status().map(|status| status.as_u16()), None),
+            _ => (None, None),
+        };
+        Self {
+            code,
+            message: err.to_string(),
+            status,
+            request_id,
+        }
+    }
+}
+
+impl From<serde_json::Error> for FfiError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::validation(format!("invalid JSON: {err}"))
+    }
+}
+
+type FfiResult<T> = std::result::Result<T, FfiError>;
+
+/// Runs `body`, turning a panic into a `TALARIA_ERR_PANIC` error.
+fn guard<T>(body: impl FnOnce() -> FfiResult<T>) -> FfiResult<T> {
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|msg| msg.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic in talaria".into());
+        Err(FfiError {
+            code: TALARIA_ERR_PANIC,
+            message,
+            status: None,
+            request_id: None,
+        })
+    })
+}
+
+/// Runs `body` and stores its response or error as JSON in `out_json`.
+fn call<T: Serialize>(out_json: *mut *mut c_char, body: impl FnOnce() -> FfiResult<T>) -> c_int {
+    let result = guard(body).and_then(|value| Ok(serde_json::to_string(&value)?));
+    match result {
+        Ok(json) => {
+            write_out(out_json, json);
+            TALARIA_OK
+        }
+        Err(err) => fail(out_json, err),
+    }
+}
+
+fn fail(out_json: *mut *mut c_char, err: FfiError) -> c_int {
+    let code = err.code;
+    // Serializing strings and numbers cannot fail.
+    write_out(out_json, serde_json::to_string(&err).unwrap_or_default());
+    code
+}
+
+fn write_out(out: *mut *mut c_char, json: String) {
+    if out.is_null() {
+        return;
+    }
+    // JSON escapes control characters, so the only NUL would be a bug.
+    let json = CString::new(json).unwrap_or_default();
+    // SAFETY: the caller passes a valid, writable `char **` or NULL.
+    unsafe { *out = json.into_raw() };
+}
+
+/// # Safety
+/// `ptr` is NULL or a NUL-terminated string that outlives the call.
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> FfiResult<&'a str> {
+    if ptr.is_null() {
+        return Err(FfiError::validation(format!("{name} must not be NULL")));
+    }
+    // SAFETY: checked for NULL above; the rest is the caller's contract.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| FfiError::validation(format!("{name} is not valid UTF-8")))
+}
+
+/// # Safety
+/// As for [`str_arg`].
+unsafe fn json_arg<T: DeserializeOwned>(ptr: *const c_char, name: &str) -> FfiResult<T> {
+    // SAFETY: forwarded from the caller.
+    let json = unsafe { str_arg(ptr, name) }?;
+    Ok(serde_json::from_str(json)?)
+}
+
+/// # Safety
+/// `client` is NULL or a live handle from [`talaria_client_new`].
+unsafe fn client_arg<'a>(client: *const TalariaClient) -> FfiResult<&'a HermesClientBlocking> {
+    // SAFETY: the caller's contract.
+    unsafe { client.as_ref() }
+        .map(|client| &client.inner)
+        .ok_or_else(|| FfiError::validation("client must not be NULL"))
+}
+
+/// Creates a client from the user's Talaria config, overridden by the
+/// `base_url`, `api_key`, `org_id` and `timeouts` keys of `config_json`
+/// (which may be NULL). On success `*out_client` holds a handle for
+/// [`talaria_client_free`]; `out_json` is set only on failure.
+///
+/// # Safety
+/// `config_json` is NULL or a NUL-terminated string; `out_client` is a valid
+/// pointer; `out_json` is NULL or a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn talaria_client_new(
+    config_json: *const c_char,
+    out_client: *mut *mut TalariaClient,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    let result = guard(|| {
+        if out_client.is_null() {
+            return Err(FfiError::validation("out_client must not be NULL"));
+        }
+        let options: ClientOptions = if config_json.is_null() {
+            ClientOptions::default()
+        } else {
+            // SAFETY: the caller's contract.
+            unsafe { json_arg(config_json, "config_json") }?
+        };
+        let mut config = Config::load()?;
+        if let Some(base_url) = options.base_url {
+            config.base_url = base_url;
+        }
+        if options.api_key.is_some() {
+            config.api_key = options.api_key;
+        }
+        if options.org_id.is_some() {
+            config.org_id = options.org_id;
+        }
+        if let Some(timeouts) = options.timeouts {
+            timeouts.validate()?;
+            config.timeouts = timeouts;
+        }
+        Ok(HermesClientBlocking::from_async(HermesClient::new(
+            config,
+        )?)?)
+    });
+    match result {
+        Ok(inner) => {
+            // SAFETY: checked for NULL above.
+            unsafe { *out_client = Box::into_raw(Box::new(TalariaClient { inner })) };
+            TALARIA_OK
+        }
+        Err(err) => fail(out_json, err),
+    }
+}
+
+/// Frees a client. NULL is ignored.
+///
+/// # Safety
+/// `client` is NULL or a handle from [`talaria_client_new`] not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn talaria_client_free(client: *mut TalariaClient) {
+    if !client.is_null() {
+        // SAFETY: the caller's contract.
+        drop(unsafe { Box::from_raw(client) });
+    }
+}
+
+/// Frees a string returned through `out_json`. NULL is ignored.
+///
+/// # Safety
+/// `json` is NULL or a string from this library not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn talaria_string_free(json: *mut c_char) {
+    if !json.is_null() {
+        // SAFETY: the caller's contract.
+        drop(unsafe { CString::from_raw(json) });
+    }
+}
+
+/// Runs HSUF enrichment: `request_json` is an `HsufEnrichRequest` (image
+/// URLs and optional context), the result an `HsufEnrichResponse`.
+///
+/// # Safety
+/// `client` is a live handle; `request_json` a NUL-terminated string;
+/// `out_json` NULL or a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn talaria_enrich(
+    client: *const TalariaClient,
+    request_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    call(out_json, || {
+        // SAFETY: the caller's contract.
+        let client = unsafe { client_arg(client) }?;
+        // SAFETY: the caller's contract.
+        let request: HsufEnrichRequest = unsafe { json_arg(request_json, "request_json") }?;
+        Ok(client.hsuf_enrich(&request, true)?)
+    })
+}
+
+/// Which listing endpoint [`listing`] calls.
+#[derive(Clone, Copy)]
+enum ListingMode {
+    Create,
+    Enqueue,
+}
+
+impl ListingMode {
+    fn scope(self) -> &'static str {
+        match self {
+            ListingMode::Create => "listings",
+            ListingMode::Enqueue => "jobs/listings",
+        }
+    }
+}
+
+/// # Safety
+/// As for [`talaria_create_listing`].
+unsafe fn listing(
+    client: *const TalariaClient,
+    request_json: *const c_char,
+    idempotency_key: *const c_char,
+    mode: ListingMode,
+) -> FfiResult<serde_json::Value> {
+    // SAFETY: the caller's contract.
+    let client = unsafe { client_arg(client) }?;
+    // SAFETY: the caller's contract.
+    let request: PublicListingRequest = unsafe { json_arg(request_json, "request_json") }?;
+    let send = |key: &str| -> FfiResult<serde_json::Value> {
+        Ok(match mode {
+            ListingMode::Create => {
+                serde_json::to_value(client.create_listing_idempotent(&request, key)?)?
+            }
+            ListingMode::Enqueue => {
+                let job = client.enqueue_listing_idempotent(&request, key)?;
+                // Same ledger as the CLI, so the job shows up in `jobs list`
+                // and the TUI's failed-jobs queue.
+                JobLedger::open_default()?.record(&job.job_id, &request)?;
+                serde_json::to_value(job)?
+            }
+        })
+    };
+    if !idempotency_key.is_null() {
+        // SAFETY: non-NULL; the rest is the caller's contract.
+        return send(unsafe { str_arg(idempotency_key, "idempotency_key") }?);
+    }
+    // Same store as the CLI: a retry after a failure reuses the key.
+    let mut store = IdempotencyStore::open_default()?;
+    let key = store.key_for(mode.scope(), &request)?;
+    let response = send(&key)?;
+    store.release(mode.scope(), &request)?;
+    Ok(response)
+}
+
+/// Runs the listing pipeline and waits for it: `request_json` is a
+/// `PublicListingRequest`, the result a `ListingResponse`.
+/// `idempotency_key` may be NULL, in which case a key is kept per request
+/// body in the local idempotency store, as the CLI does.
+///
+/// # Safety
+/// `client` is a live handle; `request_json` a NUL-terminated string;
+/// `idempotency_key` NULL or a NUL-terminated string; `out_json` NULL or a
+/// valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn talaria_create_listing(
+    client: *const TalariaClient,
+    request_json: *const c_char,
+    idempotency_key: *const c_char,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    call(out_json, || {
+        // SAFETY: forwarded from the caller.
+        unsafe { listing(client, request_json, idempotency_key, ListingMode::Create) }
+    })
+}
+
+/// Like [`talaria_create_listing`] but queues the pipeline and returns at
+/// once with an `EnqueueResponse` (`{"job_id", ...}`) for
+/// [`talaria_poll_job`].
+///
+/// # Safety
+/// As for [`talaria_create_listing`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn talaria_enqueue_listing(
+    client: *const TalariaClient,
+    request_json: *const c_char,
+    idempotency_key: *const c_char,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    call(out_json, || {
+        // SAFETY: forwarded from the caller.
+        unsafe { listing(client, request_json, idempotency_key, ListingMode::Enqueue) }
+    })
+}
+
+/// Fetches the status of a queued job once, as a `JobInfo`. Poll until its
+/// `state` is `completed` or `failed`.
+///
+/// # Safety
+/// `client` is a live handle; `job_id` a NUL-terminated string; `out_json`
+/// NULL or a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn talaria_poll_job(
+    client: *const TalariaClient,
+    job_id: *const c_char,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    call(out_json, || {
+        // SAFETY: the caller's contract.
+        let client = unsafe { client_arg(client) }?;
+        // SAFETY: the caller's contract.
+        let job_id = unsafe { str_arg(job_id, "job_id") }?;
+        Ok(client.get_job_status(job_id)?)
+    })
+}
@@ -0,0 +1,177 @@
+use serde_json::{Value, json};
+use std::ffi::{CStr, CString, c_char};
+use std::path::PathBuf;
+use std::ptr;
+use std::sync::OnceLock;
+use talaria::*;
+use talaria_core::ledger::{JobLedger, TrackedState};
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Takes ownership of a string returned through `out_json`.
+fn take_json(out: *mut c_char) -> Value {
+    assert!(!out.is_null());
+    let json = unsafe { CStr::from_ptr(out) }.to_str().unwrap().to_owned();
+    unsafe { talaria_string_free(out) };
+    serde_json::from_str(&json).unwrap()
+}
+
+fn cstr(value: &Value) -> CString {
+    CString::new(value.to_string()).unwrap()
+}
+
+/// Points the local data dir (job ledger, idempotency keys) at a temp dir.
+/// Every test calls this first.
+fn isolate_data_dir() -> &'static PathBuf {
+    static DIR: OnceLock<PathBuf> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("talaria-ffi-test-{}", std::process::id()));
+        // SAFETY: runs before any test reads the environment; tests racing
+        // here wait on the OnceLock until it is set.
+        unsafe { std::env::set_var("XDG_DATA_HOME", &dir) };
+        dir
+    })
+}
+
+#[test]
+fn c_api_enqueues_and_polls_a_listing_job() {
+    isolate_data_dir();
+    let server_rt = tokio::runtime::Runtime::new().unwrap();
+    let server =
+        server_rt.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+            .and(path("/hsuf/enrich"))
+            .and(header("x-hermes-key", "sk_test"))
+            .and(body_partial_json(json!({"images": ["https://img.example/a.jpg"]})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "product": {"name": "Blue mug", "image": "https://img.example/a.jpg", "offers": {}},
+            })))
+            .mount(&server)
+            .await;
+            Mock::given(method("POST"))
+                .and(path("/jobs/listings"))
+                .and(header("idempotency-key", "key-1"))
+                .respond_with(ResponseTemplate::new(202).set_body_json(json!({"job_id": "job_1"})))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/jobs/job_1"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": "job_1",
+                    "state": "running",
+                    "created_at": "2026-01-01T00:00:00Z",
+                    "updated_at": "2026-01-01T00:00:00Z",
+                    "request": {
+                        "fulfillment_policy_id": "1",
+                        "payment_policy_id": "2",
+                        "return_policy_id": "3",
+                        "merchant_location_key": "home",
+                        "images_source": "https://img.example/a.jpg",
+                    },
+                })))
+                .mount(&server)
+                .await;
+            server
+        });
+
+    let config = cstr(&json!({"base_url": server.uri(), "api_key": "sk_test"}));
+    let mut client = ptr::null_mut();
+    let mut out = ptr::null_mut();
+    let code = unsafe { talaria_client_new(config.as_ptr(), &mut client, &mut out) };
+    assert_eq!(code, TALARIA_OK);
+    assert!(out.is_null());
+
+    let enrich = cstr(&json!({"images": ["https://img.example/a.jpg"]}));
+    let code = unsafe { talaria_enrich(client, enrich.as_ptr(), &mut out) };
+    assert_eq!(code, TALARIA_OK);
+    assert_eq!(take_json(out)["product"]["name"], "Blue mug");
+
+    let listing = cstr(&json!({
+        "fulfillment_policy_id": "1",
+        "payment_policy_id": "2",
+        "return_policy_id": "3",
+        "merchant_location_key": "home",
+        "images_source": "https://img.example/a.jpg",
+    }));
+    let key = CString::new("key-1").unwrap();
+    let code = unsafe { talaria_enqueue_listing(client, listing.as_ptr(), key.as_ptr(), &mut out) };
+    assert_eq!(code, TALARIA_OK);
+    assert_eq!(take_json(out)["job_id"], "job_1");
+    let recorded = JobLedger::open_default().unwrap().get("job_1").unwrap();
+    let recorded = recorded.expect("enqueued job in the ledger");
+    assert_eq!(recorded.state, TrackedState::Pending);
+    assert_eq!(recorded.request.unwrap().merchant_location_key, "home");
+
+    let job_id = CString::new("job_1").unwrap();
+    let code = unsafe { talaria_poll_job(client, job_id.as_ptr(), &mut out) };
+    assert_eq!(code, TALARIA_OK);
+    assert_eq!(take_json(out)["state"], "running");
+
+    unsafe { talaria_client_free(client) };
+    server_rt.block_on(server.verify());
+}
+
+#[test]
+fn c_api_reports_errors_as_codes_and_json() {
+    isolate_data_dir();
+    let server_rt = tokio::runtime::Runtime::new().unwrap();
+    let server = server_rt.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/jobs/denied"))
+            .respond_with(
+                ResponseTemplate::new(401)
+                    .insert_header("x-request-id", "req_9")
+                    .set_body_json(json!({"error": "invalid key"})),
+            )
+            .mount(&server)
+            .await;
+        server
+    });
+
+    let config = cstr(&json!({"base_url": server.uri(), "api_key": "sk_bad"}));
+    let mut client = ptr::null_mut();
+    let mut out = ptr::null_mut();
+    assert_eq!(
+        unsafe { talaria_client_new(config.as_ptr(), &mut client, &mut out) },
+        TALARIA_OK
+    );
+
+    let job_id = CString::new("denied").unwrap();
+    let code = unsafe { talaria_poll_job(client, job_id.as_ptr(), &mut out) };
+    assert_eq!(code, TALARIA_ERR_AUTH);
+    let err = take_json(out);
+    assert_eq!(err["code"], TALARIA_ERR_AUTH);
+    assert_eq!(err["status"], 401);
+    assert_eq!(err["request_id"], "req_9");
+
+    let code = unsafe { talaria_poll_job(client, ptr::null(), &mut out) };
+    assert_eq!(code, TALARIA_ERR_VALIDATION);
+    assert!(
+        take_json(out)["message"]
+            .as_str()
+            .unwrap()
+            .contains("job_id")
+    );
+
+    let bad = CString::new("{not json").unwrap();
+    let code = unsafe { talaria_enrich(client, bad.as_ptr(), &mut out) };
+    assert_eq!(code, TALARIA_ERR_VALIDATION);
+    assert!(
+        take_json(out)["message"]
+            .as_str()
+            .unwrap()
+            .contains("invalid JSON")
+    );
+
+    let unknown = cstr(&json!({"base_uri": "typo"}));
+    let mut other = ptr::null_mut();
+    let code = unsafe { talaria_client_new(unknown.as_ptr(), &mut other, &mut out) };
+    assert_eq!(code, TALARIA_ERR_VALIDATION);
+    assert!(other.is_null());
+    take_json(out);
+
+    unsafe { talaria_client_free(client) };
+}
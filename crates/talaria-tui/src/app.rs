@@ -1,19 +1,31 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fs::{self, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::PreviewCommand;
 use crate::camera;
 use crate::camera::LatestFrameSlot;
+use crate::jobs::{JobKey, JobKind, RunningJobs};
+use crate::keymap::{Action, KeyMap};
+use crate::shutdown::ShutdownReport;
 use crate::storage;
+use crate::storage::sync::SyncResolution;
+use crate::types::PreviewCacheCommand;
 use crate::types::{
-    AccountCommand, AccountEvent, ActivityEntry, ActivityLog, AppCommand, AppEvent, CaptureCommand,
-    CaptureEvent, CaptureStatus, CreditsSnapshot, JobStatus, PreviewEvent, Severity,
-    StorageCommand, StorageEvent, UploadCommand, UploadJob,
+    AccountCommand, AccountEvent, ActivityEntry, ActivityLog, AppCommand, AppEvent, BulkAction,
+    CaptureCommand, CaptureEvent, CaptureStatus, CapturedFrame, ConnectionState, CreditsSnapshot,
+    HealthReport, JobStatus, PedalEvent, PreviewEvent, Severity, StorageCommand, StorageEvent,
+    UploadCommand, UploadJob,
 };
+use crate::util::assist::{PreviewAssist, draw_peaking};
+use crate::util::clipboard::Clipboard;
+use crate::util::countdown::draw_countdown;
+use crate::util::downscale::fit_within;
+use crate::util::measure::{MeasureOverlay, draw_overlay};
+use crate::util::validate;
 use chrono::{DateTime, Local};
 use crossbeam_channel::Sender;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -21,12 +33,33 @@ use image::DynamicImage;
 use ratatui_image::picker::{Picker, ProtocolType};
 use ratatui_image::protocol::StatefulProtocol;
 use serde_json::{Number, Value};
-use talaria_core::config::EbaySettings;
-use talaria_core::models::{LlmModel, LlmStageOptions, MarketplaceId};
+use talaria_core::barcode::{self, Barcode};
+use talaria_core::browser;
+use talaria_core::color::WhiteBalance;
+use talaria_core::config::{
+    AccessMode, CaptureFiles, CaptureTriggers, DEFAULT_DASHBOARD_URL, EbaySettings, PreviewBackend,
+    SecondCamera, SupabaseConfig,
+};
+use talaria_core::grading::{self, ConditionGrade, DefectNote, ProductGrading};
+use talaria_core::i18n;
+use talaria_core::ledger::JobLedgerEntry;
+use talaria_core::measure::{LengthUnit, MeasuredDimensions, MeasurementConfig};
+use talaria_core::models::{
+    ComparablePricesRequest, LlmModel, LlmStageOptions, MarketplaceId, PublicPipelineOverrides,
+};
+use talaria_core::package::{self, PackageTemplate};
+use talaria_core::pricing::{PriceSuggestion, PricingRules};
+use talaria_core::quality::QualityRules;
+use talaria_core::shots::SessionTemplate;
+use talaria_core::t;
+use talaria_core::workspace::{self, KnownCategory};
 
 pub const PREVIEW_HEIGHT_MIN_PCT: u8 = 20;
 pub const PREVIEW_HEIGHT_MAX_PCT: u8 = 80;
 const CREDITS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const CONFIG_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+/// Switching to the Products tab re-lists products at most this often.
+const PRODUCTS_REFRESH_DEBOUNCE: Duration = Duration::from_secs(10);
 
 fn load_activity_log(path: &Path, capacity: usize) -> ActivityLog {
     let mut log = ActivityLog::new(capacity);
@@ -65,7 +98,13 @@ fn append_activity_log(path: &Path, entry: &ActivityEntry) {
             return;
         }
     };
-    if serde_json::to_writer(&mut file, entry).is_ok() {
+    let Ok(mut line) = serde_json::to_value(entry) else {
+        return;
+    };
+    if let Some(operator) = storage::operator() {
+        line["operator"] = operator.into();
+    }
+    if serde_json::to_writer(&mut file, &line).is_ok() {
         let _ = writeln!(file);
     }
 }
@@ -86,6 +125,7 @@ pub enum AppTab {
     Quickstart,
     Products,
     Activity,
+    Analytics,
     Settings,
 }
 
@@ -95,6 +135,88 @@ pub enum ProductsMode {
     Workspace,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProductStatusFilter {
+    #[default]
+    All,
+    HasListing,
+    Published,
+    Sold,
+    NeedsEnrich,
+}
+
+impl ProductStatusFilter {
+    pub fn next(self) -> Self {
+        match self {
+            ProductStatusFilter::All => ProductStatusFilter::HasListing,
+            ProductStatusFilter::HasListing => ProductStatusFilter::Published,
+            ProductStatusFilter::Published => ProductStatusFilter::Sold,
+            ProductStatusFilter::Sold => ProductStatusFilter::NeedsEnrich,
+            ProductStatusFilter::NeedsEnrich => ProductStatusFilter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProductStatusFilter::All => "all",
+            ProductStatusFilter::HasListing => "has listing",
+            ProductStatusFilter::Published => "published",
+            ProductStatusFilter::Sold => "sold",
+            ProductStatusFilter::NeedsEnrich => "needs enrich",
+        }
+    }
+
+    fn matches(self, product: &storage::ProductSummary) -> bool {
+        match self {
+            ProductStatusFilter::All => true,
+            ProductStatusFilter::HasListing => product.has_listing,
+            ProductStatusFilter::Published => product.published,
+            ProductStatusFilter::Sold => product.sold,
+            ProductStatusFilter::NeedsEnrich => product.needs_enrich,
+        }
+    }
+}
+
+/// Date range filter applied to a product's `updated_at`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProductDateFilter {
+    #[default]
+    Any,
+    Today,
+    LastWeek,
+    LastMonth,
+}
+
+impl ProductDateFilter {
+    pub fn next(self) -> Self {
+        match self {
+            ProductDateFilter::Any => ProductDateFilter::Today,
+            ProductDateFilter::Today => ProductDateFilter::LastWeek,
+            ProductDateFilter::LastWeek => ProductDateFilter::LastMonth,
+            ProductDateFilter::LastMonth => ProductDateFilter::Any,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProductDateFilter::Any => "any date",
+            ProductDateFilter::Today => "today",
+            ProductDateFilter::LastWeek => "last 7 days",
+            ProductDateFilter::LastMonth => "last 30 days",
+        }
+    }
+
+    fn matches(self, product: &storage::ProductSummary) -> bool {
+        let now = Local::now();
+        match self {
+            ProductDateFilter::Any => true,
+            ProductDateFilter::Today => product.updated_at.date_naive() == now.date_naive(),
+            ProductDateFilter::LastWeek => now - product.updated_at <= chrono::Duration::days(7),
+            ProductDateFilter::LastMonth => now - product.updated_at <= chrono::Duration::days(30),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProductsSubTab {
     Context,
@@ -115,12 +237,19 @@ pub enum ContextImageEntry {
         sharpness_score: Option<f64>,
         created_at: DateTime<Local>,
         selected: bool,
+        shot: Option<String>,
+        /// Camera of a paired capture (e.g. `side`) or `turntable`.
+        tag: Option<String>,
+        /// The user's hero, or (`suggested`) the automatic pick.
+        hero: bool,
+        suggested: bool,
     },
     Product {
         rel_path: String,
         created_at: DateTime<Local>,
         source: String,
         hero: bool,
+        label: Option<String>,
     },
 }
 
@@ -137,16 +266,24 @@ pub enum PostSaveNotice {
     ListingsUpdated,
 }
 
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+/// Shown per toast while others are waiting, so a backlog drains quickly.
+const TOAST_BUSY_DURATION: Duration = Duration::from_millis(1200);
+const TOAST_QUEUE_LIMIT: usize = 20;
+
 #[derive(Debug, Clone)]
 pub struct Toast {
     pub message: String,
     pub severity: Severity,
-    pub expires_at: Instant,
+    /// Sticky toasts stay until dismissed instead of expiring.
+    pub sticky: bool,
+    /// Set once the toast reaches the front of the queue.
+    pub expires_at: Option<Instant>,
 }
 
 #[derive(Debug, Clone)]
 pub struct DeleteConfirm {
-    pub product_id: String,
+    pub product_ids: Vec<String>,
     pub expires_at: Instant,
 }
 
@@ -156,6 +293,106 @@ pub struct PickerState {
     pub search: String,
     pub selected: usize,
     pub products: Vec<storage::ProductSummary>,
+    pub status_filter: ProductStatusFilter,
+    pub marketplace_filter: Option<String>,
+    pub date_filter: ProductDateFilter,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TrashViewState {
+    pub open: bool,
+    pub selected: usize,
+    pub items: Vec<storage::trash::TrashItem>,
+}
+
+/// Failed listing jobs from the job ledger, retried from the Home tab.
+/// `overrides` holds the request's overrides as JSON while they are edited.
+#[derive(Debug, Clone, Default)]
+pub struct FailedJobsState {
+    pub open: bool,
+    pub selected: usize,
+    pub jobs: Vec<JobLedgerEntry>,
+    pub overrides: Option<String>,
+}
+
+/// The active product's Hermes media next to its local images, with the
+/// orphans on either side called out.
+#[derive(Debug, Clone, Default)]
+pub struct MediaViewState {
+    pub open: bool,
+    pub product_id: String,
+    pub loading: bool,
+    pub selected: usize,
+    pub items: Vec<storage::media::GalleryItem>,
+    /// Rank being typed for the selected media.
+    pub rank_input: Option<String>,
+    /// Remote orphan that a second `d` deletes.
+    pub delete_armed: Option<String>,
+}
+
+/// Startup prompt offering to resume sessions that were never committed.
+#[derive(Debug, Clone, Default)]
+pub struct ResumePromptState {
+    pub open: bool,
+    pub selected: usize,
+    pub sessions: Vec<storage::ResumableSession>,
+}
+
+/// A barcode read from a product image, waiting to be accepted.
+#[derive(Debug, Clone)]
+pub struct BarcodePrompt {
+    pub product_id: String,
+    pub barcode: Barcode,
+    pub frame: PathBuf,
+}
+
+/// A suggested price range for one listing; `price` is what Enter applies.
+#[derive(Debug, Clone)]
+pub struct PricePrompt {
+    pub product_id: String,
+    pub marketplace_key: String,
+    pub suggestion: PriceSuggestion,
+    pub price: f64,
+}
+
+/// Fields that both this machine and Hermes changed since the last sync,
+/// waiting for the user to pick a side.
+#[derive(Debug, Clone)]
+pub struct SyncConflictPrompt {
+    pub product_id: String,
+    pub sku_alias: String,
+    pub conflicts: Vec<talaria_core::product_diff::FieldDiff>,
+    pub selected: usize,
+}
+
+/// Measuring the active product against the calibrated camera preview. The
+/// box is sized in calibration units; the front view measures width × height,
+/// the side view depth × height.
+#[derive(Debug, Clone, Default)]
+pub struct MeasureState {
+    pub open: bool,
+    pub side_view: bool,
+    pub across: f64,
+    pub tall: f64,
+}
+
+/// Condition grading form for the active product.
+#[derive(Debug, Clone, Default)]
+pub struct GradingState {
+    pub open: bool,
+    pub grades: Vec<ConditionGrade>,
+    pub selected: usize,
+    pub field: GradingField,
+    pub notes: String,
+    pub defects: Vec<DefectNote>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradingField {
+    #[default]
+    Grade,
+    Notes,
+    Defect(usize),
 }
 
 #[derive(Debug, Clone)]
@@ -164,6 +401,8 @@ pub struct CameraPickerState {
     pub selected: usize,
     pub devices: Vec<camera::CameraDevice>,
     pub error: Option<String>,
+    /// A network camera URL being typed (`u`).
+    pub url_input: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -178,8 +417,34 @@ pub struct SettingsPickerState {
 pub struct ConfigInfo {
     pub base_url: Option<String>,
     pub hermes_api_key_present: bool,
+    /// Organization Hermes calls act for; `None` is the key's default.
+    pub org_id: Option<String>,
     pub online_ready: bool,
     pub preview_height_pct: u8,
+    pub supabase: Option<SupabaseConfig>,
+    /// Configured workspace override; `None` means the default location.
+    pub captures_dir: Option<PathBuf>,
+    pub preview_backend: PreviewBackend,
+    /// Network cameras listed in the device picker.
+    pub camera_urls: Vec<String>,
+    /// Fake uploads and synthetic camera frames (`--simulate`/`TALARIA_SIMULATE`).
+    pub simulate: bool,
+    /// Grading rubric; empty means the built-in one.
+    pub condition_grades: Vec<ConditionGrade>,
+    pub measurement: MeasurementConfig,
+    pub white_balance: WhiteBalance,
+    pub package_templates: Vec<PackageTemplate>,
+    pub session_templates: Vec<SessionTemplate>,
+    pub pricing: PricingRules,
+    pub quality: QualityRules,
+    pub capture_triggers: CaptureTriggers,
+    pub capture_files: CaptureFiles,
+    pub second_camera: Option<SecondCamera>,
+    /// `CaptureOnly` turns off publishing, deleting and credit-spending
+    /// actions.
+    pub mode: AccessMode,
+    /// `dashboard_url` from config; `None` means the default dashboard.
+    pub dashboard_url: Option<String>,
 }
 
 pub struct TerminalPreviewState {
@@ -189,12 +454,35 @@ pub struct TerminalPreviewState {
     pub image_path: Option<PathBuf>,
     pub last_camera_seq: u64,
     pub last_camera_refresh: Instant,
+    /// Cells the live panel had on the last draw; frames are shrunk to that
+    /// many pixels before encoding.
+    pub camera_cells: Option<(u16, u16)>,
     pub last_error: Option<String>,
+    /// Measurement overlay drawn on the last camera frame.
+    pub overlay: Option<MeasureOverlay>,
+    /// Countdown drawn on the last camera frame.
+    pub countdown: Option<u32>,
+    /// Zoom and peaking applied to the last camera frame.
+    pub assist: PreviewAssist,
+}
+
+/// Cached grid thumbnail for one product. `protocol` is only built when the
+/// terminal supports a graphics protocol; otherwise the UI draws `halfblocks`.
+pub struct ProductThumbnail {
+    pub path: PathBuf,
+    /// Modification time of `path` when it was decoded.
+    pub modified: Option<SystemTime>,
+    pub image: DynamicImage,
+    pub protocol: Option<StatefulProtocol>,
+    pub halfblocks: Option<((u16, u16), Vec<ratatui::text::Line<'static>>)>,
 }
 
 pub struct AppState {
     pub should_quit: bool,
     pub help_open: bool,
+    /// Debug overlay with the live metrics registry.
+    pub metrics_open: bool,
+    pub keys: KeyMap,
     pub active_tab: AppTab,
     pub spinner_started_at: Instant,
 
@@ -202,12 +490,16 @@ pub struct AppState {
     pub stderr_log_path: Option<PathBuf>,
     pub activity_log_path: Option<PathBuf>,
     pub latest_frame: Arc<LatestFrameSlot>,
+    /// Running uploads and generation jobs, for cancelling them.
+    pub jobs: RunningJobs,
 
     pub camera_connected: bool,
     pub preview_enabled: bool,
     pub device_index: i32,
     pub capture_status: CaptureStatus,
     pub preview_image_path: Option<PathBuf>,
+    /// Cached, preview-sized copy of `preview_image_path` once it is ready.
+    pub preview_display_path: Option<PathBuf>,
     pub terminal_preview: Option<TerminalPreviewState>,
     pub preview_height_pct: u8,
 
@@ -215,15 +507,40 @@ pub struct AppState {
     pub active_session: Option<storage::SessionManifest>,
 
     pub last_capture_rel: Option<String>,
+    /// Shot list new sessions start with: the one last picked with `T`.
+    pub session_template: Option<String>,
+    /// Session whose missing required shots were already pointed out; the
+    /// next save commits it anyway.
+    pub shot_override: Option<String>,
     pub last_commit_message: Option<String>,
     pub last_error: Option<String>,
+    /// Hermes `request_id` of the last failed call, for `copy_request_id`.
+    pub last_request_id: Option<String>,
+    /// Latest listing job queued per product id, for `copy_job_id`.
+    pub listing_job_ids: HashMap<String, String>,
+    clipboard: Clipboard,
 
     pub activity: ActivityLog,
-    pub toast: Option<Toast>,
+    pub toasts: VecDeque<Toast>,
     pub delete_confirm: Option<DeleteConfirm>,
 
     pub picker: PickerState,
     pub camera_picker: CameraPickerState,
+    pub trash_view: TrashViewState,
+    pub failed_jobs: FailedJobsState,
+    pub media_view: MediaViewState,
+    pub resume_prompt: ResumePromptState,
+    pub grading: GradingState,
+    pub measure: MeasureState,
+    /// Focus assist for the live preview.
+    pub preview_assist: PreviewAssist,
+    pub barcode_prompt: Option<BarcodePrompt>,
+    /// Codes the user declined; automatic scans stop offering them.
+    barcode_dismissed: HashSet<String>,
+    pub price_prompt: Option<PricePrompt>,
+    pub sync_conflict: Option<SyncConflictPrompt>,
+    /// The Analytics tab's numbers, reloaded each time the tab is entered.
+    pub stats: Option<talaria_core::stats::Stats>,
     pub settings_picker: SettingsPickerState,
 
     pub config: ConfigInfo,
@@ -236,10 +553,21 @@ pub struct AppState {
     pub credits_error: Option<String>,
     pub credits_last_updated: Option<Instant>,
     pub credits_next_refresh: Instant,
+    /// Latest Hermes health check; drives the tab bar indicator.
+    pub connection: HealthReport,
+    /// Last seen config file mtime; a change triggers a client reload.
+    config_mtime: Option<std::time::SystemTime>,
+    config_next_check: Instant,
 
     pub uploads: Vec<UploadJob>,
     pub product_grid_selected: usize,
     pub product_grid_cols: usize,
+    /// First grid row on screen; only visible rows are rendered.
+    pub product_grid_scroll: usize,
+    pub product_thumbnails: HashMap<String, ProductThumbnail>,
+    /// Thumbnail files that failed to decode, with their modification time;
+    /// retried only once the file changes.
+    pub product_thumbnail_failures: HashMap<String, (PathBuf, Option<SystemTime>)>,
     pub products_mode: ProductsMode,
     pub products_subtab: ProductsSubTab,
     pub context_focus: ContextFocus,
@@ -264,8 +592,15 @@ pub struct AppState {
     pub listings_field_edit_image_index: Option<usize>,
     pub listings_field_edit_dimension: Option<PackageDimensionKey>,
     pub listings_field_edit_kind: ListingEditKind,
+    pub listings_aspect_completion: Option<(String, usize)>,
+    pub listings_category_choice: Option<(String, String)>,
+    pub listings_category_index: usize,
+    pub listings_condition_choice: Option<(String, Option<i32>)>,
+    pub listings_condition_index: usize,
     pub listings_field_list_offset: usize,
     pub listings_editing: bool,
+    pub product_search_editing: bool,
+    pub product_marks: BTreeSet<String>,
     pub listings_edit_buffer: String,
     pub settings_selected: usize,
     pub settings_editing: bool,
@@ -275,6 +610,8 @@ pub struct AppState {
     pub pending_product_selection: Option<String>,
     pub pending_new_product_session: bool,
     pub products_loading: bool,
+    /// When the product list was last fully loaded.
+    products_listed_at: Option<Instant>,
     pub product_syncing: bool,
     pub structure_inference: bool,
     pub listing_inference: bool,
@@ -291,9 +628,11 @@ impl AppState {
         llm_ingest: Option<LlmStageOptions>,
         llm_aspects: Option<LlmStageOptions>,
         prompt_rules: Option<String>,
+        keys: KeyMap,
         startup_warnings: Vec<String>,
         latest_frame: Arc<LatestFrameSlot>,
         terminal_preview: Option<TerminalPreviewState>,
+        jobs: RunningJobs,
     ) -> Self {
         let activity = activity_log_path
             .as_ref()
@@ -302,12 +641,15 @@ impl AppState {
         let mut state = Self {
             should_quit: false,
             help_open: false,
+            metrics_open: false,
+            keys,
             active_tab: AppTab::Home,
             spinner_started_at: Instant::now(),
             captures_dir,
             stderr_log_path,
             activity_log_path,
             latest_frame,
+            jobs,
             camera_connected: false,
             preview_enabled: false,
             device_index: 0,
@@ -317,8 +659,14 @@ impl AppState {
                 fps: 0.0,
                 dropped_frames: 0,
                 frame_size: None,
+                device_url: None,
+                link: None,
+                countdown: None,
+                turntable: None,
+                second: None,
             },
             preview_image_path: None,
+            preview_display_path: None,
             terminal_preview,
             preview_height_pct: {
                 let raw = if config.preview_height_pct == 0 {
@@ -331,22 +679,43 @@ impl AppState {
             active_product: None,
             active_session: None,
             last_capture_rel: None,
+            session_template: None,
+            shot_override: None,
             last_commit_message: None,
             last_error: None,
+            last_request_id: None,
+            listing_job_ids: HashMap::new(),
+            clipboard: Clipboard::default(),
             activity,
-            toast: None,
+            toasts: VecDeque::new(),
             delete_confirm: None,
             picker: PickerState {
                 open: false,
                 search: String::new(),
                 selected: 0,
                 products: Vec::new(),
+                status_filter: ProductStatusFilter::All,
+                marketplace_filter: None,
+                date_filter: ProductDateFilter::Any,
             },
+            trash_view: TrashViewState::default(),
+            failed_jobs: FailedJobsState::default(),
+            media_view: MediaViewState::default(),
+            resume_prompt: ResumePromptState::default(),
+            grading: GradingState::default(),
+            measure: MeasureState::default(),
+            preview_assist: PreviewAssist::default(),
+            barcode_prompt: None,
+            barcode_dismissed: HashSet::new(),
+            price_prompt: None,
+            sync_conflict: None,
+            stats: None,
             camera_picker: CameraPickerState {
                 open: false,
                 selected: 0,
                 devices: Vec::new(),
                 error: None,
+                url_input: None,
             },
             settings_picker: SettingsPickerState {
                 open: false,
@@ -364,9 +733,15 @@ impl AppState {
             credits_error: None,
             credits_last_updated: None,
             credits_next_refresh: Instant::now(),
+            connection: HealthReport::default(),
+            config_mtime: config_file_mtime(),
+            config_next_check: Instant::now() + CONFIG_WATCH_INTERVAL,
             uploads: Vec::new(),
             product_grid_selected: 0,
             product_grid_cols: 3,
+            product_grid_scroll: 0,
+            product_thumbnails: HashMap::new(),
+            product_thumbnail_failures: HashMap::new(),
             products_mode: ProductsMode::Grid,
             products_subtab: ProductsSubTab::Context,
             context_focus: ContextFocus::Images,
@@ -390,8 +765,15 @@ impl AppState {
             listings_field_edit_image_index: None,
             listings_field_edit_dimension: None,
             listings_field_edit_kind: ListingEditKind::Text,
+            listings_aspect_completion: None,
+            listings_category_choice: None,
+            listings_category_index: 0,
+            listings_condition_choice: None,
+            listings_condition_index: 0,
             listings_field_list_offset: 0,
             listings_editing: false,
+            product_search_editing: false,
+            product_marks: BTreeSet::new(),
             listings_edit_buffer: String::new(),
             settings_selected: 0,
             settings_editing: false,
@@ -401,6 +783,7 @@ impl AppState {
             pending_product_selection: None,
             pending_new_product_session: false,
             products_loading: false,
+            products_listed_at: None,
             product_syncing: false,
             structure_inference: false,
             listing_inference: false,
@@ -427,6 +810,29 @@ impl AppState {
         std::mem::take(&mut self.pending_commands)
     }
 
+    /// Notes workers that had to be abandoned (or panicked) at exit.
+    pub fn record_shutdown(&mut self, report: &ShutdownReport) {
+        self.record_activity(ActivityEntry {
+            at: Local::now(),
+            severity: Severity::Warning,
+            message: report.summary(),
+        });
+    }
+
+    /// Whether this station may do `action`; a capture-only one says why not.
+    fn permitted(&mut self, action: &str) -> bool {
+        match self.config.mode.permit(action) {
+            Ok(()) => true,
+            Err(err) => {
+                self.toast(
+                    t!("tui.toast.not_permitted", error = err),
+                    Severity::Warning,
+                );
+                false
+            }
+        }
+    }
+
     fn record_activity(&mut self, entry: ActivityEntry) {
         self.activity.push(entry.clone());
         if let Some(path) = self.activity_log_path.as_ref() {
@@ -434,10 +840,29 @@ impl AppState {
         }
     }
 
+    /// The toast currently on screen; the rest of the queue waits behind it.
+    pub fn current_toast(&self) -> Option<&Toast> {
+        self.toasts.front()
+    }
+
     pub fn prune_toast(&mut self) {
-        if let Some(toast) = &self.toast {
-            if Instant::now() >= toast.expires_at {
-                self.toast = None;
+        let now = Instant::now();
+        if self
+            .toasts
+            .front()
+            .is_some_and(|t| !t.sticky && t.expires_at.is_some_and(|at| now >= at))
+        {
+            self.toasts.pop_front();
+        }
+        let busy = self.toasts.len() > 1;
+        if let Some(front) = self.toasts.front_mut() {
+            if front.expires_at.is_none() {
+                let duration = if busy {
+                    TOAST_BUSY_DURATION
+                } else {
+                    TOAST_DURATION
+                };
+                front.expires_at = Some(now + duration);
             }
         }
         if let Some(confirm) = &self.delete_confirm {
@@ -448,6 +873,7 @@ impl AppState {
     }
 
     pub fn tick(&mut self) {
+        self.watch_config_file();
         if !self.config.hermes_api_key_present {
             return;
         }
@@ -464,33 +890,129 @@ impl AppState {
         self.credits_next_refresh = Instant::now() + CREDITS_REFRESH_INTERVAL;
     }
 
+    /// Reloads clients when the config file changes underneath us, e.g. after
+    /// `talaria auth login` in another terminal.
+    fn watch_config_file(&mut self) {
+        if Instant::now() < self.config_next_check {
+            return;
+        }
+        self.config_next_check = Instant::now() + CONFIG_WATCH_INTERVAL;
+        let mtime = config_file_mtime();
+        if mtime != self.config_mtime {
+            self.config_mtime = mtime;
+            self.pending_commands.push(AppCommand::ReloadConfig);
+        }
+    }
+
+    fn apply_reloaded_config(&mut self, cfg: talaria_core::config::Config) {
+        let had_key = self.config.hermes_api_key_present;
+        storage::set_operator(cfg.operator_name());
+        self.config.base_url = Some(cfg.base_url);
+        self.config.hermes_api_key_present = cfg.api_key.is_some();
+        if self.config.org_id != cfg.org_id {
+            // Credits belong to the organization.
+            self.config.org_id = cfg.org_id;
+            self.credits = None;
+            self.credits_next_refresh = Instant::now();
+        }
+        self.config.supabase = cfg.supabase;
+        self.config.captures_dir = cfg.tui_captures_dir;
+        self.config.preview_backend = cfg.tui_preview_backend;
+        self.config.camera_urls = cfg.tui_camera_urls;
+        self.config.condition_grades = cfg.condition_grades;
+        self.config.measurement = cfg.measurement;
+        if self.config.white_balance != cfg.white_balance {
+            self.config.white_balance = cfg.white_balance;
+            if let Some(session) = self.active_session.clone() {
+                self.send_capture_target(&session);
+            }
+        }
+        self.config.package_templates = cfg.package_templates;
+        self.config.session_templates = cfg.session_templates;
+        self.config.pricing = cfg.pricing;
+        self.config.quality = cfg.quality;
+        self.config.capture_triggers = cfg.tui_capture_triggers;
+        self.config.capture_files = cfg.tui_capture_files;
+        self.config.second_camera = cfg.tui_second_camera;
+        self.config.mode = cfg.mode;
+        self.config.dashboard_url = cfg.dashboard_url;
+        i18n::init(cfg.locale.as_deref());
+        self.ebay_settings = cfg.ebay;
+        self.llm_ingest = cfg.llm_ingest;
+        self.llm_aspects = cfg.llm_aspects;
+        self.prompt_rules = cfg.prompt_rules;
+        if had_key != self.config.hermes_api_key_present {
+            self.credits = None;
+            self.credits_error = None;
+            self.credits_next_refresh = Instant::now();
+            if self.config.hermes_api_key_present {
+                self.toast(t!("tui.toast.key_found"), Severity::Success);
+            } else {
+                self.toast(t!("tui.toast.key_removed"), Severity::Warning);
+            }
+        }
+    }
+
     pub fn preview_height_pct(&self) -> u16 {
         self.preview_height_pct
             .clamp(PREVIEW_HEIGHT_MIN_PCT, PREVIEW_HEIGHT_MAX_PCT) as u16
     }
 
     pub fn update_terminal_preview(&mut self) {
+        let overlay = self.measure_overlay();
+        let countdown = self.capture_status.countdown;
+        let assist = self.preview_assist;
         let Some(preview) = self.terminal_preview.as_mut() else {
             return;
         };
         let has_camera = self.capture_status.streaming;
 
-        if has_camera {
-            if let Some((seq, frame, _)) = self.latest_frame.get_latest() {
-                let should_refresh = seq != preview.last_camera_seq
-                    && preview.last_camera_refresh.elapsed() >= Duration::from_millis(100);
-                if should_refresh {
-                    let image = DynamicImage::ImageRgb8(frame);
-                    preview.camera_state = Some(preview.picker.new_resize_protocol(image));
-                    preview.last_camera_seq = seq;
-                    preview.last_camera_refresh = Instant::now();
-                    preview.last_error = None;
+        if has_camera && preview.last_camera_refresh.elapsed() >= Duration::from_millis(100) {
+            let since = if preview.overlay != overlay
+                || preview.countdown != countdown
+                || preview.assist != assist
+            {
+                0
+            } else {
+                preview.last_camera_seq
+            };
+            let (font_w, font_h) = preview.picker.font_size();
+            let bound = preview
+                .camera_cells
+                .map(|(cols, rows)| {
+                    (
+                        u32::from(cols) * u32::from(font_w),
+                        u32::from(rows) * u32::from(font_h),
+                    )
+                })
+                .unwrap_or((1280, 720));
+            let latest = self.latest_frame.map_latest_since(since, |frame| {
+                let (mut small, scale) = fit_within(&assist.crop(frame), bound);
+                if assist.peaking {
+                    draw_peaking(&mut small);
+                }
+                if let Some(overlay) = overlay.filter(|_| !assist.zoomed()) {
+                    draw_overlay(&mut small, &overlay.scaled(scale));
+                }
+                if let Some(secs) = countdown {
+                    draw_countdown(&mut small, secs);
                 }
+                small
+            });
+            if let Some((seq, frame)) = latest {
+                let image = DynamicImage::ImageRgb8(frame);
+                preview.camera_state = Some(preview.picker.new_resize_protocol(image));
+                preview.overlay = overlay;
+                preview.countdown = countdown;
+                preview.assist = assist;
+                preview.last_camera_seq = seq;
+                preview.last_camera_refresh = Instant::now();
+                preview.last_error = None;
             }
         }
 
-        if preview.image_path != self.preview_image_path {
-            preview.image_path = self.preview_image_path.clone();
+        if preview.image_path != self.preview_display_path {
+            preview.image_path = self.preview_display_path.clone();
             preview.image_state = None;
             preview.last_error = None;
             if let Some(path) = &preview.image_path {
@@ -506,6 +1028,54 @@ impl AppState {
         }
     }
 
+    /// Loads thumbnails for the `visible` products, replacing those whose
+    /// thumbnail file changed (path or modification time). Files that fail to
+    /// decode are remembered and not retried until they change.
+    pub fn update_product_thumbnails(&mut self, visible: &[storage::ProductSummary]) {
+        for product in visible {
+            let Some(path) = &product.thumbnail_path else {
+                continue;
+            };
+            let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+            if self
+                .product_thumbnails
+                .get(&product.product_id)
+                .is_some_and(|thumb| &thumb.path == path && thumb.modified == modified)
+            {
+                continue;
+            }
+            if self
+                .product_thumbnail_failures
+                .get(&product.product_id)
+                .is_some_and(|(failed, failed_modified)| {
+                    failed == path && *failed_modified == modified
+                })
+            {
+                continue;
+            }
+            let Ok(image) = image::open(path) else {
+                self.product_thumbnail_failures
+                    .insert(product.product_id.clone(), (path.clone(), modified));
+                continue;
+            };
+            self.product_thumbnail_failures.remove(&product.product_id);
+            let protocol = self
+                .terminal_preview
+                .as_mut()
+                .map(|preview| preview.picker.new_resize_protocol(image.clone()));
+            self.product_thumbnails.insert(
+                product.product_id.clone(),
+                ProductThumbnail {
+                    path: path.clone(),
+                    modified,
+                    image,
+                    protocol,
+                    halfblocks: None,
+                },
+            );
+        }
+    }
+
     fn handle_delete_confirmation(
         &mut self,
         key: KeyEvent,
@@ -520,17 +1090,31 @@ impl AppState {
         }
         match key.code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
-                let product_id = confirm.product_id.clone();
+                let mut product_ids = confirm.product_ids.clone();
                 self.delete_confirm = None;
-                let _ = command_tx.send(AppCommand::Storage(StorageCommand::DeleteProduct {
-                    product_id,
-                }));
-                self.toast("Deleting product...".to_string(), Severity::Warning);
+                if product_ids.len() == 1 {
+                    let _ = command_tx.send(AppCommand::Storage(StorageCommand::DeleteProduct {
+                        product_id: product_ids.remove(0),
+                    }));
+                    self.toast(t!("tui.toast.deleting_product"), Severity::Warning);
+                } else {
+                    let count = product_ids.len();
+                    let _ = command_tx.send(AppCommand::Storage(StorageCommand::BulkProducts {
+                        action: BulkAction::Delete,
+                        product_ids,
+                        llm_ingest: None,
+                        prompt_rules: None,
+                    }));
+                    self.toast(
+                        t!("tui.toast.deleting_products", count = count),
+                        Severity::Warning,
+                    );
+                }
                 true
             }
             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                 self.delete_confirm = None;
-                self.toast("Delete canceled.".to_string(), Severity::Info);
+                self.toast(t!("tui.toast.delete_canceled"), Severity::Info);
                 true
             }
             _ => {
@@ -546,6 +1130,41 @@ impl AppState {
             let _ = command_tx.send(AppCommand::Shutdown);
             return;
         }
+        if self.grading.open {
+            self.handle_grading_key(key, command_tx);
+            return;
+        }
+        if self.measure.open {
+            self.handle_measure_key(key, command_tx);
+            return;
+        }
+        if self.barcode_prompt.is_some() {
+            self.handle_barcode_prompt_key(key, command_tx);
+            return;
+        }
+        if self.price_prompt.is_some() {
+            self.handle_price_prompt_key(key, command_tx);
+            return;
+        }
+        if self.sync_conflict.is_some() {
+            self.handle_sync_conflict_key(key, command_tx);
+            return;
+        }
+        if self.failed_jobs.open {
+            self.handle_failed_jobs_key(key, command_tx);
+            return;
+        }
+        if self.media_view.open {
+            self.handle_media_view_key(key, command_tx);
+            return;
+        }
+        if self.product_search_editing
+            && self.active_tab == AppTab::Products
+            && self.products_mode == ProductsMode::Grid
+        {
+            self.handle_product_search_keys(key);
+            return;
+        }
         if self.text_editing
             && self.active_tab == AppTab::Products
             && self.products_mode == ProductsMode::Workspace
@@ -603,14 +1222,13 @@ impl AppState {
             return;
         }
 
-        if key.code == KeyCode::Char('q') {
-            self.should_quit = true;
-            let _ = command_tx.send(AppCommand::Shutdown);
+        if self.keys.matches(Action::DismissToast, &key) && self.dismiss_toast() {
             return;
         }
 
-        if key.code == KeyCode::Char('?') {
-            self.help_open = !self.help_open;
+        if self.keys.matches(Action::Quit, &key) {
+            self.should_quit = true;
+            let _ = command_tx.send(AppCommand::Shutdown);
             return;
         }
 
@@ -619,7 +1237,7 @@ impl AppState {
         }
 
         if self.help_open {
-            if key.code == KeyCode::Esc || key.code == KeyCode::Char('?') {
+            if key.code == KeyCode::Esc || self.keys.matches(Action::Help, &key) {
                 self.help_open = false;
             }
             return;
@@ -635,20 +1253,72 @@ impl AppState {
             return;
         }
 
+        if self.trash_view.open {
+            self.handle_trash_view_key(key, command_tx);
+            return;
+        }
+
+        if self.resume_prompt.open {
+            self.handle_resume_prompt_key(key, command_tx);
+            return;
+        }
+
+        // Global actions; modals above get the key first so nothing fires
+        // behind them.
+        if self.keys.matches(Action::Help, &key) {
+            self.help_open = !self.help_open;
+            return;
+        }
+
+        if self.keys.matches(Action::Metrics, &key) {
+            self.metrics_open = !self.metrics_open;
+            return;
+        }
+
+        if self.keys.matches(Action::CopyRequestId, &key) {
+            match self.last_request_id.clone() {
+                Some(request_id) => self.copy_to_clipboard(&t!("tui.copy.request_id"), &request_id),
+                None => self.toast(t!("tui.toast.no_failed_request"), Severity::Info),
+            }
+            return;
+        }
+
+        if self.keys.matches(Action::OpenDashboard, &key) {
+            let url = self
+                .config
+                .dashboard_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_DASHBOARD_URL.to_string());
+            self.open_in_browser(&url);
+            return;
+        }
+
+        if self.keys.matches(Action::CaptureHotkey, &key) {
+            self.trigger_capture(false, command_tx);
+            return;
+        }
+
         // Tab-local actions first.
         match self.active_tab {
+            AppTab::Home if self.keys.matches(Action::FailedJobs, &key) => {
+                self.failed_jobs.open = true;
+                let _ = command_tx.send(AppCommand::Storage(StorageCommand::ListFailedJobs));
+            }
             AppTab::Products => self.handle_products_keys(key, command_tx),
             AppTab::Activity => {
                 if key.code == KeyCode::Char('f') {
-                    self.toast("Filter TODO".to_string(), Severity::Info);
+                    self.toast(t!("tui.toast.filter_todo"), Severity::Info);
                 }
             }
+            AppTab::Analytics if self.keys.matches(Action::RefreshStats, &key) => {
+                let _ = command_tx.send(AppCommand::Storage(StorageCommand::LoadStats));
+            }
             AppTab::Settings => self.handle_settings_keys(key),
             _ => {}
         }
 
         let prev_tab = self.active_tab;
-        if key.code == KeyCode::BackTab {
+        if self.keys.matches(Action::NextTab, &key) {
             self.next_tab();
         }
         if self.active_tab != prev_tab && self.active_tab == AppTab::Products {
@@ -657,11 +1327,27 @@ impl AppState {
             } else {
                 ProductsMode::Grid
             };
-            self.products_loading = true;
-            let _ = command_tx.send(AppCommand::Storage(StorageCommand::ListProducts));
+            self.refresh_products(command_tx, false);
+        }
+        if self.active_tab != prev_tab && self.active_tab == AppTab::Analytics {
+            let _ = command_tx.send(AppCommand::Storage(StorageCommand::LoadStats));
         }
     }
 
+    /// Re-lists products unless a listing is under way or, when not
+    /// `force`d, the list was loaded less than [`PRODUCTS_REFRESH_DEBOUNCE`]
+    /// ago.
+    fn refresh_products(&mut self, command_tx: &Sender<AppCommand>, force: bool) {
+        let recent = self
+            .products_listed_at
+            .is_some_and(|at| at.elapsed() < PRODUCTS_REFRESH_DEBOUNCE);
+        if self.products_loading || (recent && !force) {
+            return;
+        }
+        self.products_loading = true;
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::ListProducts));
+    }
+
     fn save_context_text(&mut self, command_tx: &Sender<AppCommand>) {
         let Some(product) = &self.active_product else {
             return;
@@ -676,10 +1362,7 @@ impl AppState {
 
     fn handle_ctrl_save(&mut self, command_tx: &Sender<AppCommand>) {
         if self.active_tab != AppTab::Products {
-            self.toast(
-                "Save is available from Products.".to_string(),
-                Severity::Warning,
-            );
+            self.toast(t!("tui.toast.save_from_products"), Severity::Warning);
             return;
         }
 
@@ -705,6 +1388,17 @@ impl AppState {
 
         if let Some(session) = &self.active_session {
             if session.committed_at.is_none() && !session.frames.is_empty() {
+                let missing = session.missing_shots();
+                if !missing.is_empty()
+                    && self.shot_override.as_deref() != Some(session.session_id.as_str())
+                {
+                    self.shot_override = Some(session.session_id.clone());
+                    self.toast(
+                        t!("tui.toast.missing_shots", shots = missing.join(", ")),
+                        Severity::Warning,
+                    );
+                    return;
+                }
                 let _ = command_tx.send(AppCommand::Storage(StorageCommand::CommitSession {
                     session_id: session.session_id.clone(),
                 }));
@@ -712,7 +1406,7 @@ impl AppState {
         }
 
         let Some(product) = &self.active_product else {
-            self.toast("No active product selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
             return;
         };
         if self.config.online_ready && product.images.iter().any(|img| img.uploaded_url.is_none()) {
@@ -727,7 +1421,7 @@ impl AppState {
             product_id: product.product_id.clone(),
         }));
         self.product_syncing = true;
-        self.toast("Saved + syncing...".to_string(), Severity::Info);
+        self.toast(t!("tui.toast.saved_syncing"), Severity::Info);
     }
 
     fn save_structure_text(&mut self, command_tx: &Sender<AppCommand>) -> bool {
@@ -737,7 +1431,7 @@ impl AppState {
         let parsed = match serde_json::from_str::<serde_json::Value>(&self.structure_text) {
             Ok(value) => value,
             Err(err) => {
-                self.toast(format!("Invalid JSON: {err}"), Severity::Error);
+                self.toast(t!("tui.toast.invalid_json", error = err), Severity::Error);
                 return false;
             }
         };
@@ -756,14 +1450,17 @@ impl AppState {
             return false;
         };
         let Some(key) = self.selected_listing_key() else {
-            self.toast("No listing selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_listing_selected"), Severity::Warning);
             return false;
         };
         let parsed =
             match serde_json::from_str::<storage::MarketplaceListing>(&self.listings_edit_buffer) {
                 Ok(value) => value,
                 Err(err) => {
-                    self.toast(format!("Invalid listing JSON: {err}"), Severity::Error);
+                    self.toast(
+                        t!("tui.toast.invalid_listing_json", error = err),
+                        Severity::Error,
+                    );
                     return false;
                 }
             };
@@ -795,10 +1492,7 @@ impl AppState {
             self.structure_text = "{\n}".to_string();
         }
         self.structure_editing = true;
-        self.toast(
-            "Editing structure (Esc to save).".to_string(),
-            Severity::Info,
-        );
+        self.toast(t!("tui.toast.editing_structure"), Severity::Info);
     }
 
     fn start_structure_field_editing(&mut self) {
@@ -806,15 +1500,12 @@ impl AppState {
             return;
         }
         let Some(product) = &self.active_product else {
-            self.toast("No active product selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
             return;
         };
         let entries = self.structure_entries();
         if entries.is_empty() {
-            self.toast(
-                "No structure fields available.".to_string(),
-                Severity::Warning,
-            );
+            self.toast(t!("tui.toast.no_structure_fields"), Severity::Warning);
             return;
         }
         if self.structure_field_selected >= entries.len() {
@@ -830,7 +1521,7 @@ impl AppState {
         self.structure_field_edit_kind = edit_kind_for_value(&entry.value);
         self.structure_field_edit_buffer = edit_buffer_for_value(&entry.value);
         self.toast(
-            format!("Editing {} (Esc to save).", entry.path),
+            t!("tui.toast.editing_field", field = entry.path),
             Severity::Info,
         );
     }
@@ -860,7 +1551,7 @@ impl AppState {
         }
         self.listings_edit_buffer = serde_json::to_string_pretty(&payload).unwrap_or_default();
         self.listings_editing = true;
-        self.toast("Editing listing (Esc to save).".to_string(), Severity::Info);
+        self.toast(t!("tui.toast.editing_listing"), Severity::Info);
     }
 
     fn start_listings_field_editing(&mut self) {
@@ -869,10 +1560,7 @@ impl AppState {
         }
         let entries = self.listing_field_entries();
         if entries.is_empty() {
-            self.toast(
-                "No listing fields available.".to_string(),
-                Severity::Warning,
-            );
+            self.toast(t!("tui.toast.no_listing_fields"), Severity::Warning);
             return;
         }
         if self.listings_field_selected >= entries.len() {
@@ -880,11 +1568,11 @@ impl AppState {
         }
         let entry = &entries[self.listings_field_selected];
         if entry.key == ListingFieldKey::Aspects {
-            self.toast("Select an aspect to edit.".to_string(), Severity::Info);
+            self.toast(t!("tui.toast.select_aspect"), Severity::Info);
             return;
         }
         if entry.key == ListingFieldKey::PackageDimensions {
-            self.toast("Select a dimension to edit.".to_string(), Severity::Info);
+            self.toast(t!("tui.toast.select_dimension"), Severity::Info);
             return;
         }
         self.listings_editing = false;
@@ -894,6 +1582,9 @@ impl AppState {
         self.listings_field_edit_image_index = entry.image_index;
         self.listings_field_edit_dimension = entry.dimension_key;
         self.listings_field_edit_kind = entry.kind;
+        self.listings_aspect_completion = None;
+        self.listings_category_choice = None;
+        self.listings_condition_choice = None;
         self.listings_field_edit_buffer = if entry.key == ListingFieldKey::AspectValue {
             format_aspect_values_edit_buffer(&entry.value)
         } else if entry.kind == ListingEditKind::Lines {
@@ -902,7 +1593,7 @@ impl AppState {
             listing_edit_buffer_for_value(&entry.value)
         };
         self.toast(
-            format!("Editing {} (Esc to save).", entry.label.as_str()),
+            t!("tui.toast.editing_field", field = entry.label.as_str()),
             Severity::Info,
         );
     }
@@ -930,26 +1621,42 @@ impl AppState {
         let Some(product) = &self.active_product else {
             return (None, None);
         };
-        let Some(key) = self.selected_listing_key() else {
-            return (None, None);
-        };
-        let Some(listing) = product.listings.get(&key) else {
-            return (None, None);
-        };
-        (listing.condition.clone(), listing.condition_id)
+        let listing = self
+            .selected_listing_key()
+            .and_then(|key| product.listings.get(&key));
+        match (&product.grading, listing) {
+            (Some(grading), Some(listing)) => {
+                let (condition, condition_id) = grading
+                    .listing_condition(&listing.allowed_conditions, &listing.allowed_condition_ids);
+                (
+                    condition.or_else(|| listing.condition.clone()),
+                    condition_id.or(listing.condition_id),
+                )
+            }
+            (Some(grading), None) => (Some(grading.condition.clone()), Some(grading.condition_id)),
+            (None, Some(listing)) => (listing.condition.clone(), listing.condition_id),
+            (None, None) => (None, None),
+        }
     }
 
     fn generate_listing(&mut self, dry_run: bool, publish: bool) {
+        if !self.permitted(if publish {
+            "publishing listings"
+        } else {
+            "generating listings"
+        }) {
+            return;
+        }
         if let Some(cmd) = self.build_listing_command(dry_run, publish) {
             self.pending_commands.push(AppCommand::Storage(cmd));
             self.listing_inference = true;
-            self.toast("Listing request queued.".to_string(), Severity::Info);
+            self.toast(t!("tui.toast.listing_queued"), Severity::Info);
         }
     }
 
     fn build_listing_command(&mut self, dry_run: bool, publish: bool) -> Option<StorageCommand> {
         let Some(product) = &self.active_product else {
-            self.toast("No active product selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
             return None;
         };
         let marketplace =
@@ -972,7 +1679,10 @@ impl AppState {
                     });
                 }
                 self.toast(
-                    format!("Draft missing fields: {}", missing.join(", ")),
+                    t!(
+                        "tui.toast.draft_missing_fields",
+                        fields = missing.join(", ")
+                    ),
                     Severity::Warning,
                 );
                 return None;
@@ -989,6 +1699,7 @@ impl AppState {
             condition_id,
             dry_run,
             publish,
+            quality: self.config.quality.clone(),
         })
     }
 
@@ -998,8 +1709,11 @@ impl AppState {
         dry_run: bool,
         publish: bool,
     ) {
+        if !self.permitted("the listing pipeline") {
+            return;
+        }
         let Some(product) = &self.active_product else {
-            self.toast("No active product selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
             return;
         };
         self.pending_context_pipeline = Some(ContextPipelineRequest { dry_run, publish });
@@ -1010,18 +1724,19 @@ impl AppState {
                 llm_ingest: self.llm_ingest.clone(),
                 context_text: non_empty(self.context_text.clone()),
                 prompt_rules: self.prompt_rules.clone(),
+                quality: self.config.quality.clone(),
             },
         ));
         self.structure_inference = true;
-        self.toast(
-            "Generating structure (pipeline queued)...".to_string(),
-            Severity::Info,
-        );
+        self.toast(t!("tui.toast.generating_structure_queued"), Severity::Info);
     }
 
     fn start_structure_inference(&mut self, command_tx: &Sender<AppCommand>) {
+        if !self.permitted("generating structure") {
+            return;
+        }
         let Some(product) = &self.active_product else {
-            self.toast("No active product selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
             return;
         };
         self.pending_context_pipeline = None;
@@ -1032,17 +1747,21 @@ impl AppState {
                 llm_ingest: self.llm_ingest.clone(),
                 context_text: non_empty(self.context_text.clone()),
                 prompt_rules: self.prompt_rules.clone(),
+                quality: self.config.quality.clone(),
             },
         ));
         self.structure_inference = true;
-        self.toast("Generating structure...".to_string(), Severity::Info);
+        self.toast(t!("tui.toast.generating_structure"), Severity::Info);
     }
 
     fn persist_config_settings(&mut self) -> bool {
         let mut cfg = match talaria_core::config::Config::load() {
             Ok(cfg) => cfg,
             Err(err) => {
-                self.toast(format!("Config load failed: {err}"), Severity::Error);
+                self.toast(
+                    t!("tui.toast.config_load_failed", error = err),
+                    Severity::Error,
+                );
                 return false;
             }
         };
@@ -1051,9 +1770,13 @@ impl AppState {
         cfg.llm_aspects = self.llm_aspects.clone();
         cfg.prompt_rules = self.prompt_rules.clone();
         if let Err(err) = cfg.save() {
-            self.toast(format!("Config save failed: {err}"), Severity::Error);
+            self.toast(
+                t!("tui.toast.config_save_failed", error = err),
+                Severity::Error,
+            );
             return false;
         }
+        self.config_mtime = config_file_mtime();
         true
     }
 
@@ -1071,16 +1794,13 @@ impl AppState {
                         web_search,
                     });
                 } else {
-                    self.toast(
-                        "Model must be gpt-5.2, gpt-5-mini, or gpt-5-nano.".to_string(),
-                        Severity::Error,
-                    );
+                    self.toast(t!("tui.toast.invalid_model"), Severity::Error);
                     return false;
                 }
             }
             SettingsField::LlmIngestReasoning | SettingsField::LlmIngestWebSearch => {
                 if self.llm_ingest.is_none() {
-                    self.toast("Set LLM ingest model first.".to_string(), Severity::Warning);
+                    self.toast(t!("tui.toast.set_ingest_model"), Severity::Warning);
                     return false;
                 }
                 let parsed = match parse_optional_bool(value) {
@@ -1110,19 +1830,13 @@ impl AppState {
                         web_search,
                     });
                 } else {
-                    self.toast(
-                        "Model must be gpt-5.2, gpt-5-mini, or gpt-5-nano.".to_string(),
-                        Severity::Error,
-                    );
+                    self.toast(t!("tui.toast.invalid_model"), Severity::Error);
                     return false;
                 }
             }
             SettingsField::LlmAspectsReasoning | SettingsField::LlmAspectsWebSearch => {
                 if self.llm_aspects.is_none() {
-                    self.toast(
-                        "Set LLM aspects model first.".to_string(),
-                        Severity::Warning,
-                    );
+                    self.toast(t!("tui.toast.set_aspects_model"), Severity::Warning);
                     return false;
                 }
                 let parsed = match parse_optional_bool(value) {
@@ -1171,7 +1885,7 @@ impl AppState {
             ),
             SettingsField::LlmIngestReasoning => {
                 if self.llm_ingest.is_none() {
-                    self.toast("Set LLM ingest model first.".to_string(), Severity::Warning);
+                    self.toast(t!("tui.toast.set_ingest_model"), Severity::Warning);
                     return;
                 }
                 (
@@ -1184,7 +1898,7 @@ impl AppState {
             }
             SettingsField::LlmIngestWebSearch => {
                 if self.llm_ingest.is_none() {
-                    self.toast("Set LLM ingest model first.".to_string(), Severity::Warning);
+                    self.toast(t!("tui.toast.set_ingest_model"), Severity::Warning);
                     return;
                 }
                 (
@@ -1197,10 +1911,7 @@ impl AppState {
             }
             SettingsField::LlmAspectsReasoning => {
                 if self.llm_aspects.is_none() {
-                    self.toast(
-                        "Set LLM aspects model first.".to_string(),
-                        Severity::Warning,
-                    );
+                    self.toast(t!("tui.toast.set_aspects_model"), Severity::Warning);
                     return;
                 }
                 (
@@ -1213,10 +1924,7 @@ impl AppState {
             }
             SettingsField::LlmAspectsWebSearch => {
                 if self.llm_aspects.is_none() {
-                    self.toast(
-                        "Set LLM aspects model first.".to_string(),
-                        Severity::Warning,
-                    );
+                    self.toast(t!("tui.toast.set_aspects_model"), Severity::Warning);
                     return;
                 }
                 (
@@ -1227,6 +1935,30 @@ impl AppState {
                         .map(|value| value.to_string()),
                 )
             }
+            SettingsField::SupabaseSignedUrls => {
+                let Some(supabase) = self.config.supabase.as_ref() else {
+                    self.toast(t!("tui.toast.set_supabase_url"), Severity::Warning);
+                    return;
+                };
+                (
+                    vec!["on".to_string(), "off".to_string()],
+                    Some(
+                        if supabase.use_signed_urls {
+                            "on"
+                        } else {
+                            "off"
+                        }
+                        .to_string(),
+                    ),
+                )
+            }
+            SettingsField::PreviewBackend => (
+                PreviewBackend::ALL
+                    .iter()
+                    .map(|b| b.as_str().to_string())
+                    .collect(),
+                Some(self.config.preview_backend.as_str().to_string()),
+            ),
             _ => {
                 return;
             }
@@ -1270,8 +2002,8 @@ impl AppState {
                     .cloned()
                 {
                     let field = self.settings_picker.field;
-                    if self.apply_llm_setting(field, &value) && self.persist_config_settings() {
-                        self.toast("Settings saved.".to_string(), Severity::Success);
+                    if self.save_setting(field, value) {
+                        self.toast(t!("tui.toast.settings_saved"), Severity::Success);
                         self.settings_picker.open = false;
                     }
                 }
@@ -1286,16 +2018,132 @@ impl AppState {
         if self.settings_selected >= fields.len() {
             self.settings_selected = fields.len().saturating_sub(1);
         }
-        let field = fields[self.settings_selected];
+        self.save_setting(fields[self.settings_selected], value)
+    }
+
+    /// Loads the config file, applies `edit` and writes it back. Validation
+    /// errors from `edit` are shown as toasts and nothing is saved.
+    fn update_config(
+        &mut self,
+        edit: impl FnOnce(&mut talaria_core::config::Config) -> Result<(), String>,
+    ) -> Option<talaria_core::config::Config> {
+        let mut cfg = match talaria_core::config::Config::load() {
+            Ok(cfg) => cfg,
+            Err(err) => {
+                self.toast(
+                    t!("tui.toast.config_load_failed", error = err),
+                    Severity::Error,
+                );
+                return None;
+            }
+        };
+        if let Err(err) = edit(&mut cfg) {
+            self.toast(err, Severity::Error);
+            return None;
+        }
+        if let Err(err) = cfg.save() {
+            self.toast(
+                t!("tui.toast.config_save_failed", error = err),
+                Severity::Error,
+            );
+            return None;
+        }
+        self.config_mtime = config_file_mtime();
+        Some(cfg)
+    }
+
+    /// Asks the router to rebuild the Hermes client from the saved config and
+    /// hand it to every worker.
+    fn reload_clients(&mut self) {
+        self.credits = None;
+        self.credits_error = None;
+        self.credits_loading = false;
+        self.credits_next_refresh = Instant::now();
+        self.config_mtime = config_file_mtime();
+        self.pending_commands.push(AppCommand::ReloadConfig);
+    }
+
+    fn save_supabase_setting(&mut self, field: SettingsField, value: String) -> bool {
+        use talaria_core::config::{DEFAULT_SUPABASE_BUCKET, DEFAULT_SUPABASE_UPLOAD_PREFIX};
+
+        if field == SettingsField::SupabaseServiceRoleKey && value.is_empty() {
+            self.toast(t!("tui.toast.supabase_key_unchanged"), Severity::Info);
+            return true;
+        }
+        let saved = self.update_config(|cfg| {
+            if field == SettingsField::SupabaseUrl {
+                if value.eq_ignore_ascii_case("clear") {
+                    cfg.supabase = None;
+                    return Ok(());
+                }
+                let url = validate::http_url("Supabase URL", &value)?;
+                match cfg.supabase.as_mut() {
+                    Some(supabase) => supabase.url = url,
+                    None => {
+                        cfg.supabase = Some(SupabaseConfig {
+                            url,
+                            service_role_key: None,
+                            bucket: DEFAULT_SUPABASE_BUCKET.to_string(),
+                            public_base: None,
+                            upload_prefix: DEFAULT_SUPABASE_UPLOAD_PREFIX.to_string(),
+                            use_signed_urls: false,
+                            network: cfg.network.clone(),
+                        })
+                    }
+                }
+                return Ok(());
+            }
+            let supabase = cfg
+                .supabase
+                .as_mut()
+                .ok_or_else(|| "Set Supabase URL first.".to_string())?;
+            match field {
+                SettingsField::SupabaseServiceRoleKey => {
+                    supabase.service_role_key =
+                        (!value.eq_ignore_ascii_case("clear")).then(|| value.clone());
+                }
+                SettingsField::SupabaseBucket => {
+                    supabase.bucket = if value.is_empty() {
+                        DEFAULT_SUPABASE_BUCKET.to_string()
+                    } else {
+                        validate::identifier("Bucket", &value)?
+                    };
+                }
+                SettingsField::SupabaseUploadPrefix => {
+                    supabase.upload_prefix = if value.is_empty() {
+                        DEFAULT_SUPABASE_UPLOAD_PREFIX.to_string()
+                    } else {
+                        validate::upload_prefix(&value)?
+                    };
+                }
+                SettingsField::SupabaseSignedUrls => {
+                    supabase.use_signed_urls = value.eq_ignore_ascii_case("on");
+                }
+                _ => {}
+            }
+            Ok(())
+        });
+        let Some(cfg) = saved else {
+            return false;
+        };
+        self.config.supabase = cfg.supabase;
+        self.reload_clients();
+        true
+    }
+
+    fn save_setting(&mut self, field: SettingsField, value: String) -> bool {
         match field {
             SettingsField::HermesApiKey => {
                 if value.is_empty() {
-                    self.toast("Hermes API key unchanged.".to_string(), Severity::Info);
+                    self.toast(t!("tui.toast.key_unchanged"), Severity::Info);
                 } else {
                     let mut cfg = match talaria_core::config::Config::load() {
                         Ok(cfg) => cfg,
                         Err(err) => {
-                            self.toast(format!("Config load failed: {err}"), Severity::Error);
+                            self.toast(
+                                t!("tui.toast.config_load_failed", error = err),
+                                Severity::Error,
+                            );
                             return false;
                         }
                     };
@@ -1306,30 +2154,96 @@ impl AppState {
                         cfg.api_key = non_empty(value);
                         self.config.hermes_api_key_present = cfg.api_key.is_some();
                     }
-                    self.credits = None;
-                    self.credits_error = None;
-                    self.credits_loading = false;
-                    self.credits_next_refresh = Instant::now();
                     if let Err(err) = cfg.save() {
-                        self.toast(format!("Config save failed: {err}"), Severity::Error);
+                        self.toast(
+                            t!("tui.toast.config_save_failed", error = err),
+                            Severity::Error,
+                        );
                         return false;
                     }
-                    self.toast(
-                        "Hermes API key saved (restart to apply).".to_string(),
-                        Severity::Info,
-                    );
+                    self.reload_clients();
+                }
+                return true;
+            }
+            SettingsField::BaseUrl => {
+                if value.is_empty() {
+                    self.toast(t!("tui.toast.base_url_unchanged"), Severity::Info);
+                    return true;
+                }
+                let saved = self.update_config(|cfg| {
+                    cfg.base_url = if value.eq_ignore_ascii_case("default") {
+                        talaria_core::config::DEFAULT_BASE_URL.to_string()
+                    } else {
+                        validate::http_url("Base URL", &value)?
+                    };
+                    Ok(())
+                });
+                let Some(cfg) = saved else {
+                    return false;
+                };
+                self.config.base_url = Some(cfg.base_url);
+                self.reload_clients();
+                return true;
+            }
+            SettingsField::SupabaseUrl
+            | SettingsField::SupabaseServiceRoleKey
+            | SettingsField::SupabaseBucket
+            | SettingsField::SupabaseUploadPrefix
+            | SettingsField::SupabaseSignedUrls => {
+                return self.save_supabase_setting(field, value);
+            }
+            SettingsField::CapturesDir => {
+                if value.is_empty() {
+                    self.toast(t!("tui.toast.capture_dir_unchanged"), Severity::Info);
+                    return true;
                 }
+                let saved = self.update_config(|cfg| {
+                    if value.eq_ignore_ascii_case("default") || value.eq_ignore_ascii_case("clear")
+                    {
+                        cfg.tui_captures_dir = None;
+                        return Ok(());
+                    }
+                    let dir = PathBuf::from(&value);
+                    if !dir.is_absolute() {
+                        return Err("Capture directory must be an absolute path.".to_string());
+                    }
+                    if dir.is_file() {
+                        return Err(format!("{} is a file, not a directory.", dir.display()));
+                    }
+                    cfg.tui_captures_dir = Some(dir);
+                    Ok(())
+                });
+                let Some(cfg) = saved else {
+                    return false;
+                };
+                self.config.captures_dir = cfg.tui_captures_dir;
+                self.toast(t!("tui.toast.capture_dir_saved"), Severity::Info);
+                return true;
+            }
+            SettingsField::PreviewBackend => {
+                let saved = self.update_config(|cfg| {
+                    cfg.tui_preview_backend = value.parse().map_err(|err| format!("{err}"))?;
+                    Ok(())
+                });
+                let Some(cfg) = saved else {
+                    return false;
+                };
+                self.config.preview_backend = cfg.tui_preview_backend;
+                self.toast(t!("tui.toast.preview_backend_saved"), Severity::Info);
                 return true;
             }
             SettingsField::PreviewHeightPct => {
                 if value.is_empty() {
-                    self.toast("Preview height unchanged.".to_string(), Severity::Info);
+                    self.toast(t!("tui.toast.preview_height_unchanged"), Severity::Info);
                     return true;
                 }
                 let mut cfg = match talaria_core::config::Config::load() {
                     Ok(cfg) => cfg,
                     Err(err) => {
-                        self.toast(format!("Config load failed: {err}"), Severity::Error);
+                        self.toast(
+                            t!("tui.toast.config_load_failed", error = err),
+                            Severity::Error,
+                        );
                         return false;
                     }
                 };
@@ -1341,17 +2255,16 @@ impl AppState {
                     let parsed = match value.parse::<u8>() {
                         Ok(parsed) => parsed,
                         Err(_) => {
-                            self.toast(
-                                "Preview height must be a number (20-80).".to_string(),
-                                Severity::Error,
-                            );
+                            self.toast(t!("tui.toast.preview_height_not_number"), Severity::Error);
                             return false;
                         }
                     };
                     if !(PREVIEW_HEIGHT_MIN_PCT..=PREVIEW_HEIGHT_MAX_PCT).contains(&parsed) {
                         self.toast(
-                            format!(
-                                "Preview height must be between {PREVIEW_HEIGHT_MIN_PCT} and {PREVIEW_HEIGHT_MAX_PCT}."
+                            t!(
+                                "tui.toast.preview_height_range",
+                                min = PREVIEW_HEIGHT_MIN_PCT,
+                                max = PREVIEW_HEIGHT_MAX_PCT
                             ),
                             Severity::Error,
                         );
@@ -1362,26 +2275,51 @@ impl AppState {
                     self.config.preview_height_pct = parsed;
                 }
                 if let Err(err) = cfg.save() {
-                    self.toast(format!("Config save failed: {err}"), Severity::Error);
+                    self.toast(
+                        t!("tui.toast.config_save_failed", error = err),
+                        Severity::Error,
+                    );
                     return false;
                 }
-                self.toast("Preview height saved.".to_string(), Severity::Info);
+                self.config_mtime = config_file_mtime();
+                self.toast(t!("tui.toast.preview_height_saved"), Severity::Info);
                 return true;
             }
-            SettingsField::Marketplace => {
-                self.ebay_settings.marketplace = non_empty(value);
-            }
-            SettingsField::MerchantLocation => {
-                self.ebay_settings.merchant_location_key = non_empty(value);
-            }
-            SettingsField::FulfillmentPolicy => {
-                self.ebay_settings.fulfillment_policy_id = non_empty(value);
-            }
-            SettingsField::PaymentPolicy => {
-                self.ebay_settings.payment_policy_id = non_empty(value);
-            }
-            SettingsField::ReturnPolicy => {
-                self.ebay_settings.return_policy_id = non_empty(value);
+            SettingsField::Marketplace
+            | SettingsField::MerchantLocation
+            | SettingsField::FulfillmentPolicy
+            | SettingsField::PaymentPolicy
+            | SettingsField::ReturnPolicy => {
+                let checked = non_empty(value)
+                    .map(|value| match field {
+                        SettingsField::Marketplace => validate::marketplace(&value),
+                        SettingsField::MerchantLocation => {
+                            validate::identifier("Merchant location key", &value)
+                        }
+                        SettingsField::FulfillmentPolicy => {
+                            validate::policy_id("Fulfillment policy", &value)
+                        }
+                        SettingsField::PaymentPolicy => {
+                            validate::policy_id("Payment policy", &value)
+                        }
+                        _ => validate::policy_id("Return policy", &value),
+                    })
+                    .transpose();
+                let value = match checked {
+                    Ok(value) => value,
+                    Err(err) => {
+                        self.toast(err, Severity::Error);
+                        return false;
+                    }
+                };
+                let ebay = &mut self.ebay_settings;
+                match field {
+                    SettingsField::Marketplace => ebay.marketplace = value,
+                    SettingsField::MerchantLocation => ebay.merchant_location_key = value,
+                    SettingsField::FulfillmentPolicy => ebay.fulfillment_policy_id = value,
+                    SettingsField::PaymentPolicy => ebay.payment_policy_id = value,
+                    _ => ebay.return_policy_id = value,
+                }
             }
             SettingsField::HsufPromptRules => {
                 if value.eq_ignore_ascii_case("clear") {
@@ -1411,14 +2349,14 @@ impl AppState {
         if is_save_edit_key(key) {
             self.text_editing = false;
             self.save_context_text(command_tx);
-            self.toast("Text saved.".to_string(), Severity::Success);
+            self.toast(t!("tui.toast.text_saved"), Severity::Success);
             return true;
         }
         match key.code {
             KeyCode::Esc => {
                 self.text_editing = false;
                 self.save_context_text(command_tx);
-                self.toast("Text saved.".to_string(), Severity::Success);
+                self.toast(t!("tui.toast.text_saved"), Severity::Success);
                 true
             }
             KeyCode::Enter => {
@@ -1449,7 +2387,7 @@ impl AppState {
         if is_save_edit_key(key) {
             if self.save_structure_text(command_tx) {
                 self.structure_editing = false;
-                self.toast("Structure saved.".to_string(), Severity::Success);
+                self.toast(t!("tui.toast.structure_saved"), Severity::Success);
             }
             return true;
         }
@@ -1457,7 +2395,7 @@ impl AppState {
             KeyCode::Esc => {
                 if self.save_structure_text(command_tx) {
                     self.structure_editing = false;
-                    self.toast("Structure saved.".to_string(), Severity::Success);
+                    self.toast(t!("tui.toast.structure_saved"), Severity::Success);
                 }
                 true
             }
@@ -1491,7 +2429,7 @@ impl AppState {
                 self.structure_field_editing = false;
                 self.structure_field_edit_buffer.clear();
                 self.structure_field_edit_path = None;
-                self.toast("Structure field saved.".to_string(), Severity::Success);
+                self.toast(t!("tui.toast.structure_field_saved"), Severity::Success);
             }
             return true;
         }
@@ -1501,7 +2439,7 @@ impl AppState {
                     self.structure_field_editing = false;
                     self.structure_field_edit_buffer.clear();
                     self.structure_field_edit_path = None;
-                    self.toast("Structure field saved.".to_string(), Severity::Success);
+                    self.toast(t!("tui.toast.structure_field_saved"), Severity::Success);
                 }
                 true
             }
@@ -1538,7 +2476,7 @@ impl AppState {
                 self.listings_field_edit_name = None;
                 self.listings_field_edit_image_index = None;
                 self.listings_field_edit_dimension = None;
-                self.toast("Listing field saved.".to_string(), Severity::Success);
+                self.toast(t!("tui.toast.listing_field_saved"), Severity::Success);
                 self.queue_image_preview();
             }
             return true;
@@ -1552,20 +2490,49 @@ impl AppState {
                     self.listings_field_edit_name = None;
                     self.listings_field_edit_image_index = None;
                     self.listings_field_edit_dimension = None;
-                    self.toast("Listing field saved.".to_string(), Severity::Success);
+                    self.toast(t!("tui.toast.listing_field_saved"), Severity::Success);
                     self.queue_image_preview();
                 }
                 true
             }
+            KeyCode::Tab if self.listings_field_edit_key == Some(ListingFieldKey::AspectValue) => {
+                self.complete_aspect_value();
+                true
+            }
+            KeyCode::Tab
+                if matches!(
+                    self.listings_field_edit_key,
+                    Some(ListingFieldKey::CategoryId | ListingFieldKey::CategoryLabel)
+                ) =>
+            {
+                self.cycle_known_category();
+                true
+            }
+            KeyCode::Tab
+                if matches!(
+                    self.listings_field_edit_key,
+                    Some(ListingFieldKey::Condition | ListingFieldKey::ConditionId)
+                ) =>
+            {
+                self.cycle_allowed_condition();
+                true
+            }
             KeyCode::Enter => {
+                self.listings_aspect_completion = None;
                 self.listings_field_edit_buffer.push('\n');
                 true
             }
             KeyCode::Backspace => {
+                self.listings_aspect_completion = None;
+                self.listings_category_choice = None;
+                self.listings_condition_choice = None;
                 self.listings_field_edit_buffer.pop();
                 true
             }
             KeyCode::Char(c) => {
+                self.listings_aspect_completion = None;
+                self.listings_category_choice = None;
+                self.listings_condition_choice = None;
                 self.listings_field_edit_buffer.push(c);
                 true
             }
@@ -1574,6 +2541,190 @@ impl AppState {
         }
     }
 
+    /// Replaces the value being typed with the next matching sample from the
+    /// aspect spec; repeated Tab presses cycle through matches.
+    fn complete_aspect_value(&mut self) {
+        let Some(name) = self.listings_field_edit_name.clone() else {
+            return;
+        };
+        let samples = self
+            .aspect_spec(&name)
+            .map(|spec| spec.samples)
+            .unwrap_or_default();
+        if samples.is_empty() {
+            self.toast(t!("tui.toast.no_suggestions", name = name), Severity::Info);
+            return;
+        }
+        let (head, current) = match self.listings_field_edit_buffer.rfind(',') {
+            Some(idx) => (
+                self.listings_field_edit_buffer[..=idx].to_string(),
+                self.listings_field_edit_buffer[idx + 1..]
+                    .trim()
+                    .to_string(),
+            ),
+            None => (
+                String::new(),
+                self.listings_field_edit_buffer.trim().to_string(),
+            ),
+        };
+        let (prefix, index) = match self.listings_aspect_completion.take() {
+            Some((prefix, index)) => (prefix, index + 1),
+            None => (current, 0),
+        };
+        let needle = prefix.to_lowercase();
+        let matches = samples
+            .iter()
+            .filter(|sample| sample.to_lowercase().starts_with(&needle))
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            self.toast(
+                t!("tui.toast.no_matching_suggestions", prefix = prefix),
+                Severity::Info,
+            );
+            return;
+        }
+        let choice = matches[index % matches.len()];
+        self.listings_field_edit_buffer = if head.is_empty() {
+            choice.clone()
+        } else {
+            format!("{head} {choice}")
+        };
+        self.listings_aspect_completion = Some((prefix, index));
+    }
+
+    /// Cycles the category field through categories already used by other
+    /// local listings on this marketplace; saving applies both id and label.
+    fn cycle_known_category(&mut self) {
+        let Some(marketplace) = self.selected_listing_key() else {
+            return;
+        };
+        let categories = workspace::known_categories(&self.captures_dir)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|c| c.marketplace == marketplace)
+            .collect::<Vec<_>>();
+        if categories.is_empty() {
+            self.toast(
+                t!("tui.toast.no_categories", marketplace = marketplace),
+                Severity::Info,
+            );
+            return;
+        }
+        let index = if self.listings_category_choice.is_some() {
+            (self.listings_category_index + 1) % categories.len()
+        } else {
+            0
+        };
+        let KnownCategory { id, label, .. } = categories[index].clone();
+        self.listings_field_edit_buffer =
+            if self.listings_field_edit_key == Some(ListingFieldKey::CategoryId) {
+                id.clone()
+            } else {
+                label.clone()
+            };
+        self.toast(
+            t!(
+                "tui.toast.category_choice",
+                index = index + 1,
+                count = categories.len(),
+                label = label,
+                id = id
+            ),
+            Severity::Info,
+        );
+        self.listings_category_index = index;
+        self.listings_category_choice = Some((id, label));
+    }
+
+    /// Cycles the condition field through the allowed conditions from the last
+    /// listing run; saving applies both label and id.
+    fn cycle_allowed_condition(&mut self) {
+        let listing = self.selected_listing().unwrap_or_default();
+        if listing.allowed_conditions.is_empty() {
+            self.toast(t!("tui.toast.no_conditions"), Severity::Info);
+            return;
+        }
+        let count = listing.allowed_conditions.len();
+        let index = if self.listings_condition_choice.is_some() {
+            (self.listings_condition_index + 1) % count
+        } else {
+            0
+        };
+        let label = listing.allowed_conditions[index].clone();
+        let id = if listing.allowed_condition_ids.len() == count {
+            listing.allowed_condition_ids.get(index).copied()
+        } else {
+            None
+        };
+        self.listings_field_edit_buffer =
+            if self.listings_field_edit_key == Some(ListingFieldKey::ConditionId) {
+                id.map(|id| id.to_string()).unwrap_or_default()
+            } else {
+                label.clone()
+            };
+        self.toast(
+            t!(
+                "tui.toast.condition_choice",
+                index = index + 1,
+                count = count,
+                label = label
+            ),
+            Severity::Info,
+        );
+        self.listings_condition_index = index;
+        self.listings_condition_choice = Some((label, id));
+    }
+
+    pub(crate) fn selected_listing(&self) -> Option<storage::MarketplaceListing> {
+        let product = self.active_product.as_ref()?;
+        let key = self.selected_listing_key()?;
+        product.listings.get(&key).cloned()
+    }
+
+    pub fn aspect_spec(&self, name: &str) -> Option<storage::ListingAspectSpec> {
+        self.selected_listing()?
+            .aspect_specs
+            .into_iter()
+            .find(|spec| spec.name.trim() == name)
+    }
+
+    /// Names of required aspects on the selected listing that have no value yet.
+    pub fn missing_required_aspects(&self) -> Vec<String> {
+        let Some(listing) = self.selected_listing() else {
+            return Vec::new();
+        };
+        listing
+            .aspect_specs
+            .iter()
+            .filter(|spec| spec.required)
+            .map(|spec| spec.name.trim().to_string())
+            .filter(|name| {
+                !name.is_empty()
+                    && listing
+                        .aspects
+                        .get(name)
+                        .is_none_or(|values| values.is_empty())
+            })
+            .collect()
+    }
+
+    fn start_new_aspect_editing(&mut self) {
+        if self.active_product.is_none() {
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
+            return;
+        }
+        self.listings_editing = false;
+        self.listings_field_editing = true;
+        self.listings_field_edit_key = Some(ListingFieldKey::AspectValue);
+        self.listings_field_edit_name = None;
+        self.listings_field_edit_image_index = None;
+        self.listings_field_edit_dimension = None;
+        self.listings_field_edit_kind = ListingEditKind::Text;
+        self.listings_aspect_completion = None;
+        self.listings_field_edit_buffer.clear();
+        self.toast(t!("tui.toast.new_aspect"), Severity::Info);
+    }
+
     fn handle_listings_edit_keys(
         &mut self,
         key: KeyEvent,
@@ -1585,7 +2736,7 @@ impl AppState {
         if is_save_edit_key(key) {
             if self.save_listings_text(command_tx) {
                 self.listings_editing = false;
-                self.toast("Listing saved.".to_string(), Severity::Success);
+                self.toast(t!("tui.toast.listing_saved"), Severity::Success);
             }
             return true;
         }
@@ -1593,7 +2744,7 @@ impl AppState {
             KeyCode::Esc => {
                 if self.save_listings_text(command_tx) {
                     self.listings_editing = false;
-                    self.toast("Listing saved.".to_string(), Severity::Success);
+                    self.toast(t!("tui.toast.listing_saved"), Severity::Success);
                 }
                 true
             }
@@ -1622,7 +2773,7 @@ impl AppState {
             if self.save_settings_buffer() {
                 self.settings_editing = false;
                 self.settings_edit_buffer.clear();
-                self.toast("Settings saved.".to_string(), Severity::Success);
+                self.toast(t!("tui.toast.settings_saved"), Severity::Success);
             }
             return true;
         }
@@ -1630,14 +2781,14 @@ impl AppState {
             KeyCode::Esc => {
                 self.settings_editing = false;
                 self.settings_edit_buffer.clear();
-                self.toast("Edit canceled.".to_string(), Severity::Info);
+                self.toast(t!("tui.toast.edit_canceled"), Severity::Info);
                 true
             }
             KeyCode::Enter => {
                 if self.save_settings_buffer() {
                     self.settings_editing = false;
                     self.settings_edit_buffer.clear();
-                    self.toast("Settings saved.".to_string(), Severity::Success);
+                    self.toast(t!("tui.toast.settings_saved"), Severity::Success);
                 }
                 true
             }
@@ -1654,11 +2805,19 @@ impl AppState {
         }
     }
 
+    /// Asks the preview cache for the selected image; it is shown once
+    /// [`PreviewEvent::Ready`] arrives.
     fn queue_image_preview(&mut self) {
         let path = self.preview_image_path();
+        if path != self.preview_image_path {
+            self.preview_display_path = None;
+        }
         self.preview_image_path = path.clone();
-        self.pending_commands
-            .push(AppCommand::Preview(PreviewCommand::ShowImage(path)));
+        let cmd = match path {
+            Some(source) => AppCommand::PreviewCache(PreviewCacheCommand::Prepare(source)),
+            None => AppCommand::Preview(PreviewCommand::ShowImage(None)),
+        };
+        self.pending_commands.push(cmd);
     }
 
     fn preview_image_path(&self) -> Option<PathBuf> {
@@ -1741,13 +2900,46 @@ impl AppState {
             AppEvent::UploadJob(job) => self.apply_upload_job(job),
             AppEvent::UploadFinished { product_id } => {
                 self.product_syncing = true;
+                let gallery = self.media_view.open && self.media_view.product_id == product_id;
                 self.pending_commands
                     .push(AppCommand::Storage(StorageCommand::SyncProductMedia {
-                        product_id,
+                        product_id: product_id.clone(),
                     }));
+                if gallery {
+                    self.pending_commands.push(AppCommand::Storage(
+                        StorageCommand::ListProductMedia { product_id },
+                    ));
+                }
             }
             AppEvent::Activity(entry) => self.record_activity(entry),
             AppEvent::Account(event) => self.apply_account_event(event),
+            AppEvent::Health(report) => self.apply_health_report(report),
+            AppEvent::Pedal(PedalEvent::Pressed) => {
+                let countdown = self.config.capture_triggers.pedal_countdown;
+                self.pending_commands.push(self.capture_command(countdown));
+            }
+            AppEvent::Pedal(PedalEvent::Error(message)) => self.toast(message, Severity::Warning),
+            AppEvent::ConfigReloaded {
+                config,
+                online_ready,
+                error,
+            } => {
+                if let Some(cfg) = config {
+                    self.apply_reloaded_config(*cfg);
+                }
+                self.config.online_ready = online_ready;
+                match error {
+                    Some(err) => self.toast(err, Severity::Warning),
+                    None => self.record_activity(ActivityEntry {
+                        at: Local::now(),
+                        severity: Severity::Info,
+                        message: format!(
+                            "Hermes clients reloaded ({}).",
+                            if online_ready { "online" } else { "offline" }
+                        ),
+                    }),
+                }
+            }
         }
     }
 
@@ -1757,6 +2949,15 @@ impl AppState {
                 self.preview_enabled = false;
                 self.toast(message, Severity::Warning);
             }
+            PreviewEvent::Ready { source, cached } => {
+                // A later selection supersedes this one.
+                if self.preview_image_path.as_ref() != Some(&source) {
+                    return;
+                }
+                self.preview_display_path = Some(cached.clone());
+                self.pending_commands
+                    .push(AppCommand::Preview(PreviewCommand::ShowImage(Some(cached))));
+            }
         }
     }
 
@@ -1796,7 +2997,7 @@ impl AppState {
                     }
                 }
             }
-            KeyCode::Char('t') => {
+            _ if self.keys.matches(Action::ToggleCamera, &key) => {
                 let enable = !self.capture_status.streaming;
                 let cmd = if enable {
                     CaptureCommand::StartStream
@@ -1809,33 +3010,72 @@ impl AppState {
                     crate::types::PreviewCommand::SetEnabled(enable),
                 ));
             }
-            KeyCode::Char('d') => {
+            _ if self.keys.matches(Action::PrevDevice, &key) => {
                 self.device_index = (self.device_index - 1).max(0);
                 let _ = command_tx.send(AppCommand::Capture(CaptureCommand::SetDevice {
                     index: self.device_index,
+                    tethered: None,
                 }));
             }
-            KeyCode::Char('D') => {
+            _ if self.keys.matches(Action::NextDevice, &key) => {
                 self.device_index += 1;
                 let _ = command_tx.send(AppCommand::Capture(CaptureCommand::SetDevice {
                     index: self.device_index,
+                    tethered: None,
                 }));
             }
-            KeyCode::Char('v') => {
+            _ if self.keys.matches(Action::DevicePicker, &key) => {
                 self.open_camera_picker();
             }
-            KeyCode::Char('c') => {
+            _ if self.keys.matches(Action::Capture, &key) => {
                 let _ = command_tx.send(AppCommand::Capture(CaptureCommand::CaptureOne));
             }
-            KeyCode::Char('r') => {
+            _ if self.keys.matches(Action::RetroCapture, &key) => {
+                let _ = command_tx.send(AppCommand::Capture(CaptureCommand::CaptureRetro));
+            }
+            _ if self.keys.matches(Action::CountdownCapture, &key) => {
+                self.trigger_capture(true, command_tx);
+            }
+            _ if self.keys.matches(Action::Turntable, &key) => {
+                self.trigger_turntable(command_tx);
+            }
+            _ if self.keys.matches(Action::CaptureBoth, &key) => {
+                if self.config.second_camera.is_some() {
+                    let _ = command_tx.send(AppCommand::Capture(CaptureCommand::CaptureBoth));
+                } else {
+                    self.toast(t!("tui.toast.no_second_camera"), Severity::Warning);
+                }
+            }
+            _ if self.keys.matches(Action::GenerateStructure, &key) => {
                 self.start_structure_inference(command_tx);
             }
-            KeyCode::Char('p') => {
+            _ if self.keys.matches(Action::Draft, &key) => {
                 self.start_context_pipeline(command_tx, true, false);
             }
-            KeyCode::Char('P') => {
+            _ if self.keys.matches(Action::Publish, &key) => {
                 self.start_context_pipeline(command_tx, false, true);
             }
+            _ if self.keys.matches(Action::Grade, &key) => {
+                self.open_grading();
+            }
+            _ if self.keys.matches(Action::ScanBarcode, &key) => {
+                self.scan_product_barcodes();
+            }
+            _ if self.keys.matches(Action::Measure, &key) => {
+                self.open_measure();
+            }
+            _ if self.keys.matches(Action::GrayCard, &key) => {
+                self.calibrate_white_balance();
+            }
+            _ if self.keys.matches(Action::ZoomAssist, &key) => {
+                self.set_preview_assist(self.preview_assist.next_zoom());
+            }
+            _ if self.keys.matches(Action::FocusPeaking, &key) => {
+                self.set_preview_assist(PreviewAssist {
+                    peaking: !self.preview_assist.peaking,
+                    ..self.preview_assist
+                });
+            }
             KeyCode::Backspace | KeyCode::Delete => {
                 if self.context_focus != ContextFocus::Images {
                     return;
@@ -1869,11 +3109,16 @@ impl AppState {
                     None => {}
                 }
             }
-            KeyCode::Char('n') => {
+            _ if self.keys.matches(Action::ShotList, &key) => self.cycle_shot_list(command_tx),
+            _ if self.keys.matches(Action::ShotLabel, &key) => self.cycle_shot_label(command_tx),
+            _ if self.keys.matches(Action::SetHero, &key) => self.toggle_session_hero(command_tx),
+            _ if self.keys.matches(Action::OpenImage, &key) => self.open_selected_image(),
+            _ if self.keys.matches(Action::NewProduct, &key) => {
                 self.pending_new_product_session = true;
                 let _ =
                     command_tx.send(AppCommand::Storage(StorageCommand::CreateProductAndSession));
             }
+            _ if self.keys.matches(Action::Undo, &key) => self.undo_last_delete(command_tx),
             KeyCode::Esc => {
                 if let Some(session) = &self.active_session {
                     let _ = command_tx.send(AppCommand::Storage(StorageCommand::AbandonSession {
@@ -1898,18 +3143,24 @@ impl AppState {
                     self.structure_field_selected += 1;
                 }
             }
-            KeyCode::Enter | KeyCode::Char('e') => {
+            _ if key.code == KeyCode::Enter || self.keys.matches(Action::Edit, &key) => {
                 self.start_structure_field_editing();
             }
-            KeyCode::Char('E') => {
+            _ if self.keys.matches(Action::EditJson, &key) => {
                 self.start_structure_editing();
             }
-            KeyCode::Char('g') => {
+            _ if self.keys.matches(Action::CancelJob, &key) => {
+                self.cancel_product_jobs(&[JobKind::Structure]);
+            }
+            _ if self.keys.matches(Action::GenerateListing, &key) => {
                 self.generate_listing(false, false);
             }
-            KeyCode::Char('r') => {
+            _ if self.keys.matches(Action::GenerateStructure, &key) => {
+                if !self.permitted("generating structure") {
+                    return;
+                }
                 let Some(product) = &self.active_product else {
-                    self.toast("No active product selected.".to_string(), Severity::Warning);
+                    self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
                     return;
                 };
                 let _ = command_tx.send(AppCommand::Storage(
@@ -1919,10 +3170,11 @@ impl AppState {
                         llm_ingest: self.llm_ingest.clone(),
                         context_text: non_empty(self.context_text.clone()),
                         prompt_rules: self.prompt_rules.clone(),
+                        quality: self.config.quality.clone(),
                     },
                 ));
                 self.structure_inference = true;
-                self.toast("Generating structure...".to_string(), Severity::Info);
+                self.toast(t!("tui.toast.generating_structure"), Severity::Info);
             }
             _ => {}
         }
@@ -1937,12 +3189,17 @@ impl AppState {
                 .iter()
                 .map(|s| s.as_str())
                 .collect();
+            let suggested_hero = session.suggested_hero();
             for frame in &session.frames {
                 entries.push(ContextImageEntry::Session {
                     rel_path: frame.rel_path.clone(),
                     sharpness_score: frame.sharpness_score,
                     created_at: frame.created_at,
                     selected: selected.contains(frame.rel_path.as_str()),
+                    shot: session.shot_label(&frame.rel_path).map(str::to_string),
+                    tag: frame.tag.clone(),
+                    hero: session.picks.hero_rel_path.as_ref() == Some(&frame.rel_path),
+                    suggested: suggested_hero == Some(frame.rel_path.as_str()),
                 });
             }
         }
@@ -1963,6 +3220,7 @@ impl AppState {
                     created_at: image.created_at,
                     source: image_source(&image.rel_path, image.uploaded_url.is_some()),
                     hero: hero_rel == Some(image.rel_path.as_str()),
+                    label: image.label.clone(),
                 });
             }
             if let Some(hero_rel) = hero_rel {
@@ -1976,6 +3234,7 @@ impl AppState {
                             created_at: product.updated_at,
                             source: image_source(hero_rel, product.hero_uploaded_url.is_some()),
                             hero: true,
+                            label: None,
                         });
                     }
                 }
@@ -1984,55 +3243,256 @@ impl AppState {
         entries
     }
 
-    pub(crate) fn context_image_count(&self) -> usize {
-        self.context_image_entries().len()
+    /// Capture now, or after the configured countdown; triggering a countdown
+    /// while one runs cancels it.
+    fn capture_command(&self, countdown: bool) -> AppCommand {
+        let cmd = if !countdown {
+            CaptureCommand::CaptureOne
+        } else if self.capture_status.countdown.is_some() {
+            CaptureCommand::CancelCountdown
+        } else {
+            CaptureCommand::CaptureAfter {
+                secs: self.config.capture_triggers.countdown_secs,
+            }
+        };
+        AppCommand::Capture(cmd)
     }
 
-    fn handle_listings_keys(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
-        match key.code {
-            KeyCode::Up => {
-                if self.listings_field_selected > 0 {
-                    self.listings_field_selected -= 1;
-                    self.queue_image_preview();
-                }
-            }
-            KeyCode::Down => {
-                let entries = self.listing_field_entries();
-                if self.listings_field_selected + 1 < entries.len() {
-                    self.listings_field_selected += 1;
-                    self.queue_image_preview();
-                }
-            }
-            KeyCode::Enter | KeyCode::Char('e') => {
-                self.start_listings_field_editing();
-            }
-            KeyCode::Char('E') => {
-                self.start_listings_editing();
-            }
-            KeyCode::Char('r') => {
-                self.toast(
-                    "Use p for draft listings (r is for Structure).".to_string(),
-                    Severity::Info,
-                );
-            }
-            KeyCode::Char('g') => {
-                self.generate_listing(false, false);
-            }
-            KeyCode::Char('p') => {
-                self.generate_listing(true, false);
-            }
-            KeyCode::Char('P') => {
-                self.generate_listing(false, true);
+    fn trigger_capture(&mut self, countdown: bool, command_tx: &Sender<AppCommand>) {
+        if self.active_session.is_none() {
+            self.toast(t!("tui.toast.no_session_capture"), Severity::Warning);
+            return;
+        }
+        let _ = command_tx.send(self.capture_command(countdown));
+    }
+
+    /// Starts a turntable run with the `[capture_triggers]` interval and
+    /// frame count, or stops the one running.
+    fn trigger_turntable(&mut self, command_tx: &Sender<AppCommand>) {
+        if self.capture_status.turntable.is_some() {
+            let _ = command_tx.send(AppCommand::Capture(CaptureCommand::CancelInterval));
+            return;
+        }
+        if self.active_session.is_none() {
+            self.toast(t!("tui.toast.no_session_capture"), Severity::Warning);
+            return;
+        }
+        let triggers = &self.config.capture_triggers;
+        let (every_ms, total) = (triggers.turntable_every_ms, triggers.turntable_frames);
+        let _ = command_tx.send(AppCommand::Capture(CaptureCommand::CaptureInterval {
+            every_ms,
+            total,
+        }));
+        self.toast(
+            t!(
+                "tui.toast.turntable_run",
+                total = total,
+                seconds = format!("{:.1}", every_ms as f64 / 1000.0)
+            ),
+            Severity::Info,
+        );
+    }
+
+    /// Moves the active session to the next configured shot list, then back
+    /// to none. New sessions start with the last one picked.
+    fn cycle_shot_list(&mut self, command_tx: &Sender<AppCommand>) {
+        let Some(session) = &self.active_session else {
+            self.toast(t!("tui.toast.no_session"), Severity::Warning);
+            return;
+        };
+        let templates = &self.config.session_templates;
+        if templates.is_empty() {
+            self.toast(t!("tui.toast.no_shot_lists"), Severity::Info);
+            return;
+        }
+        let next = match &session.template {
+            None => templates.first(),
+            Some(current) => templates
+                .iter()
+                .position(|t| t.name == current.name)
+                .and_then(|pos| templates.get(pos + 1)),
+        }
+        .cloned();
+        self.session_template = next.as_ref().map(|t| t.name.clone());
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::SetSessionTemplate {
+            session_id: session.session_id.clone(),
+            template: next,
+        }));
+    }
+
+    /// Steps the selected session frame's label through the shot list.
+    fn cycle_shot_label(&mut self, command_tx: &Sender<AppCommand>) {
+        let Some(session) = &self.active_session else {
+            return;
+        };
+        let Some(template) = &session.template else {
+            self.toast(
+                t!(
+                    "tui.toast.pick_shot_list",
+                    key = self.keys.label(Action::ShotList)
+                ),
+                Severity::Info,
+            );
+            return;
+        };
+        let entry = self
+            .context_image_entries()
+            .get(self.session_frame_selected)
+            .cloned();
+        let Some(ContextImageEntry::Session { rel_path, shot, .. }) = entry else {
+            return;
+        };
+        let labels: Vec<&str> = template.labels().collect();
+        let next = match shot {
+            None => labels.first(),
+            Some(current) => labels
+                .iter()
+                .position(|label| *label == current)
+                .and_then(|pos| labels.get(pos + 1)),
+        };
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::SetSessionFrameShot {
+            session_id: session.session_id.clone(),
+            frame_rel_path: rel_path,
+            label: next.map(|label| label.to_string()),
+        }));
+    }
+
+    /// Makes the selected session frame the hero, or clears the hero when it
+    /// already is one so the suggestion applies again.
+    fn toggle_session_hero(&mut self, command_tx: &Sender<AppCommand>) {
+        let Some(session) = &self.active_session else {
+            return;
+        };
+        let entry = self
+            .context_image_entries()
+            .get(self.session_frame_selected)
+            .cloned();
+        let Some(ContextImageEntry::Session { rel_path, hero, .. }) = entry else {
+            return;
+        };
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::SetSessionHero {
+            session_id: session.session_id.clone(),
+            frame_rel_path: (!hero).then_some(rel_path),
+        }));
+    }
+
+    pub(crate) fn context_image_count(&self) -> usize {
+        self.context_image_entries().len()
+    }
+
+    /// Cancels the active product's running jobs of `kinds`; they report
+    /// back once their request is dropped.
+    fn cancel_product_jobs(&mut self, kinds: &[JobKind]) {
+        let Some(product) = &self.active_product else {
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
+            return;
+        };
+        let canceled: Vec<&str> = kinds
+            .iter()
+            .filter(|kind| {
+                self.jobs
+                    .cancel(&JobKey::new(**kind, product.product_id.clone()))
+            })
+            .map(|kind| kind.label())
+            .collect();
+        if canceled.is_empty() {
+            self.toast(t!("tui.toast.no_running_job"), Severity::Info);
+        } else {
+            self.toast(
+                t!("tui.toast.canceling", what = canceled.join(" and ")),
+                Severity::Info,
+            );
+        }
+    }
+
+    /// ` (c cancel)` while the active product runs a job of `kinds`.
+    pub(crate) fn job_cancel_hint(&self, kinds: &[JobKind]) -> String {
+        let running = self.active_product.as_ref().is_some_and(|product| {
+            kinds.iter().any(|kind| {
+                self.jobs
+                    .is_running(&JobKey::new(*kind, product.product_id.clone()))
+            })
+        });
+        if running {
+            format!(" ({} cancel)", self.keys.label(Action::CancelJob))
+        } else {
+            String::new()
+        }
+    }
+
+    fn handle_listings_keys(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        match key.code {
+            KeyCode::Up => {
+                if self.listings_field_selected > 0 {
+                    self.listings_field_selected -= 1;
+                    self.queue_image_preview();
+                }
             }
-            KeyCode::Char('u') => {
+            KeyCode::Down => {
+                let entries = self.listing_field_entries();
+                if self.listings_field_selected + 1 < entries.len() {
+                    self.listings_field_selected += 1;
+                    self.queue_image_preview();
+                }
+            }
+            _ if key.code == KeyCode::Enter || self.keys.matches(Action::Edit, &key) => {
+                self.start_listings_field_editing();
+            }
+            _ if self.keys.matches(Action::EditJson, &key) => {
+                self.start_listings_editing();
+            }
+            _ if self.keys.matches(Action::AddAspect, &key) => {
+                self.start_new_aspect_editing();
+            }
+            _ if self.keys.matches(Action::GenerateListing, &key) => {
+                self.generate_listing(false, false);
+            }
+            _ if self.keys.matches(Action::Draft, &key) => {
+                self.generate_listing(true, false);
+            }
+            _ if self.keys.matches(Action::Publish, &key) => {
+                self.generate_listing(false, true);
+            }
+            _ if self.keys.matches(Action::Grade, &key) => {
+                self.open_grading();
+            }
+            _ if self.keys.matches(Action::SuggestPrice, &key) => {
+                self.suggest_price(command_tx);
+            }
+            _ if self.keys.matches(Action::CancelJob, &key) => {
+                self.cancel_product_jobs(&[JobKind::Listing, JobKind::Upload]);
+            }
+            _ if self.keys.matches(Action::CopyListingId, &key) => {
+                match self
+                    .selected_listing()
+                    .and_then(|listing| listing.listing_id)
+                {
+                    Some(listing_id) => {
+                        self.copy_to_clipboard(&t!("tui.copy.listing_id"), &listing_id)
+                    }
+                    None => self.toast(t!("tui.toast.no_listing_id"), Severity::Info),
+                }
+            }
+            _ if self.keys.matches(Action::OpenListing, &key) => self.open_selected_listing(),
+            _ if self.keys.matches(Action::UploadImages, &key) => {
                 let Some(product) = &self.active_product else {
-                    self.toast("No active product selected.".to_string(), Severity::Warning);
+                    self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
                     return;
                 };
                 let _ = command_tx.send(AppCommand::Upload(UploadCommand::UploadProduct {
                     product_id: product.product_id.clone(),
                 }));
             }
+            _ if self.keys.matches(Action::GenerateStructure, &key) => {
+                self.toast(
+                    t!(
+                        "tui.toast.use_draft_key",
+                        draft = self.keys.label(Action::Draft),
+                        structure = self.keys.label(Action::GenerateStructure)
+                    ),
+                    Severity::Info,
+                );
+            }
             _ => {}
         }
     }
@@ -2040,7 +3500,8 @@ impl AppState {
     fn handle_products_keys(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
         match self.products_mode {
             ProductsMode::Grid => {
-                let product_count = self.picker.products.len();
+                let products = self.filtered_products();
+                let product_count = products.len();
                 let cols = self.product_grid_cols.max(1);
                 match key.code {
                     KeyCode::Left => {
@@ -2063,43 +3524,81 @@ impl AppState {
                             self.product_grid_selected += cols;
                         }
                     }
-                    KeyCode::Char('n') => {
+                    _ if self.keys.matches(Action::NewProduct, &key) => {
                         self.pending_new_product_session = true;
                         let _ = command_tx
                             .send(AppCommand::Storage(StorageCommand::CreateProductAndSession));
                     }
-                    KeyCode::Char('d') | KeyCode::Delete | KeyCode::Backspace => {
-                        if let Some(product) = self.picker.products.get(self.product_grid_selected)
-                        {
-                            let active_block = self.active_session.as_ref().is_some_and(|s| {
-                                s.product_id == product.product_id && s.committed_at.is_none()
-                            });
-                            if active_block {
-                                self.toast(
-                                    "Finish or abandon the active session before deleting."
-                                        .to_string(),
-                                    Severity::Warning,
-                                );
-                                return;
+                    _ if self.keys.matches(Action::Search, &key) => {
+                        self.product_search_editing = true;
+                    }
+                    KeyCode::Char(' ') => {
+                        if let Some(product) = products.get(self.product_grid_selected) {
+                            if !self.product_marks.remove(&product.product_id) {
+                                self.product_marks.insert(product.product_id.clone());
+                            }
+                        }
+                    }
+                    _ if self.keys.matches(Action::MarkAll, &key) => {
+                        let all_marked = products
+                            .iter()
+                            .all(|p| self.product_marks.contains(&p.product_id));
+                        for product in &products {
+                            if all_marked {
+                                self.product_marks.remove(&product.product_id);
+                            } else {
+                                self.product_marks.insert(product.product_id.clone());
                             }
-                            self.delete_confirm = Some(DeleteConfirm {
-                                product_id: product.product_id.clone(),
-                                expires_at: Instant::now() + Duration::from_secs(6),
-                            });
-                            self.toast(
-                                format!(
-                                    "Delete {}? Press y to confirm, n to cancel.",
-                                    product.sku_alias
-                                ),
-                                Severity::Warning,
-                            );
-                        } else {
-                            self.toast("No products available.".to_string(), Severity::Warning);
                         }
                     }
+                    KeyCode::Esc => {
+                        self.product_marks.clear();
+                    }
+                    _ if self.keys.matches(Action::Undo, &key) => self.undo_last_delete(command_tx),
+                    _ if self.keys.matches(Action::Trash, &key) => {
+                        self.trash_view = TrashViewState {
+                            open: true,
+                            ..Default::default()
+                        };
+                        let _ = command_tx.send(AppCommand::Storage(StorageCommand::ListTrash));
+                    }
+                    _ if self.keys.matches(Action::BulkUpload, &key) => {
+                        self.bulk_upload(command_tx)
+                    }
+                    _ if self.keys.matches(Action::BulkEnrich, &key) => {
+                        self.start_bulk(BulkAction::Enrich, command_tx)
+                    }
+                    _ if self.keys.matches(Action::BulkExport, &key) => {
+                        self.start_bulk(BulkAction::Export, command_tx)
+                    }
+                    _ if self.keys.matches(Action::BulkDelete, &key) => {
+                        self.start_bulk(BulkAction::Delete, command_tx)
+                    }
+                    _ if self.keys.matches(Action::StatusFilter, &key) => {
+                        self.picker.status_filter = self.picker.status_filter.next();
+                        self.on_product_filter_changed();
+                    }
+                    _ if self.keys.matches(Action::MarketplaceFilter, &key) => {
+                        self.cycle_marketplace_filter();
+                        self.on_product_filter_changed();
+                    }
+                    _ if self.keys.matches(Action::DateFilter, &key) => {
+                        self.picker.date_filter = self.picker.date_filter.next();
+                        self.on_product_filter_changed();
+                    }
+                    _ if self.keys.matches(Action::ClearFilters, &key) => {
+                        self.picker.search.clear();
+                        self.picker.status_filter = ProductStatusFilter::All;
+                        self.picker.marketplace_filter = None;
+                        self.picker.date_filter = ProductDateFilter::Any;
+                        self.on_product_filter_changed();
+                    }
+                    KeyCode::Delete | KeyCode::Backspace => self.confirm_delete_selected(&products),
+                    _ if self.keys.matches(Action::DeleteProduct, &key) => {
+                        self.confirm_delete_selected(&products)
+                    }
                     KeyCode::Enter => {
-                        if let Some(product) = self.picker.products.get(self.product_grid_selected)
-                        {
+                        if let Some(product) = products.get(self.product_grid_selected) {
                             self.pending_product_selection = Some(product.product_id.clone());
                             let _ = command_tx.send(AppCommand::Storage(
                                 StorageCommand::StartSessionForProduct {
@@ -2107,7 +3606,7 @@ impl AppState {
                                 },
                             ));
                         } else {
-                            self.toast("No products available.".to_string(), Severity::Warning);
+                            self.toast(t!("tui.toast.no_products"), Severity::Warning);
                         }
                     }
                     _ => {}
@@ -2156,13 +3655,32 @@ impl AppState {
                             }
                         }
                     }
-                    KeyCode::Char('S') => {
+                    _ if self.keys.matches(Action::SaveSync, &key) => {
                         self.handle_ctrl_save(command_tx);
                     }
-                    KeyCode::Char('G') => {
+                    _ if self.keys.matches(Action::BackToGrid, &key) => {
                         self.products_mode = ProductsMode::Grid;
-                        self.products_loading = true;
-                        let _ = command_tx.send(AppCommand::Storage(StorageCommand::ListProducts));
+                        self.refresh_products(command_tx, true);
+                    }
+                    _ if self.keys.matches(Action::MediaGallery, &key) => {
+                        self.open_media_view(command_tx);
+                        return;
+                    }
+                    _ if self.keys.matches(Action::CopyImageUrls, &key) => {
+                        self.copy_image_urls();
+                        return;
+                    }
+                    _ if self.keys.matches(Action::CopyJobId, &key) => {
+                        let job_id = self
+                            .active_product
+                            .as_ref()
+                            .and_then(|product| self.listing_job_ids.get(&product.product_id))
+                            .cloned();
+                        match job_id {
+                            Some(job_id) => self.copy_to_clipboard(&t!("tui.copy.job_id"), &job_id),
+                            None => self.toast(t!("tui.toast.no_job_id"), Severity::Info),
+                        }
+                        return;
                     }
                     _ => {}
                 }
@@ -2170,12 +3688,14 @@ impl AppState {
                 if self.products_subtab == ProductsSubTab::Context {
                     if key.code == KeyCode::Enter && self.context_focus == ContextFocus::Text {
                         self.text_editing = true;
-                        self.toast("Editing text (Esc to save).".to_string(), Severity::Info);
+                        self.toast(t!("tui.toast.editing_text"), Severity::Info);
                         return;
                     }
-                    if key.code == KeyCode::Char('e') && self.context_focus == ContextFocus::Text {
+                    if self.keys.matches(Action::Edit, &key)
+                        && self.context_focus == ContextFocus::Text
+                    {
                         self.text_editing = true;
-                        self.toast("Editing text (Esc to save).".to_string(), Severity::Info);
+                        self.toast(t!("tui.toast.editing_text"), Severity::Info);
                         return;
                     }
                 }
@@ -2189,6 +3709,32 @@ impl AppState {
         }
     }
 
+    fn confirm_delete_selected(&mut self, products: &[storage::ProductSummary]) {
+        if !self.permitted("deleting products") {
+            return;
+        }
+        if let Some(product) = products.get(self.product_grid_selected) {
+            let active_block = self
+                .active_session
+                .as_ref()
+                .is_some_and(|s| s.product_id == product.product_id && s.committed_at.is_none());
+            if active_block {
+                self.toast(t!("tui.toast.finish_session_first"), Severity::Warning);
+                return;
+            }
+            self.delete_confirm = Some(DeleteConfirm {
+                product_ids: vec![product.product_id.clone()],
+                expires_at: Instant::now() + Duration::from_secs(6),
+            });
+            self.toast(
+                t!("tui.toast.confirm_delete", sku = product.sku_alias),
+                Severity::Warning,
+            );
+        } else {
+            self.toast(t!("tui.toast.no_products"), Severity::Warning);
+        }
+    }
+
     fn handle_settings_keys(&mut self, key: KeyEvent) {
         if self.settings_editing {
             return;
@@ -2206,13 +3752,32 @@ impl AppState {
             }
             KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char('E') => {
                 let field = settings_fields()[self.settings_selected];
-                if is_llm_settings_field(field) {
+                if is_picker_settings_field(field) {
                     self.open_settings_picker(field);
                     return;
                 }
                 self.settings_editing = true;
+                let supabase = self.config.supabase.as_ref();
                 self.settings_edit_buffer = match field {
-                    SettingsField::HermesApiKey => String::new(),
+                    SettingsField::HermesApiKey | SettingsField::SupabaseServiceRoleKey => {
+                        String::new()
+                    }
+                    SettingsField::BaseUrl => self.config.base_url.clone().unwrap_or_default(),
+                    SettingsField::SupabaseUrl => {
+                        supabase.map(|s| s.url.clone()).unwrap_or_default()
+                    }
+                    SettingsField::SupabaseBucket => {
+                        supabase.map(|s| s.bucket.clone()).unwrap_or_default()
+                    }
+                    SettingsField::SupabaseUploadPrefix => supabase
+                        .map(|s| s.upload_prefix.clone())
+                        .unwrap_or_default(),
+                    SettingsField::CapturesDir => self
+                        .config
+                        .captures_dir
+                        .as_ref()
+                        .map(|dir| dir.display().to_string())
+                        .unwrap_or_default(),
                     SettingsField::PreviewHeightPct => self.preview_height_pct.to_string(),
                     SettingsField::Marketplace => {
                         self.ebay_settings.marketplace.clone().unwrap_or_default()
@@ -2238,7 +3803,9 @@ impl AppState {
                         .clone()
                         .unwrap_or_default(),
                     SettingsField::HsufPromptRules => self.prompt_rules.clone().unwrap_or_default(),
-                    SettingsField::LlmIngestModel
+                    SettingsField::SupabaseSignedUrls
+                    | SettingsField::PreviewBackend
+                    | SettingsField::LlmIngestModel
                     | SettingsField::LlmIngestReasoning
                     | SettingsField::LlmIngestWebSearch
                     | SettingsField::LlmAspectsModel
@@ -2301,16 +3868,31 @@ impl AppState {
 
     fn refresh_camera_picker(&mut self) {
         self.camera_picker.error = None;
-        match camera::list_devices() {
+        self.camera_picker.url_input = None;
+        let devices = if self.config.simulate {
+            Ok(vec![camera::simulated_device()])
+        } else {
+            camera::list_devices().map(|mut devices| {
+                let first = devices.iter().map(|d| d.index + 1).max().unwrap_or(0);
+                devices.extend(camera::network_devices(&self.config.camera_urls, first));
+                devices
+            })
+        };
+        let current_url = self.capture_status.device_url.clone();
+        match devices {
             Ok(devices) => {
                 self.camera_picker.devices = devices;
                 if self.camera_picker.devices.is_empty() {
                     self.camera_picker.error = Some("No cameras found.".to_string());
-                } else if let Some(pos) = self
-                    .camera_picker
-                    .devices
-                    .iter()
-                    .position(|d| d.index == self.device_index)
+                } else if let Some(pos) =
+                    self.camera_picker
+                        .devices
+                        .iter()
+                        .position(|d| match (&d.kind, &current_url) {
+                            (camera::DeviceKind::Network(url), Some(current)) => url == current,
+                            (_, Some(_)) => false,
+                            (_, None) => d.index == self.device_index,
+                        })
                 {
                     self.camera_picker.selected = pos;
                 } else {
@@ -2324,59 +3906,1322 @@ impl AppState {
         }
     }
 
-    fn handle_camera_picker_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+    /// Re-scans every session frame and product image of the active product.
+    fn scan_product_barcodes(&mut self) {
+        let Some(product) = &self.active_product else {
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
+            return;
+        };
+        if !barcode::SCAN_FILES_ENABLED {
+            self.toast(t!("tui.toast.barcode_unsupported"), Severity::Warning);
+            return;
+        }
+        let session_dir = self
+            .active_session
+            .as_ref()
+            .map(|s| storage::session_dir(&self.captures_dir, &s.session_id));
+        let product_dir = storage::product_dir(&self.captures_dir, &product.product_id);
+        let frames = self
+            .context_image_entries()
+            .into_iter()
+            .filter_map(|entry| match entry {
+                ContextImageEntry::Session { rel_path, .. } => {
+                    session_dir.as_ref().map(|dir| dir.join(rel_path))
+                }
+                ContextImageEntry::Product { rel_path, .. } => Some(product_dir.join(rel_path)),
+            })
+            .collect::<Vec<_>>();
+        if frames.is_empty() {
+            self.toast(t!("tui.toast.no_images_to_scan"), Severity::Info);
+            return;
+        }
+        self.pending_commands
+            .push(AppCommand::Storage(StorageCommand::ScanBarcodes {
+                product_id: product.product_id.clone(),
+                frames,
+                report_misses: true,
+            }));
+        self.toast(t!("tui.toast.scanning_barcodes"), Severity::Info);
+    }
+
+    fn handle_barcode_prompt_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        let set_sku = match key.code {
+            KeyCode::Enter => true,
+            KeyCode::Char('c') => false,
+            KeyCode::Esc => {
+                if let Some(prompt) = self.barcode_prompt.take() {
+                    self.barcode_dismissed.insert(prompt.barcode.value);
+                }
+                return;
+            }
+            _ => return,
+        };
+        let Some(prompt) = self.barcode_prompt.take() else {
+            return;
+        };
+        // Keep an in-progress edit in step with what gets stored.
+        if self.text_editing
+            && self
+                .active_product
+                .as_ref()
+                .is_some_and(|p| p.product_id == prompt.product_id)
+        {
+            self.context_text = prompt.barcode.add_to_context(&self.context_text);
+        }
+        let message = if set_sku {
+            t!("tui.toast.sku_set", sku = prompt.barcode.value)
+        } else {
+            t!(
+                "tui.toast.barcode_added",
+                line = prompt.barcode.context_line()
+            )
+        };
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::ApplyBarcode {
+            product_id: prompt.product_id,
+            barcode: prompt.barcode,
+            set_sku,
+        }));
+        self.toast(message, Severity::Success);
+    }
+
+    fn suggest_price(&mut self, command_tx: &Sender<AppCommand>) {
+        let Some(product) = &self.active_product else {
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
+            return;
+        };
+        let Some(key) = self.selected_listing_key() else {
+            self.toast(t!("tui.toast.no_listing_selected"), Severity::Warning);
+            return;
+        };
+        let listing = product.listings.get(&key).cloned().unwrap_or_default();
+        let request = comparable_prices_request(
+            product,
+            &listing,
+            selected_marketplace(Some(&key), &self.ebay_settings),
+        );
+        if request.query.is_none() && request.brand.is_none() && request.mpn.is_none() {
+            self.toast(t!("tui.toast.needs_title"), Severity::Warning);
+            return;
+        }
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::SuggestListingPrice {
+            product_id: product.product_id.clone(),
+            marketplace_key: key,
+            request,
+            currency: listing.currency.clone(),
+            condition: listing.condition.clone(),
+            rules: self.config.pricing.clone(),
+        }));
+        self.toast(t!("tui.toast.looking_up_prices"), Severity::Info);
+    }
+
+    fn handle_price_prompt_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        let Some(prompt) = self.price_prompt.as_mut() else {
+            return;
+        };
+        let step = if key.modifiers.contains(KeyModifiers::SHIFT) {
+            10.0
+        } else {
+            1.0
+        };
         match key.code {
             KeyCode::Esc => {
-                self.camera_picker.open = false;
+                self.price_prompt = None;
+                return;
             }
+            KeyCode::Left | KeyCode::Down => prompt.price = (prompt.price - step).max(0.01),
+            KeyCode::Right | KeyCode::Up => prompt.price += step,
+            KeyCode::Char('l') => prompt.price = prompt.suggestion.low,
+            KeyCode::Char('m') => prompt.price = prompt.suggestion.suggested,
+            KeyCode::Char('h') => prompt.price = prompt.suggestion.high,
+            KeyCode::Enter => {}
+            _ => return,
+        }
+        prompt.price = (prompt.price * 100.0).round() / 100.0;
+        if key.code != KeyCode::Enter {
+            return;
+        }
+        let Some(prompt) = self.price_prompt.take() else {
+            return;
+        };
+        let Some(product) = self
+            .active_product
+            .as_ref()
+            .filter(|p| p.product_id == prompt.product_id)
+        else {
+            self.toast(t!("tui.toast.price_not_applied"), Severity::Warning);
+            return;
+        };
+        let mut listings = product.listings.clone();
+        let listing = listings.entry(prompt.marketplace_key).or_default();
+        listing.price = Some(prompt.price);
+        if listing.currency.is_none() {
+            listing.currency = prompt.suggestion.currency.clone();
+        }
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::SetProductListings {
+            product_id: prompt.product_id,
+            listings,
+        }));
+        self.pending_post_save_notice = Some(PostSaveNotice::ListingsUpdated);
+        self.toast(
+            t!(
+                "tui.toast.price_set",
+                price = format!("{:.2}", prompt.price)
+            ),
+            Severity::Success,
+        );
+    }
+
+    fn handle_sync_conflict_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        let Some(prompt) = self.sync_conflict.as_mut() else {
+            return;
+        };
+        let resolution = match key.code {
             KeyCode::Up => {
-                if self.camera_picker.selected > 0 {
-                    self.camera_picker.selected -= 1;
-                }
+                prompt.selected = prompt.selected.saturating_sub(1);
+                return;
             }
             KeyCode::Down => {
-                if self.camera_picker.selected + 1 < self.camera_picker.devices.len() {
-                    self.camera_picker.selected += 1;
-                }
-            }
-            KeyCode::Enter => {
-                if let Some(device) = self.camera_picker.devices.get(self.camera_picker.selected) {
-                    self.device_index = device.index;
-                    let _ = command_tx.send(AppCommand::Capture(CaptureCommand::SetDevice {
-                        index: self.device_index,
-                    }));
-                }
-                self.camera_picker.open = false;
+                prompt.selected =
+                    (prompt.selected + 1).min(prompt.conflicts.len().saturating_sub(1));
+                return;
             }
-            KeyCode::Char('r') => {
-                self.refresh_camera_picker();
+            KeyCode::Esc => {
+                self.sync_conflict = None;
+                self.toast(t!("tui.toast.conflict_left_open"), Severity::Warning);
+                return;
             }
-            _ => {}
-        }
+            KeyCode::Char('l') => SyncResolution::KeepLocal,
+            KeyCode::Char('r') => SyncResolution::KeepRemote,
+            KeyCode::Char('m') => SyncResolution::Merge,
+            _ => return,
+        };
+        let Some(prompt) = self.sync_conflict.take() else {
+            return;
+        };
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::ResolveSyncConflict {
+            product_id: prompt.product_id,
+            resolution,
+        }));
+        self.product_syncing = true;
+        self.toast(
+            t!(
+                "tui.toast.resolving_conflict",
+                resolution = resolution.label()
+            ),
+            Severity::Info,
+        );
+    }
+
+    /// The overlay to draw on camera frames, while measuring with a
+    /// calibration.
+    pub fn measure_overlay(&self) -> Option<MeasureOverlay> {
+        if !self.measure.open {
+            return None;
+        }
+        Some(MeasureOverlay {
+            pixels_per_unit: self.config.measurement.pixels_per_unit?,
+            width: self.measure.across,
+            height: self.measure.tall,
+        })
+    }
+
+    fn set_preview_assist(&mut self, assist: PreviewAssist) {
+        self.preview_assist = assist;
+        self.pending_commands
+            .push(AppCommand::Preview(PreviewCommand::SetAssist(assist)));
+    }
+
+    fn sync_measure_overlay(&mut self) {
+        let overlay = self.measure_overlay();
+        self.pending_commands
+            .push(AppCommand::Preview(PreviewCommand::SetOverlay(overlay)));
+    }
+
+    fn open_measure(&mut self) {
+        let Some(product) = &self.active_product else {
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
+            return;
+        };
+        let unit = self.config.measurement.unit;
+        let measured = product.measured.as_ref().filter(|m| m.unit == unit);
+        let default = match unit {
+            LengthUnit::Inch => 4.0,
+            LengthUnit::Centimeter => 10.0,
+        };
+        self.measure = MeasureState {
+            open: true,
+            side_view: false,
+            across: measured.and_then(|m| m.width).unwrap_or(default),
+            tall: measured.and_then(|m| m.height).unwrap_or(default),
+        };
+        self.sync_measure_overlay();
+        if self.config.measurement.pixels_per_unit.is_none() {
+            self.toast(t!("tui.toast.not_calibrated"), Severity::Warning);
+        }
+    }
+
+    fn handle_measure_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        let fine = match self.config.measurement.unit {
+            LengthUnit::Inch => 0.125,
+            LengthUnit::Centimeter => 0.25,
+        };
+        let step = if key.modifiers.contains(KeyModifiers::SHIFT) {
+            fine * 8.0
+        } else {
+            fine
+        };
+        let adjust = |value: f64, delta: f64| (value + delta).max(fine);
+        match key.code {
+            KeyCode::Esc => {
+                self.measure.open = false;
+            }
+            KeyCode::Left => self.measure.across = adjust(self.measure.across, -step),
+            KeyCode::Right => self.measure.across = adjust(self.measure.across, step),
+            KeyCode::Down => self.measure.tall = adjust(self.measure.tall, -step),
+            KeyCode::Up => self.measure.tall = adjust(self.measure.tall, step),
+            KeyCode::Tab => {
+                self.measure.side_view = !self.measure.side_view;
+                let unit = self.config.measurement.unit;
+                if let Some(measured) = self
+                    .active_product
+                    .as_ref()
+                    .and_then(|p| p.measured.as_ref())
+                    .filter(|m| m.unit == unit)
+                {
+                    let across = if self.measure.side_view {
+                        measured.depth
+                    } else {
+                        measured.width
+                    };
+                    self.measure.across = across.unwrap_or(self.measure.across);
+                }
+            }
+            KeyCode::Char('k') => self.calibrate_measurement(),
+            KeyCode::Enter => self.record_measurement(command_tx, true),
+            KeyCode::Delete => self.record_measurement(command_tx, false),
+            _ if self.keys.matches(Action::Capture, &key) => {
+                let _ = command_tx.send(AppCommand::Capture(CaptureCommand::CaptureOne));
+            }
+            _ if self.keys.matches(Action::RetroCapture, &key) => {
+                let _ = command_tx.send(AppCommand::Capture(CaptureCommand::CaptureRetro));
+            }
+            _ => return,
+        }
+        self.sync_measure_overlay();
+    }
+
+    /// Points the capture thread at `session` with the current file naming and
+    /// gray card gains, and records the gains on the session.
+    fn send_capture_target(&mut self, session: &storage::SessionManifest) {
+        let sku = self
+            .active_product
+            .as_ref()
+            .filter(|p| p.product_id == session.product_id)
+            .map_or_else(|| session.product_id.clone(), |p| p.sku_alias.clone());
+        let white_balance = self.config.white_balance.gains;
+        let target = crate::util::fs::CaptureTarget {
+            dir: storage::session_frames_dir(&self.captures_dir, &session.session_id),
+            sku,
+            session_id: session.session_id.clone(),
+            files: self.config.capture_files.clone(),
+            white_balance,
+        };
+        self.pending_commands
+            .push(AppCommand::Capture(CaptureCommand::SetOutputDir(target)));
+        if session.white_balance != white_balance {
+            self.pending_commands.push(AppCommand::Storage(
+                StorageCommand::SetSessionWhiteBalance {
+                    session_id: session.session_id.clone(),
+                    gains: white_balance,
+                },
+            ));
+        }
+    }
+
+    /// Reads gray card gains from the current camera frame (or the previewed
+    /// image), saves them to the config file and applies them to the
+    /// session's next captures.
+    fn calibrate_white_balance(&mut self) {
+        let frame = if self.capture_status.streaming {
+            self.latest_frame.get_latest().map(|(_, frame, _)| frame)
+        } else {
+            self.preview_image_path
+                .as_ref()
+                .and_then(|path| image::open(path).ok())
+                .map(|image| image.to_rgb8())
+        };
+        let Some(frame) = frame else {
+            self.toast(t!("tui.toast.white_balance_needs_frame"), Severity::Warning);
+            return;
+        };
+        // Calibrate from the raw frame, not one corrected with older gains.
+        let gains = match self.config.white_balance.calibrate(
+            frame.width() as usize,
+            frame.height() as usize,
+            frame.as_raw(),
+        ) {
+            Ok(gains) => gains,
+            Err(err) => {
+                self.toast(err.to_string(), Severity::Warning);
+                return;
+            }
+        };
+        let mut cfg = match talaria_core::config::Config::load() {
+            Ok(cfg) => cfg,
+            Err(err) => {
+                self.toast(
+                    t!("tui.toast.config_load_failed", error = err),
+                    Severity::Error,
+                );
+                return;
+            }
+        };
+        cfg.white_balance.gains = Some(gains);
+        if let Err(err) = cfg.save() {
+            self.toast(
+                t!("tui.toast.config_save_failed", error = err),
+                Severity::Error,
+            );
+            return;
+        }
+        self.config_mtime = config_file_mtime();
+        self.config.white_balance.gains = Some(gains);
+        if let Some(session) = self.active_session.clone() {
+            self.send_capture_target(&session);
+        }
+        self.toast(
+            t!(
+                "tui.toast.white_balance_set",
+                r = format!("{:.2}", gains[0]),
+                g = format!("{:.2}", gains[1]),
+                b = format!("{:.2}", gains[2])
+            ),
+            Severity::Success,
+        );
+    }
+
+    /// Reads the pixels-per-unit calibration from the current camera frame
+    /// (or the previewed image) and saves it to the config file.
+    fn calibrate_measurement(&mut self) {
+        let frame = if self.capture_status.streaming {
+            self.latest_frame
+                .get_latest()
+                .map(|(_, frame, _)| DynamicImage::ImageRgb8(frame))
+        } else {
+            self.preview_image_path
+                .as_ref()
+                .and_then(|path| image::open(path).ok())
+        };
+        let Some(frame) = frame else {
+            self.toast(t!("tui.toast.calibrate_needs_frame"), Severity::Warning);
+            return;
+        };
+        let luma = frame.to_luma8();
+        let measurement = &self.config.measurement;
+        let pixels_per_unit = match measurement.calibrate(
+            luma.width() as usize,
+            luma.height() as usize,
+            luma.as_raw(),
+        ) {
+            Ok(Some(value)) => value,
+            Ok(None) => {
+                let reference = if measurement.marker_size.is_some() {
+                    "marker"
+                } else {
+                    "grid"
+                };
+                self.toast(
+                    t!("tui.toast.no_reference", reference = reference),
+                    Severity::Warning,
+                );
+                return;
+            }
+            Err(err) => {
+                self.toast(err.to_string(), Severity::Warning);
+                return;
+            }
+        };
+        let mut cfg = match talaria_core::config::Config::load() {
+            Ok(cfg) => cfg,
+            Err(err) => {
+                self.toast(
+                    t!("tui.toast.config_load_failed", error = err),
+                    Severity::Error,
+                );
+                return;
+            }
+        };
+        cfg.measurement.pixels_per_unit = Some(pixels_per_unit);
+        if let Err(err) = cfg.save() {
+            self.toast(
+                t!("tui.toast.config_save_failed", error = err),
+                Severity::Error,
+            );
+            return;
+        }
+        self.config_mtime = config_file_mtime();
+        self.config.measurement.pixels_per_unit = Some(pixels_per_unit);
+        self.toast(
+            t!(
+                "tui.toast.calibrated",
+                pixels = format!("{pixels_per_unit:.1}"),
+                unit = self.config.measurement.unit.label()
+            ),
+            Severity::Success,
+        );
+    }
+
+    /// Records the box as the product's width × height (front view) or
+    /// depth × height (side view), or clears all measured dimensions.
+    fn record_measurement(&mut self, command_tx: &Sender<AppCommand>, keep: bool) {
+        let Some(product) = &self.active_product else {
+            self.measure.open = false;
+            return;
+        };
+        let measured = if keep {
+            if self.config.measurement.pixels_per_unit.is_none() {
+                self.toast(t!("tui.toast.calibrate_first"), Severity::Warning);
+                return;
+            }
+            let unit = self.config.measurement.unit;
+            let mut measured = product
+                .measured
+                .clone()
+                .filter(|m| m.unit == unit)
+                .unwrap_or(MeasuredDimensions {
+                    unit,
+                    width: None,
+                    height: None,
+                    depth: None,
+                    measured_at: chrono::Utc::now(),
+                });
+            if self.measure.side_view {
+                measured.depth = Some(self.measure.across);
+            } else {
+                measured.width = Some(self.measure.across);
+            }
+            measured.height = Some(self.measure.tall);
+            measured.measured_at = chrono::Utc::now();
+            Some(measured)
+        } else {
+            None
+        };
+        let message = match &measured {
+            Some(measured) if measured.depth.is_none() => {
+                t!("tui.toast.measured_no_depth", summary = measured.summary())
+            }
+            Some(measured) => t!("tui.toast.measured", summary = measured.summary()),
+            None => t!("tui.toast.measurements_cleared", sku = product.sku_alias),
+        };
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::SetProductMeasurement {
+            product_id: product.product_id.clone(),
+            measured,
+        }));
+        self.toast(message, Severity::Success);
+    }
+
+    fn open_grading(&mut self) {
+        let Some(product) = &self.active_product else {
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
+            return;
+        };
+        let grades = if self.config.condition_grades.is_empty() {
+            grading::default_rubric()
+        } else {
+            self.config.condition_grades.clone()
+        };
+        let existing = product.grading.as_ref();
+        self.grading = GradingState {
+            open: true,
+            selected: existing
+                .and_then(|g| grades.iter().position(|grade| grade.id == g.grade))
+                .unwrap_or(0),
+            grades,
+            field: GradingField::Grade,
+            notes: existing.and_then(|g| g.notes.clone()).unwrap_or_default(),
+            defects: existing.map(|g| g.defects.clone()).unwrap_or_default(),
+        };
+        self.fit_defect_slots();
+    }
+
+    /// Matches the defect rows to the selected grade's slot count, keeping
+    /// any rows that already have a note.
+    fn fit_defect_slots(&mut self) {
+        let slots = self
+            .grading
+            .grades
+            .get(self.grading.selected)
+            .map_or(0, |grade| grade.defect_slots);
+        let defects = &mut self.grading.defects;
+        while defects.len() > slots && defects.last().is_some_and(|d| d.note.is_empty()) {
+            defects.pop();
+        }
+        while defects.len() < slots {
+            defects.push(DefectNote {
+                note: String::new(),
+                photo: None,
+            });
+        }
+        if let GradingField::Defect(idx) = self.grading.field
+            && idx >= defects.len()
+        {
+            self.grading.field = GradingField::Grade;
+        }
+    }
+
+    fn handle_grading_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        let field = self.grading.field;
+        let defect_count = self.grading.defects.len();
+        match key.code {
+            KeyCode::Esc => {
+                self.grading.open = false;
+            }
+            KeyCode::Up => {
+                self.grading.field = match field {
+                    GradingField::Grade | GradingField::Notes => GradingField::Grade,
+                    GradingField::Defect(0) => GradingField::Notes,
+                    GradingField::Defect(idx) => GradingField::Defect(idx - 1),
+                };
+            }
+            KeyCode::Down => {
+                self.grading.field = match field {
+                    GradingField::Grade => GradingField::Notes,
+                    GradingField::Notes if defect_count > 0 => GradingField::Defect(0),
+                    GradingField::Defect(idx) if idx + 1 < defect_count => {
+                        GradingField::Defect(idx + 1)
+                    }
+                    other => other,
+                };
+            }
+            KeyCode::Left | KeyCode::Right if field == GradingField::Grade => {
+                let count = self.grading.grades.len().max(1);
+                self.grading.selected = if key.code == KeyCode::Left {
+                    (self.grading.selected + count - 1) % count
+                } else {
+                    (self.grading.selected + 1) % count
+                };
+                self.fit_defect_slots();
+            }
+            KeyCode::Tab => {
+                if let GradingField::Defect(idx) = field {
+                    self.cycle_defect_photo(idx);
+                }
+            }
+            KeyCode::Delete if field == GradingField::Grade => {
+                self.save_grading(command_tx, false);
+            }
+            KeyCode::Enter => {
+                self.save_grading(command_tx, true);
+            }
+            KeyCode::Backspace => match field {
+                GradingField::Grade => {}
+                GradingField::Notes => {
+                    self.grading.notes.pop();
+                }
+                GradingField::Defect(idx) => {
+                    if let Some(defect) = self.grading.defects.get_mut(idx) {
+                        defect.note.pop();
+                    }
+                }
+            },
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => match field {
+                GradingField::Grade => {}
+                GradingField::Notes => self.grading.notes.push(c),
+                GradingField::Defect(idx) => {
+                    if let Some(defect) = self.grading.defects.get_mut(idx) {
+                        defect.note.push(c);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Steps a defect's photo through the product images, then back to none.
+    fn cycle_defect_photo(&mut self, idx: usize) {
+        let Some(product) = &self.active_product else {
+            return;
+        };
+        let Some(defect) = self.grading.defects.get_mut(idx) else {
+            return;
+        };
+        let next = match &defect.photo {
+            None => 0,
+            Some(current) => product
+                .images
+                .iter()
+                .position(|image| &image.rel_path == current)
+                .map_or(0, |pos| pos + 1),
+        };
+        defect.photo = product.images.get(next).map(|image| image.rel_path.clone());
+    }
+
+    fn save_grading(&mut self, command_tx: &Sender<AppCommand>, keep: bool) {
+        let Some(product) = &self.active_product else {
+            self.grading.open = false;
+            return;
+        };
+        let grading = if keep {
+            let Some(grade) = self.grading.grades.get(self.grading.selected) else {
+                return;
+            };
+            Some(ProductGrading::new(
+                grade,
+                Some(self.grading.notes.clone()),
+                self.grading.defects.clone(),
+            ))
+        } else {
+            None
+        };
+        let message = match &grading {
+            Some(grading) => t!(
+                "tui.toast.graded",
+                sku = product.sku_alias,
+                grade = grading.label
+            ),
+            None => t!("tui.toast.grading_cleared", sku = product.sku_alias),
+        };
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::SetProductGrading {
+            product_id: product.product_id.clone(),
+            grading,
+        }));
+        self.grading.open = false;
+        self.toast(message, Severity::Success);
+    }
+
+    fn handle_camera_picker_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        if self.camera_picker.url_input.is_some() {
+            self.handle_camera_url_key(key, command_tx);
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.camera_picker.open = false;
+            }
+            KeyCode::Up => {
+                if self.camera_picker.selected > 0 {
+                    self.camera_picker.selected -= 1;
+                }
+            }
+            KeyCode::Down => {
+                if self.camera_picker.selected + 1 < self.camera_picker.devices.len() {
+                    self.camera_picker.selected += 1;
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(device) = self.camera_picker.devices.get(self.camera_picker.selected) {
+                    let cmd = match &device.kind {
+                        camera::DeviceKind::Network(url) => {
+                            CaptureCommand::SetDeviceUrl(url.clone())
+                        }
+                        kind => {
+                            self.device_index = device.index;
+                            CaptureCommand::SetDevice {
+                                index: self.device_index,
+                                tethered: match kind {
+                                    camera::DeviceKind::Tethered(cam) => Some(cam.clone()),
+                                    _ => None,
+                                },
+                            }
+                        }
+                    };
+                    let _ = command_tx.send(AppCommand::Capture(cmd));
+                }
+                self.camera_picker.open = false;
+            }
+            KeyCode::Char('r') => {
+                self.refresh_camera_picker();
+            }
+            KeyCode::Char('u') if !self.config.simulate => {
+                self.camera_picker.url_input = Some(String::new());
+            }
+            _ => {}
+        }
+    }
+
+    /// Typing a network camera URL: Enter streams from it and remembers it
+    /// in the config so it is listed next time.
+    fn handle_camera_url_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        let Some(input) = self.camera_picker.url_input.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.camera_picker.url_input = None;
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+            }
+            KeyCode::Enter => {
+                let url = input.trim().to_string();
+                if url.is_empty() {
+                    self.camera_picker.url_input = None;
+                    return;
+                }
+                if !url.contains("://") {
+                    self.toast(t!("tui.toast.camera_url_scheme"), Severity::Warning);
+                    return;
+                }
+                if !self.config.camera_urls.contains(&url) {
+                    let saved = self.update_config(|cfg| {
+                        if !cfg.tui_camera_urls.contains(&url) {
+                            cfg.tui_camera_urls.push(url.clone());
+                        }
+                        Ok(())
+                    });
+                    if let Some(cfg) = saved {
+                        self.config.camera_urls = cfg.tui_camera_urls;
+                    }
+                }
+                let _ = command_tx.send(AppCommand::Capture(CaptureCommand::SetDeviceUrl(url)));
+                self.camera_picker.url_input = None;
+                self.camera_picker.open = false;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn filtered_products(&self) -> Vec<storage::ProductSummary> {
+        self.filtered_products_iter().cloned().collect()
+    }
+
+    pub fn filtered_products_iter(&self) -> impl Iterator<Item = &storage::ProductSummary> {
+        let q = self.picker.search.to_lowercase();
+        self.picker
+            .products
+            .iter()
+            .filter(move |p| {
+                q.is_empty()
+                    || p.sku_alias.to_lowercase().contains(&q)
+                    || p.display_name
+                        .as_ref()
+                        .map(|d| d.to_lowercase().contains(&q))
+                        .unwrap_or(false)
+            })
+            .filter(|p| self.picker.status_filter.matches(p))
+            .filter(|p| self.picker.date_filter.matches(p))
+            .filter(|p| {
+                self.picker.marketplace_filter.as_ref().is_none_or(|m| {
+                    p.marketplace_statuses
+                        .iter()
+                        .any(|status| &status.marketplace == m)
+                })
+            })
+    }
+
+    /// Drops marks and thumbnails of products that are gone.
+    fn finish_products_listing(&mut self) {
+        self.products_loading = false;
+        self.products_listed_at = Some(Instant::now());
+        let listed: HashSet<&str> = self
+            .picker
+            .products
+            .iter()
+            .map(|p| p.product_id.as_str())
+            .collect();
+        self.product_marks.retain(|id| listed.contains(id.as_str()));
+        self.product_thumbnails
+            .retain(|id, _| listed.contains(id.as_str()));
+        self.product_thumbnail_failures
+            .retain(|id, _| listed.contains(id.as_str()));
+    }
+
+    /// Selects `keep` in the grid, else the active product, else the first.
+    fn place_grid_selection(&mut self, keep: Option<String>) {
+        let active = self.active_product.as_ref().map(|p| &p.product_id);
+        let position = |id: &String| {
+            self.filtered_products_iter()
+                .position(|p| &p.product_id == id)
+        };
+        self.product_grid_selected = keep
+            .as_ref()
+            .and_then(position)
+            .or_else(|| active.and_then(position))
+            .unwrap_or(0);
+    }
+
+    /// True when any search text or filter narrows the product list.
+    pub fn product_filters_active(&self) -> bool {
+        !self.picker.search.is_empty()
+            || self.picker.status_filter != ProductStatusFilter::All
+            || self.picker.marketplace_filter.is_some()
+            || self.picker.date_filter != ProductDateFilter::Any
+    }
+
+    fn handle_product_search_keys(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.picker.search.clear();
+                self.product_search_editing = false;
+                self.on_product_filter_changed();
+            }
+            KeyCode::Enter => {
+                self.product_search_editing = false;
+            }
+            KeyCode::Backspace => {
+                self.picker.search.pop();
+                self.on_product_filter_changed();
+            }
+            KeyCode::Char(c) => {
+                if !c.is_control() {
+                    self.picker.search.push(c);
+                    self.on_product_filter_changed();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Steps through "all marketplaces" and each marketplace seen in the
+    /// listed products.
+    fn cycle_marketplace_filter(&mut self) {
+        let marketplaces = self
+            .picker
+            .products
+            .iter()
+            .flat_map(|p| p.marketplace_statuses.iter().map(|s| s.marketplace.clone()))
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        self.picker.marketplace_filter = match &self.picker.marketplace_filter {
+            None => marketplaces.first().cloned(),
+            Some(current) => marketplaces
+                .iter()
+                .position(|m| m == current)
+                .and_then(|idx| marketplaces.get(idx + 1))
+                .cloned(),
+        };
+    }
+
+    fn undo_last_delete(&mut self, command_tx: &Sender<AppCommand>) {
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::RestoreLatestTrash));
+        self.toast(t!("tui.toast.restoring_last"), Severity::Info);
+    }
+
+    fn handle_trash_view_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        match key.code {
+            KeyCode::Esc => {
+                self.trash_view.open = false;
+            }
+            KeyCode::Up => {
+                self.trash_view.selected = self.trash_view.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                if self.trash_view.selected + 1 < self.trash_view.items.len() {
+                    self.trash_view.selected += 1;
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('u') => {
+                if let Some(item) = self.trash_view.items.get(self.trash_view.selected) {
+                    let _ = command_tx.send(AppCommand::Storage(StorageCommand::RestoreTrash {
+                        item: item.clone(),
+                    }));
+                    self.toast(
+                        t!("tui.toast.restoring", item = item.describe()),
+                        Severity::Info,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_failed_jobs_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        if let Some(buffer) = self.failed_jobs.overrides.as_mut() {
+            if is_save_edit_key(key) {
+                match serde_json::from_str::<PublicPipelineOverrides>(buffer) {
+                    Ok(overrides) => {
+                        self.failed_jobs.overrides = None;
+                        self.retry_failed_job(Some(overrides), command_tx);
+                    }
+                    Err(err) => self.toast(
+                        t!("tui.toast.invalid_overrides", error = err),
+                        Severity::Error,
+                    ),
+                }
+                return;
+            }
+            match key.code {
+                KeyCode::Esc => {
+                    self.failed_jobs.overrides = None;
+                    self.toast(t!("tui.toast.edit_canceled"), Severity::Info);
+                }
+                KeyCode::Enter => buffer.push('\n'),
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.failed_jobs.open = false;
+            }
+            KeyCode::Up => {
+                self.failed_jobs.selected = self.failed_jobs.selected.saturating_sub(1);
+            }
+            KeyCode::Down if self.failed_jobs.selected + 1 < self.failed_jobs.jobs.len() => {
+                self.failed_jobs.selected += 1;
+            }
+            KeyCode::Enter | KeyCode::Char('r') => self.retry_failed_job(None, command_tx),
+            KeyCode::Char('y') => {
+                if let Some(job) = self.failed_jobs.jobs.get(self.failed_jobs.selected) {
+                    let job_id = job.job_id.clone();
+                    self.copy_to_clipboard(&t!("tui.copy.job_id"), &job_id);
+                }
+            }
+            KeyCode::Char('e') => {
+                let Some(job) = self.failed_jobs.jobs.get(self.failed_jobs.selected) else {
+                    return;
+                };
+                let overrides = job
+                    .request
+                    .as_ref()
+                    .and_then(|req| req.overrides.as_ref())
+                    .and_then(|overrides| serde_json::to_string_pretty(overrides).ok())
+                    .unwrap_or_else(|| "{}".to_string());
+                self.failed_jobs.overrides = Some(overrides);
+                self.toast(t!("tui.toast.editing_overrides"), Severity::Info);
+            }
+            KeyCode::Char('d') => {
+                if let Some(job) = self.failed_jobs.jobs.get(self.failed_jobs.selected) {
+                    let _ =
+                        command_tx.send(AppCommand::Storage(StorageCommand::DismissFailedJob {
+                            job_id: job.job_id.clone(),
+                        }));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn open_media_view(&mut self, command_tx: &Sender<AppCommand>) {
+        let Some(product) = &self.active_product else {
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
+            return;
+        };
+        if !self.config.hermes_api_key_present && !self.config.simulate {
+            self.toast(t!("tui.toast.gallery_needs_sign_in"), Severity::Warning);
+            return;
+        }
+        let product_id = product.product_id.clone();
+        self.media_view = MediaViewState {
+            open: true,
+            product_id: product_id.clone(),
+            loading: true,
+            ..Default::default()
+        };
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::ListProductMedia {
+            product_id,
+        }));
+    }
+
+    fn handle_media_view_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        let view = &mut self.media_view;
+        let selected = view.items.get(view.selected);
+        let media_id = selected
+            .and_then(|item| item.remote.as_ref())
+            .map(|media| media.media_id.clone());
+        let product_id = view.product_id.clone();
+
+        if let Some(buffer) = view.rank_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => view.rank_input = None,
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() => buffer.push(c),
+                KeyCode::Enter => {
+                    let Ok(rank) = buffer.parse::<i32>() else {
+                        self.toast(t!("tui.toast.rank_not_number"), Severity::Error);
+                        return;
+                    };
+                    view.rank_input = None;
+                    if let Some(media_id) = media_id {
+                        view.loading = true;
+                        let _ = command_tx.send(AppCommand::Storage(
+                            StorageCommand::UpdateRemoteMedia {
+                                product_id,
+                                media_id,
+                                purpose: None,
+                                rank: Some(rank),
+                            },
+                        ));
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        let armed = view.delete_armed.take();
+        let remote_orphan = selected.is_some_and(|item| item.remote_orphan());
+        let current_rank = selected
+            .and_then(|item| item.remote.as_ref())
+            .and_then(|media| media.rank);
+        let missing = view
+            .items
+            .iter()
+            .filter(|item| item.local_orphan() || item.by_hash)
+            .count();
+        match key.code {
+            KeyCode::Esc => view.open = false,
+            KeyCode::Up => view.selected = view.selected.saturating_sub(1),
+            KeyCode::Down if view.selected + 1 < view.items.len() => view.selected += 1,
+            KeyCode::Char('g') => {
+                view.loading = true;
+                let _ = command_tx.send(AppCommand::Storage(StorageCommand::ListProductMedia {
+                    product_id,
+                }));
+            }
+            KeyCode::Char('u') if missing == 0 => {
+                self.toast(t!("tui.toast.all_images_uploaded"), Severity::Info);
+            }
+            KeyCode::Char('u') => {
+                view.loading = true;
+                let _ = command_tx.send(AppCommand::Storage(StorageCommand::UploadMissingMedia {
+                    product_id,
+                }));
+            }
+            KeyCode::Char('d') if !remote_orphan => {
+                self.toast(t!("tui.toast.delete_remote_only"), Severity::Warning)
+            }
+            KeyCode::Char('d') if self.config.mode == AccessMode::CaptureOnly => {
+                self.permitted("deleting Hermes media");
+            }
+            KeyCode::Char('d') => {
+                let Some(media_id) = media_id else {
+                    return;
+                };
+                if armed.as_deref() == Some(media_id.as_str()) {
+                    view.loading = true;
+                    let _ =
+                        command_tx.send(AppCommand::Storage(StorageCommand::DeleteRemoteMedia {
+                            product_id,
+                            media_id,
+                        }));
+                } else {
+                    view.delete_armed = Some(media_id.clone());
+                    self.toast(
+                        t!("tui.toast.confirm_media_delete", media_id = media_id),
+                        Severity::Warning,
+                    );
+                }
+            }
+            KeyCode::Char('h') | KeyCode::Char('r') if media_id.is_none() => {
+                self.toast(t!("tui.toast.not_on_hermes"), Severity::Warning)
+            }
+            KeyCode::Char('h') => {
+                view.loading = true;
+                let _ = command_tx.send(AppCommand::Storage(StorageCommand::UpdateRemoteMedia {
+                    product_id,
+                    media_id: media_id.unwrap_or_default(),
+                    purpose: Some(talaria_core::models::MediaPurpose::Hero),
+                    rank: None,
+                }));
+            }
+            KeyCode::Char('r') => {
+                view.rank_input = Some(
+                    current_rank
+                        .map(|rank| rank.to_string())
+                        .unwrap_or_default(),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn retry_failed_job(
+        &mut self,
+        overrides: Option<PublicPipelineOverrides>,
+        command_tx: &Sender<AppCommand>,
+    ) {
+        if !self.permitted("retrying listing jobs") {
+            return;
+        }
+        let Some(job) = self.failed_jobs.jobs.get(self.failed_jobs.selected) else {
+            return;
+        };
+        if !self.config.hermes_api_key_present {
+            self.toast(t!("tui.toast.retry_needs_sign_in"), Severity::Warning);
+            return;
+        }
+        let job_id = job.job_id.clone();
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::RetryListingJob {
+            job_id: job_id.clone(),
+            overrides,
+        }));
+        self.toast(
+            t!("tui.toast.retrying_job", job_id = job_id),
+            Severity::Info,
+        );
+    }
+
+    fn handle_resume_prompt_key(&mut self, key: KeyEvent, command_tx: &Sender<AppCommand>) {
+        match key.code {
+            KeyCode::Esc => {
+                self.resume_prompt.open = false;
+            }
+            KeyCode::Up => {
+                self.resume_prompt.selected = self.resume_prompt.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                if self.resume_prompt.selected + 1 < self.resume_prompt.sessions.len() {
+                    self.resume_prompt.selected += 1;
+                }
+            }
+            KeyCode::Enter => {
+                let Some(resumable) = self
+                    .resume_prompt
+                    .sessions
+                    .get(self.resume_prompt.selected)
+                    .cloned()
+                else {
+                    return;
+                };
+                self.resume_prompt.open = false;
+                self.pending_product_selection = Some(resumable.session.product_id.clone());
+                let _ = command_tx.send(AppCommand::Storage(StorageCommand::ResumeSession {
+                    session_id: resumable.session.session_id,
+                }));
+                self.toast(
+                    t!("tui.toast.resuming_session", sku = resumable.sku_alias),
+                    Severity::Info,
+                );
+            }
+            KeyCode::Char('d') => {
+                let idx = self.resume_prompt.selected;
+                if idx >= self.resume_prompt.sessions.len() {
+                    return;
+                }
+                let resumable = self.resume_prompt.sessions.remove(idx);
+                let _ = command_tx.send(AppCommand::Storage(StorageCommand::AbandonSession {
+                    session_id: resumable.session.session_id,
+                }));
+                if self.resume_prompt.sessions.is_empty() {
+                    self.resume_prompt.open = false;
+                } else {
+                    self.resume_prompt.selected =
+                        idx.min(self.resume_prompt.sessions.len().saturating_sub(1));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn marked_products(&self) -> Vec<storage::ProductSummary> {
+        self.picker
+            .products
+            .iter()
+            .filter(|p| self.product_marks.contains(&p.product_id))
+            .cloned()
+            .collect()
+    }
+
+    fn bulk_upload(&mut self, command_tx: &Sender<AppCommand>) {
+        let marked = self.marked_products();
+        if marked.is_empty() {
+            self.toast(t!("tui.toast.mark_products_first"), Severity::Warning);
+            return;
+        }
+        for product in &marked {
+            let _ = command_tx.send(AppCommand::Upload(UploadCommand::UploadProduct {
+                product_id: product.product_id.clone(),
+            }));
+        }
+        self.toast(
+            t!("tui.toast.bulk_uploads_queued", count = marked.len()),
+            Severity::Info,
+        );
+    }
+
+    fn start_bulk(&mut self, action: BulkAction, command_tx: &Sender<AppCommand>) {
+        let restricted = match action {
+            BulkAction::Enrich => Some("enriching products"),
+            BulkAction::Delete => Some("deleting products"),
+            BulkAction::Export => None,
+        };
+        if restricted.is_some_and(|what| !self.permitted(what)) {
+            return;
+        }
+        let marked = self.marked_products();
+        if marked.is_empty() {
+            self.toast(t!("tui.toast.mark_products_first"), Severity::Warning);
+            return;
+        }
+        let product_ids = marked
+            .iter()
+            .map(|p| p.product_id.clone())
+            .collect::<Vec<_>>();
+        if action == BulkAction::Delete {
+            let active_block = self
+                .active_session
+                .as_ref()
+                .is_some_and(|s| s.committed_at.is_none() && product_ids.contains(&s.product_id));
+            if active_block {
+                self.toast(t!("tui.toast.finish_session_first"), Severity::Warning);
+                return;
+            }
+            self.toast(
+                t!("tui.toast.confirm_bulk_delete", count = product_ids.len()),
+                Severity::Warning,
+            );
+            self.delete_confirm = Some(DeleteConfirm {
+                product_ids,
+                expires_at: Instant::now() + Duration::from_secs(6),
+            });
+            return;
+        }
+        if action == BulkAction::Enrich && !self.config.online_ready {
+            self.toast(t!("tui.toast.bulk_enrich_needs_hermes"), Severity::Warning);
+            return;
+        }
+        let count = product_ids.len();
+        let _ = command_tx.send(AppCommand::Storage(StorageCommand::BulkProducts {
+            action,
+            product_ids,
+            llm_ingest: self.llm_ingest.clone(),
+            prompt_rules: self.prompt_rules.clone(),
+        }));
+        self.toast(
+            t!(
+                "tui.toast.bulk_started",
+                action = action.label(),
+                count = count
+            ),
+            Severity::Info,
+        );
     }
 
-    pub fn filtered_products(&self) -> Vec<storage::ProductSummary> {
-        let q = self.picker.search.to_lowercase();
-        if q.is_empty() {
-            return self.picker.products.clone();
-        }
-        self.picker
-            .products
-            .iter()
-            .cloned()
-            .filter(|p| {
-                p.sku_alias.to_lowercase().contains(&q)
-                    || p.display_name
-                        .as_ref()
-                        .map(|d| d.to_lowercase().contains(&q))
-                        .unwrap_or(false)
-            })
-            .collect()
+    fn on_product_filter_changed(&mut self) {
+        self.picker.selected = 0;
+        self.product_grid_selected = 0;
     }
 
     fn apply_capture_event(&mut self, event: CaptureEvent) {
         match event {
             CaptureEvent::Status(status) => {
+                if status.countdown != self.capture_status.countdown
+                    && self.terminal_preview.is_none()
+                {
+                    self.pending_commands
+                        .push(AppCommand::Preview(PreviewCommand::SetCountdown(
+                            status.countdown,
+                        )));
+                }
+                if let (Some((_, total)), None) = (self.capture_status.turntable, status.turntable)
+                {
+                    self.record_activity(ActivityEntry {
+                        at: Local::now(),
+                        severity: Severity::Info,
+                        message: format!("Turntable run of {total} frames ended"),
+                    });
+                }
                 self.capture_status = status.clone();
                 self.device_index = status.device_index;
                 self.camera_connected = status.streaming || status.frame_size.is_some();
@@ -2389,16 +5234,14 @@ impl AppState {
                     message,
                 });
             }
-            CaptureEvent::CaptureCompleted {
+            CaptureEvent::CaptureCompleted(CapturedFrame {
                 path,
                 created_at,
                 sharpness_score,
-            } => {
+                tag,
+            }) => {
                 let Some(session) = self.active_session.clone() else {
-                    self.toast(
-                        "Captured frame but no active session.".to_string(),
-                        Severity::Warning,
-                    );
+                    self.toast(t!("tui.toast.captured_without_session"), Severity::Warning);
                     return;
                 };
                 let rel = self.make_session_rel(&session, Path::new(&path));
@@ -2406,7 +5249,10 @@ impl AppState {
                 self.record_activity(ActivityEntry {
                     at: Local::now(),
                     severity: Severity::Success,
-                    message: format!("Captured {}", rel),
+                    message: match &tag {
+                        Some(tag) => format!("Captured {rel} ({tag})"),
+                        None => format!("Captured {}", rel),
+                    },
                 });
 
                 self.pending_commands.push(AppCommand::Storage(
@@ -2415,8 +5261,17 @@ impl AppState {
                         frame_rel_path: rel,
                         created_at,
                         sharpness_score,
+                        tag,
                     },
                 ));
+                if barcode::SCAN_FILES_ENABLED {
+                    self.pending_commands
+                        .push(AppCommand::Storage(StorageCommand::ScanBarcodes {
+                            product_id: session.product_id,
+                            frames: vec![PathBuf::from(path)],
+                            report_misses: false,
+                        }));
+                }
             }
         }
     }
@@ -2424,23 +5279,114 @@ impl AppState {
     fn apply_storage_event(&mut self, event: StorageEvent) {
         match event {
             StorageEvent::ProductsListed(products) => {
-                self.products_loading = false;
                 self.picker.products = products;
+                self.finish_products_listing();
                 self.picker.selected = 0;
-                if let Some(active) = &self.active_product {
-                    if let Some(idx) = self
-                        .picker
-                        .products
-                        .iter()
-                        .position(|p| p.product_id == active.product_id)
-                    {
-                        self.product_grid_selected = idx;
-                    } else {
-                        self.product_grid_selected = 0;
-                    }
+                self.place_grid_selection(None);
+            }
+            StorageEvent::ProductsBatch {
+                products,
+                first,
+                done,
+            } => {
+                // Keep the selected product selected as batches re-sort the
+                // list under it.
+                let keep = if first {
+                    self.picker.selected = 0;
+                    self.picker.products.clear();
+                    None
                 } else {
-                    self.product_grid_selected = 0;
+                    self.filtered_products_iter()
+                        .nth(self.product_grid_selected)
+                        .map(|p| p.product_id.clone())
+                };
+                self.picker
+                    .products
+                    .retain(|p| !products.iter().any(|n| n.product_id == p.product_id));
+                self.picker.products.extend(products);
+                self.picker
+                    .products
+                    .sort_by_key(|p| std::cmp::Reverse(p.updated_at));
+                if done {
+                    self.finish_products_listing();
+                }
+                self.place_grid_selection(keep);
+            }
+            StorageEvent::BarcodeScanned {
+                product_id,
+                found,
+                report_misses,
+            } => {
+                let Some((barcode, frame)) = found else {
+                    if report_misses {
+                        self.toast(t!("tui.toast.no_barcode"), Severity::Info);
+                    }
+                    return;
+                };
+                let applied = self
+                    .active_product
+                    .as_ref()
+                    .filter(|p| p.product_id == product_id)
+                    .is_some_and(|p| {
+                        p.sku_alias == barcode.value
+                            && p.context_text
+                                .as_deref()
+                                .is_some_and(|t| t.contains(&barcode.value))
+                    });
+                if applied {
+                    if report_misses {
+                        self.toast(
+                            t!("tui.toast.barcode_is_sku", barcode = barcode.value),
+                            Severity::Info,
+                        );
+                    }
+                    return;
+                }
+                if !report_misses && self.barcode_dismissed.contains(&barcode.value) {
+                    return;
                 }
+                self.barcode_prompt = Some(BarcodePrompt {
+                    product_id,
+                    barcode,
+                    frame,
+                });
+            }
+            StorageEvent::PriceSuggested {
+                product_id,
+                marketplace_key,
+                suggestion,
+            } => {
+                let Some(suggestion) = suggestion else {
+                    self.toast(t!("tui.toast.no_comparable_prices"), Severity::Info);
+                    return;
+                };
+                self.price_prompt = Some(PricePrompt {
+                    product_id,
+                    marketplace_key,
+                    price: suggestion.suggested,
+                    suggestion,
+                });
+            }
+            StorageEvent::SyncConflict {
+                product_id,
+                sku_alias,
+                conflicts,
+            } => {
+                self.product_syncing = false;
+                self.record_activity(ActivityEntry {
+                    at: Local::now(),
+                    severity: Severity::Warning,
+                    message: format!(
+                        "Sync conflict on {sku_alias}: {} field(s) changed here and on Hermes.",
+                        conflicts.len()
+                    ),
+                });
+                self.sync_conflict = Some(SyncConflictPrompt {
+                    product_id,
+                    sku_alias,
+                    conflicts,
+                    selected: 0,
+                });
             }
             StorageEvent::ProductSelected(product) => {
                 let incoming_id = product.product_id.clone();
@@ -2570,24 +5516,36 @@ impl AppState {
                         {
                             self.pending_commands.push(AppCommand::Storage(cmd));
                             self.listing_inference = true;
-                            self.toast("Listing request queued.".to_string(), Severity::Info);
+                            self.toast(t!("tui.toast.listing_queued"), Severity::Info);
                         }
                     }
                 }
             }
             StorageEvent::SessionStarted(session) => {
-                let frames_dir =
-                    storage::session_frames_dir(&self.captures_dir, &session.session_id);
-                self.pending_commands
-                    .push(AppCommand::Capture(CaptureCommand::SetOutputDir(
-                        frames_dir,
-                    )));
+                self.send_capture_target(&session);
                 self.pending_commands
                     .push(AppCommand::Capture(CaptureCommand::StartStream));
                 self.preview_enabled = true;
                 self.pending_commands.push(AppCommand::Preview(
                     crate::types::PreviewCommand::SetEnabled(true),
                 ));
+                if session.template.is_none()
+                    && session.frames.is_empty()
+                    && let Some(template) = self.session_template.as_ref().and_then(|name| {
+                        self.config
+                            .session_templates
+                            .iter()
+                            .find(|t| &t.name == name)
+                    })
+                {
+                    self.pending_commands.push(AppCommand::Storage(
+                        StorageCommand::SetSessionTemplate {
+                            session_id: session.session_id.clone(),
+                            template: Some(template.clone()),
+                        },
+                    ));
+                }
+                self.shot_override = None;
                 self.active_session = Some(session);
                 self.session_frame_selected = 0;
                 self.context_focus = ContextFocus::Images;
@@ -2596,6 +5554,15 @@ impl AppState {
                 self.products_mode = ProductsMode::Workspace;
                 self.products_subtab = ProductsSubTab::Context;
             }
+            StorageEvent::ResumableSessions(sessions) => {
+                if self.active_session.is_none() {
+                    self.resume_prompt = ResumePromptState {
+                        open: true,
+                        selected: 0,
+                        sessions,
+                    };
+                }
+            }
             StorageEvent::SessionUpdated(session) => {
                 self.active_session = Some(session);
                 let count = self.context_image_count();
@@ -2613,10 +5580,11 @@ impl AppState {
                 committed_count,
             } => {
                 self.active_product = Some(product.clone());
-                self.active_session = Some(session);
-                let mut commit_message = format!(
-                    "Committed {} image(s) to {}",
-                    committed_count, product.sku_alias
+                self.active_session = Some(*session);
+                let mut commit_message = t!(
+                    "tui.toast.committed",
+                    count = committed_count,
+                    sku = product.sku_alias
                 );
                 if committed_count > 0 {
                     self.products_subtab = ProductsSubTab::Listings;
@@ -2626,9 +5594,9 @@ impl AppState {
                                 product_id: product.product_id.clone(),
                             },
                         ));
-                        commit_message.push_str(" (upload queued)");
+                        commit_message.push_str(&t!("tui.toast.upload_queued"));
                     } else {
-                        commit_message.push_str(" (upload ready via 'u')");
+                        commit_message.push_str(&t!("tui.toast.upload_ready"));
                     }
                 }
                 self.last_commit_message = Some(commit_message);
@@ -2681,12 +5649,105 @@ impl AppState {
                 self.listings_field_list_offset = 0;
                 self.context_focus = ContextFocus::Images;
                 self.queue_image_preview();
-                let mut message = "Product deleted.".to_string();
+                let mut message = t!("tui.toast.trashed");
                 if removed_sessions > 0 {
-                    message.push_str(&format!(" ({removed_sessions} session(s) removed)"));
+                    message.push_str(&t!("tui.toast.sessions_removed", count = removed_sessions));
                 }
+                message.push_str(&t!("tui.toast.undo_hint"));
                 self.toast(message, Severity::Success);
             }
+            StorageEvent::TrashListed(items) => {
+                self.trash_view.items = items;
+                self.trash_view.selected = self
+                    .trash_view
+                    .selected
+                    .min(self.trash_view.items.len().saturating_sub(1));
+            }
+            StorageEvent::JobCanceled(key) => {
+                let current = self
+                    .active_product
+                    .as_ref()
+                    .is_some_and(|p| p.product_id == key.product_id);
+                if current {
+                    match key.kind {
+                        JobKind::Structure => self.structure_inference = false,
+                        JobKind::Listing => self.listing_inference = false,
+                        JobKind::Upload => {}
+                    }
+                }
+            }
+            StorageEvent::FailedJobsListed(jobs) => {
+                self.failed_jobs.jobs = jobs;
+                self.failed_jobs.selected = self
+                    .failed_jobs
+                    .selected
+                    .min(self.failed_jobs.jobs.len().saturating_sub(1));
+            }
+            StorageEvent::StatsLoaded(stats) => self.stats = Some(stats),
+            StorageEvent::MediaListed { product_id, items } => {
+                if self.media_view.product_id == product_id {
+                    self.media_view.items = items;
+                    self.media_view.loading = false;
+                    self.media_view.selected = self
+                        .media_view
+                        .selected
+                        .min(self.media_view.items.len().saturating_sub(1));
+                }
+            }
+            StorageEvent::MediaUploadReady { product_id } => {
+                self.pending_commands
+                    .push(AppCommand::Upload(UploadCommand::UploadProduct {
+                        product_id,
+                    }));
+                self.toast(t!("tui.toast.uploading_missing"), Severity::Info);
+            }
+            StorageEvent::TrashRestored(description) => {
+                self.toast(
+                    t!("tui.toast.restored", description = description),
+                    Severity::Success,
+                );
+            }
+            StorageEvent::BulkProgress {
+                action,
+                done,
+                total,
+                failed,
+            } => {
+                let mut message = t!(
+                    "tui.toast.bulk_progress",
+                    action = action.label(),
+                    done = done,
+                    total = total
+                );
+                if failed > 0 {
+                    message.push_str(&t!("tui.toast.bulk_progress_failed", count = failed));
+                }
+                self.toast(message, Severity::Info);
+            }
+            StorageEvent::BulkFinished {
+                action,
+                succeeded,
+                failed,
+                detail,
+            } => {
+                let mut message = t!(
+                    "tui.toast.bulk_done",
+                    action = action.label(),
+                    count = succeeded
+                );
+                if failed > 0 {
+                    message.push_str(&t!("tui.toast.bulk_done_failed", count = failed));
+                }
+                if let Some(detail) = &detail {
+                    message.push_str(&t!("tui.toast.bulk_detail", detail = detail));
+                }
+                let severity = if failed > 0 {
+                    Severity::Warning
+                } else {
+                    Severity::Success
+                };
+                self.toast(message, severity);
+            }
             StorageEvent::SessionAbandoned {
                 session_id,
                 moved_to,
@@ -2703,10 +5764,16 @@ impl AppState {
                     .push(AppCommand::Capture(CaptureCommand::ClearOutputDir));
                 self.queue_image_preview();
                 self.toast(
-                    format!("Session abandoned → {}", moved_to),
+                    t!("tui.toast.session_abandoned", path = moved_to),
                     Severity::Warning,
                 );
             }
+            StorageEvent::ListingJobQueued { product_id, job_id } => {
+                self.listing_job_ids.insert(product_id, job_id);
+            }
+            StorageEvent::FailedRequest(request_id) => {
+                self.last_request_id = Some(request_id);
+            }
             StorageEvent::Error(message) => {
                 self.last_error = Some(message.clone());
                 self.pending_post_save_notice = None;
@@ -2715,6 +5782,7 @@ impl AppState {
                 self.product_syncing = false;
                 self.structure_inference = false;
                 self.listing_inference = false;
+                self.media_view.loading = false;
                 self.toast(message, Severity::Error);
             }
         }
@@ -2727,7 +5795,10 @@ impl AppState {
             self.uploads.push(job.clone());
         }
         if job.status == JobStatus::Completed {
-            self.toast("Upload completed.".to_string(), Severity::Success);
+            self.toast(t!("tui.toast.upload_completed"), Severity::Success);
+        }
+        if job.status == JobStatus::Canceled {
+            self.toast(t!("tui.toast.upload_canceled"), Severity::Info);
         }
         if job.status == JobStatus::Failed {
             if let Some(err) = &job.last_error {
@@ -2736,6 +5807,48 @@ impl AppState {
         }
     }
 
+    /// Logs connection transitions and pauses or resumes the upload and
+    /// storage workers around offline spells.
+    fn apply_health_report(&mut self, report: HealthReport) {
+        let previous = self.connection.state;
+        let state = report.state;
+        let latency_ms = report.latency.map(|l| l.as_millis()).unwrap_or_default();
+        let detail = report.detail.clone().unwrap_or_default();
+        self.connection = report;
+        if previous == state {
+            return;
+        }
+        match (previous, state) {
+            (_, ConnectionState::Offline) => self.toast(
+                t!("tui.toast.hermes_unreachable", detail = detail),
+                Severity::Warning,
+            ),
+            (ConnectionState::Offline, ConnectionState::Online | ConnectionState::Degraded) => {
+                self.toast(t!("tui.toast.hermes_back"), Severity::Success)
+            }
+            (_, ConnectionState::Degraded) => self.record_activity(ActivityEntry {
+                at: Local::now(),
+                severity: Severity::Warning,
+                message: format!("Hermes degraded: {detail} ({latency_ms} ms)."),
+            }),
+            (ConnectionState::Degraded, ConnectionState::Online) => {
+                self.record_activity(ActivityEntry {
+                    at: Local::now(),
+                    severity: Severity::Info,
+                    message: format!("Hermes healthy again ({latency_ms} ms)."),
+                })
+            }
+            _ => {}
+        }
+        let offline = state == ConnectionState::Offline;
+        if offline != (previous == ConnectionState::Offline) {
+            self.pending_commands
+                .push(AppCommand::Upload(UploadCommand::SetOnline(!offline)));
+            self.pending_commands
+                .push(AppCommand::Storage(StorageCommand::SetOnline(!offline)));
+        }
+    }
+
     fn apply_account_event(&mut self, event: AccountEvent) {
         self.credits_loading = false;
         match event {
@@ -2764,14 +5877,124 @@ impl AppState {
         format!("frames/{filename}")
     }
 
+    /// Copies `text` and confirms what was copied; `what` names it, e.g.
+    /// "listing id". Multi-line text is not repeated in the toast.
+    fn copy_to_clipboard(&mut self, what: &str, text: &str) {
+        match self.clipboard.copy(text) {
+            Ok(()) if text.contains('\n') => {
+                self.toast(t!("tui.toast.copied", what = what), Severity::Success)
+            }
+            Ok(()) => self.toast(
+                t!("tui.toast.copied_text", what = what, text = text),
+                Severity::Success,
+            ),
+            Err(err) => self.toast(t!("tui.toast.copy_failed", error = err), Severity::Error),
+        }
+    }
+
+    /// Opens `url` in the default browser, toasting where it went.
+    fn open_in_browser(&mut self, url: &str) {
+        match browser::open(url) {
+            Ok(()) => self.toast(t!("tui.toast.opened", url = url), Severity::Success),
+            Err(err) => self.toast(
+                t!("tui.toast.open_failed", error = err, url = url),
+                Severity::Error,
+            ),
+        }
+    }
+
+    /// Opens the selected listing's live item page on its marketplace.
+    fn open_selected_listing(&mut self) {
+        let Some(listing_id) = self
+            .selected_listing()
+            .and_then(|listing| listing.listing_id)
+        else {
+            self.toast(t!("tui.toast.not_published"), Severity::Info);
+            return;
+        };
+        let marketplace =
+            selected_marketplace(self.selected_listing_key().as_deref(), &self.ebay_settings);
+        match browser::listing_url(&marketplace, &listing_id) {
+            Some(url) => self.open_in_browser(&url),
+            None => self.toast(
+                t!("tui.toast.no_item_page", marketplace = marketplace),
+                Severity::Warning,
+            ),
+        }
+    }
+
+    /// Opens the uploaded copy of the selected product image.
+    fn open_selected_image(&mut self) {
+        let entry = self
+            .context_image_entries()
+            .get(self.session_frame_selected)
+            .cloned();
+        let url = match entry {
+            Some(ContextImageEntry::Product { rel_path, .. }) => {
+                self.active_product.as_ref().and_then(|product| {
+                    product
+                        .images
+                        .iter()
+                        .find(|image| image.rel_path == rel_path)
+                        .and_then(|image| image.uploaded_url.clone())
+                })
+            }
+            _ => None,
+        };
+        match url {
+            Some(url) => self.open_in_browser(&url),
+            None => self.toast(t!("tui.toast.image_not_uploaded"), Severity::Info),
+        }
+    }
+
+    /// Copies the active product's uploaded image URLs, one per line.
+    fn copy_image_urls(&mut self) {
+        let Some(product) = &self.active_product else {
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
+            return;
+        };
+        let urls: Vec<String> = product
+            .images
+            .iter()
+            .filter_map(|img| img.uploaded_url.clone())
+            .collect();
+        match urls.len() {
+            0 => self.toast(t!("tui.toast.no_uploaded_images"), Severity::Info),
+            1 => self.copy_to_clipboard(&t!("tui.copy.image_url"), &urls[0]),
+            n => self.copy_to_clipboard(&t!("tui.copy.image_urls", count = n), &urls.join("\n")),
+        }
+    }
+
+    /// Queues a toast and mirrors it into the Activity log. Errors are sticky
+    /// and stay on screen until dismissed.
     fn toast(&mut self, message: String, severity: Severity) {
-        self.toast = Some(Toast {
+        self.record_activity(ActivityEntry {
+            at: Local::now(),
+            severity,
+            message: message.clone(),
+        });
+        if self.toasts.back().is_some_and(|t| t.message == message) {
+            return;
+        }
+        if self.toasts.len() >= TOAST_QUEUE_LIMIT {
+            // Drop the oldest waiting non-sticky toast; never the one on screen.
+            if let Some(idx) = self.toasts.iter().skip(1).position(|t| !t.sticky) {
+                self.toasts.remove(idx + 1);
+            }
+        }
+        self.toasts.push_back(Toast {
             message,
             severity,
-            expires_at: Instant::now() + Duration::from_secs(3),
+            sticky: severity == Severity::Error,
+            expires_at: None,
         });
     }
 
+    /// Dismisses the toast on screen, sticky or not.
+    fn dismiss_toast(&mut self) -> bool {
+        self.toasts.pop_front().is_some()
+    }
+
     pub fn spinner_frame(&self) -> &'static str {
         const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
         let elapsed = self.spinner_started_at.elapsed().as_millis() / 100;
@@ -2813,7 +6036,8 @@ impl AppState {
             AppTab::Home => AppTab::Quickstart,
             AppTab::Quickstart => AppTab::Products,
             AppTab::Products => AppTab::Activity,
-            AppTab::Activity => AppTab::Settings,
+            AppTab::Activity => AppTab::Analytics,
+            AppTab::Analytics => AppTab::Settings,
             AppTab::Settings => AppTab::Home,
         };
     }
@@ -2854,9 +6078,25 @@ impl AppState {
                 continue;
             }
             if *field == ListingFieldKey::Aspects {
+                let missing = listing
+                    .aspect_specs
+                    .iter()
+                    .filter(|spec| {
+                        spec.required
+                            && listing
+                                .aspects
+                                .get(spec.name.trim())
+                                .is_none_or(|values| values.is_empty())
+                    })
+                    .count();
+                let label = if missing == 0 {
+                    (*label).to_string()
+                } else {
+                    format!("{label} ({missing} required missing)")
+                };
                 entries.push(ListingFieldEntry {
                     key: *field,
-                    label: (*label).to_string(),
+                    label,
                     value: Value::Null,
                     kind: *kind,
                     indent: 0,
@@ -3037,27 +6277,38 @@ impl AppState {
 
     fn save_listings_field_edit(&mut self, command_tx: &Sender<AppCommand>) -> bool {
         let Some(product) = &self.active_product else {
-            self.toast("No active product selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
             return false;
         };
         let Some(key) = self.selected_listing_key() else {
-            self.toast("No listing selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_listing_selected"), Severity::Warning);
             return false;
         };
         let Some(field_key) = self.listings_field_edit_key else {
-            self.toast("No listing field selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_listing_field_selected"), Severity::Warning);
             return false;
         };
         let mut listing = product.listings.get(&key).cloned().unwrap_or_default();
         if field_key == ListingFieldKey::AspectValue {
-            let Some(name) = self.listings_field_edit_name.clone() else {
-                self.toast("No aspect selected.".to_string(), Severity::Warning);
-                return false;
+            let (name, buffer) = match self.listings_field_edit_name.clone() {
+                Some(name) => (name, self.listings_field_edit_buffer.clone()),
+                None => match self.listings_field_edit_buffer.split_once('=') {
+                    Some((name, values)) if !name.trim().is_empty() => {
+                        (name.trim().to_string(), values.to_string())
+                    }
+                    _ => {
+                        self.toast(t!("tui.toast.aspect_format"), Severity::Warning);
+                        return false;
+                    }
+                },
             };
-            let values = match parse_aspect_values_input(&self.listings_field_edit_buffer) {
+            let values = match parse_aspect_values_input(&buffer) {
                 Ok(values) => values,
                 Err(err) => {
-                    self.toast(format!("Invalid aspect values: {err}"), Severity::Error);
+                    self.toast(
+                        t!("tui.toast.invalid_aspect_values", error = err),
+                        Severity::Error,
+                    );
                     return false;
                 }
             };
@@ -3068,7 +6319,7 @@ impl AppState {
             }
         } else if field_key == ListingFieldKey::ImageValue {
             let Some(index) = self.listings_field_edit_image_index else {
-                self.toast("No image selected.".to_string(), Severity::Warning);
+                self.toast(t!("tui.toast.no_image_selected"), Severity::Warning);
                 return false;
             };
             let value = self
@@ -3079,10 +6330,7 @@ impl AppState {
                 .unwrap_or("")
                 .to_string();
             if index >= listing.images.len() {
-                self.toast(
-                    "Image selection out of range.".to_string(),
-                    Severity::Warning,
-                );
+                self.toast(t!("tui.toast.image_out_of_range"), Severity::Warning);
                 return false;
             }
             if value.is_empty() {
@@ -3092,7 +6340,7 @@ impl AppState {
             }
         } else if field_key == ListingFieldKey::PackageDimensionValue {
             let Some(dimension) = self.listings_field_edit_dimension else {
-                self.toast("No dimension selected.".to_string(), Severity::Warning);
+                self.toast(t!("tui.toast.no_dimension_selected"), Severity::Warning);
                 return false;
             };
             let value = match parse_listing_edit_buffer(
@@ -3101,12 +6349,12 @@ impl AppState {
             ) {
                 Ok(value) => value,
                 Err(err) => {
-                    self.toast(format!("Invalid value: {err}"), Severity::Error);
+                    self.toast(t!("tui.toast.invalid_value", error = err), Severity::Error);
                     return false;
                 }
             };
             if let Err(err) = apply_package_dimension_value(&mut listing, dimension, &value) {
-                self.toast(format!("Invalid value: {err}"), Severity::Error);
+                self.toast(t!("tui.toast.invalid_value", error = err), Severity::Error);
                 return false;
             }
         } else {
@@ -3116,12 +6364,33 @@ impl AppState {
             ) {
                 Ok(value) => value,
                 Err(err) => {
-                    self.toast(format!("Invalid value: {err}"), Severity::Error);
+                    self.toast(t!("tui.toast.invalid_value", error = err), Severity::Error);
                     return false;
                 }
             };
-            if let Err(err) = apply_listing_field_value(&mut listing, field_key, &value) {
-                self.toast(format!("Invalid value: {err}"), Severity::Error);
+            if let Err(err) = apply_listing_field_value(
+                &mut listing,
+                field_key,
+                &value,
+                &self.config.package_templates,
+            ) {
+                self.toast(t!("tui.toast.invalid_value", error = err), Severity::Error);
+                return false;
+            }
+            if let Some((id, label)) = self.listings_category_choice.take() {
+                listing.category_id = Some(id);
+                listing.category_label = Some(label);
+            }
+            if let Some((label, id)) = self.listings_condition_choice.take() {
+                listing.condition = Some(label);
+                listing.condition_id = id;
+            }
+            if matches!(
+                field_key,
+                ListingFieldKey::Condition | ListingFieldKey::ConditionId
+            ) && let Err(err) = validate_listing_condition(&listing)
+            {
+                self.toast(err, Severity::Error);
                 return false;
             }
         }
@@ -3138,12 +6407,12 @@ impl AppState {
 
     fn save_structure_field_edit(&mut self, command_tx: &Sender<AppCommand>) -> bool {
         let Some(product) = &self.active_product else {
-            self.toast("No active product selected.".to_string(), Severity::Warning);
+            self.toast(t!("tui.toast.no_active_product"), Severity::Warning);
             return false;
         };
         let Some(path) = self.structure_field_edit_path.clone() else {
             self.toast(
-                "No structure field selected.".to_string(),
+                t!("tui.toast.no_structure_field_selected"),
                 Severity::Warning,
             );
             return false;
@@ -3155,7 +6424,7 @@ impl AppState {
         ) {
             Ok(value) => value,
             Err(err) => {
-                self.toast(format!("Invalid value: {err}"), Severity::Error);
+                self.toast(t!("tui.toast.invalid_value", error = err), Severity::Error);
                 return false;
             }
         };
@@ -3282,10 +6551,12 @@ pub enum ListingFieldKey {
     AspectValue,
     Condition,
     ConditionId,
+    PackageTemplate,
     PackageWeight,
     PackageDimensions,
     PackageDimensionValue,
     Quantity,
+    QuantitySold,
     MerchantLocationKey,
     FulfillmentPolicyId,
     PaymentPolicyId,
@@ -3358,6 +6629,11 @@ const LISTING_FIELDS: &[(ListingFieldKey, &str, ListingEditKind)] = &[
         "Condition ID",
         ListingEditKind::Integer,
     ),
+    (
+        ListingFieldKey::PackageTemplate,
+        "Package Template",
+        ListingEditKind::Text,
+    ),
     (
         ListingFieldKey::PackageWeight,
         "Package Weight",
@@ -3373,6 +6649,11 @@ const LISTING_FIELDS: &[(ListingFieldKey, &str, ListingEditKind)] = &[
         "Quantity",
         ListingEditKind::Integer,
     ),
+    (
+        ListingFieldKey::QuantitySold,
+        "Quantity Sold",
+        ListingEditKind::Integer,
+    ),
     (
         ListingFieldKey::MerchantLocationKey,
         "Merchant Location Key",
@@ -3464,6 +6745,11 @@ fn listing_field_value(listing: &storage::MarketplaceListing, key: ListingFieldK
             .condition_id
             .map(|value| Value::Number(Number::from(value)))
             .unwrap_or(Value::Null),
+        ListingFieldKey::PackageTemplate => listing
+            .package_template
+            .clone()
+            .map(Value::String)
+            .unwrap_or(Value::Null),
         ListingFieldKey::PackageWeight => listing
             .package
             .as_ref()
@@ -3481,6 +6767,10 @@ fn listing_field_value(listing: &storage::MarketplaceListing, key: ListingFieldK
             .quantity
             .map(|value| Value::Number(Number::from(value)))
             .unwrap_or(Value::Null),
+        ListingFieldKey::QuantitySold => listing
+            .quantity_sold
+            .map(|value| Value::Number(Number::from(value)))
+            .unwrap_or(Value::Null),
         ListingFieldKey::MerchantLocationKey => listing
             .merchant_location_key
             .clone()
@@ -3577,6 +6867,32 @@ fn format_package_dimensions(dimensions: &storage::ListingDimensions) -> String
     )
 }
 
+/// Rejects conditions that are not in the allowed lists recorded from the
+/// last listing run (when those lists are known).
+fn validate_listing_condition(listing: &storage::MarketplaceListing) -> Result<(), String> {
+    if let Some(condition) = &listing.condition
+        && !listing.allowed_conditions.is_empty()
+        && !listing
+            .allowed_conditions
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(condition))
+    {
+        return Err(format!(
+            "Condition not allowed; choose one of: {}",
+            listing.allowed_conditions.join(", ")
+        ));
+    }
+    if let Some(id) = listing.condition_id
+        && !listing.allowed_condition_ids.is_empty()
+        && !listing.allowed_condition_ids.contains(&id)
+    {
+        return Err(format!(
+            "Condition id {id} not allowed (Tab cycles allowed values)."
+        ));
+    }
+    Ok(())
+}
+
 fn aspect_entry(name: &str, values: &[String]) -> ListingFieldEntry {
     ListingFieldEntry {
         key: ListingFieldKey::AspectValue,
@@ -3854,6 +7170,44 @@ fn apply_package_dimension_value(
 
     package.dimensions = Some(dimensions);
     listing.package = Some(package);
+    listing.package_template = None;
+    Ok(())
+}
+
+/// Sets the weight and dimensions the named template defines, keeping the
+/// others; an empty name only forgets which template was applied.
+fn apply_package_template(
+    listing: &mut storage::MarketplaceListing,
+    name: Option<String>,
+    templates: &[PackageTemplate],
+) -> Result<(), String> {
+    let Some(name) = name else {
+        listing.package_template = None;
+        return Ok(());
+    };
+    let template = package::find_template(templates, &name).map_err(|err| err.to_string())?;
+    let input = template.package().map_err(|err| err.to_string())?;
+    if let Some(weight) = input.weight {
+        apply_package_weight(
+            listing,
+            Some(storage::ListingWeight {
+                value: weight.value,
+                unit: weight.unit,
+            }),
+        );
+    }
+    if let Some(dims) = input.dimensions {
+        apply_package_dimensions(
+            listing,
+            Some(storage::ListingDimensions {
+                height: dims.height,
+                length: dims.length,
+                width: dims.width,
+                unit: dims.unit,
+            }),
+        );
+    }
+    listing.package_template = Some(template.name.clone());
     Ok(())
 }
 
@@ -3924,15 +7278,24 @@ fn parse_package_weight_text(text: &str) -> Result<Option<storage::ListingWeight
     {
         return parse_package_weight_value(&json);
     }
+    if trimmed.chars().any(|ch| ch.is_ascii_alphabetic()) {
+        return package::parse_weight(trimmed)
+            .map(|weight| {
+                Some(storage::ListingWeight {
+                    value: weight.value,
+                    unit: weight.unit,
+                })
+            })
+            .map_err(|err| err.to_string());
+    }
     let number = extract_numbers(trimmed)
         .first()
         .copied()
         .ok_or_else(|| "expected a weight like 10 OUNCE".to_string())?;
-    let unit = parse_weight_unit(trimmed).unwrap_or("OUNCE");
     let value = number.ceil().max(1.0) as u32;
     Ok(Some(storage::ListingWeight {
         value,
-        unit: unit.to_string(),
+        unit: "OUNCE".to_string(),
     }))
 }
 
@@ -3959,11 +7322,22 @@ fn parse_package_dimensions_text(text: &str) -> Result<Option<storage::ListingDi
     {
         return parse_package_dimensions_value(&json);
     }
+    if unit_tokens(trimmed).iter().any(|token| token != "x") {
+        return package::parse_dimensions(trimmed)
+            .map(|dims| {
+                Some(storage::ListingDimensions {
+                    length: dims.length,
+                    width: dims.width,
+                    height: dims.height,
+                    unit: dims.unit,
+                })
+            })
+            .map_err(|err| err.to_string());
+    }
     let numbers = extract_numbers(trimmed);
     if numbers.len() < 3 {
         return Err("expected 3 dimension values (L x W x H)".to_string());
     }
-    let unit = parse_dimension_unit(trimmed).unwrap_or("INCH");
     let length = ceil_one_decimal(numbers[0]);
     let width = ceil_one_decimal(numbers[1]);
     let height = ceil_one_decimal(numbers[2]);
@@ -3974,21 +7348,10 @@ fn parse_package_dimensions_text(text: &str) -> Result<Option<storage::ListingDi
         length,
         width,
         height,
-        unit: unit.to_string(),
+        unit: "INCH".to_string(),
     }))
 }
 
-fn parse_weight_unit(text: &str) -> Option<&'static str> {
-    for token in unit_tokens(text) {
-        match token.as_str() {
-            "lb" | "lbs" | "pound" | "pounds" => return Some("POUND"),
-            "oz" | "ounce" | "ounces" => return Some("OUNCE"),
-            _ => {}
-        }
-    }
-    None
-}
-
 fn parse_dimension_unit(text: &str) -> Option<&'static str> {
     for token in unit_tokens(text) {
         match token.as_str() {
@@ -4036,6 +7399,7 @@ fn apply_listing_field_value(
     listing: &mut storage::MarketplaceListing,
     key: ListingFieldKey,
     value: &Value,
+    templates: &[PackageTemplate],
 ) -> Result<(), String> {
     let text_value = |value: &Value| match value {
         Value::Null => Ok(None),
@@ -4086,15 +7450,21 @@ fn apply_listing_field_value(
         ListingFieldKey::Aspects => listing.aspects = parse_aspects_value(value)?,
         ListingFieldKey::Condition => listing.condition = text_value(value)?,
         ListingFieldKey::ConditionId => listing.condition_id = integer_value(value)?,
+        ListingFieldKey::PackageTemplate => {
+            apply_package_template(listing, text_value(value)?, templates)?;
+        }
         ListingFieldKey::PackageWeight => {
             let weight = parse_package_weight_value(value)?;
             apply_package_weight(listing, weight);
+            listing.package_template = None;
         }
         ListingFieldKey::PackageDimensions => {
             let dimensions = parse_package_dimensions_value(value)?;
             apply_package_dimensions(listing, dimensions);
+            listing.package_template = None;
         }
         ListingFieldKey::Quantity => listing.quantity = integer_value(value)?,
+        ListingFieldKey::QuantitySold => listing.quantity_sold = integer_value(value)?,
         ListingFieldKey::MerchantLocationKey => listing.merchant_location_key = text_value(value)?,
         ListingFieldKey::FulfillmentPolicyId => listing.fulfillment_policy_id = text_value(value)?,
         ListingFieldKey::PaymentPolicyId => listing.payment_policy_id = text_value(value)?,
@@ -4192,6 +7562,14 @@ fn flatten_json(root: &Value, prefix: &str, out: &mut Vec<StructureFieldEntry>)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SettingsField {
     HermesApiKey,
+    BaseUrl,
+    SupabaseUrl,
+    SupabaseServiceRoleKey,
+    SupabaseBucket,
+    SupabaseUploadPrefix,
+    SupabaseSignedUrls,
+    CapturesDir,
+    PreviewBackend,
     PreviewHeightPct,
     Marketplace,
     MerchantLocation,
@@ -4207,9 +7585,17 @@ pub enum SettingsField {
     LlmAspectsWebSearch,
 }
 
-pub fn settings_fields() -> [SettingsField; 14] {
+pub fn settings_fields() -> [SettingsField; 22] {
     [
         SettingsField::HermesApiKey,
+        SettingsField::BaseUrl,
+        SettingsField::SupabaseUrl,
+        SettingsField::SupabaseServiceRoleKey,
+        SettingsField::SupabaseBucket,
+        SettingsField::SupabaseUploadPrefix,
+        SettingsField::SupabaseSignedUrls,
+        SettingsField::CapturesDir,
+        SettingsField::PreviewBackend,
         SettingsField::PreviewHeightPct,
         SettingsField::Marketplace,
         SettingsField::MerchantLocation,
@@ -4226,7 +7612,13 @@ pub fn settings_fields() -> [SettingsField; 14] {
     ]
 }
 
-pub fn detect_terminal_preview() -> Option<TerminalPreviewState> {
+/// Picks the preview surface. `Auto` uses inline graphics only for Kitty/iTerm2
+/// capable terminals, `Terminal` forces them (falling back to half blocks) and
+/// `Window` always uses the separate preview window.
+pub fn detect_terminal_preview(backend: PreviewBackend) -> Option<TerminalPreviewState> {
+    if backend == PreviewBackend::Window {
+        return None;
+    }
     let mut picker = Picker::from_query_stdio().unwrap_or_else(|_| Picker::halfblocks());
     let mut protocol = picker.protocol_type();
     if !matches!(protocol, ProtocolType::Kitty | ProtocolType::Iterm2)
@@ -4235,7 +7627,9 @@ pub fn detect_terminal_preview() -> Option<TerminalPreviewState> {
         protocol = ProtocolType::Kitty;
         picker.set_protocol_type(protocol);
     }
-    if matches!(protocol, ProtocolType::Kitty | ProtocolType::Iterm2) {
+    if backend == PreviewBackend::Terminal
+        || matches!(protocol, ProtocolType::Kitty | ProtocolType::Iterm2)
+    {
         Some(TerminalPreviewState {
             picker,
             camera_state: None,
@@ -4243,7 +7637,11 @@ pub fn detect_terminal_preview() -> Option<TerminalPreviewState> {
             image_path: None,
             last_camera_seq: 0,
             last_camera_refresh: Instant::now(),
+            camera_cells: None,
             last_error: None,
+            overlay: None,
+            countdown: None,
+            assist: PreviewAssist::default(),
         })
     } else {
         None
@@ -4259,6 +7657,12 @@ fn terminal_env_supports_kitty() -> bool {
             .unwrap_or(false)
 }
 
+fn config_file_mtime() -> Option<std::time::SystemTime> {
+    talaria_core::config::config_path()
+        .and_then(|path| fs::metadata(path).ok())
+        .and_then(|meta| meta.modified().ok())
+}
+
 fn non_empty(value: String) -> Option<String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -4268,12 +7672,8 @@ fn non_empty(value: String) -> Option<String> {
     }
 }
 
-fn llm_model_label(model: &LlmModel) -> &'static str {
-    match model {
-        LlmModel::Gpt5_2 => "gpt-5.2",
-        LlmModel::Gpt5Mini => "gpt-5-mini",
-        LlmModel::Gpt5Nano => "gpt-5-nano",
-    }
+fn llm_model_label(model: &LlmModel) -> &str {
+    model.as_str()
 }
 
 fn llm_model_from_str(value: &str) -> Option<LlmModel> {
@@ -4297,6 +7697,15 @@ fn parse_optional_bool(value: &str) -> Result<Option<bool>, String> {
     }
 }
 
+/// Fields edited through the option picker rather than free text.
+pub fn is_picker_settings_field(field: SettingsField) -> bool {
+    is_llm_settings_field(field)
+        || matches!(
+            field,
+            SettingsField::SupabaseSignedUrls | SettingsField::PreviewBackend
+        )
+}
+
 fn is_llm_settings_field(field: SettingsField) -> bool {
     matches!(
         field,
@@ -4318,6 +7727,38 @@ fn marketplace_key_from_settings(settings: &EbaySettings) -> String {
         .to_uppercase()
 }
 
+/// What Hermes needs to find comparable items: brand and MPN from the
+/// structured product, the listing title (or product name) as the query.
+fn comparable_prices_request(
+    product: &storage::ProductManifest,
+    listing: &storage::MarketplaceListing,
+    marketplace: MarketplaceId,
+) -> ComparablePricesRequest {
+    let structure = product.structure_json.as_ref().unwrap_or(&Value::Null);
+    let text = |value: &Value| {
+        value
+            .as_str()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+    ComparablePricesRequest {
+        marketplace: Some(marketplace),
+        query: listing
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+            .map(str::to_string)
+            .or_else(|| text(&structure["name"])),
+        brand: text(&structure["brand"]["name"]).or_else(|| text(&structure["brand"])),
+        mpn: text(&structure["mpn"]),
+        condition: listing.condition.clone(),
+        category_id: listing.category_id.clone(),
+        limit: None,
+    }
+}
+
 fn selected_marketplace(selected_key: Option<&str>, settings: &EbaySettings) -> MarketplaceId {
     let key = selected_key
         .map(|k| k.to_string())
@@ -4327,8 +7768,8 @@ fn selected_marketplace(selected_key: Option<&str>, settings: &EbaySettings) ->
 
 fn parse_marketplace(input: &str) -> MarketplaceId {
     match input.trim().to_uppercase().as_str() {
-        "EBAY_UK" | "EBAY_GB" => MarketplaceId::EbayUk,
-        "EBAY_DE" => MarketplaceId::EbayDe,
-        _ => MarketplaceId::EbayUs,
+        "" => MarketplaceId::EbayUs,
+        "EBAY_GB" => MarketplaceId::EbayUk,
+        other => MarketplaceId::from(other),
     }
 }
@@ -0,0 +1,237 @@
+//! Tethered DSLR/mirrorless capture through gPhoto2: the camera's live view
+//! (where it has one) streams as preview frames, and a capture fires the
+//! shutter and downloads the full-resolution JPEG into the session, where it
+//! is scored and curated like any other frame.
+//!
+//! The `gphoto2` command-line tool drives libgphoto2, so nothing links
+//! against it and a machine without it just lists no tethered cameras.
+//! Listing them at all needs the `gphoto2` feature.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use image::RgbImage;
+
+use super::{Backend, Frame};
+
+/// Whether tethered cameras are offered in this build.
+pub const ENABLED: bool = cfg!(feature = "gphoto2");
+
+const PROGRAM: &str = "gphoto2";
+/// Detecting and live view answer quickly; a still can wait on autofocus
+/// and a long exposure.
+const QUICK_TIMEOUT: Duration = Duration::from_secs(10);
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(60);
+/// How often a camera without live view shows its placeholder frame.
+const NO_PREVIEW_INTERVAL: Duration = Duration::from_millis(500);
+const PLACEHOLDER_SIZE: (u32, u32) = (640, 426);
+
+/// A camera `gphoto2 --auto-detect` reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TetheredCamera {
+    pub model: String,
+    /// `usb:001,012` and the like; changes when the camera is replugged.
+    pub port: String,
+}
+
+impl TetheredCamera {
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(PROGRAM);
+        cmd.args(["--camera", &self.model, "--port", &self.port]);
+        cmd
+    }
+}
+
+/// Connected cameras; none when the feature is off or gPhoto2 is missing.
+pub fn detect() -> Vec<TetheredCamera> {
+    if !ENABLED {
+        return Vec::new();
+    }
+    let mut cmd = Command::new(PROGRAM);
+    cmd.arg("--auto-detect");
+    match run(cmd, QUICK_TIMEOUT) {
+        Ok(output) => parse_auto_detect(&String::from_utf8_lossy(&output.stdout)),
+        Err(err) => {
+            tracing::debug!(error = %err, "gphoto2 auto-detect failed");
+            Vec::new()
+        }
+    }
+}
+
+/// Rows of the `Model  Port` table `--auto-detect` prints under a dashed rule.
+fn parse_auto_detect(text: &str) -> Vec<TetheredCamera> {
+    text.lines()
+        .skip_while(|line| !line.trim_start().starts_with("---"))
+        .skip(1)
+        .filter_map(|line| {
+            let line = line.trim();
+            let (model, port) = line.rsplit_once(char::is_whitespace)?;
+            let model = model.trim();
+            (!model.is_empty() && port.contains(':')).then(|| TetheredCamera {
+                model: model.to_string(),
+                port: port.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Runs `cmd`, killing it after `timeout`; a non-zero exit is an error
+/// carrying gPhoto2's own message.
+fn run(mut cmd: Command, timeout: Duration) -> Result<Output> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("run {PROGRAM} (is gPhoto2 installed?)"))?;
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("{PROGRAM} did not finish within {}s", timeout.as_secs());
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    if let Some(mut out) = child.stdout.take() {
+        out.read_to_end(&mut stdout)?;
+    }
+    if let Some(mut err) = child.stderr.take() {
+        err.read_to_end(&mut stderr)?;
+    }
+    if !status.success() {
+        let message = String::from_utf8_lossy(&stderr);
+        let message = message
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('*'))
+            .unwrap_or("failed");
+        bail!("{PROGRAM}: {message}");
+    }
+    Ok(Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+pub struct Gphoto2Backend {
+    camera: TetheredCamera,
+    preview_path: PathBuf,
+    /// Cleared after the first failed live-view frame.
+    live_view: bool,
+}
+
+impl Gphoto2Backend {
+    /// Checks the camera answers before the capture thread relies on it.
+    pub fn open(camera: TetheredCamera) -> Result<Self> {
+        let mut cmd = camera.command();
+        cmd.arg("--summary");
+        run(cmd, QUICK_TIMEOUT).with_context(|| format!("open {}", camera.model))?;
+        let preview_path = std::env::temp_dir().join(format!(
+            "talaria-gphoto2-preview-{}.jpg",
+            std::process::id()
+        ));
+        Ok(Self {
+            camera,
+            preview_path,
+            live_view: true,
+        })
+    }
+
+    fn preview(&self) -> Result<Frame> {
+        let mut cmd = self.camera.command();
+        cmd.arg("--capture-preview")
+            .arg("--force-overwrite")
+            .arg("--filename")
+            .arg(&self.preview_path);
+        run(cmd, QUICK_TIMEOUT)?;
+        Ok(image::open(&self.preview_path)
+            .context("decode live view frame")?
+            .to_rgb8())
+    }
+}
+
+impl Backend for Gphoto2Backend {
+    fn read(&mut self) -> Result<Option<Frame>> {
+        if self.live_view {
+            match self.preview() {
+                Ok(frame) => return Ok(Some(frame)),
+                Err(err) => {
+                    // Many bodies have no live view over USB; stills still work.
+                    self.live_view = false;
+                    tracing::info!(model = %self.camera.model, error = %err, "no gphoto2 live view");
+                    return Err(err.context("live view unavailable; captures still work"));
+                }
+            }
+        }
+        thread::sleep(NO_PREVIEW_INTERVAL);
+        let (width, height) = PLACEHOLDER_SIZE;
+        Ok(Some(RgbImage::from_pixel(
+            width,
+            height,
+            image::Rgb([40, 40, 40]),
+        )))
+    }
+
+    fn capture_still(&mut self, stem: &Path) -> Option<Result<PathBuf>> {
+        Some(self.download_still(stem))
+    }
+}
+
+impl Gphoto2Backend {
+    /// Fires the shutter and downloads to `stem` plus the camera's
+    /// extension. With RAW+JPEG both files are kept and the JPEG is returned.
+    fn download_still(&self, stem: &Path) -> Result<PathBuf> {
+        let mut cmd = self.camera.command();
+        cmd.arg("--capture-image-and-download")
+            .arg("--force-overwrite")
+            .arg("--filename")
+            .arg(format!("{}.%C", stem.display()));
+        run(cmd, CAPTURE_TIMEOUT)?;
+        ["jpg", "JPG", "jpeg", "JPEG"]
+            .iter()
+            .map(|ext| stem.with_extension(ext))
+            .find(|path| path.exists())
+            .context("the camera sent no JPEG; set it to JPEG or RAW+JPEG")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_detect_table_is_parsed_into_cameras() {
+        let text = "\
+Model                          Port
+----------------------------------------------------------
+Canon EOS 5D Mark III          usb:001,012
+Sony Alpha-A7 III (Control)    usb:002,004
+
+";
+        assert_eq!(
+            parse_auto_detect(text),
+            vec![
+                TetheredCamera {
+                    model: "Canon EOS 5D Mark III".into(),
+                    port: "usb:001,012".into(),
+                },
+                TetheredCamera {
+                    model: "Sony Alpha-A7 III (Control)".into(),
+                    port: "usb:002,004".into(),
+                },
+            ]
+        );
+        assert!(parse_auto_detect("Model  Port\n-----------\n").is_empty());
+    }
+}
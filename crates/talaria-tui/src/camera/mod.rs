@@ -1,9 +1,14 @@
+pub mod gphoto2;
+mod network;
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Receiver;
 use image::RgbImage;
 use nokhwa::Camera;
 use nokhwa::pixel_format::RgbFormat;
@@ -12,10 +17,19 @@ use nokhwa::utils::{
     Resolution,
 };
 use parking_lot::Mutex;
+use talaria_core::color::apply_gains;
+use talaria_core::config::SecondCamera;
+
+use self::gphoto2::{Gphoto2Backend, TetheredCamera};
+use self::network::NetworkBackend;
 
-use crate::types::{AppEvent, CaptureCommand, CaptureEvent, CaptureStatus};
-use crate::util::fs::timestamped_capture_path;
-use crate::util::sharpness::laplacian_variance;
+use crate::event_bus::EventSender;
+use crate::shutdown::ShutdownToken;
+use crate::types::{
+    AppEvent, CaptureCommand, CaptureEvent, CaptureStatus, CapturedFrame, SecondCameraStatus,
+};
+use crate::util::fs::CaptureTarget;
+use crate::util::sharpness::{laplacian_variance, quick_laplacian_variance};
 
 pub type Frame = RgbImage;
 
@@ -23,6 +37,28 @@ pub type Frame = RgbImage;
 pub struct CameraDevice {
     pub index: i32,
     pub name: String,
+    /// Tethered and network cameras are numbered after the webcams.
+    pub kind: DeviceKind,
+}
+
+/// Where a device's frames come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceKind {
+    Webcam,
+    /// A DSLR/mirrorless body driven through gPhoto2.
+    Tethered(TetheredCamera),
+    /// An IP camera or phone streaming HTTP MJPEG or RTSP.
+    Network(String),
+}
+
+impl DeviceKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeviceKind::Webcam => "webcam",
+            DeviceKind::Tethered(_) => "gPhoto2",
+            DeviceKind::Network(_) => "network",
+        }
+    }
 }
 
 pub struct LatestFrameSlot {
@@ -71,6 +107,16 @@ impl LatestFrameSlot {
             .map(|frame| (inner.seq, frame.clone(), inner.size))
     }
 
+    /// Runs `f` on the latest frame, under the lock and without copying it,
+    /// but only when it is newer than `seq`.
+    pub fn map_latest_since<T>(&self, seq: u64, f: impl FnOnce(&Frame) -> T) -> Option<(u64, T)> {
+        let inner = self.inner.lock();
+        match &inner.frame {
+            Some(frame) if inner.seq != seq => Some((inner.seq, f(frame))),
+            _ => None,
+        }
+    }
+
     pub fn dropped(&self) -> u64 {
         self.inner.lock().dropped
     }
@@ -84,9 +130,18 @@ fn frame_dimensions(frame: &Frame) -> (i32, i32) {
     (frame.width() as i32, frame.height() as i32)
 }
 
+/// Webcams, then any tethered cameras gPhoto2 detects.
 pub fn list_devices() -> Result<Vec<CameraDevice>> {
-    let devices = nokhwa::query(preferred_backend()).context("query cameras")?;
-    let mut results = Vec::with_capacity(devices.len());
+    let tethered = gphoto2::detect();
+    let devices = match nokhwa::query(preferred_backend()).context("query cameras") {
+        Ok(devices) => devices,
+        Err(err) if tethered.is_empty() => return Err(err),
+        Err(err) => {
+            tracing::warn!(error = %err, "webcam query failed");
+            Vec::new()
+        }
+    };
+    let mut results = Vec::with_capacity(devices.len() + tethered.len());
     for (fallback_idx, dev) in devices.into_iter().enumerate() {
         let mut name = dev.human_name();
         if name.trim().is_empty() {
@@ -98,40 +153,330 @@ pub fn list_devices() -> Result<Vec<CameraDevice>> {
                 results.push(CameraDevice {
                     index: fallback_idx as i32,
                     name: format!("{name} ({id})"),
+                    kind: DeviceKind::Webcam,
                 });
                 continue;
             }
         };
-        results.push(CameraDevice { index, name });
+        results.push(CameraDevice {
+            index,
+            name,
+            kind: DeviceKind::Webcam,
+        });
+    }
+    let first = results.iter().map(|d| d.index + 1).max().unwrap_or(0);
+    for (offset, camera) in tethered.into_iter().enumerate() {
+        results.push(CameraDevice {
+            index: first + offset as i32,
+            name: format!("{} ({})", camera.model, camera.port),
+            kind: DeviceKind::Tethered(camera),
+        });
     }
     Ok(results)
 }
 
+/// Picker entries for the configured network camera URLs, numbered from
+/// `first_index`.
+pub fn network_devices(urls: &[String], first_index: i32) -> Vec<CameraDevice> {
+    urls.iter()
+        .enumerate()
+        .map(|(offset, url)| CameraDevice {
+            index: first_index + offset as i32,
+            name: url.clone(),
+            kind: DeviceKind::Network(url.clone()),
+        })
+        .collect()
+}
+
+/// The synthetic camera shown in simulation mode.
+pub fn simulated_device() -> CameraDevice {
+    CameraDevice {
+        index: 0,
+        name: talaria_core::simulate::SIMULATED_CAMERA_NAME.to_string(),
+        kind: DeviceKind::Webcam,
+    }
+}
+
+/// Where the capture thread gets frames from. Every backend feeds the same
+/// [`LatestFrameSlot`] and [`CaptureEvent`]s, so they are interchangeable.
+pub trait Backend {
+    /// The next frame; may block until one is ready. `None` when none
+    /// arrived in time, so the capture loop can handle commands meanwhile.
+    fn read(&mut self) -> Result<Option<Frame>>;
+
+    /// Connection health for sources that can drop and come back.
+    fn link_status(&self) -> Option<String> {
+        None
+    }
+
+    /// Takes a full-resolution still to `stem` plus the extension of what
+    /// the camera sends and returns its path. `None` when the backend has no
+    /// shutter of its own, in which case a capture saves the latest streamed
+    /// frame.
+    fn capture_still(&mut self, _stem: &Path) -> Option<Result<PathBuf>> {
+        None
+    }
+}
+
+/// Opens the backend for device `index`: the synthetic test pattern in
+/// simulation mode, otherwise whatever `kind` of camera was picked.
+fn open_backend(index: i32, kind: &DeviceKind, simulate: bool) -> Result<Box<dyn Backend>> {
+    if simulate {
+        return Ok(Box::new(SyntheticBackend::new()));
+    }
+    match kind {
+        DeviceKind::Webcam => {}
+        DeviceKind::Tethered(camera) => {
+            return Gphoto2Backend::open(camera.clone())
+                .map(|cam| Box::new(cam) as Box<dyn Backend>);
+        }
+        DeviceKind::Network(url) => {
+            return NetworkBackend::open(url).map(|cam| Box::new(cam) as Box<dyn Backend>);
+        }
+    }
+    open_device(index).map(|cam| Box::new(NokhwaBackend(cam)) as Box<dyn Backend>)
+}
+
+struct NokhwaBackend(Camera);
+
+impl Backend for NokhwaBackend {
+    fn read(&mut self) -> Result<Option<Frame>> {
+        read_frame(&mut self.0).map(Some)
+    }
+}
+
+const SYNTHETIC_FRAME_INTERVAL: Duration = Duration::from_millis(66);
+
+struct SyntheticBackend {
+    seq: u64,
+    next_at: Instant,
+}
+
+impl SyntheticBackend {
+    fn new() -> Self {
+        Self {
+            seq: 0,
+            next_at: Instant::now(),
+        }
+    }
+}
+
+impl Backend for SyntheticBackend {
+    fn read(&mut self) -> Result<Option<Frame>> {
+        // Pace like a ~15 fps webcam instead of spinning.
+        thread::sleep(self.next_at.saturating_duration_since(Instant::now()));
+        self.next_at = Instant::now() + SYNTHETIC_FRAME_INTERVAL;
+        self.seq += 1;
+        let (width, height) = (
+            talaria_core::simulate::SYNTHETIC_FRAME_WIDTH,
+            talaria_core::simulate::SYNTHETIC_FRAME_HEIGHT,
+        );
+        let pixels = talaria_core::simulate::synthetic_frame(width, height, self.seq);
+        RgbImage::from_raw(width, height, pixels)
+            .context("synthetic frame")
+            .map(Some)
+    }
+}
+
+/// How far back retro capture looks.
+const RETRO_WINDOW: Duration = Duration::from_secs(1);
+/// Keep one streamed frame per interval; full frames are large.
+const RETRO_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+/// Longest side of the copy each buffered frame is scored on.
+const RETRO_SCORE_SIDE: u32 = 320;
+
+struct BufferedFrame {
+    at: Instant,
+    created_at: chrono::DateTime<chrono::Local>,
+    sharpness: f64,
+    frame: Frame,
+}
+
+/// A rolling buffer of recent streamed frames for "capture the moment before".
+#[derive(Default)]
+struct FrameHistory {
+    frames: VecDeque<BufferedFrame>,
+}
+
+impl FrameHistory {
+    fn push(&mut self, frame: &Frame, at: Instant) {
+        if self
+            .frames
+            .back()
+            .is_some_and(|last| at.duration_since(last.at) < RETRO_SAMPLE_INTERVAL)
+        {
+            return;
+        }
+        while self
+            .frames
+            .front()
+            .is_some_and(|first| at.duration_since(first.at) > RETRO_WINDOW)
+        {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(BufferedFrame {
+            at,
+            created_at: chrono::Local::now(),
+            sharpness: quick_laplacian_variance(frame, RETRO_SCORE_SIDE),
+            frame: frame.clone(),
+        });
+    }
+
+    /// The sharpest frame from the window ending at `now`.
+    fn sharpest(&self, now: Instant) -> Option<&BufferedFrame> {
+        self.frames
+            .iter()
+            .filter(|f| now.saturating_duration_since(f.at) <= RETRO_WINDOW)
+            .max_by(|a, b| a.sharpness.total_cmp(&b.sharpness))
+    }
+
+    fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+/// Tag of the frames a turntable run saves.
+pub const TURNTABLE_TAG: &str = "turntable";
+
+/// A running turntable run: a capture every `every` until `total` are taken.
+struct IntervalRun {
+    every: Duration,
+    total: u32,
+    taken: u32,
+    next: Instant,
+}
+
+/// The `[second_camera]`, streamed in the same loop as the picked device so
+/// a paired capture can take both latest frames at once.
+struct SecondRig {
+    config: SecondCamera,
+    kind: DeviceKind,
+    index: i32,
+    backend: Option<Box<dyn Backend>>,
+    latest: LatestFrameSlot,
+    fps_frames: u32,
+    /// Set when opening failed; retried on the next stream start.
+    failed: bool,
+}
+
+impl SecondRig {
+    fn new(config: SecondCamera) -> Self {
+        let (kind, index) = match &config.url {
+            Some(url) => (DeviceKind::Network(url.clone()), 0),
+            None => (DeviceKind::Webcam, config.index.unwrap_or(0) as i32),
+        };
+        Self {
+            config,
+            kind,
+            index,
+            backend: None,
+            latest: LatestFrameSlot::new(),
+            fps_frames: 0,
+            failed: false,
+        }
+    }
+
+    fn source(&self) -> String {
+        match &self.kind {
+            DeviceKind::Network(url) => url.clone(),
+            _ => format!("device {}", self.index),
+        }
+    }
+
+    /// Reads one frame, opening the camera first if needed.
+    fn poll(&mut self, simulate: bool, event_tx: &EventSender) {
+        if self.failed {
+            return;
+        }
+        if self.backend.is_none() {
+            match open_backend(self.index, &self.kind, simulate) {
+                Ok(backend) => self.backend = Some(backend),
+                Err(err) => {
+                    tracing::warn!(source = %self.source(), error = %err, "second camera open failed");
+                    let _ = event_tx.send(AppEvent::Capture(CaptureEvent::Error(format!(
+                        "open {} camera ({}): {err}",
+                        self.config.label,
+                        self.source()
+                    ))));
+                    self.failed = true;
+                    return;
+                }
+            }
+        }
+        if let Some(backend) = self.backend.as_mut() {
+            match backend.read() {
+                Ok(Some(frame)) => {
+                    let _ = self.latest.set(frame);
+                    self.fps_frames += 1;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::debug!(source = %self.source(), error = %err, "second camera read failed");
+                }
+            }
+        }
+    }
+
+    fn status(&mut self, streaming: bool, elapsed: f32) -> SecondCameraStatus {
+        let fps = self.fps_frames as f32 / elapsed;
+        self.fps_frames = 0;
+        SecondCameraStatus {
+            label: self.config.label.clone(),
+            primary_label: self.config.primary_label.clone(),
+            source: self.source(),
+            streaming: streaming && self.backend.is_some(),
+            fps,
+            frame_size: self.latest.frame_size(),
+            link: self.backend.as_ref().and_then(|cam| cam.link_status()),
+        }
+    }
+}
+
 pub fn spawn_capture_thread(
     cmd_rx: Receiver<CaptureCommand>,
-    event_tx: Sender<AppEvent>,
+    event_tx: EventSender,
     latest: Arc<LatestFrameSlot>,
+    second_camera: Option<SecondCamera>,
+    simulate: bool,
+    shutdown: ShutdownToken,
 ) -> thread::JoinHandle<()> {
+    // Returning drops the backend, which closes the device.
     thread::spawn(move || {
         let mut device_index = 0;
+        let mut kind = DeviceKind::Webcam;
         let mut streaming = false;
-        let mut capture: Option<Camera> = None;
-        let mut output_dir: Option<std::path::PathBuf> = None;
+        let mut capture: Option<Box<dyn Backend>> = None;
+        let mut history = FrameHistory::default();
+        let mut output: Option<CaptureTarget> = None;
+        // Number of the last frame named in the current output dir.
+        let mut seq = 0u32;
         let mut fps_last = Instant::now();
         let mut fps_frames = 0u32;
         let mut status_last = Instant::now();
+        let mut countdown: Option<Instant> = None;
+        let mut countdown_shown: Option<u32> = None;
+        let mut turntable: Option<IntervalRun> = None;
+        let mut turntable_shown: Option<(u32, u32)> = None;
+        let mut second = second_camera.map(SecondRig::new);
 
         loop {
+            if shutdown.is_cancelled() {
+                return;
+            }
             while let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
                     CaptureCommand::StartStream => {
+                        if let Some(rig) = second.as_mut() {
+                            rig.failed = false;
+                        }
                         if capture.is_none() {
-                            match open_device(device_index) {
+                            match open_backend(device_index, &kind, simulate) {
                                 Ok(cap) => {
                                     capture = Some(cap);
                                     streaming = true;
                                 }
                                 Err(err) => {
+                                    tracing::warn!(device_index, error = %err, "camera open failed");
                                     let _ = event_tx.send(AppEvent::Capture(CaptureEvent::Error(
                                         format!("open device {device_index}: {err}"),
                                     )));
@@ -144,14 +489,22 @@ pub fn spawn_capture_thread(
                     }
                     CaptureCommand::StopStream => {
                         streaming = false;
+                        history.clear();
+                        if let Some(rig) = second.as_mut() {
+                            rig.backend = None;
+                            rig.latest = LatestFrameSlot::new();
+                        }
                     }
-                    CaptureCommand::SetDevice { index } => {
+                    CaptureCommand::SetDevice { index, tethered } => {
                         device_index = index.max(0);
+                        kind = tethered.map_or(DeviceKind::Webcam, DeviceKind::Tethered);
+                        history.clear();
                         if streaming {
                             capture = None;
-                            match open_device(device_index) {
+                            match open_backend(device_index, &kind, simulate) {
                                 Ok(cap) => capture = Some(cap),
                                 Err(err) => {
+                                    tracing::warn!(device_index, error = %err, "camera open failed");
                                     let _ = event_tx.send(AppEvent::Capture(CaptureEvent::Error(
                                         format!("open device {device_index}: {err}"),
                                     )));
@@ -160,42 +513,151 @@ pub fn spawn_capture_thread(
                             }
                         }
                     }
-                    CaptureCommand::SetOutputDir(dir) => {
-                        output_dir = Some(dir);
+                    CaptureCommand::SetDeviceUrl(url) => {
+                        kind = DeviceKind::Network(url.clone());
+                        history.clear();
+                        if streaming {
+                            capture = None;
+                            match open_backend(device_index, &kind, simulate) {
+                                Ok(cap) => capture = Some(cap),
+                                Err(err) => {
+                                    tracing::warn!(url, error = %err, "network camera open failed");
+                                    let _ = event_tx.send(AppEvent::Capture(CaptureEvent::Error(
+                                        format!("{err:#}"),
+                                    )));
+                                    streaming = false;
+                                }
+                            }
+                        }
+                    }
+                    CaptureCommand::SetOutputDir(target) => {
+                        // Frames already in the dir keep their numbers.
+                        seq = std::fs::read_dir(&target.dir)
+                            .map(|entries| entries.count() as u32)
+                            .unwrap_or(0);
+                        output = Some(target);
                     }
                     CaptureCommand::ClearOutputDir => {
-                        output_dir = None;
+                        output = None;
                     }
                     CaptureCommand::CaptureOne => {
-                        match capture_one(
+                        let result = capture_one(
+                            &mut capture,
+                            device_index,
+                            &kind,
+                            simulate,
+                            &latest,
+                            output.as_ref().map(|target| (target, &mut seq)),
+                        );
+                        report_capture(&event_tx, result, "capture");
+                    }
+                    CaptureCommand::CaptureAfter { secs } => {
+                        countdown = Some(Instant::now() + Duration::from_secs(secs.into()));
+                    }
+                    CaptureCommand::CancelCountdown => countdown = None,
+                    CaptureCommand::CaptureInterval { every_ms, total } => {
+                        turntable = (total > 0).then(|| IntervalRun {
+                            every: Duration::from_millis(every_ms),
+                            total,
+                            taken: 0,
+                            next: Instant::now(),
+                        });
+                    }
+                    CaptureCommand::CancelInterval => turntable = None,
+                    CaptureCommand::CaptureBoth => {
+                        let result = capture_pair(
                             &mut capture,
                             device_index,
+                            &kind,
+                            simulate,
                             &latest,
-                            output_dir.as_deref(),
-                        ) {
-                            Ok((path, created_at, sharpness_score)) => {
-                                let _ = event_tx.send(AppEvent::Capture(
-                                    CaptureEvent::CaptureCompleted {
-                                        path,
-                                        created_at,
-                                        sharpness_score,
-                                    },
-                                ));
+                            second.as_ref(),
+                            output.as_ref().map(|target| (target, &mut seq)),
+                        );
+                        match result {
+                            Ok(frames) => {
+                                for frame in frames {
+                                    report_capture(&event_tx, Ok(frame), "pair");
+                                }
+                            }
+                            Err(err) => report_capture(&event_tx, Err(err), "pair"),
+                        }
+                    }
+                    CaptureCommand::SetSecondCamera(config) => {
+                        if second.as_ref().map(|rig| &rig.config) != config.as_ref() {
+                            second = config.map(SecondRig::new);
+                        }
+                    }
+                    CaptureCommand::CaptureRetro => {
+                        let saved = match (history.sharpest(Instant::now()), output.as_ref()) {
+                            (_, None) => {
+                                Err(anyhow::anyhow!("no active session (set output dir first)"))
+                            }
+                            (None, _) => Err(anyhow::anyhow!(
+                                "nothing buffered for retro capture; start the camera first"
+                            )),
+                            (Some(buffered), Some(target)) => {
+                                save_frame(target, &mut seq, &buffered.frame, buffered.created_at)
+                            }
+                        };
+                        let event = match saved {
+                            Ok(frame) => {
+                                trace_capture(&frame, "retro");
+                                CaptureEvent::CaptureCompleted(frame)
                             }
                             Err(err) => {
-                                let _ = event_tx
-                                    .send(AppEvent::Capture(CaptureEvent::Error(err.to_string())));
+                                tracing::warn!(error = %err, "retro capture failed");
+                                CaptureEvent::Error(err.to_string())
                             }
-                        }
+                        };
+                        let _ = event_tx.send(AppEvent::Capture(event));
                     }
                     CaptureCommand::Shutdown => return,
                 }
             }
 
+            if countdown.is_some_and(|deadline| Instant::now() >= deadline) {
+                countdown = None;
+                let result = capture_one(
+                    &mut capture,
+                    device_index,
+                    &kind,
+                    simulate,
+                    &latest,
+                    output.as_ref().map(|target| (target, &mut seq)),
+                );
+                report_capture(&event_tx, result, "countdown");
+            }
+
+            if let Some(run) = turntable.as_mut().filter(|run| Instant::now() >= run.next) {
+                let result = capture_one(
+                    &mut capture,
+                    device_index,
+                    &kind,
+                    simulate,
+                    &latest,
+                    output.as_ref().map(|target| (target, &mut seq)),
+                )
+                .map(|frame| CapturedFrame {
+                    tag: Some(TURNTABLE_TAG.to_string()),
+                    ..frame
+                });
+                let failed = result.is_err();
+                report_capture(&event_tx, result, TURNTABLE_TAG);
+                run.taken += 1;
+                // A capture slower than the interval delays the next one
+                // rather than firing a burst to catch up.
+                run.next = (run.next + run.every).max(Instant::now());
+                if failed || run.taken >= run.total {
+                    turntable = None;
+                }
+            }
+
             if streaming && capture.is_none() {
-                match open_device(device_index) {
+                match open_backend(device_index, &kind, simulate) {
                     Ok(cap) => capture = Some(cap),
                     Err(err) => {
+                        tracing::warn!(device_index, error = %err, "camera open failed");
                         let _ = event_tx.send(AppEvent::Capture(CaptureEvent::Error(format!(
                             "open device {device_index}: {err}"
                         ))));
@@ -206,27 +668,51 @@ pub fn spawn_capture_thread(
 
             if streaming {
                 if let Some(cam) = capture.as_mut() {
-                    match read_frame(cam) {
-                        Ok(frame) => {
+                    match cam.read() {
+                        Ok(Some(frame)) => {
+                            history.push(&frame, Instant::now());
                             let _ = latest.set(frame);
                             fps_frames += 1;
                         }
+                        Ok(None) => {}
                         Err(err) => {
+                            tracing::debug!(device_index, error = %err, "frame read failed");
                             let _ = event_tx
                                 .send(AppEvent::Capture(CaptureEvent::Error(err.to_string())));
                             thread::sleep(Duration::from_millis(10));
                         }
                     }
                 }
+                if let Some(rig) = second.as_mut() {
+                    rig.poll(simulate, &event_tx);
+                }
             } else {
                 thread::sleep(Duration::from_millis(10));
             }
 
-            if status_last.elapsed() >= Duration::from_millis(500) {
+            // Each countdown tick goes out right away, not with the next status.
+            let seconds_left = countdown.map(|deadline| {
+                deadline
+                    .saturating_duration_since(Instant::now())
+                    .as_millis()
+                    .div_ceil(1000) as u32
+            });
+            let progress = turntable.as_ref().map(|run| (run.taken, run.total));
+            if status_last.elapsed() >= Duration::from_millis(500)
+                || seconds_left != countdown_shown
+                || progress != turntable_shown
+            {
+                countdown_shown = seconds_left;
+                turntable_shown = progress;
                 let elapsed = fps_last.elapsed().as_secs_f32().max(0.001);
                 let fps = fps_frames as f32 / elapsed;
                 fps_last = Instant::now();
                 fps_frames = 0;
+                talaria_core::metrics::global().set_gauge(
+                    talaria_core::metrics::CAPTURE_FPS,
+                    &[],
+                    if streaming { fps as f64 } else { 0.0 },
+                );
 
                 let status = CaptureStatus {
                     streaming,
@@ -234,6 +720,14 @@ pub fn spawn_capture_thread(
                     fps,
                     dropped_frames: latest.dropped(),
                     frame_size: latest.frame_size(),
+                    device_url: match &kind {
+                        DeviceKind::Network(url) => Some(url.clone()),
+                        _ => None,
+                    },
+                    link: capture.as_ref().and_then(|cam| cam.link_status()),
+                    countdown: seconds_left,
+                    turntable: progress,
+                    second: second.as_mut().map(|rig| rig.status(streaming, elapsed)),
                 };
                 let _ = event_tx.send(AppEvent::Capture(CaptureEvent::Status(status)));
                 status_last = Instant::now();
@@ -242,6 +736,29 @@ pub fn spawn_capture_thread(
     })
 }
 
+fn report_capture(event_tx: &EventSender, result: Result<CapturedFrame>, kind: &str) {
+    let event = match result {
+        Ok(frame) => {
+            trace_capture(&frame, kind);
+            CaptureEvent::CaptureCompleted(frame)
+        }
+        Err(err) => {
+            tracing::warn!(kind, error = %err, "capture failed");
+            CaptureEvent::Error(err.to_string())
+        }
+    };
+    let _ = event_tx.send(AppEvent::Capture(event));
+}
+
+fn trace_capture(frame: &CapturedFrame, kind: &str) {
+    tracing::info!(
+        kind,
+        path = %frame.path,
+        sharpness = frame.sharpness_score.unwrap_or(f64::NAN),
+        "frame captured"
+    );
+}
+
 fn preferred_backend() -> ApiBackend {
     #[cfg(target_os = "windows")]
     {
@@ -345,35 +862,161 @@ fn read_frame(cam: &mut Camera) -> Result<Frame> {
 }
 
 fn capture_one(
-    capture: &mut Option<Camera>,
+    capture: &mut Option<Box<dyn Backend>>,
     device_index: i32,
+    kind: &DeviceKind,
+    simulate: bool,
     latest: &LatestFrameSlot,
-    out_dir: Option<&std::path::Path>,
-) -> Result<(String, chrono::DateTime<chrono::Local>, Option<f64>)> {
-    let out_dir = out_dir.context("no active session (set output dir first)")?;
+    output: Option<(&CaptureTarget, &mut u32)>,
+) -> Result<CapturedFrame> {
+    let (target, seq) = output.context("no active session (set output dir first)")?;
+    if matches!(kind, DeviceKind::Tethered(_)) && capture.is_none() {
+        *capture =
+            Some(open_backend(device_index, kind, simulate).context("open device for capture")?);
+    }
+    if let Some(cam) = capture.as_mut() {
+        std::fs::create_dir_all(&target.dir).context("create output dir")?;
+        let path = target.next_path(seq);
+        match cam.capture_still(&path.with_extension("")) {
+            Some(still) => return score_still(still?),
+            // Nothing was written; the number goes to the streamed frame.
+            None => *seq -= 1,
+        }
+    }
     if let Some((_, frame, _)) = latest.get_latest() {
-        return save_frame(out_dir, &frame);
+        return save_frame(target, seq, &frame, chrono::Local::now());
     }
 
     let temp = if let Some(cam) = capture {
         cam
     } else {
-        capture.insert(open_device(device_index).context("open device for capture")?)
+        capture
+            .insert(open_backend(device_index, kind, simulate).context("open device for capture")?)
     };
 
-    let frame = read_frame(temp)?;
-    save_frame(out_dir, &frame)
+    let frame = temp
+        .read()?
+        .context("no frame from the camera yet; try again")?;
+    save_frame(target, seq, &frame, chrono::Local::now())
+}
+
+/// Captures from the picked camera and the second one. The second camera's
+/// latest frame is taken before the first capture starts, so both show the
+/// same moment even when the first is a tethered still.
+fn capture_pair(
+    capture: &mut Option<Box<dyn Backend>>,
+    device_index: i32,
+    kind: &DeviceKind,
+    simulate: bool,
+    latest: &LatestFrameSlot,
+    second: Option<&SecondRig>,
+    output: Option<(&CaptureTarget, &mut u32)>,
+) -> Result<[CapturedFrame; 2]> {
+    let rig = second.context("no second camera configured ([second_camera] in the config)")?;
+    let (target, seq) = output.context("no active session (set output dir first)")?;
+    let (_, other, _) = rig.latest.get_latest().with_context(|| {
+        format!(
+            "no frame from the {} camera yet; start the camera first",
+            rig.config.label
+        )
+    })?;
+    let created_at = chrono::Local::now();
+    let mut first = capture_one(
+        capture,
+        device_index,
+        kind,
+        simulate,
+        latest,
+        Some((target, &mut *seq)),
+    )?;
+    first.tag = Some(rig.config.primary_label.clone());
+    // The gray card was read off the picked camera, not this one.
+    let uncorrected = CaptureTarget {
+        white_balance: None,
+        ..target.clone()
+    };
+    let mut second = save_frame(&uncorrected, seq, &other, created_at)?;
+    second.tag = Some(rig.config.label.clone());
+    Ok([first, second])
+}
+
+/// Scores a still the backend already wrote, like [`save_frame`] does.
+fn score_still(path: PathBuf) -> Result<CapturedFrame> {
+    let created_at = chrono::Local::now();
+    let frame = image::open(&path)
+        .with_context(|| format!("decode {}", path.display()))?
+        .to_rgb8();
+    Ok(CapturedFrame {
+        path: path.to_string_lossy().to_string(),
+        created_at,
+        sharpness_score: laplacian_variance(&frame).ok(),
+        tag: None,
+    })
 }
 
 fn save_frame(
-    out_dir: &std::path::Path,
+    target: &CaptureTarget,
+    seq: &mut u32,
     frame: &Frame,
-) -> Result<(String, chrono::DateTime<chrono::Local>, Option<f64>)> {
-    std::fs::create_dir_all(out_dir).context("create output dir")?;
-    let created_at = chrono::Local::now();
-    let path = timestamped_capture_path(out_dir, "jpg")?;
+    created_at: chrono::DateTime<chrono::Local>,
+) -> Result<CapturedFrame> {
+    std::fs::create_dir_all(&target.dir).context("create output dir")?;
+    let mut corrected = None;
+    let frame: &Frame = match target.white_balance {
+        Some(gains) => corrected.insert({
+            let mut frame = frame.clone();
+            apply_gains(&mut frame, gains);
+            frame
+        }),
+        None => frame,
+    };
+    let path = target.next_path(seq);
     let path_str = path.to_string_lossy().to_string();
     let sharpness_score = laplacian_variance(frame).ok();
     frame.save(&path).context("write frame")?;
-    Ok((path_str, created_at, sharpness_score))
+    Ok(CapturedFrame {
+        path: path_str,
+        created_at,
+        sharpness_score,
+        tag: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    fn frame(sharp: bool) -> Frame {
+        RgbImage::from_fn(64, 64, |x, _| {
+            let v = if sharp {
+                if x < 32 { 0 } else { 255 }
+            } else {
+                (x * 4) as u8
+            };
+            Rgb([v, v, v])
+        })
+    }
+
+    #[test]
+    fn history_samples_and_keeps_the_last_second() {
+        let start = Instant::now();
+        let mut history = FrameHistory::default();
+        history.push(&frame(true), start);
+        // Within the sample interval: skipped.
+        history.push(&frame(false), start + Duration::from_millis(20));
+        assert_eq!(history.frames.len(), 1);
+        history.push(&frame(false), start + Duration::from_millis(300));
+        history.push(&frame(false), start + Duration::from_millis(600));
+
+        let now = start + Duration::from_millis(700);
+        let best = history.sharpest(now).expect("buffered frame");
+        assert_eq!(best.at, start);
+
+        // The sharp frame ages out of the window.
+        history.push(&frame(false), start + Duration::from_millis(1200));
+        assert_eq!(history.frames.len(), 3);
+        let later = start + Duration::from_millis(1250);
+        assert_ne!(history.sharpest(later).unwrap().at, start);
+    }
 }
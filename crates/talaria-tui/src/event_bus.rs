@@ -1,18 +1,26 @@
-use crossbeam_channel::{Receiver, Sender, unbounded};
+use std::sync::Arc;
 
-use crate::types::{AppCommand, AppEvent};
+use crossbeam_channel::{Receiver, Sender, bounded, unbounded};
+use parking_lot::Mutex;
+
+use crate::types::{AppCommand, AppEvent, BulkAction, CaptureEvent, JobStatus, StorageEvent};
+
+/// Queued events before workers block on `send` until the UI catches up.
+pub const EVENT_CAPACITY: usize = 1024;
+/// Most queued events the UI applies between two draws.
+pub const MAX_EVENTS_PER_FRAME: usize = 256;
 
 pub struct EventBus {
     pub command_tx: Sender<AppCommand>,
     pub command_rx: Receiver<AppCommand>,
-    pub event_tx: Sender<AppEvent>,
-    pub event_rx: Receiver<AppEvent>,
+    pub event_tx: EventSender,
+    pub event_rx: EventReceiver,
 }
 
 impl EventBus {
     pub fn new() -> Self {
         let (command_tx, command_rx) = unbounded();
-        let (event_tx, event_rx) = unbounded();
+        let (event_tx, event_rx) = event_channel(EVENT_CAPACITY);
         Self {
             command_tx,
             command_rx,
@@ -21,3 +29,178 @@ impl EventBus {
         }
     }
 }
+
+/// High-frequency events where only the newest value matters. They skip the
+/// queue and overwrite each other, one slot per key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SlotKey {
+    CaptureStatus,
+    UploadProgress(String),
+    BulkProgress(BulkAction),
+}
+
+enum Route {
+    Queue,
+    Slot(SlotKey),
+    /// Queued, and drops any pending slot value it makes stale.
+    QueueClearing(SlotKey),
+}
+
+fn route(event: &AppEvent) -> Route {
+    match event {
+        AppEvent::Capture(CaptureEvent::Status(_)) => Route::Slot(SlotKey::CaptureStatus),
+        AppEvent::UploadJob(job) if job.status == JobStatus::InProgress => {
+            Route::Slot(SlotKey::UploadProgress(job.id.clone()))
+        }
+        AppEvent::UploadJob(job) => Route::QueueClearing(SlotKey::UploadProgress(job.id.clone())),
+        AppEvent::Storage(StorageEvent::BulkProgress { action, .. }) => {
+            Route::Slot(SlotKey::BulkProgress(*action))
+        }
+        AppEvent::Storage(StorageEvent::BulkFinished { action, .. }) => {
+            Route::QueueClearing(SlotKey::BulkProgress(*action))
+        }
+        _ => Route::Queue,
+    }
+}
+
+#[derive(Default)]
+struct Slots {
+    latest: Vec<(SlotKey, AppEvent)>,
+    closed: bool,
+}
+
+/// The UI has gone away; the event was dropped.
+#[derive(Debug)]
+pub struct Closed;
+
+/// The worker side of the event bus; a drop-in for `Sender<AppEvent>`.
+#[derive(Clone)]
+pub struct EventSender {
+    tx: Sender<AppEvent>,
+    slots: Arc<Mutex<Slots>>,
+}
+
+impl EventSender {
+    pub fn send(&self, event: AppEvent) -> Result<(), Closed> {
+        match route(&event) {
+            Route::Queue => self.tx.send(event).map_err(|_| Closed),
+            Route::QueueClearing(key) => {
+                self.slots.lock().latest.retain(|(k, _)| *k != key);
+                self.tx.send(event).map_err(|_| Closed)
+            }
+            Route::Slot(key) => {
+                let mut slots = self.slots.lock();
+                if slots.closed {
+                    return Err(Closed);
+                }
+                match slots.latest.iter_mut().find(|(k, _)| *k == key) {
+                    Some(slot) => slot.1 = event,
+                    None => slots.latest.push((key, event)),
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The UI side of the event bus.
+pub struct EventReceiver {
+    rx: Receiver<AppEvent>,
+    slots: Arc<Mutex<Slots>>,
+}
+
+impl EventReceiver {
+    /// Up to `max` queued events in order, then the newest value of every
+    /// coalesced slot.
+    pub fn drain(&self, max: usize) -> Vec<AppEvent> {
+        let mut events: Vec<AppEvent> = self.rx.try_iter().take(max).collect();
+        events.extend(
+            std::mem::take(&mut self.slots.lock().latest)
+                .into_iter()
+                .map(|(_, event)| event),
+        );
+        events
+    }
+}
+
+impl Drop for EventReceiver {
+    fn drop(&mut self) {
+        self.slots.lock().closed = true;
+    }
+}
+
+fn event_channel(capacity: usize) -> (EventSender, EventReceiver) {
+    let (tx, rx) = bounded(capacity);
+    let slots = Arc::new(Mutex::new(Slots::default()));
+    (
+        EventSender {
+            tx,
+            slots: slots.clone(),
+        },
+        EventReceiver { rx, slots },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CaptureStatus, UploadJob};
+
+    fn status(fps: f32) -> AppEvent {
+        AppEvent::Capture(CaptureEvent::Status(CaptureStatus {
+            streaming: true,
+            device_index: 0,
+            fps,
+            dropped_frames: 0,
+            frame_size: None,
+            device_url: None,
+            link: None,
+            countdown: None,
+            turntable: None,
+            second: None,
+        }))
+    }
+
+    fn upload(id: &str, status: JobStatus, progress: f32) -> AppEvent {
+        AppEvent::UploadJob(UploadJob {
+            id: id.to_string(),
+            status,
+            progress,
+            last_error: None,
+        })
+    }
+
+    #[test]
+    fn chatty_events_coalesce_and_the_queue_is_bounded() {
+        let (tx, rx) = event_channel(4);
+        for fps in 0..100 {
+            tx.send(status(fps as f32)).unwrap();
+            tx.send(upload("a", JobStatus::InProgress, fps as f32 / 100.0))
+                .unwrap();
+        }
+        tx.send(upload("b", JobStatus::InProgress, 0.5)).unwrap();
+        tx.send(upload("b", JobStatus::Completed, 1.0)).unwrap();
+
+        let events = rx.drain(MAX_EVENTS_PER_FRAME);
+        assert_eq!(events.len(), 3);
+        assert!(
+            matches!(&events[0], AppEvent::UploadJob(j) if j.id == "b" && j.status == JobStatus::Completed)
+        );
+        assert!(matches!(&events[1], AppEvent::Capture(CaptureEvent::Status(s)) if s.fps == 99.0));
+        assert!(matches!(&events[2], AppEvent::UploadJob(j) if j.id == "a" && j.progress == 0.99));
+
+        for _ in 0..4 {
+            tx.send(AppEvent::Activity(crate::types::ActivityEntry {
+                at: chrono::Local::now(),
+                severity: crate::types::Severity::Info,
+                message: String::new(),
+            }))
+            .unwrap();
+        }
+        assert!(tx.tx.is_full());
+        assert_eq!(rx.drain(2).len(), 2);
+
+        drop(rx);
+        assert!(tx.send(status(1.0)).is_err());
+    }
+}
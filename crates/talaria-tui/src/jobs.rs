@@ -0,0 +1,207 @@
+//! Cancellation of running jobs (uploads, structure and listing runs). A job
+//! registers under its [`JobKey`] while it runs and wraps its Hermes calls in
+//! [`JobGuard::run`]; cancelling the key drops the call in flight. Jobs only
+//! run cancellable steps before they write anything, so a cancelled job
+//! leaves the manifests as they were.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use tokio::runtime::Runtime;
+
+use crate::shutdown::ShutdownToken;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JobKind {
+    Upload,
+    Structure,
+    Listing,
+}
+
+impl JobKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            JobKind::Upload => "upload",
+            JobKind::Structure => "structure generation",
+            JobKind::Listing => "listing generation",
+        }
+    }
+}
+
+/// One job of a kind runs per product at a time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JobKey {
+    pub kind: JobKind,
+    pub product_id: String,
+}
+
+impl JobKey {
+    pub fn new(kind: JobKind, product_id: impl Into<String>) -> Self {
+        Self {
+            kind,
+            product_id: product_id.into(),
+        }
+    }
+}
+
+/// Error of a step cut short by [`JobGuard::run`]; workers check for it to
+/// report a cancellation instead of a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Canceled;
+
+impl fmt::Display for Canceled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("canceled")
+    }
+}
+
+impl std::error::Error for Canceled {}
+
+pub fn is_canceled(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<Canceled>().is_some()
+}
+
+/// Tokens of the running jobs, shared by the UI (which cancels) and the
+/// workers (which register). Job tokens are children of the shutdown token,
+/// so exiting cancels every job too.
+#[derive(Clone)]
+pub struct RunningJobs {
+    shutdown: ShutdownToken,
+    running: Arc<Mutex<HashMap<JobKey, (u64, ShutdownToken)>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl RunningJobs {
+    pub fn new(shutdown: ShutdownToken) -> Self {
+        Self {
+            shutdown,
+            running: Arc::default(),
+            next_id: Arc::default(),
+        }
+    }
+
+    /// Registers a job, replacing (but not cancelling) an earlier one under
+    /// the same key.
+    pub fn register(&self, key: JobKey) -> JobGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let token = self.shutdown.child_token();
+        self.running.lock().insert(key.clone(), (id, token.clone()));
+        JobGuard {
+            jobs: self.clone(),
+            key,
+            id,
+            token,
+        }
+    }
+
+    /// Cancels the job running under `key`; false when there is none.
+    pub fn cancel(&self, key: &JobKey) -> bool {
+        match self.running.lock().get(key) {
+            Some((_, token)) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_running(&self, key: &JobKey) -> bool {
+        self.running.lock().contains_key(key)
+    }
+}
+
+/// A registered job; dropping it unregisters the job.
+pub struct JobGuard {
+    jobs: RunningJobs,
+    key: JobKey,
+    id: u64,
+    token: ShutdownToken,
+}
+
+impl JobGuard {
+    pub fn key(&self) -> &JobKey {
+        &self.key
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Runs `fut` to completion on `rt`, or drops it (aborting its request)
+    /// once the job is cancelled.
+    pub fn run<F, T, E>(&self, rt: &Runtime, fut: F) -> anyhow::Result<T>
+    where
+        F: Future<Output = Result<T, E>>,
+        E: Into<anyhow::Error>,
+    {
+        if self.is_canceled() {
+            return Err(Canceled.into());
+        }
+        rt.block_on(async {
+            tokio::select! {
+                biased;
+                _ = self.token.cancelled() => Err(Canceled.into()),
+                res = fut => res.map_err(Into::into),
+            }
+        })
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        let mut running = self.jobs.running.lock();
+        if running.get(&self.key).is_some_and(|(id, _)| *id == self.id) {
+            running.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn cancel_aborts_the_running_step_and_unregisters_on_drop() {
+        let rt = Runtime::new().unwrap();
+        let jobs = RunningJobs::new(ShutdownToken::new());
+        let key = JobKey::new(JobKind::Structure, "prod_1");
+        assert!(!jobs.cancel(&key));
+
+        let guard = jobs.register(key.clone());
+        assert_eq!(
+            guard.run(&rt, async { Ok::<_, anyhow::Error>(7) }).unwrap(),
+            7
+        );
+        let canceller = jobs.clone();
+        let cancel_key = key.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            assert!(canceller.cancel(&cancel_key));
+        });
+        let err = guard
+            .run(&rt, async {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Ok::<_, anyhow::Error>(())
+            })
+            .unwrap_err();
+        assert!(is_canceled(&err));
+        assert!(is_canceled(
+            &guard
+                .run(&rt, async { Ok::<_, anyhow::Error>(()) })
+                .unwrap_err()
+        ));
+
+        // A newer job under the same key outlives the older guard.
+        let newer = jobs.register(key.clone());
+        drop(guard);
+        assert!(jobs.is_running(&key));
+        assert!(!newer.is_canceled());
+        drop(newer);
+        assert!(!jobs.is_running(&key));
+    }
+}
@@ -0,0 +1,453 @@
+//! Remappable key bindings, loaded from the `[keys]` section of the config file.
+//!
+//! Each entry maps an action name to a key spec, e.g. `quit = "q"`,
+//! `capture = "C"`, `next_tab = "shift+tab"` or `save_sync = "ctrl+s"`.
+//! Navigation keys (arrows, Enter, Esc, Tab, Space, Backspace/Del) stay fixed.
+
+use std::collections::BTreeMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Where an action is active. Two actions may share a key only when their
+/// scopes never overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyScope {
+    Global,
+    Home,
+    Grid,
+    /// Shared by the Context, Structure and Listings views.
+    Workspace,
+    Context,
+    Structure,
+    Listings,
+    Analytics,
+}
+
+impl KeyScope {
+    fn overlaps(self, other: KeyScope) -> bool {
+        use KeyScope::*;
+        match (self, other) {
+            (Global, _) | (_, Global) => true,
+            (Workspace, Context | Structure | Listings)
+            | (Context | Structure | Listings, Workspace) => true,
+            (a, b) => a == b,
+        }
+    }
+}
+
+macro_rules! actions {
+    ($($variant:ident => ($name:literal, $default:literal, [$($scope:ident),+], $desc:literal)),+ $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum Action {
+            $($variant),+
+        }
+
+        impl Action {
+            pub const ALL: &'static [Action] = &[$(Action::$variant),+];
+
+            /// Name used in the `[keys]` config section.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Action::$variant => $name),+
+                }
+            }
+
+            fn default_spec(self) -> &'static str {
+                match self {
+                    $(Action::$variant => $default),+
+                }
+            }
+
+            pub fn scopes(self) -> &'static [KeyScope] {
+                match self {
+                    $(Action::$variant => &[$(KeyScope::$scope),+]),+
+                }
+            }
+
+            pub fn description(self) -> &'static str {
+                match self {
+                    $(Action::$variant => $desc),+
+                }
+            }
+        }
+    };
+}
+
+actions! {
+    Quit => ("quit", "q", [Global], "quit"),
+    Help => ("help", "?", [Global], "toggle help"),
+    Metrics => ("metrics", "f12", [Global], "toggle metrics overlay"),
+    NextTab => ("next_tab", "shift+tab", [Global], "next main tab"),
+    DismissToast => ("dismiss_toast", "x", [Global], "dismiss message"),
+    CopyRequestId => ("copy_request_id", "ctrl+y", [Global], "copy failed request's id"),
+    OpenDashboard => ("open_dashboard", "ctrl+o", [Global], "open Hermes dashboard"),
+    CaptureHotkey => ("capture_hotkey", "f8", [Global], "capture from any view"),
+    FailedJobs => ("failed_jobs", "j", [Home], "failed listing jobs"),
+    RefreshStats => ("refresh_stats", "r", [Analytics], "refresh analytics"),
+    NewProduct => ("new_product", "n", [Grid, Context], "new product"),
+    Search => ("search", "/", [Grid], "search products"),
+    MarkAll => ("mark_all", "a", [Grid], "mark all"),
+    Undo => ("undo", "u", [Grid, Context], "undo last delete"),
+    Trash => ("trash", "T", [Grid], "open trash"),
+    DeleteProduct => ("delete_product", "d", [Grid], "delete product"),
+    BulkUpload => ("bulk_upload", "U", [Grid], "upload marked"),
+    BulkEnrich => ("bulk_enrich", "E", [Grid], "enrich marked"),
+    BulkExport => ("bulk_export", "X", [Grid], "export marked"),
+    BulkDelete => ("bulk_delete", "D", [Grid], "delete marked"),
+    StatusFilter => ("status_filter", "f", [Grid], "status filter"),
+    MarketplaceFilter => ("marketplace_filter", "m", [Grid], "marketplace filter"),
+    DateFilter => ("date_filter", "t", [Grid], "date range"),
+    ClearFilters => ("clear_filters", "c", [Grid], "clear filters"),
+    SaveSync => ("save_sync", "S", [Workspace], "save + sync"),
+    BackToGrid => ("back_to_grid", "G", [Workspace], "back to grid"),
+    MediaGallery => ("media_gallery", "M", [Workspace], "media gallery"),
+    CopyImageUrls => ("copy_image_urls", "Y", [Workspace], "copy uploaded image URLs"),
+    CopyJobId => ("copy_job_id", "J", [Workspace], "copy listing job id"),
+    Edit => ("edit", "e", [Context, Structure, Listings], "edit"),
+    EditJson => ("edit_json", "E", [Structure, Listings], "edit JSON"),
+    ToggleCamera => ("toggle_camera", "t", [Context], "camera on/off"),
+    DevicePicker => ("device_picker", "v", [Context], "device picker"),
+    PrevDevice => ("prev_device", "d", [Context], "previous device"),
+    NextDevice => ("next_device", "D", [Context], "next device"),
+    Capture => ("capture", "c", [Context], "capture"),
+    RetroCapture => ("retro_capture", "R", [Context], "capture sharpest of last second"),
+    CountdownCapture => ("countdown_capture", "C", [Context], "capture after countdown"),
+    Turntable => ("turntable", "I", [Context], "turntable run (again to stop)"),
+    CaptureBoth => ("capture_both", "B", [Context], "capture from both cameras"),
+    GenerateStructure => ("generate_structure", "r", [Context, Structure], "generate structure"),
+    Draft => ("draft", "p", [Context, Listings], "draft listing"),
+    Publish => ("publish", "P", [Context, Listings], "publish listing"),
+    Grade => ("grade", "o", [Context, Listings], "grade condition"),
+    SuggestPrice => ("suggest_price", "$", [Listings], "suggest price"),
+    ScanBarcode => ("scan_barcode", "b", [Context], "scan for barcode"),
+    Measure => ("measure", "m", [Context], "measure item"),
+    GrayCard => ("gray_card", "W", [Context], "white balance from a gray card"),
+    ZoomAssist => ("zoom_assist", "z", [Context], "cycle preview zoom 1×/2×/4×"),
+    FocusPeaking => ("focus_peaking", "F", [Context], "toggle focus peaking"),
+    ShotList => ("shot_list", "T", [Context], "cycle session shot list"),
+    ShotLabel => ("shot_label", "l", [Context], "cycle frame's shot label"),
+    SetHero => ("set_hero", "h", [Context], "make frame the hero"),
+    OpenImage => ("open_image", "O", [Context], "open uploaded image in browser"),
+    GenerateListing => ("generate_listing", "g", [Structure, Listings], "full listing run"),
+    AddAspect => ("add_aspect", "a", [Listings], "add aspect"),
+    UploadImages => ("upload_images", "u", [Listings], "upload images"),
+    CancelJob => ("cancel_job", "c", [Structure, Listings], "cancel running job"),
+    CopyListingId => ("copy_listing_id", "y", [Listings], "copy listing id"),
+    OpenListing => ("open_listing", "O", [Listings], "open live listing in browser"),
+}
+
+impl Action {
+    /// Publishes, deletes or spends credits, which a capture-only station
+    /// may not do.
+    pub fn restricted(self) -> bool {
+        matches!(
+            self,
+            Action::DeleteProduct
+                | Action::BulkEnrich
+                | Action::BulkDelete
+                | Action::GenerateStructure
+                | Action::Draft
+                | Action::Publish
+                | Action::GenerateListing
+        )
+    }
+}
+
+/// A single key plus the modifiers that must be held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err("empty key".to_string());
+        }
+        // A lone "+" is a key, not a separator.
+        let (mods, key) = match spec.rsplit_once('+') {
+            Some((mods, "")) => (mods.trim_end_matches('+'), "+"),
+            Some((mods, key)) => (mods, key),
+            None => ("", spec),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for part in mods.split('+').filter(|p| !p.is_empty()) {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{other}'")),
+            };
+        }
+        let lower = key.to_ascii_lowercase();
+        let code = match lower.as_str() {
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            "backtab" => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "del" | "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            f if f.len() > 1 && f.starts_with('f') && f[1..].parse::<u8>().is_ok() => {
+                KeyCode::F(f[1..].parse().unwrap_or(1))
+            }
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => {
+                        // "shift+h" means "H"; terminals report it that way.
+                        if modifiers.contains(KeyModifiers::SHIFT) && c.is_ascii_alphabetic() {
+                            modifiers.remove(KeyModifiers::SHIFT);
+                            KeyCode::Char(c.to_ascii_uppercase())
+                        } else {
+                            KeyCode::Char(c)
+                        }
+                    }
+                    _ => return Err(format!("unknown key '{key}'")),
+                }
+            }
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    /// Unmodified navigation keys that cannot be rebound.
+    fn is_reserved(&self) -> bool {
+        self.modifiers.is_empty()
+            && matches!(
+                self.code,
+                KeyCode::Up
+                    | KeyCode::Down
+                    | KeyCode::Left
+                    | KeyCode::Right
+                    | KeyCode::Enter
+                    | KeyCode::Esc
+                    | KeyCode::Tab
+                    | KeyCode::Backspace
+                    | KeyCode::Delete
+                    | KeyCode::Char(' ')
+            )
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        // Shift is implied by the character itself (or by BackTab).
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        let ignore_shift = matches!(self.code, KeyCode::Char(_) | KeyCode::BackTab);
+        let wanted = if ignore_shift {
+            self.modifiers & relevant
+        } else {
+            self.modifiers & (relevant | KeyModifiers::SHIFT)
+        };
+        let got = if ignore_shift {
+            key.modifiers & relevant
+        } else {
+            key.modifiers & (relevant | KeyModifiers::SHIFT)
+        };
+        key.code == self.code && wanted == got
+    }
+
+    pub fn label(&self) -> String {
+        let mut out = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            out.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            out.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            out.push_str("Shift+");
+        }
+        match self.code {
+            KeyCode::BackTab => out.push_str("Shift+Tab"),
+            KeyCode::Tab => out.push_str("Tab"),
+            KeyCode::Enter => out.push_str("Enter"),
+            KeyCode::Esc => out.push_str("Esc"),
+            KeyCode::Char(' ') => out.push_str("Space"),
+            KeyCode::Char(c) => out.push(c),
+            KeyCode::Backspace => out.push_str("Backspace"),
+            KeyCode::Delete => out.push_str("Del"),
+            KeyCode::Insert => out.push_str("Ins"),
+            KeyCode::Home => out.push_str("Home"),
+            KeyCode::End => out.push_str("End"),
+            KeyCode::PageUp => out.push_str("PgUp"),
+            KeyCode::PageDown => out.push_str("PgDn"),
+            KeyCode::Up => out.push('↑'),
+            KeyCode::Down => out.push('↓'),
+            KeyCode::Left => out.push('←'),
+            KeyCode::Right => out.push('→'),
+            KeyCode::F(n) => out.push_str(&format!("F{n}")),
+            other => out.push_str(&format!("{other:?}")),
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: BTreeMap<Action, KeyBinding>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .map(|action| {
+                let binding =
+                    KeyBinding::parse(action.default_spec()).expect("default key specs are valid");
+                (*action, binding)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl KeyMap {
+    /// Applies user overrides on top of the defaults. Unknown actions and
+    /// unparsable keys are skipped; if the result has conflicting bindings all
+    /// overrides are dropped. Problems are returned as human-readable warnings.
+    pub fn from_overrides(overrides: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut map = Self::default();
+        for (name, spec) in overrides {
+            let Some(action) = Action::ALL.iter().find(|a| a.name() == name) else {
+                warnings.push(format!("[keys] unknown action '{name}' ignored"));
+                continue;
+            };
+            match KeyBinding::parse(spec) {
+                Ok(binding) if binding.is_reserved() => warnings.push(format!(
+                    "[keys] {name} = \"{spec}\": {} is reserved for navigation",
+                    binding.label()
+                )),
+                Ok(binding) => {
+                    map.bindings.insert(*action, binding);
+                }
+                Err(err) => warnings.push(format!("[keys] {name} = \"{spec}\": {err}")),
+            }
+        }
+        let conflicts = map.conflicts();
+        if !conflicts.is_empty() {
+            for (a, b) in &conflicts {
+                warnings.push(format!(
+                    "[keys] {} and {} are both bound to {}",
+                    a.name(),
+                    b.name(),
+                    map.label(*a)
+                ));
+            }
+            warnings.push("[keys] conflicting bindings; using default keys".to_string());
+            map = Self::default();
+        }
+        (map, warnings)
+    }
+
+    /// Pairs of actions that share a key in an overlapping scope.
+    pub fn conflicts(&self) -> Vec<(Action, Action)> {
+        let mut out = Vec::new();
+        for (i, a) in Action::ALL.iter().enumerate() {
+            for b in &Action::ALL[i + 1..] {
+                let same_key = self.bindings.get(a) == self.bindings.get(b);
+                let overlap = a
+                    .scopes()
+                    .iter()
+                    .any(|sa| b.scopes().iter().any(|sb| sa.overlaps(*sb)));
+                if same_key && overlap {
+                    out.push((*a, *b));
+                }
+            }
+        }
+        out
+    }
+
+    pub fn matches(&self, action: Action, key: &KeyEvent) -> bool {
+        self.bindings
+            .get(&action)
+            .is_some_and(|binding| binding.matches(key))
+    }
+
+    /// Display label for the key bound to `action`, e.g. `q` or `Ctrl+S`.
+    pub fn label(&self, action: Action) -> String {
+        self.bindings
+            .get(&action)
+            .map(KeyBinding::label)
+            .unwrap_or_default()
+    }
+
+    /// `"<key> <description>"` for footer hints.
+    pub fn hint(&self, action: Action) -> String {
+        format!("{} {}", self.label(action), action.description())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn defaults_have_no_conflicts() {
+        assert!(KeyMap::default().conflicts().is_empty());
+    }
+
+    #[test]
+    fn parses_specs() {
+        let b = KeyBinding::parse("ctrl+s").unwrap();
+        assert_eq!(b.code, KeyCode::Char('s'));
+        assert_eq!(b.modifiers, KeyModifiers::CONTROL);
+        assert_eq!(
+            KeyBinding::parse("shift+h").unwrap().code,
+            KeyCode::Char('H')
+        );
+        assert_eq!(
+            KeyBinding::parse("shift+tab").unwrap().code,
+            KeyCode::BackTab
+        );
+        assert_eq!(KeyBinding::parse("+").unwrap().code, KeyCode::Char('+'));
+        assert!(KeyBinding::parse("hyper+x").is_err());
+    }
+
+    #[test]
+    fn overrides_apply_and_conflicts_fall_back() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("quit".to_string(), "ctrl+q".to_string());
+        let (map, warnings) = KeyMap::from_overrides(&overrides);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert!(map.matches(
+            Action::Quit,
+            &key(KeyCode::Char('q'), KeyModifiers::CONTROL)
+        ));
+
+        // "c" is capture in the context view; quit is global.
+        overrides.insert("quit".to_string(), "c".to_string());
+        let (map, warnings) = KeyMap::from_overrides(&overrides);
+        assert!(!warnings.is_empty());
+        assert!(map.matches(Action::Quit, &key(KeyCode::Char('q'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn shifted_chars_match_with_shift_modifier() {
+        let map = KeyMap::default();
+        assert!(map.matches(Action::Trash, &key(KeyCode::Char('T'), KeyModifiers::SHIFT)));
+        assert!(map.matches(Action::NextTab, &key(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert!(!map.matches(
+            Action::Quit,
+            &key(KeyCode::Char('q'), KeyModifiers::CONTROL)
+        ));
+    }
+}
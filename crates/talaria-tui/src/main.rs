@@ -1,7 +1,11 @@
 mod app;
 mod camera;
 mod event_bus;
+mod jobs;
+mod keymap;
+mod pedal;
 mod preview;
+mod shutdown;
 mod storage;
 mod types;
 mod ui;
@@ -23,33 +27,70 @@ use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 
 use camera::LatestFrameSlot;
-use event_bus::EventBus;
+use event_bus::{EventBus, MAX_EVENTS_PER_FRAME};
+use shutdown::{SHUTDOWN_TIMEOUT, ShutdownToken, Workers};
 use talaria_core::client::HermesClient;
-use talaria_core::config::{Config, EbaySettings};
-use types::{AppCommand, AppEvent, CaptureCommand, PreviewCommand, StorageCommand};
+use talaria_core::config::{Config, EbaySettings, PreviewBackend};
+use talaria_core::i18n;
+use talaria_core::logging;
+use talaria_core::metrics;
+use types::{
+    AccountCommand, AppCommand, AppEvent, CaptureCommand, HealthCommand, PreviewCacheCommand,
+    PreviewCommand, StorageCommand, UploadCommand,
+};
 
 fn main() -> Result<()> {
-    let captures_dir = storage::default_captures_dir();
+    let captures_dir = talaria_core::config::captures_dir();
     storage::ensure_base_dirs(&captures_dir)?;
-    let stderr_log = captures_dir.join("logs").join(format!(
+    let logs_dir = logging::logs_dir(&captures_dir);
+    let stderr_log = logs_dir.join(format!(
         "talaria-tui-{}.stderr.log",
         chrono::Local::now().format("%Y%m%d_%H%M%S")
     ));
     let stderr_path = util::log_redirect::redirect_stderr_to_file(&stderr_log).ok();
     let activity_log_path = storage::activity_log_path(&captures_dir);
 
+    let simulate_arg = std::env::args().skip(1).any(|arg| arg == "--simulate");
     let mut startup_warnings = Vec::new();
     let mut config_info = app::ConfigInfo {
         preview_height_pct: talaria_core::config::DEFAULT_TUI_PREVIEW_HEIGHT_PCT,
+        simulate: simulate_arg,
         ..app::ConfigInfo::default()
     };
     let mut ebay_settings = EbaySettings::default();
     let mut llm_ingest = None;
     let mut llm_aspects = None;
     let mut prompt_rules = None;
+    let mut keys = keymap::KeyMap::default();
+    let mut use_signed_urls = false;
+    let mut preview_backend = PreviewBackend::default();
+    let mut log_filter = None;
     let hermes = match Config::load() {
-        Ok(cfg) => {
+        Ok(mut cfg) => {
+            cfg.simulate |= simulate_arg;
+            i18n::init(cfg.locale.as_deref());
+            config_info.simulate = cfg.simulate;
             config_info.base_url = Some(cfg.base_url.clone());
+            config_info.org_id = cfg.org_id.clone();
+            config_info.supabase = cfg.supabase.clone();
+            config_info.captures_dir = cfg.tui_captures_dir.clone();
+            config_info.preview_backend = cfg.tui_preview_backend;
+            config_info.camera_urls = cfg.tui_camera_urls.clone();
+            config_info.condition_grades = cfg.condition_grades.clone();
+            config_info.measurement = cfg.measurement.clone();
+            config_info.white_balance = cfg.white_balance;
+            config_info.package_templates = cfg.package_templates.clone();
+            config_info.session_templates = cfg.session_templates.clone();
+            config_info.pricing = cfg.pricing.clone();
+            config_info.quality = cfg.quality.clone();
+            config_info.capture_triggers = cfg.tui_capture_triggers.clone();
+            config_info.capture_files = cfg.tui_capture_files.clone();
+            config_info.second_camera = cfg.tui_second_camera.clone();
+            config_info.mode = cfg.mode;
+            config_info.dashboard_url = cfg.dashboard_url.clone();
+            storage::set_operator(cfg.operator_name());
+            preview_backend = cfg.tui_preview_backend;
+            log_filter = cfg.log_filter.clone();
             config_info.hermes_api_key_present = cfg.api_key.is_some();
             config_info.preview_height_pct = cfg
                 .tui_preview_height_pct
@@ -58,12 +99,19 @@ fn main() -> Result<()> {
             llm_ingest = cfg.llm_ingest.clone();
             llm_aspects = cfg.llm_aspects.clone();
             prompt_rules = cfg.prompt_rules.clone();
+            use_signed_urls = cfg.use_signed_urls();
+            let (loaded_keys, key_warnings) = keymap::KeyMap::from_overrides(&cfg.tui_keys);
+            keys = loaded_keys;
+            startup_warnings.extend(key_warnings);
             if cfg.api_key.is_none() {
                 startup_warnings.push(
                     "HERMES_API_KEY missing; run `talaria auth login` to enable online mode."
                         .to_string(),
                 );
             }
+            if let Some(addr) = &cfg.metrics_listen {
+                startup_warnings.extend(serve_metrics(addr));
+            }
             match HermesClient::new(cfg) {
                 Ok(client) => {
                     config_info.online_ready = client.has_api_key();
@@ -79,9 +127,26 @@ fn main() -> Result<()> {
         }
         Err(err) => {
             startup_warnings.push(format!("Config load failed (offline mode): {err}"));
+            i18n::init(None);
+            storage::set_operator(Config::default().operator_name());
             None
         }
     };
+    if let Err(err) = logging::init("talaria-tui", &logs_dir, log_filter.as_deref()) {
+        startup_warnings.push(format!("File logging disabled: {err}"));
+    }
+    tracing::info!(
+        simulate = config_info.simulate,
+        online = config_info.online_ready,
+        "tui started"
+    );
+
+    if config_info.simulate {
+        startup_warnings.push(
+            "Simulation mode: uploads return fake URLs and the camera shows a test pattern."
+                .to_string(),
+        );
+    }
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -90,45 +155,96 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
     let _guard = TerminalGuard;
 
-    let terminal_preview = app::detect_terminal_preview();
+    let terminal_preview = app::detect_terminal_preview(preview_backend);
 
     let bus = EventBus::new();
     let (capture_cmd_tx, capture_cmd_rx) = unbounded::<CaptureCommand>();
     let (preview_cmd_tx, preview_cmd_rx) = unbounded::<PreviewCommand>();
+    let (preview_cache_tx, preview_cache_rx) = unbounded::<PreviewCacheCommand>();
     let (upload_cmd_tx, upload_cmd_rx) = unbounded();
     let (account_cmd_tx, account_cmd_rx) = unbounded();
+    let (health_cmd_tx, health_cmd_rx) = unbounded::<HealthCommand>();
     let (storage_cmd_tx, storage_cmd_rx) = unbounded::<StorageCommand>();
 
     let slot = LatestFrameSlot::shared();
-    let capture_handle =
-        camera::spawn_capture_thread(capture_cmd_rx, bus.event_tx.clone(), slot.clone());
-    let preview_handle = if terminal_preview.is_some() {
-        None
-    } else {
-        Some(preview::spawn_preview_thread(
-            preview_cmd_rx,
+    let shutdown = ShutdownToken::new();
+    let jobs = jobs::RunningJobs::new(shutdown.clone());
+    let mut threads = Workers::default();
+    // Capture goes first so the camera is released before anything else is
+    // waited on.
+    threads.add(
+        "capture",
+        camera::spawn_capture_thread(
+            capture_cmd_rx,
             bus.event_tx.clone(),
             slot.clone(),
-        ))
-    };
-    let upload_handle = workers::upload::spawn_upload_worker(
-        captures_dir.clone(),
-        hermes.clone(),
-        upload_cmd_rx,
-        bus.event_tx.clone(),
+            config_info.second_camera.clone(),
+            config_info.simulate,
+            shutdown.clone(),
+        ),
     );
-    let account_handle = workers::account::spawn_account_worker(
-        hermes.clone(),
-        account_cmd_rx,
-        bus.event_tx.clone(),
+    if terminal_preview.is_none() {
+        threads.add(
+            "preview",
+            preview::spawn_preview_thread(preview_cmd_rx, bus.event_tx.clone(), slot.clone()),
+        );
+    }
+    threads.add(
+        "preview-cache",
+        workers::previews::spawn_preview_cache_worker(
+            captures_dir.clone(),
+            preview_cache_rx,
+            bus.event_tx.clone(),
+        ),
+    );
+    threads.add(
+        "upload",
+        workers::upload::spawn_upload_worker(
+            captures_dir.clone(),
+            hermes.clone(),
+            upload_cmd_rx,
+            bus.event_tx.clone(),
+            shutdown.clone(),
+            jobs.clone(),
+        ),
+    );
+    threads.add(
+        "account",
+        workers::account::spawn_account_worker(
+            hermes.clone(),
+            account_cmd_rx,
+            bus.event_tx.clone(),
+        ),
     );
-    let storage_handle = storage::worker::spawn_storage_worker(
-        captures_dir.clone(),
-        hermes.clone(),
-        storage_cmd_rx,
-        bus.event_tx.clone(),
+    threads.add(
+        "health",
+        workers::health::spawn_health_worker(hermes.clone(), health_cmd_rx, bus.event_tx.clone()),
     );
+    threads.add(
+        "storage",
+        storage::worker::spawn_storage_worker(
+            captures_dir.clone(),
+            hermes.clone(),
+            use_signed_urls,
+            storage_cmd_rx,
+            bus.event_tx.clone(),
+            jobs.clone(),
+        ),
+    );
+
+    if let Some(device) = config_info.capture_triggers.pedal_device.clone() {
+        threads.add(
+            "pedal",
+            pedal::spawn_pedal_thread(
+                device,
+                config_info.capture_triggers.pedal_keys.clone(),
+                bus.event_tx.clone(),
+                shutdown.clone(),
+            ),
+        );
+    }
 
+    let reload_event_tx = bus.event_tx.clone();
     let router_handle = std::thread::spawn(move || {
         while let Ok(cmd) = bus.command_rx.recv() {
             match cmd {
@@ -138,6 +254,9 @@ fn main() -> Result<()> {
                 AppCommand::Preview(cmd) => {
                     let _ = preview_cmd_tx.send(cmd);
                 }
+                AppCommand::PreviewCache(cmd) => {
+                    let _ = preview_cache_tx.send(cmd);
+                }
                 AppCommand::Upload(cmd) => {
                     let _ = upload_cmd_tx.send(cmd);
                 }
@@ -147,12 +266,54 @@ fn main() -> Result<()> {
                 AppCommand::Storage(cmd) => {
                     let _ = storage_cmd_tx.send(cmd);
                 }
+                AppCommand::ReloadConfig => {
+                    let cfg = match Config::load() {
+                        Ok(mut cfg) => {
+                            cfg.simulate |= simulate_arg;
+                            cfg
+                        }
+                        Err(err) => {
+                            let _ = reload_event_tx.send(AppEvent::ConfigReloaded {
+                                config: None,
+                                online_ready: false,
+                                error: Some(format!("Config load failed: {err}")),
+                            });
+                            continue;
+                        }
+                    };
+                    let use_signed_urls = cfg.use_signed_urls();
+                    let (hermes, error) = match HermesClient::new(cfg.clone()) {
+                        Ok(client) => (Some(client), None),
+                        Err(err) => (None, Some(format!("Hermes client unavailable: {err}"))),
+                    };
+                    let online_ready = hermes.as_ref().is_some_and(|h| h.has_api_key());
+                    let _ = capture_cmd_tx.send(CaptureCommand::SetSecondCamera(
+                        cfg.tui_second_camera.clone(),
+                    ));
+                    let _ =
+                        upload_cmd_tx.send(UploadCommand::SetClient(hermes.clone().map(Box::new)));
+                    let _ = account_cmd_tx
+                        .send(AccountCommand::SetClient(hermes.clone().map(Box::new)));
+                    let _ =
+                        health_cmd_tx.send(HealthCommand::SetClient(hermes.clone().map(Box::new)));
+                    let _ = storage_cmd_tx.send(StorageCommand::SetClient {
+                        hermes,
+                        use_signed_urls,
+                    });
+                    let _ = reload_event_tx.send(AppEvent::ConfigReloaded {
+                        config: Some(Box::new(cfg)),
+                        online_ready,
+                        error,
+                    });
+                }
                 AppCommand::Shutdown => {
                     let _ = capture_cmd_tx.send(CaptureCommand::Shutdown);
                     let _ = preview_cmd_tx.send(PreviewCommand::Shutdown);
-                    let _ = upload_cmd_tx.send(crate::types::UploadCommand::Shutdown);
-                    let _ = account_cmd_tx.send(crate::types::AccountCommand::Shutdown);
-                    let _ = storage_cmd_tx.send(crate::types::StorageCommand::Shutdown);
+                    let _ = preview_cache_tx.send(PreviewCacheCommand::Shutdown);
+                    let _ = upload_cmd_tx.send(UploadCommand::Shutdown);
+                    let _ = account_cmd_tx.send(AccountCommand::Shutdown);
+                    let _ = health_cmd_tx.send(HealthCommand::Shutdown);
+                    let _ = storage_cmd_tx.send(StorageCommand::Shutdown);
                     break;
                 }
             }
@@ -168,22 +329,30 @@ fn main() -> Result<()> {
         llm_ingest,
         llm_aspects,
         prompt_rules,
+        keys,
         startup_warnings,
         slot.clone(),
         terminal_preview,
+        jobs,
     );
     let command_tx = bus.command_tx.clone();
     let res = run_app(&mut terminal, &mut app, bus.event_rx, command_tx);
 
+    shutdown.cancel();
     let _ = bus.command_tx.send(AppCommand::Shutdown);
-    let _ = capture_handle.join();
-    if let Some(handle) = preview_handle {
-        let _ = handle.join();
+    threads.add("router", router_handle);
+    let report = threads.join_all(SHUTDOWN_TIMEOUT);
+    if !report.is_clean() {
+        // stderr is the session's log file; the activity log shows it on the
+        // next start too.
+        eprintln!("talaria-tui: {}", report.summary());
+        app.record_shutdown(&report);
+    }
+    if let Some(path) = metrics::default_totals_path()
+        && let Err(err) = metrics::flush_totals(metrics::global(), &path)
+    {
+        eprintln!("talaria-tui: metrics totals not saved: {err}");
     }
-    let _ = upload_handle.join();
-    let _ = account_handle.join();
-    let _ = storage_handle.join();
-    let _ = router_handle.join();
 
     res
 }
@@ -191,13 +360,13 @@ fn main() -> Result<()> {
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut app::AppState,
-    app_event_rx: crossbeam_channel::Receiver<AppEvent>,
+    app_event_rx: event_bus::EventReceiver,
     command_tx: crossbeam_channel::Sender<AppCommand>,
 ) -> Result<()> {
     loop {
         terminal.draw(|f| ui::draw(f, app))?;
 
-        while let Ok(msg) = app_event_rx.try_recv() {
+        for msg in app_event_rx.drain(MAX_EVENTS_PER_FRAME) {
             app.apply_event(msg);
         }
         app.tick();
@@ -223,6 +392,21 @@ fn run_app(
     Ok(())
 }
 
+/// Starts the Prometheus listener; returns a warning when it cannot.
+#[cfg(feature = "prometheus")]
+fn serve_metrics(addr: &str) -> Option<String> {
+    metrics::serve_prometheus(addr)
+        .err()
+        .map(|err| format!("Metrics listener not started: {err}"))
+}
+
+#[cfg(not(feature = "prometheus"))]
+fn serve_metrics(addr: &str) -> Option<String> {
+    Some(format!(
+        "metrics_listen = {addr} ignored; this build lacks the `prometheus` feature."
+    ))
+}
+
 struct TerminalGuard;
 
 impl Drop for TerminalGuard {
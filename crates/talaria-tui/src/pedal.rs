@@ -0,0 +1,197 @@
+//! USB foot pedals as capture triggers. A pedal enumerates as a keyboard;
+//! the device named by `capture_triggers.pedal_device` is read directly and
+//! grabbed, so a press fires a capture even while another window has focus
+//! and does not also type into the terminal.
+//!
+//! Only Linux evdev devices can be read. Elsewhere, program the pedal to
+//! send the `capture_hotkey` key instead.
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::event_bus::EventSender;
+use crate::shutdown::ShutdownToken;
+use crate::types::{AppEvent, PedalEvent};
+
+/// Wait before reopening a device that is missing or was unplugged.
+const REOPEN_INTERVAL: Duration = Duration::from_secs(2);
+
+pub fn spawn_pedal_thread(
+    device: PathBuf,
+    keys: Vec<u16>,
+    event_tx: EventSender,
+    shutdown: ShutdownToken,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        // Problems are shown once, not on every reopen attempt.
+        let mut reported = false;
+        while !shutdown.is_cancelled() {
+            let result = evdev::open(&device).and_then(|file| {
+                reported = false;
+                tracing::info!(device = %device.display(), "foot pedal connected");
+                evdev::read_presses(&file, &shutdown, |code| {
+                    if keys.is_empty() || keys.contains(&code) {
+                        let _ = event_tx.send(AppEvent::Pedal(PedalEvent::Pressed));
+                    }
+                })
+            });
+            let Err(err) = result else {
+                continue;
+            };
+            tracing::warn!(device = %device.display(), error = %err, "foot pedal unavailable");
+            if !reported {
+                reported = true;
+                let _ = event_tx.send(AppEvent::Pedal(PedalEvent::Error(format!(
+                    "Foot pedal {}: {err:#}",
+                    device.display()
+                ))));
+            }
+            if !evdev::SUPPORTED {
+                return;
+            }
+            let mut waited = Duration::ZERO;
+            while waited < REOPEN_INTERVAL && !shutdown.is_cancelled() {
+                thread::sleep(Duration::from_millis(100));
+                waited += Duration::from_millis(100);
+            }
+        }
+    })
+}
+
+#[cfg(target_os = "linux")]
+mod evdev {
+    use std::fs::File;
+    use std::io::{ErrorKind, Read};
+    use std::os::fd::AsRawFd;
+    use std::path::Path;
+
+    use anyhow::{Context, Result, bail};
+
+    use crate::shutdown::ShutdownToken;
+
+    pub const SUPPORTED: bool = true;
+
+    /// `_IOW('E', 0x90, int)`: exclusive access to the device.
+    const EVIOCGRAB: u32 = 0x4004_4590;
+    const EV_KEY: u16 = 1;
+    /// Key event values: 0 release, 1 press, 2 autorepeat.
+    const KEY_PRESS: i32 = 1;
+    /// How long a read waits before checking for shutdown.
+    const POLL_TIMEOUT_MS: i32 = 200;
+
+    pub fn open(path: &Path) -> Result<File> {
+        let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+        // Safety: EVIOCGRAB takes an int flag on an fd we own; the grab
+        // ends when the file is closed.
+        if unsafe { libc::ioctl(file.as_raw_fd(), EVIOCGRAB as _, 1 as libc::c_int) } < 0 {
+            let err = std::io::Error::last_os_error();
+            bail!(
+                "grab {} (not an input event device?): {err}",
+                path.display()
+            );
+        }
+        Ok(file)
+    }
+
+    /// Calls `on_press` with the code of every key pressed until shutdown
+    /// (`Ok`) or the device goes away (`Err`).
+    pub fn read_presses(
+        mut file: &File,
+        shutdown: &ShutdownToken,
+        mut on_press: impl FnMut(u16),
+    ) -> Result<()> {
+        let mut buf = [0u8; 64 * std::mem::size_of::<libc::input_event>()];
+        while !shutdown.is_cancelled() {
+            let mut pollfd = libc::pollfd {
+                fd: file.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // Safety: one valid pollfd for the duration of the call.
+            let ready = unsafe { libc::poll(&mut pollfd, 1, POLL_TIMEOUT_MS) };
+            if ready < 0 {
+                let err = std::io::Error::last_os_error();
+                if err.kind() == ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err).context("poll pedal");
+            }
+            if ready == 0 {
+                continue;
+            }
+            let read = match file.read(&mut buf) {
+                Ok(0) => bail!("device closed"),
+                Ok(read) => read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err).context("disconnected"),
+            };
+            key_presses(&buf[..read]).for_each(&mut on_press);
+        }
+        Ok(())
+    }
+
+    /// Codes of the key presses among raw `input_event` records.
+    pub(super) fn key_presses(buf: &[u8]) -> impl Iterator<Item = u16> + '_ {
+        let time = std::mem::size_of::<libc::timeval>();
+        buf.chunks_exact(std::mem::size_of::<libc::input_event>())
+            .filter_map(move |event| {
+                let kind = u16::from_ne_bytes([event[time], event[time + 1]]);
+                let code = u16::from_ne_bytes([event[time + 2], event[time + 3]]);
+                let value = i32::from_ne_bytes(event[time + 4..time + 8].try_into().ok()?);
+                (kind == EV_KEY && value == KEY_PRESS).then_some(code)
+            })
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod evdev {
+    use std::fs::File;
+    use std::path::Path;
+
+    use anyhow::{Result, bail};
+
+    use crate::shutdown::ShutdownToken;
+
+    pub const SUPPORTED: bool = false;
+
+    pub fn open(_path: &Path) -> Result<File> {
+        bail!("pedal devices can only be read on Linux; map the pedal to capture_hotkey")
+    }
+
+    pub fn read_presses(
+        _file: &File,
+        _shutdown: &ShutdownToken,
+        _on_press: impl FnMut(u16),
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::evdev::key_presses;
+
+    fn event(kind: u16, code: u16, value: i32) -> Vec<u8> {
+        let mut bytes = vec![0u8; std::mem::size_of::<libc::timeval>()];
+        bytes.extend(kind.to_ne_bytes());
+        bytes.extend(code.to_ne_bytes());
+        bytes.extend(value.to_ne_bytes());
+        bytes.resize(std::mem::size_of::<libc::input_event>(), 0);
+        bytes
+    }
+
+    #[test]
+    fn only_key_presses_are_reported() {
+        let buf = [
+            event(4, 4, 0x70005), // EV_MSC scan code
+            event(1, 48, 1),      // KEY_B pressed
+            event(0, 0, 0),       // EV_SYN
+            event(1, 48, 2),      // autorepeat
+            event(1, 48, 0),      // released
+            event(1, 30, 1),      // KEY_A pressed
+        ]
+        .concat();
+        assert_eq!(key_presses(&buf).collect::<Vec<_>>(), [48, 30]);
+    }
+}
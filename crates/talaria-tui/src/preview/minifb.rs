@@ -3,15 +3,23 @@ use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Receiver;
 use image::{Rgb, RgbImage};
-use minifb::{Window, WindowOptions};
+use minifb::{ScaleMode, Window, WindowOptions};
 
 use crate::camera::LatestFrameSlot;
+use crate::event_bus::EventSender;
 use crate::types::{AppEvent, PreviewCommand, PreviewEvent};
+use crate::util::assist::{PreviewAssist, draw_peaking};
+use crate::util::countdown::draw_countdown;
+use crate::util::downscale::{fill_argb, fit_within};
+use crate::util::measure::{MeasureOverlay, draw_overlay};
 
 const CAMERA_WINDOW: &str = "talaria-camera-preview";
 const IMAGE_WINDOW: &str = "talaria-image-preview";
+/// Frames are shrunk to fit a new window this big, then to the window's
+/// current size once it is open.
+const DEFAULT_BOUND: (u32, u32) = (1280, 720);
 
 struct WindowState {
     window: Window,
@@ -23,6 +31,7 @@ impl WindowState {
     fn new(title: &str, size: (usize, usize)) -> Result<Self, String> {
         let mut options = WindowOptions::default();
         options.resize = true;
+        options.scale_mode = ScaleMode::AspectRatioStretch;
         let window = Window::new(title, size.0, size.1, options).map_err(|e| e.to_string())?;
         Ok(Self {
             window,
@@ -31,13 +40,17 @@ impl WindowState {
         })
     }
 
+    /// The size frames should be downscaled to before they are drawn.
+    fn bound(&self) -> (u32, u32) {
+        let (width, height) = self.window.get_size();
+        (width as u32, height as u32)
+    }
+
+    /// Draws `frame` into the reused buffer; the window scales it to fit.
     fn update_frame(&mut self, frame: &RgbImage) -> Result<(), String> {
         let (width, height) = frame.dimensions();
-        let size = (width as usize, height as usize);
-        if self.size != size {
-            return Err("frame size changed".to_string());
-        }
-        fill_buffer(frame, &mut self.buffer);
+        self.size = (width as usize, height as usize);
+        fill_argb(frame, &mut self.buffer);
         self.window
             .update_with_buffer(&self.buffer, self.size.0, self.size.1)
             .map_err(|e| e.to_string())
@@ -57,7 +70,7 @@ impl WindowState {
 
 pub fn spawn_preview_thread(
     cmd_rx: Receiver<PreviewCommand>,
-    event_tx: Sender<AppEvent>,
+    event_tx: EventSender,
     latest: Arc<LatestFrameSlot>,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
@@ -68,6 +81,12 @@ pub fn spawn_preview_thread(
         let mut image_buffer: Option<RgbImage> = None;
         let mut camera_window: Option<WindowState> = None;
         let mut image_window: Option<WindowState> = None;
+        let mut camera_bound = DEFAULT_BOUND;
+        let mut image_bound = DEFAULT_BOUND;
+        let mut image_dirty = false;
+        let mut overlay: Option<MeasureOverlay> = None;
+        let mut countdown: Option<u32> = None;
+        let mut assist = PreviewAssist::default();
         let placeholder = RgbImage::from_pixel(640, 480, Rgb([12, 12, 12]));
 
         loop {
@@ -75,6 +94,7 @@ pub fn spawn_preview_thread(
                 match cmd {
                     PreviewCommand::SetEnabled(next) => {
                         enabled = next;
+                        last_seq = 0;
                         if !enabled {
                             camera_window = None;
                         }
@@ -87,6 +107,18 @@ pub fn spawn_preview_thread(
                             image_window = None;
                         }
                     }
+                    PreviewCommand::SetOverlay(next) => {
+                        overlay = next;
+                        last_seq = 0;
+                    }
+                    PreviewCommand::SetCountdown(next) => {
+                        countdown = next;
+                        last_seq = 0;
+                    }
+                    PreviewCommand::SetAssist(next) => {
+                        assist = next;
+                        last_seq = 0;
+                    }
                     PreviewCommand::Shutdown => return,
                 }
             }
@@ -98,22 +130,47 @@ pub fn spawn_preview_thread(
             }
 
             if enabled {
-                let frame = if let Some((seq, frame, _)) = latest.get_latest() {
-                    if seq != last_seq {
+                let bound = camera_window
+                    .as_ref()
+                    .map(WindowState::bound)
+                    .unwrap_or(DEFAULT_BOUND);
+                if bound != camera_bound {
+                    camera_bound = bound;
+                    last_seq = 0;
+                }
+                // Only new frames are converted, and only after shrinking
+                // them to the window, so a 4K stream costs what the window
+                // shows rather than what the sensor delivers.
+                let frame = latest.map_latest_since(last_seq, |frame| {
+                    let (mut small, scale) = fit_within(&assist.crop(frame), bound);
+                    if assist.peaking {
+                        draw_peaking(&mut small);
+                    }
+                    // The ruler starts at the frame's edge, so it is left out
+                    // of a zoomed view.
+                    if let Some(overlay) = overlay.filter(|_| !assist.zoomed()) {
+                        draw_overlay(&mut small, &overlay.scaled(scale));
+                    }
+                    if let Some(secs) = countdown {
+                        draw_countdown(&mut small, secs);
+                    }
+                    small
+                });
+                let frame = match frame {
+                    Some((seq, frame)) => {
                         last_seq = seq;
+                        Some(frame)
                     }
-                    Some(frame)
-                } else {
-                    None
+                    None if camera_window.is_none() => Some(placeholder.clone()),
+                    None => None,
                 };
 
-                if let Err(err) = render_frame(
-                    CAMERA_WINDOW,
-                    &mut camera_window,
-                    frame.as_ref().unwrap_or(&placeholder),
-                ) {
-                    let _ = event_tx
-                        .send(AppEvent::Preview(PreviewEvent::Error(err.to_string())));
+                let rendered = match frame {
+                    Some(frame) => render_frame(CAMERA_WINDOW, &mut camera_window, &frame),
+                    None => Ok(()),
+                };
+                if let Err(err) = rendered {
+                    let _ = event_tx.send(AppEvent::Preview(PreviewEvent::Error(err.to_string())));
                     enabled = false;
                     camera_window = None;
                 }
@@ -126,18 +183,28 @@ pub fn spawn_preview_thread(
                         Ok(img) => {
                             image_buffer = Some(img.to_rgb8());
                             image_loaded = Some(path.clone());
+                            image_dirty = true;
                         }
                         Err(err) => {
-                            let _ = event_tx.send(AppEvent::Preview(PreviewEvent::Error(
-                                err.to_string(),
-                            )));
+                            let _ = event_tx
+                                .send(AppEvent::Preview(PreviewEvent::Error(err.to_string())));
                         }
                     }
                 }
-                if let Some(img) = &image_buffer {
-                    if let Err(err) = render_frame(IMAGE_WINDOW, &mut image_window, img) {
-                        let _ = event_tx
-                            .send(AppEvent::Preview(PreviewEvent::Error(err.to_string())));
+                let bound = image_window
+                    .as_ref()
+                    .map(WindowState::bound)
+                    .unwrap_or(DEFAULT_BOUND);
+                if bound != image_bound || image_window.is_none() {
+                    image_bound = bound;
+                    image_dirty = true;
+                }
+                if let Some(img) = image_buffer.as_ref().filter(|_| image_dirty) {
+                    image_dirty = false;
+                    let (small, _) = fit_within(img, bound);
+                    if let Err(err) = render_frame(IMAGE_WINDOW, &mut image_window, &small) {
+                        let _ =
+                            event_tx.send(AppEvent::Preview(PreviewEvent::Error(err.to_string())));
                         image_window = None;
                     }
                 }
@@ -173,37 +240,17 @@ fn render_frame(
     window: &mut Option<WindowState>,
     frame: &RgbImage,
 ) -> Result<(), String> {
-    let size = (frame.width() as usize, frame.height() as usize);
     let needs_new = match window {
-        Some(win) => !win.window.is_open() || win.size != size,
+        Some(win) => !win.window.is_open(),
         None => true,
     };
     if needs_new {
+        let size = (frame.width() as usize, frame.height() as usize);
         *window = Some(WindowState::new(title, size)?);
     }
 
-    if let Some(win) = window.as_mut() {
-        if let Err(err) = win.update_frame(frame) {
-            if err.contains("frame size changed") {
-                *window = Some(WindowState::new(title, size)?);
-                if let Some(win) = window.as_mut() {
-                    win.update_frame(frame)?;
-                }
-            } else {
-                return Err(err);
-            }
-        }
-    }
-
-    Ok(())
-}
-
-fn fill_buffer(frame: &RgbImage, buffer: &mut Vec<u32>) {
-    let (width, height) = frame.dimensions();
-    let len = (width * height) as usize;
-    buffer.resize(len, 0);
-    for (idx, pixel) in frame.pixels().enumerate() {
-        let [r, g, b] = pixel.0;
-        buffer[idx] = 0xFF00_0000 | ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
+    match window.as_mut() {
+        Some(win) => win.update_frame(frame),
+        None => Ok(()),
     }
 }
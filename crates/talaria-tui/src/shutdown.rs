@@ -0,0 +1,110 @@
+//! Coordinated exit for the worker threads: a shared token they check (or
+//! await) between steps, and joins bounded by a deadline so one hung request
+//! cannot keep the TUI from quitting.
+
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+pub use tokio_util::sync::CancellationToken as ShutdownToken;
+
+/// How long exit waits for workers before abandoning them.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Named worker threads, joined in the order they were added.
+#[derive(Default)]
+pub struct Workers {
+    threads: Vec<(&'static str, JoinHandle<()>)>,
+}
+
+/// What happened to each worker at exit.
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    pub elapsed: Duration,
+    pub panicked: Vec<&'static str>,
+    /// Still running at the deadline; left to die with the process.
+    pub abandoned: Vec<&'static str>,
+}
+
+impl ShutdownReport {
+    pub fn is_clean(&self) -> bool {
+        self.panicked.is_empty() && self.abandoned.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.abandoned.is_empty() {
+            parts.push(format!("abandoned {}", self.abandoned.join(", ")));
+        }
+        if !self.panicked.is_empty() {
+            parts.push(format!("panicked {}", self.panicked.join(", ")));
+        }
+        format!(
+            "Shutdown after {:.1}s: {}",
+            self.elapsed.as_secs_f64(),
+            if parts.is_empty() {
+                "all workers stopped".to_string()
+            } else {
+                parts.join("; ")
+            }
+        )
+    }
+}
+
+impl Workers {
+    pub fn add(&mut self, name: &'static str, handle: JoinHandle<()>) {
+        self.threads.push((name, handle));
+    }
+
+    /// Waits up to `timeout` for every worker to finish. Threads still running
+    /// at the deadline are detached and reported rather than waited on.
+    pub fn join_all(self, timeout: Duration) -> ShutdownReport {
+        let started = Instant::now();
+        let deadline = started + timeout;
+        let mut report = ShutdownReport::default();
+        for (name, handle) in self.threads {
+            while !handle.is_finished() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(10));
+            }
+            if !handle.is_finished() {
+                report.abandoned.push(name);
+            } else if handle.join().is_err() {
+                report.panicked.push(name);
+            }
+        }
+        report.elapsed = started.elapsed();
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_all_abandons_workers_past_the_deadline() {
+        let token = ShutdownToken::new();
+        let mut workers = Workers::default();
+        let stops = token.clone();
+        workers.add(
+            "polite",
+            thread::spawn(move || {
+                while !stops.is_cancelled() {
+                    thread::sleep(Duration::from_millis(5));
+                }
+            }),
+        );
+        workers.add(
+            "hung",
+            thread::spawn(|| thread::sleep(Duration::from_secs(5))),
+        );
+        workers.add("broken", thread::spawn(|| panic!("worker bug")));
+
+        token.cancel();
+        let report = workers.join_all(Duration::from_millis(200));
+        assert_eq!(report.abandoned, vec!["hung"]);
+        assert_eq!(report.panicked, vec!["broken"]);
+        assert!(!report.is_clean());
+        assert!(report.elapsed < Duration::from_secs(2));
+        assert!(report.summary().contains("abandoned hung"));
+    }
+}
@@ -0,0 +1,223 @@
+//! Matches a product's local images to its Hermes media records for the
+//! media gallery. A local image is linked to a record by the media id or URL
+//! its manifest recorded at upload, else by the SHA-256 of its file; records
+//! and images left over are orphans on their side.
+
+use talaria_core::models::{Media, MediaPurpose};
+
+use super::ProductManifest;
+
+/// One row of the gallery: a local image, a Hermes media record, or both.
+#[derive(Debug, Clone)]
+pub struct GalleryItem {
+    pub rel_path: Option<String>,
+    /// The manifest's hero.
+    pub local_hero: bool,
+    pub remote: Option<Media>,
+    /// Linked by content hash only; the manifest does not record the link.
+    pub by_hash: bool,
+}
+
+impl GalleryItem {
+    /// A local image Hermes has no record of.
+    pub fn local_orphan(&self) -> bool {
+        self.rel_path.is_some() && self.remote.is_none()
+    }
+
+    /// A Hermes record with no local image.
+    pub fn remote_orphan(&self) -> bool {
+        self.rel_path.is_none() && self.remote.is_some()
+    }
+
+    pub fn remote_hero(&self) -> bool {
+        self.remote
+            .as_ref()
+            .is_some_and(|media| media.purpose == Some(MediaPurpose::Hero))
+    }
+}
+
+/// Local images in manifest order (the hero first when it is not among the
+/// images), then remote orphans by rank. `sha_of` hashes a local image; it is
+/// only called for images not linked by id or URL.
+pub fn reconcile(
+    product: &ProductManifest,
+    remote: Vec<Media>,
+    mut sha_of: impl FnMut(&str) -> Option<String>,
+) -> Vec<GalleryItem> {
+    let hero = product.hero_rel_path.as_deref();
+    let mut locals = Vec::new();
+    if let Some(rel) = hero.filter(|rel| !product.images.iter().any(|img| img.rel_path == *rel)) {
+        locals.push((
+            rel,
+            product.hero_media_id.as_deref(),
+            product.hero_uploaded_url.as_deref(),
+        ));
+    }
+    for img in &product.images {
+        let is_hero = hero == Some(img.rel_path.as_str());
+        locals.push((
+            img.rel_path.as_str(),
+            img.uploaded_media_id
+                .as_deref()
+                .or(product.hero_media_id.as_deref().filter(|_| is_hero)),
+            img.uploaded_url
+                .as_deref()
+                .or(product.hero_uploaded_url.as_deref().filter(|_| is_hero)),
+        ));
+    }
+
+    let mut remote: Vec<Option<Media>> = remote.into_iter().map(Some).collect();
+    let mut items = Vec::new();
+    let mut unlinked = Vec::new();
+    for (rel, media_id, url) in locals {
+        let linked = remote
+            .iter()
+            .position(|media| {
+                media.as_ref().is_some_and(|media| {
+                    media_id == Some(media.media_id.as_str()) || url == Some(media.url.as_str())
+                })
+            })
+            .and_then(|idx| remote[idx].take());
+        if linked.is_none() {
+            unlinked.push(items.len());
+        }
+        items.push(GalleryItem {
+            rel_path: Some(rel.to_string()),
+            local_hero: hero == Some(rel),
+            remote: linked,
+            by_hash: false,
+        });
+    }
+    // Hashing reads whole files, so it is left for images still unlinked and
+    // skipped when no record carries a hash.
+    for idx in unlinked {
+        if !remote.iter().flatten().any(|media| media.sha256.is_some()) {
+            break;
+        }
+        let Some(sha) = items[idx].rel_path.as_deref().and_then(&mut sha_of) else {
+            continue;
+        };
+        let found = remote.iter().position(|media| {
+            media.as_ref().is_some_and(|media| {
+                media
+                    .sha256
+                    .as_deref()
+                    .is_some_and(|remote_sha| remote_sha.eq_ignore_ascii_case(&sha))
+            })
+        });
+        if let Some(media) = found.and_then(|idx| remote[idx].take()) {
+            items[idx].remote = Some(media);
+            items[idx].by_hash = true;
+        }
+    }
+
+    let mut orphans: Vec<Media> = remote.into_iter().flatten().collect();
+    orphans.sort_by(|a, b| {
+        a.rank
+            .unwrap_or(i32::MAX)
+            .cmp(&b.rank.unwrap_or(i32::MAX))
+            .then_with(|| a.created_at.cmp(&b.created_at))
+    });
+    items.extend(orphans.into_iter().map(|media| GalleryItem {
+        rel_path: None,
+        local_hero: false,
+        remote: Some(media),
+        by_hash: false,
+    }));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::ProductImageEntry;
+    use chrono::{Local, Utc};
+
+    fn media(id: &str, sha: Option<&str>, rank: Option<i32>) -> Media {
+        Media {
+            content_length: None,
+            content_type: None,
+            created_at: Utc::now(),
+            filename: None,
+            media_id: id.to_string(),
+            object_key: format!("media/{id}"),
+            product_id: Some("p1".to_string()),
+            purpose: Some(MediaPurpose::ProductImage),
+            rank,
+            session_id: None,
+            sha256: sha.map(str::to_string),
+            updated_at: Utc::now(),
+            url: format!("https://cdn.test/{id}.jpg"),
+        }
+    }
+
+    fn image(rel: &str, media_id: Option<&str>) -> ProductImageEntry {
+        ProductImageEntry {
+            rel_path: rel.to_string(),
+            created_at: Local::now(),
+            sharpness_score: None,
+            uploaded_url: media_id.map(|id| format!("https://cdn.test/{id}.jpg")),
+            uploaded_media_id: media_id.map(str::to_string),
+            label: None,
+            sha256: None,
+            committed_by: None,
+            uploaded_by: None,
+        }
+    }
+
+    #[test]
+    fn links_by_id_then_hash_and_keeps_orphans_on_both_sides() {
+        let mut product: ProductManifest = serde_json::from_value(serde_json::json!({
+            "product_id": "p1",
+            "sku_alias": "SKU-1",
+            "display_name": null,
+            "created_at": Local::now(),
+            "updated_at": Local::now(),
+            "images": [],
+            "hero_rel_path": "images/hero.jpg",
+        }))
+        .unwrap();
+        product.images = vec![
+            image("images/a.jpg", Some("m1")),
+            image("images/b.jpg", None),
+            image("images/c.jpg", Some("gone")),
+        ];
+        let remote = vec![
+            media("m9", None, Some(5)),
+            media("m2", Some("ABC"), None),
+            media("m1", None, Some(0)),
+            media("m8", None, Some(1)),
+        ];
+        let mut hashed = Vec::new();
+        let items = reconcile(&product, remote, |rel| {
+            hashed.push(rel.to_string());
+            (rel == "images/b.jpg").then(|| "abc".to_string())
+        });
+
+        let rows: Vec<_> = items
+            .iter()
+            .map(|item| {
+                (
+                    item.rel_path.as_deref(),
+                    item.remote.as_ref().map(|m| m.media_id.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("images/hero.jpg"), None),
+                (Some("images/a.jpg"), Some("m1")),
+                (Some("images/b.jpg"), Some("m2")),
+                (Some("images/c.jpg"), None),
+                (None, Some("m8")),
+                (None, Some("m9")),
+            ]
+        );
+        assert!(items[0].local_hero && items[0].local_orphan());
+        assert!(items[2].by_hash && !items[1].by_hash);
+        assert!(items[3].local_orphan() && items[4].remote_orphan());
+        // Nothing left carries a hash once b is linked, so c is never read.
+        assert_eq!(hashed, ["images/hero.jpg", "images/b.jpg"]);
+    }
+}
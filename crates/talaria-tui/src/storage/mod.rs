@@ -1,16 +1,49 @@
+use std::cmp::Reverse;
 use std::collections::{BTreeMap, HashMap};
 use std::ffi::OsStr;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use talaria_core::barcode::Barcode;
+use talaria_core::grading::{ProductGrading, strip_condition_report};
+use talaria_core::measure::MeasuredDimensions;
+use talaria_core::product_diff::ProductFields;
+use talaria_core::shots::SessionTemplate;
+use talaria_core::stats::SessionSample;
+use talaria_core::upload_cache::sha256_hex;
 use uuid::Uuid;
 
+use crate::util::hero::{self, FrameSubject, HeroCandidate};
+
+pub mod media;
+pub mod previews;
+pub mod sync;
+pub mod trash;
 pub mod worker;
 
+const THUMBNAIL_MAX_PX: u32 = 128;
+
+/// Who sessions, commits, uploads and listing submissions are attributed to;
+/// see [`set_operator`].
+static OPERATOR: RwLock<Option<String>> = RwLock::new(None);
+
+/// Sets the operator recorded on what this station writes from now on, from
+/// [`talaria_core::config::Config::operator_name`].
+pub fn set_operator(operator: Option<String>) {
+    if let Ok(mut current) = OPERATOR.write() {
+        *current = operator;
+    }
+}
+
+pub fn operator() -> Option<String> {
+    OPERATOR.read().ok().and_then(|current| current.clone())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductImageEntry {
     pub rel_path: String,
@@ -19,6 +52,18 @@ pub struct ProductImageEntry {
     pub uploaded_url: Option<String>,
     #[serde(default)]
     pub uploaded_media_id: Option<String>,
+    /// Shot from the session template the frame was picked for, e.g. `tag`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// SHA-256 of the file as committed; uploads are checked against it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Operator who committed the image from a session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub committed_by: Option<String>,
+    /// Operator whose station uploaded it to Hermes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uploaded_by: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +85,22 @@ pub struct ProductManifest {
     pub hero_uploaded_url: Option<String>,
     #[serde(default)]
     pub hero_media_id: Option<String>,
+    /// SHA-256 of `hero_rel_path` when it is the curated hero, not one of
+    /// the images.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero_uploaded_by: Option<String>,
+    /// Condition grade picked during curation; applied to every listing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grading: Option<ProductGrading>,
+    /// Dimensions measured at photo time; override the structured product's
+    /// width/height/depth and the listings' package size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measured: Option<MeasuredDimensions>,
+    /// The fields as of the last sync with Hermes; see [`sync`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced: Option<ProductFields>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -64,14 +125,23 @@ pub struct MarketplaceListing {
     #[serde(default)]
     pub aspect_specs: Vec<ListingAspectSpec>,
     pub quantity: Option<i32>,
+    /// Units sold so far; `quantity` is what is left.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_sold: Option<i32>,
     pub merchant_location_key: Option<String>,
     pub fulfillment_policy_id: Option<String>,
     pub payment_policy_id: Option<String>,
     pub return_policy_id: Option<String>,
     #[serde(default)]
     pub package: Option<ListingPackage>,
+    /// Name of the `[[package_templates]]` preset last applied to `package`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_template: Option<String>,
     pub status: Option<String>,
     pub listing_id: Option<String>,
+    /// Operator who submitted the listing to Hermes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submitted_by: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -109,6 +179,17 @@ pub struct SessionFrameEntry {
     pub rel_path: String,
     pub created_at: DateTime<Local>,
     pub sharpness_score: Option<f64>,
+    /// Subject placement, for the hero suggestion; absent on frames captured
+    /// before it was measured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<FrameSubject>,
+    /// SHA-256 of the frame at capture; commit refuses a frame that changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// How the frame was taken: the camera of a paired capture
+    /// (`[second_camera]`, e.g. `top` or `side`) or `turntable`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -119,6 +200,10 @@ pub struct SessionPicks {
     pub hero_rel_path: Option<String>,
     #[serde(default)]
     pub angle_rel_paths: Vec<String>,
+    /// Session template shot each picked frame covers (rel path -> label);
+    /// a shot is covered by at most one frame.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub shot_labels: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -129,6 +214,70 @@ pub struct SessionManifest {
     pub committed_at: Option<DateTime<Local>>,
     pub frames: Vec<SessionFrameEntry>,
     pub picks: SessionPicks,
+    /// Shot list the session is checked against; a copy, so later config
+    /// edits leave running sessions alone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<SessionTemplate>,
+    /// Gray card gains (red, green, blue) captures are corrected with. Frames
+    /// from the live stream have them applied; tethered stills are kept as
+    /// the camera sent them, for a batch step to correct.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub white_balance: Option<[f64; 3]>,
+    /// Operator who started the session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    /// Operator who committed it; differs from `operator` when a session is
+    /// resumed at another station.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub committed_by: Option<String>,
+}
+
+impl SessionManifest {
+    /// Required shots of the template that no frame covers yet.
+    pub fn missing_shots(&self) -> Vec<String> {
+        let Some(template) = &self.template else {
+            return Vec::new();
+        };
+        let assigned: Vec<&str> = self
+            .picks
+            .shot_labels
+            .values()
+            .map(String::as_str)
+            .collect();
+        template
+            .missing(&assigned)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn shot_label(&self, frame_rel_path: &str) -> Option<&str> {
+        self.picks
+            .shot_labels
+            .get(frame_rel_path)
+            .map(String::as_str)
+    }
+
+    /// Frame offered as the hero while the user has not set one: the best of
+    /// the selected frames (or of all frames with nothing selected), once the
+    /// session has [`hero::MIN_FRAMES`] frames.
+    pub fn suggested_hero(&self) -> Option<&str> {
+        if self.picks.hero_rel_path.is_some() || self.frames.len() < hero::MIN_FRAMES {
+            return None;
+        }
+        let selected = &self.picks.selected_rel_paths;
+        let candidates: Vec<HeroCandidate> = self
+            .frames
+            .iter()
+            .filter(|f| selected.is_empty() || selected.contains(&f.rel_path))
+            .map(|f| HeroCandidate {
+                rel_path: &f.rel_path,
+                sharpness: f.sharpness_score,
+                subject: f.subject,
+            })
+            .collect();
+        hero::suggest(&candidates)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -139,23 +288,57 @@ pub struct ProductSummary {
     pub updated_at: DateTime<Local>,
     pub image_count: usize,
     pub has_structure: bool,
+    /// At least one marketplace listing draft exists.
+    pub has_listing: bool,
+    /// At least one marketplace listing is published.
+    pub published: bool,
+    /// At least one marketplace listing sold out.
+    pub sold: bool,
+    /// Has images but no structure yet.
+    pub needs_enrich: bool,
     pub marketplace_statuses: Vec<MarketplaceStatus>,
+    pub thumbnail_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
 pub struct MarketplaceStatus {
     pub marketplace: String,
-    pub published: bool,
+    pub state: ListingState,
+}
+
+/// Where a marketplace listing stands, from its `status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingState {
+    Draft,
+    Active,
+    Sold,
+    Ended,
 }
 
-pub fn default_captures_dir() -> PathBuf {
-    if let Some(dir) = std::env::var_os("TALARIA_CAPTURES_DIR") {
-        return PathBuf::from(dir);
+impl ListingState {
+    pub fn from_status(status: Option<&str>) -> Self {
+        match status.map(|s| s.trim().to_ascii_lowercase()).as_deref() {
+            Some("published" | "active") => ListingState::Active,
+            Some("sold") => ListingState::Sold,
+            Some("ended") => ListingState::Ended,
+            _ => ListingState::Draft,
+        }
+    }
+
+    /// Went live at some point.
+    pub fn published(self) -> bool {
+        self != ListingState::Draft
+    }
+
+    /// Grid badge after the marketplace label.
+    pub fn badge(self) -> &'static str {
+        match self {
+            ListingState::Draft => "-",
+            ListingState::Active => "+",
+            ListingState::Sold => "$",
+            ListingState::Ended => "x",
+        }
     }
-    dirs::data_local_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("talaria")
-        .join("captures")
 }
 
 pub fn products_dir(base: &Path) -> PathBuf {
@@ -166,6 +349,10 @@ pub fn sessions_dir(base: &Path) -> PathBuf {
     base.join("sessions")
 }
 
+pub fn exports_dir(base: &Path) -> PathBuf {
+    base.join("exports")
+}
+
 pub fn logs_dir(base: &Path) -> PathBuf {
     base.join("logs")
 }
@@ -202,6 +389,10 @@ pub fn product_remote_dir(base: &Path, product_id: &str) -> PathBuf {
     product_dir(base, product_id).join("remote")
 }
 
+pub fn product_thumbnails_dir(base: &Path, product_id: &str) -> PathBuf {
+    product_dir(base, product_id).join("thumbnails")
+}
+
 pub fn session_frames_dir(base: &Path, session_id: &str) -> PathBuf {
     session_dir(base, session_id).join("frames")
 }
@@ -217,6 +408,23 @@ pub fn ensure_base_dirs(base: &Path) -> Result<()> {
     Ok(())
 }
 
+/// What [`gc`] removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcReport {
+    pub trash_purged: usize,
+    pub previews_pruned: usize,
+}
+
+/// Housekeeping run when the storage worker starts: purges expired trash and
+/// prunes the preview cache to [`previews::CACHE_MAX_BYTES`].
+pub fn gc(base: &Path) -> Result<GcReport> {
+    Ok(GcReport {
+        trash_purged: trash::purge_expired(base)?,
+        previews_pruned: previews::prune(base, previews::CACHE_MAX_BYTES)
+            .context("prune preview cache")?,
+    })
+}
+
 pub fn new_product_id() -> String {
     Uuid::new_v4().to_string()
 }
@@ -261,7 +469,7 @@ fn marketplace_statuses_from_listings(
         .iter()
         .map(|(marketplace, listing)| MarketplaceStatus {
             marketplace: marketplace.clone(),
-            published: listing.status.as_deref() == Some("published"),
+            state: ListingState::from_status(listing.status.as_deref()),
         })
         .collect::<Vec<_>>();
     statuses.sort_by(|a, b| a.marketplace.cmp(&b.marketplace));
@@ -270,29 +478,113 @@ fn marketplace_statuses_from_listings(
 
 pub fn list_products(base: &Path) -> Result<Vec<ProductSummary>> {
     let mut out = Vec::new();
+    list_products_in_batches(base, usize::MAX, |batch| out.extend(batch))?;
+    out.sort_by_key(|p| Reverse(p.updated_at));
+    Ok(out)
+}
+
+/// Hands product summaries to `on_batch` `batch_size` at a time, most
+/// recently written manifest first, so a large catalog shows its recent
+/// products before every manifest (and thumbnail) has been read. Each batch
+/// is sorted newest first.
+pub fn list_products_in_batches(
+    base: &Path,
+    batch_size: usize,
+    mut on_batch: impl FnMut(Vec<ProductSummary>),
+) -> Result<()> {
     let dir = products_dir(base);
     if !dir.exists() {
-        return Ok(out);
+        return Ok(());
     }
+    let mut manifests = Vec::new();
     for entry in fs::read_dir(dir).context("read products dir")? {
-        let entry = entry?;
-        let path = entry.path().join("product.json");
-        if !path.exists() {
+        let path = entry?.path().join("product.json");
+        let Ok(meta) = fs::metadata(&path) else {
             continue;
+        };
+        manifests.push((meta.modified().ok(), path));
+    }
+    manifests.sort_by_key(|(modified, _)| Reverse(*modified));
+
+    for chunk in manifests.chunks(batch_size.max(1)) {
+        let mut batch = chunk
+            .iter()
+            .map(|(_, path)| product_summary(base, path))
+            .collect::<Result<Vec<_>>>()?;
+        batch.sort_by_key(|p| Reverse(p.updated_at));
+        on_batch(batch);
+    }
+    Ok(())
+}
+
+fn product_summary(base: &Path, manifest_path: &Path) -> Result<ProductSummary> {
+    let manifest: ProductManifest = read_json(manifest_path)?;
+    let thumbnail_path = ensure_product_thumbnail(base, &manifest).ok().flatten();
+    let marketplace_statuses = marketplace_statuses_from_listings(&manifest.listings);
+    Ok(ProductSummary {
+        product_id: manifest.product_id,
+        sku_alias: manifest.sku_alias,
+        display_name: manifest.display_name,
+        updated_at: manifest.updated_at,
+        image_count: manifest.images.len(),
+        has_structure: manifest.structure_json.is_some(),
+        has_listing: !manifest.listings.is_empty(),
+        published: marketplace_statuses.iter().any(|s| s.state.published()),
+        sold: marketplace_statuses
+            .iter()
+            .any(|s| s.state == ListingState::Sold),
+        needs_enrich: manifest.structure_json.is_none() && !manifest.images.is_empty(),
+        marketplace_statuses,
+        thumbnail_path,
+    })
+}
+
+/// Returns a small PNG of the product's hero (or first image), regenerating it
+/// when the source image is newer. Stale thumbnails for other sources are removed.
+pub fn ensure_product_thumbnail(
+    base: &Path,
+    manifest: &ProductManifest,
+) -> Result<Option<PathBuf>> {
+    let Some(source_rel) = manifest
+        .hero_rel_path
+        .as_deref()
+        .or_else(|| manifest.images.first().map(|img| img.rel_path.as_str()))
+    else {
+        return Ok(None);
+    };
+    let source = product_dir(base, &manifest.product_id).join(source_rel);
+    let Ok(source_meta) = fs::metadata(&source) else {
+        return Ok(None);
+    };
+    let thumbs_dir = product_thumbnails_dir(base, &manifest.product_id);
+    let thumb_name = format!("{}.png", source_rel.replace(['/', '\\'], "_"));
+    let thumb = thumbs_dir.join(&thumb_name);
+    let fresh = match (fs::metadata(&thumb), source_meta.modified()) {
+        (Ok(thumb_meta), Ok(source_modified)) => thumb_meta
+            .modified()
+            .is_ok_and(|thumb_modified| thumb_modified >= source_modified),
+        _ => false,
+    };
+    if fresh {
+        return Ok(Some(thumb));
+    }
+
+    fs::create_dir_all(&thumbs_dir).context("create thumbnails dir")?;
+    let img = image::open(&source).with_context(|| format!("open {}", source.display()))?;
+    let tmp = thumb.with_extension("png.tmp");
+    img.thumbnail(THUMBNAIL_MAX_PX, THUMBNAIL_MAX_PX)
+        .save_with_format(&tmp, image::ImageFormat::Png)
+        .context("write thumbnail")?;
+    fs::rename(&tmp, &thumb).context("rename thumbnail")?;
+    for entry in fs::read_dir(&thumbs_dir)
+        .context("read thumbnails dir")?
+        .flatten()
+    {
+        if entry.file_name() != OsStr::new(&thumb_name) {
+            let _ = fs::remove_file(entry.path());
         }
-        let manifest: ProductManifest = read_json(&path)?;
-        out.push(ProductSummary {
-            product_id: manifest.product_id,
-            sku_alias: manifest.sku_alias,
-            display_name: manifest.display_name,
-            updated_at: manifest.updated_at,
-            image_count: manifest.images.len(),
-            has_structure: manifest.structure_json.is_some(),
-            marketplace_statuses: marketplace_statuses_from_listings(&manifest.listings),
-        });
     }
-    out.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
-    Ok(out)
+    Ok(Some(thumb))
 }
 
 pub fn create_product(base: &Path) -> Result<ProductManifest> {
@@ -313,6 +605,11 @@ pub fn create_product(base: &Path) -> Result<ProductManifest> {
         hero_rel_path: None,
         hero_uploaded_url: None,
         hero_media_id: None,
+        hero_sha256: None,
+        hero_uploaded_by: None,
+        grading: None,
+        measured: None,
+        synced: None,
     };
 
     fs::create_dir_all(product_images_dir(base, &product_id)).context("create product images")?;
@@ -333,6 +630,7 @@ pub fn set_product_image_uploaded_url(
     if let Some(img) = manifest.images.iter_mut().find(|i| i.rel_path == rel_path) {
         img.uploaded_url = Some(url);
         img.uploaded_media_id = media_id;
+        img.uploaded_by = operator();
         manifest.updated_at = Local::now();
         atomic_write_json(&path, &manifest)?;
     }
@@ -349,6 +647,37 @@ pub fn set_product_hero_uploaded_url(
     let mut manifest: ProductManifest = read_json(&path)?;
     manifest.hero_uploaded_url = Some(url);
     manifest.hero_media_id = media_id;
+    manifest.hero_uploaded_by = operator();
+    manifest.updated_at = Local::now();
+    atomic_write_json(&path, &manifest)?;
+    Ok(manifest)
+}
+
+/// Forgets the upload URL and media id of the given images (the hero's too
+/// when it is among them), so the next upload sends them again.
+pub fn clear_product_media_links(
+    base: &Path,
+    product_id: &str,
+    rel_paths: &[String],
+) -> Result<ProductManifest> {
+    let path = product_manifest_path(base, product_id);
+    let mut manifest: ProductManifest = read_json(&path)?;
+    for img in &mut manifest.images {
+        if rel_paths.contains(&img.rel_path) {
+            img.uploaded_url = None;
+            img.uploaded_media_id = None;
+            img.uploaded_by = None;
+        }
+    }
+    if manifest
+        .hero_rel_path
+        .as_ref()
+        .is_some_and(|hero| rel_paths.contains(hero))
+    {
+        manifest.hero_uploaded_url = None;
+        manifest.hero_media_id = None;
+        manifest.hero_uploaded_by = None;
+    }
     manifest.updated_at = Local::now();
     atomic_write_json(&path, &manifest)?;
     Ok(manifest)
@@ -403,6 +732,8 @@ pub fn delete_product_image(
         manifest.hero_rel_path = None;
         manifest.hero_uploaded_url = None;
         manifest.hero_media_id = None;
+        manifest.hero_uploaded_by = None;
+        manifest.hero_sha256 = None;
         removed = true;
     }
 
@@ -433,6 +764,121 @@ pub fn set_product_listings(
     Ok(manifest)
 }
 
+/// Adds a scanned barcode to the context text and, with `set_sku`, uses it as
+/// the SKU alias.
+pub fn set_product_barcode(
+    base: &Path,
+    product_id: &str,
+    barcode: &Barcode,
+    set_sku: bool,
+) -> Result<ProductManifest> {
+    let path = product_manifest_path(base, product_id);
+    let mut manifest: ProductManifest = read_json(&path)?;
+    manifest.context_text =
+        Some(barcode.add_to_context(manifest.context_text.as_deref().unwrap_or_default()));
+    if set_sku {
+        manifest.sku_alias = barcode.value.clone();
+    }
+    manifest.updated_at = Local::now();
+    atomic_write_json(&path, &manifest)?;
+    Ok(manifest)
+}
+
+/// Stores (or clears) the product's grading and re-applies it to every
+/// listing: condition, condition id and the condition report in the
+/// description.
+pub fn set_product_grading(
+    base: &Path,
+    product_id: &str,
+    grading: Option<ProductGrading>,
+) -> Result<ProductManifest> {
+    let path = product_manifest_path(base, product_id);
+    let mut manifest: ProductManifest = read_json(&path)?;
+    for listing in manifest.listings.values_mut() {
+        match &grading {
+            Some(grading) => apply_grading(listing, grading, &manifest.images),
+            None => {
+                if let Some(description) = &listing.description {
+                    let stripped = strip_condition_report(description).to_string();
+                    listing.description = Some(stripped).filter(|d| !d.is_empty());
+                }
+            }
+        }
+    }
+    manifest.grading = grading;
+    manifest.updated_at = Local::now();
+    atomic_write_json(&path, &manifest)?;
+    Ok(manifest)
+}
+
+/// Stores (or clears) the product's measured dimensions and applies them to
+/// the structured product and every listing's package. Clearing keeps the
+/// values already written there.
+pub fn set_product_measurement(
+    base: &Path,
+    product_id: &str,
+    measured: Option<MeasuredDimensions>,
+) -> Result<ProductManifest> {
+    let path = product_manifest_path(base, product_id);
+    let mut manifest: ProductManifest = read_json(&path)?;
+    if let Some(measured) = &measured {
+        if let Some(structure) = manifest.structure_json.as_mut() {
+            measured.apply_to_structure(structure);
+        }
+        for listing in manifest.listings.values_mut() {
+            apply_measurement(listing, measured);
+        }
+    }
+    manifest.measured = measured;
+    manifest.updated_at = Local::now();
+    atomic_write_json(&path, &manifest)?;
+    Ok(manifest)
+}
+
+/// Sets the listing's package dimensions once all three sides are measured;
+/// the package weight is left alone.
+pub fn apply_measurement(listing: &mut MarketplaceListing, measured: &MeasuredDimensions) {
+    let Some([length, width, height]) = measured.package_sides() else {
+        return;
+    };
+    listing
+        .package
+        .get_or_insert_with(ListingPackage::default)
+        .dimensions = Some(ListingDimensions {
+        height,
+        length,
+        width,
+        unit: measured.unit.unit_code().to_string(),
+    });
+}
+
+/// Maps `grading` onto one listing. Defect photos are referenced by their
+/// position in the listing's images, when they were uploaded into it.
+pub fn apply_grading(
+    listing: &mut MarketplaceListing,
+    grading: &ProductGrading,
+    images: &[ProductImageEntry],
+) {
+    let (condition, condition_id) =
+        grading.listing_condition(&listing.allowed_conditions, &listing.allowed_condition_ids);
+    if condition.is_some() {
+        listing.condition = condition;
+    }
+    if condition_id.is_some() {
+        listing.condition_id = condition_id;
+    }
+    let listing_images = &listing.images;
+    let photo_number = |rel_path: &str| {
+        let url = images
+            .iter()
+            .find(|image| image.rel_path == rel_path)?
+            .uploaded_url
+            .as_deref()?;
+        listing_images.iter().position(|u| u == url).map(|i| i + 1)
+    };
+    listing.description = Some(grading.describe(listing.description.as_deref(), photo_number));
+}
+
 pub fn upsert_product_from_remote(
     base: &Path,
     row: &talaria_core::models::ProductRecord,
@@ -455,6 +901,11 @@ pub fn upsert_product_from_remote(
             hero_rel_path: None,
             hero_uploaded_url: None,
             hero_media_id: None,
+            hero_sha256: None,
+            hero_uploaded_by: None,
+            grading: None,
+            measured: None,
+            synced: None,
         }
     };
 
@@ -467,6 +918,7 @@ pub fn upsert_product_from_remote(
     if manifest.created_at < row.created_at.with_timezone(&Local) {
         manifest.created_at = row.created_at.with_timezone(&Local);
     }
+    manifest.synced = Some(sync::local_fields(&manifest));
 
     fs::create_dir_all(product_images_dir(base, &row.id)).context("create product images")?;
     fs::create_dir_all(product_curated_dir(base, &row.id)).context("create product curated")?;
@@ -485,6 +937,10 @@ pub fn create_session(base: &Path, product_id: &str) -> Result<SessionManifest>
         committed_at: None,
         frames: Vec::new(),
         picks: SessionPicks::default(),
+        template: None,
+        white_balance: None,
+        operator: operator(),
+        committed_by: None,
     };
     fs::create_dir_all(session_frames_dir(base, &session_id)).context("create session frames")?;
     fs::create_dir_all(session_picks_dir(base, &session_id)).context("create session picks")?;
@@ -497,14 +953,22 @@ pub fn append_session_frame(
     session_id: &str,
     frame_rel_path: &str,
     sharpness_score: Option<f64>,
+    subject: Option<FrameSubject>,
     created_at: DateTime<Local>,
+    tag: Option<String>,
 ) -> Result<SessionManifest> {
     let path = session_manifest_path(base, session_id);
     let mut manifest: SessionManifest = read_json(&path)?;
+    let sha256 = fs::read(session_dir(base, session_id).join(frame_rel_path))
+        .ok()
+        .map(|bytes| sha256_hex(&bytes));
     manifest.frames.push(SessionFrameEntry {
         rel_path: frame_rel_path.to_string(),
         created_at,
         sharpness_score,
+        subject,
+        sha256,
+        tag,
     });
     atomic_write_json(&path, &manifest)?;
     Ok(manifest)
@@ -527,30 +991,185 @@ pub fn toggle_session_frame_pick(
         .position(|p| p == frame_rel_path)
     {
         manifest.picks.selected_rel_paths.remove(idx);
+        manifest.picks.shot_labels.remove(frame_rel_path);
     } else {
         manifest
             .picks
             .selected_rel_paths
             .push(frame_rel_path.to_string());
+        // Picking a frame fills the next shot on the checklist.
+        if let Some(template) = &manifest.template
+            && !manifest.picks.shot_labels.contains_key(frame_rel_path)
+        {
+            let assigned: Vec<&str> = manifest
+                .picks
+                .shot_labels
+                .values()
+                .map(String::as_str)
+                .collect();
+            if let Some(shot) = template.next_unfilled(&assigned).map(str::to_string) {
+                manifest
+                    .picks
+                    .shot_labels
+                    .insert(frame_rel_path.to_string(), shot);
+            }
+        }
     }
     atomic_write_json(&path, &manifest)?;
     Ok(manifest)
 }
 
-pub fn delete_session_frame(base: &Path, session_id: &str, frame_rel_path: &str) -> Result<()> {
-    let full = session_dir(base, session_id).join(frame_rel_path);
-    if full.exists() {
-        fs::remove_file(&full).with_context(|| format!("remove {}", full.display()))?;
+/// Makes a frame the session's hero, or clears the hero with `None` so the
+/// suggestion applies again. A hero is picked when other frames are, since
+/// only picked frames are committed then.
+pub fn set_session_hero(
+    base: &Path,
+    session_id: &str,
+    frame_rel_path: Option<&str>,
+) -> Result<SessionManifest> {
+    let path = session_manifest_path(base, session_id);
+    let mut manifest: SessionManifest = read_json(&path)?;
+    if let Some(rel) = frame_rel_path {
+        if !manifest.frames.iter().any(|f| f.rel_path == rel) {
+            return Err(anyhow::anyhow!("Frame not found in session."));
+        }
+        let picks = &mut manifest.picks;
+        if !picks.selected_rel_paths.is_empty()
+            && !picks.selected_rel_paths.iter().any(|p| p == rel)
+        {
+            picks.selected_rel_paths.push(rel.to_string());
+        }
     }
+    manifest.picks.hero_rel_path = frame_rel_path.map(str::to_string);
+    atomic_write_json(&path, &manifest)?;
+    Ok(manifest)
+}
+
+/// Sets (or clears) the session's shot list, dropping labels the new list
+/// does not have.
+pub fn set_session_template(
+    base: &Path,
+    session_id: &str,
+    template: Option<SessionTemplate>,
+) -> Result<SessionManifest> {
     let path = session_manifest_path(base, session_id);
     let mut manifest: SessionManifest = read_json(&path)?;
-    manifest.frames.retain(|f| f.rel_path != frame_rel_path);
-    manifest
-        .picks
-        .selected_rel_paths
-        .retain(|p| p != frame_rel_path);
+    match &template {
+        Some(template) => manifest
+            .picks
+            .shot_labels
+            .retain(|_, label| template.labels().any(|shot| shot == label)),
+        None => manifest.picks.shot_labels.clear(),
+    }
+    manifest.template = template;
     atomic_write_json(&path, &manifest)?;
-    Ok(())
+    Ok(manifest)
+}
+
+/// Records the gray card gains the session's captures are corrected with.
+pub fn set_session_white_balance(
+    base: &Path,
+    session_id: &str,
+    gains: Option<[f64; 3]>,
+) -> Result<SessionManifest> {
+    let path = session_manifest_path(base, session_id);
+    let mut manifest: SessionManifest = read_json(&path)?;
+    manifest.white_balance = gains;
+    atomic_write_json(&path, &manifest)?;
+    Ok(manifest)
+}
+
+/// Labels a frame with a shot (or clears it with `None`). The shot moves
+/// off any other frame, and a labelled frame is picked.
+pub fn set_session_frame_shot(
+    base: &Path,
+    session_id: &str,
+    frame_rel_path: &str,
+    label: Option<String>,
+) -> Result<SessionManifest> {
+    let path = session_manifest_path(base, session_id);
+    let mut manifest: SessionManifest = read_json(&path)?;
+    if !manifest.frames.iter().any(|f| f.rel_path == frame_rel_path) {
+        return Err(anyhow::anyhow!("Frame not found in session."));
+    }
+    let picks = &mut manifest.picks;
+    match label {
+        Some(label) => {
+            picks.shot_labels.retain(|_, shot| *shot != label);
+            picks.shot_labels.insert(frame_rel_path.to_string(), label);
+            if !picks.selected_rel_paths.iter().any(|p| p == frame_rel_path) {
+                picks.selected_rel_paths.push(frame_rel_path.to_string());
+            }
+        }
+        None => {
+            picks.shot_labels.remove(frame_rel_path);
+        }
+    }
+    atomic_write_json(&path, &manifest)?;
+    Ok(manifest)
+}
+
+/// An uncommitted session that still holds frames, e.g. left behind when the
+/// TUI exited before the session was committed.
+#[derive(Debug, Clone)]
+pub struct ResumableSession {
+    pub session: SessionManifest,
+    pub sku_alias: String,
+    pub last_activity: DateTime<Local>,
+}
+
+/// Uncommitted sessions with at least one frame whose product still exists
+/// locally, most recently active first.
+pub fn list_resumable_sessions(base: &Path) -> Result<Vec<ResumableSession>> {
+    let root = sessions_dir(base);
+    let entries = match fs::read_dir(&root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).context("read sessions dir"),
+    };
+    let mut out = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || entry.file_name().to_string_lossy().starts_with('_') {
+            continue;
+        }
+        let Ok(session) = read_json::<SessionManifest>(&path.join("session.json")) else {
+            continue;
+        };
+        if session.committed_at.is_some() || session.frames.is_empty() {
+            continue;
+        }
+        let Ok(product) = load_product(base, &session.product_id) else {
+            continue;
+        };
+        let last_activity = session
+            .frames
+            .iter()
+            .map(|f| f.created_at)
+            .max()
+            .unwrap_or(session.created_at);
+        out.push(ResumableSession {
+            session,
+            sku_alias: product.sku_alias,
+            last_activity,
+        });
+    }
+    out.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+    Ok(out)
+}
+
+/// Every session outside the trash, for [`talaria_core::stats`].
+pub fn session_samples(base: &Path) -> Result<Vec<SessionSample>> {
+    let entries = match fs::read_dir(sessions_dir(base)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).context("read sessions dir"),
+    };
+    Ok(entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('_'))
+        .filter_map(|entry| read_json(&entry.path().join("session.json")).ok())
+        .collect())
 }
 
 pub fn abandon_session(base: &Path, session_id: &str) -> Result<PathBuf> {
@@ -581,30 +1200,12 @@ pub fn commit_session(
     let mut copied = 0usize;
     let now = Local::now();
 
-    let mut commit_paths = Vec::new();
-    if !session.picks.selected_rel_paths.is_empty() {
-        let selected: std::collections::HashSet<&str> = session
-            .picks
-            .selected_rel_paths
-            .iter()
-            .map(|s| s.as_str())
-            .collect();
-        for frame in &session.frames {
-            if selected.contains(frame.rel_path.as_str()) {
-                commit_paths.push(frame.rel_path.clone());
-            }
-        }
-    } else {
-        if let Some(hero) = &session.picks.hero_rel_path {
-            commit_paths.push(hero.clone());
-        }
-        commit_paths.extend(session.picks.angle_rel_paths.iter().cloned());
-    }
-
+    let mut commit_paths = curated_order(&session);
     if commit_paths.is_empty() {
         for frame in &session.frames {
             commit_paths.push(frame.rel_path.clone());
         }
+        lead_with_suggested_hero(&session, &mut commit_paths);
     }
     if commit_paths.is_empty() {
         return Err(anyhow::anyhow!("No images captured for this session."));
@@ -623,7 +1224,17 @@ pub fn commit_session(
         let filename = format!("img_{:03}_{}.{}", idx + 1, now.format("%Y%m%d_%H%M%S"), ext);
         let dst_rel = format!("images/{filename}");
         let dst = product_dir(base, &product_id).join(&dst_rel);
-        fs::copy(&src, &dst)
+        let bytes = fs::read(&src).with_context(|| format!("read {}", src.display()))?;
+        let sha256 = sha256_hex(&bytes);
+        let frame = session.frames.iter().find(|frame| frame.rel_path == *rel);
+        let captured = frame.and_then(|frame| frame.sha256.as_deref());
+        if let Some(captured) = captured.filter(|captured| *captured != sha256) {
+            return Err(anyhow::anyhow!(
+                "{rel} changed on disk since capture (sha256 {captured}, now {sha256}); \
+                 recapture or delete it before committing"
+            ));
+        }
+        fs::write(&dst, &bytes)
             .with_context(|| format!("copy {} -> {}", src.display(), dst.display()))?;
         product.images.push(ProductImageEntry {
             rel_path: dst_rel.clone(),
@@ -631,6 +1242,16 @@ pub fn commit_session(
             sharpness_score: None,
             uploaded_url: None,
             uploaded_media_id: None,
+            // A shot from the template wins over the frame's tag.
+            label: session
+                .picks
+                .shot_labels
+                .get(rel)
+                .or(frame.and_then(|frame| frame.tag.as_ref()))
+                .cloned(),
+            sha256: Some(sha256),
+            committed_by: operator(),
+            uploaded_by: None,
         });
         copied += 1;
     }
@@ -638,20 +1259,87 @@ pub fn commit_session(
     if let Some(_) = &session.picks.hero_rel_path {
         let hero_src = session_picks_dir(base, session_id).join("hero.jpg");
         let hero_dst = product_curated_dir(base, &product_id).join("hero.jpg");
-        if hero_src.exists() {
-            fs::copy(&hero_src, &hero_dst).ok();
+        if let Ok(bytes) = fs::read(&hero_src) {
+            fs::write(&hero_dst, &bytes).ok();
             product.hero_rel_path = Some("curated/hero.jpg".to_string());
+            product.hero_sha256 = Some(sha256_hex(&bytes));
         }
     }
 
     product.updated_at = now;
     session.committed_at = Some(now);
+    session.committed_by = operator();
 
     atomic_write_json(&product_path, &product)?;
     atomic_write_json(&session_path, &session)?;
     Ok((product, session, copied))
 }
 
+/// Frames to commit in listing order: hero first, then angle picks, then any
+/// other selected frames in capture order. With no explicit selection only
+/// the hero and angle picks are taken. Without a hero the suggested one
+/// leads, but it never adds a frame.
+pub fn curated_order(session: &SessionManifest) -> Vec<String> {
+    let picks = &session.picks;
+    let selected: std::collections::HashSet<&str> = picks
+        .selected_rel_paths
+        .iter()
+        .map(|s| s.as_str())
+        .collect();
+    let wanted = |rel: &str| selected.is_empty() || selected.contains(rel);
+    let mut out: Vec<String> = Vec::new();
+    let curated = picks
+        .hero_rel_path
+        .iter()
+        .chain(picks.angle_rel_paths.iter());
+    for rel in curated {
+        if wanted(rel) && !out.contains(rel) {
+            out.push(rel.clone());
+        }
+    }
+    if !selected.is_empty() {
+        for frame in &session.frames {
+            if selected.contains(frame.rel_path.as_str()) && !out.contains(&frame.rel_path) {
+                out.push(frame.rel_path.clone());
+            }
+        }
+    }
+    lead_with_suggested_hero(session, &mut out);
+    out
+}
+
+fn lead_with_suggested_hero(session: &SessionManifest, paths: &mut [String]) {
+    if let Some(suggested) = session.suggested_hero()
+        && let Some(pos) = paths.iter().position(|p| p == suggested)
+    {
+        paths[..=pos].rotate_right(1);
+    }
+}
+
+/// Uploaded media ids in curated order (hero, then images as committed);
+/// the position is the rank Hermes should store.
+pub fn curated_media_ids(product: &ProductManifest) -> Vec<String> {
+    product
+        .hero_media_id
+        .iter()
+        .chain(
+            product
+                .images
+                .iter()
+                .filter_map(|img| img.uploaded_media_id.as_ref()),
+        )
+        .cloned()
+        .collect()
+}
+
+/// Writes the given manifests as one JSON array under `exports/` and returns the file path.
+pub fn export_products(base: &Path, products: &[ProductManifest]) -> Result<PathBuf> {
+    let name = format!("products-{}.json", Local::now().format("%Y%m%d-%H%M%S"));
+    let path = exports_dir(base).join(name);
+    atomic_write_json(&path, &products)?;
+    Ok(path)
+}
+
 pub fn load_product(base: &Path, product_id: &str) -> Result<ProductManifest> {
     read_json(&product_manifest_path(base, product_id))
 }
@@ -660,37 +1348,260 @@ pub fn load_session(base: &Path, session_id: &str) -> Result<SessionManifest> {
     read_json(&session_manifest_path(base, session_id))
 }
 
-pub fn delete_product(base: &Path, product_id: &str) -> Result<usize> {
-    let product_path = product_dir(base, product_id);
-    if product_path.exists() {
-        fs::remove_dir_all(&product_path)
-            .with_context(|| format!("remove {}", product_path.display()))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(selected: &[&str], hero: Option<&str>, angles: &[&str]) -> SessionManifest {
+        let frames = ["f1", "f2", "f3", "f4"]
+            .iter()
+            .map(|rel| SessionFrameEntry {
+                rel_path: rel.to_string(),
+                created_at: Local::now(),
+                sharpness_score: None,
+                subject: None,
+                sha256: None,
+                tag: None,
+            })
+            .collect();
+        SessionManifest {
+            session_id: "s".into(),
+            product_id: "p".into(),
+            created_at: Local::now(),
+            committed_at: None,
+            frames,
+            picks: SessionPicks {
+                selected_rel_paths: selected.iter().map(|s| s.to_string()).collect(),
+                hero_rel_path: hero.map(str::to_string),
+                angle_rel_paths: angles.iter().map(|s| s.to_string()).collect(),
+                shot_labels: BTreeMap::new(),
+            },
+            template: None,
+            white_balance: None,
+            operator: None,
+            committed_by: None,
+        }
     }
 
-    let mut removed_sessions = 0usize;
-    let sessions_root = sessions_dir(base);
-    if sessions_root.exists() {
-        for entry in fs::read_dir(&sessions_root).context("read sessions dir")? {
-            let entry = entry?;
-            let path = entry.path();
-            if !path.is_dir() {
-                continue;
-            }
-            let name = entry.file_name();
-            if name.to_str().map(|s| s.starts_with('_')).unwrap_or(false) {
-                continue;
-            }
-            let manifest_path = path.join("session.json");
-            if !manifest_path.exists() {
-                continue;
-            }
-            let manifest: SessionManifest = read_json(&manifest_path)?;
-            if manifest.product_id == product_id {
-                fs::remove_dir_all(&path).with_context(|| format!("remove {}", path.display()))?;
-                removed_sessions += 1;
-            }
+    #[test]
+    fn curated_order_puts_hero_and_angles_first() {
+        let s = session(&["f1", "f2", "f3", "f4"], Some("f3"), &["f4", "f3"]);
+        assert_eq!(curated_order(&s), ["f3", "f4", "f1", "f2"]);
+
+        let unselected_hero = session(&["f1", "f2"], Some("f3"), &["f2"]);
+        assert_eq!(curated_order(&unselected_hero), ["f2", "f1"]);
+
+        let picks_only = session(&[], Some("f2"), &["f4"]);
+        assert_eq!(curated_order(&picks_only), ["f2", "f4"]);
+    }
+
+    #[test]
+    fn suggested_hero_leads_picks_until_a_hero_is_set() {
+        let mut s = session(&["f1", "f2", "f3"], None, &[]);
+        for (frame, sharpness) in s.frames.iter_mut().zip([10.0, 20.0, 80.0, 100.0]) {
+            frame.sharpness_score = Some(sharpness);
+        }
+        // f4 is sharpest but not picked, so f3 is suggested.
+        assert_eq!(s.suggested_hero(), Some("f3"));
+        assert_eq!(curated_order(&s), ["f3", "f1", "f2"]);
+
+        s.picks.hero_rel_path = Some("f1".into());
+        assert_eq!(s.suggested_hero(), None);
+        assert_eq!(curated_order(&s), ["f1", "f2", "f3"]);
+
+        let nothing_picked = session(&[], None, &[]);
+        assert!(curated_order(&nothing_picked).is_empty());
+        s.frames.truncate(hero::MIN_FRAMES - 1);
+        s.picks.hero_rel_path = None;
+        assert_eq!(s.suggested_hero(), None);
+    }
+
+    #[test]
+    fn missing_shots_ignore_optional_and_labelled_shots() {
+        let mut s = session(&["f1", "f2"], None, &[]);
+        assert!(s.missing_shots().is_empty(), "no template, nothing missing");
+        s.template = Some(SessionTemplate {
+            name: "apparel".into(),
+            shots: vec!["front".into(), "back".into(), "tag".into()],
+            optional: vec!["defect close-up".into()],
+        });
+        s.picks.shot_labels.insert("f1".into(), "front".into());
+        s.picks
+            .shot_labels
+            .insert("f2".into(), "defect close-up".into());
+        assert_eq!(s.missing_shots(), ["back", "tag"]);
+        assert_eq!(s.shot_label("f2"), Some("defect close-up"));
+        assert_eq!(s.shot_label("f3"), None);
+    }
+
+    #[test]
+    fn grading_sets_condition_and_numbers_defect_photos_by_listing_order() {
+        let image = |rel: &str, url: Option<&str>| ProductImageEntry {
+            rel_path: rel.to_string(),
+            created_at: Local::now(),
+            sharpness_score: None,
+            uploaded_url: url.map(str::to_string),
+            uploaded_media_id: None,
+            label: None,
+            sha256: None,
+            committed_by: None,
+            uploaded_by: None,
+        };
+        let images = [
+            image("a.jpg", Some("https://cdn/a.jpg")),
+            image("b.jpg", Some("https://cdn/b.jpg")),
+            image("c.jpg", None),
+        ];
+        let rubric = talaria_core::grading::default_rubric();
+        let grading = ProductGrading::new(
+            &rubric[2],
+            None,
+            ["b.jpg", "c.jpg"]
+                .iter()
+                .map(|rel| talaria_core::grading::DefectNote {
+                    note: format!("mark on {rel}"),
+                    photo: Some(rel.to_string()),
+                })
+                .collect(),
+        );
+        let mut listing = MarketplaceListing {
+            description: Some("Boots.".into()),
+            images: vec!["https://cdn/b.jpg".into(), "https://cdn/a.jpg".into()],
+            condition: Some("NEW".into()),
+            condition_id: Some(1000),
+            allowed_conditions: vec!["NEW".into(), "USED_GOOD".into()],
+            allowed_condition_ids: vec![1000, 5000],
+            ..Default::default()
+        };
+        apply_grading(&mut listing, &grading, &images);
+        apply_grading(&mut listing, &grading, &images);
+        assert_eq!(listing.condition.as_deref(), Some("USED_GOOD"));
+        assert_eq!(listing.condition_id, Some(5000));
+        assert_eq!(
+            listing.description.as_deref(),
+            Some(
+                "Boots.\n\nCondition report: Good\n- mark on b.jpg (see photo 1)\n- mark on c.jpg"
+            )
+        );
+    }
+
+    #[test]
+    fn measurement_sets_package_dimensions_and_keeps_weight() {
+        let mut listing = MarketplaceListing {
+            package: Some(ListingPackage {
+                weight: Some(ListingWeight {
+                    value: 12,
+                    unit: "OUNCE".into(),
+                }),
+                dimensions: None,
+            }),
+            ..Default::default()
+        };
+        let mut measured = MeasuredDimensions {
+            unit: talaria_core::measure::LengthUnit::Inch,
+            width: Some(9.5),
+            height: Some(4.0),
+            depth: None,
+            measured_at: chrono::Utc::now(),
+        };
+        apply_measurement(&mut listing, &measured);
+        assert!(listing.package.as_ref().unwrap().dimensions.is_none());
+
+        measured.depth = Some(11.25);
+        apply_measurement(&mut listing, &measured);
+        let package = listing.package.unwrap();
+        let dims = package.dimensions.unwrap();
+        assert_eq!((dims.length, dims.width, dims.height), (11.25, 9.5, 4.0));
+        assert_eq!(dims.unit, "INCH");
+        assert_eq!(package.weight.unwrap().value, 12);
+    }
+
+    #[test]
+    fn listing_state_follows_status() {
+        let mut listings = HashMap::new();
+        for (marketplace, status) in [
+            ("EBAY_US", Some("published")),
+            ("EBAY_UK", Some("Sold")),
+            ("EBAY_DE", Some("ended")),
+            ("EBAY_AU", Some("draft")),
+            ("EBAY_CA", None),
+        ] {
+            listings.insert(
+                marketplace.to_string(),
+                MarketplaceListing {
+                    status: status.map(str::to_string),
+                    ..Default::default()
+                },
+            );
+        }
+        let badges: Vec<_> = marketplace_statuses_from_listings(&listings)
+            .iter()
+            .map(|s| format!("{}{}", s.marketplace, s.state.badge()))
+            .collect();
+        assert_eq!(
+            badges,
+            ["EBAY_AU-", "EBAY_CA-", "EBAY_DEx", "EBAY_UK$", "EBAY_US+"]
+        );
+        assert!(ListingState::Ended.published());
+        assert!(!ListingState::Draft.published());
+    }
+    #[test]
+    fn products_are_listed_in_batches_newest_manifest_first() {
+        let base = std::env::temp_dir().join(format!("talaria-list-{}", Uuid::new_v4()));
+        let mut created = Vec::new();
+        for age in [30, 20, 10] {
+            let product = create_product(&base).unwrap();
+            let manifest = fs::File::options()
+                .append(true)
+                .open(product_manifest_path(&base, &product.product_id))
+                .unwrap();
+            manifest
+                .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(age))
+                .unwrap();
+            created.push(product.product_id);
         }
+
+        let mut batches = Vec::new();
+        list_products_in_batches(&base, 2, |batch| {
+            batches.push(batch.into_iter().map(|p| p.product_id).collect::<Vec<_>>())
+        })
+        .unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1], [created[0].clone()]);
+        assert_eq!(list_products(&base).unwrap().len(), 3);
+        let _ = fs::remove_dir_all(&base);
     }
 
-    Ok(removed_sessions)
+    #[test]
+    fn commit_records_capture_hash_and_camera_and_refuses_changed_frames() {
+        let base = std::env::temp_dir().join(format!("talaria-commit-{}", Uuid::new_v4()));
+        let product = create_product(&base).unwrap();
+        let session = create_session(&base, &product.product_id).unwrap();
+        let frame = session_frames_dir(&base, &session.session_id).join("f1.jpg");
+        fs::write(&frame, b"captured").unwrap();
+        let session = append_session_frame(
+            &base,
+            &session.session_id,
+            "frames/f1.jpg",
+            None,
+            None,
+            Local::now(),
+            Some("side".into()),
+        )
+        .unwrap();
+        let captured = sha256_hex(b"captured");
+        assert_eq!(session.frames[0].sha256.as_deref(), Some(captured.as_str()));
+
+        fs::write(&frame, b"edited").unwrap();
+        let err = commit_session(&base, &session.session_id).unwrap_err();
+        assert!(err.to_string().contains("changed on disk since capture"));
+
+        fs::write(&frame, b"captured").unwrap();
+        let (product, _, copied) = commit_session(&base, &session.session_id).unwrap();
+        assert_eq!(copied, 1);
+        assert_eq!(product.images[0].sha256.as_deref(), Some(captured.as_str()));
+        // With no shot label, the frame's tag (its camera here) labels the image.
+        assert_eq!(product.images[0].label.as_deref(), Some("side"));
+        let _ = fs::remove_dir_all(&base);
+    }
 }
@@ -0,0 +1,151 @@
+//! Downscaled copies of photos for the previews, so selecting a frame does
+//! not decode a full-resolution JPEG (slow on network drives) every time.
+//!
+//! Entries are keyed by a hash of the file's first bytes plus its size, so a
+//! frame copied from a session into a product reuses its entry. They are made
+//! lazily by [`crate::workers::previews`] and pruned, oldest used first, by
+//! [`super::gc`].
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use image::codecs::jpeg::JpegEncoder;
+use sha2::{Digest, Sha256};
+
+use crate::util::downscale::fit_within;
+
+/// Longest side, in pixels, of a cached preview; enough for a full-screen
+/// preview window.
+pub const PREVIEW_MAX_PX: u32 = 1600;
+/// Size the cache is pruned down to.
+pub const CACHE_MAX_BYTES: u64 = 512 * 1024 * 1024;
+/// Bytes of the source hashed for its key. JPEG headers carry the capture
+/// time and EXIF, which tell frames apart without reading whole files.
+const KEY_PREFIX_BYTES: u64 = 64 * 1024;
+const JPEG_QUALITY: u8 = 85;
+
+pub fn previews_dir(base: &Path) -> PathBuf {
+    base.join("cache").join("previews")
+}
+
+/// `<sha256 of the first 64 KiB>-<size>` of `source`.
+pub fn cache_key(source: &Path) -> Result<String> {
+    let file = File::open(source).with_context(|| format!("open {}", source.display()))?;
+    let len = file.metadata().context("read source metadata")?.len();
+    let mut prefix = Vec::new();
+    file.take(KEY_PREFIX_BYTES)
+        .read_to_end(&mut prefix)
+        .with_context(|| format!("read {}", source.display()))?;
+    let digest = Sha256::digest(&prefix);
+    let hex: String = digest[..16].iter().map(|b| format!("{b:02x}")).collect();
+    Ok(format!("{hex}-{len}"))
+}
+
+/// The cached preview of `source`, made now if there is none. Hits are
+/// touched so pruning drops the least recently used entries.
+pub fn cached_preview(base: &Path, source: &Path) -> Result<PathBuf> {
+    let dir = previews_dir(base);
+    let cached = dir.join(format!("{}.jpg", cache_key(source)?));
+    if let Ok(file) = File::options().append(true).open(&cached) {
+        let _ = file.set_modified(SystemTime::now());
+        return Ok(cached);
+    }
+
+    fs::create_dir_all(&dir).context("create previews dir")?;
+    let img = image::open(source).with_context(|| format!("open {}", source.display()))?;
+    let (small, _) = fit_within(&img.to_rgb8(), (PREVIEW_MAX_PX, PREVIEW_MAX_PX));
+    let tmp = cached.with_extension("jpg.tmp");
+    let mut out = File::create(&tmp).context("create preview")?;
+    JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY)
+        .encode_image(&small)
+        .context("write preview")?;
+    drop(out);
+    fs::rename(&tmp, &cached).context("rename preview")?;
+    Ok(cached)
+}
+
+/// Deletes the least recently used previews until the cache fits in
+/// `max_bytes`, along with leftovers of interrupted writes. Returns how many
+/// files were removed.
+pub fn prune(base: &Path, max_bytes: u64) -> Result<usize> {
+    let entries = match fs::read_dir(previews_dir(base)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err).context("read previews dir"),
+    };
+    let mut removed = 0;
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        if path.extension().is_some_and(|ext| ext == "tmp") {
+            if fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+            continue;
+        }
+        let used = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        files.push((used, meta.len(), path));
+    }
+
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    files.sort_by_key(|(used, ..)| *used);
+    for (_, len, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        fs::remove_file(&path).with_context(|| format!("remove {}", path.display()))?;
+        total -= len;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+    use std::time::Duration;
+
+    #[test]
+    fn previews_are_shared_by_copies_and_pruned_oldest_first() {
+        let base = std::env::temp_dir().join(format!("talaria-previews-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&base).unwrap();
+        let frame = base.join("frame.jpg");
+        RgbImage::from_pixel(3200, 1800, Rgb([200, 40, 40]))
+            .save(&frame)
+            .unwrap();
+        let copy = base.join("copy.jpg");
+        fs::copy(&frame, &copy).unwrap();
+
+        let cached = cached_preview(&base, &frame).unwrap();
+        assert_eq!(image::image_dimensions(&cached).unwrap(), (1600, 900));
+        assert_eq!(cached_preview(&base, &copy).unwrap(), cached);
+
+        let other = base.join("other.jpg");
+        RgbImage::from_pixel(400, 300, Rgb([10, 10, 10]))
+            .save(&other)
+            .unwrap();
+        let older = File::options().append(true).open(&cached).unwrap();
+        older
+            .set_modified(SystemTime::now() - Duration::from_secs(60))
+            .unwrap();
+        let newer = cached_preview(&base, &other).unwrap();
+        fs::write(previews_dir(&base).join("stale.jpg.tmp"), b"x").unwrap();
+
+        let newer_len = fs::metadata(&newer).unwrap().len();
+        assert_eq!(prune(&base, newer_len).unwrap(), 2);
+        assert!(!cached.exists());
+        assert!(newer.exists());
+        assert_eq!(prune(&base, 0).unwrap(), 1);
+        let _ = fs::remove_dir_all(&base);
+    }
+}
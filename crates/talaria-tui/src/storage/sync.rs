@@ -0,0 +1,219 @@
+//! Two-way sync of a product's fields with its Hermes record.
+//!
+//! Every write that comes back from Hermes records the fields both sides
+//! then agreed on as the manifest's `synced` base. A later sync compares
+//! each side with that base: what only one side changed is merged, and
+//! paths both sides changed are conflicts the user resolves. Resolutions
+//! are appended to `logs/sync-audit.jsonl`.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use talaria_core::models::{ProductRecord, ProductUpdateRequest};
+use talaria_core::product_diff::{self, FieldDiff, ProductFields};
+
+use super::{ProductManifest, logs_dir};
+
+/// What a sync does with a product.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncPlan {
+    /// Nothing changed locally; take the Hermes record.
+    Pull,
+    /// Send these merged fields to Hermes, then take the record it returns.
+    Push(ProductFields),
+    /// Both sides changed these paths; ask the user.
+    Conflict(Vec<FieldDiff>),
+}
+
+/// How the user settled a conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncResolution {
+    KeepLocal,
+    KeepRemote,
+    /// Both sides' changes, local winning where they overlap.
+    Merge,
+}
+
+impl SyncResolution {
+    pub fn label(self) -> &'static str {
+        match self {
+            SyncResolution::KeepLocal => "kept local",
+            SyncResolution::KeepRemote => "kept Hermes",
+            SyncResolution::Merge => "merged",
+        }
+    }
+}
+
+/// One line of the sync audit log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncAuditEntry {
+    pub at: DateTime<Local>,
+    pub product_id: String,
+    pub sku_alias: String,
+    pub resolution: SyncResolution,
+    /// The conflicting values as they were when the user chose.
+    pub conflicts: Vec<FieldDiff>,
+}
+
+pub fn sync_audit_path(base: &Path) -> PathBuf {
+    logs_dir(base).join("sync-audit.jsonl")
+}
+
+pub fn append_sync_audit(base: &Path, entry: &SyncAuditEntry) -> Result<()> {
+    let path = sync_audit_path(base);
+    fs::create_dir_all(logs_dir(base)).context("create logs dir")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("open {}", path.display()))?;
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("write {}", path.display()))
+}
+
+/// The manifest's synced fields, as [`ProductFields::from_manifest`] reads
+/// them.
+pub fn local_fields(product: &ProductManifest) -> ProductFields {
+    serde_json::to_value(product)
+        .map(|manifest| ProductFields::from_manifest(&manifest))
+        .unwrap_or_default()
+}
+
+/// Compares `local` and `remote` with the last synced base. A manifest
+/// synced before bases were recorded has none, so any difference is a
+/// conflict.
+pub fn plan_sync(local: &ProductManifest, remote: &ProductRecord) -> SyncPlan {
+    let local_fields = local_fields(local);
+    let remote_fields = ProductFields::from(remote);
+    let Some(base) = &local.synced else {
+        let differences = product_diff::diff(&local_fields, &remote_fields);
+        return if differences.is_empty() {
+            SyncPlan::Pull
+        } else {
+            SyncPlan::Conflict(differences)
+        };
+    };
+    let conflicts = product_diff::conflicts(base, &local_fields, &remote_fields);
+    if !conflicts.is_empty() {
+        return SyncPlan::Conflict(conflicts);
+    }
+    let merged = product_diff::merge(base, &local_fields, &remote_fields);
+    if product_diff::diff(&merged, &remote_fields).is_empty() {
+        SyncPlan::Pull
+    } else {
+        SyncPlan::Push(merged)
+    }
+}
+
+/// The fields `resolution` settles on, or `None` when that is the Hermes
+/// record as it stands.
+pub fn resolve(
+    local: &ProductManifest,
+    remote: &ProductRecord,
+    resolution: SyncResolution,
+) -> Option<ProductFields> {
+    let local_fields = local_fields(local);
+    let remote_fields = ProductFields::from(remote);
+    let resolved = match resolution {
+        SyncResolution::KeepRemote => return None,
+        SyncResolution::KeepLocal => local_fields,
+        // Without a base every difference counts as a local change.
+        SyncResolution::Merge => match &local.synced {
+            Some(base) => product_diff::merge(base, &local_fields, &remote_fields),
+            None => local_fields,
+        },
+    };
+    (!product_diff::diff(&resolved, &remote_fields).is_empty()).then_some(resolved)
+}
+
+/// A Hermes update that sets every synced field to `fields`. Fields cleared
+/// locally are left as Hermes has them; an update cannot unset them.
+pub fn update_request(fields: ProductFields) -> ProductUpdateRequest {
+    let text = |value: Option<String>| value.filter(|text| !text.trim().is_empty());
+    ProductUpdateRequest {
+        sku_alias: Some(fields.sku_alias).filter(|sku| !sku.is_empty()),
+        display_name: text(fields.display_name),
+        context_text: text(fields.context_text),
+        structure_json: fields.structure_json,
+        listings_json: Some(fields.listings_json).filter(|listings| !listings.is_null()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn manifest(display_name: &str, price: f64) -> ProductManifest {
+        serde_json::from_value(json!({
+            "product_id": "p1",
+            "sku_alias": "SKU-1",
+            "display_name": display_name,
+            "created_at": Local::now(),
+            "updated_at": Local::now(),
+            "images": [],
+            "hero_rel_path": null,
+            "listings": {"EBAY_US": {"title": "Lamp", "price": price}},
+        }))
+        .unwrap()
+    }
+
+    fn record(display_name: &str, price: f64) -> ProductRecord {
+        let mut listings = local_fields(&manifest(display_name, price)).listings_json;
+        listings["EBAY_US"]["extra"] = json!("kept by Hermes");
+        ProductRecord {
+            id: "p1".to_string(),
+            sku_alias: "SKU-1".to_string(),
+            display_name: Some(display_name.to_string()),
+            context_text: None,
+            structure_json: None,
+            listings_json: listings,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn one_sided_changes_merge_and_overlaps_conflict() {
+        let mut local = manifest("Lamp", 25.0);
+        assert!(matches!(
+            plan_sync(&local, &record("Brass lamp", 25.0)),
+            SyncPlan::Conflict(_)
+        ));
+
+        local.synced = Some(local_fields(&local));
+        assert_eq!(
+            plan_sync(&local, &record("Brass lamp", 25.0)),
+            SyncPlan::Pull
+        );
+
+        local.listings.get_mut("EBAY_US").unwrap().price = Some(20.0);
+        let SyncPlan::Push(merged) = plan_sync(&local, &record("Brass lamp", 25.0)) else {
+            panic!("expected a push");
+        };
+        assert_eq!(merged.display_name.as_deref(), Some("Brass lamp"));
+        assert_eq!(merged.listings_json["EBAY_US"]["price"], json!(20.0));
+        assert_eq!(merged.listings_json["EBAY_US"]["extra"], "kept by Hermes");
+
+        let remote = record("Brass lamp", 30.0);
+        let SyncPlan::Conflict(conflicts) = plan_sync(&local, &remote) else {
+            panic!("expected a conflict");
+        };
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].path, "listings_json.EBAY_US.price");
+        assert_eq!(resolve(&local, &remote, SyncResolution::KeepRemote), None);
+        let merged = resolve(&local, &remote, SyncResolution::Merge).unwrap();
+        assert_eq!(merged.display_name.as_deref(), Some("Brass lamp"));
+        assert_eq!(merged.listings_json["EBAY_US"]["price"], json!(20.0));
+        let kept = resolve(&local, &remote, SyncResolution::KeepLocal).unwrap();
+        assert_eq!(kept.display_name.as_deref(), Some("Lamp"));
+    }
+}
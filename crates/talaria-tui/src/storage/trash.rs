@@ -0,0 +1,437 @@
+//! Soft-delete layer for products and session frames.
+//!
+//! Deleted products move to `products/_trash/<product_id>_<stamp>/` (their
+//! sessions ride along under `_sessions/`), deleted frames move to
+//! `sessions/<session_id>/_trash/`. Each trashed item carries a record so it
+//! can be restored; items older than the retention window are purged.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    SessionFrameEntry, SessionManifest, atomic_write_json, product_dir, products_dir, read_json,
+    session_dir, session_manifest_path, sessions_dir,
+};
+
+pub const TRASH_RETENTION_DAYS: i64 = 7;
+const TRASH_DIR: &str = "_trash";
+const PRODUCT_RECORD: &str = "_trash.json";
+const PRODUCT_SESSIONS_DIR: &str = "_sessions";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TrashItem {
+    Product {
+        product_id: String,
+        sku_alias: String,
+        deleted_at: DateTime<Local>,
+        /// Directory name under `products/_trash`.
+        entry: String,
+    },
+    Frame {
+        session_id: String,
+        frame: SessionFrameEntry,
+        selected: bool,
+        deleted_at: DateTime<Local>,
+        /// File name under `sessions/<session_id>/_trash`.
+        entry: String,
+    },
+}
+
+impl TrashItem {
+    pub fn deleted_at(&self) -> DateTime<Local> {
+        match self {
+            TrashItem::Product { deleted_at, .. } | TrashItem::Frame { deleted_at, .. } => {
+                *deleted_at
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            TrashItem::Product { sku_alias, .. } => format!("product {sku_alias}"),
+            TrashItem::Frame { frame, .. } => format!("frame {}", frame.rel_path),
+        }
+    }
+}
+
+pub fn products_trash_dir(base: &Path) -> PathBuf {
+    products_dir(base).join(TRASH_DIR)
+}
+
+pub fn session_trash_dir(base: &Path, session_id: &str) -> PathBuf {
+    session_dir(base, session_id).join(TRASH_DIR)
+}
+
+fn stamp() -> String {
+    Local::now().format("%Y%m%d_%H%M%S%3f").to_string()
+}
+
+/// Moves a product and its sessions into the products trash.
+/// Returns the number of sessions moved along with it.
+pub fn trash_product(base: &Path, product_id: &str) -> Result<usize> {
+    let src = product_dir(base, product_id);
+    let sku_alias = read_json::<super::ProductManifest>(&src.join("product.json"))
+        .map(|p| p.sku_alias)
+        .unwrap_or_else(|_| product_id.to_string());
+    let entry = format!("{product_id}_{}", stamp());
+    let dst = products_trash_dir(base).join(&entry);
+    fs::create_dir_all(products_trash_dir(base)).context("create products trash")?;
+    if src.exists() {
+        fs::rename(&src, &dst)
+            .with_context(|| format!("move {} -> {}", src.display(), dst.display()))?;
+    } else {
+        fs::create_dir_all(&dst).context("create product trash entry")?;
+    }
+
+    let mut moved_sessions = 0usize;
+    let sessions_root = sessions_dir(base);
+    if sessions_root.exists() {
+        let sessions_dst = dst.join(PRODUCT_SESSIONS_DIR);
+        for entry in fs::read_dir(&sessions_root).context("read sessions dir")? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = entry.file_name();
+            if !path.is_dir() || name.to_str().is_some_and(|s| s.starts_with('_')) {
+                continue;
+            }
+            let Ok(manifest) = read_json::<SessionManifest>(&path.join("session.json")) else {
+                continue;
+            };
+            if manifest.product_id == product_id {
+                fs::create_dir_all(&sessions_dst).context("create trashed sessions dir")?;
+                fs::rename(&path, sessions_dst.join(&name))
+                    .with_context(|| format!("move {}", path.display()))?;
+                moved_sessions += 1;
+            }
+        }
+    }
+
+    let item = TrashItem::Product {
+        product_id: product_id.to_string(),
+        sku_alias,
+        deleted_at: Local::now(),
+        entry,
+    };
+    atomic_write_json(&dst.join(PRODUCT_RECORD), &item)?;
+    Ok(moved_sessions)
+}
+
+/// Moves a frame file into the session trash and removes it from the manifest.
+pub fn trash_session_frame(base: &Path, session_id: &str, frame_rel_path: &str) -> Result<()> {
+    let path = session_manifest_path(base, session_id);
+    let mut manifest: SessionManifest = read_json(&path)?;
+    let frame = manifest
+        .frames
+        .iter()
+        .find(|f| f.rel_path == frame_rel_path)
+        .cloned()
+        .context("frame not found in session")?;
+    let selected = manifest
+        .picks
+        .selected_rel_paths
+        .iter()
+        .any(|p| p == frame_rel_path);
+
+    let file_name = Path::new(frame_rel_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("frame");
+    let entry = format!("{}_{file_name}", stamp());
+    let trash = session_trash_dir(base, session_id);
+    fs::create_dir_all(&trash).context("create session trash")?;
+    let full = session_dir(base, session_id).join(frame_rel_path);
+    if full.exists() {
+        fs::rename(&full, trash.join(&entry))
+            .with_context(|| format!("move {}", full.display()))?;
+    }
+    let item = TrashItem::Frame {
+        session_id: session_id.to_string(),
+        frame,
+        selected,
+        deleted_at: Local::now(),
+        entry: entry.clone(),
+    };
+    atomic_write_json(&trash.join(format!("{entry}.json")), &item)?;
+
+    manifest.frames.retain(|f| f.rel_path != frame_rel_path);
+    manifest
+        .picks
+        .selected_rel_paths
+        .retain(|p| p != frame_rel_path);
+    manifest.picks.shot_labels.remove(frame_rel_path);
+    atomic_write_json(&path, &manifest)?;
+    Ok(())
+}
+
+/// All restorable items, newest first.
+pub fn list_trash(base: &Path) -> Result<Vec<TrashItem>> {
+    let mut items = Vec::new();
+    if let Ok(entries) = fs::read_dir(products_trash_dir(base)) {
+        for entry in entries.flatten() {
+            if let Ok(item) = read_json::<TrashItem>(&entry.path().join(PRODUCT_RECORD)) {
+                items.push(item);
+            }
+        }
+    }
+    if let Ok(sessions) = fs::read_dir(sessions_dir(base)) {
+        for session in sessions.flatten() {
+            let Ok(entries) = fs::read_dir(session.path().join(TRASH_DIR)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                if let Ok(item) = read_json::<TrashItem>(&path) {
+                    items.push(item);
+                }
+            }
+        }
+    }
+    items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at()));
+    Ok(items)
+}
+
+/// Puts a trashed item back where it came from.
+pub fn restore(base: &Path, item: &TrashItem) -> Result<()> {
+    match item {
+        TrashItem::Product {
+            product_id, entry, ..
+        } => {
+            let src = products_trash_dir(base).join(entry);
+            let dst = product_dir(base, product_id);
+            if dst.exists() {
+                return Err(anyhow::anyhow!(
+                    "product {product_id} already exists; not restoring over it"
+                ));
+            }
+            let trashed_sessions = src.join(PRODUCT_SESSIONS_DIR);
+            if let Ok(sessions) = fs::read_dir(&trashed_sessions) {
+                fs::create_dir_all(sessions_dir(base)).context("create sessions dir")?;
+                for session in sessions.flatten() {
+                    let target = sessions_dir(base).join(session.file_name());
+                    fs::rename(session.path(), &target)
+                        .with_context(|| format!("restore {}", target.display()))?;
+                }
+                let _ = fs::remove_dir(&trashed_sessions);
+            }
+            let _ = fs::remove_file(src.join(PRODUCT_RECORD));
+            fs::rename(&src, &dst)
+                .with_context(|| format!("move {} -> {}", src.display(), dst.display()))?;
+        }
+        TrashItem::Frame {
+            session_id,
+            frame,
+            selected,
+            entry,
+            ..
+        } => {
+            let trash = session_trash_dir(base, session_id);
+            let dst = session_dir(base, session_id).join(&frame.rel_path);
+            if let Some(parent) = dst.parent() {
+                fs::create_dir_all(parent).context("create frame dir")?;
+            }
+            fs::rename(trash.join(entry), &dst)
+                .with_context(|| format!("restore {}", dst.display()))?;
+            let path = session_manifest_path(base, session_id);
+            let mut manifest: SessionManifest = read_json(&path)?;
+            if !manifest.frames.iter().any(|f| f.rel_path == frame.rel_path) {
+                manifest.frames.push(frame.clone());
+                manifest.frames.sort_by_key(|f| f.created_at);
+            }
+            if *selected && !manifest.picks.selected_rel_paths.contains(&frame.rel_path) {
+                manifest
+                    .picks
+                    .selected_rel_paths
+                    .push(frame.rel_path.clone());
+            }
+            atomic_write_json(&path, &manifest)?;
+            let _ = fs::remove_file(trash.join(format!("{entry}.json")));
+        }
+    }
+    Ok(())
+}
+
+/// Deletes trashed items older than [`TRASH_RETENTION_DAYS`]. Returns how many were purged.
+pub fn purge_expired(base: &Path) -> Result<usize> {
+    let cutoff = Local::now() - Duration::days(TRASH_RETENTION_DAYS);
+    let mut purged = 0usize;
+    for item in list_trash(base)? {
+        if item.deleted_at() >= cutoff {
+            continue;
+        }
+        match &item {
+            TrashItem::Product { entry, .. } => {
+                fs::remove_dir_all(products_trash_dir(base).join(entry))
+                    .context("purge trashed product")?;
+            }
+            TrashItem::Frame {
+                session_id, entry, ..
+            } => {
+                let trash = session_trash_dir(base, session_id);
+                let _ = fs::remove_file(trash.join(entry));
+                fs::remove_file(trash.join(format!("{entry}.json")))
+                    .context("purge trashed frame")?;
+            }
+        }
+        purged += 1;
+    }
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{
+        append_session_frame, create_product, create_session, load_product, load_session,
+        session_frames_dir,
+    };
+    use uuid::Uuid;
+
+    fn temp_base(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("talaria-trash-{name}-{}", Uuid::new_v4()))
+    }
+
+    /// A session for `product_id` with frames `f1.jpg` and `f2.jpg`, `f1` picked.
+    fn session_with_frames(base: &Path, product_id: &str) -> SessionManifest {
+        let session = create_session(base, product_id).unwrap();
+        let start = Local::now() - Duration::seconds(10);
+        for (offset, name) in ["f1.jpg", "f2.jpg"].into_iter().enumerate() {
+            fs::write(
+                session_frames_dir(base, &session.session_id).join(name),
+                name,
+            )
+            .unwrap();
+            append_session_frame(
+                base,
+                &session.session_id,
+                &format!("frames/{name}"),
+                None,
+                None,
+                start + Duration::seconds(offset as i64),
+                None,
+            )
+            .unwrap();
+        }
+        let mut session = load_session(base, &session.session_id).unwrap();
+        session.picks.selected_rel_paths = vec!["frames/f1.jpg".into()];
+        atomic_write_json(&session_manifest_path(base, &session.session_id), &session).unwrap();
+        session
+    }
+
+    #[test]
+    fn trashed_product_takes_its_sessions_and_restores_them() {
+        let base = temp_base("product");
+        let product = create_product(&base).unwrap();
+        let other = create_product(&base).unwrap();
+        let own = create_session(&base, &product.product_id).unwrap();
+        let kept = create_session(&base, &other.product_id).unwrap();
+
+        assert_eq!(trash_product(&base, &product.product_id).unwrap(), 1);
+        assert!(!product_dir(&base, &product.product_id).exists());
+        assert!(!session_dir(&base, &own.session_id).exists());
+        assert!(session_dir(&base, &kept.session_id).exists());
+
+        let items = list_trash(&base).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].describe(),
+            format!("product {}", product.sku_alias)
+        );
+
+        restore(&base, &items[0]).unwrap();
+        assert_eq!(
+            load_product(&base, &product.product_id).unwrap().sku_alias,
+            product.sku_alias
+        );
+        assert_eq!(
+            load_session(&base, &own.session_id).unwrap().product_id,
+            product.product_id
+        );
+        assert!(list_trash(&base).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite_an_existing_product() {
+        let base = temp_base("overwrite");
+        let product = create_product(&base).unwrap();
+        trash_product(&base, &product.product_id).unwrap();
+        fs::create_dir_all(product_dir(&base, &product.product_id)).unwrap();
+
+        let items = list_trash(&base).unwrap();
+        let err = restore(&base, &items[0]).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        assert_eq!(list_trash(&base).unwrap().len(), 1);
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn trashed_frame_restores_with_its_pick() {
+        let base = temp_base("frame");
+        let product = create_product(&base).unwrap();
+        let session = session_with_frames(&base, &product.product_id);
+        let frame = session_dir(&base, &session.session_id).join("frames/f1.jpg");
+
+        trash_session_frame(&base, &session.session_id, "frames/f1.jpg").unwrap();
+        assert!(!frame.exists());
+        let trashed = load_session(&base, &session.session_id).unwrap();
+        assert_eq!(trashed.frames.len(), 1);
+        assert!(trashed.picks.selected_rel_paths.is_empty());
+
+        let items = list_trash(&base).unwrap();
+        assert!(matches!(
+            &items[..],
+            [TrashItem::Frame { selected: true, .. }]
+        ));
+
+        restore(&base, &items[0]).unwrap();
+        assert_eq!(fs::read(&frame).unwrap(), b"f1.jpg");
+        let restored = load_session(&base, &session.session_id).unwrap();
+        let frames = restored
+            .frames
+            .iter()
+            .map(|f| f.rel_path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(frames, ["frames/f1.jpg", "frames/f2.jpg"]);
+        assert_eq!(restored.picks.selected_rel_paths, ["frames/f1.jpg"]);
+        assert!(list_trash(&base).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn purge_drops_only_items_past_retention() {
+        let base = temp_base("purge");
+        let product = create_product(&base).unwrap();
+        let other = create_product(&base).unwrap();
+        let session = session_with_frames(&base, &other.product_id);
+        trash_session_frame(&base, &session.session_id, "frames/f2.jpg").unwrap();
+        trash_product(&base, &product.product_id).unwrap();
+
+        let Some(TrashItem::Product { entry, .. }) = list_trash(&base)
+            .unwrap()
+            .into_iter()
+            .find(|item| matches!(item, TrashItem::Product { .. }))
+        else {
+            panic!("product not in trash");
+        };
+        let record = products_trash_dir(&base).join(&entry).join(PRODUCT_RECORD);
+        let mut item: TrashItem = read_json(&record).unwrap();
+        if let TrashItem::Product { deleted_at, .. } = &mut item {
+            *deleted_at = Local::now() - Duration::days(TRASH_RETENTION_DAYS + 1);
+        }
+        atomic_write_json(&record, &item).unwrap();
+
+        assert_eq!(purge_expired(&base).unwrap(), 1);
+        assert!(!products_trash_dir(&base).join(&entry).exists());
+        let items = list_trash(&base).unwrap();
+        assert!(matches!(&items[..], [TrashItem::Frame { .. }]));
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -1,28 +1,49 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use chrono::Local;
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Receiver;
 use serde_json::Value;
 use tokio::runtime::Runtime;
 
+use crate::event_bus::EventSender;
+use crate::jobs::{self, JobGuard, JobKey, JobKind, RunningJobs};
 use crate::storage;
-use crate::types::{ActivityEntry, AppEvent, Severity, StorageCommand, StorageEvent};
+use crate::storage::media::GalleryItem;
+use crate::storage::sync::{self, SyncPlan, SyncResolution};
+use crate::storage::trash;
+use crate::types::{ActivityEntry, AppEvent, BulkAction, Severity, StorageCommand, StorageEvent};
+use crate::util::hero::FrameSubject;
 use talaria_core::client::HermesClient;
+use talaria_core::idempotency::IdempotencyStore;
+use talaria_core::images;
+use talaria_core::ledger::{JobLedger, JobLedgerEntry, TrackedState};
 use talaria_core::models::{
     CategorySelectionInput, HsufEnrichRequest, ImagesSource, JobState, ListingDimensionsInput,
     ListingDraftInput, ListingDraftRequest, ListingPackageInput, ListingResponse,
-    ListingWeightInput, MarketplaceId, ProductCreateRequest, ProductRecord, ProductUpdateRequest,
-    PublicListingRequest, PublicPipelineOverrides,
+    ListingWeightInput, LlmStageOptions, MarketplaceId, MediaPurpose, ProductCreateRequest,
+    ProductRecord, ProductUpdateRequest, PublicListingRequest, PublicPipelineOverrides,
+    UpdateMediaRequest,
 };
+use talaria_core::quality::QualityRules;
+use talaria_core::stats::{self, DayRange};
 
+/// How many of the newest ledger entries are checked for a missed outcome.
+const PENDING_JOB_CHECKS: usize = 20;
+/// Product summaries per `ProductsBatch` event while listing local products.
+const PRODUCTS_BATCH: usize = 48;
+
+/// Follows a queued listing job; `job` keeps it cancellable. Cancelling only
+/// stops following it, as the Hermes job runs on and its outcome is picked
+/// up by the next failed-jobs refresh.
 fn spawn_listing_job_poll(
     base: PathBuf,
     hermes: HermesClient,
-    event_tx: Sender<AppEvent>,
+    event_tx: EventSender,
+    job: JobGuard,
     job_id: String,
     product_id: String,
     marketplace: MarketplaceId,
@@ -31,6 +52,12 @@ fn spawn_listing_job_poll(
     publish: bool,
 ) {
     thread::spawn(move || {
+        let _span = tracing::info_span!(
+            "listing_job",
+            job_id = %job_id,
+            product_id = %product_id
+        )
+        .entered();
         let rt = match Runtime::new() {
             Ok(rt) => rt,
             Err(err) => {
@@ -44,16 +71,31 @@ fn spawn_listing_job_poll(
         let res: Result<()> = (|| {
             let deadline = Instant::now() + Duration::from_secs(180);
             let (job_request, resp) = loop {
-                let info = rt.block_on(hermes.get_job_status(&job_id))?;
+                let info = job.run(&rt, hermes.get_job_status(&job_id))?;
                 match info.state {
-                    JobState::Queued {} | JobState::Running {} => {}
-                    JobState::Completed { result } => break (info.request, result),
+                    JobState::Queued {} | JobState::Running {} | JobState::Unknown { .. } => {}
+                    JobState::Completed { result } => {
+                        record_job_outcome(&event_tx, &job_id, TrackedState::Completed, None);
+                        break (info.request, result);
+                    }
                     JobState::Failed { error, stage } => {
                         let detail = stage
                             .as_deref()
                             .map(|s| format!(" (stage: {s})"))
                             .unwrap_or_default();
-                        return Err(anyhow::anyhow!("Listing job failed{detail}: {error}"));
+                        let recorded = match &stage {
+                            Some(stage) => format!("{stage}: {error}"),
+                            None => error.clone(),
+                        };
+                        record_job_outcome(
+                            &event_tx,
+                            &job_id,
+                            TrackedState::Failed,
+                            Some(&recorded),
+                        );
+                        return Err(anyhow::anyhow!(
+                            "Listing job failed{detail}: {error} (Home: j to retry)"
+                        ));
                     }
                 }
                 if Instant::now() >= deadline {
@@ -64,7 +106,10 @@ fn spawn_listing_job_poll(
                     }));
                     return Ok(());
                 }
-                thread::sleep(Duration::from_secs(2));
+                job.run(&rt, async {
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                    anyhow::Ok(())
+                })?;
             };
 
             let mut listings = None;
@@ -79,8 +124,9 @@ fn spawn_listing_job_poll(
             }
 
             let mut listings_map = listings.unwrap_or_else(std::collections::HashMap::new);
-            let listing =
+            let mut listing =
                 listing_from_response(&resp, Some(&job_request), &settings, dry_run, publish)?;
+            apply_local_overrides(&base, &product_id, &mut listing);
             let marketplace_key = marketplace_key(marketplace);
             listings_map.insert(marketplace_key, listing);
 
@@ -111,13 +157,139 @@ fn spawn_listing_job_poll(
             Ok(())
         })();
 
+        match res {
+            Err(err) if jobs::is_canceled(&err) => {
+                let _ = event_tx.send(AppEvent::Storage(StorageEvent::JobCanceled(
+                    job.key().clone(),
+                )));
+                let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                    at: Local::now(),
+                    severity: Severity::Warning,
+                    message: format!(
+                        "Stopped following listing job {job_id}; Hermes may still finish it."
+                    ),
+                }));
+            }
+            Err(err) => report_job_error(&event_tx, &job, err),
+            Ok(()) => {}
+        }
+    });
+}
+
+/// Reports a spawned job that did not succeed: a cancellation as such,
+/// anything else as an error.
+fn report_job_error(event_tx: &EventSender, job: &JobGuard, err: anyhow::Error) {
+    if jobs::is_canceled(&err) {
+        let key = job.key();
+        let _ = event_tx.send(AppEvent::Storage(StorageEvent::JobCanceled(key.clone())));
+        let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+            at: Local::now(),
+            severity: Severity::Warning,
+            message: format!("Canceled {} of {}.", key.kind.label(), key.product_id),
+        }));
+        return;
+    }
+    report_error(event_tx, &err);
+}
+
+/// Shows `err` and logs it, passing on the Hermes `request_id` when the
+/// failure came from an API call.
+fn report_error(event_tx: &EventSender, err: &anyhow::Error) {
+    let request_id = err.chain().find_map(|cause| {
+        cause
+            .downcast_ref::<talaria_core::Error>()
+            .and_then(talaria_core::Error::request_id)
+    });
+    if let Some(request_id) = request_id {
+        let _ = event_tx.send(AppEvent::Storage(StorageEvent::FailedRequest(
+            request_id.to_string(),
+        )));
+    }
+    let message = format!("{err:#}");
+    let _ = event_tx.send(AppEvent::Storage(StorageEvent::Error(message.clone())));
+    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+        at: Local::now(),
+        severity: Severity::Error,
+        message,
+    }));
+}
+
+/// Records a listing job's outcome in the ledger and sends the refreshed
+/// failed-jobs queue.
+fn record_job_outcome(
+    event_tx: &EventSender,
+    job_id: &str,
+    state: TrackedState,
+    error: Option<&str>,
+) {
+    let res = JobLedger::open_default().and_then(|ledger| {
+        ledger.set_state(job_id, state, error)?;
+        ledger.failed()
+    });
+    match res {
+        Ok(failed) => {
+            let _ = event_tx.send(AppEvent::Storage(StorageEvent::FailedJobsListed(failed)));
+        }
+        Err(err) => {
+            let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                at: Local::now(),
+                severity: Severity::Warning,
+                message: format!("Job ledger update failed: {err}"),
+            }));
+        }
+    }
+}
+
+/// Asks Hermes about the most recent jobs the ledger still has as pending
+/// (they may have failed while the TUI was closed), then sends the failed
+/// ones.
+fn spawn_failed_jobs_refresh(hermes: Option<HermesClient>, event_tx: EventSender) {
+    thread::spawn(move || {
+        let res: Result<()> = (|| {
+            let ledger = JobLedger::open_default()?;
+            if let Some(hermes) = hermes.filter(|h| h.has_api_key()) {
+                let pending: Vec<_> = ledger
+                    .recent(PENDING_JOB_CHECKS)?
+                    .into_iter()
+                    .filter(|entry| entry.state == TrackedState::Pending)
+                    .collect();
+                if !pending.is_empty() {
+                    let rt = Runtime::new()?;
+                    for entry in pending {
+                        let Ok(info) = rt.block_on(hermes.get_job_status(&entry.job_id)) else {
+                            continue;
+                        };
+                        match info.state {
+                            JobState::Completed { .. } => {
+                                ledger.set_state(&entry.job_id, TrackedState::Completed, None)?;
+                            }
+                            JobState::Failed { error, stage } => {
+                                let error = match stage {
+                                    Some(stage) => format!("{stage}: {error}"),
+                                    None => error,
+                                };
+                                ledger.set_state(
+                                    &entry.job_id,
+                                    TrackedState::Failed,
+                                    Some(&error),
+                                )?;
+                            }
+                            JobState::Queued {}
+                            | JobState::Running {}
+                            | JobState::Unknown { .. } => {}
+                        }
+                    }
+                }
+            }
+            let failed = ledger.failed()?;
+            let _ = event_tx.send(AppEvent::Storage(StorageEvent::FailedJobsListed(failed)));
+            Ok(())
+        })();
         if let Err(err) = res {
-            let message = format!("{err:#}");
-            let _ = event_tx.send(AppEvent::Storage(StorageEvent::Error(message.clone())));
             let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
                 at: Local::now(),
-                severity: Severity::Error,
-                message,
+                severity: Severity::Warning,
+                message: format!("Checking listing jobs failed: {err:#}"),
             }));
         }
     });
@@ -125,23 +297,105 @@ fn spawn_listing_job_poll(
 
 pub fn spawn_storage_worker(
     base_dir: PathBuf,
-    hermes: Option<HermesClient>,
+    mut hermes: Option<HermesClient>,
+    mut use_signed_urls: bool,
     cmd_rx: Receiver<StorageCommand>,
-    event_tx: Sender<AppEvent>,
+    event_tx: EventSender,
+    jobs: RunningJobs,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
         let base = base_dir;
         let rt = Runtime::new().expect("tokio runtime");
 
+        match storage::gc(&base) {
+            Ok(report) => {
+                if report.trash_purged > 0 {
+                    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                        at: Local::now(),
+                        severity: Severity::Info,
+                        message: format!(
+                            "Purged {} trashed item(s) older than {} days.",
+                            report.trash_purged,
+                            trash::TRASH_RETENTION_DAYS
+                        ),
+                    }));
+                }
+                if report.previews_pruned > 0 {
+                    tracing::info!(removed = report.previews_pruned, "pruned preview cache");
+                }
+            }
+            Err(err) => {
+                let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                    at: Local::now(),
+                    severity: Severity::Warning,
+                    message: format!("Storage cleanup failed: {err:#}"),
+                }));
+            }
+        }
+
+        match storage::list_resumable_sessions(&base) {
+            Ok(sessions) if !sessions.is_empty() => {
+                let _ = event_tx.send(AppEvent::Storage(StorageEvent::ResumableSessions(sessions)));
+            }
+            Ok(_) => {}
+            Err(err) => {
+                let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                    at: Local::now(),
+                    severity: Severity::Warning,
+                    message: format!("Scanning for unfinished sessions failed: {err:#}"),
+                }));
+            }
+        }
+
+        spawn_failed_jobs_refresh(hermes.clone(), event_tx.clone());
+
+        let mut online = true;
+        // Enrich/listing jobs held while offline, and those released to run.
+        let mut paused: VecDeque<StorageCommand> = VecDeque::new();
+        let mut resumed: VecDeque<StorageCommand> = VecDeque::new();
+
         loop {
-            let cmd = match cmd_rx.recv() {
-                Ok(cmd) => cmd,
-                Err(_) => return,
+            let cmd = match resumed.pop_front() {
+                Some(cmd) => cmd,
+                None => match cmd_rx.recv() {
+                    Ok(cmd) => cmd,
+                    Err(_) => return,
+                },
             };
 
-            if matches!(cmd, StorageCommand::Shutdown) {
-                return;
-            }
+            let cmd = match cmd {
+                StorageCommand::Shutdown => return,
+                StorageCommand::SetClient {
+                    hermes: client,
+                    use_signed_urls: signed,
+                } => {
+                    hermes = client;
+                    use_signed_urls = signed;
+                    continue;
+                }
+                StorageCommand::SetOnline(now_online) => {
+                    online = now_online;
+                    if online {
+                        resumed.extend(paused.drain(..));
+                    }
+                    continue;
+                }
+                StorageCommand::GenerateProductStructure { ref product_id, .. }
+                | StorageCommand::GenerateProductListing { ref product_id, .. }
+                    if !online =>
+                {
+                    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                        at: Local::now(),
+                        severity: Severity::Info,
+                        message: format!(
+                            "Hermes offline; generation for {product_id} waits until it is back."
+                        ),
+                    }));
+                    paused.push_back(cmd);
+                    continue;
+                }
+                cmd => cmd,
+            };
 
             let res: Result<()> = (|| match cmd {
                 StorageCommand::CreateProductAndSession => {
@@ -187,9 +441,20 @@ pub fn spawn_storage_worker(
                             .send(AppEvent::Storage(StorageEvent::ProductsListed(products)));
                         return Ok(());
                     }
-                    let products = storage::list_products(&base)?;
-                    let _ =
-                        event_tx.send(AppEvent::Storage(StorageEvent::ProductsListed(products)));
+                    let mut first = true;
+                    storage::list_products_in_batches(&base, PRODUCTS_BATCH, |products| {
+                        let _ = event_tx.send(AppEvent::Storage(StorageEvent::ProductsBatch {
+                            products,
+                            first,
+                            done: false,
+                        }));
+                        first = false;
+                    })?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::ProductsBatch {
+                        products: Vec::new(),
+                        first,
+                        done: true,
+                    }));
                     Ok(())
                 }
                 StorageCommand::StartSessionForProduct { product_id } => {
@@ -228,7 +493,7 @@ pub fn spawn_storage_worker(
                     if let Some(hermes) = hermes.as_ref().filter(|h| h.has_api_key()) {
                         rt.block_on(hermes.delete_product(&product_id))?;
                     }
-                    let removed_sessions = storage::delete_product(&base, &product_id)?;
+                    let removed_sessions = trash::trash_product(&base, &product_id)?;
                     let _ = event_tx.send(AppEvent::Storage(StorageEvent::ProductDeleted {
                         product_id: product_id.clone(),
                         removed_sessions,
@@ -253,6 +518,7 @@ pub fn spawn_storage_worker(
                     if removed_sessions > 0 {
                         message.push_str(&format!(" ({} session(s) removed)", removed_sessions));
                     }
+                    message.push_str("; press u to undo");
                     let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
                         at: Local::now(),
                         severity: Severity::Warning,
@@ -327,12 +593,118 @@ pub fn spawn_storage_worker(
                         event_tx.send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
                     Ok(())
                 }
+                StorageCommand::ScanBarcodes {
+                    product_id,
+                    frames,
+                    report_misses,
+                } => {
+                    let event_tx = event_tx.clone();
+                    thread::spawn(move || {
+                        let mut found = None;
+                        for frame in frames {
+                            match talaria_core::barcode::scan_file(&frame) {
+                                Ok(Some(barcode)) => {
+                                    found = Some((barcode, frame));
+                                    break;
+                                }
+                                Ok(None) => {}
+                                Err(err) => {
+                                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::Error(
+                                        format!("Barcode scan failed: {err}"),
+                                    )));
+                                    return;
+                                }
+                            }
+                        }
+                        let _ = event_tx.send(AppEvent::Storage(StorageEvent::BarcodeScanned {
+                            product_id,
+                            found,
+                            report_misses,
+                        }));
+                    });
+                    Ok(())
+                }
+                StorageCommand::ApplyBarcode {
+                    product_id,
+                    barcode,
+                    set_sku,
+                } => {
+                    if let Some(hermes) = hermes.as_ref().filter(|h| h.has_api_key()) {
+                        let local = storage::load_product(&base, &product_id)?;
+                        let update =
+                            ProductUpdateRequest {
+                                sku_alias: set_sku.then(|| barcode.value.clone()),
+                                context_text: Some(barcode.add_to_context(
+                                    local.context_text.as_deref().unwrap_or_default(),
+                                )),
+                                ..Default::default()
+                            };
+                        let row = rt.block_on(hermes.update_product(&product_id, &update))?;
+                        let updated = storage::upsert_product_from_remote(&base, &row)?;
+                        let _ = event_tx
+                            .send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
+                        return Ok(());
+                    }
+                    let updated =
+                        storage::set_product_barcode(&base, &product_id, &barcode, set_sku)?;
+                    let _ =
+                        event_tx.send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
+                    Ok(())
+                }
+                StorageCommand::SetProductGrading {
+                    product_id,
+                    grading,
+                } => {
+                    let updated = storage::set_product_grading(&base, &product_id, grading)?;
+                    if let Some(hermes) = hermes.as_ref().filter(|h| h.has_api_key())
+                        && !updated.listings.is_empty()
+                    {
+                        let update = ProductUpdateRequest {
+                            listings_json: Some(serde_json::to_value(&updated.listings)?),
+                            ..Default::default()
+                        };
+                        let row = rt.block_on(hermes.update_product(&product_id, &update))?;
+                        let updated = storage::upsert_product_from_remote(&base, &row)?;
+                        let _ = event_tx
+                            .send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
+                        return Ok(());
+                    }
+                    let _ =
+                        event_tx.send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
+                    Ok(())
+                }
+                StorageCommand::SetProductMeasurement {
+                    product_id,
+                    measured,
+                } => {
+                    let updated = storage::set_product_measurement(&base, &product_id, measured)?;
+                    if let Some(hermes) = hermes.as_ref().filter(|h| h.has_api_key())
+                        && (updated.structure_json.is_some() || !updated.listings.is_empty())
+                    {
+                        let update = ProductUpdateRequest {
+                            structure_json: updated.structure_json.clone(),
+                            listings_json: (!updated.listings.is_empty())
+                                .then(|| serde_json::to_value(&updated.listings))
+                                .transpose()?,
+                            ..Default::default()
+                        };
+                        let row = rt.block_on(hermes.update_product(&product_id, &update))?;
+                        let updated = storage::upsert_product_from_remote(&base, &row)?;
+                        let _ = event_tx
+                            .send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
+                        return Ok(());
+                    }
+                    let _ =
+                        event_tx.send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
+                    Ok(())
+                }
                 StorageCommand::GenerateProductStructure {
                     product_id,
                     sku_alias,
                     llm_ingest,
                     context_text,
                     prompt_rules,
+                    quality,
                 } => {
                     let hermes = hermes
                         .as_ref()
@@ -345,6 +717,7 @@ pub fn spawn_storage_worker(
                     let base = base.clone();
                     let hermes = hermes.clone();
                     let event_tx = event_tx.clone();
+                    let job = jobs.register(JobKey::new(JobKind::Structure, &product_id));
                     thread::spawn(move || {
                         let rt = match Runtime::new() {
                             Ok(rt) => rt,
@@ -356,27 +729,18 @@ pub fn spawn_storage_worker(
                             }
                         };
                         let res: Result<()> = (|| {
-                            let images = rt.block_on(fetch_product_images(&hermes, &product_id))?;
-                            if images.is_empty() {
-                                return Err(anyhow::anyhow!(
-                                    "No uploaded images found for product."
-                                ));
-                            }
-                            let enrich = HsufEnrichRequest {
-                                images,
-                                sku: Some(sku_alias),
+                            check_photo_quality(&base, &product_id, &quality, &event_tx)?;
+                            let updated = generate_structure(
+                                &rt,
+                                &job,
+                                &hermes,
+                                &base,
+                                &product_id,
+                                sku_alias,
+                                llm_ingest,
                                 context_text,
                                 prompt_rules,
-                                llm_ingest,
-                            };
-                            let response = rt.block_on(hermes.hsuf_enrich(&enrich, false))?;
-                            let structure_json = serde_json::to_value(&response.product)?;
-                            let update = ProductUpdateRequest {
-                                structure_json: Some(structure_json.clone()),
-                                ..Default::default()
-                            };
-                            let row = rt.block_on(hermes.update_product(&product_id, &update))?;
-                            let updated = storage::upsert_product_from_remote(&base, &row)?;
+                            )?;
                             let _ = event_tx
                                 .send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
                             let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
@@ -388,14 +752,7 @@ pub fn spawn_storage_worker(
                         })();
 
                         if let Err(err) = res {
-                            let message = format!("{err:#}");
-                            let _ = event_tx
-                                .send(AppEvent::Storage(StorageEvent::Error(message.clone())));
-                            let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
-                                at: Local::now(),
-                                severity: Severity::Error,
-                                message,
-                            }));
+                            report_job_error(&event_tx, &job, err);
                         }
                     });
                     Ok(())
@@ -411,6 +768,7 @@ pub fn spawn_storage_worker(
                     condition_id,
                     dry_run,
                     publish,
+                    quality,
                 } => {
                     let hermes = hermes
                         .as_ref()
@@ -423,6 +781,7 @@ pub fn spawn_storage_worker(
                     let base = base.clone();
                     let hermes = hermes.clone();
                     let event_tx = event_tx.clone();
+                    let mut job = Some(jobs.register(JobKey::new(JobKind::Listing, &product_id)));
                     thread::spawn(move || {
                         let rt = match Runtime::new() {
                             Ok(rt) => rt,
@@ -434,14 +793,17 @@ pub fn spawn_storage_worker(
                             }
                         };
                         let res: Result<()> = (|| {
-                            let images = rt.block_on(fetch_product_images(&hermes, &product_id))?;
+                            let guard = job.as_ref().expect("listing job guard");
+                            check_photo_quality(&base, &product_id, &quality, &event_tx)?;
+                            let images =
+                                guard.run(&rt, fetch_product_images(&hermes, &product_id))?;
                             if images.is_empty() {
                                 return Err(anyhow::anyhow!(
                                     "No uploaded images found for product."
                                 ));
                             }
 
-                            let row = rt.block_on(hermes.get_product(&product_id))?;
+                            let row = guard.run(&rt, hermes.get_product(&product_id))?;
                             let mut structure_json = row.structure_json.clone();
                             if structure_json.is_none() {
                                 if let Ok(local) = storage::load_product(&base, &product_id) {
@@ -489,10 +851,25 @@ pub fn spawn_storage_worker(
                                 publish: Some(publish),
                                 return_policy_id,
                                 sku: Some(sku_alias),
-                                use_signed_urls: None,
+                                use_signed_urls: use_signed_urls.then_some(true),
                             };
-                            let job = rt.block_on(hermes.enqueue_listing(&req))?;
-                            let job_id = job.job_id;
+                            // A cancelled enqueue keeps its key, so trying
+                            // again cannot queue the job twice.
+                            let mut keys = IdempotencyStore::open_default()?;
+                            let key = keys.key_for("jobs/listings", &req)?;
+                            let queued =
+                                guard.run(&rt, hermes.enqueue_listing_idempotent(&req, &key))?;
+                            keys.release("jobs/listings", &req)?;
+                            JobLedger::open_default()?.append(JobLedgerEntry {
+                                product_id: Some(product_id.clone()),
+                                ..JobLedgerEntry::new(&queued.job_id, &req)
+                            })?;
+                            let job_id = queued.job_id;
+                            let _ =
+                                event_tx.send(AppEvent::Storage(StorageEvent::ListingJobQueued {
+                                    product_id: product_id.clone(),
+                                    job_id: job_id.clone(),
+                                }));
                             let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
                                 at: Local::now(),
                                 severity: Severity::Info,
@@ -502,6 +879,7 @@ pub fn spawn_storage_worker(
                                 base.clone(),
                                 hermes.clone(),
                                 event_tx.clone(),
+                                job.take().expect("listing job guard"),
                                 job_id,
                                 product_id,
                                 marketplace,
@@ -512,19 +890,140 @@ pub fn spawn_storage_worker(
                             Ok(())
                         })();
 
-                        if let Err(err) = res {
-                            let message = format!("{err:#}");
-                            let _ = event_tx
-                                .send(AppEvent::Storage(StorageEvent::Error(message.clone())));
-                            let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
-                                at: Local::now(),
-                                severity: Severity::Error,
-                                message,
-                            }));
+                        if let Err(err) = res
+                            && let Some(job) = &job
+                        {
+                            report_job_error(&event_tx, job, err);
                         }
                     });
                     Ok(())
                 }
+                StorageCommand::ListFailedJobs => {
+                    spawn_failed_jobs_refresh(hermes.clone(), event_tx.clone());
+                    Ok(())
+                }
+                StorageCommand::LoadStats => {
+                    let sessions = storage::session_samples(&base)?;
+                    let sku_products: HashMap<String, String> = storage::list_products(&base)?
+                        .into_iter()
+                        .map(|product| (product.sku_alias, product.product_id))
+                        .collect();
+                    let jobs = JobLedger::open_default()?.recent(usize::MAX)?;
+                    let stats = stats::compute(DayRange::recent(), &sessions, &jobs, &sku_products);
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::StatsLoaded(stats)));
+                    Ok(())
+                }
+                StorageCommand::DismissFailedJob { job_id } => {
+                    let ledger = JobLedger::open_default()?;
+                    ledger.set_state(&job_id, TrackedState::Dismissed, None)?;
+                    let failed = ledger.failed()?;
+                    let _ =
+                        event_tx.send(AppEvent::Storage(StorageEvent::FailedJobsListed(failed)));
+                    Ok(())
+                }
+                StorageCommand::RetryListingJob { job_id, overrides } => {
+                    let hermes = hermes
+                        .as_ref()
+                        .filter(|h| h.has_api_key())
+                        .context("HERMES_API_KEY missing; retrying listing jobs requires Hermes.")?
+                        .clone();
+                    let ledger = JobLedger::open_default()?;
+                    let failed = ledger
+                        .get(&job_id)?
+                        .filter(JobLedgerEntry::retryable)
+                        .with_context(|| format!("Job {job_id} is not a failed job to retry."))?;
+                    let mut req = failed
+                        .request
+                        .clone()
+                        .context("Job has no stored request.")?;
+                    if let Some(overrides) = overrides {
+                        req.overrides = Some(overrides);
+                    }
+                    let product_id = match failed.product_id.clone() {
+                        Some(product_id) => product_id,
+                        None => storage::list_products(&base)?
+                            .into_iter()
+                            .find(|p| failed.sku.as_deref() == Some(p.sku_alias.as_str()))
+                            .map(|p| p.product_id)
+                            .with_context(|| {
+                                format!(
+                                    "No local product for SKU {}; cannot track the retry.",
+                                    failed.sku.as_deref().unwrap_or("-")
+                                )
+                            })?,
+                    };
+                    let settings = talaria_core::config::EbaySettings {
+                        marketplace: req.marketplace.as_ref().map(|m| m.as_str().to_string()),
+                        merchant_location_key: Some(req.merchant_location_key.clone()),
+                        fulfillment_policy_id: Some(req.fulfillment_policy_id.clone()),
+                        payment_policy_id: Some(req.payment_policy_id.clone()),
+                        return_policy_id: Some(req.return_policy_id.clone()),
+                    };
+                    let marketplace = req.marketplace.clone().unwrap_or(MarketplaceId::EbayUs);
+
+                    let mut keys = IdempotencyStore::open_default()?;
+                    let key = keys.key_for("jobs/listings", &req)?;
+                    let job = rt.block_on(hermes.enqueue_listing_idempotent(&req, &key))?;
+                    keys.release("jobs/listings", &req)?;
+                    ledger.append(JobLedgerEntry {
+                        product_id: Some(product_id.clone()),
+                        retry_of: Some(job_id.clone()),
+                        ..JobLedgerEntry::new(&job.job_id, &req)
+                    })?;
+                    ledger.set_state(&job_id, TrackedState::Retried, None)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::ListingJobQueued {
+                        product_id: product_id.clone(),
+                        job_id: job.job_id.clone(),
+                    }));
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::FailedJobsListed(
+                        ledger.failed()?,
+                    )));
+                    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                        at: Local::now(),
+                        severity: Severity::Info,
+                        message: format!("Listing job {job_id} retried as {}", job.job_id),
+                    }));
+                    spawn_listing_job_poll(
+                        base.clone(),
+                        hermes,
+                        event_tx.clone(),
+                        jobs.register(JobKey::new(JobKind::Listing, &product_id)),
+                        job.job_id,
+                        product_id,
+                        marketplace,
+                        settings,
+                        req.dry_run.unwrap_or(false),
+                        req.publish.unwrap_or(false),
+                    );
+                    Ok(())
+                }
+                StorageCommand::SuggestListingPrice {
+                    product_id,
+                    marketplace_key,
+                    request,
+                    currency,
+                    condition,
+                    rules,
+                } => {
+                    let hermes = hermes
+                        .as_ref()
+                        .filter(|h| h.has_api_key())
+                        .context("HERMES_API_KEY missing; price suggestions require Hermes.")?;
+                    let resp = rt.block_on(hermes.comparable_prices(&request))?;
+                    let currency = currency.or(resp.currency);
+                    let suggestion = talaria_core::pricing::suggest_price(
+                        &resp.comparables,
+                        currency.as_deref(),
+                        condition.as_deref(),
+                        &rules,
+                    );
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::PriceSuggested {
+                        product_id,
+                        marketplace_key,
+                        suggestion,
+                    }));
+                    Ok(())
+                }
                 StorageCommand::PublishListingDraft {
                     product_id,
                     sku_alias,
@@ -574,6 +1073,7 @@ pub fn spawn_storage_worker(
                     if published_listing.images.is_empty() {
                         published_listing.images = draft_listing.images.clone();
                     }
+                    apply_local_overrides(&base, &product_id, &mut published_listing);
                     listings_map.insert(marketplace_key, published_listing);
 
                     if hermes.has_api_key() {
@@ -620,12 +1120,27 @@ pub fn spawn_storage_worker(
                     }));
                     Ok(())
                 }
+                StorageCommand::ResumeSession { session_id } => {
+                    let session = storage::load_session(&base, &session_id)?;
+                    let product = storage::load_product(&base, &session.product_id)?;
+                    let frames = session.frames.len();
+                    let sku = product.sku_alias.clone();
+                    let _ =
+                        event_tx.send(AppEvent::Storage(StorageEvent::ProductSelected(product)));
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionStarted(session)));
+                    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                        at: Local::now(),
+                        severity: Severity::Success,
+                        message: format!("Resumed session for {sku} ({frames} frame(s))"),
+                    }));
+                    Ok(())
+                }
                 StorageCommand::CommitSession { session_id } => {
                     let (product, session, committed_count) =
                         storage::commit_session(&base, &session_id)?;
                     let _ = event_tx.send(AppEvent::Storage(StorageEvent::CommitCompleted {
                         product,
-                        session,
+                        session: Box::new(session),
                         committed_count,
                     }));
                     Ok(())
@@ -635,13 +1150,20 @@ pub fn spawn_storage_worker(
                     frame_rel_path,
                     created_at,
                     sharpness_score,
+                    tag,
                 } => {
+                    let subject = FrameSubject::of_file(
+                        &storage::session_dir(&base, &session_id).join(&frame_rel_path),
+                    )
+                    .ok();
                     let session = storage::append_session_frame(
                         &base,
                         &session_id,
                         &frame_rel_path,
                         sharpness_score,
+                        subject,
                         created_at,
+                        tag,
                     )?;
                     let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionUpdated(session)));
                     Ok(())
@@ -655,11 +1177,47 @@ pub fn spawn_storage_worker(
                     let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionUpdated(session)));
                     Ok(())
                 }
+                StorageCommand::SetSessionTemplate {
+                    session_id,
+                    template,
+                } => {
+                    let session = storage::set_session_template(&base, &session_id, template)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionUpdated(session)));
+                    Ok(())
+                }
+                StorageCommand::SetSessionWhiteBalance { session_id, gains } => {
+                    let session = storage::set_session_white_balance(&base, &session_id, gains)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionUpdated(session)));
+                    Ok(())
+                }
+                StorageCommand::SetSessionHero {
+                    session_id,
+                    frame_rel_path,
+                } => {
+                    let session =
+                        storage::set_session_hero(&base, &session_id, frame_rel_path.as_deref())?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionUpdated(session)));
+                    Ok(())
+                }
+                StorageCommand::SetSessionFrameShot {
+                    session_id,
+                    frame_rel_path,
+                    label,
+                } => {
+                    let session = storage::set_session_frame_shot(
+                        &base,
+                        &session_id,
+                        &frame_rel_path,
+                        label,
+                    )?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionUpdated(session)));
+                    Ok(())
+                }
                 StorageCommand::DeleteSessionFrame {
                     session_id,
                     frame_rel_path,
                 } => {
-                    storage::delete_session_frame(&base, &session_id, &frame_rel_path)?;
+                    trash::trash_session_frame(&base, &session_id, &frame_rel_path)?;
                     let session = storage::load_session(&base, &session_id)?;
                     let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionUpdated(session)));
                     Ok(())
@@ -731,6 +1289,122 @@ pub fn spawn_storage_worker(
                     }));
                     Ok(())
                 }
+                StorageCommand::ListProductMedia { product_id } => {
+                    let hermes = media_client(hermes.as_ref())?;
+                    let product = storage::load_product(&base, &product_id)?;
+                    let items = gallery_items(&rt, hermes, &base, &product)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::MediaListed {
+                        product_id,
+                        items,
+                    }));
+                    Ok(())
+                }
+                StorageCommand::UploadMissingMedia { product_id } => {
+                    let hermes = media_client(hermes.as_ref())?;
+                    let product = storage::load_product(&base, &product_id)?;
+                    let items = gallery_items(&rt, hermes, &base, &product)?;
+                    let mut missing = Vec::new();
+                    for item in &items {
+                        let Some(rel) = &item.rel_path else {
+                            continue;
+                        };
+                        match &item.remote {
+                            Some(media) if item.by_hash => {
+                                if item.local_hero {
+                                    storage::set_product_hero_uploaded_url(
+                                        &base,
+                                        &product_id,
+                                        media.url.clone(),
+                                        Some(media.media_id.clone()),
+                                    )?;
+                                }
+                                storage::set_product_image_uploaded_url(
+                                    &base,
+                                    &product_id,
+                                    rel,
+                                    media.url.clone(),
+                                    Some(media.media_id.clone()),
+                                )?;
+                            }
+                            Some(_) => {}
+                            None => missing.push(rel.clone()),
+                        }
+                    }
+                    let updated = storage::clear_product_media_links(&base, &product_id, &missing)?;
+                    let _ =
+                        event_tx.send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
+                    if missing.is_empty() {
+                        let product = storage::load_product(&base, &product_id)?;
+                        let items = gallery_items(&rt, hermes, &base, &product)?;
+                        let _ = event_tx.send(AppEvent::Storage(StorageEvent::MediaListed {
+                            product_id,
+                            items,
+                        }));
+                        let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                            at: Local::now(),
+                            severity: Severity::Info,
+                            message: "Every local image is on Hermes.".to_string(),
+                        }));
+                    } else {
+                        let _ = event_tx.send(AppEvent::Storage(StorageEvent::MediaUploadReady {
+                            product_id,
+                        }));
+                    }
+                    Ok(())
+                }
+                StorageCommand::DeleteRemoteMedia {
+                    product_id,
+                    media_id,
+                } => {
+                    let hermes = media_client(hermes.as_ref())?;
+                    rt.block_on(hermes.delete_media(&media_id))?;
+                    let product = storage::load_product(&base, &product_id)?;
+                    let items = gallery_items(&rt, hermes, &base, &product)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::MediaListed {
+                        product_id,
+                        items,
+                    }));
+                    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                        at: Local::now(),
+                        severity: Severity::Success,
+                        message: format!("Deleted Hermes media {media_id}."),
+                    }));
+                    Ok(())
+                }
+                StorageCommand::UpdateRemoteMedia {
+                    product_id,
+                    media_id,
+                    purpose,
+                    rank,
+                } => {
+                    let hermes = media_client(hermes.as_ref())?;
+                    if purpose == Some(MediaPurpose::Hero) {
+                        let current = rt.block_on(hermes.list_product_media(&product_id))?;
+                        for media in current.items.iter().filter(|media| {
+                            media.media_id != media_id && media.purpose == Some(MediaPurpose::Hero)
+                        }) {
+                            let demote = UpdateMediaRequest {
+                                metadata: None,
+                                purpose: Some(MediaPurpose::ProductImage),
+                                rank: None,
+                            };
+                            rt.block_on(hermes.update_media(&media.media_id, &demote))?;
+                        }
+                    }
+                    let update = UpdateMediaRequest {
+                        metadata: None,
+                        purpose,
+                        rank,
+                    };
+                    rt.block_on(hermes.update_media(&media_id, &update))?;
+                    let product = storage::load_product(&base, &product_id)?;
+                    let items = gallery_items(&rt, hermes, &base, &product)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::MediaListed {
+                        product_id,
+                        items,
+                    }));
+                    Ok(())
+                }
                 StorageCommand::SyncProductMedia { product_id } => {
                     let hermes = hermes
                         .as_ref()
@@ -759,7 +1433,11 @@ pub fn spawn_storage_worker(
                             "HERMES_API_KEY missing; sync requires Hermes."
                         ));
                     }
-                    let updated = sync_product_data(&rt, hermes, &base, &product_id)?;
+                    let Some(updated) =
+                        sync_product_data(&rt, hermes, &base, &product_id, &event_tx)?
+                    else {
+                        return Ok(());
+                    };
                     let _ =
                         event_tx.send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
                     let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
@@ -769,26 +1447,358 @@ pub fn spawn_storage_worker(
                     }));
                     Ok(())
                 }
-                StorageCommand::Shutdown => Ok(()),
+                StorageCommand::ResolveSyncConflict {
+                    product_id,
+                    resolution,
+                } => {
+                    let hermes = hermes
+                        .as_ref()
+                        .context("Hermes client unavailable for sync")?;
+                    if !hermes.has_api_key() {
+                        return Err(anyhow::anyhow!(
+                            "HERMES_API_KEY missing; sync requires Hermes."
+                        ));
+                    }
+                    let updated =
+                        resolve_sync_conflict(&rt, hermes, &base, &product_id, resolution)?;
+                    let message = format!(
+                        "Sync conflict on {}: {}.",
+                        updated.sku_alias,
+                        resolution.label()
+                    );
+                    let _ =
+                        event_tx.send(AppEvent::Storage(StorageEvent::ProductSelected(updated)));
+                    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                        at: Local::now(),
+                        severity: Severity::Success,
+                        message,
+                    }));
+                    Ok(())
+                }
+                StorageCommand::ListTrash => {
+                    let items = trash::list_trash(&base)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::TrashListed(items)));
+                    Ok(())
+                }
+                StorageCommand::RestoreLatestTrash => {
+                    let item = trash::list_trash(&base)?
+                        .into_iter()
+                        .next()
+                        .context("Trash is empty; nothing to undo.")?;
+                    let hermes = hermes.as_ref().filter(|h| h.has_api_key());
+                    restore_trash_item(&rt, hermes, &base, &item, &event_tx)
+                }
+                StorageCommand::RestoreTrash { item } => {
+                    let hermes = hermes.as_ref().filter(|h| h.has_api_key());
+                    restore_trash_item(&rt, hermes, &base, &item, &event_tx)?;
+                    let items = trash::list_trash(&base)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::TrashListed(items)));
+                    Ok(())
+                }
+                StorageCommand::BulkProducts {
+                    action,
+                    product_ids,
+                    llm_ingest,
+                    prompt_rules,
+                } => {
+                    let base = base.clone();
+                    let hermes = hermes.clone();
+                    let event_tx = event_tx.clone();
+                    let jobs = jobs.clone();
+                    thread::spawn(move || {
+                        let rt = match Runtime::new() {
+                            Ok(rt) => rt,
+                            Err(err) => {
+                                let _ = event_tx.send(AppEvent::Storage(StorageEvent::Error(
+                                    format!("Bulk job runtime init failed: {err}"),
+                                )));
+                                return;
+                            }
+                        };
+                        run_bulk(
+                            &rt,
+                            &jobs,
+                            hermes.as_ref(),
+                            &base,
+                            action,
+                            &product_ids,
+                            llm_ingest,
+                            prompt_rules,
+                            &event_tx,
+                        );
+                    });
+                    Ok(())
+                }
+                StorageCommand::SetClient { .. }
+                | StorageCommand::SetOnline(_)
+                | StorageCommand::Shutdown => Ok(()),
             })();
 
             if let Err(err) = res {
-                let message = format!("{err:#}");
-                let _ = event_tx.send(AppEvent::Storage(StorageEvent::Error(message.clone())));
+                report_error(&event_tx, &err);
+            }
+        }
+    })
+}
+
+/// Checks the product's local photos against `rules` and logs each finding
+/// to Activity; under `rules.strict` any finding stops the job before Hermes
+/// is called.
+fn check_photo_quality(
+    base: &Path,
+    product_id: &str,
+    rules: &QualityRules,
+    event_tx: &EventSender,
+) -> Result<()> {
+    let Ok(product) = storage::load_product(base, product_id) else {
+        return Ok(());
+    };
+    let dir = storage::product_dir(base, product_id);
+    let paths: Vec<PathBuf> = product
+        .images
+        .iter()
+        .map(|image| dir.join(&image.rel_path))
+        .filter(|path| path.is_file())
+        .collect();
+    let report = images::quality_report(&paths, rules)?;
+    for finding in &report.findings {
+        let name = finding
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+            at: Local::now(),
+            severity: Severity::Warning,
+            message: format!("Photo {name}: {}", finding.message),
+        }));
+    }
+    if rules.strict && !report.passed() {
+        return Err(anyhow::anyhow!(
+            "{} photo quality finding(s); retake the photos or turn off [quality] strict.",
+            report.findings.len()
+        ));
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_structure(
+    rt: &Runtime,
+    job: &JobGuard,
+    hermes: &HermesClient,
+    base: &Path,
+    product_id: &str,
+    sku_alias: String,
+    llm_ingest: Option<LlmStageOptions>,
+    context_text: Option<String>,
+    prompt_rules: Option<String>,
+) -> Result<storage::ProductManifest> {
+    let images = job.run(rt, fetch_product_images(hermes, product_id))?;
+    if images.is_empty() {
+        return Err(anyhow::anyhow!("No uploaded images found for product."));
+    }
+    let enrich = HsufEnrichRequest {
+        images,
+        sku: Some(sku_alias),
+        context_text,
+        prompt_rules,
+        llm_ingest,
+    };
+    let response = job.run(rt, hermes.hsuf_enrich(&enrich, false))?;
+    let mut structure_json = serde_json::to_value(&response.product)?;
+    if let Ok(local) = storage::load_product(base, product_id)
+        && let Some(measured) = &local.measured
+    {
+        measured.apply_to_structure(&mut structure_json);
+    }
+    let update = ProductUpdateRequest {
+        structure_json: Some(structure_json),
+        ..Default::default()
+    };
+    let row = rt.block_on(hermes.update_product(product_id, &update))?;
+    storage::upsert_product_from_remote(base, &row)
+}
+
+/// Runs `action` for each product in turn, reporting progress after every item
+/// and logging per-item failures to Activity.
+#[allow(clippy::too_many_arguments)]
+fn run_bulk(
+    rt: &Runtime,
+    jobs: &RunningJobs,
+    hermes: Option<&HermesClient>,
+    base: &Path,
+    action: BulkAction,
+    product_ids: &[String],
+    llm_ingest: Option<LlmStageOptions>,
+    prompt_rules: Option<String>,
+    event_tx: &EventSender,
+) {
+    let hermes = hermes.filter(|h| h.has_api_key());
+    let total = product_ids.len();
+    let mut failed = 0;
+    let mut exported = Vec::new();
+    for (idx, product_id) in product_ids.iter().enumerate() {
+        let local = storage::load_product(base, product_id);
+        let label = local
+            .as_ref()
+            .map(|p| p.sku_alias.clone())
+            .unwrap_or_else(|_| product_id.clone());
+        let res: Result<()> = (|| {
+            match action {
+                BulkAction::Enrich => {
+                    let hermes = hermes
+                        .context("HERMES_API_KEY missing; structure generation requires Hermes.")?;
+                    let product = local?;
+                    generate_structure(
+                        rt,
+                        &jobs.register(JobKey::new(JobKind::Structure, product_id)),
+                        hermes,
+                        base,
+                        product_id,
+                        product.sku_alias,
+                        llm_ingest.clone(),
+                        product.context_text,
+                        prompt_rules.clone(),
+                    )?;
+                }
+                BulkAction::Delete => {
+                    if let Some(hermes) = hermes {
+                        rt.block_on(hermes.delete_product(product_id))?;
+                    }
+                    let removed_sessions = trash::trash_product(base, product_id)?;
+                    let _ = event_tx.send(AppEvent::Storage(StorageEvent::ProductDeleted {
+                        product_id: product_id.clone(),
+                        removed_sessions,
+                    }));
+                }
+                BulkAction::Export => exported.push(local?),
+            }
+            Ok(())
+        })();
+        if let Err(err) = res {
+            failed += 1;
+            let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                at: Local::now(),
+                severity: Severity::Error,
+                message: format!("Bulk {} failed for {label}: {err:#}", action.label()),
+            }));
+        }
+        let _ = event_tx.send(AppEvent::Storage(StorageEvent::BulkProgress {
+            action,
+            done: idx + 1,
+            total,
+            failed,
+        }));
+    }
+
+    let mut detail = None;
+    if action == BulkAction::Export && !exported.is_empty() {
+        match storage::export_products(base, &exported) {
+            Ok(path) => detail = Some(path.display().to_string()),
+            Err(err) => {
+                failed = total;
                 let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
                     at: Local::now(),
                     severity: Severity::Error,
-                    message,
+                    message: format!("Bulk export failed: {err:#}"),
                 }));
             }
         }
-    })
+    }
+
+    let _ = send_product_list(rt, hermes, base, event_tx);
+    let _ = event_tx.send(AppEvent::Storage(StorageEvent::BulkFinished {
+        action,
+        succeeded: total - failed,
+        failed,
+        detail,
+    }));
+}
+
+fn send_product_list(
+    rt: &Runtime,
+    hermes: Option<&HermesClient>,
+    base: &Path,
+    event_tx: &EventSender,
+) -> Result<()> {
+    let products = match hermes {
+        Some(hermes) => rt
+            .block_on(hermes.list_products())?
+            .iter()
+            .map(|row| product_summary_from_record(base, row))
+            .collect(),
+        None => storage::list_products(base)?,
+    };
+    let _ = event_tx.send(AppEvent::Storage(StorageEvent::ProductsListed(products)));
+    Ok(())
+}
+
+/// Restores a trashed item locally and, for products deleted from Hermes,
+/// recreates the remote row. Remote media is gone, so upload markers are
+/// cleared and the images go up again on the next sync.
+fn restore_trash_item(
+    rt: &Runtime,
+    hermes: Option<&HermesClient>,
+    base: &Path,
+    item: &trash::TrashItem,
+    event_tx: &EventSender,
+) -> Result<()> {
+    trash::restore(base, item)?;
+    match item {
+        trash::TrashItem::Product { product_id, .. } => {
+            if let Some(hermes) = hermes {
+                let mut product = storage::load_product(base, product_id)?;
+                let create = ProductCreateRequest {
+                    id: Some(product.product_id.clone()),
+                    sku_alias: Some(product.sku_alias.clone()),
+                    display_name: product.display_name.clone(),
+                };
+                rt.block_on(hermes.create_product(&create))?;
+                let update = ProductUpdateRequest {
+                    context_text: product.context_text.clone(),
+                    structure_json: product.structure_json.clone(),
+                    listings_json: Some(serde_json::to_value(&product.listings)?),
+                    ..Default::default()
+                };
+                rt.block_on(hermes.update_product(product_id, &update))?;
+                for image in &mut product.images {
+                    image.uploaded_url = None;
+                    image.uploaded_media_id = None;
+                    image.uploaded_by = None;
+                }
+                product.hero_uploaded_url = None;
+                product.hero_media_id = None;
+                product.hero_uploaded_by = None;
+                storage::atomic_write_json(
+                    &storage::product_manifest_path(base, product_id),
+                    &product,
+                )?;
+            }
+            send_product_list(rt, hermes, base, event_tx)?;
+        }
+        trash::TrashItem::Frame { session_id, .. } => {
+            let session = storage::load_session(base, session_id)?;
+            let _ = event_tx.send(AppEvent::Storage(StorageEvent::SessionUpdated(session)));
+        }
+    }
+    let _ = event_tx.send(AppEvent::Storage(StorageEvent::TrashRestored(
+        item.describe(),
+    )));
+    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+        at: Local::now(),
+        severity: Severity::Success,
+        message: format!("Restored {}", item.describe()),
+    }));
+    Ok(())
 }
 
 fn product_summary_from_record(base: &Path, row: &ProductRecord) -> storage::ProductSummary {
-    let image_count = storage::load_product(base, &row.id)
-        .map(|p| p.images.len())
-        .unwrap_or(0);
+    let local = storage::load_product(base, &row.id).ok();
+    let image_count = local.as_ref().map(|p| p.images.len()).unwrap_or(0);
+    let thumbnail_path = local
+        .as_ref()
+        .and_then(|p| storage::ensure_product_thumbnail(base, p).ok().flatten());
     let listings = serde_json::from_value::<
         std::collections::HashMap<String, storage::MarketplaceListing>,
     >(row.listings_json.clone())
@@ -797,7 +1807,7 @@ fn product_summary_from_record(base: &Path, row: &ProductRecord) -> storage::Pro
         .iter()
         .map(|(marketplace, listing)| storage::MarketplaceStatus {
             marketplace: marketplace.clone(),
-            published: listing.status.as_deref() == Some("published"),
+            state: storage::ListingState::from_status(listing.status.as_deref()),
         })
         .collect::<Vec<_>>();
     marketplace_statuses.sort_by(|a, b| a.marketplace.cmp(&b.marketplace));
@@ -808,7 +1818,14 @@ fn product_summary_from_record(base: &Path, row: &ProductRecord) -> storage::Pro
         updated_at: row.updated_at.with_timezone(&Local),
         image_count,
         has_structure: row.structure_json.is_some(),
+        has_listing: !listings.is_empty(),
+        published: marketplace_statuses.iter().any(|s| s.state.published()),
+        sold: marketplace_statuses
+            .iter()
+            .any(|s| s.state == storage::ListingState::Sold),
+        needs_enrich: row.structure_json.is_none() && image_count > 0,
         marketplace_statuses,
+        thumbnail_path,
     }
 }
 
@@ -848,7 +1865,11 @@ fn sync_product_media(
         }
     }
 
-    let download_client = reqwest::Client::new();
+    let download_client = hermes
+        .network()
+        .client_builder()?
+        .build()
+        .context("build media download client")?;
     let mut hero = None::<(chrono::DateTime<chrono::Utc>, String, String, String)>;
 
     for media in remote_by_url.values() {
@@ -877,6 +1898,10 @@ fn sync_product_media(
                 sharpness_score: None,
                 uploaded_url: Some(url.clone()),
                 uploaded_media_id: Some(media.media_id.clone()),
+                label: None,
+                sha256: media.sha256.clone(),
+                committed_by: None,
+                uploaded_by: None,
             });
             rel_path
         };
@@ -908,13 +1933,14 @@ fn sync_product_media(
         if target.exists() {
             continue;
         }
-        download_media(rt, &download_client, url, &target)?;
+        download_media(rt, &download_client, url, img.sha256.as_deref(), &target)?;
     }
 
     if let Some((_, url, media_id, rel_path)) = hero {
         manifest.hero_uploaded_url = Some(url);
         manifest.hero_media_id = Some(media_id);
         manifest.hero_rel_path = Some(rel_path);
+        manifest.hero_sha256 = None;
     } else if manifest
         .hero_uploaded_url
         .as_ref()
@@ -922,12 +1948,14 @@ fn sync_product_media(
     {
         manifest.hero_uploaded_url = None;
         manifest.hero_media_id = None;
+        manifest.hero_uploaded_by = None;
         if manifest
             .hero_rel_path
             .as_ref()
             .is_some_and(|p| p.starts_with("remote/"))
         {
             manifest.hero_rel_path = None;
+            manifest.hero_sha256 = None;
         }
     }
 
@@ -957,14 +1985,92 @@ fn sync_product_media(
     Ok(manifest)
 }
 
+/// The client for media gallery calls: signed in, or simulated.
+fn media_client(hermes: Option<&HermesClient>) -> Result<&HermesClient> {
+    hermes
+        .filter(|h| h.has_api_key() || h.is_simulated())
+        .context("HERMES_API_KEY missing; the media gallery requires Hermes.")
+}
+
+fn gallery_items(
+    rt: &Runtime,
+    hermes: &HermesClient,
+    base: &Path,
+    product: &storage::ProductManifest,
+) -> Result<Vec<GalleryItem>> {
+    let response = rt.block_on(hermes.list_product_media(&product.product_id))?;
+    let dir = storage::product_dir(base, &product.product_id);
+    Ok(storage::media::reconcile(product, response.items, |rel| {
+        std::fs::read(dir.join(rel))
+            .ok()
+            .map(|bytes| talaria_core::upload_cache::sha256_hex(&bytes))
+    }))
+}
+
+/// Merges what changed on either side since the last sync. `None` when
+/// both sides changed the same fields; the conflict goes to the app and
+/// nothing is written until the user resolves it.
 fn sync_product_data(
     rt: &Runtime,
     hermes: &HermesClient,
     base: &Path,
     product_id: &str,
+    event_tx: &EventSender,
+) -> Result<Option<storage::ProductManifest>> {
+    let row = rt.block_on(hermes.get_product(product_id))?;
+    let row = match storage::load_product(base, product_id) {
+        Ok(local) => match sync::plan_sync(&local, &row) {
+            SyncPlan::Pull => row,
+            SyncPlan::Push(fields) => {
+                rt.block_on(hermes.update_product(product_id, &sync::update_request(fields)))?
+            }
+            SyncPlan::Conflict(conflicts) => {
+                let _ = event_tx.send(AppEvent::Storage(StorageEvent::SyncConflict {
+                    product_id: product_id.to_string(),
+                    sku_alias: local.sku_alias,
+                    conflicts,
+                }));
+                return Ok(None);
+            }
+        },
+        // Not on this machine yet.
+        Err(_) => row,
+    };
+    Ok(Some(storage::upsert_product_from_remote(base, &row)?))
+}
+
+/// Applies `resolution` against the current Hermes record and records it in
+/// the sync audit log.
+fn resolve_sync_conflict(
+    rt: &Runtime,
+    hermes: &HermesClient,
+    base: &Path,
+    product_id: &str,
+    resolution: SyncResolution,
 ) -> Result<storage::ProductManifest> {
+    let local = storage::load_product(base, product_id)?;
     let row = rt.block_on(hermes.get_product(product_id))?;
+    let conflicts = match sync::plan_sync(&local, &row) {
+        SyncPlan::Conflict(conflicts) => conflicts,
+        _ => Vec::new(),
+    };
+    let row = match sync::resolve(&local, &row, resolution) {
+        Some(fields) => {
+            rt.block_on(hermes.update_product(product_id, &sync::update_request(fields)))?
+        }
+        None => row,
+    };
     let updated = storage::upsert_product_from_remote(base, &row)?;
+    sync::append_sync_audit(
+        base,
+        &sync::SyncAuditEntry {
+            at: Local::now(),
+            product_id: product_id.to_string(),
+            sku_alias: updated.sku_alias.clone(),
+            resolution,
+            conflicts,
+        },
+    )?;
     Ok(updated)
 }
 
@@ -1003,7 +2109,14 @@ fn disambiguate_filename(base: &str, used: &HashSet<String>) -> String {
     format!("{base}_dup")
 }
 
-fn download_media(rt: &Runtime, client: &reqwest::Client, url: &str, path: &Path) -> Result<()> {
+/// Downloads `url` to `path`, refusing bytes that do not hash to `sha256`.
+fn download_media(
+    rt: &Runtime,
+    client: &reqwest::Client,
+    url: &str,
+    sha256: Option<&str>,
+    path: &Path,
+) -> Result<()> {
     let bytes = rt.block_on(async {
         let resp = client.get(url).send().await?;
         let status = resp.status();
@@ -1013,6 +2126,12 @@ fn download_media(rt: &Runtime, client: &reqwest::Client, url: &str, path: &Path
         }
         Ok(resp.bytes().await?)
     })?;
+    let served = talaria_core::upload_cache::sha256_hex(&bytes);
+    if let Some(sha256) = sha256.filter(|sha256| !sha256.eq_ignore_ascii_case(&served)) {
+        return Err(anyhow::anyhow!(
+            "corrupted upload at {url}: served sha256 {served}, Hermes recorded {sha256}"
+        ));
+    }
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).context("create media cache dir")?;
     }
@@ -1044,6 +2163,21 @@ async fn fetch_product_images(hermes: &HermesClient, product_id: &str) -> Result
     Ok(hero)
 }
 
+/// Keeps a freshly generated listing in line with the product's grading.
+/// Re-applies the grading and measured package size kept in the local
+/// manifest to a listing that came back from Hermes.
+fn apply_local_overrides(base: &Path, product_id: &str, listing: &mut storage::MarketplaceListing) {
+    let Ok(local) = storage::load_product(base, product_id) else {
+        return;
+    };
+    if let Some(grading) = &local.grading {
+        storage::apply_grading(listing, grading, &local.images);
+    }
+    if let Some(measured) = &local.measured {
+        storage::apply_measurement(listing, measured);
+    }
+}
+
 fn listing_from_response(
     resp: &ListingResponse,
     request: Option<&PublicListingRequest>,
@@ -1226,13 +2360,16 @@ fn listing_from_response(
         aspects,
         aspect_specs,
         quantity: Some(1),
+        quantity_sold: None,
         merchant_location_key: settings.merchant_location_key.clone(),
         fulfillment_policy_id: settings.fulfillment_policy_id.clone(),
         payment_policy_id: settings.payment_policy_id.clone(),
         return_policy_id: settings.return_policy_id.clone(),
         package,
+        package_template: None,
         status: Some(status),
         listing_id: Some(resp.listing_id.clone()),
+        submitted_by: storage::operator(),
     })
 }
 
@@ -1461,9 +2598,5 @@ fn is_redacted_token(value: &str) -> bool {
 }
 
 fn marketplace_key(marketplace: MarketplaceId) -> String {
-    match marketplace {
-        MarketplaceId::EbayUs => "EBAY_US".to_string(),
-        MarketplaceId::EbayUk => "EBAY_UK".to_string(),
-        MarketplaceId::EbayDe => "EBAY_DE".to_string(),
-    }
+    marketplace.as_str().to_string()
 }
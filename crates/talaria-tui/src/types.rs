@@ -1,10 +1,13 @@
 use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
-use talaria_core::config::EbaySettings;
+use talaria_core::client::HermesClient;
+use talaria_core::config::{Config, EbaySettings, SecondCamera};
 use talaria_core::models::{LlmStageOptions, MarketplaceId};
+use talaria_core::quality::QualityRules;
 
 #[derive(Debug, Clone)]
 pub struct CaptureStatus {
@@ -13,6 +16,29 @@ pub struct CaptureStatus {
     pub fps: f32,
     pub dropped_frames: u64,
     pub frame_size: Option<(i32, i32)>,
+    /// Set while a network camera is the source.
+    pub device_url: Option<String>,
+    /// Connection health of a source that can drop, e.g. "reconnecting".
+    pub link: Option<String>,
+    /// Seconds left before a countdown capture fires.
+    pub countdown: Option<u32>,
+    /// Frames taken and planned of a running turntable run.
+    pub turntable: Option<(u32, u32)>,
+    /// The `[second_camera]`, when one is configured.
+    pub second: Option<SecondCameraStatus>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SecondCameraStatus {
+    pub label: String,
+    /// Label of the picked camera's frames in a paired capture.
+    pub primary_label: String,
+    /// Webcam index or stream URL.
+    pub source: String,
+    pub streaming: bool,
+    pub fps: f32,
+    pub frame_size: Option<(i32, i32)>,
+    pub link: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +46,7 @@ pub enum JobStatus {
     InProgress,
     Completed,
     Failed,
+    Canceled,
 }
 
 impl std::fmt::Display for JobStatus {
@@ -28,6 +55,7 @@ impl std::fmt::Display for JobStatus {
             JobStatus::InProgress => "active",
             JobStatus::Completed => "done",
             JobStatus::Failed => "failed",
+            JobStatus::Canceled => "canceled",
         };
         write!(f, "{s}")
     }
@@ -48,6 +76,9 @@ pub struct CreditsSnapshot {
     pub listings_run: i64,
     pub window_from: Option<String>,
     pub window_to: Option<String>,
+    /// The organization's recent days from the local usage history, oldest
+    /// first, for the credits burn-down.
+    pub burn_down: Vec<talaria_core::usage_history::UsageDay>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -91,33 +122,74 @@ impl ActivityLog {
 pub enum CaptureCommand {
     StartStream,
     StopStream,
-    SetDevice { index: i32 },
-    SetOutputDir(PathBuf),
+    SetDevice {
+        index: i32,
+        /// Drive this gPhoto2 camera instead of webcam `index`.
+        tethered: Option<crate::camera::gphoto2::TetheredCamera>,
+    },
+    /// Stream from a network camera (HTTP MJPEG or RTSP) instead.
+    SetDeviceUrl(String),
+    /// Save captures into the session's frames dir, named by its config.
+    SetOutputDir(crate::util::fs::CaptureTarget),
     ClearOutputDir,
     CaptureOne,
+    /// Save the sharpest frame streamed during the last second.
+    CaptureRetro,
+    /// Capture once `secs` have counted down; replaces a running countdown.
+    CaptureAfter {
+        secs: u32,
+    },
+    CancelCountdown,
+    /// Capture a frame every `every_ms` until `total` are taken, while a
+    /// turntable turns the item; the frames are tagged `turntable`. Replaces
+    /// a running run.
+    CaptureInterval {
+        every_ms: u64,
+        total: u32,
+    },
+    CancelInterval,
+    /// Capture from the picked camera and the `[second_camera]` at once.
+    CaptureBoth,
+    /// Replace the `[second_camera]` after a config reload.
+    SetSecondCamera(Option<SecondCamera>),
     Shutdown,
 }
 
+/// A frame saved to disk by the capture thread, whatever backend produced it.
+#[derive(Debug, Clone)]
+pub struct CapturedFrame {
+    pub path: String,
+    pub created_at: DateTime<Local>,
+    /// Laplacian variance; `None` if it could not be computed.
+    pub sharpness_score: Option<f64>,
+    /// How it was taken, when that matters: the camera of a paired capture
+    /// (e.g. `side`) or `turntable`.
+    pub tag: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum CaptureEvent {
     Status(CaptureStatus),
     Error(String),
-    CaptureCompleted {
-        path: String,
-        created_at: DateTime<Local>,
-        sharpness_score: Option<f64>,
-    },
+    CaptureCompleted(CapturedFrame),
 }
 
 #[derive(Debug, Clone)]
 pub enum UploadCommand {
-    UploadProduct { product_id: String },
+    UploadProduct {
+        product_id: String,
+    },
+    /// Swap in a client rebuilt from the saved config.
+    SetClient(Option<Box<HermesClient>>),
+    /// Hold new uploads while Hermes is unreachable; resume them once it is.
+    SetOnline(bool),
     Shutdown,
 }
 
 #[derive(Debug, Clone)]
 pub enum AccountCommand {
     FetchCredits,
+    SetClient(Option<Box<HermesClient>>),
     Shutdown,
 }
 
@@ -127,26 +199,94 @@ pub enum AccountEvent {
     CreditsError(String),
 }
 
+/// Reachability of Hermes as last seen by the health worker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// No check has finished yet, or there is no client.
+    #[default]
+    Unknown,
+    Online,
+    /// Reachable, but slow or reporting a non-ok status.
+    Degraded,
+    Offline,
+}
+
+impl ConnectionState {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConnectionState::Unknown => "unknown",
+            ConnectionState::Online => "online",
+            ConnectionState::Degraded => "degraded",
+            ConnectionState::Offline => "offline",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HealthReport {
+    pub state: ConnectionState,
+    pub latency: Option<Duration>,
+    /// Why the check was degraded or failed.
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum HealthCommand {
+    /// Swap in a client rebuilt from the saved config and check it right away.
+    SetClient(Option<Box<HermesClient>>),
+    Shutdown,
+}
+
 #[derive(Debug, Clone)]
 pub enum PreviewCommand {
     SetEnabled(bool),
     ShowImage(Option<PathBuf>),
+    /// Draw (or stop drawing) the measurement overlay on camera frames.
+    SetOverlay(Option<crate::util::measure::MeasureOverlay>),
+    /// Seconds to draw over camera frames while a countdown capture runs.
+    SetCountdown(Option<u32>),
+    /// Zoom and focus peaking for camera frames.
+    SetAssist(crate::util::assist::PreviewAssist),
+    Shutdown,
+}
+
+/// For the preview cache worker; see [`crate::workers::previews`].
+#[derive(Debug, Clone)]
+pub enum PreviewCacheCommand {
+    /// Cache a downscaled copy of this image and report it as ready.
+    Prepare(PathBuf),
     Shutdown,
 }
 
+/// From the foot pedal reader; see [`crate::pedal`].
+#[derive(Debug, Clone)]
+pub enum PedalEvent {
+    Pressed,
+    Error(String),
+}
+
 #[derive(Debug, Clone)]
 pub enum PreviewEvent {
     Error(String),
     Unavailable(String),
+    /// `cached` is the preview-sized copy of `source` (or `source` itself
+    /// when it could not be cached).
+    Ready {
+        source: PathBuf,
+        cached: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone)]
 pub enum AppCommand {
     Capture(CaptureCommand),
     Preview(PreviewCommand),
+    PreviewCache(PreviewCacheCommand),
     Upload(UploadCommand),
     Account(AccountCommand),
     Storage(StorageCommand),
+    /// Re-read the config file and hand fresh clients to every worker.
+    ReloadConfig,
     Shutdown,
 }
 
@@ -155,10 +295,38 @@ pub enum AppEvent {
     Capture(CaptureEvent),
     Preview(PreviewEvent),
     UploadJob(UploadJob),
-    UploadFinished { product_id: String },
+    UploadFinished {
+        product_id: String,
+    },
     Activity(ActivityEntry),
     Account(AccountEvent),
+    Health(HealthReport),
     Storage(StorageEvent),
+    Pedal(PedalEvent),
+    /// Workers now hold clients built from `config` (`None` if it failed to load).
+    ConfigReloaded {
+        config: Option<Box<Config>>,
+        online_ready: bool,
+        error: Option<String>,
+    },
+}
+
+/// Storage-side actions that can be applied to several marked products at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+    Enrich,
+    Delete,
+    Export,
+}
+
+impl BulkAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            BulkAction::Enrich => "enrich",
+            BulkAction::Delete => "delete",
+            BulkAction::Export => "export",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -183,12 +351,39 @@ pub enum StorageCommand {
         product_id: String,
         listings: HashMap<String, crate::storage::MarketplaceListing>,
     },
+    /// Look for a barcode in `frames`, stopping at the first one found.
+    ScanBarcodes {
+        product_id: String,
+        frames: Vec<PathBuf>,
+        report_misses: bool,
+    },
+    /// Add a scanned code to the product's context text and, with `set_sku`,
+    /// make it the SKU alias.
+    ApplyBarcode {
+        product_id: String,
+        barcode: talaria_core::barcode::Barcode,
+        set_sku: bool,
+    },
+    /// Store the condition grade (or clear it with `None`) and re-apply it to
+    /// the product's listings.
+    SetProductGrading {
+        product_id: String,
+        grading: Option<talaria_core::grading::ProductGrading>,
+    },
+    /// Store measured dimensions (or clear them with `None`) and apply them
+    /// to the structured product and the listings' package size.
+    SetProductMeasurement {
+        product_id: String,
+        measured: Option<talaria_core::measure::MeasuredDimensions>,
+    },
     GenerateProductStructure {
         product_id: String,
         sku_alias: String,
         llm_ingest: Option<LlmStageOptions>,
         context_text: Option<String>,
         prompt_rules: Option<String>,
+        /// Checks run on the product's local photos first.
+        quality: QualityRules,
     },
     GenerateProductListing {
         product_id: String,
@@ -201,6 +396,32 @@ pub enum StorageCommand {
         condition_id: Option<i32>,
         dry_run: bool,
         publish: bool,
+        quality: QualityRules,
+    },
+    /// Fetch comparable prices and turn them into a suggested range with
+    /// the `[pricing]` rules.
+    SuggestListingPrice {
+        product_id: String,
+        marketplace_key: String,
+        request: talaria_core::models::ComparablePricesRequest,
+        currency: Option<String>,
+        condition: Option<String>,
+        rules: talaria_core::pricing::PricingRules,
+    },
+    /// Check listing jobs still pending in the ledger, then report the
+    /// failed ones.
+    ListFailedJobs,
+    /// Recompute the Analytics tab's numbers from sessions and the ledger.
+    LoadStats,
+    /// Re-enqueue a failed job's stored request, with `overrides` replacing
+    /// the stored ones when given.
+    RetryListingJob {
+        job_id: String,
+        overrides: Option<talaria_core::models::PublicPipelineOverrides>,
+    },
+    /// Take a failed job off the retry queue.
+    DismissFailedJob {
+        job_id: String,
     },
     PublishListingDraft {
         product_id: String,
@@ -213,12 +434,42 @@ pub enum StorageCommand {
     SyncProductData {
         product_id: String,
     },
+    /// Settle the fields local and Hermes both changed, as the user chose.
+    ResolveSyncConflict {
+        product_id: String,
+        resolution: crate::storage::sync::SyncResolution,
+    },
     SyncProductMedia {
         product_id: String,
     },
+    /// Fetch the product's Hermes media and match it to its local images
+    /// for the media gallery.
+    ListProductMedia {
+        product_id: String,
+    },
+    /// Record media matched by content hash, drop links to media Hermes no
+    /// longer has, then hand the images it lacks to the upload worker.
+    UploadMissingMedia {
+        product_id: String,
+    },
+    DeleteRemoteMedia {
+        product_id: String,
+        media_id: String,
+    },
+    /// Set a media record's purpose and/or rank; making one the hero turns
+    /// the previous hero into a product image.
+    UpdateRemoteMedia {
+        product_id: String,
+        media_id: String,
+        purpose: Option<talaria_core::models::MediaPurpose>,
+        rank: Option<i32>,
+    },
     AbandonSession {
         session_id: String,
     },
+    ResumeSession {
+        session_id: String,
+    },
     CommitSession {
         session_id: String,
     },
@@ -227,11 +478,31 @@ pub enum StorageCommand {
         frame_rel_path: String,
         created_at: DateTime<Local>,
         sharpness_score: Option<f64>,
+        tag: Option<String>,
     },
     ToggleSessionFrameSelection {
         session_id: String,
         frame_rel_path: String,
     },
+    /// Check the session against a shot list, or stop with `None`.
+    SetSessionTemplate {
+        session_id: String,
+        template: Option<talaria_core::shots::SessionTemplate>,
+    },
+    SetSessionWhiteBalance {
+        session_id: String,
+        gains: Option<[f64; 3]>,
+    },
+    /// Label a frame with a template shot, or clear its label with `None`.
+    SetSessionHero {
+        session_id: String,
+        frame_rel_path: Option<String>,
+    },
+    SetSessionFrameShot {
+        session_id: String,
+        frame_rel_path: String,
+        label: Option<String>,
+    },
     DeleteSessionFrame {
         session_id: String,
         frame_rel_path: String,
@@ -240,18 +511,44 @@ pub enum StorageCommand {
         product_id: String,
         rel_path: String,
     },
+    ListTrash,
+    RestoreLatestTrash,
+    RestoreTrash {
+        item: crate::storage::trash::TrashItem,
+    },
+    BulkProducts {
+        action: BulkAction,
+        product_ids: Vec<String>,
+        llm_ingest: Option<LlmStageOptions>,
+        prompt_rules: Option<String>,
+    },
+    SetClient {
+        hermes: Option<HermesClient>,
+        use_signed_urls: bool,
+    },
+    /// Hold new enrich/listing jobs while Hermes is unreachable; run them
+    /// once it is back.
+    SetOnline(bool),
     Shutdown,
 }
 
 #[derive(Debug, Clone)]
 pub enum StorageEvent {
     ProductsListed(Vec<crate::storage::ProductSummary>),
+    /// Part of a local product listing; `first` replaces the list and `done`
+    /// ends the listing.
+    ProductsBatch {
+        products: Vec<crate::storage::ProductSummary>,
+        first: bool,
+        done: bool,
+    },
     ProductSelected(crate::storage::ProductManifest),
     SessionStarted(crate::storage::SessionManifest),
+    ResumableSessions(Vec<crate::storage::ResumableSession>),
     SessionUpdated(crate::storage::SessionManifest),
     CommitCompleted {
         product: crate::storage::ProductManifest,
-        session: crate::storage::SessionManifest,
+        session: Box<crate::storage::SessionManifest>,
         committed_count: usize,
     },
     ProductDeleted {
@@ -262,5 +559,54 @@ pub enum StorageEvent {
         session_id: String,
         moved_to: String,
     },
+    TrashListed(Vec<crate::storage::trash::TrashItem>),
+    TrashRestored(String),
+    BulkProgress {
+        action: BulkAction,
+        done: usize,
+        total: usize,
+        failed: usize,
+    },
+    BulkFinished {
+        action: BulkAction,
+        succeeded: usize,
+        failed: usize,
+        detail: Option<String>,
+    },
+    BarcodeScanned {
+        product_id: String,
+        found: Option<(talaria_core::barcode::Barcode, PathBuf)>,
+        report_misses: bool,
+    },
+    PriceSuggested {
+        product_id: String,
+        marketplace_key: String,
+        suggestion: Option<talaria_core::pricing::PriceSuggestion>,
+    },
+    FailedJobsListed(Vec<talaria_core::ledger::JobLedgerEntry>),
+    StatsLoaded(talaria_core::stats::Stats),
+    MediaListed {
+        product_id: String,
+        items: Vec<crate::storage::media::GalleryItem>,
+    },
+    /// Stale media links are cleared; the product's images can be uploaded.
+    MediaUploadReady {
+        product_id: String,
+    },
+    /// A structure or listing job stopped on the user's request.
+    JobCanceled(crate::jobs::JobKey),
+    /// Sync stopped because local and Hermes both changed these paths.
+    SyncConflict {
+        product_id: String,
+        sku_alias: String,
+        conflicts: Vec<talaria_core::product_diff::FieldDiff>,
+    },
+    /// Hermes accepted a listing job for the product.
+    ListingJobQueued {
+        product_id: String,
+        job_id: String,
+    },
+    /// Hermes `request_id` of the failed call behind the `Error` that follows.
+    FailedRequest(String),
     Error(String),
 }
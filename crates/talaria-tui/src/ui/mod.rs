@@ -3,23 +3,29 @@ mod theme;
 
 use std::path::Path;
 
+use image::DynamicImage;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
-    Block, BorderType, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Row, Table,
-    TableState, Tabs, Wrap,
+    Block, BorderType, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Row, Scrollbar,
+    ScrollbarOrientation, ScrollbarState, Sparkline, Table, TableState, Tabs, Wrap,
 };
 use ratatui_image::StatefulImage;
 use ratatui_image::protocol::StatefulProtocol;
 use serde_json::Value;
+use talaria_core::config::AccessMode;
+use talaria_core::i18n;
+use talaria_core::t;
 
 use crate::app::{
-    AppState, AppTab, ListingFieldKey, PREVIEW_HEIGHT_MAX_PCT, PREVIEW_HEIGHT_MIN_PCT,
-    PackageDimensionKey, SettingsField,
+    AppState, AppTab, GradingField, ListingFieldKey, PREVIEW_HEIGHT_MAX_PCT,
+    PREVIEW_HEIGHT_MIN_PCT, PackageDimensionKey, ProductThumbnail, SettingsField,
+    is_picker_settings_field, settings_fields,
 };
-use crate::types::Severity;
+use crate::keymap::{Action, KeyScope};
+use crate::types::{ConnectionState, Severity};
 
 use self::layout::{centered_rect, main_chunks};
 use self::theme::Theme;
@@ -36,7 +42,10 @@ pub fn draw(frame: &mut Frame, app: &mut AppState) {
     render_footer(frame, app, &theme, chunks[2]);
 
     if app.help_open {
-        render_help(frame, &theme);
+        render_help(frame, app, &theme);
+    }
+    if app.metrics_open {
+        render_metrics(frame, &theme);
     }
     if app.camera_picker.open {
         render_camera_picker(frame, app, &theme);
@@ -44,30 +53,76 @@ pub fn draw(frame: &mut Frame, app: &mut AppState) {
     if app.picker.open {
         render_product_picker(frame, app, &theme);
     }
+    if app.failed_jobs.open {
+        render_failed_jobs(frame, app, &theme);
+    }
+    if app.trash_view.open {
+        render_trash_view(frame, app, &theme);
+    }
+    if app.media_view.open {
+        render_media_view(frame, app, &theme);
+    }
+    if app.resume_prompt.open {
+        render_resume_prompt(frame, app, &theme);
+    }
+    if app.grading.open {
+        render_grading(frame, app, &theme);
+    }
+    if app.barcode_prompt.is_some() {
+        render_barcode_prompt(frame, app, &theme);
+    }
+    if app.price_prompt.is_some() {
+        render_price_prompt(frame, app, &theme);
+    }
+    if app.sync_conflict.is_some() {
+        render_sync_conflict(frame, app, &theme);
+    }
     if app.settings_picker.open {
         render_settings_picker(frame, app, &theme);
     }
 }
 
 fn render_tabs(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect) {
-    let titles = [
-        " Home ",
-        " Quickstart ",
-        " Products ",
-        " Activity ",
-        " Settings ",
-    ]
-    .iter()
-    .map(|t| Line::from(*t))
-    .collect::<Vec<_>>();
+    let failed = app.failed_jobs.jobs.len();
+    let home = if failed > 0 {
+        Line::from(vec![
+            Span::raw(format!(" {} ", i18n::text("tui.tabs.home"))),
+            Span::styled(
+                format!("[{}]", t!("tui.tabs.home_failed", count = failed)),
+                toast_style(theme, Severity::Error),
+            ),
+            Span::raw(" "),
+        ])
+    } else {
+        Line::from(format!(" {} ", i18n::text("tui.tabs.home")))
+    };
+    let titles = std::iter::once(home)
+        .chain(
+            [
+                "tui.tabs.quickstart",
+                "tui.tabs.products",
+                "tui.tabs.activity",
+                "tui.tabs.analytics",
+                "tui.tabs.settings",
+            ]
+            .iter()
+            .map(|id| Line::from(format!(" {} ", i18n::text(id)))),
+        )
+        .collect::<Vec<_>>();
 
     let selected = app.active_tab as usize;
+    let title = if app.config.mode == AccessMode::CaptureOnly {
+        i18n::text("tui.title_capture_only")
+    } else {
+        i18n::text("tui.title")
+    };
     let tabs = Tabs::new(titles)
         .select(selected)
         .block(
             theme
                 .panel_block()
-                .title(Span::styled("Talaria Mission Control", theme.title())),
+                .title(Span::styled(title, theme.title()))
+                .title_top(connection_indicator(app, theme).right_aligned()),
         )
         .style(theme.panel())
         .highlight_style(theme.title())
@@ -75,12 +130,29 @@ fn render_tabs(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect) {
     frame.render_widget(tabs, area);
 }
 
+/// `● online 120ms` in the tab bar, colored by the last health check.
+fn connection_indicator<'a>(app: &AppState, theme: &Theme) -> Line<'a> {
+    let connection = &app.connection;
+    let style = match connection.state {
+        ConnectionState::Unknown => theme.subtle(),
+        ConnectionState::Online => theme.ok(),
+        ConnectionState::Degraded => theme.warn(),
+        ConnectionState::Offline => theme.err(),
+    };
+    let mut text = format!("● {}", connection.state.label());
+    if let Some(latency) = connection.latency {
+        text.push_str(&format!(" {}ms", latency.as_millis()));
+    }
+    Line::from(vec![Span::styled(text, style), Span::raw(" ")])
+}
+
 fn render_body(frame: &mut Frame, app: &mut AppState, theme: &Theme, area: Rect) {
     match app.active_tab {
         AppTab::Home => render_home(frame, app, theme, area),
         AppTab::Quickstart => render_quickstart(frame, app, theme, area),
         AppTab::Products => render_products(frame, app, theme, area),
         AppTab::Activity => render_activity(frame, app, theme, area),
+        AppTab::Analytics => render_analytics(frame, app, theme, area),
         AppTab::Settings => render_settings(frame, app, theme, area),
     }
 }
@@ -91,47 +163,26 @@ fn render_quickstart(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rec
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
         .split(area);
 
-    let left = [
-        "Quickstart",
-        "",
-        "1) Create a product",
-        "   - Shift+Tab to Products, press n",
-        "   - Capture images, then Shift+S to save",
-        "",
-        "2) Generate structure (HSUF)",
-        "   - Tab to Structure, press r",
-        "   - Enter to edit any field",
-        "",
-        "3) Generate listing",
-        "   - Set Settings (policies + location)",
-        "   - From Context: r (structure), p (draft pipeline), P (publish pipeline)",
-        "   - From Structure: g (full listing)",
-        "   - Or Tab to Listings: g (full), p (draft), P (publish draft)",
-        "",
-        "4) Sync + refresh",
-        "   - Shift+S syncs product data + media",
-    ]
-    .join("\n");
-
-    let right = [
-        "Hotkeys",
-        "",
-        "Shift+Tab: next main tab",
-        "Tab: Context/Structure/Listings",
-        "Shift+S: save + sync",
-        "G: back to grid",
-        "E: edit full JSON",
-        "?: help",
-        "q: quit",
-        "",
-        "Tips",
-        "",
-        "Use the Structure tab to edit",
-        "details before listing.",
-        "Listings can be updated any time",
-        "from the Listings panel.",
-    ]
-    .join("\n");
+    let k = |action: Action| app.keys.label(action);
+    let left = t!(
+        "tui.quickstart.steps",
+        next_tab = k(Action::NextTab),
+        new = k(Action::NewProduct),
+        save_sync = k(Action::SaveSync),
+        structure = k(Action::GenerateStructure),
+        draft = k(Action::Draft),
+        publish = k(Action::Publish),
+        listing = k(Action::GenerateListing)
+    );
+    let right = t!(
+        "tui.quickstart.notes",
+        next_tab = k(Action::NextTab),
+        save_sync = k(Action::SaveSync),
+        back = k(Action::BackToGrid),
+        edit_json = k(Action::EditJson),
+        help = k(Action::Help),
+        quit = k(Action::Quit)
+    );
 
     let spinner = if app.product_syncing
         || app.structure_inference
@@ -142,7 +193,7 @@ fn render_quickstart(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rec
     } else {
         String::new()
     };
-    let status = format!("Quickstart{spinner}");
+    let status = format!("{}{spinner}", i18n::text("tui.tabs.quickstart"));
 
     frame.render_widget(
         Paragraph::new(left)
@@ -154,7 +205,7 @@ fn render_quickstart(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rec
     frame.render_widget(
         Paragraph::new(right)
             .style(theme.panel())
-            .block(panel_title(theme, "Notes"))
+            .block(panel_title(theme, i18n::text("tui.quickstart.notes_title")))
             .wrap(Wrap { trim: true }),
         columns[1],
     );
@@ -240,6 +291,9 @@ fn render_products(frame: &mut Frame, app: &mut AppState, theme: &Theme, area: R
     }
 }
 
+/// Height of a product card when the grid scrolls, borders included.
+const GRID_ROW_HEIGHT: u16 = 6;
+
 fn render_products_grid(frame: &mut Frame, app: &mut AppState, theme: &Theme, area: Rect) {
     let palette = mondrian_palette();
     let mut idx = 0usize;
@@ -251,12 +305,38 @@ fn render_products_grid(frame: &mut Frame, app: &mut AppState, theme: &Theme, ar
         .split(area);
 
     let spinner = if app.products_loading {
-        format!(" {}", app.spinner_frame())
+        format!(" {} loading", app.spinner_frame())
+    } else {
+        String::new()
+    };
+    let product_count = app.filtered_products_iter().count();
+    let search = if app.product_search_editing {
+        format!("{}_", app.picker.search)
+    } else if app.picker.search.is_empty() {
+        "-".to_string()
     } else {
+        app.picker.search.clone()
+    };
+    let marked = if app.product_marks.is_empty() {
         String::new()
+    } else {
+        format!(
+            " | {} marked (U upload, E enrich, X export, D delete)",
+            app.product_marks.len()
+        )
     };
     let header_text = format!(
-        "Products{spinner}: n = new product | Enter = select product | d = delete (y confirm) | arrows = move"
+        "Products{spinner}: n = new product | Enter = select product | d = delete (y confirm) | arrows = move\n\
+         Search (/): {search} | Status (f): {} | Marketplace (m): {} | Updated (t): {} | {} of {} shown{marked}",
+        app.picker.status_filter.label(),
+        app.picker
+            .marketplace_filter
+            .as_deref()
+            .map(marketplace_label)
+            .unwrap_or_else(|| "all".to_string()),
+        app.picker.date_filter.label(),
+        product_count,
+        app.picker.products.len(),
     );
     frame.render_widget(
         Paragraph::new(header_text)
@@ -266,10 +346,12 @@ fn render_products_grid(frame: &mut Frame, app: &mut AppState, theme: &Theme, ar
         chunks[0],
     );
 
-    if app.picker.products.is_empty() {
+    if product_count == 0 {
         let empty_style = next_style(&palette, &mut idx);
         let body = if app.products_loading {
             format!("Loading products {}...", app.spinner_frame())
+        } else if app.product_filters_active() {
+            "No products match the current filters.\n\nPress c to clear filters.".to_string()
         } else {
             "No products yet.\n\nPress n to create your first product.".to_string()
         };
@@ -289,13 +371,43 @@ fn render_products_grid(frame: &mut Frame, app: &mut AppState, theme: &Theme, ar
     cols = cols.min(4).max(1);
     app.product_grid_cols = cols;
 
-    if app.product_grid_selected >= app.picker.products.len() {
+    if app.product_grid_selected >= product_count {
         app.product_grid_selected = 0;
     }
 
-    let rows = (app.picker.products.len() + cols - 1) / cols;
+    // Only the rows on screen are built; the window scrolls to keep the
+    // selection in view.
+    let total_rows = product_count.div_ceil(cols);
+    let fit_rows = ((grid_area.height as usize + 1) / (GRID_ROW_HEIGHT as usize + 1)).max(1);
+    let selected_row = app.product_grid_selected / cols;
+    let mut scroll = app
+        .product_grid_scroll
+        .min(total_rows.saturating_sub(fit_rows));
+    if selected_row < scroll {
+        scroll = selected_row;
+    } else if selected_row >= scroll + fit_rows {
+        scroll = selected_row + 1 - fit_rows;
+    }
+    app.product_grid_scroll = scroll;
+    let first_index = scroll * cols;
+    let products: Vec<_> = app
+        .filtered_products_iter()
+        .skip(first_index)
+        .take(fit_rows * cols)
+        .cloned()
+        .collect();
+    app.update_product_thumbnails(&products);
+
+    let rows = products.len().div_ceil(cols);
+    // Few enough rows to fit are stretched over the grid, as before.
     let row_constraints = (0..rows)
-        .map(|_| Constraint::Ratio(1, rows as u32))
+        .map(|_| {
+            if total_rows > fit_rows {
+                Constraint::Length(GRID_ROW_HEIGHT)
+            } else {
+                Constraint::Ratio(1, rows as u32)
+            }
+        })
         .collect::<Vec<_>>();
     let row_areas = Layout::default()
         .direction(Direction::Vertical)
@@ -314,14 +426,17 @@ fn render_products_grid(frame: &mut Frame, app: &mut AppState, theme: &Theme, ar
             .split(*row_area);
 
         for (col_idx, cell) in col_areas.iter().enumerate() {
-            let product_index = row_idx * cols + col_idx;
-            if product_index >= app.picker.products.len() {
+            let Some(product) = products.get(row_idx * cols + col_idx) else {
                 continue;
-            }
-            let product = &app.picker.products[product_index];
+            };
+            let product_index = first_index + row_idx * cols + col_idx;
             let style = palette[(product_index + idx) % palette.len()];
             let selected = product_index == app.product_grid_selected;
-            let title = format!("{}", product.sku_alias);
+            let title = if app.product_marks.contains(&product.product_id) {
+                format!("[x] {}", product.sku_alias)
+            } else {
+                product.sku_alias.clone()
+            };
             let name = product
                 .display_name
                 .clone()
@@ -343,15 +458,94 @@ fn render_products_grid(frame: &mut Frame, app: &mut AppState, theme: &Theme, ar
                         .add_modifier(Modifier::BOLD),
                 );
             }
+            let inner = block.inner(*cell);
+            frame.render_widget(block.style(mondrian_style(style)), *cell);
+
+            let mut text_area = inner;
+            let thumb = app.product_thumbnails.get_mut(&product.product_id);
+            if let Some(thumb) = thumb.filter(|_| inner.width >= 24 && inner.height >= 3) {
+                let thumb_width = (inner.height * 2).min(inner.width / 2);
+                let parts = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .spacing(1)
+                    .constraints([Constraint::Length(thumb_width), Constraint::Min(1)])
+                    .split(inner);
+                render_product_thumbnail(frame, thumb, parts[0]);
+                text_area = parts[1];
+            }
             frame.render_widget(
                 Paragraph::new(text)
                     .style(mondrian_style(style))
-                    .block(block)
                     .wrap(Wrap { trim: true }),
-                *cell,
+                text_area,
             );
         }
     }
+
+    if total_rows > fit_rows {
+        let mut state = ScrollbarState::new(total_rows.saturating_sub(fit_rows) + 1)
+            .position(scroll)
+            .viewport_content_length(fit_rows);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            grid_area,
+            &mut state,
+        );
+    }
+}
+
+fn render_product_thumbnail(frame: &mut Frame, thumb: &mut ProductThumbnail, area: Rect) {
+    if let Some(protocol) = thumb.protocol.as_mut() {
+        frame.render_stateful_widget(StatefulImage::default(), area, protocol);
+        return;
+    }
+    let size = (area.width, area.height);
+    let cached = thumb
+        .halfblocks
+        .as_ref()
+        .is_some_and(|(cached_size, _)| *cached_size == size);
+    if !cached {
+        thumb.halfblocks = Some((size, halfblock_lines(&thumb.image, size.0, size.1)));
+    }
+    if let Some((_, lines)) = &thumb.halfblocks {
+        frame.render_widget(Paragraph::new(lines.clone()), area);
+    }
+}
+
+/// Downscales `image` to fit `width` x `height` cells, two pixels per cell
+/// using the upper half block with foreground/background colours.
+fn halfblock_lines(image: &DynamicImage, width: u16, height: u16) -> Vec<Line<'static>> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let rgb = image
+        .resize(
+            u32::from(width),
+            u32::from(height) * 2,
+            image::imageops::FilterType::Triangle,
+        )
+        .to_rgb8();
+    let pixel = |x: u32, y: u32| {
+        let [r, g, b] = rgb.get_pixel(x, y).0;
+        Color::Rgb(r, g, b)
+    };
+    (0..rgb.height())
+        .step_by(2)
+        .map(|y| {
+            let spans = (0..rgb.width())
+                .map(|x| {
+                    let top = pixel(x, y);
+                    let bottom = if y + 1 < rgb.height() {
+                        pixel(x, y + 1)
+                    } else {
+                        Color::Reset
+                    };
+                    Span::styled("\u{2580}", Style::default().fg(top).bg(bottom))
+                })
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect()
 }
 
 fn format_product_status(product: &crate::storage::ProductSummary) -> String {
@@ -364,8 +558,7 @@ fn format_product_status(product: &crate::storage::ProductSummary) -> String {
         .iter()
         .map(|status| {
             let label = marketplace_label(&status.marketplace);
-            let flag = if status.published { "+" } else { "-" };
-            format!("{label}{flag}")
+            format!("{label}{}", status.state.badge())
         })
         .collect::<Vec<_>>()
         .join(" ");
@@ -374,12 +567,8 @@ fn format_product_status(product: &crate::storage::ProductSummary) -> String {
 
 fn marketplace_label(marketplace: &str) -> String {
     let upper = marketplace.to_ascii_uppercase();
-    if upper.contains("EBAY_US") {
-        "EUS".to_string()
-    } else if upper.contains("EBAY_UK") {
-        "EUK".to_string()
-    } else if upper.contains("EBAY_DE") {
-        "EDE".to_string()
+    if let Some(country) = upper.strip_prefix("EBAY_") {
+        format!("E{country}")
     } else {
         upper
             .chars()
@@ -518,25 +707,31 @@ fn render_context_images_panel(
     frame.render_widget(block, area);
 
     let has_terminal_preview = app.terminal_preview.is_some();
+    let checklist = shot_checklist(app);
+    let info_height = if checklist.is_some() { 3 } else { 2 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(if has_terminal_preview {
             vec![
-                Constraint::Length(2),
+                Constraint::Length(info_height),
                 Constraint::Percentage(app.preview_height_pct()),
                 Constraint::Min(4),
             ]
         } else {
-            vec![Constraint::Length(2), Constraint::Min(4)]
+            vec![Constraint::Length(info_height), Constraint::Min(4)]
         })
         .split(inner);
 
     let entries = app.context_image_entries();
     let stored_count = entries.len();
-    let info = format!(
-        "Images: {}  |  Shift+S save+sync  |  t camera | v device picker | c capture",
+    let mut info = format!(
+        "Images: {}  |  Shift+S save+sync  |  t camera | v device picker | c capture | R retro | T shot list | h hero",
         stored_count
     );
+    if let Some(checklist) = checklist {
+        info.push_str("  |  l label shot\n");
+        info.push_str(&checklist);
+    }
     frame.render_widget(
         Paragraph::new(info)
             .style(mondrian_style(style))
@@ -568,16 +763,31 @@ fn render_context_images_panel(
         .iter()
         .enumerate()
         .map(|(idx, entry)| {
-            let (tag, rel_path, source, sharp, created) = match entry {
+            let (tag, rel_path, source, shot, sharp, created) = match entry {
                 crate::app::ContextImageEntry::Session {
                     rel_path,
                     sharpness_score,
                     created_at,
                     selected,
+                    shot,
+                    tag,
+                    hero,
+                    suggested,
                 } => (
-                    if *selected { "*" } else { "" }.to_string(),
+                    format!(
+                        "{}{}",
+                        if *selected { "*" } else { "" },
+                        if *hero {
+                            "H"
+                        } else if *suggested {
+                            "H?"
+                        } else {
+                            ""
+                        }
+                    ),
                     rel_path.clone(),
-                    "session".to_string(),
+                    tag.clone().unwrap_or_else(|| "session".to_string()),
+                    shot.clone().unwrap_or_default(),
                     sharpness_score
                         .map(|s| format!("{s:.1}"))
                         .unwrap_or_else(|| "n/a".to_string()),
@@ -588,10 +798,12 @@ fn render_context_images_panel(
                     created_at,
                     source,
                     hero,
+                    label,
                 } => (
                     if *hero { "H" } else { "" }.to_string(),
                     rel_path.clone(),
                     source.clone(),
+                    label.clone().unwrap_or_default(),
                     "n/a".to_string(),
                     created_at.format("%H:%M:%S").to_string(),
                 ),
@@ -605,6 +817,7 @@ fn render_context_images_panel(
                 format!("{idx:02}"),
                 name.to_string(),
                 source,
+                shot,
                 sharp,
                 created,
             ])
@@ -622,16 +835,18 @@ fn render_context_images_panel(
     let table = Table::new(
         rows,
         [
-            Constraint::Length(3),
             Constraint::Length(4),
-            Constraint::Percentage(48),
+            Constraint::Length(4),
+            Constraint::Percentage(36),
             Constraint::Length(10),
+            Constraint::Length(14),
             Constraint::Length(8),
             Constraint::Length(10),
         ],
     )
     .header(
-        Row::new(vec!["Tag", "#", "Filename", "Src", "Sharp", "Time"]).style(mondrian_title(style)),
+        Row::new(vec!["Tag", "#", "Filename", "Src", "Shot", "Sharp", "Time"])
+            .style(mondrian_title(style)),
     )
     .row_highlight_style(
         Style::default()
@@ -645,6 +860,33 @@ fn render_context_images_panel(
     frame.render_stateful_widget(table, list_area, &mut state);
 }
 
+/// `Shots (apparel): [x] front [ ] back [ ] defect close-up (opt)` for an
+/// active session with a shot list.
+fn shot_checklist(app: &AppState) -> Option<String> {
+    let session = app.active_session.as_ref()?;
+    let template = session.template.as_ref()?;
+    let assigned: Vec<&str> = session
+        .picks
+        .shot_labels
+        .values()
+        .map(String::as_str)
+        .collect();
+    let items = template
+        .labels()
+        .map(|label| {
+            let mark = if assigned.contains(&label) { "x" } else { " " };
+            let optional = if template.is_required(label) {
+                ""
+            } else {
+                " (opt)"
+            };
+            format!("[{mark}] {label}{optional}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(format!("Shots ({}): {items}", template.name))
+}
+
 fn render_terminal_preview(
     frame: &mut Frame,
     app: &mut AppState,
@@ -654,6 +896,27 @@ fn render_terminal_preview(
 ) {
     let show_camera = app.capture_status.streaming;
     let has_image = app.preview_image_path.is_some();
+    let live_label = match (measure_readout(app), app.capture_status.countdown) {
+        (_, Some(secs)) => format!("Live · capture in {secs}s"),
+        (Some(readout), None) => format!("Live · {readout}"),
+        (None, None) => match &app.capture_status.second {
+            Some(second) if second.streaming => format!(
+                "Live ({}) · {} {:.0} fps",
+                second.primary_label, second.label, second.fps
+            ),
+            _ => "Live".to_string(),
+        },
+    };
+    let live_label = match app.capture_status.turntable {
+        Some((taken, total)) if app.capture_status.countdown.is_none() => {
+            format!("Live · turntable {taken}/{total}")
+        }
+        _ => live_label,
+    };
+    let live_label = match app.preview_assist.label() {
+        Some(assist) => format!("{live_label} · {assist}"),
+        None => live_label,
+    };
     let block = mondrian_block(theme, "Preview", style);
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -672,6 +935,16 @@ fn render_terminal_preview(
         return;
     }
 
+    if show_camera {
+        // One row goes to the label; see `AppState::update_terminal_preview`.
+        let width = if has_image {
+            inner.width / 2
+        } else {
+            inner.width
+        };
+        preview.camera_cells = Some((width, inner.height.saturating_sub(1)));
+    }
+
     let mut render_panel =
         |label: &str, panel_area: Rect, state: &mut Option<StatefulProtocol>, placeholder: &str| {
             let rows = Layout::default()
@@ -704,7 +977,7 @@ fn render_terminal_preview(
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
             .split(inner);
         render_panel(
-            "Live",
+            &live_label,
             panels[0],
             &mut preview.camera_state,
             "Waiting for camera...",
@@ -713,14 +986,14 @@ fn render_terminal_preview(
             "Selected",
             panels[1],
             &mut preview.image_state,
-            "Select an image to preview.",
+            "Loading preview...",
         );
         return;
     }
 
     if show_camera {
         render_panel(
-            "Live",
+            &live_label,
             inner,
             &mut preview.camera_state,
             "Waiting for camera...",
@@ -733,7 +1006,7 @@ fn render_terminal_preview(
             "Selected",
             inner,
             &mut preview.image_state,
-            "Select an image to preview.",
+            "Loading preview...",
         );
         return;
     }
@@ -755,12 +1028,15 @@ fn render_context_text_panel(
 ) {
     let focused = app.products_subtab == crate::app::ProductsSubTab::Context
         && app.context_focus == crate::app::ContextFocus::Text;
-    let title = if app.text_editing {
-        "Text (editing)"
+    let mut title = if app.text_editing {
+        "Text (editing)".to_string()
     } else {
-        "Text"
+        "Text".to_string()
     };
-    let block = focus_block(mondrian_block(theme, title, style), focused, theme);
+    if let Some(grading) = app.active_product.as_ref().and_then(|p| p.grading.as_ref()) {
+        title.push_str(&format!(" · {}", grading.label));
+    }
+    let block = focus_block(mondrian_block(theme, &title, style), focused, theme);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -799,7 +1075,11 @@ fn render_structure_panel(
 ) {
     let focused = app.products_subtab == crate::app::ProductsSubTab::Structure;
     let spinner = if app.structure_inference {
-        format!(" {}", app.spinner_frame())
+        format!(
+            " {}{}",
+            app.spinner_frame(),
+            app.job_cancel_hint(&[crate::jobs::JobKind::Structure])
+        )
     } else {
         String::new()
     };
@@ -967,7 +1247,11 @@ fn render_listings_panel(
         .cloned()
         .unwrap_or_else(|| "EBAY_US".to_string());
     let spinner = if app.listing_inference {
-        format!(" {}", app.spinner_frame())
+        format!(
+            " {}{}",
+            app.spinner_frame(),
+            app.job_cancel_hint(&[crate::jobs::JobKind::Listing, crate::jobs::JobKind::Upload])
+        )
     } else {
         String::new()
     };
@@ -1023,6 +1307,7 @@ fn render_listings_panel(
         app.listings_field_list_offset = entries.len().saturating_sub(1);
     }
 
+    let missing_required = app.missing_required_aspects();
     let items = entries
         .iter()
         .map(|entry| {
@@ -1037,7 +1322,16 @@ fn render_listings_panel(
                 ListItem::new(format!("{label}:"))
             } else if entry.key == ListingFieldKey::AspectValue {
                 let value = format_aspect_value_inline(&entry.value);
-                ListItem::new(format!("{label}: {value}"))
+                let missing = entry
+                    .aspect_name
+                    .as_ref()
+                    .is_some_and(|name| missing_required.contains(name));
+                if missing {
+                    ListItem::new(format!("{label} *: {value}"))
+                        .style(Style::default().fg(theme.warn).add_modifier(Modifier::BOLD))
+                } else {
+                    ListItem::new(format!("{label}: {value}"))
+                }
             } else if entry.key == ListingFieldKey::ImageValue {
                 let value = format_image_value_inline(&entry.value);
                 ListItem::new(format!("{label}: {value}"))
@@ -1107,16 +1401,23 @@ fn render_listings_detail_panel(
         lines.push("Editing full listing JSON (Esc to save).".to_string());
     } else if app.listings_field_editing {
         lines.push(format!("Editing {label} (Esc to save)."));
-        if selected_key == Some(ListingFieldKey::AspectValue) {
+        if app.listings_field_edit_key == Some(ListingFieldKey::AspectValue)
+            && app.listings_field_edit_name.is_none()
+        {
+            lines.push("Format: Name = Value1, Value2.".to_string());
+        } else if selected_key == Some(ListingFieldKey::AspectValue) {
             lines.push("Format: Value1, Value2 (or JSON array).".to_string());
+            lines.extend(aspect_spec_lines(app, label));
         } else if selected_key == Some(ListingFieldKey::Images) {
             lines.push("Format: one URL per line (or JSON array).".to_string());
         } else if selected_key == Some(ListingFieldKey::ImageValue) {
             lines.push("Format: full image URL.".to_string());
+        } else if selected_key == Some(ListingFieldKey::PackageTemplate) {
+            lines.push(package_template_hint(app));
         } else if selected_key == Some(ListingFieldKey::PackageWeight) {
-            lines.push("Format: 10 OUNCE (or 2 POUND).".to_string());
+            lines.push("Format: 12oz, 1.5 lb, 500 g (bare numbers are OUNCE).".to_string());
         } else if selected_key == Some(ListingFieldKey::PackageDimensions) {
-            lines.push("Format: L x W x H INCH (ex: 6.0 x 4.5 x 2.0 INCH).".to_string());
+            lines.push("Format: L x W x H in or cm (ex: 6.0 x 4.5 x 2.0 in).".to_string());
         } else if selected_key == Some(ListingFieldKey::PackageDimensionValue) {
             let is_unit = selected_entry.and_then(|entry| entry.dimension_key)
                 == Some(PackageDimensionKey::Unit);
@@ -1137,9 +1438,14 @@ fn render_listings_detail_panel(
         lines.push(String::new());
         if entry.key == ListingFieldKey::Aspects {
             lines.push("Select an aspect below to view or edit values.".to_string());
+            let missing = app.missing_required_aspects();
+            if !missing.is_empty() {
+                lines.push(format!("Missing required: {}", missing.join(", ")));
+            }
             lines.push(String::new());
             lines.push(
-                "Enter edit | g full | p draft | P publish | E edit JSON | u upload".to_string(),
+                "Enter edit | a add aspect | g full | p draft | P publish | E edit JSON"
+                    .to_string(),
             );
             lines.push("Format: Value1, Value2 (or JSON array).".to_string());
         } else if entry.key == ListingFieldKey::Images {
@@ -1160,17 +1466,26 @@ fn render_listings_detail_panel(
             );
         } else if entry.key == ListingFieldKey::AspectValue {
             lines.extend(format_aspect_values_lines(&entry.value));
+            lines.extend(aspect_spec_lines(app, entry.label.as_str()));
+            lines.push(String::new());
+            lines.push(
+                "Enter edit | a add aspect | g full | p draft | P publish | E edit JSON"
+                    .to_string(),
+            );
+        } else if entry.key == ListingFieldKey::PackageTemplate {
+            lines.push(format_structure_value_full(&entry.value));
             lines.push(String::new());
             lines.push(
                 "Enter edit | g full | p draft | P publish | E edit JSON | u upload".to_string(),
             );
+            lines.push(package_template_hint(app));
         } else if entry.key == ListingFieldKey::PackageWeight {
             lines.push(format_structure_value_full(&entry.value));
             lines.push(String::new());
             lines.push(
                 "Enter edit | g full | p draft | P publish | E edit JSON | u upload".to_string(),
             );
-            lines.push("Format: 10 OUNCE (or 2 POUND).".to_string());
+            lines.push("Format: 12oz, 1.5 lb, 500 g (bare numbers are OUNCE).".to_string());
         } else if entry.key == ListingFieldKey::PackageDimensions {
             lines.push("Select a dimension below to edit.".to_string());
             lines.push(String::new());
@@ -1188,6 +1503,16 @@ fn render_listings_detail_panel(
             } else {
                 lines.push("Format: number (rounded up to 1 decimal).".to_string());
             }
+        } else if matches!(
+            entry.key,
+            ListingFieldKey::Condition | ListingFieldKey::ConditionId
+        ) {
+            lines.push(format_structure_value_full(&entry.value));
+            lines.extend(allowed_condition_lines(app));
+            lines.push(String::new());
+            lines.push(
+                "Enter edit (Tab cycles allowed) | p draft | P publish | E edit JSON".to_string(),
+            );
         } else {
             lines.push(format_structure_value_full(&entry.value));
             lines.push(String::new());
@@ -1233,6 +1558,153 @@ fn render_activity(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect)
     );
 }
 
+/// Throughput over the last [`talaria_core::stats::DEFAULT_DAYS`] days:
+/// sparklines of captures and listings, the days as a table and failure
+/// rates by stage, next to the credits burn-down.
+fn render_analytics(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect) {
+    let palette = mondrian_palette();
+    let Some(stats) = &app.stats else {
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Loading stats… ({} to refresh)",
+                app.keys.label(Action::RefreshStats)
+            ))
+            .style(mondrian_style(palette[0]))
+            .block(mondrian_block(theme, "Analytics", palette[0])),
+            area,
+        );
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Min(6)])
+        .split(area);
+    let sparks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+            Constraint::Percentage(40),
+        ])
+        .split(chunks[0]);
+    let captured: Vec<u64> = stats.days.iter().map(|day| day.captured as u64).collect();
+    let listed: Vec<u64> = stats.days.iter().map(|day| day.listed as u64).collect();
+    let captured_title = format!("Captured · {}", stats.captured);
+    let listed_title = format!("Listed · {} ({} published)", stats.listed, stats.published);
+    let burn_down = app
+        .credits
+        .as_ref()
+        .map(|credits| credits.burn_down.as_slice())
+        .unwrap_or_default();
+    let balances: Vec<u64> = burn_down
+        .iter()
+        .map(|day| day.credit_balance_cents.unwrap_or_default().max(0) as u64)
+        .collect();
+    let burned: i64 = burn_down.iter().map(|day| day.credits_consumed).sum();
+    let burn_title = format!(
+        "Credits balance · {burned} used in {}d",
+        talaria_core::usage_history::BURN_DOWN_DAYS
+    );
+    for (area, data, title, style) in [
+        (sparks[0], &captured, &captured_title, palette[1]),
+        (sparks[1], &listed, &listed_title, palette[2]),
+        (sparks[2], &balances, &burn_title, palette[0]),
+    ] {
+        frame.render_widget(
+            Sparkline::default()
+                .data(data)
+                .style(mondrian_style(style))
+                .block(mondrian_block(theme, title, style)),
+            area,
+        );
+    }
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+    let style = palette[0];
+    let cycle = |secs: Option<u64>| secs.map(format_cycle).unwrap_or_else(|| "-".to_string());
+    let rows = stats
+        .days
+        .iter()
+        .rev()
+        .map(|day| {
+            Row::new(vec![
+                day.date.format("%a %d %b").to_string(),
+                day.captured.to_string(),
+                day.listed.to_string(),
+                day.published.to_string(),
+                day.failed.to_string(),
+                cycle(day.avg_cycle_secs),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let days_title = format!("Per day · avg cycle {}", cycle(stats.avg_cycle_secs));
+    let days = Table::new(
+        rows,
+        [
+            Constraint::Length(11),
+            Constraint::Length(9),
+            Constraint::Length(7),
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Min(8),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Day",
+            "Captured",
+            "Listed",
+            "Published",
+            "Failed",
+            "Cycle",
+        ])
+        .style(mondrian_title(style)),
+    )
+    .block(mondrian_block(theme, &days_title, style))
+    .style(mondrian_style(style));
+    frame.render_widget(days, columns[0]);
+
+    let failure_rows = stats
+        .failures_by_stage
+        .iter()
+        .map(|stage| {
+            Row::new(vec![
+                stage.stage.clone(),
+                stage.failed.to_string(),
+                format!("{:.0}%", stage.rate * 100.0),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let failures = Table::new(
+        failure_rows,
+        [
+            Constraint::Min(12),
+            Constraint::Length(7),
+            Constraint::Length(6),
+        ],
+    )
+    .header(Row::new(vec!["Stage", "Failed", "Rate"]).style(mondrian_title(style)))
+    .block(mondrian_block(theme, "Failures by stage", style))
+    .style(mondrian_style(style));
+    frame.render_widget(failures, columns[1]);
+}
+
+/// `2d 3h`, `3h 20m` or `20m`.
+fn format_cycle(secs: u64) -> String {
+    let minutes = secs / 60;
+    let (days, hours) = (minutes / (60 * 24), minutes / 60 % 24);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {}m", minutes % 60)
+    } else {
+        format!("{minutes}m")
+    }
+}
+
 fn render_settings(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect) {
     let style = mondrian_palette()[0];
     let chunks = Layout::default()
@@ -1251,7 +1723,7 @@ fn render_settings(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect)
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|| "not set".to_string());
     let text = format!(
-        "captures dir: {}\nlog stderr: {}\nactivity log: {}\n\nConfig:\n  base_url: {}\n  hermes api key: {}\n  hermes online: {}\n  preview height: {}%\n\nTALARIA_CAPTURES_DIR overrides base capture path.",
+        "captures dir: {}\nlog stderr: {}\nactivity log: {}\n\nConfig:\n  base_url: {}\n  hermes api key: {}\n  org: {}\n  hermes online: {}\n  preview height: {}%\n\nEdits are validated and saved to the config file; clients reload on save.",
         app.captures_dir.display(),
         stderr,
         activity_log,
@@ -1264,6 +1736,7 @@ fn render_settings(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect)
         } else {
             "missing"
         },
+        app.config.org_id.as_deref().unwrap_or("(key default)"),
         if app.config.online_ready {
             "ready"
         } else {
@@ -1279,91 +1752,17 @@ fn render_settings(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect)
         chunks[0],
     );
 
-    let fields = [
-        ("Hermes API Key", SettingsField::HermesApiKey),
-        ("Preview Height (%)", SettingsField::PreviewHeightPct),
-        ("Marketplace", SettingsField::Marketplace),
-        ("Merchant Location Key", SettingsField::MerchantLocation),
-        ("Fulfillment Policy ID", SettingsField::FulfillmentPolicy),
-        ("Payment Policy ID", SettingsField::PaymentPolicy),
-        ("Return Policy ID", SettingsField::ReturnPolicy),
-        ("HSUF Prompt Rules", SettingsField::HsufPromptRules),
-        ("LLM Ingest Model", SettingsField::LlmIngestModel),
-        ("LLM Ingest Reasoning", SettingsField::LlmIngestReasoning),
-        ("LLM Ingest Web Search", SettingsField::LlmIngestWebSearch),
-        ("LLM Aspects Model", SettingsField::LlmAspectsModel),
-        ("LLM Aspects Reasoning", SettingsField::LlmAspectsReasoning),
-        ("LLM Aspects Web Search", SettingsField::LlmAspectsWebSearch),
-    ];
+    let fields = settings_fields();
     let rows = fields
         .iter()
         .enumerate()
-        .map(|(idx, (label, field))| {
-            let mut value = match field {
-                SettingsField::HermesApiKey => {
-                    if app.config.hermes_api_key_present {
-                        "(present)".to_string()
-                    } else {
-                        "(unset)".to_string()
-                    }
-                }
-                SettingsField::PreviewHeightPct => app.preview_height_pct.to_string(),
-                SettingsField::Marketplace => app
-                    .ebay_settings
-                    .marketplace
-                    .clone()
-                    .unwrap_or_else(|| "(unset)".to_string()),
-                SettingsField::MerchantLocation => app
-                    .ebay_settings
-                    .merchant_location_key
-                    .clone()
-                    .unwrap_or_else(|| "(unset)".to_string()),
-                SettingsField::FulfillmentPolicy => app
-                    .ebay_settings
-                    .fulfillment_policy_id
-                    .clone()
-                    .unwrap_or_else(|| "(unset)".to_string()),
-                SettingsField::PaymentPolicy => app
-                    .ebay_settings
-                    .payment_policy_id
-                    .clone()
-                    .unwrap_or_else(|| "(unset)".to_string()),
-                SettingsField::ReturnPolicy => app
-                    .ebay_settings
-                    .return_policy_id
-                    .clone()
-                    .unwrap_or_else(|| "(unset)".to_string()),
-                SettingsField::HsufPromptRules => app
-                    .prompt_rules
-                    .clone()
-                    .unwrap_or_else(|| "(unset)".to_string()),
-                SettingsField::LlmIngestModel => app
-                    .llm_ingest
-                    .as_ref()
-                    .map(|opts| llm_model_label(&opts.model).to_string())
-                    .unwrap_or_else(|| "(unset)".to_string()),
-                SettingsField::LlmIngestReasoning => {
-                    llm_bool_label(app.llm_ingest.as_ref().and_then(|opts| opts.reasoning))
-                }
-                SettingsField::LlmIngestWebSearch => {
-                    llm_bool_label(app.llm_ingest.as_ref().and_then(|opts| opts.web_search))
-                }
-                SettingsField::LlmAspectsModel => app
-                    .llm_aspects
-                    .as_ref()
-                    .map(|opts| llm_model_label(&opts.model).to_string())
-                    .unwrap_or_else(|| "(unset)".to_string()),
-                SettingsField::LlmAspectsReasoning => {
-                    llm_bool_label(app.llm_aspects.as_ref().and_then(|opts| opts.reasoning))
-                }
-                SettingsField::LlmAspectsWebSearch => {
-                    llm_bool_label(app.llm_aspects.as_ref().and_then(|opts| opts.web_search))
-                }
+        .map(|(idx, field)| {
+            let value = if app.settings_editing && app.settings_selected == idx {
+                app.settings_edit_buffer.clone()
+            } else {
+                settings_value(app, *field)
             };
-            if app.settings_editing && app.settings_selected == idx {
-                value = app.settings_edit_buffer.clone();
-            }
-            Row::new(vec![label.to_string(), value])
+            Row::new(vec![settings_field_label(*field).to_string(), value])
         })
         .collect::<Vec<_>>();
 
@@ -1394,24 +1793,10 @@ fn render_settings_detail_panel(
     area: Rect,
     style: BoxStyle,
 ) {
-    let fields = [
-        ("Hermes API Key", SettingsField::HermesApiKey),
-        ("Preview Height (%)", SettingsField::PreviewHeightPct),
-        ("Marketplace", SettingsField::Marketplace),
-        ("Merchant Location Key", SettingsField::MerchantLocation),
-        ("Fulfillment Policy ID", SettingsField::FulfillmentPolicy),
-        ("Payment Policy ID", SettingsField::PaymentPolicy),
-        ("Return Policy ID", SettingsField::ReturnPolicy),
-        ("HSUF Prompt Rules", SettingsField::HsufPromptRules),
-        ("LLM Ingest Model", SettingsField::LlmIngestModel),
-        ("LLM Ingest Reasoning", SettingsField::LlmIngestReasoning),
-        ("LLM Ingest Web Search", SettingsField::LlmIngestWebSearch),
-        ("LLM Aspects Model", SettingsField::LlmAspectsModel),
-        ("LLM Aspects Reasoning", SettingsField::LlmAspectsReasoning),
-        ("LLM Aspects Web Search", SettingsField::LlmAspectsWebSearch),
-    ];
+    let fields = settings_fields();
     let selected = app.settings_selected.min(fields.len().saturating_sub(1));
-    let (label, field) = fields[selected];
+    let field = fields[selected];
+    let label = settings_field_label(field);
     let title = if app.settings_editing {
         format!("Setting Editor: {label}")
     } else {
@@ -1421,67 +1806,7 @@ fn render_settings_detail_panel(
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let value = match field {
-        SettingsField::HermesApiKey => {
-            if app.config.hermes_api_key_present {
-                "(present)".to_string()
-            } else {
-                "(unset)".to_string()
-            }
-        }
-        SettingsField::PreviewHeightPct => app.preview_height_pct.to_string(),
-        SettingsField::Marketplace => app
-            .ebay_settings
-            .marketplace
-            .clone()
-            .unwrap_or_else(|| "(unset)".to_string()),
-        SettingsField::MerchantLocation => app
-            .ebay_settings
-            .merchant_location_key
-            .clone()
-            .unwrap_or_else(|| "(unset)".to_string()),
-        SettingsField::FulfillmentPolicy => app
-            .ebay_settings
-            .fulfillment_policy_id
-            .clone()
-            .unwrap_or_else(|| "(unset)".to_string()),
-        SettingsField::PaymentPolicy => app
-            .ebay_settings
-            .payment_policy_id
-            .clone()
-            .unwrap_or_else(|| "(unset)".to_string()),
-        SettingsField::ReturnPolicy => app
-            .ebay_settings
-            .return_policy_id
-            .clone()
-            .unwrap_or_else(|| "(unset)".to_string()),
-        SettingsField::HsufPromptRules => app
-            .prompt_rules
-            .clone()
-            .unwrap_or_else(|| "(unset)".to_string()),
-        SettingsField::LlmIngestModel => app
-            .llm_ingest
-            .as_ref()
-            .map(|opts| llm_model_label(&opts.model).to_string())
-            .unwrap_or_else(|| "(unset)".to_string()),
-        SettingsField::LlmIngestReasoning => {
-            llm_bool_label(app.llm_ingest.as_ref().and_then(|opts| opts.reasoning))
-        }
-        SettingsField::LlmIngestWebSearch => {
-            llm_bool_label(app.llm_ingest.as_ref().and_then(|opts| opts.web_search))
-        }
-        SettingsField::LlmAspectsModel => app
-            .llm_aspects
-            .as_ref()
-            .map(|opts| llm_model_label(&opts.model).to_string())
-            .unwrap_or_else(|| "(unset)".to_string()),
-        SettingsField::LlmAspectsReasoning => {
-            llm_bool_label(app.llm_aspects.as_ref().and_then(|opts| opts.reasoning))
-        }
-        SettingsField::LlmAspectsWebSearch => {
-            llm_bool_label(app.llm_aspects.as_ref().and_then(|opts| opts.web_search))
-        }
-    };
+    let value = settings_value(app, field);
 
     let mut lines = Vec::new();
     if app.settings_editing {
@@ -1492,6 +1817,44 @@ fn render_settings_detail_panel(
             lines.push("Or run: talaria auth login".to_string());
             lines.push(String::new());
         }
+        if matches!(field, SettingsField::BaseUrl | SettingsField::SupabaseUrl) {
+            lines.push("Enter an http(s) URL. Clients reload on save.".to_string());
+            if matches!(field, SettingsField::BaseUrl) {
+                lines.push("DEFAULT resets to the hosted Hermes API.".to_string());
+            } else {
+                lines.push("Type CLEAR to remove the Supabase config.".to_string());
+            }
+            lines.push(String::new());
+        }
+        if matches!(field, SettingsField::SupabaseServiceRoleKey) {
+            lines.push("Paste new key. Blank = keep current. Type CLEAR to remove.".to_string());
+            lines.push(String::new());
+        }
+        if matches!(
+            field,
+            SettingsField::SupabaseBucket | SettingsField::SupabaseUploadPrefix
+        ) {
+            lines.push("Letters, digits, '-', '_' and '.' only.".to_string());
+            lines.push(String::new());
+        }
+        if matches!(field, SettingsField::CapturesDir) {
+            lines.push("Absolute path. DEFAULT resets. Applies after restart.".to_string());
+            lines.push("TALARIA_CAPTURES_DIR still takes precedence.".to_string());
+            lines.push(String::new());
+        }
+        if matches!(field, SettingsField::Marketplace) {
+            lines.push("e.g. EBAY_US, EBAY_UK, EBAY_DE.".to_string());
+            lines.push(String::new());
+        }
+        if matches!(
+            field,
+            SettingsField::FulfillmentPolicy
+                | SettingsField::PaymentPolicy
+                | SettingsField::ReturnPolicy
+        ) {
+            lines.push("Numeric eBay business policy id. Blank clears.".to_string());
+            lines.push(String::new());
+        }
         if matches!(field, SettingsField::PreviewHeightPct) {
             lines.push(format!(
                 "Enter {PREVIEW_HEIGHT_MIN_PCT}-{PREVIEW_HEIGHT_MAX_PCT}. DEFAULT resets."
@@ -1527,15 +1890,12 @@ fn render_settings_detail_panel(
         lines.push(String::new());
         lines.push(value);
         lines.push(String::new());
-        if matches!(
-            field,
-            SettingsField::LlmIngestModel
-                | SettingsField::LlmIngestReasoning
-                | SettingsField::LlmIngestWebSearch
-                | SettingsField::LlmAspectsModel
-                | SettingsField::LlmAspectsReasoning
-                | SettingsField::LlmAspectsWebSearch
-        ) {
+        if matches!(field, SettingsField::PreviewBackend) {
+            lines.push("auto picks inline graphics when the terminal supports them.".to_string());
+            lines.push("Applies after restart.".to_string());
+            lines.push(String::new());
+        }
+        if is_picker_settings_field(field) {
             lines.push("Enter pick | Esc cancel".to_string());
         } else {
             lines.push("Enter edit | Esc cancel".to_string());
@@ -1550,9 +1910,115 @@ fn render_settings_detail_panel(
     );
 }
 
+fn settings_value(app: &AppState, field: SettingsField) -> String {
+    let supabase = app.config.supabase.as_ref();
+    match field {
+        SettingsField::HermesApiKey => {
+            if app.config.hermes_api_key_present {
+                "(present)".to_string()
+            } else {
+                "(unset)".to_string()
+            }
+        }
+        SettingsField::PreviewHeightPct => app.preview_height_pct.to_string(),
+        SettingsField::BaseUrl => app
+            .config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::SupabaseUrl => supabase
+            .map(|s| s.url.clone())
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::SupabaseServiceRoleKey => {
+            if supabase.is_some_and(|s| s.service_role_key.is_some()) {
+                "(present)".to_string()
+            } else {
+                "(unset)".to_string()
+            }
+        }
+        SettingsField::SupabaseBucket => supabase
+            .map(|s| s.bucket.clone())
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::SupabaseUploadPrefix => supabase
+            .map(|s| s.upload_prefix.clone())
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::SupabaseSignedUrls => {
+            if supabase.is_some_and(|s| s.use_signed_urls) {
+                "on".to_string()
+            } else {
+                "off".to_string()
+            }
+        }
+        SettingsField::CapturesDir => match &app.config.captures_dir {
+            Some(dir) => dir.display().to_string(),
+            None => format!("(default) {}", app.captures_dir.display()),
+        },
+        SettingsField::PreviewBackend => app.config.preview_backend.as_str().to_string(),
+        SettingsField::Marketplace => app
+            .ebay_settings
+            .marketplace
+            .clone()
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::MerchantLocation => app
+            .ebay_settings
+            .merchant_location_key
+            .clone()
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::FulfillmentPolicy => app
+            .ebay_settings
+            .fulfillment_policy_id
+            .clone()
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::PaymentPolicy => app
+            .ebay_settings
+            .payment_policy_id
+            .clone()
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::ReturnPolicy => app
+            .ebay_settings
+            .return_policy_id
+            .clone()
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::HsufPromptRules => app
+            .prompt_rules
+            .clone()
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::LlmIngestModel => app
+            .llm_ingest
+            .as_ref()
+            .map(|opts| llm_model_label(&opts.model).to_string())
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::LlmIngestReasoning => {
+            llm_bool_label(app.llm_ingest.as_ref().and_then(|opts| opts.reasoning))
+        }
+        SettingsField::LlmIngestWebSearch => {
+            llm_bool_label(app.llm_ingest.as_ref().and_then(|opts| opts.web_search))
+        }
+        SettingsField::LlmAspectsModel => app
+            .llm_aspects
+            .as_ref()
+            .map(|opts| llm_model_label(&opts.model).to_string())
+            .unwrap_or_else(|| "(unset)".to_string()),
+        SettingsField::LlmAspectsReasoning => {
+            llm_bool_label(app.llm_aspects.as_ref().and_then(|opts| opts.reasoning))
+        }
+        SettingsField::LlmAspectsWebSearch => {
+            llm_bool_label(app.llm_aspects.as_ref().and_then(|opts| opts.web_search))
+        }
+    }
+}
+
 fn settings_field_label(field: SettingsField) -> &'static str {
     match field {
         SettingsField::HermesApiKey => "Hermes API Key",
+        SettingsField::BaseUrl => "Hermes Base URL",
+        SettingsField::SupabaseUrl => "Supabase URL",
+        SettingsField::SupabaseServiceRoleKey => "Supabase Service Key",
+        SettingsField::SupabaseBucket => "Supabase Bucket",
+        SettingsField::SupabaseUploadPrefix => "Supabase Upload Prefix",
+        SettingsField::SupabaseSignedUrls => "Supabase Signed URLs",
+        SettingsField::CapturesDir => "Capture Directory",
+        SettingsField::PreviewBackend => "Preview Backend",
         SettingsField::PreviewHeightPct => "Preview Height (%)",
         SettingsField::Marketplace => "Marketplace",
         SettingsField::MerchantLocation => "Merchant Location Key",
@@ -1569,12 +2035,8 @@ fn settings_field_label(field: SettingsField) -> &'static str {
     }
 }
 
-fn llm_model_label(model: &talaria_core::models::LlmModel) -> &'static str {
-    match model {
-        talaria_core::models::LlmModel::Gpt5_2 => "gpt-5.2",
-        talaria_core::models::LlmModel::Gpt5Mini => "gpt-5-mini",
-        talaria_core::models::LlmModel::Gpt5Nano => "gpt-5-nano",
-    }
+fn llm_model_label(model: &talaria_core::models::LlmModel) -> &str {
+    model.as_str()
 }
 
 fn llm_bool_label(value: Option<bool>) -> String {
@@ -1666,12 +2128,25 @@ fn render_footer(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect) {
         footer_hints(app),
         Style::default().fg(theme.subtle),
     ));
-    if let Some(toast) = &app.toast {
+    if let Some(toast) = app.current_toast() {
         spans.push(Span::raw("  |  "));
         spans.push(Span::styled(
             &toast.message,
             toast_style(theme, toast.severity),
         ));
+        let waiting = app.toasts.len() - 1;
+        if waiting > 0 {
+            spans.push(Span::styled(
+                format!(" (+{waiting} more)"),
+                Style::default().fg(theme.subtle),
+            ));
+        }
+        if toast.sticky {
+            spans.push(Span::styled(
+                format!(" [{} dismiss]", app.keys.label(Action::DismissToast)),
+                Style::default().fg(theme.subtle),
+            ));
+        }
     }
 
     frame.render_widget(
@@ -1683,57 +2158,202 @@ fn render_footer(frame: &mut Frame, app: &AppState, theme: &Theme, area: Rect) {
     );
 }
 
-fn render_help(frame: &mut Frame, theme: &Theme) {
+fn render_help(frame: &mut Frame, app: &AppState, theme: &Theme) {
     let area = centered_rect(70, 70, frame.area());
     frame.render_widget(Clear, area);
-    let text = [
-        "Navigation:",
-        "  Shift+Tab: next main tab",
-        "  ?: help",
-        "  q: quit",
-        "  Quickstart tab: step-by-step flow",
-        "",
-        "Products grid:",
-        "  n new product | Enter select | d delete (y confirm)",
-        "  ↑/↓/←/→ move selection",
-        "",
-        "Products workspace:",
-        "  Tab switch view (Context / Structure / Listings)",
-        "  G back to grid",
-        "",
-        "Context view:",
-        "  ←/→ focus Images/Text",
-        "  ↑/↓ select image | Enter select frame or edit text | Del delete",
-        "  t camera on/off | v device picker | d/D device | c capture",
-        "  r structure | p draft pipeline | P publish pipeline",
-        "  Shift+S save + sync | Esc abandon session | Ctrl+S save text",
-        "",
-        "Structure view:",
-        "  ↑/↓ select field | Enter edit | r generate | g listing | Shift+S save + sync | E edit JSON",
-        "  Esc save while editing",
-        "",
-        "Listings view:",
-        "  ←/→ switch marketplace",
-        "  ↑/↓ select field | Enter edit | E edit JSON",
-        "  g run full | p run draft | P publish draft | Shift+S save + sync | u upload images",
-        "  Esc save while editing",
-        "  Images format: one URL per line (or JSON array)",
-        "  Aspects format: Value1, Value2 (or JSON array)",
-        "",
-        "Settings view:",
-        "  ↑/↓ select field | Enter edit/pick | Enter save | Esc cancel",
-    ]
-    .join("\n");
+    let k = |action: Action| app.keys.label(action);
+    let mut lines = Vec::new();
+    if app.config.mode == AccessMode::CaptureOnly {
+        lines.push(t!("tui.help.capture_only"));
+        lines.push(String::new());
+    }
+    lines.extend([
+        t!("tui.help.navigation"),
+        t!(
+            "tui.help.navigation_keys",
+            next_tab = k(Action::NextTab),
+            help = k(Action::Help),
+            metrics = k(Action::Metrics),
+            quit = k(Action::Quit),
+            capture = k(Action::CaptureHotkey),
+            dismiss = k(Action::DismissToast),
+            copy_request_id = k(Action::CopyRequestId),
+            dashboard = k(Action::OpenDashboard)
+        ),
+        String::new(),
+        t!("tui.help.home"),
+        t!("tui.help.home_keys", failed_jobs = k(Action::FailedJobs)),
+        String::new(),
+        t!("tui.help.grid"),
+        t!(
+            "tui.help.grid_keys",
+            new = k(Action::NewProduct),
+            delete = k(Action::DeleteProduct),
+            search = k(Action::Search),
+            status = k(Action::StatusFilter),
+            marketplace = k(Action::MarketplaceFilter),
+            date = k(Action::DateFilter),
+            clear = k(Action::ClearFilters),
+            mark_all = k(Action::MarkAll),
+            undo = k(Action::Undo),
+            trash = k(Action::Trash),
+            bulk_upload = k(Action::BulkUpload),
+            bulk_enrich = k(Action::BulkEnrich),
+            bulk_export = k(Action::BulkExport),
+            bulk_delete = k(Action::BulkDelete)
+        ),
+        String::new(),
+        t!("tui.help.workspace"),
+        t!(
+            "tui.help.workspace_keys",
+            back = k(Action::BackToGrid),
+            gallery = k(Action::MediaGallery),
+            copy_urls = k(Action::CopyImageUrls),
+            copy_job_id = k(Action::CopyJobId)
+        ),
+        String::new(),
+        t!("tui.help.context"),
+        t!(
+            "tui.help.context_keys",
+            edit = k(Action::Edit),
+            camera = k(Action::ToggleCamera),
+            device_picker = k(Action::DevicePicker),
+            prev_device = k(Action::PrevDevice),
+            next_device = k(Action::NextDevice),
+            capture = k(Action::Capture),
+            retro = k(Action::RetroCapture),
+            countdown = k(Action::CountdownCapture),
+            both = k(Action::CaptureBoth),
+            turntable = k(Action::Turntable),
+            structure = k(Action::GenerateStructure),
+            draft = k(Action::Draft),
+            publish = k(Action::Publish),
+            grade = k(Action::Grade),
+            barcode = k(Action::ScanBarcode),
+            measure = k(Action::Measure),
+            gray_card = k(Action::GrayCard),
+            zoom = k(Action::ZoomAssist),
+            peaking = k(Action::FocusPeaking),
+            save_sync = k(Action::SaveSync),
+            open_image = k(Action::OpenImage),
+            undo = k(Action::Undo)
+        ),
+        String::new(),
+        t!("tui.help.structure"),
+        t!(
+            "tui.help.structure_keys",
+            edit = k(Action::Edit),
+            structure = k(Action::GenerateStructure),
+            listing = k(Action::GenerateListing),
+            save_sync = k(Action::SaveSync),
+            edit_json = k(Action::EditJson)
+        ),
+        String::new(),
+        t!("tui.help.listings"),
+        t!(
+            "tui.help.listings_keys",
+            edit = k(Action::Edit),
+            edit_json = k(Action::EditJson),
+            listing = k(Action::GenerateListing),
+            draft = k(Action::Draft),
+            publish = k(Action::Publish),
+            save_sync = k(Action::SaveSync),
+            upload = k(Action::UploadImages),
+            grade = k(Action::Grade),
+            copy_listing_id = k(Action::CopyListingId),
+            open_listing = k(Action::OpenListing),
+            price = k(Action::SuggestPrice),
+            add_aspect = k(Action::AddAspect)
+        ),
+        String::new(),
+        t!("tui.help.analytics"),
+        t!(
+            "tui.help.analytics_keys",
+            days = talaria_core::stats::DEFAULT_DAYS,
+            refresh = k(Action::RefreshStats),
+            burn_down_days = talaria_core::usage_history::BURN_DOWN_DAYS
+        ),
+        String::new(),
+        t!("tui.help.settings"),
+        t!("tui.help.settings_keys"),
+        String::new(),
+        t!("tui.help.key_bindings"),
+    ]);
+    for action in Action::ALL {
+        let scopes = action
+            .scopes()
+            .iter()
+            .map(|scope| match scope {
+                KeyScope::Global => "global",
+                KeyScope::Home => "home",
+                KeyScope::Grid => "grid",
+                KeyScope::Workspace => "workspace",
+                KeyScope::Context => "context",
+                KeyScope::Structure => "structure",
+                KeyScope::Listings => "listings",
+                KeyScope::Analytics => "analytics",
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        lines.push(format!(
+            "  {:<10} {} = {} ({})",
+            k(*action),
+            action.name(),
+            action.description(),
+            scopes
+        ));
+    }
+    let text = lines.join("\n");
 
     frame.render_widget(
         Paragraph::new(text)
             .style(theme.panel())
-            .block(panel_title(theme, "Help"))
+            .block(panel_title(theme, i18n::text("tui.help.title")))
             .wrap(Wrap { trim: true }),
         area,
     );
 }
 
+/// Corner overlay with this session's metrics; it does not take focus.
+fn render_metrics(frame: &mut Frame, theme: &Theme) {
+    let snapshot = talaria_core::metrics::global().snapshot();
+    let mut lines = Vec::new();
+    for (series, value) in &snapshot.gauges {
+        lines.push(format!("{series} {value:.1}"));
+    }
+    for (series, value) in &snapshot.counters {
+        lines.push(format!("{series} {value}"));
+    }
+    for (series, timing) in &snapshot.timings {
+        lines.push(format!(
+            "{series} n={} avg={:.0}ms max={:.0}ms",
+            timing.count,
+            timing.mean_secs() * 1000.0,
+            timing.max_secs * 1000.0
+        ));
+    }
+    if lines.is_empty() {
+        lines.push("Nothing recorded yet.".to_string());
+    }
+
+    let full = frame.area();
+    let width = (full.width / 2).max(40).min(full.width);
+    let height = (lines.len() as u16 + 2).min(full.height.saturating_sub(2));
+    let area = Rect {
+        x: full.x + full.width - width,
+        y: full.y + 1,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines.join("\n"))
+            .style(theme.panel())
+            .block(panel_title(theme, "Metrics")),
+        area,
+    );
+}
+
 fn render_product_picker(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
     let area = centered_rect(80, 70, frame.area());
     frame.render_widget(Clear, area);
@@ -1747,9 +2367,14 @@ fn render_product_picker(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
         ])
         .split(area);
 
-    let header = Paragraph::new(format!("Search: {}", app.picker.search))
-        .style(theme.panel())
-        .block(panel_title(theme, "Select Product"));
+    let header = Paragraph::new(format!(
+        "Search: {} | Status: {} | Updated: {}",
+        app.picker.search,
+        app.picker.status_filter.label(),
+        app.picker.date_filter.label()
+    ))
+    .style(theme.panel())
+    .block(panel_title(theme, "Select Product"));
     frame.render_widget(header, chunks[0]);
 
     let filtered = app.filtered_products();
@@ -1800,6 +2425,557 @@ fn render_product_picker(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
     frame.render_widget(footer, chunks[2]);
 }
 
+fn render_failed_jobs(frame: &mut Frame, app: &AppState, theme: &Theme) {
+    let area = centered_rect(80, 70, frame.area());
+    frame.render_widget(Clear, area);
+    let view = &app.failed_jobs;
+
+    if let Some(buffer) = &view.overrides {
+        let job = view
+            .jobs
+            .get(view.selected)
+            .map(|job| job.job_id.as_str())
+            .unwrap_or("-");
+        frame.render_widget(
+            Paragraph::new(format!("{buffer}_"))
+                .style(theme.panel())
+                .block(panel_title(
+                    theme,
+                    &format!("Overrides for retry of {job} (Ctrl+S retry | Esc cancel)"),
+                ))
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(2)])
+        .split(area);
+
+    let rows = view
+        .jobs
+        .iter()
+        .map(|job| {
+            Row::new(vec![
+                job.job_id.clone(),
+                job.sku.clone().unwrap_or_else(|| "-".to_string()),
+                job.enqueued_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+                job.error.clone().unwrap_or_default(),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let mut state = TableState::default();
+    if !view.jobs.is_empty() {
+        state.select(Some(view.selected.min(view.jobs.len() - 1)));
+    }
+
+    let title = if view.jobs.is_empty() {
+        "Failed listing jobs (none)".to_string()
+    } else {
+        format!("Failed listing jobs ({})", view.jobs.len())
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(20),
+            Constraint::Length(16),
+            Constraint::Length(17),
+            Constraint::Min(20),
+        ],
+    )
+    .header(Row::new(vec!["Job", "SKU", "Enqueued", "Error"]).style(theme.title()))
+    .block(panel_title(theme, &title))
+    .row_highlight_style(
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_stateful_widget(table, chunks[0], &mut state);
+
+    let footer = Paragraph::new(
+        "↑/↓ select | Enter/r retry | e edit overrides, then retry | d dismiss | y copy id | Esc close",
+    )
+    .style(theme.panel())
+    .block(theme.panel_block());
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn render_media_view(frame: &mut Frame, app: &AppState, theme: &Theme) {
+    let area = centered_rect(85, 70, frame.area());
+    frame.render_widget(Clear, area);
+    let view = &app.media_view;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(2)])
+        .split(area);
+
+    let rows = view
+        .items
+        .iter()
+        .map(|item| {
+            let (status, style) = if item.local_orphan() {
+                ("local only", theme.warn())
+            } else if item.remote_orphan() {
+                ("remote only", theme.err())
+            } else if item.by_hash {
+                ("same file", theme.panel())
+            } else {
+                ("linked", theme.panel())
+            };
+            let local = item.rel_path.clone().map_or_else(
+                || "-".to_string(),
+                |rel| {
+                    if item.local_hero {
+                        format!("{rel} *")
+                    } else {
+                        rel
+                    }
+                },
+            );
+            let (media, purpose, rank) = match &item.remote {
+                Some(media) => (
+                    media.media_id.clone(),
+                    media
+                        .purpose
+                        .as_ref()
+                        .map_or_else(|| "-".to_string(), |purpose| purpose.to_string()),
+                    media
+                        .rank
+                        .map_or_else(|| "-".to_string(), |rank| rank.to_string()),
+                ),
+                None => ("-".to_string(), "-".to_string(), "-".to_string()),
+            };
+            let purpose = if item.remote_hero() {
+                format!("{purpose} *")
+            } else {
+                purpose
+            };
+            Row::new(vec![status.to_string(), local, media, purpose, rank]).style(style)
+        })
+        .collect::<Vec<_>>();
+
+    let mut state = TableState::default();
+    if !view.items.is_empty() {
+        state.select(Some(view.selected.min(view.items.len() - 1)));
+    }
+
+    let local_orphans = view.items.iter().filter(|item| item.local_orphan()).count();
+    let remote_orphans = view
+        .items
+        .iter()
+        .filter(|item| item.remote_orphan())
+        .count();
+    let mut title = format!(
+        "Media ({} | {local_orphans} local only | {remote_orphans} remote only)",
+        view.items.len()
+    );
+    if view.loading {
+        title.push_str(&format!(" {}", app.spinner_frame()));
+    }
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Percentage(40),
+            Constraint::Length(24),
+            Constraint::Length(16),
+            Constraint::Length(5),
+        ],
+    )
+    .header(Row::new(vec!["Status", "Local", "Media", "Purpose", "Rank"]).style(theme.title()))
+    .block(panel_title(theme, &title))
+    .row_highlight_style(
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )
+    .style(theme.panel());
+    frame.render_stateful_widget(table, chunks[0], &mut state);
+
+    let footer = match &view.rank_input {
+        Some(buffer) => format!("Rank: {buffer}_ | Enter save | Esc cancel"),
+        None => "↑/↓ select | u upload missing | d delete remote orphan | h make hero | r edit rank | g refresh | Esc close (* hero)"
+            .to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(footer)
+            .style(theme.panel())
+            .block(theme.panel_block()),
+        chunks[1],
+    );
+}
+
+fn render_trash_view(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
+    let area = centered_rect(70, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(2)])
+        .split(area);
+
+    let rows = app
+        .trash_view
+        .items
+        .iter()
+        .map(|item| {
+            let kind = match item {
+                crate::storage::trash::TrashItem::Product { .. } => "product",
+                crate::storage::trash::TrashItem::Frame { .. } => "frame",
+            };
+            Row::new(vec![
+                kind.to_string(),
+                item.describe(),
+                item.deleted_at().format("%Y-%m-%d %H:%M").to_string(),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let mut state = TableState::default();
+    if !app.trash_view.items.is_empty() {
+        state.select(Some(
+            app.trash_view.selected.min(app.trash_view.items.len() - 1),
+        ));
+    }
+
+    let title = format!(
+        "Trash (kept {} days)",
+        crate::storage::trash::TRASH_RETENTION_DAYS
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Percentage(60),
+            Constraint::Length(18),
+        ],
+    )
+    .header(Row::new(vec!["Kind", "Item", "Deleted"]).style(theme.title()))
+    .block(panel_title(theme, &title))
+    .row_highlight_style(
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )
+    .style(theme.panel());
+    frame.render_stateful_widget(table, chunks[0], &mut state);
+
+    let footer = Paragraph::new("↑/↓ select | Enter restore | Esc close")
+        .style(theme.panel())
+        .block(theme.panel_block());
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn render_resume_prompt(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
+    let area = centered_rect(70, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(2)])
+        .split(area);
+
+    let now = chrono::Local::now();
+    let rows = app
+        .resume_prompt
+        .sessions
+        .iter()
+        .map(|resumable| {
+            Row::new(vec![
+                resumable.sku_alias.clone(),
+                resumable.session.frames.len().to_string(),
+                format_age(now - resumable.last_activity),
+                resumable.session.session_id.clone(),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let mut state = TableState::default();
+    if !app.resume_prompt.sessions.is_empty() {
+        state.select(Some(
+            app.resume_prompt
+                .selected
+                .min(app.resume_prompt.sessions.len() - 1),
+        ));
+    }
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(30),
+            Constraint::Length(7),
+            Constraint::Length(10),
+            Constraint::Percentage(40),
+        ],
+    )
+    .header(Row::new(vec!["Product", "Frames", "Age", "Session"]).style(theme.title()))
+    .block(panel_title(theme, "Resume unfinished session?"))
+    .row_highlight_style(
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )
+    .style(theme.panel());
+    frame.render_stateful_widget(table, chunks[0], &mut state);
+
+    let footer = Paragraph::new("↑/↓ select | Enter resume | d discard | Esc skip")
+        .style(theme.panel())
+        .block(theme.panel_block());
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn render_grading(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
+    let area = centered_rect(70, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(2)])
+        .split(area);
+
+    let state = &app.grading;
+    let selected = |field: GradingField| {
+        if state.field == field {
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            theme.panel()
+        }
+    };
+    let grade = state.grades.get(state.selected);
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "Grade: ‹ {} › ({}/{})",
+            grade.map_or("-", |g| g.label.as_str()),
+            state.selected + 1,
+            state.grades.len()
+        ),
+        selected(GradingField::Grade),
+    ))];
+    if let Some(grade) = grade {
+        lines.push(Line::from(format!(
+            "  maps to {} / {}",
+            grade.condition, grade.condition_id
+        )));
+        if let Some(guidance) = &grade.guidance {
+            lines.push(Line::from(format!("  {guidance}")));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("Notes: {}", state.notes),
+        selected(GradingField::Notes),
+    )));
+    if !state.defects.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from("Defects:"));
+    }
+    for (idx, defect) in state.defects.iter().enumerate() {
+        let photo = defect.photo.as_deref().map_or("no photo".to_string(), |p| {
+            Path::new(p)
+                .file_name()
+                .map_or(p.to_string(), |name| name.to_string_lossy().into_owned())
+        });
+        lines.push(Line::from(Span::styled(
+            format!("  {}. {} [{photo}]", idx + 1, defect.note),
+            selected(GradingField::Defect(idx)),
+        )));
+    }
+
+    let body = Paragraph::new(lines)
+        .style(theme.panel())
+        .block(panel_title(theme, "Grade condition"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(body, chunks[0]);
+
+    let footer = Paragraph::new(
+        "↑/↓ field | ←/→ grade | type notes | Tab defect photo | Enter save | Del clear | Esc cancel",
+    )
+    .style(theme.panel())
+    .block(theme.panel_block());
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn render_barcode_prompt(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
+    let Some(prompt) = &app.barcode_prompt else {
+        return;
+    };
+    let area = centered_rect(60, 30, frame.area());
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(4), Constraint::Length(2)])
+        .split(area);
+
+    let source = prompt.frame.file_name().map_or_else(
+        || prompt.frame.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let current_sku = app
+        .active_product
+        .as_ref()
+        .filter(|p| p.product_id == prompt.product_id)
+        .map_or("-", |p| p.sku_alias.as_str());
+    let lines = vec![
+        Line::from(Span::styled(
+            prompt.barcode.context_line(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!("  found in {source}")),
+        Line::from(format!("  current SKU: {current_sku}")),
+    ];
+    let body = Paragraph::new(lines)
+        .style(theme.panel())
+        .block(panel_title(theme, "Barcode found"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(body, chunks[0]);
+
+    let footer = Paragraph::new("Enter set as SKU + add to context | c context only | Esc ignore")
+        .style(theme.panel())
+        .block(theme.panel_block());
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn render_price_prompt(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
+    let Some(prompt) = &app.price_prompt else {
+        return;
+    };
+    let area = centered_rect(60, 35, frame.area());
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(2)])
+        .split(area);
+
+    let suggestion = &prompt.suggestion;
+    let currency = suggestion.currency.as_deref().unwrap_or("");
+    let current = app
+        .active_product
+        .as_ref()
+        .filter(|p| p.product_id == prompt.product_id)
+        .and_then(|p| p.listings.get(&prompt.marketplace_key))
+        .and_then(|listing| listing.price)
+        .map_or_else(|| "-".to_string(), |price| format!("{price:.2}"));
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("{:.2} {currency}", prompt.price),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!(
+            "  low {:.2} | suggested {:.2} | high {:.2}",
+            suggestion.low, suggestion.suggested, suggestion.high
+        )),
+        Line::from(format!(
+            "  from {} comparable prices, adjusted ×{:.2}",
+            suggestion.comparables, suggestion.adjustment
+        )),
+        Line::from(format!(
+            "  current price on {}: {current}",
+            prompt.marketplace_key
+        )),
+    ];
+    let body = Paragraph::new(lines)
+        .style(theme.panel())
+        .block(panel_title(theme, "Price suggestion"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(body, chunks[0]);
+
+    let footer = Paragraph::new(
+        "←/→ adjust (Shift ×10) | l/m/h low/suggested/high | Enter apply | Esc cancel",
+    )
+    .style(theme.panel())
+    .block(theme.panel_block());
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn render_sync_conflict(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
+    let Some(prompt) = &app.sync_conflict else {
+        return;
+    };
+    let area = centered_rect(70, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(2)])
+        .split(area);
+
+    let shown = |value: &Option<Value>| {
+        value.as_ref().map_or_else(
+            || "(unset)".to_string(),
+            |value| truncate(&format_structure_value_inline(value), 80),
+        )
+    };
+    let mut lines = vec![
+        Line::from(format!(
+            "{} changed both here and on Hermes since the last sync:",
+            prompt.sku_alias
+        )),
+        Line::from(""),
+    ];
+    // Keep the selected conflict in view; each takes three lines.
+    let visible = (chunks[0].height.saturating_sub(4) / 3).max(1) as usize;
+    let skip = prompt.selected.saturating_sub(visible - 1);
+    for (idx, conflict) in prompt.conflicts.iter().enumerate().skip(skip).take(visible) {
+        let style = if idx == prompt.selected {
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().add_modifier(Modifier::BOLD)
+        };
+        lines.push(Line::from(Span::styled(conflict.path.clone(), style)));
+        lines.push(Line::from(Span::styled(
+            format!("  local:  {}", shown(&conflict.local)),
+            theme.err(),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("  Hermes: {}", shown(&conflict.remote)),
+            theme.ok(),
+        )));
+    }
+    let title = format!("Sync conflict ({})", prompt.conflicts.len());
+    let body = Paragraph::new(lines)
+        .style(theme.panel())
+        .block(panel_title(theme, &title))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(body, chunks[0]);
+
+    let footer = Paragraph::new(
+        "↑/↓ scroll | l keep local | r keep Hermes | m merge (local wins overlaps) | Esc later",
+    )
+    .style(theme.panel())
+    .block(theme.panel_block());
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn format_age(age: chrono::Duration) -> String {
+    let minutes = age.num_minutes().max(0);
+    if minutes < 1 {
+        "just now".to_string()
+    } else if minutes < 60 {
+        format!("{minutes}m ago")
+    } else if minutes < 60 * 24 {
+        format!("{}h ago", minutes / 60)
+    } else {
+        format!("{}d ago", minutes / (60 * 24))
+    }
+}
+
 fn render_camera_picker(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
     let area = centered_rect(70, 55, frame.area());
     frame.render_widget(Clear, area);
@@ -1825,7 +3001,7 @@ fn render_camera_picker(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
             .wrap(Wrap { trim: true });
         frame.render_widget(body, chunks[1]);
     } else if app.camera_picker.devices.is_empty() {
-        let body = Paragraph::new("No cameras found. Plug in a USB camera and press r to refresh.")
+        let body = Paragraph::new("No cameras found. Plug in a USB camera and press r to refresh, or u to add a network camera.")
             .style(theme.panel())
             .block(theme.panel_block())
             .wrap(Wrap { trim: true });
@@ -1835,7 +3011,13 @@ fn render_camera_picker(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
             .camera_picker
             .devices
             .iter()
-            .map(|dev| Row::new(vec![dev.index.to_string(), dev.name.clone()]))
+            .map(|dev| {
+                Row::new(vec![
+                    dev.index.to_string(),
+                    dev.kind.label().to_string(),
+                    dev.name.clone(),
+                ])
+            })
             .collect::<Vec<_>>();
 
         let mut state = TableState::default();
@@ -1845,19 +3027,32 @@ fn render_camera_picker(frame: &mut Frame, app: &mut AppState, theme: &Theme) {
                 .min(app.camera_picker.devices.len().saturating_sub(1)),
         ));
 
-        let table = Table::new(rows, [Constraint::Length(8), Constraint::Percentage(80)])
-            .header(Row::new(vec!["Index", "Name"]).style(theme.title()))
-            .block(panel_title(theme, "Devices"))
-            .row_highlight_style(
-                Style::default()
-                    .fg(theme.accent)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .style(theme.panel());
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(9),
+                Constraint::Percentage(70),
+            ],
+        )
+        .header(Row::new(vec!["Index", "Type", "Name"]).style(theme.title()))
+        .block(panel_title(theme, "Devices"))
+        .row_highlight_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(theme.panel());
         frame.render_stateful_widget(table, chunks[1], &mut state);
     }
 
-    let footer = Paragraph::new("↑/↓ select | Enter choose | r refresh | Esc cancel")
+    let footer_text = match &app.camera_picker.url_input {
+        Some(input) => format!("URL: {input}_  (Enter stream | Esc cancel)"),
+        None => {
+            "↑/↓ select | Enter choose | u add network camera | r refresh | Esc cancel".to_string()
+        }
+    };
+    let footer = Paragraph::new(footer_text)
         .style(theme.panel())
         .block(theme.panel_block());
     frame.render_widget(footer, chunks[2]);
@@ -1929,9 +3124,49 @@ fn system_status_text(app: &AppState) -> String {
     } else {
         "idle"
     };
+    let device = match &app.capture_status.device_url {
+        Some(url) => url.clone(),
+        None => format!("device {}", app.device_index),
+    };
+    let link = app
+        .capture_status
+        .link
+        .as_ref()
+        .map(|link| format!("  Link: {link}"))
+        .unwrap_or_default();
+    let (label, second) = match &app.capture_status.second {
+        Some(second) => (
+            format!(" [{}]", second.primary_label),
+            format!(
+                "\n{}: {} ({})  FPS: {:.1}  Size: {}{}",
+                second.label,
+                if second.streaming {
+                    "streaming"
+                } else {
+                    "idle"
+                },
+                second.source,
+                second.fps,
+                second
+                    .frame_size
+                    .map(|(w, h)| format!("{w}x{h}"))
+                    .unwrap_or_else(|| "n/a".to_string()),
+                second
+                    .link
+                    .as_ref()
+                    .map(|link| format!("  Link: {link}"))
+                    .unwrap_or_default()
+            ),
+        ),
+        None => (String::new(), String::new()),
+    };
+    let turntable = app
+        .capture_status
+        .turntable
+        .map(|(taken, total)| format!("\nTurntable: {taken}/{total} frames"))
+        .unwrap_or_default();
     format!(
-        "Camera: {camera} (device {})\nStream: {stream}  FPS: {:.1}  Dropped: {}\nCaptures: {}",
-        app.device_index,
+        "Camera{label}: {camera} ({device})\nStream: {stream}{link}  FPS: {:.1}  Dropped: {}{second}{turntable}\nCaptures: {}",
         app.capture_status.fps,
         app.capture_status.dropped_frames,
         app.captures_dir.display()
@@ -1979,7 +3214,7 @@ fn pipeline_text(app: &AppState) -> String {
         return [
             "Hermes sign-in required for online mode.",
             "Run: talaria auth login",
-            "Restart Talaria after login.",
+            "Talaria picks up the new key automatically.",
         ]
         .join("\n");
     }
@@ -2006,6 +3241,13 @@ fn pipeline_text(app: &AppState) -> String {
     if let Some(updated) = app.credits_last_updated {
         lines.push(format!("Updated {}s ago", updated.elapsed().as_secs()));
     }
+    if !app.failed_jobs.jobs.is_empty() {
+        lines.push(format!(
+            "Failed listing jobs: {} ({} to retry)",
+            app.failed_jobs.jobs.len(),
+            app.keys.label(Action::FailedJobs)
+        ));
+    }
 
     lines.join("\n")
 }
@@ -2026,28 +3268,106 @@ fn session_progress(app: &AppState) -> u16 {
     10
 }
 
+/// Box size shown while measuring, e.g. `front W 4.25 × H 3 in`.
+fn measure_readout(app: &AppState) -> Option<String> {
+    if !app.measure.open {
+        return None;
+    }
+    let measurement = &app.config.measurement;
+    let (view, across) = if app.measure.side_view {
+        ("side", "D")
+    } else {
+        ("front", "W")
+    };
+    let calibrated = if measurement.pixels_per_unit.is_some() {
+        ""
+    } else {
+        " (not calibrated)"
+    };
+    Some(format!(
+        "{view} {across} {} × H {} {}{calibrated}",
+        app.measure.across,
+        app.measure.tall,
+        measurement.unit.label()
+    ))
+}
+
 fn footer_hints(app: &AppState) -> String {
-    let base = "Shift+Tab tabs | ? help | q quit";
-    let base_no_arrows = "Shift+Tab tabs | ? help | q quit";
+    let keys = &app.keys;
+    if let Some(readout) = measure_readout(app) {
+        return format!(
+            "Measuring {readout} | ←/→ width | ↑/↓ height | Shift larger steps | Tab front/side | k calibrate | {} capture | Enter record | Del clear | Esc done",
+            keys.label(Action::Capture)
+        );
+    }
+    let base = format!(
+        "{} tabs | {} help | {} quit",
+        keys.label(Action::NextTab),
+        keys.label(Action::Help),
+        keys.label(Action::Quit)
+    );
+    let capture_only = app.config.mode == AccessMode::CaptureOnly;
+    let hints = |actions: &[Action]| {
+        actions
+            .iter()
+            .filter(|action| !(capture_only && action.restricted()))
+            .map(|action| keys.hint(*action))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
     match app.active_tab {
+        AppTab::Home => format!("{base} | {}", hints(&[Action::FailedJobs])),
         AppTab::Products => match app.products_mode {
-            crate::app::ProductsMode::Grid => {
-                format!("{base_no_arrows} | n new | Enter select | d delete | ↑/↓/←/→ move")
-            }
+            crate::app::ProductsMode::Grid => format!(
+                "{base} | {} | Enter select | {} | ↑/↓/←/→ move",
+                hints(&[Action::NewProduct]),
+                hints(&[Action::DeleteProduct])
+            ),
             crate::app::ProductsMode::Workspace => match app.products_subtab {
                 crate::app::ProductsSubTab::Context => format!(
-                    "{base_no_arrows} | Tab view | Shift+S save+sync | r structure | p draft | P publish | G grid | ←/→ focus | ↑/↓ select | Enter edit | Del delete | t camera on/off | v device picker | d/D device | c capture | Esc abandon"
+                    "{base} | Tab view | {} | ←/→ focus | ↑/↓ select | Enter edit | Del delete | {} | Esc abandon",
+                    hints(&[
+                        Action::SaveSync,
+                        Action::GenerateStructure,
+                        Action::Draft,
+                        Action::Publish,
+                        Action::Grade,
+                        Action::ScanBarcode,
+                        Action::Measure,
+                        Action::BackToGrid
+                    ]),
+                    hints(&[
+                        Action::ToggleCamera,
+                        Action::DevicePicker,
+                        Action::PrevDevice,
+                        Action::NextDevice,
+                        Action::Capture
+                    ])
                 ),
                 crate::app::ProductsSubTab::Structure => format!(
-                    "{base_no_arrows} | Tab view | Shift+S save+sync | G grid | ↑/↓ select | Enter edit | r generate | g listing | E edit JSON"
+                    "{base} | Tab view | {} | ↑/↓ select | Enter edit | {}",
+                    hints(&[Action::SaveSync, Action::BackToGrid]),
+                    hints(&[
+                        Action::GenerateStructure,
+                        Action::GenerateListing,
+                        Action::EditJson
+                    ])
+                ),
+                crate::app::ProductsSubTab::Listings => format!(
+                    "{base} | Tab view | {} | ←/→ marketplace | ↑/↓ field | Enter edit | {}",
+                    hints(&[Action::SaveSync, Action::BackToGrid]),
+                    hints(&[
+                        Action::GenerateListing,
+                        Action::Draft,
+                        Action::Publish,
+                        Action::SuggestPrice,
+                        Action::EditJson,
+                        Action::UploadImages
+                    ])
                 ),
-                crate::app::ProductsSubTab::Listings => {
-                    format!(
-                        "{base_no_arrows} | Tab view | Shift+S save+sync | G grid | ←/→ marketplace | ↑/↓ field | Enter edit | g full | p draft | P publish draft | E edit JSON | u upload"
-                    )
-                }
             },
         },
+        AppTab::Analytics => format!("{base} | {}", hints(&[Action::RefreshStats])),
         AppTab::Settings => {
             if app.settings_picker.open {
                 format!("{base} | ↑/↓ select | Enter choose | Esc cancel")
@@ -2055,7 +3375,7 @@ fn footer_hints(app: &AppState) -> String {
                 format!("{base} | ↑/↓ select | Enter edit | Enter save | Esc cancel")
             }
         }
-        _ => base.to_string(),
+        _ => base,
     }
 }
 
@@ -2102,6 +3422,58 @@ fn format_aspect_value_inline(value: &Value) -> String {
     truncate(&joined, 80)
 }
 
+fn allowed_condition_lines(app: &AppState) -> Vec<String> {
+    let listing = app.selected_listing().unwrap_or_default();
+    if listing.allowed_conditions.is_empty() {
+        return vec![
+            String::new(),
+            "Allowed conditions appear after a draft run.".to_string(),
+        ];
+    }
+    let mut lines = vec![String::new(), "Allowed:".to_string()];
+    for (idx, label) in listing.allowed_conditions.iter().enumerate() {
+        match listing.allowed_condition_ids.get(idx) {
+            Some(id) => lines.push(format!("  {label} ({id})")),
+            None => lines.push(format!("  {label}")),
+        }
+    }
+    lines
+}
+
+fn package_template_hint(app: &AppState) -> String {
+    let names: Vec<_> = app
+        .config
+        .package_templates
+        .iter()
+        .map(|template| template.name.as_str())
+        .collect();
+    if names.is_empty() {
+        "No [[package_templates]] in config.".to_string()
+    } else {
+        format!("Templates: {} (empty clears).", names.join(", "))
+    }
+}
+
+fn aspect_spec_lines(app: &AppState, name: &str) -> Vec<String> {
+    let Some(spec) = app.aspect_spec(name) else {
+        return vec![
+            String::new(),
+            "Custom aspect (not in category spec).".to_string(),
+        ];
+    };
+    let mut lines = vec![String::new()];
+    lines.push(if spec.required {
+        "Required by category.".to_string()
+    } else {
+        "Optional.".to_string()
+    });
+    if !spec.samples.is_empty() {
+        lines.push(format!("Suggested: {}", spec.samples.join(", ")));
+        lines.push("Tab completes from suggestions while editing.".to_string());
+    }
+    lines
+}
+
 fn format_aspect_values_lines(value: &Value) -> Vec<String> {
     let values = clean_aspect_values(coerce_aspect_values(value));
     if values.is_empty() {
@@ -0,0 +1,133 @@
+//! Focus assist for the live previews. Zoom crops the centre of the
+//! full-resolution frame before it is shrunk to the panel, so a model number
+//! shows at sensor detail; focus peaking paints pixels whose luma gradient is
+//! steep, which only happens on edges that are in focus.
+
+use std::borrow::Cow;
+
+use image::{Rgb, RgbImage, imageops};
+
+const PEAK_COLOR: Rgb<u8> = Rgb([255, 40, 40]);
+/// Sobel magnitude (|gx| + |gy| on 0–255 luma) an edge needs to be painted.
+const PEAK_THRESHOLD: i32 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewAssist {
+    /// 1 (off), 2 or 4.
+    pub zoom: u32,
+    pub peaking: bool,
+}
+
+impl Default for PreviewAssist {
+    fn default() -> Self {
+        Self {
+            zoom: 1,
+            peaking: false,
+        }
+    }
+}
+
+impl PreviewAssist {
+    /// 1× → 2× → 4× → 1×.
+    pub fn next_zoom(self) -> Self {
+        let zoom = match self.zoom {
+            1 => 2,
+            2 => 4,
+            _ => 1,
+        };
+        Self { zoom, ..self }
+    }
+
+    pub fn zoomed(self) -> bool {
+        self.zoom > 1
+    }
+
+    /// The centre `1/zoom` of `frame`, or the frame itself at 1×.
+    pub fn crop<'a>(self, frame: &'a RgbImage) -> Cow<'a, RgbImage> {
+        if !self.zoomed() {
+            return Cow::Borrowed(frame);
+        }
+        let (width, height) = frame.dimensions();
+        let crop_w = (width / self.zoom).max(1);
+        let crop_h = (height / self.zoom).max(1);
+        Cow::Owned(
+            imageops::crop_imm(
+                frame,
+                (width - crop_w) / 2,
+                (height - crop_h) / 2,
+                crop_w,
+                crop_h,
+            )
+            .to_image(),
+        )
+    }
+
+    /// Short label for the preview title, e.g. `2× · peaking`.
+    pub fn label(self) -> Option<String> {
+        match (self.zoomed(), self.peaking) {
+            (false, false) => None,
+            (true, false) => Some(format!("{}×", self.zoom)),
+            (false, true) => Some("peaking".to_string()),
+            (true, true) => Some(format!("{}× · peaking", self.zoom)),
+        }
+    }
+}
+
+/// Paints in-focus edges of `frame`. Run it on the downscaled frame: the
+/// gradients are those of what is shown, and it stays cheap.
+pub fn draw_peaking(frame: &mut RgbImage) {
+    let (width, height) = frame.dimensions();
+    if width < 3 || height < 3 {
+        return;
+    }
+    let luma: Vec<i32> = frame
+        .as_raw()
+        .chunks_exact(3)
+        .map(|px| (px[0] as i32 * 299 + px[1] as i32 * 587 + px[2] as i32 * 114) / 1000)
+        .collect();
+    let w = width as usize;
+    let at = |x: usize, y: usize| luma[y * w + x];
+    for y in 1..height as usize - 1 {
+        for x in 1..w - 1 {
+            let gx = at(x + 1, y - 1) + 2 * at(x + 1, y) + at(x + 1, y + 1)
+                - at(x - 1, y - 1)
+                - 2 * at(x - 1, y)
+                - at(x - 1, y + 1);
+            let gy = at(x - 1, y + 1) + 2 * at(x, y + 1) + at(x + 1, y + 1)
+                - at(x - 1, y - 1)
+                - 2 * at(x, y - 1)
+                - at(x + 1, y - 1);
+            if gx.abs() + gy.abs() >= PEAK_THRESHOLD {
+                frame.put_pixel(x as u32, y as u32, PEAK_COLOR);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoom_crops_the_centre_and_peaking_marks_only_sharp_edges() {
+        let mut frame = RgbImage::from_fn(400, 200, |x, _| {
+            if x < 200 {
+                Rgb([0, 0, 0])
+            } else {
+                Rgb([255, 255, 255])
+            }
+        });
+        let assist = PreviewAssist::default().next_zoom();
+        assert_eq!(assist.label().as_deref(), Some("2×"));
+        let cropped = assist.crop(&frame);
+        assert_eq!(cropped.dimensions(), (200, 100));
+        assert_eq!(cropped.get_pixel(99, 50), &Rgb([0, 0, 0]));
+        assert_eq!(cropped.get_pixel(100, 50), &Rgb([255, 255, 255]));
+        assert_eq!(assist.next_zoom().next_zoom(), PreviewAssist::default());
+
+        draw_peaking(&mut frame);
+        assert_eq!(frame.get_pixel(200, 100), &PEAK_COLOR);
+        assert_eq!(frame.get_pixel(100, 100), &Rgb([0, 0, 0]));
+        assert_eq!(frame.get_pixel(300, 100), &Rgb([255, 255, 255]));
+    }
+}
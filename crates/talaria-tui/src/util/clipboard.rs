@@ -0,0 +1,20 @@
+/// The system clipboard, opened on first use. The handle is kept because on
+/// X11 and Wayland this process serves what it copied, which is lost once
+/// the handle is dropped.
+#[derive(Default)]
+pub struct Clipboard {
+    inner: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    pub fn copy(&mut self, text: &str) -> Result<(), String> {
+        let clipboard = match self.inner.take() {
+            Some(clipboard) => clipboard,
+            None => arboard::Clipboard::new().map_err(|err| err.to_string())?,
+        };
+        self.inner
+            .insert(clipboard)
+            .set_text(text)
+            .map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,87 @@
+//! The capture countdown drawn over camera frames as big seven-segment
+//! digits, so it shows in both preview backends without a font.
+
+use image::{Rgb, RgbImage};
+
+use super::measure::fill;
+
+const DIGIT_COLOR: Rgb<u8> = Rgb([255, 210, 0]);
+const BACKDROP_COLOR: Rgb<u8> = Rgb([0, 0, 0]);
+
+/// Lit segments per digit; bits 0..=6 are a (top), b (top right),
+/// c (bottom right), d (bottom), e (bottom left), f (top left), g (middle).
+const SEGMENTS: [u8; 10] = [0x3f, 0x06, 0x5b, 0x4f, 0x66, 0x6d, 0x7d, 0x07, 0x7f, 0x6f];
+
+/// Draws `secs` centred on the frame, a third of its height tall, on a
+/// black backdrop.
+pub fn draw_countdown(frame: &mut RgbImage, secs: u32) {
+    let (width, height) = frame.dimensions();
+    let digit_h = height / 3;
+    if digit_h < 10 {
+        return;
+    }
+    let digit_w = digit_h / 2;
+    let thickness = (digit_h / 8).max(2);
+    let gap = thickness * 2;
+    let digits: Vec<u8> = secs.to_string().bytes().map(|b| b - b'0').collect();
+    let count = digits.len() as u32;
+    let total_w = count * digit_w + (count - 1) * gap;
+    let left = width.saturating_sub(total_w) / 2;
+    let top = (height - digit_h) / 2;
+    fill(
+        frame,
+        left.saturating_sub(gap),
+        top.saturating_sub(gap),
+        total_w + 2 * gap,
+        digit_h + 2 * gap,
+        BACKDROP_COLOR,
+    );
+    for (idx, digit) in digits.iter().enumerate() {
+        let x = left + idx as u32 * (digit_w + gap);
+        draw_digit(
+            frame,
+            x,
+            top,
+            digit_w,
+            digit_h,
+            thickness,
+            SEGMENTS[*digit as usize],
+        );
+    }
+}
+
+fn draw_digit(frame: &mut RgbImage, x: u32, y: u32, w: u32, h: u32, t: u32, lit: u8) {
+    let half = h / 2;
+    let segments = [
+        (x, y, w, t),
+        (x + w - t, y, t, half),
+        (x + w - t, y + half, t, h - half),
+        (x, y + h - t, w, t),
+        (x, y + half, t, h - half),
+        (x, y, t, half),
+        (x, y + half - t / 2, w, t),
+    ];
+    for (bit, (sx, sy, sw, sh)) in segments.into_iter().enumerate() {
+        if lit & (1 << bit) != 0 {
+            fill(frame, sx, sy, sw, sh, DIGIT_COLOR);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_lights_only_the_right_segments() {
+        let gray = Rgb([90, 90, 90]);
+        let mut frame = RgbImage::from_pixel(120, 90, gray);
+        draw_countdown(&mut frame, 1);
+        // 30 px tall, 15 px wide digit at x = 52..67, y = 30..60.
+        assert_eq!(*frame.get_pixel(65, 35), DIGIT_COLOR, "upper right");
+        assert_eq!(*frame.get_pixel(65, 55), DIGIT_COLOR, "lower right");
+        assert_eq!(*frame.get_pixel(53, 35), BACKDROP_COLOR, "no left side");
+        assert_eq!(*frame.get_pixel(59, 31), BACKDROP_COLOR, "no top");
+        assert_eq!(*frame.get_pixel(5, 5), gray, "outside the backdrop");
+    }
+}
@@ -0,0 +1,51 @@
+//! Downscale-first helpers for the live previews: a 4K webcam frame is shrunk
+//! to the size it is shown at before any per-pixel conversion.
+
+use image::{RgbImage, imageops};
+
+/// `frame` shrunk to fit within `max` (width, height) with its aspect ratio
+/// kept, plus the scale that was applied. Frames that already fit are copied.
+pub fn fit_within(frame: &RgbImage, max: (u32, u32)) -> (RgbImage, f64) {
+    let (width, height) = frame.dimensions();
+    let scale = (max.0.max(1) as f64 / width.max(1) as f64)
+        .min(max.1.max(1) as f64 / height.max(1) as f64)
+        .min(1.0);
+    if scale >= 1.0 {
+        return (frame.clone(), 1.0);
+    }
+    let target_w = ((width as f64 * scale).round() as u32).max(1);
+    let target_h = ((height as f64 * scale).round() as u32).max(1);
+    (imageops::thumbnail(frame, target_w, target_h), scale)
+}
+
+/// Packs `frame` into `buffer` as `0xFFRRGGBB`, reusing its allocation.
+pub fn fill_argb(frame: &RgbImage, buffer: &mut Vec<u32>) {
+    let len = frame.width() as usize * frame.height() as usize;
+    buffer.resize(len, 0);
+    for (dst, px) in buffer.iter_mut().zip(frame.as_raw().chunks_exact(3)) {
+        *dst = 0xFF00_0000 | ((px[0] as u32) << 16) | ((px[1] as u32) << 8) | (px[2] as u32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn fits_the_bound_and_keeps_the_aspect_ratio() {
+        let frame = RgbImage::from_pixel(3840, 2160, Rgb([10, 20, 30]));
+        let (small, scale) = fit_within(&frame, (960, 960));
+        assert_eq!(small.dimensions(), (960, 540));
+        assert!((scale - 0.25).abs() < 1e-9);
+
+        let (same, scale) = fit_within(&small, (1920, 1080));
+        assert_eq!(same.dimensions(), (960, 540));
+        assert_eq!(scale, 1.0);
+
+        let mut buffer = vec![0; 4];
+        fill_argb(&same, &mut buffer);
+        assert_eq!(buffer.len(), 960 * 540);
+        assert_eq!(buffer[0], 0xFF0A_141E);
+    }
+}
@@ -1,24 +1,61 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
-use anyhow::Result;
 use chrono::Local;
+use talaria_core::config::CaptureFiles;
 
-pub fn timestamped_capture_path(dir: &Path, ext: &str) -> Result<PathBuf> {
-    let ext = ext.trim_start_matches('.');
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S_%3f");
-    let filename = format!("frame_{timestamp}.{ext}");
-    Ok(dir.join(filename))
+/// Where a session's captures go and what they are named.
+#[derive(Debug, Clone)]
+pub struct CaptureTarget {
+    pub dir: PathBuf,
+    pub sku: String,
+    pub session_id: String,
+    pub files: CaptureFiles,
+    /// Gray card gains for frames saved from the stream; tethered stills are
+    /// kept as the camera sent them.
+    pub white_balance: Option<[f64; 3]>,
+}
+
+impl CaptureTarget {
+    /// The next free path in `dir` for a frame numbered after `seq`, which
+    /// is bumped past names already taken.
+    pub fn next_path(&self, seq: &mut u32) -> PathBuf {
+        loop {
+            *seq += 1;
+            let timestamp = Local::now().format("%Y%m%d_%H%M%S_%3f").to_string();
+            let name = self
+                .files
+                .file_name(&self.sku, &self.session_id, *seq, &timestamp);
+            let path = self.dir.join(name);
+            if !path.exists() {
+                return path;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use talaria_core::config::CaptureFormat;
 
     #[test]
-    fn timestamped_path_in_captures_dir() {
-        let path = timestamped_capture_path(Path::new("./captures"), "jpg").expect("path");
-        let path_str = path.to_string_lossy();
-        assert!(path_str.contains("captures"));
-        assert!(path_str.ends_with(".jpg"));
+    fn next_path_skips_names_already_taken() {
+        let dir = std::env::temp_dir().join(format!("talaria-fs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let target = CaptureTarget {
+            dir: dir.clone(),
+            sku: "SKU-1".into(),
+            session_id: "s1".into(),
+            files: CaptureFiles {
+                template: "{sku}_{seq}.{ext}".into(),
+                format: CaptureFormat::Png,
+            },
+            white_balance: None,
+        };
+        std::fs::write(dir.join("SKU-1_001.png"), b"taken").expect("write");
+        let mut seq = 0;
+        assert_eq!(target.next_path(&mut seq), dir.join("SKU-1_002.png"));
+        assert_eq!(seq, 2);
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
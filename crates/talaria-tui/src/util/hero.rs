@@ -0,0 +1,204 @@
+//! Hero suggestion for capture sessions: frames are scored on sharpness, how
+//! much of the frame the subject fills and how centred it sits, and the best
+//! one is offered as the hero until the user picks one.
+
+use image::{GrayImage, imageops};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Sessions with fewer frames than this get no suggestion; picking among a
+/// handful is quick enough by eye.
+pub const MIN_FRAMES: usize = 4;
+/// Longest side, in pixels, the subject is found at.
+const SUBJECT_SIDE: u32 = 96;
+/// Luma distance from the backdrop at which a pixel counts as subject.
+const SUBJECT_DELTA: u8 = 28;
+/// Subject share of the frame that earns the full size score; filling more
+/// usually means the item is cropped.
+const FILL_TARGET: f64 = 0.45;
+const SHARPNESS_WEIGHT: f64 = 0.5;
+const FILL_WEIGHT: f64 = 0.3;
+const CENTERING_WEIGHT: f64 = 0.2;
+
+/// Where the subject sits in a frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameSubject {
+    /// Share of the frame covered by the largest region that differs from
+    /// the backdrop.
+    pub fill: f64,
+    /// 1 when that region's centroid is the frame centre, 0 in a corner.
+    pub centering: f64,
+}
+
+impl FrameSubject {
+    /// Finds the subject as the largest 4-connected region whose luma is
+    /// [`SUBJECT_DELTA`] away from the backdrop, taken as the median of the
+    /// border pixels.
+    pub fn of_gray(gray: &GrayImage) -> Self {
+        let (width, height) = gray.dimensions();
+        let scale = (SUBJECT_SIDE as f64 / width.max(height).max(1) as f64).min(1.0);
+        let small = imageops::thumbnail(
+            gray,
+            ((width as f64 * scale) as u32).max(1),
+            ((height as f64 * scale) as u32).max(1),
+        );
+        let (w, h) = (small.width() as usize, small.height() as usize);
+        let luma = small.as_raw();
+
+        let mut border: Vec<u8> = (0..w * h)
+            .filter(|idx| {
+                let (x, y) = (idx % w, idx / w);
+                x == 0 || y == 0 || x == w - 1 || y == h - 1
+            })
+            .map(|idx| luma[idx])
+            .collect();
+        border.sort_unstable();
+        let backdrop = border[border.len() / 2];
+        let mask: Vec<bool> = luma
+            .iter()
+            .map(|&v| v.abs_diff(backdrop) > SUBJECT_DELTA)
+            .collect();
+
+        let mut seen = vec![false; w * h];
+        let mut best: Option<(usize, f64, f64)> = None;
+        let mut stack = Vec::new();
+        for start in 0..w * h {
+            if !mask[start] || seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            stack.push(start);
+            let (mut area, mut sum_x, mut sum_y) = (0usize, 0.0, 0.0);
+            while let Some(idx) = stack.pop() {
+                let (x, y) = (idx % w, idx / w);
+                area += 1;
+                sum_x += x as f64;
+                sum_y += y as f64;
+                let neighbours = [
+                    (x > 0).then(|| idx - 1),
+                    (x + 1 < w).then(|| idx + 1),
+                    (y > 0).then(|| idx - w),
+                    (y + 1 < h).then(|| idx + w),
+                ];
+                for next in neighbours.into_iter().flatten() {
+                    if mask[next] && !seen[next] {
+                        seen[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+            if best.is_none_or(|(largest, ..)| area > largest) {
+                best = Some((area, sum_x / area as f64, sum_y / area as f64));
+            }
+        }
+
+        let Some((area, cx, cy)) = best else {
+            return Self::default();
+        };
+        let (half_w, half_h) = ((w - 1) as f64 / 2.0, (h - 1) as f64 / 2.0);
+        let off_centre = (cx - half_w).hypot(cy - half_h) / half_w.hypot(half_h).max(1.0);
+        Self {
+            fill: area as f64 / (w * h) as f64,
+            centering: (1.0 - off_centre).clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn of_file(path: &Path) -> anyhow::Result<Self> {
+        Ok(Self::of_gray(&image::open(path)?.to_luma8()))
+    }
+}
+
+/// A frame as seen by [`suggest`].
+#[derive(Debug, Clone, Copy)]
+pub struct HeroCandidate<'a> {
+    pub rel_path: &'a str,
+    pub sharpness: Option<f64>,
+    pub subject: Option<FrameSubject>,
+}
+
+/// Scores a frame in `0..=1`; `sharpness` is relative to the session's
+/// sharpest frame.
+pub fn score(sharpness: f64, subject: FrameSubject) -> f64 {
+    SHARPNESS_WEIGHT * sharpness.clamp(0.0, 1.0)
+        + FILL_WEIGHT * (subject.fill / FILL_TARGET).min(1.0)
+        + CENTERING_WEIGHT * subject.centering
+}
+
+/// The best-scoring candidate; earlier frames win ties.
+pub fn suggest<'a>(candidates: &[HeroCandidate<'a>]) -> Option<&'a str> {
+    let sharpest = candidates
+        .iter()
+        .filter_map(|c| c.sharpness)
+        .fold(0.0_f64, f64::max);
+    let mut best: Option<(f64, &str)> = None;
+    for candidate in candidates {
+        let sharpness = match candidate.sharpness {
+            Some(s) if sharpest > 0.0 => s / sharpest,
+            _ => 0.0,
+        };
+        let value = score(sharpness, candidate.subject.unwrap_or_default());
+        if best.is_none_or(|(top, _)| value > top) {
+            best = Some((value, candidate.rel_path));
+        }
+    }
+    best.map(|(_, rel_path)| rel_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    fn frame_with_square(left: u32, top: u32, side: u32) -> GrayImage {
+        let mut frame = GrayImage::from_pixel(200, 150, Luma([235]));
+        for y in top..top + side {
+            for x in left..left + side {
+                frame.put_pixel(x, y, Luma([60]));
+            }
+        }
+        frame
+    }
+
+    #[test]
+    fn subject_fill_and_centering() {
+        let centred = FrameSubject::of_gray(&frame_with_square(55, 30, 90));
+        assert!((centred.fill - 0.27).abs() < 0.03, "{centred:?}");
+        assert!(centred.centering > 0.95, "{centred:?}");
+
+        let cornered = FrameSubject::of_gray(&frame_with_square(5, 5, 40));
+        assert!(cornered.fill < centred.fill, "{cornered:?}");
+        assert!(cornered.centering < 0.5, "{cornered:?}");
+
+        let empty = FrameSubject::of_gray(&GrayImage::from_pixel(200, 150, Luma([235])));
+        assert_eq!(empty, FrameSubject::default());
+    }
+
+    #[test]
+    fn suggests_sharp_well_framed_frame() {
+        let subject = |fill, centering| Some(FrameSubject { fill, centering });
+        let candidates = [
+            HeroCandidate {
+                rel_path: "blurry.jpg",
+                sharpness: Some(20.0),
+                subject: subject(0.4, 0.9),
+            },
+            HeroCandidate {
+                rel_path: "hero.jpg",
+                sharpness: Some(90.0),
+                subject: subject(0.4, 0.9),
+            },
+            HeroCandidate {
+                rel_path: "cropped.jpg",
+                sharpness: Some(100.0),
+                subject: subject(0.05, 0.2),
+            },
+            HeroCandidate {
+                rel_path: "unscored.jpg",
+                sharpness: None,
+                subject: None,
+            },
+        ];
+        assert_eq!(suggest(&candidates), Some("hero.jpg"));
+        assert_eq!(suggest(&[]), None);
+    }
+}
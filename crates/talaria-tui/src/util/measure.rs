@@ -0,0 +1,110 @@
+use image::{Rgb, RgbImage};
+
+const BOX_COLOR: Rgb<u8> = Rgb([255, 210, 0]);
+const TICK_COLOR: Rgb<u8> = Rgb([0, 220, 255]);
+
+/// What the preview draws while measuring: a ruler along the top and left
+/// edges (a tick every unit, a short one every half) and a box of the
+/// measured size centred in the frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasureOverlay {
+    pub pixels_per_unit: f64,
+    /// Box size in units.
+    pub width: f64,
+    pub height: f64,
+}
+
+impl MeasureOverlay {
+    /// The same overlay for a frame resized by `scale`.
+    pub fn scaled(self, scale: f64) -> Self {
+        Self {
+            pixels_per_unit: self.pixels_per_unit * scale,
+            ..self
+        }
+    }
+}
+
+pub fn draw_overlay(frame: &mut RgbImage, overlay: &MeasureOverlay) {
+    let (width, height) = frame.dimensions();
+    if width == 0 || height == 0 || overlay.pixels_per_unit <= 0.0 {
+        return;
+    }
+    let thickness = (width.min(height) / 400).max(1);
+    let tick = (width.min(height) / 30).max(4);
+
+    let half = overlay.pixels_per_unit / 2.0;
+    let mut idx = 1;
+    loop {
+        let pos = (idx as f64 * half).round() as u32;
+        if pos >= width && pos >= height {
+            break;
+        }
+        let len = if idx % 2 == 0 { tick } else { tick / 2 };
+        fill(frame, pos, 0, thickness, len, TICK_COLOR);
+        fill(frame, 0, pos, len, thickness, TICK_COLOR);
+        idx += 1;
+    }
+
+    let box_w = (overlay.width * overlay.pixels_per_unit).round() as u32;
+    let box_h = (overlay.height * overlay.pixels_per_unit).round() as u32;
+    let left = width.saturating_sub(box_w) / 2;
+    let top = height.saturating_sub(box_h) / 2;
+    let right = (left + box_w).min(width - 1);
+    let bottom = (top + box_h).min(height - 1);
+    fill(frame, left, top, right - left + 1, thickness, BOX_COLOR);
+    fill(
+        frame,
+        left,
+        bottom.saturating_sub(thickness - 1),
+        right - left + 1,
+        thickness,
+        BOX_COLOR,
+    );
+    fill(frame, left, top, thickness, bottom - top + 1, BOX_COLOR);
+    fill(
+        frame,
+        right.saturating_sub(thickness - 1),
+        top,
+        thickness,
+        bottom - top + 1,
+        BOX_COLOR,
+    );
+}
+
+/// Fills a rectangle, clipped to the frame.
+pub(crate) fn fill(frame: &mut RgbImage, x: u32, y: u32, w: u32, h: u32, color: Rgb<u8>) {
+    let (width, height) = frame.dimensions();
+    for py in y..(y + h).min(height) {
+        for px in x..(x + w).min(width) {
+            frame.put_pixel(px, py, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_is_centred_and_sized_in_units() {
+        let mut frame = RgbImage::from_pixel(200, 100, Rgb([0, 0, 0]));
+        draw_overlay(
+            &mut frame,
+            &MeasureOverlay {
+                pixels_per_unit: 20.0,
+                width: 4.0,
+                height: 2.5,
+            },
+        );
+        // 80x50 box: edges at x = 60 and 140, y = 25 and 75.
+        for (x, y) in [(60, 50), (140, 50), (100, 25), (100, 75)] {
+            assert_eq!(*frame.get_pixel(x, y), BOX_COLOR, "({x}, {y})");
+        }
+        assert_eq!(*frame.get_pixel(100, 50), Rgb([0, 0, 0]));
+        assert_eq!(*frame.get_pixel(59, 50), Rgb([0, 0, 0]));
+        // Ruler ticks every half unit along the top edge.
+        assert_eq!(*frame.get_pixel(20, 0), TICK_COLOR);
+        assert_eq!(*frame.get_pixel(30, 0), TICK_COLOR);
+        assert_eq!(*frame.get_pixel(25, 0), Rgb([0, 0, 0]));
+    }
+}
@@ -1,3 +1,10 @@
+pub mod assist;
+pub mod clipboard;
+pub mod countdown;
+pub mod downscale;
 pub mod fs;
+pub mod hero;
 pub mod log_redirect;
+pub mod measure;
 pub mod sharpness;
+pub mod validate;
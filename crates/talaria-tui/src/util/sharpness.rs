@@ -1,39 +1,45 @@
-use image::RgbImage;
+//! Focus measure for captured frames, in plain Rust on grayscale images.
 
+use image::{GrayImage, RgbImage, imageops};
+
+/// Variance of the 4-neighbour Laplacian of the frame's luma; higher is
+/// sharper.
 pub fn laplacian_variance(frame: &RgbImage) -> anyhow::Result<f64> {
+    Ok(laplacian_variance_gray(&imageops::grayscale(frame)))
+}
+
+/// [`laplacian_variance`] on a copy at most `max_side` px across; cheap
+/// enough to score every buffered frame, but only comparable between frames
+/// of the same size.
+pub fn quick_laplacian_variance(frame: &RgbImage, max_side: u32) -> f64 {
     let (width, height) = frame.dimensions();
+    let scale = (max_side as f64 / width.max(height).max(1) as f64).min(1.0);
+    let small = imageops::thumbnail(
+        frame,
+        ((width as f64 * scale) as u32).max(1),
+        ((height as f64 * scale) as u32).max(1),
+    );
+    laplacian_variance_gray(&imageops::grayscale(&small))
+}
+
+pub fn laplacian_variance_gray(gray: &GrayImage) -> f64 {
+    let (width, height) = gray.dimensions();
     if width < 3 || height < 3 {
-        return Ok(0.0);
+        return 0.0;
     }
 
-    let gray_at = |x: u32, y: u32| -> f64 {
-        let px = frame.get_pixel(x, y);
-        0.299 * px[0] as f64 + 0.587 * px[1] as f64 + 0.114 * px[2] as f64
-    };
-
-    let mut values = Vec::with_capacity(((width - 2) * (height - 2)) as usize);
+    let at = |x: u32, y: u32| gray.get_pixel(x, y)[0] as f64;
+    let (mut sum, mut sum_sq) = (0.0, 0.0);
     for y in 1..(height - 1) {
         for x in 1..(width - 1) {
-            let center = gray_at(x, y);
-            let lap = -4.0 * center
-                + gray_at(x - 1, y)
-                + gray_at(x + 1, y)
-                + gray_at(x, y - 1)
-                + gray_at(x, y + 1);
-            values.push(lap);
+            let lap = -4.0 * at(x, y) + at(x - 1, y) + at(x + 1, y) + at(x, y - 1) + at(x, y + 1);
+            sum += lap;
+            sum_sq += lap * lap;
         }
     }
-
-    let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
-    let variance = values
-        .iter()
-        .map(|v| {
-            let d = v - mean;
-            d * d
-        })
-        .sum::<f64>()
-        / values.len().max(1) as f64;
-    Ok(variance)
+    let n = ((width - 2) * (height - 2)) as f64;
+    let mean = sum / n;
+    (sum_sq / n - mean * mean).max(0.0)
 }
 
 #[cfg(test)]
@@ -50,10 +56,12 @@ mod tests {
             }
         }
 
+        // The same square with its edges ramped over 10 px.
         let mut blurred = RgbImage::from_pixel(120, 120, Rgb([0, 0, 0]));
-        for y in 20..100 {
-            for x in 20..100 {
-                let value = if (x + y) % 2 == 0 { 200 } else { 55 };
+        for y in 10..110u32 {
+            for x in 10..110u32 {
+                let edge = x.min(y).min(119 - x).min(119 - y) - 10;
+                let value = (edge.min(10) * 255 / 10) as u8;
                 blurred.put_pixel(x, y, Rgb([value, value, value]));
             }
         }
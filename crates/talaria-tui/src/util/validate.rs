@@ -0,0 +1,94 @@
+//! Checks applied to Settings values before they are written to the config file.
+//! Errors are short, user-facing sentences shown as toasts.
+
+use reqwest::Url;
+use talaria_core::models::MarketplaceId;
+
+/// An absolute http(s) URL with a host; returned without a trailing slash.
+pub fn http_url(label: &str, value: &str) -> Result<String, String> {
+    let url = Url::parse(value).map_err(|err| format!("{label} is not a valid URL: {err}."))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("{label} must start with http:// or https://."));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(format!("{label} needs a host name."));
+    }
+    Ok(value.trim_end_matches('/').to_string())
+}
+
+/// Keys, bucket names and similar tokens: ASCII letters, digits, `-`, `_`, `.`.
+pub fn identifier(label: &str, value: &str) -> Result<String, String> {
+    if value.len() > 64 {
+        return Err(format!("{label} must be at most 64 characters."));
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!(
+            "{label} may only contain letters, digits, '-', '_' and '.'."
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// eBay business policy ids are numeric.
+pub fn policy_id(label: &str, value: &str) -> Result<String, String> {
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("{label} must be a numeric eBay policy id."));
+    }
+    Ok(value.to_string())
+}
+
+/// A marketplace Hermes knows about, normalised to upper case (`EBAY_US`).
+pub fn marketplace(value: &str) -> Result<String, String> {
+    let upper = value.to_ascii_uppercase();
+    if !MarketplaceId::from(upper.as_str()).is_known() {
+        return Err(format!(
+            "Unknown marketplace '{value}' (e.g. EBAY_US, EBAY_UK, EBAY_DE)."
+        ));
+    }
+    Ok(upper)
+}
+
+/// Relative object prefix inside the bucket: no leading `/`, no `..` segments.
+pub fn upload_prefix(value: &str) -> Result<String, String> {
+    let trimmed = value.trim_matches('/');
+    if trimmed
+        .split('/')
+        .any(|segment| segment.is_empty() || segment == "..")
+    {
+        return Err("Upload prefix must be a relative path like talaria/images.".to_string());
+    }
+    for segment in trimmed.split('/') {
+        identifier("Upload prefix", segment)?;
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_need_http_scheme_and_host() {
+        assert_eq!(
+            http_url("Base URL", "https://api.example.com/").unwrap(),
+            "https://api.example.com"
+        );
+        assert!(http_url("Base URL", "ftp://example.com").is_err());
+        assert!(http_url("Base URL", "example.com").is_err());
+    }
+
+    #[test]
+    fn ids_and_prefixes_reject_unsafe_values() {
+        assert!(identifier("Bucket", "images-bucket").is_ok());
+        assert!(identifier("Bucket", "my bucket").is_err());
+        assert!(policy_id("Payment policy", "6196932000").is_ok());
+        assert!(policy_id("Payment policy", "abc").is_err());
+        assert_eq!(upload_prefix("/talaria/img/").unwrap(), "talaria/img");
+        assert!(upload_prefix("talaria/../etc").is_err());
+        assert_eq!(marketplace("ebay_de").unwrap(), "EBAY_DE");
+        assert!(marketplace("AMAZON_US").is_err());
+    }
+}
@@ -1,16 +1,20 @@
 use std::thread;
 
 use anyhow::Result;
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Receiver;
 use tokio::runtime::Runtime;
 
+use crate::event_bus::EventSender;
 use crate::types::{AccountCommand, AccountEvent, AppEvent, CreditsSnapshot};
 use talaria_core::client::HermesClient;
+use talaria_core::models::UsageSummary;
+use talaria_core::stats::DayRange;
+use talaria_core::usage_history::{BURN_DOWN_DAYS, UsageDay, UsageHistory};
 
 pub fn spawn_account_worker(
-    hermes: Option<HermesClient>,
+    mut hermes: Option<HermesClient>,
     cmd_rx: Receiver<AccountCommand>,
-    event_tx: Sender<AppEvent>,
+    event_tx: EventSender,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
         let rt = Runtime::new().expect("tokio runtime");
@@ -21,9 +25,14 @@ pub fn spawn_account_worker(
                 Err(_) => return,
             };
 
-            if matches!(cmd, AccountCommand::Shutdown) {
-                return;
-            }
+            let cmd = match cmd {
+                AccountCommand::Shutdown => return,
+                AccountCommand::SetClient(client) => {
+                    hermes = client.map(|c| *c);
+                    continue;
+                }
+                cmd => cmd,
+            };
 
             let res: Result<()> = (|| match cmd {
                 AccountCommand::FetchCredits => {
@@ -51,12 +60,13 @@ pub fn spawn_account_worker(
                         listings_run: summary.counters.listings_run,
                         window_from: summary.window_from.map(|d| d.to_rfc3339()),
                         window_to: summary.window_to.map(|d| d.to_rfc3339()),
+                        burn_down: record_usage(&rows, &summary.org_id),
                     };
-                    let _ = event_tx
-                        .send(AppEvent::Account(AccountEvent::CreditsUpdated(snapshot)));
+                    let _ =
+                        event_tx.send(AppEvent::Account(AccountEvent::CreditsUpdated(snapshot)));
                     Ok(())
                 }
-                AccountCommand::Shutdown => Ok(()),
+                AccountCommand::SetClient(_) | AccountCommand::Shutdown => Ok(()),
             })();
 
             if let Err(err) = res {
@@ -67,3 +77,16 @@ pub fn spawn_account_worker(
         }
     })
 }
+
+/// Adds `rows` to the local usage history and reads back `org_id`'s
+/// burn-down. The history is a nicety; failing to keep it only logs.
+fn record_usage(rows: &[UsageSummary], org_id: &str) -> Vec<UsageDay> {
+    let history = UsageHistory::open_default().and_then(|history| {
+        history.record(rows, chrono::Utc::now())?;
+        history.days(Some(org_id), DayRange::last(BURN_DOWN_DAYS))
+    });
+    history.unwrap_or_else(|err| {
+        tracing::warn!(error = %err, "usage history not updated");
+        Vec::new()
+    })
+}
@@ -0,0 +1,96 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use tokio::runtime::Runtime;
+
+use crate::event_bus::EventSender;
+use crate::types::{AppEvent, ConnectionState, HealthCommand, HealthReport};
+use talaria_core::client::HermesClient;
+use talaria_core::error::Error;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+/// Check more often while offline so paused work resumes promptly.
+const OFFLINE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Slower answers than this mark the connection degraded.
+const DEGRADED_LATENCY: Duration = Duration::from_secs(2);
+
+/// Pings Hermes `health()` on an interval and reports every result; the app
+/// works out the transitions.
+pub fn spawn_health_worker(
+    mut hermes: Option<HermesClient>,
+    cmd_rx: Receiver<HealthCommand>,
+    event_tx: EventSender,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let rt = Runtime::new().expect("tokio runtime");
+        let mut next_check = Instant::now();
+
+        loop {
+            let wait = next_check.saturating_duration_since(Instant::now());
+            match cmd_rx.recv_timeout(wait) {
+                Ok(HealthCommand::Shutdown) | Err(RecvTimeoutError::Disconnected) => return,
+                Ok(HealthCommand::SetClient(client)) => {
+                    hermes = client.map(|c| *c);
+                    next_check = Instant::now();
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+
+            let report = match hermes.as_ref() {
+                Some(hermes) => check(&rt, hermes),
+                None => HealthReport::default(),
+            };
+            next_check = Instant::now()
+                + if report.state == ConnectionState::Offline {
+                    OFFLINE_CHECK_INTERVAL
+                } else {
+                    CHECK_INTERVAL
+                };
+            if event_tx.send(AppEvent::Health(report)).is_err() {
+                return;
+            }
+        }
+    })
+}
+
+fn check(rt: &Runtime, hermes: &HermesClient) -> HealthReport {
+    let started = Instant::now();
+    let res = rt.block_on(tokio::time::timeout(CHECK_TIMEOUT, hermes.health()));
+    let latency = started.elapsed();
+    match res {
+        Ok(Ok(health)) if health.status != "ok" => HealthReport {
+            state: ConnectionState::Degraded,
+            latency: Some(latency),
+            detail: Some(format!("status {}", health.status)),
+        },
+        Ok(Ok(_)) if latency > DEGRADED_LATENCY => HealthReport {
+            state: ConnectionState::Degraded,
+            latency: Some(latency),
+            detail: Some("slow responses".to_string()),
+        },
+        Ok(Ok(_)) => HealthReport {
+            state: ConnectionState::Online,
+            latency: Some(latency),
+            detail: None,
+        },
+        // Hermes answered, just not happily.
+        Ok(Err(err @ Error::Api { .. })) => HealthReport {
+            state: ConnectionState::Degraded,
+            latency: Some(latency),
+            detail: Some(err.to_string()),
+        },
+        Ok(Err(err)) => HealthReport {
+            state: ConnectionState::Offline,
+            latency: None,
+            detail: Some(err.to_string()),
+        },
+        Err(_) => HealthReport {
+            state: ConnectionState::Offline,
+            latency: None,
+            detail: Some(format!("no answer within {}s", CHECK_TIMEOUT.as_secs())),
+        },
+    }
+}
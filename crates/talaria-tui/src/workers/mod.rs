@@ -1,2 +1,4 @@
 pub mod account;
+pub mod health;
+pub mod previews;
 pub mod upload;
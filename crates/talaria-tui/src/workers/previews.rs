@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+use std::thread;
+
+use crossbeam_channel::Receiver;
+
+use crate::event_bus::EventSender;
+use crate::storage::previews;
+use crate::types::{AppEvent, PreviewCacheCommand, PreviewEvent};
+
+/// Fills the preview cache (see [`crate::storage::previews`]) for the image
+/// the user selected. Requests queued behind the current one are collapsed
+/// to the newest, so scrolling through frames only decodes where it stops.
+pub fn spawn_preview_cache_worker(
+    base_dir: PathBuf,
+    cmd_rx: Receiver<PreviewCacheCommand>,
+    event_tx: EventSender,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        while let Ok(cmd) = cmd_rx.recv() {
+            let mut source = match cmd {
+                PreviewCacheCommand::Prepare(source) => source,
+                PreviewCacheCommand::Shutdown => return,
+            };
+            for cmd in cmd_rx.try_iter() {
+                match cmd {
+                    PreviewCacheCommand::Prepare(next) => source = next,
+                    PreviewCacheCommand::Shutdown => return,
+                }
+            }
+
+            // Falling back to the source keeps previews working when the
+            // cache cannot be written (read-only or full disk).
+            let cached = match previews::cached_preview(&base_dir, &source) {
+                Ok(cached) => cached,
+                Err(err) => {
+                    tracing::warn!(
+                        source = %source.display(),
+                        error = %format!("{err:#}"),
+                        "preview not cached"
+                    );
+                    source.clone()
+                }
+            };
+            if event_tx
+                .send(AppEvent::Preview(PreviewEvent::Ready { source, cached }))
+                .is_err()
+            {
+                return;
+            }
+        }
+    })
+}
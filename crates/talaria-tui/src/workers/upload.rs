@@ -1,36 +1,76 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::thread;
 
 use chrono::Local;
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Receiver;
 use reqwest::header::HeaderMap;
 use tokio::runtime::Runtime;
 
+use crate::event_bus::EventSender;
+use crate::jobs::{JobKey, JobKind, RunningJobs, is_canceled};
+use crate::shutdown::ShutdownToken;
 use crate::storage;
 use crate::types::{ActivityEntry, AppEvent, JobStatus, Severity, UploadCommand, UploadJob};
+use talaria_core::integrity::{ServedCheck, verify_served};
+use talaria_core::metrics;
+use talaria_core::upload_journal::{UploadJournal, abort_stale};
+
+/// Every this many uploads, the served copy is downloaded and hashed again
+/// rather than only checked by its headers.
+const REHASH_EVERY: usize = 10;
 
 pub fn spawn_upload_worker(
     captures_dir: PathBuf,
-    hermes: Option<talaria_core::client::HermesClient>,
+    mut hermes: Option<talaria_core::client::HermesClient>,
     cmd_rx: Receiver<UploadCommand>,
-    event_tx: Sender<AppEvent>,
+    event_tx: EventSender,
+    shutdown: ShutdownToken,
+    running: RunningJobs,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
         let rt = Runtime::new().expect("tokio runtime");
         let mut jobs: HashMap<String, UploadJob> = HashMap::new();
-        let upload_http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(60))
-            .build()
-            .expect("upload http client");
+        let mut upload_http = upload_client(hermes.as_ref());
+        let mut online = true;
+        let mut attempts = 0usize;
+        // Products held while offline, and those released to run again.
+        let mut paused: VecDeque<String> = VecDeque::new();
+        let mut resumed: VecDeque<String> = VecDeque::new();
+        if let Some(hermes) = hermes
+            .as_ref()
+            .filter(|h| h.has_api_key() && !h.is_simulated())
+        {
+            abort_stale_uploads(&rt, hermes, &event_tx);
+        }
 
         loop {
-            let cmd = match cmd_rx.recv() {
-                Ok(cmd) => cmd,
-                Err(_) => return,
+            if shutdown.is_cancelled() {
+                return;
+            }
+            let cmd = match resumed.pop_front() {
+                Some(product_id) => UploadCommand::UploadProduct { product_id },
+                None => match cmd_rx.recv() {
+                    Ok(cmd) => cmd,
+                    Err(_) => return,
+                },
             };
             match cmd {
+                UploadCommand::UploadProduct { product_id } if !online => {
+                    if !paused.contains(&product_id) {
+                        let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                            at: Local::now(),
+                            severity: Severity::Info,
+                            message: format!(
+                                "Hermes offline; upload of {product_id} waits until it is back."
+                            ),
+                        }));
+                        paused.push_back(product_id);
+                    }
+                }
                 UploadCommand::UploadProduct { product_id } => {
+                    // Hermes client events inside carry the product id.
+                    let _span = tracing::info_span!("upload", product_id = %product_id).entered();
                     if hermes.is_none() {
                         let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
                             at: Local::now(),
@@ -41,7 +81,7 @@ pub fn spawn_upload_worker(
                         continue;
                     }
                     let hermes = hermes.clone().unwrap();
-                    if !hermes.has_api_key() {
+                    if !hermes.has_api_key() && !hermes.is_simulated() {
                         let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
                             at: Local::now(),
                             severity: Severity::Warning,
@@ -82,6 +122,9 @@ pub fn spawn_upload_worker(
                         continue;
                     }
 
+                    // Cancelling stops at the file in flight; files already
+                    // uploaded keep their URLs.
+                    let guard = running.register(JobKey::new(JobKind::Upload, &product_id));
                     for rel in targets {
                         let abs = storage::product_dir(&captures_dir, &product_id).join(&rel);
                         if !abs.exists() {
@@ -106,16 +149,35 @@ pub fn spawn_upload_worker(
                         };
                         jobs.insert(id.clone(), job.clone());
                         let _ = event_tx.send(AppEvent::UploadJob(job.clone()));
+                        tracing::info!(job_id = %id, file = %rel, "upload started");
 
-                        let result = rt.block_on(upload_one(
-                            &hermes,
-                            &upload_http,
-                            &product_id,
-                            product.hero_rel_path.as_deref(),
-                            &rel,
-                            &abs,
-                        ));
+                        let rehash = attempts.is_multiple_of(REHASH_EVERY);
+                        attempts += 1;
+                        // Exit or a cancel abandons an upload mid-flight; the
+                        // file keeps no URL and goes again next time.
+                        let result = guard.run(
+                            &rt,
+                            upload_one(&hermes, &upload_http, &product, &rel, &abs, rehash),
+                        );
+                        if shutdown.is_cancelled() {
+                            return;
+                        }
                         match result {
+                            Err(err) if is_canceled(&err) => {
+                                tracing::info!(job_id = %id, file = %rel, "upload canceled");
+                                job.status = JobStatus::Canceled;
+                                jobs.insert(id.clone(), job.clone());
+                                let _ = event_tx.send(AppEvent::UploadJob(job));
+                                let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                                    at: Local::now(),
+                                    severity: Severity::Warning,
+                                    message: format!(
+                                        "Upload canceled at {}; the rest are skipped.",
+                                        short_name(&rel)
+                                    ),
+                                }));
+                                break;
+                            }
                             Ok(uploaded) => {
                                 if rel == product.hero_rel_path.clone().unwrap_or_default() {
                                     let _ = storage::set_product_hero_uploaded_url(
@@ -133,6 +195,13 @@ pub fn spawn_upload_worker(
                                         Some(uploaded.media_id.clone()),
                                     );
                                 }
+                                tracing::info!(
+                                    job_id = %id,
+                                    file = %rel,
+                                    media_id = %uploaded.media_id,
+                                    url = %uploaded.url,
+                                    "upload completed"
+                                );
                                 job.status = JobStatus::Completed;
                                 job.progress = 1.0;
                                 jobs.insert(id.clone(), job.clone());
@@ -148,6 +217,12 @@ pub fn spawn_upload_worker(
                                 }));
                             }
                             Err(err) => {
+                                tracing::warn!(
+                                    job_id = %id,
+                                    file = %rel,
+                                    error = %err,
+                                    "upload failed"
+                                );
                                 job.status = JobStatus::Failed;
                                 job.last_error = Some(err.to_string());
                                 jobs.insert(id.clone(), job.clone());
@@ -160,10 +235,38 @@ pub fn spawn_upload_worker(
                             }
                         }
                     }
+                    match storage::load_product(&captures_dir, &product_id) {
+                        Ok(product) => {
+                            if let Err(err) = rt.block_on(sync_media_ranks(&hermes, &product)) {
+                                let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                                    at: Local::now(),
+                                    severity: Severity::Warning,
+                                    message: format!("Image order not synced to Hermes: {err}"),
+                                }));
+                            }
+                        }
+                        Err(err) => {
+                            let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+                                at: Local::now(),
+                                severity: Severity::Warning,
+                                message: format!("Image order not synced to Hermes: {err}"),
+                            }));
+                        }
+                    }
                     let _ = event_tx.send(AppEvent::UploadFinished {
                         product_id: product_id.clone(),
                     });
                 }
+                UploadCommand::SetClient(client) => {
+                    hermes = client.map(|c| *c);
+                    upload_http = upload_client(hermes.as_ref());
+                }
+                UploadCommand::SetOnline(now_online) => {
+                    online = now_online;
+                    if online {
+                        resumed.extend(paused.drain(..));
+                    }
+                }
                 UploadCommand::Shutdown => return,
             }
         }
@@ -185,6 +288,27 @@ fn file_id(rel: &str) -> String {
         .collect()
 }
 
+/// Client for the storage PUTs, behind the same proxy and CAs as Hermes.
+fn upload_client(hermes: Option<&talaria_core::client::HermesClient>) -> reqwest::Client {
+    hermes
+        .and_then(|h| h.network().client_builder().ok())
+        .unwrap_or_default()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .expect("upload http client")
+}
+
+/// The hash `rel` was committed with, when the manifest recorded one.
+fn committed_sha256<'a>(product: &'a storage::ProductManifest, rel: &str) -> Option<&'a str> {
+    match product.images.iter().find(|img| img.rel_path == rel) {
+        Some(img) => img.sha256.as_deref(),
+        None => product
+            .hero_sha256
+            .as_deref()
+            .filter(|_| product.hero_rel_path.as_deref() == Some(rel)),
+    }
+}
+
 #[derive(Debug, Clone)]
 struct UploadedAsset {
     url: String,
@@ -194,10 +318,10 @@ struct UploadedAsset {
 async fn upload_one(
     hermes: &talaria_core::client::HermesClient,
     upload_http: &reqwest::Client,
-    product_id: &str,
-    hero_rel_path: Option<&str>,
+    product: &storage::ProductManifest,
     rel: &str,
     abs: &Path,
+    rehash: bool,
 ) -> anyhow::Result<UploadedAsset> {
     let filename = abs
         .file_name()
@@ -205,9 +329,17 @@ async fn upload_one(
         .unwrap_or("image.jpg")
         .to_string();
     let content_type = guess_content_type(abs);
-    let content_length = std::fs::metadata(abs).ok().map(|m| m.len() as i64);
+    let body = tokio::fs::read(abs).await?;
+    let content_length = Some(body.len() as i64);
+    let sha256 = talaria_core::upload_cache::sha256_hex(&body);
+    if let Some(committed) = committed_sha256(product, rel).filter(|committed| *committed != sha256)
+    {
+        return Err(anyhow::anyhow!(
+            "{rel} changed on disk since it was committed (sha256 {committed}, now {sha256}); not uploaded"
+        ));
+    }
 
-    let purpose = if hero_rel_path.is_some_and(|h| h == rel) {
+    let purpose = if product.hero_rel_path.as_deref() == Some(rel) {
         talaria_core::models::MediaPurpose::Hero
     } else {
         talaria_core::models::MediaPurpose::ProductImage
@@ -218,13 +350,36 @@ async fn upload_one(
         content_type: Some(content_type.to_string()),
         filename,
         metadata: None,
-        product_id: Some(product_id.to_string()),
+        product_id: Some(product.product_id.clone()),
         purpose: Some(purpose),
         session_id: None,
-        sha256: None,
+        sha256: Some(sha256.clone()),
     };
 
     let session = hermes.create_media_upload(&create).await?;
+    if hermes.is_simulated() {
+        let done = hermes
+            .complete_media_upload(&session.upload_id, None)
+            .await?;
+        return Ok(UploadedAsset {
+            url: done.media.url,
+            media_id: done.media.media_id,
+        });
+    }
+    // Left in the journal if this upload never finishes (exit, cancel or a
+    // failed completion); the next startup aborts it on Hermes.
+    let journal = UploadJournal::open_default().ok();
+    if let Some(Err(err)) = journal
+        .as_ref()
+        .map(|journal| journal.record(&session, &create))
+    {
+        tracing::warn!(upload_id = %session.upload_id, error = %err, "upload not journaled");
+    }
+    let finish = |upload_id: &str| {
+        if let Some(Err(err)) = journal.as_ref().map(|journal| journal.finish(upload_id)) {
+            tracing::warn!(upload_id, error = %err, "upload journal not updated");
+        }
+    };
     let mut headers = HeaderMap::new();
     if let Some(h) = &session.headers {
         for (k, v) in h {
@@ -239,7 +394,7 @@ async fn upload_one(
         reqwest::header::HeaderValue::from_static(content_type),
     );
 
-    let body = tokio::fs::read(abs).await?;
+    let len = body.len() as u64;
     let put = upload_http
         .put(&session.upload_url)
         .headers(headers)
@@ -249,9 +404,12 @@ async fn upload_one(
     if !put.status().is_success() {
         let status = put.status();
         let text = put.text().await.unwrap_or_default();
-        let _ = hermes.abort_media_upload(&session.upload_id).await;
+        if hermes.abort_media_upload(&session.upload_id).await.is_ok() {
+            finish(&session.upload_id);
+        }
         return Err(anyhow::anyhow!("upload PUT failed: {status} {text}"));
     }
+    metrics::global().incr(metrics::UPLOAD_BYTES, &[("target", "hermes")], len);
 
     let etag = put
         .headers()
@@ -259,10 +417,51 @@ async fn upload_one(
         .and_then(|v| v.to_str().ok())
         .map(|s| s.trim_matches('"').to_string());
 
-    let complete = talaria_core::models::CompleteUploadRequest { etag, sha256: None };
+    let complete = talaria_core::models::CompleteUploadRequest {
+        etag,
+        sha256: Some(sha256.clone()),
+    };
     let done = hermes
         .complete_media_upload(&session.upload_id, Some(&complete))
         .await?;
+    finish(&session.upload_id);
+
+    // A copy that does not match is removed so the file uploads again next
+    // time instead of being listed as it is.
+    let mismatch = match done.media.sha256.as_deref() {
+        Some(recorded) if !recorded.eq_ignore_ascii_case(&sha256) => Some(format!(
+            "Hermes recorded sha256 {recorded}, uploaded {sha256}"
+        )),
+        _ => match verify_served(upload_http, &done.media.url, &sha256, len, rehash).await {
+            Ok(ServedCheck::Mismatch(why)) => Some(why),
+            Ok(check) => {
+                tracing::debug!(file = %rel, ?check, "served copy verified");
+                None
+            }
+            Err(err) => {
+                tracing::warn!(
+                    file = %rel,
+                    url = %done.media.url,
+                    error = %err,
+                    "served copy not verified"
+                );
+                None
+            }
+        },
+    };
+    if let Some(why) = mismatch {
+        if let Err(err) = hermes.delete_media(&done.media.media_id).await {
+            tracing::warn!(
+                media_id = %done.media.media_id,
+                error = %err,
+                "corrupted media not deleted"
+            );
+        }
+        return Err(anyhow::anyhow!(
+            "corrupted upload at {}: {why}",
+            done.media.url
+        ));
+    }
 
     Ok(UploadedAsset {
         url: done.media.url,
@@ -270,6 +469,63 @@ async fn upload_one(
     })
 }
 
+/// Aborts upload sessions an earlier run left open once they expire; see
+/// [`talaria_core::upload_journal`].
+fn abort_stale_uploads(
+    rt: &Runtime,
+    hermes: &talaria_core::client::HermesClient,
+    event_tx: &EventSender,
+) {
+    let result = UploadJournal::open_default()
+        .and_then(|journal| rt.block_on(abort_stale(hermes, &journal, chrono::Utc::now())));
+    let (severity, message) = match result {
+        Ok(aborted) if aborted.is_empty() => return,
+        Ok(aborted) => {
+            let failed = aborted.iter().filter(|a| a.error.is_some()).count();
+            if failed == 0 {
+                (
+                    Severity::Info,
+                    format!("Cleaned up {} interrupted upload(s).", aborted.len()),
+                )
+            } else {
+                (
+                    Severity::Warning,
+                    format!(
+                        "Cleaned up {} interrupted upload(s); {failed} could not be aborted and are retried next start.",
+                        aborted.len() - failed
+                    ),
+                )
+            }
+        }
+        Err(err) => (
+            Severity::Warning,
+            format!("Interrupted uploads not cleaned up: {err}"),
+        ),
+    };
+    let _ = event_tx.send(AppEvent::Activity(ActivityEntry {
+        at: Local::now(),
+        severity,
+        message,
+    }));
+}
+
+/// Sets each media's `rank` to its curated position so listings built from
+/// Hermes media keep the local order.
+async fn sync_media_ranks(
+    hermes: &talaria_core::client::HermesClient,
+    product: &storage::ProductManifest,
+) -> anyhow::Result<()> {
+    for (rank, media_id) in storage::curated_media_ids(product).iter().enumerate() {
+        let update = talaria_core::models::UpdateMediaRequest {
+            metadata: None,
+            purpose: None,
+            rank: Some(rank as i32),
+        };
+        hermes.update_media(media_id, &update).await?;
+    }
+    Ok(())
+}
+
 fn guess_content_type(path: &Path) -> &'static str {
     let ext = path
         .extension()